@@ -0,0 +1,2317 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Configuration module for COSMIC Monitor Applet
+//!
+//! This module defines the persistent configuration structure that is shared between
+//! the panel applet, the standalone widget, and the settings application. Configuration
+//! is stored using COSMIC's cosmic-config system and automatically syncs across all
+//! components.
+//!
+//! # Architecture
+//!
+//! The configuration is stored at `~/.config/cosmic/com.github.zoliviragh.CosmicMonitor/v1/`
+//! and uses the CosmicConfigEntry derive macro for automatic serialization and versioning.
+//! Each field is a file in that directory, holding its value as RON.
+//!
+//! When [`Config::VERSION`] is bumped, cosmic-config reads from a new, empty
+//! `v<N>` directory. [`migrate_stored`] runs before the config is loaded and
+//! fills it from the newest older version, passing the stored keys through
+//! the [`MIGRATIONS`] steps (renamed fields, renamed enum variants, ...):
+//!
+//! ```text
+//! v1/ ──copy keys──► MIGRATIONS[from 1] ──► MIGRATIONS[from 2] ──► v3/
+//! ```
+//!
+//! Keys without a step are carried over unchanged, so fields that didn't
+//! change keep their values. The old directory is left in place for
+//! downgrades.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use cosmic::cosmic_config::{Config as CosmicConfig, CosmicConfigEntry};
+//! use cosmic_monitor_core::config::{Config, CONFIG_ID};
+//!
+//! let handler = CosmicConfig::new(CONFIG_ID, Config::VERSION)?;
+//! let config = Config::get_entry(&handler).unwrap_or_default();
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fmt::Display;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use chrono::format::{Item, StrftimeItems};
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
+
+/// cosmic-config ID shared by the applet, widget and settings app.
+pub const CONFIG_ID: &str = "com.github.zoliviragh.CosmicMonitor";
+
+// ============================================================================
+// Widget Section Ordering & Layout
+// ============================================================================
+
+/// Represents the different sections that can be displayed in the widget.
+///
+/// Users can reorder these sections via the settings application to customize
+/// the widget layout. Each section corresponds to a distinct monitoring feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WidgetSection {
+    /// Digital clock and date
+    Clock,
+    /// CPU, Memory, GPU usage bars and percentages
+    Utilization,
+    /// CPU and GPU temperature displays (circular or text)
+    Temperatures,
+    /// Disk space usage for mounted filesystems
+    Storage,
+    /// Battery levels for laptops and Bluetooth devices (via Solaar)
+    Battery,
+    /// Current weather conditions from OpenWeatherMap
+    Weather,
+    /// Desktop notifications with grouping and dismiss controls
+    Notifications,
+    /// Now playing information from Cider (Apple Music client)
+    Media,
+    /// Volume and name of the default audio output
+    Audio,
+    /// Rows of output from user-defined commands
+    Custom,
+    /// Failed systemd units and pending package updates
+    SystemHealth,
+    /// Unread mail counts and latest RSS/Atom headlines
+    Feeds,
+    /// Prices and 24h changes of cryptocurrencies or stocks
+    Ticker,
+    /// Blocks drawn by Lua plugin scripts (`lua-plugins` feature)
+    Plugins,
+    /// Coffee cup that keeps the screen from blanking while switched on
+    Caffeine,
+    /// Health, scrub status and capacity of ZFS pools and btrfs filesystems
+    StoragePools,
+    /// Running Docker/Podman containers and the busiest one
+    Containers,
+    /// Running libvirt virtual machines with their vCPUs and memory
+    VirtualMachines,
+    /// Kernel statistics: processes, threads and context switches
+    System,
+    /// Network download/upload rates
+    Network,
+    /// Disk read/write activity
+    Disk,
+}
+
+impl WidgetSection {
+    /// Returns the human-readable label for this section.
+    ///
+    /// Used in the settings UI for the section reordering list.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WidgetSection::Clock => "Clock & Date",
+            WidgetSection::Utilization => "Utilization",
+            WidgetSection::Temperatures => "Temperatures",
+            WidgetSection::Storage => "Storage",
+            WidgetSection::Battery => "Battery",
+            WidgetSection::Weather => "Weather",
+            WidgetSection::Notifications => "Notifications",
+            WidgetSection::Media => "Media Player",
+            WidgetSection::Audio => "Audio Output",
+            WidgetSection::Custom => "Custom Commands",
+            WidgetSection::SystemHealth => "System Health",
+            WidgetSection::Feeds => "Mail & Feeds",
+            WidgetSection::Ticker => "Ticker",
+            WidgetSection::Plugins => "Plugins",
+            WidgetSection::Caffeine => "Caffeine",
+            WidgetSection::StoragePools => "Storage Pools",
+            WidgetSection::Containers => "Containers",
+            WidgetSection::VirtualMachines => "Virtual Machines",
+            WidgetSection::System => "System",
+            WidgetSection::Network => "Network",
+            WidgetSection::Disk => "Disk Activity",
+        }
+    }
+}
+
+/// A user-defined command shown as one row of the Custom Commands section.
+///
+/// The command runs with `sh -c` on a background thread every
+/// `interval_secs`; its trimmed output (first line) is shown next to the
+/// label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomCommand {
+    /// Row label (e.g., "Uptime")
+    pub label: String,
+    /// Shell command (e.g., "uptime -p")
+    pub command: String,
+    /// Seconds between runs (at least 1)
+    pub interval_secs: u32,
+    /// Row text with `{}` replaced by the output (e.g., "{} users");
+    /// empty shows the output as is
+    pub format: String,
+}
+
+impl Default for CustomCommand {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            command: String::new(),
+            interval_secs: 10,
+            format: String::from("{}"),
+        }
+    }
+}
+
+/// What a [`Feed`] is read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeedKind {
+    /// Unread count of an IMAP mailbox, checked with curl
+    Imap,
+    /// Latest headline of an RSS or Atom feed
+    #[default]
+    Rss,
+}
+
+impl FeedKind {
+    /// All kinds, in the order shown in the settings app.
+    pub const ALL: [FeedKind; 2] = [FeedKind::Imap, FeedKind::Rss];
+
+    /// Returns the human-readable label for this kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeedKind::Imap => "Mail (IMAP)",
+            FeedKind::Rss => "RSS / Atom",
+        }
+    }
+}
+
+/// A mailbox or news feed shown as one row of the Mail & Feeds section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Feed {
+    /// Where the row is read from
+    pub kind: FeedKind,
+    /// Row label (e.g., "Mail")
+    pub label: String,
+    /// Mailbox URL (`imaps://user@imap.example.com/INBOX`, password from
+    /// `~/.netrc`) or feed URL
+    pub url: String,
+}
+
+/// Where the ticker's quotes come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TickerProvider {
+    /// CoinGecko's free price API; symbols are coin ids ("bitcoin")
+    CoinGecko,
+    /// Any JSON API queried once per symbol (`ticker_endpoint`)
+    JsonEndpoint,
+}
+
+impl TickerProvider {
+    /// All providers, in the order shown in the settings app.
+    pub const ALL: [TickerProvider; 2] = [TickerProvider::CoinGecko, TickerProvider::JsonEndpoint];
+
+    /// Returns the human-readable label for this provider.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TickerProvider::CoinGecko => "CoinGecko (crypto)",
+            TickerProvider::JsonEndpoint => "JSON endpoint (stocks)",
+        }
+    }
+}
+
+/// Where the air quality badge next to the weather comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AirQualityProvider {
+    /// Open-Meteo's air quality API (US AQI, no API key needed)
+    OpenMeteo,
+    /// OpenWeatherMap's Air Pollution API (1-5 index, uses the weather API key)
+    OpenWeatherMap,
+}
+
+impl AirQualityProvider {
+    /// All providers, in the order shown in the settings app.
+    pub const ALL: [AirQualityProvider; 2] = [AirQualityProvider::OpenMeteo, AirQualityProvider::OpenWeatherMap];
+
+    /// Returns the human-readable label for this provider.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AirQualityProvider::OpenMeteo => "Open-Meteo (US AQI)",
+            AirQualityProvider::OpenWeatherMap => "OpenWeatherMap",
+        }
+    }
+}
+
+/// What the ambient status tint follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmbientSource {
+    /// No tint
+    Off,
+    /// Busiest of CPU, memory and GPU usage
+    Load,
+    /// Hottest of the CPU, GPU and hottest core temperatures
+    Temperature,
+}
+
+impl AmbientSource {
+    /// All sources, in the order shown in the settings app.
+    pub const ALL: [AmbientSource; 3] = [AmbientSource::Off, AmbientSource::Load, AmbientSource::Temperature];
+
+    /// Returns the human-readable label for this source.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AmbientSource::Off => "Off",
+            AmbientSource::Load => "System load",
+            AmbientSource::Temperature => "Hottest temperature",
+        }
+    }
+}
+
+/// What the ambient status tint colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmbientTarget {
+    /// The outline of the clock digits
+    ClockOutline,
+    /// A translucent wash behind the whole widget
+    Background,
+}
+
+impl AmbientTarget {
+    /// All targets, in the order shown in the settings app.
+    pub const ALL: [AmbientTarget; 2] = [AmbientTarget::ClockOutline, AmbientTarget::Background];
+
+    /// Returns the human-readable label for this target.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AmbientTarget::ClockOutline => "Clock outline",
+            AmbientTarget::Background => "Background tint",
+        }
+    }
+}
+
+/// Where the temperature graph goes relative to the gauges (or text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TempGraphMode {
+    /// Draw the graph under the gauges
+    Accompany,
+    /// Draw only the graph
+    Replace,
+}
+
+impl TempGraphMode {
+    /// All modes, in the order shown in the settings app.
+    pub const ALL: [TempGraphMode; 2] = [TempGraphMode::Accompany, TempGraphMode::Replace];
+
+    /// Returns the human-readable label for this mode.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TempGraphMode::Accompany => "Below the gauges",
+            TempGraphMode::Replace => "Instead of the gauges",
+        }
+    }
+}
+
+/// How much the applet, widget and settings app log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    /// Only failures
+    Error,
+    /// Failures and degraded features (e.g., a missing sensor)
+    Warn,
+    /// Startup, reconnects and config changes
+    Info,
+    /// Per-update details, for troubleshooting sensors
+    Debug,
+    /// Everything, including per-frame timings
+    Trace,
+}
+
+impl LogLevel {
+    /// All levels, in the order shown in the settings app.
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    /// Returns the human-readable label for this level.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "Errors",
+            LogLevel::Warn => "Warnings",
+            LogLevel::Info => "Info",
+            LogLevel::Debug => "Debug",
+            LogLevel::Trace => "Trace",
+        }
+    }
+}
+
+/// An extra time zone shown as a small row under the clock.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldClock {
+    /// IANA time zone name (e.g., "America/New_York")
+    pub zone: String,
+    /// Row label; empty uses the zone's city (e.g., "New York")
+    pub label: String,
+}
+
+/// A daily time range that dims the widget and/or hides sections
+/// (e.g., notifications and media at night).
+///
+/// Times are "HH:MM" in local time; an end before the start runs past
+/// midnight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Schedule {
+    /// Name shown in the settings (e.g., "Night")
+    pub label: String,
+    /// Start of the range (e.g., "22:00")
+    pub start: String,
+    /// End of the range, not included (e.g., "07:00")
+    pub end: String,
+    /// Widget opacity in percent while active (10-100)
+    pub opacity: u32,
+    /// Sections hidden while active
+    pub hidden_sections: Vec<WidgetSection>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            start: String::from("22:00"),
+            end: String::from("07:00"),
+            opacity: 100,
+            hidden_sections: Vec::new(),
+        }
+    }
+}
+
+/// Rounded translucent card drawn behind a section, for a dashboard look
+/// instead of text floating on the wallpaper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionCard {
+    /// Section drawn on the card
+    pub section: WidgetSection,
+    /// Card opacity in percent (10-100)
+    pub opacity: u32,
+}
+
+/// Where upcoming calendar events are read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarSource {
+    /// A local `.ics` file (`calendar_ics_path`)
+    IcsFile,
+    /// Calendars configured in Evolution / GNOME Online Accounts, via D-Bus
+    EvolutionDataServer,
+}
+
+impl CalendarSource {
+    /// All sources, in the order shown in the settings app.
+    pub const ALL: [CalendarSource; 2] = [CalendarSource::IcsFile, CalendarSource::EvolutionDataServer];
+
+    /// Returns the human-readable label for this source.
+    pub fn label(&self) -> &'static str {
+        match self {
+            CalendarSource::IcsFile => "ICS file",
+            CalendarSource::EvolutionDataServer => "Evolution Data Server",
+        }
+    }
+}
+
+/// What is written next to the memory bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoryLabel {
+    /// "63.0%", or used / total while `show_percentages` is off
+    Percentage,
+    /// Used / total ("12.3 / 32.0 GB")
+    Absolute,
+    /// Used / total and percentage ("12.3 / 32.0 GB · 38%")
+    Both,
+}
+
+impl MemoryLabel {
+    /// All labels, in the order shown in the settings app.
+    pub const ALL: [MemoryLabel; 3] = [MemoryLabel::Percentage, MemoryLabel::Absolute, MemoryLabel::Both];
+
+    /// Returns the human-readable label for this option.
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemoryLabel::Percentage => "Percentage",
+            MemoryLabel::Absolute => "Used / Total",
+            MemoryLabel::Both => "Both",
+        }
+    }
+}
+
+/// What scrolling the mouse wheel over a section does.
+///
+/// Any action can be bound to the clock, media, audio and notification
+/// sections; scrolling down moves forward (quieter, next zone, older
+/// notifications).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrollAction {
+    /// Scrolling is ignored
+    Nothing,
+    /// Change the volume of the current media player
+    MediaVolume,
+    /// Change the volume of the default audio output
+    SystemVolume,
+    /// Skip to the next or previous track
+    MediaTrack,
+    /// Show the large clock in the next or previous world clock zone
+    TimeZones,
+    /// Scroll through older notifications in each group
+    Notifications,
+}
+
+impl ScrollAction {
+    /// All actions, in the order shown in the settings app.
+    pub const ALL: [ScrollAction; 6] = [
+        ScrollAction::Nothing,
+        ScrollAction::MediaVolume,
+        ScrollAction::SystemVolume,
+        ScrollAction::MediaTrack,
+        ScrollAction::TimeZones,
+        ScrollAction::Notifications,
+    ];
+
+    /// Returns the human-readable label for this action.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScrollAction::Nothing => "Nothing",
+            ScrollAction::MediaVolume => "Media Volume",
+            ScrollAction::SystemVolume => "System Volume",
+            ScrollAction::MediaTrack => "Previous / Next Track",
+            ScrollAction::TimeZones => "Cycle Time Zones",
+            ScrollAction::Notifications => "Scroll Notifications",
+        }
+    }
+}
+
+/// How full or hot a metric is, which picks its bar or gauge color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Level {
+    /// Below the warning threshold (green by default)
+    Normal,
+    /// Between the warning and critical thresholds (yellow by default)
+    Warning,
+    /// At or above the critical threshold (red by default)
+    Critical,
+}
+
+impl Level {
+    /// All levels, from coolest to hottest.
+    pub const ALL: [Level; 3] = [Level::Normal, Level::Warning, Level::Critical];
+
+    /// Returns the human-readable label for this level.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Normal => "Normal",
+            Level::Warning => "Warning",
+            Level::Critical => "Critical",
+        }
+    }
+}
+
+/// Values at which a bar or gauge turns to the warning and critical colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Thresholds {
+    /// Warning from this value on (percent, or °C for temperatures)
+    pub warning: u32,
+    /// Critical from this value on
+    pub critical: u32,
+}
+
+impl Thresholds {
+    /// Thresholds at `warning` and `critical`.
+    pub const fn new(warning: u32, critical: u32) -> Self {
+        Self { warning, critical }
+    }
+
+    /// Level of `value` against these thresholds.
+    pub fn level(&self, value: f32) -> Level {
+        if value >= self.critical as f32 {
+            Level::Critical
+        } else if value >= self.warning as f32 {
+            Level::Warning
+        } else {
+            Level::Normal
+        }
+    }
+}
+
+/// Metrics with their own color thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThresholdMetric {
+    Cpu,
+    Memory,
+    Gpu,
+    Disk,
+    Temperature,
+}
+
+impl ThresholdMetric {
+    /// All metrics, in the order shown in the settings app.
+    pub const ALL: [ThresholdMetric; 5] = [
+        ThresholdMetric::Cpu,
+        ThresholdMetric::Memory,
+        ThresholdMetric::Gpu,
+        ThresholdMetric::Disk,
+        ThresholdMetric::Temperature,
+    ];
+
+    /// Returns the human-readable label for this metric.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThresholdMetric::Cpu => "CPU",
+            ThresholdMetric::Memory => "Memory",
+            ThresholdMetric::Gpu => "GPU",
+            ThresholdMetric::Disk => "Disk Space",
+            ThresholdMetric::Temperature => "Temperature (°C)",
+        }
+    }
+}
+
+/// Color thresholds of each metric.
+///
+/// Usage bars are in percent, temperatures in °C. The defaults match the
+/// fixed 50/80 steps the widget always used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricThresholds {
+    pub cpu: Thresholds,
+    pub memory: Thresholds,
+    pub gpu: Thresholds,
+    pub disk: Thresholds,
+    pub temperature: Thresholds,
+}
+
+impl Default for MetricThresholds {
+    fn default() -> Self {
+        Self {
+            cpu: Thresholds::new(50, 80),
+            memory: Thresholds::new(50, 80),
+            gpu: Thresholds::new(50, 80),
+            disk: Thresholds::new(50, 80),
+            temperature: Thresholds::new(50, 80),
+        }
+    }
+}
+
+impl MetricThresholds {
+    /// Thresholds of `metric`.
+    pub fn get(&self, metric: ThresholdMetric) -> Thresholds {
+        match metric {
+            ThresholdMetric::Cpu => self.cpu,
+            ThresholdMetric::Memory => self.memory,
+            ThresholdMetric::Gpu => self.gpu,
+            ThresholdMetric::Disk => self.disk,
+            ThresholdMetric::Temperature => self.temperature,
+        }
+    }
+
+    /// Mutable thresholds of `metric`.
+    pub fn get_mut(&mut self, metric: ThresholdMetric) -> &mut Thresholds {
+        match metric {
+            ThresholdMetric::Cpu => &mut self.cpu,
+            ThresholdMetric::Memory => &mut self.memory,
+            ThresholdMetric::Gpu => &mut self.gpu,
+            ThresholdMetric::Disk => &mut self.disk,
+            ThresholdMetric::Temperature => &mut self.temperature,
+        }
+    }
+}
+
+/// Bar and gauge colors per level, as `#RRGGBB`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BarColors {
+    pub normal: String,
+    pub warning: String,
+    pub critical: String,
+}
+
+impl Default for BarColors {
+    fn default() -> Self {
+        Self {
+            normal: String::from("#66E666"),
+            warning: String::from("#E6E666"),
+            critical: String::from("#E66666"),
+        }
+    }
+}
+
+impl BarColors {
+    /// Configured color of `level`.
+    pub fn get(&self, level: Level) -> &str {
+        match level {
+            Level::Normal => &self.normal,
+            Level::Warning => &self.warning,
+            Level::Critical => &self.critical,
+        }
+    }
+
+    /// Mutable color of `level`.
+    pub fn get_mut(&mut self, level: Level) -> &mut String {
+        match level {
+            Level::Normal => &mut self.normal,
+            Level::Warning => &mut self.warning,
+            Level::Critical => &mut self.critical,
+        }
+    }
+
+    /// Color of `level` as cairo RGB, falling back to the default color
+    /// when the configured one isn't valid.
+    pub fn rgb(&self, level: Level) -> (f64, f64, f64) {
+        parse_hex_color(self.get(level))
+            .or_else(|| parse_hex_color(BarColors::default().get(level)))
+            .unwrap_or((1.0, 1.0, 1.0))
+    }
+}
+
+/// Kind of text drawn on the widget, each with its own font.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextRole {
+    /// Large clock (and the date under it)
+    Clock,
+    /// Section headers ("Utilization", "Storage", ...)
+    Header,
+    /// Rows, values and labels
+    Body,
+    /// Secondary details, badges and captions
+    Small,
+}
+
+impl TextRole {
+    /// All roles, in the order shown in the settings app.
+    pub const ALL: [TextRole; 4] = [TextRole::Clock, TextRole::Header, TextRole::Body, TextRole::Small];
+
+    /// Returns the human-readable label for this role.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextRole::Clock => "Clock",
+            TextRole::Header => "Headers",
+            TextRole::Body => "Body",
+            TextRole::Small => "Small labels",
+        }
+    }
+
+    /// Size (points) the layout was designed for; other sizes scale every
+    /// text of the role by the same factor.
+    pub fn design_size(&self) -> u32 {
+        match self {
+            TextRole::Clock => 48,
+            TextRole::Header => 14,
+            TextRole::Body => 12,
+            TextRole::Small => 10,
+        }
+    }
+}
+
+/// Family and size of a text role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleFont {
+    /// Font family (e.g., "Ubuntu"); a family that isn't installed falls
+    /// back to the default
+    pub family: String,
+    /// Size in points
+    pub size: u32,
+}
+
+/// Font of each text role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    pub clock: RoleFont,
+    pub header: RoleFont,
+    pub body: RoleFont,
+    pub small: RoleFont,
+}
+
+/// Family of every text role unless configured otherwise.
+pub const DEFAULT_FONT_FAMILY: &str = "Ubuntu";
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        let font = |role: TextRole| RoleFont {
+            family: String::from(DEFAULT_FONT_FAMILY),
+            size: role.design_size(),
+        };
+        Self {
+            clock: font(TextRole::Clock),
+            header: font(TextRole::Header),
+            body: font(TextRole::Body),
+            small: font(TextRole::Small),
+        }
+    }
+}
+
+impl FontSettings {
+    /// Configured font of `role`.
+    pub fn get(&self, role: TextRole) -> &RoleFont {
+        match role {
+            TextRole::Clock => &self.clock,
+            TextRole::Header => &self.header,
+            TextRole::Body => &self.body,
+            TextRole::Small => &self.small,
+        }
+    }
+
+    /// Mutable font of `role`.
+    pub fn get_mut(&mut self, role: TextRole) -> &mut RoleFont {
+        match role {
+            TextRole::Clock => &mut self.clock,
+            TextRole::Header => &mut self.header,
+            TextRole::Body => &mut self.body,
+            TextRole::Small => &mut self.small,
+        }
+    }
+}
+
+/// Parse `#RRGGBB` (the `#` is optional) into cairo RGB components.
+pub fn parse_hex_color(hex: &str) -> Option<(f64, f64, f64)> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok().map(|c| c as f64 / 255.0);
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// How sections are arranged on the widget surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutMode {
+    /// Sections stacked top to bottom (classic desktop widget)
+    Vertical,
+    /// Sections side by side in a single row, for a screen edge
+    Horizontal,
+    /// Single row of short metrics (CPU 12%, 45°C, ...) like a status bar
+    Compact,
+}
+
+impl LayoutMode {
+    /// All modes, in the order shown in the settings app.
+    pub const ALL: [LayoutMode; 3] = [LayoutMode::Vertical, LayoutMode::Horizontal, LayoutMode::Compact];
+
+    /// Returns the human-readable label for this mode.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutMode::Vertical => "Vertical",
+            LayoutMode::Horizontal => "Horizontal",
+            LayoutMode::Compact => "Compact",
+        }
+    }
+}
+
+/// Color of the text drawn straight onto the wallpaper.
+///
+/// Section text has no background of its own, so it needs to contrast with
+/// the wallpaper behind the widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextContrast {
+    /// White text with a black outline, for dark wallpapers
+    Light,
+    /// Dark text with a white outline, for light wallpapers
+    Dark,
+    /// Light text in COSMIC's dark mode, dark text in its light mode
+    Auto,
+}
+
+impl TextContrast {
+    /// All modes, in the order shown in the settings app.
+    pub const ALL: [TextContrast; 3] = [TextContrast::Light, TextContrast::Dark, TextContrast::Auto];
+
+    /// Returns the human-readable label for this mode.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextContrast::Light => "Light text",
+            TextContrast::Dark => "Dark text",
+            TextContrast::Auto => "Follow dark mode",
+        }
+    }
+
+    /// Whether to draw dark text, given COSMIC's current dark mode.
+    pub fn dark_text(&self, system_dark: bool) -> bool {
+        match self {
+            TextContrast::Light => false,
+            TextContrast::Dark => true,
+            TextContrast::Auto => !system_dark,
+        }
+    }
+}
+
+/// Screen corner the widget is anchored to.
+///
+/// `widget_x`/`widget_y` are measured from the edges of this corner, so a
+/// widget in the bottom-right stays there when the screen resolution changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WidgetAnchor {
+    /// Offset from the top and left edges
+    TopLeft,
+    /// Offset from the top and right edges
+    TopRight,
+    /// Offset from the bottom and left edges
+    BottomLeft,
+    /// Offset from the bottom and right edges
+    BottomRight,
+}
+
+impl WidgetAnchor {
+    /// All anchors in reading order.
+    pub const ALL: [WidgetAnchor; 4] = [
+        WidgetAnchor::TopLeft,
+        WidgetAnchor::TopRight,
+        WidgetAnchor::BottomLeft,
+        WidgetAnchor::BottomRight,
+    ];
+
+    /// Anchor for the corner of a screen quadrant.
+    pub fn from_sides(right: bool, bottom: bool) -> Self {
+        match (right, bottom) {
+            (false, false) => WidgetAnchor::TopLeft,
+            (true, false) => WidgetAnchor::TopRight,
+            (false, true) => WidgetAnchor::BottomLeft,
+            (true, true) => WidgetAnchor::BottomRight,
+        }
+    }
+
+    /// Whether `widget_x` is measured from the right edge.
+    pub fn is_right(&self) -> bool {
+        matches!(self, WidgetAnchor::TopRight | WidgetAnchor::BottomRight)
+    }
+
+    /// Whether `widget_y` is measured from the bottom edge.
+    pub fn is_bottom(&self) -> bool {
+        matches!(self, WidgetAnchor::BottomLeft | WidgetAnchor::BottomRight)
+    }
+
+    /// Returns the human-readable label for this anchor.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WidgetAnchor::TopLeft => "Top left",
+            WidgetAnchor::TopRight => "Top right",
+            WidgetAnchor::BottomLeft => "Bottom left",
+            WidgetAnchor::BottomRight => "Bottom right",
+        }
+    }
+}
+
+// ============================================================================
+// Main Configuration Structure
+// ============================================================================
+
+/// Main configuration structure for the COSMIC Monitor Applet.
+///
+/// This struct holds all user-configurable options and is automatically
+/// persisted to disk via cosmic-config. Changes are detected by the widget
+/// through periodic polling and applied in real-time.
+///
+/// # Sections
+///
+/// Configuration is organized into logical groups:
+/// - **Monitoring toggles**: Enable/disable specific metrics (CPU, Memory, etc.)
+/// - **Display options**: Visual preferences (percentages, 24-hour time, etc.)
+/// - **Weather settings**: API key and location for weather data
+/// - **Position settings**: Widget placement on screen
+/// - **Advanced options**: Logging, API tokens, etc.
+///
+/// The serde derives are used for profile files (see the settings app's
+/// `profiles` module); missing fields take their default values.
+#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+// Add a step to `MIGRATIONS` when bumping this
+#[version = 1]
+pub struct Config {
+    // ========================================================================
+    // Utilization Section
+    // ========================================================================
+    
+    /// Show CPU usage bar and percentage in the Utilization section.
+    /// Uses sysinfo crate to read from /proc/stat.
+    pub show_cpu: bool,
+    
+    /// Show the average clock speed of each CPU package under the CPU bar
+    /// (cpufreq `scaling_cur_freq`).
+    pub show_cpu_frequency: bool,
+    
+    /// Show the active cpufreq governor under the CPU bar.
+    pub show_cpu_governor: bool,
+    
+    /// Clicking the governor switches to the next available one. Needs
+    /// root, so it asks for authentication through pkexec.
+    pub cpu_governor_click: bool,
+    
+    /// Show the CPU model, core/thread count and GPU name under the
+    /// Utilization header (read once at startup, see `widget::hardware`).
+    pub show_hardware_info: bool,
+    
+    /// Show memory (RAM) usage bar and percentage in the Utilization section.
+    /// Displays used/total memory from /proc/meminfo.
+    pub show_memory: bool,
+    
+    /// Show GPU usage bar and percentage in the Utilization section.
+    /// Supports NVIDIA (nvidia-smi), AMD, and Intel GPUs.
+    pub show_gpu: bool,
+    
+    /// List the processes using the GPU the most under the GPU bar.
+    /// Off by default: reading them costs more than the usage itself
+    /// (nvidia-smi pmon, or scanning /proc fdinfo on AMD/Intel).
+    pub show_gpu_processes: bool,
+    
+    /// Show the GPU fan speed and core/memory clocks in a row under the
+    /// GPU bar (nvidia-smi, or amdgpu/i915 sysfs).
+    pub show_gpu_details: bool,
+    
+    /// Show CPU, memory and I/O pressure (PSI `some avg10`) in a row at
+    /// the bottom of the Utilization section.
+    pub show_psi: bool,
+    
+    /// Show network transfer rates (upload/download speeds).
+    pub show_network: bool,
+    
+    /// Show network rates in bits per second (Mbit/s) instead of bytes (MB/s).
+    pub network_rate_bits: bool,
+    
+    /// Draw a download/upload graph under the network rates.
+    pub show_network_graph: bool,
+    
+    /// Seconds of history shown in the network graph.
+    pub network_graph_seconds: u32,
+    
+    /// Show disk I/O activity.
+    /// Currently shows placeholder rates.
+    pub show_disk: bool,
+
+    // ========================================================================
+    // Temperature Section
+    // ========================================================================
+    
+    /// Show CPU temperature in the Temperatures section.
+    /// Reads from hwmon sensors via sysinfo.
+    pub show_cpu_temp: bool,
+    
+    /// Show GPU temperature in the Temperatures section.
+    /// Uses nvidia-smi for NVIDIA, hwmon for AMD/Intel.
+    pub show_gpu_temp: bool,
+    
+    /// Use circular gauge display for temperatures instead of text.
+    /// When true, shows a visual arc gauge; when false, shows "XX°C" text.
+    pub use_circular_temp_display: bool,
+    
+    /// Show the hottest core's temperature under the temperatures.
+    pub show_hottest_core: bool,
+    
+    /// Show a "THROTTLING" badge while the CPU is thermally throttled
+    /// (Intel throttle counters, CPU hwmon alarms).
+    pub show_throttling: bool,
+    
+    /// Draw a graph of the CPU/GPU temperatures with min/avg/max in the
+    /// Temperatures section (handy for laptop thermals).
+    pub show_temp_graph: bool,
+    
+    /// Minutes of history shown in the temperature graph.
+    pub temp_graph_minutes: u32,
+    
+    /// Whether the graph is drawn under the gauges or instead of them.
+    pub temp_graph_mode: TempGraphMode,
+
+    // ========================================================================
+    // Storage Section
+    // ========================================================================
+    
+    /// Show disk space usage for mounted filesystems.
+    /// Displays each mounted disk with used/total space and a progress bar.
+    pub show_storage: bool,
+    
+    /// Warn (red bar and a badge) when a filesystem has less than this
+    /// percentage of its space free (0 = off).
+    pub storage_min_free_percent: u32,
+    
+    /// Warn when a filesystem has this percentage of its inodes in use
+    /// (0 = off).
+    pub storage_max_inode_percent: u32,
+    
+    /// Send a desktop notification when a filesystem crosses one of the
+    /// warning limits.
+    pub storage_alerts: bool,
+    
+    /// Show the SMART health verdict and temperature of each drive under
+    /// the filesystems (verdicts need smartctl with raw device access).
+    pub show_smart: bool,
+    
+    /// Minutes between drive health checks.
+    pub smart_refresh_minutes: u32,
+    
+    /// Show the Storage Pools section: health, scrub status and capacity
+    /// of ZFS pools and btrfs filesystems.
+    pub show_storage_pools: bool,
+    
+    /// Minutes between storage pool checks.
+    pub storage_pools_refresh_minutes: u32,
+
+    // ========================================================================
+    // Battery Section
+    // ========================================================================
+    
+    /// Show battery section for laptop and Bluetooth device batteries.
+    /// Requires enable_solaar_integration for Bluetooth devices.
+    pub show_battery: bool,
+    
+    /// Enable Solaar integration for Logitech device battery monitoring.
+    /// Solaar must be installed and running. Communicates via D-Bus.
+    pub enable_solaar_integration: bool,
+    
+    /// Show the active power profile (power-profiles-daemon) in the Battery
+    /// header; clicking it switches to the next profile.
+    pub show_power_profile: bool,
+
+    // ========================================================================
+    // Weather Section
+    // ========================================================================
+    
+    /// Show weather information from OpenWeatherMap.
+    /// Requires a valid API key and location to be configured.
+    pub show_weather: bool,
+    
+    /// OpenWeatherMap API key for fetching weather data.
+    /// Get a free key at https://openweathermap.org/api
+    pub weather_api_key: String,
+    
+    /// Location for weather data (city name, "City,Country" format, or coordinates).
+    /// Examples: "London,UK", "New York,US", "48.8566,2.3522"
+    pub weather_location: String,
+    
+    /// Use the detailed weather layout instead of the compact one.
+    /// Adds feels-like, min/max, humidity and wind rows below the main line.
+    pub weather_detailed: bool,
+    
+    /// Add a row with today's sunrise and sunset under the weather.
+    pub weather_sun_times: bool,
+    
+    /// Show the moon phase at the end of the sunrise/sunset row while the
+    /// sun is down.
+    pub weather_moon_phase: bool,
+    
+    /// Show an air quality badge next to the weather, for the weather
+    /// station's coordinates.
+    pub show_air_quality: bool,
+    
+    /// Where the air quality index comes from.
+    pub air_quality_provider: AirQualityProvider,
+
+    // ========================================================================
+    // Notifications Section
+    // ========================================================================
+    
+    /// Show desktop notifications in the widget.
+    /// Monitors D-Bus org.freedesktop.Notifications for new notifications.
+    pub show_notifications: bool,
+    
+    /// Maximum number of notifications to keep in the display.
+    /// Oldest notifications are removed when this limit is exceeded.
+    pub max_notifications: usize,
+    
+    /// What scrolling over the notifications does.
+    pub notifications_scroll: ScrollAction,
+    
+    /// Show notification bodies only while the pointer is on a notification
+    /// (or after clicking it open), keeping the list compact.
+    pub compact_notifications: bool,
+    
+    /// Keep captured notifications in a history file under
+    /// $XDG_STATE_HOME/cosmic-monitor and restore them at startup.
+    pub notification_history: bool,
+    
+    /// Most notifications kept in the history.
+    pub notification_history_count: usize,
+    
+    /// Days notifications are kept in the history (0 = no age limit).
+    pub notification_history_days: u32,
+
+    // ========================================================================
+    // Media Section
+    // ========================================================================
+    
+    /// Show now playing information from Cider (Apple Music client).
+    /// Requires Cider to be running with its REST API enabled.
+    pub show_media: bool,
+    
+    /// Cider REST API authentication token.
+    /// Leave empty if Cider's "Authorized Requests Only" setting is disabled.
+    /// Find this in Cider Settings → Connectivity → Remote Token.
+    pub cider_api_token: String,
+    
+    /// What scrolling over the media section does.
+    pub media_scroll: ScrollAction,
+    
+    /// Scroll track titles too long for the media panel back and forth
+    /// instead of cutting them off.
+    pub media_marquee: bool,
+    
+    /// Speed of the title marquee (pixels per second).
+    pub marquee_speed: u32,
+    
+    /// Wait at each end of the title marquee (milliseconds).
+    pub marquee_pause_ms: u32,
+    
+    /// Show the current line of the track's synced lyrics under the track
+    /// info (fetched from lrclib.net).
+    pub show_lyrics: bool,
+
+    // ========================================================================
+    // Audio Section
+    // ========================================================================
+    
+    /// Show volume, mute state and name of the default audio output
+    /// (read with pactl; works with PulseAudio and PipeWire).
+    /// Clicking the section toggles mute.
+    pub show_audio: bool,
+    
+    /// What scrolling over the audio section does.
+    pub audio_scroll: ScrollAction,
+
+    // ========================================================================
+    // System Health Section
+    // ========================================================================
+    
+    /// Show failed systemd units (system and user) as a warning badge.
+    pub show_system_health: bool,
+    
+    /// Shell command listing pending updates, one per line
+    /// (e.g., `checkupdates`). Leave empty to skip the update count.
+    pub health_update_command: String,
+    
+    /// Minutes between system health checks.
+    pub health_refresh_minutes: u32,
+
+    // ========================================================================
+    // System Section
+    // ========================================================================
+    
+    /// Show the kernel statistics row: process and thread counts and
+    /// context switches per second.
+    pub show_kernel_stats: bool,
+
+    // ========================================================================
+    // Containers Section
+    // ========================================================================
+    
+    /// Show the number of running Docker and Podman containers.
+    pub show_containers: bool,
+    
+    /// Also show the container using the most CPU (runs `stats`, which
+    /// takes a moment on every refresh).
+    pub containers_show_busiest: bool,
+    
+    /// Minutes between container checks.
+    pub containers_refresh_minutes: u32,
+
+    // ========================================================================
+    // Virtual Machines Section
+    // ========================================================================
+    
+    /// Show running libvirt domains with their vCPU and memory allocation.
+    pub show_vms: bool,
+    
+    /// libvirt connection URI (`qemu:///system` for virt-manager's VMs,
+    /// `qemu:///session` for GNOME Boxes).
+    pub vms_uri: String,
+    
+    /// Minutes between virtual machine checks.
+    pub vms_refresh_minutes: u32,
+
+    // ========================================================================
+    // Mail & Feeds Section
+    // ========================================================================
+    
+    /// Show unread mail counts and the latest headline of news feeds.
+    pub show_feeds: bool,
+    
+    /// Mailboxes and feeds, one row each.
+    pub feeds: Vec<Feed>,
+    
+    /// Minutes between feed checks.
+    pub feeds_refresh_minutes: u32,
+
+    // ========================================================================
+    // Ticker Section
+    // ========================================================================
+    
+    /// Show prices and 24h changes of cryptocurrencies or stocks.
+    pub show_ticker: bool,
+    
+    /// Where quotes come from.
+    pub ticker_provider: TickerProvider,
+    
+    /// Comma-separated symbols (CoinGecko coin ids like "bitcoin", or
+    /// whatever the JSON endpoint expects, like "AAPL").
+    pub ticker_symbols: String,
+    
+    /// Most symbols shown; the rest of the list is ignored.
+    pub ticker_max_symbols: u32,
+    
+    /// Currency prices are quoted in ("usd"); CoinGecko converts to it,
+    /// for the JSON endpoint it is only shown after the price.
+    pub ticker_currency: String,
+    
+    /// URL of the JSON endpoint with `{symbol}` in place of the symbol
+    /// (e.g., `https://finnhub.io/api/v1/quote?symbol={symbol}&token=…`).
+    pub ticker_endpoint: String,
+    
+    /// JSON pointer to the price in the endpoint's reply (e.g., `/c`).
+    pub ticker_price_path: String,
+    
+    /// JSON pointer to the 24h change in percent (e.g., `/dp`); empty
+    /// leaves the change out.
+    pub ticker_change_path: String,
+    
+    /// Minutes between quote refreshes.
+    pub ticker_refresh_minutes: u32,
+
+    // ========================================================================
+    // Custom Commands & Plugins Sections
+    // ========================================================================
+    
+    /// Show the Custom Commands section.
+    pub show_custom_commands: bool,
+    
+    /// User-defined commands, one row each.
+    pub custom_commands: Vec<CustomCommand>,
+    
+    /// Show the Plugins section (scripts in ~/.config/cosmic-monitor/plugins,
+    /// only run when built with the `lua-plugins` feature).
+    pub show_plugins: bool,
+
+    // ========================================================================
+    // Caffeine Section
+    // ========================================================================
+    
+    /// Show the caffeine toggle. Clicking it keeps the screen from blanking
+    /// (an idle inhibitor on the widget surface) until clicked again.
+    pub show_caffeine: bool,
+    
+    /// Switch caffeine on when the widget starts.
+    pub caffeine_on_start: bool,
+
+    // ========================================================================
+    // Machine Identity
+    // ========================================================================
+    
+    /// Show the hostname at the top of the widget.
+    pub show_hostname: bool,
+    
+    /// Show the logged-in user name at the top of the widget.
+    pub show_username: bool,
+    
+    /// Shown instead of the hostname and user name when not empty
+    /// (e.g., to keep the hostname off a stream).
+    pub identity_label: String,
+
+    // ========================================================================
+    // Clock & Date Display
+    // ========================================================================
+    
+    /// Show digital clock at the top of the widget.
+    pub show_clock: bool,
+    
+    /// Show current date below the clock.
+    pub show_date: bool,
+    
+    /// Use 24-hour time format (14:30) instead of 12-hour (2:30 PM).
+    /// Defaults to the convention of the user's locale (`LC_TIME`); a
+    /// stored value always takes precedence.
+    pub use_24hour_time: bool,
+    
+    /// strftime pattern of the large clock (e.g., "%H:%M:%S").
+    /// Empty draws hours and minutes with small seconds, following
+    /// `use_24hour_time`.
+    pub clock_format: String,
+    
+    /// strftime pattern of the date (e.g., "%Y-%m-%d, week %V").
+    /// Empty uses [`DEFAULT_DATE_FORMAT`].
+    pub date_format: String,
+    
+    /// Additional time zones shown as smaller rows under the clock.
+    pub world_clocks: Vec<WorldClock>,
+    
+    /// What scrolling over the clock does (by default, show the large
+    /// clock in the world clock zones in turn).
+    pub clock_scroll: ScrollAction,
+    
+    /// Show upcoming calendar events under the date.
+    pub show_calendar: bool,
+    
+    /// Where events are read from: an .ics file or Evolution Data Server.
+    pub calendar_source: CalendarSource,
+    
+    /// Path of the .ics file (`~/` is expanded).
+    pub calendar_ics_path: String,
+    
+    /// Number of upcoming events shown.
+    pub calendar_max_events: u32,
+    
+    /// Minutes between calendar refreshes.
+    pub calendar_refresh_minutes: u32,
+
+    // ========================================================================
+    // Display Preferences
+    // ========================================================================
+    
+    /// Show percentage values on utilization bars.
+    /// When true, displays "XX%" next to each bar.
+    pub show_percentages: bool,
+    
+    /// Draw CPU/GPU temperatures as small badges at the end of the CPU/GPU
+    /// usage bars instead of in their own Temperatures section.
+    pub temps_on_usage_bars: bool,
+    
+    /// Value next to the memory bar: percentage, used / total, or both.
+    pub memory_label: MemoryLabel,
+    
+    /// Show a tooltip with details (per-core usage, per-interface rates,
+    /// sensor names) when the pointer rests on a metric.
+    pub show_tooltips: bool,
+    
+    /// Where each bar and temperature gauge turns yellow and red.
+    pub thresholds: MetricThresholds,
+    
+    /// Colors of bars and gauges below, between, and above the thresholds.
+    pub bar_colors: BarColors,
+    
+    /// Light or dark text, or following COSMIC's dark mode so the widget
+    /// stays readable on light wallpapers.
+    pub text_contrast: TextContrast,
+    
+    /// Font family and size of the clock, headers, body and small labels.
+    pub fonts: FontSettings,
+    
+    /// Sections drawn on a card of their own, each with its opacity. The
+    /// corner radius follows the COSMIC theme.
+    pub section_cards: Vec<SectionCard>,
+    
+    /// Tint the widget with the system's state (see `widget::ambient`).
+    pub ambient_source: AmbientSource,
+    
+    /// Whether the ambient tint colors the clock outline or the background.
+    pub ambient_target: AmbientTarget,
+    
+    /// How often to update system statistics, in milliseconds.
+    /// Lower values = more responsive but higher CPU usage.
+    /// Recommended range: 500-2000ms.
+    pub update_interval_ms: u64,
+    
+    /// Ease bars, temperature arcs, and the widget's opacity between
+    /// samples instead of jumping once per update.
+    pub enable_animations: bool,
+    
+    /// Arrangement of sections: vertical stack, horizontal row, or a
+    /// compact single-row metric strip.
+    pub layout_mode: LayoutMode,
+    
+    /// Number of columns the vertical layout splits its sections into
+    /// (1 or 2), balanced by height while keeping the section order.
+    pub columns: u32,
+
+    // ========================================================================
+    // Widget Position & Behavior
+    // ========================================================================
+    
+    /// Horizontal offset (pixels from the anchored left or right edge).
+    /// Can be adjusted by dragging when widget_movable is true.
+    pub widget_x: i32,
+    
+    /// Vertical offset (pixels from the anchored top or bottom edge).
+    /// Can be adjusted by dragging when widget_movable is true.
+    pub widget_y: i32,
+    
+    /// Screen corner `widget_x`/`widget_y` are measured from.
+    pub widget_anchor: WidgetAnchor,
+    
+    /// Output (connector name, e.g. "DP-1") to show the widget on.
+    /// Empty lets the compositor choose.
+    pub widget_output: String,
+    
+    /// Allow the widget to be repositioned by dragging.
+    /// Automatically enabled when the settings window is open.
+    pub widget_movable: bool,
+    
+    /// Let pointer events pass through the widget to windows underneath.
+    /// Ignored while widget_movable is true so the widget can still be dragged.
+    pub click_through: bool,
+    
+    /// Let the widget take keyboard focus when clicked, so it can be moved
+    /// with the arrow keys: Enter starts move mode, Escape ends it.
+    pub keyboard_positioning: bool,
+    
+    /// While dragging, pull the widget onto the screen edges and center
+    /// lines when it comes close to them.
+    pub snap_to_edges: bool,
+    
+    /// Grid (in pixels) the widget's top-left corner is rounded to while
+    /// dragging. 0 turns it off; edges and center lines win over it.
+    pub snap_grid: u32,
+    
+    /// Time ranges that dim the widget or hide sections.
+    pub schedules: Vec<Schedule>,
+    
+    /// Hide the widget while a fullscreen window is on its output
+    /// (games, videos). Needs the wlr foreign toplevel protocol.
+    pub auto_hide_fullscreen: bool,
+    
+    /// Also auto-hide while a maximized window is on the widget's output.
+    pub auto_hide_maximized: bool,
+    
+    /// Order of sections in the widget from top to bottom.
+    /// Users can reorder via the settings application.
+    pub section_order: Vec<WidgetSection>,
+    
+    /// Automatically start the widget when the panel applet loads.
+    /// If false, the widget must be manually shown via the applet menu.
+    pub widget_autostart: bool,
+    
+    /// Show CPU, RAM and temperature next to the panel applet's icon
+    /// (horizontal panels only).
+    pub show_panel_readout: bool,
+
+    // ========================================================================
+    // Advanced Settings
+    // ========================================================================
+    
+    /// Also write logs to rotating files under $XDG_STATE_HOME/cosmic-monitor
+    /// (one per binary). Useful for troubleshooting sensor issues.
+    /// Disabled by default; logs always go to stderr.
+    pub enable_logging: bool,
+    
+    /// How much to log (`--verbose` raises it to Debug, `RUST_LOG` overrides it).
+    pub log_level: LogLevel,
+    
+    /// Draw frame time, draw duration, buffer size, sampler latency and
+    /// memory use in the widget's bottom-left corner. Also toggled at
+    /// runtime with `cosmic-monitor-widget --debug-overlay`.
+    pub show_debug_overlay: bool,
+    
+    /// Publish the latest metrics to $XDG_RUNTIME_DIR/cosmic-monitor-widget-stats.json
+    /// every update cycle, for scripts, status bars, and overlays.
+    pub publish_stats: bool,
+    
+    /// Write every rendered frame to $XDG_RUNTIME_DIR/cosmic-monitor-widget-frame.png
+    /// so it can be added to OBS as an Image source overlay.
+    pub export_frames: bool,
+    
+    /// Append sampled metrics to daily CSV files under
+    /// ~/.local/share/cosmic-monitor-applet/history for long-term analysis.
+    pub record_metrics: bool,
+    
+    /// Seconds between recorded history rows.
+    pub record_interval_secs: u64,
+    
+    /// Days of history files to keep (0 = keep forever).
+    pub record_retention_days: u32,
+    
+    /// Run background collectors at the lowest nice level and I/O priority
+    /// so monitoring doesn't compete with the workloads being measured.
+    /// Applied at startup.
+    pub low_priority_collectors: bool,
+    
+    /// Use the idle scheduling classes (`SCHED_IDLE`, idle I/O) for low
+    /// priority collectors, so they only run while a CPU is otherwise idle.
+    /// Under full load they may get no time at all. Applied at startup.
+    pub idle_priority_collectors: bool,
+    
+    /// Pin background collectors to efficiency cores on hybrid CPUs.
+    /// Applied at startup.
+    pub pin_collectors_to_efficiency_cores: bool,
+    
+    /// Check GitHub once a day for a newer release and show a badge in the
+    /// widget when one is available.
+    pub check_for_updates: bool,
+}
+
+// ============================================================================
+// Default Configuration
+// ============================================================================
+
+impl Default for Config {
+    /// Returns the default configuration for new installations.
+    ///
+    /// Defaults are chosen to provide a useful out-of-box experience:
+    /// - Basic system monitoring (CPU, Memory, Storage) enabled
+    /// - Advanced features (GPU, Weather, Media) disabled until configured
+    /// - Widget auto-starts at position (50, 50)
+    /// - 1-second update interval for good balance of responsiveness and efficiency
+    fn default() -> Self {
+        Self {
+            // Utilization: Show basic system stats by default
+            show_cpu: true,
+            show_cpu_frequency: false,
+            show_cpu_governor: false,
+            cpu_governor_click: false,
+            show_hardware_info: false,
+            show_memory: true,
+            show_gpu: false,        // Requires GPU, not always present
+            show_gpu_processes: false,
+            show_gpu_details: false,
+            show_psi: false,
+            show_network: false,
+            network_rate_bits: false,
+            show_network_graph: false,
+            network_graph_seconds: 120,
+            show_disk: false,       // Placeholder rates only
+            
+            // Temperatures: Disabled by default (not all systems have sensors)
+            show_cpu_temp: false,
+            show_gpu_temp: false,
+            show_hottest_core: false,
+            show_throttling: false,
+            show_temp_graph: false,
+            temp_graph_minutes: 10,
+            temp_graph_mode: TempGraphMode::Accompany,
+            use_circular_temp_display: true,
+            
+            // Storage: Show disk usage by default
+            show_storage: true,
+            storage_min_free_percent: 10,
+            storage_max_inode_percent: 95,
+            storage_alerts: false,
+            show_smart: false,
+            smart_refresh_minutes: 30,
+            show_storage_pools: false,
+            storage_pools_refresh_minutes: 5,
+            
+            // Battery: Disabled (laptop/Solaar specific)
+            show_battery: false,
+            enable_solaar_integration: false,
+            show_power_profile: false,
+            
+            // Weather: Disabled (requires API key)
+            show_weather: false,
+            weather_api_key: String::new(),
+            weather_location: String::from("London,UK"),
+            weather_detailed: false,
+            weather_sun_times: false,
+            weather_moon_phase: false,
+            show_air_quality: false,
+            air_quality_provider: AirQualityProvider::OpenMeteo,
+            
+            // Notifications: Disabled by default
+            show_notifications: false,
+            max_notifications: 5,
+            notifications_scroll: ScrollAction::Notifications,
+            compact_notifications: true,
+            notification_history: false,
+            notification_history_count: 50,
+            notification_history_days: 7,
+            
+            // Media: Disabled (requires Cider)
+            show_media: false,
+            cider_api_token: String::new(),
+            media_scroll: ScrollAction::MediaVolume,
+            media_marquee: true,
+            marquee_speed: 30,
+            marquee_pause_ms: 1500,
+            show_lyrics: false,
+            
+            // Audio: Disabled by default
+            show_audio: false,
+            audio_scroll: ScrollAction::SystemVolume,
+            
+            // System health: Disabled, no update command, checked every 30 min
+            show_system_health: false,
+            health_update_command: String::new(),
+            health_refresh_minutes: 30,
+            
+            // System: Disabled
+            show_kernel_stats: false,
+            
+            // Containers: Disabled, checked every 2 min
+            show_containers: false,
+            containers_show_busiest: false,
+            containers_refresh_minutes: 2,
+            
+            // Virtual machines: Disabled, system libvirt, checked every 2 min
+            show_vms: false,
+            vms_uri: String::from("qemu:///system"),
+            vms_refresh_minutes: 2,
+            
+            // Mail & feeds: Disabled, none defined, checked every 15 min
+            show_feeds: false,
+            feeds: Vec::new(),
+            feeds_refresh_minutes: 15,
+            
+            // Ticker: Disabled, Bitcoin and Ethereum in USD from CoinGecko,
+            // Finnhub's reply layout for the JSON endpoint, every 5 min
+            show_ticker: false,
+            ticker_provider: TickerProvider::CoinGecko,
+            ticker_symbols: String::from("bitcoin, ethereum"),
+            ticker_max_symbols: 5,
+            ticker_currency: String::from("usd"),
+            ticker_endpoint: String::new(),
+            ticker_price_path: String::from("/c"),
+            ticker_change_path: String::from("/dp"),
+            ticker_refresh_minutes: 5,
+            
+            // Custom commands: Disabled, none defined
+            show_custom_commands: false,
+            custom_commands: Vec::new(),
+            
+            // Plugins: Disabled until the user opts in to running scripts
+            show_plugins: false,
+            
+            // Caffeine: Disabled, and off when shown until clicked
+            show_caffeine: false,
+            caffeine_on_start: false,
+            
+            // Identity: Hidden by default
+            show_hostname: false,
+            show_username: false,
+            identity_label: String::new(),
+            
+            // Clock: Show by default, time format from the user's locale
+            show_clock: true,
+            show_date: true,
+            use_24hour_time: locale_uses_24hour_time(),
+            clock_format: String::new(),
+            date_format: String::new(),
+            world_clocks: Vec::new(),
+            clock_scroll: ScrollAction::TimeZones,
+            
+            // Calendar: Disabled until a source is set up
+            show_calendar: false,
+            calendar_source: CalendarSource::IcsFile,
+            calendar_ics_path: String::new(),
+            calendar_max_events: 3,
+            calendar_refresh_minutes: 15,
+            
+            // Display: Show percentages, update every second, animate transitions
+            show_percentages: true,
+            temps_on_usage_bars: false,
+            memory_label: MemoryLabel::Percentage,
+            show_tooltips: true,
+            thresholds: MetricThresholds::default(),
+            bar_colors: BarColors::default(),
+            text_contrast: TextContrast::Light,
+            fonts: FontSettings::default(),
+            section_cards: Vec::new(),
+            ambient_source: AmbientSource::Off,
+            ambient_target: AmbientTarget::ClockOutline,
+            update_interval_ms: 1000,
+            enable_animations: true,
+            layout_mode: LayoutMode::Vertical,
+            columns: 1,
+            
+            // Position: Top-left area of the compositor's choice of output, auto-start enabled
+            widget_x: 50,
+            widget_y: 50,
+            widget_anchor: WidgetAnchor::TopLeft,
+            widget_output: String::new(),
+            widget_movable: false,
+            click_through: false,
+            keyboard_positioning: false,
+            snap_to_edges: true,
+            snap_grid: 0,
+            schedules: Vec::new(),
+            auto_hide_fullscreen: false,
+            auto_hide_maximized: false,
+            widget_autostart: true,
+            show_panel_readout: true,
+            
+            // Section order: Logical grouping from most to least common
+            section_order: vec![
+                WidgetSection::Clock,
+                WidgetSection::Utilization,
+                WidgetSection::Temperatures,
+                WidgetSection::Storage,
+                WidgetSection::Battery,
+                WidgetSection::Weather,
+                WidgetSection::Notifications,
+                WidgetSection::Media,
+                WidgetSection::Audio,
+                WidgetSection::Custom,
+                WidgetSection::SystemHealth,
+                WidgetSection::Feeds,
+                WidgetSection::Ticker,
+                WidgetSection::Plugins,
+                WidgetSection::Caffeine,
+                WidgetSection::StoragePools,
+                WidgetSection::Containers,
+                WidgetSection::VirtualMachines,
+                WidgetSection::System,
+                WidgetSection::Network,
+                WidgetSection::Disk,
+            ],
+            
+            // Advanced: Logging off by default, stats file is cheap (tmpfs),
+            // history recording is opt-in since it writes to disk, and the
+            // update check is opt-in since it contacts GitHub
+            enable_logging: false,
+            log_level: LogLevel::Warn,
+            show_debug_overlay: false,
+            publish_stats: true,
+            export_frames: false,
+            record_metrics: false,
+            record_interval_secs: 60,
+            record_retention_days: 30,
+            low_priority_collectors: true,
+            idle_priority_collectors: false,
+            pin_collectors_to_efficiency_cores: false,
+            check_for_updates: false,
+        }
+    }
+}
+
+// ============================================================================
+// Locale Defaults
+// ============================================================================
+
+/// Regions whose locales format times with AM/PM, regardless of language.
+const TWELVE_HOUR_REGIONS: &[&str] = &["US", "AU", "NZ", "IN", "PH", "PK", "BD", "EG", "SA", "KR", "TW"];
+
+/// Whether the user's time locale prefers the 24-hour clock.
+///
+/// Checks `LC_ALL`, `LC_TIME` and `LANG` in the same order as libc. Without
+/// any locale set, keeps the previous 12-hour default.
+pub fn locale_uses_24hour_time() -> bool {
+    time_locale_name().is_some_and(|locale| uses_24hour_time(&locale))
+}
+
+/// Locale for month and day names in dates (e.g., `Mittwoch, 15 Januar`).
+///
+/// Uses the same variables as [`locale_uses_24hour_time`]; unknown locales
+/// and `C`/`POSIX` fall back to English names.
+pub fn time_locale() -> chrono::Locale {
+    time_locale_name()
+        .and_then(|locale| chrono_locale(&locale))
+        .unwrap_or(chrono::Locale::POSIX)
+}
+
+/// First non-empty of `LC_ALL`, `LC_TIME` and `LANG`.
+fn time_locale_name() -> Option<String> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// chrono locale of a locale name (`de_DE.UTF-8` → `de_DE`).
+fn chrono_locale(locale: &str) -> Option<chrono::Locale> {
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    chrono::Locale::try_from(name).ok()
+}
+
+/// Whether a locale name (e.g., "en_US.UTF-8", "de_DE@euro") uses 24-hour time.
+fn uses_24hour_time(locale: &str) -> bool {
+    // Strip encoding and modifier: language[_REGION][.encoding][@modifier]
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    let (language, region) = name.split_once('_').unwrap_or((name, ""));
+
+    // English Canada uses AM/PM, French Canada doesn't
+    if language == "en" && region == "CA" {
+        return false;
+    }
+    !TWELVE_HOUR_REGIONS.contains(&region)
+}
+
+// ============================================================================
+// Clock & Date Formats
+// ============================================================================
+
+/// Date pattern used while `date_format` is empty.
+pub const DEFAULT_DATE_FORMAT: &str = "%A, %d %B %Y";
+
+/// Whether chrono can format `pattern` (unknown specifiers like `%Q` can't
+/// be formatted and would panic in `to_string`).
+pub fn is_valid_time_format(pattern: &str) -> bool {
+    StrftimeItems::new(pattern).all(|item| item != Item::Error)
+}
+
+impl Config {
+    /// Custom pattern of the large clock, if one is set and valid.
+    pub fn clock_pattern(&self) -> Option<&str> {
+        let pattern = self.clock_format.trim();
+        (!pattern.is_empty() && is_valid_time_format(pattern)).then_some(pattern)
+    }
+
+    /// Pattern of the date: the custom one if set and valid, else the default.
+    pub fn date_pattern(&self) -> &str {
+        let pattern = self.date_format.trim();
+        if !pattern.is_empty() && is_valid_time_format(pattern) {
+            pattern
+        } else {
+            DEFAULT_DATE_FORMAT
+        }
+    }
+
+    /// Whether the frequency/governor row under the CPU bar is shown.
+    pub fn shows_cpu_frequency_row(&self) -> bool {
+        self.show_cpu && (self.show_cpu_frequency || self.show_cpu_governor)
+    }
+
+    /// Whether the pressure row is shown (it sits in the Utilization
+    /// section, which needs one of its bars).
+    pub fn shows_psi_row(&self) -> bool {
+        self.show_psi && (self.show_cpu || self.show_memory || self.show_gpu)
+    }
+
+    /// Whether the Temperatures section draws the temperature graph.
+    pub fn shows_temp_graph(&self) -> bool {
+        (self.show_cpu_temp || self.show_gpu_temp) && !self.temps_on_usage_bars && self.show_temp_graph
+    }
+
+    /// Whether the hostname/user line is drawn at the top of the widget.
+    pub fn shows_identity(&self) -> bool {
+        self.show_hostname || self.show_username
+    }
+
+    /// Whether the hottest core/throttling row of the Temperatures section
+    /// is shown.
+    pub fn shows_thermal_row(&self) -> bool {
+        (self.show_cpu_temp || self.show_gpu_temp)
+            && !self.temps_on_usage_bars
+            && (self.show_hottest_core || self.show_throttling)
+    }
+
+    /// Opacity of the card behind a section (percent), `None` without one.
+    pub fn card_opacity(&self, section: WidgetSection) -> Option<u32> {
+        self.section_cards.iter().find(|card| card.section == section).map(|card| card.opacity)
+    }
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Sampling interval of the widget (milliseconds).
+pub const UPDATE_INTERVAL_MS: RangeInclusive<u64> = 100..=10000;
+/// Time span of the network graph (seconds).
+pub const NETWORK_GRAPH_SECONDS: RangeInclusive<u32> = 10..=3600;
+/// Time span of the temperature graph (minutes).
+pub const TEMP_GRAPH_MINUTES: RangeInclusive<u32> = 1..=120;
+/// Refresh interval of calendar, system health, feeds and ticker (minutes).
+pub const REFRESH_MINUTES: RangeInclusive<u32> = 1..=1440;
+/// Free space below which a filesystem is low (percent, 0 = off).
+pub const STORAGE_MIN_FREE_PERCENT: RangeInclusive<u32> = 0..=50;
+/// Inode use at which a filesystem is low (percent, 0 = off).
+pub const STORAGE_MAX_INODE_PERCENT: RangeInclusive<u32> = 0..=100;
+/// Upcoming events listed in the calendar.
+pub const CALENDAR_MAX_EVENTS: RangeInclusive<u32> = 1..=10;
+/// Notifications shown at once.
+pub const MAX_NOTIFICATIONS: RangeInclusive<usize> = 1..=20;
+/// Notifications kept in the history.
+pub const NOTIFICATION_HISTORY_COUNT: RangeInclusive<usize> = 1..=500;
+/// Age limit of the notification history (days, 0 = none).
+pub const NOTIFICATION_HISTORY_DAYS: RangeInclusive<u32> = 0..=365;
+/// Symbols shown by the ticker.
+pub const TICKER_MAX_SYMBOLS: RangeInclusive<u32> = 1..=20;
+/// Interval of a custom command (seconds, at most a day).
+pub const CUSTOM_INTERVAL_SECS: RangeInclusive<u32> = 1..=86400;
+/// Grid the widget snaps to while dragged (pixels, 0 = off).
+pub const SNAP_GRID: RangeInclusive<u32> = 0..=200;
+/// Interval of the metric history recorder (seconds).
+pub const RECORD_INTERVAL_SECS: RangeInclusive<u64> = 1..=3600;
+/// Columns of the vertical layout.
+pub const COLUMNS: RangeInclusive<u32> = 1..=2;
+/// Speed of the media title marquee (pixels per second).
+pub const MARQUEE_SPEED: RangeInclusive<u32> = 5..=200;
+/// Wait at each end of the media title marquee (milliseconds).
+pub const MARQUEE_PAUSE_MS: RangeInclusive<u32> = 0..=10000;
+/// Opacity of a section card (percent).
+pub const CARD_OPACITY: RangeInclusive<u32> = 10..=100;
+/// Size of a text role's font (points).
+pub const FONT_SIZE: RangeInclusive<u32> = 6..=96;
+/// Highest color threshold (percent, or °C).
+pub const MAX_THRESHOLD: u32 = 1000;
+
+impl Config {
+    /// Replace stored values the widget can't use: numbers outside the
+    /// ranges the settings app accepts, strftime patterns chrono can't
+    /// format, colors that aren't `#RRGGBB`, and thresholds in the wrong
+    /// order.
+    ///
+    /// Returns what was changed, one line per value. Values of the wrong
+    /// type (e.g., an unknown anchor) are already replaced by cosmic-config
+    /// while loading and reported as load errors.
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut issues = Vec::new();
+        clamp_setting(&mut issues, "Update interval (ms)", &mut self.update_interval_ms, &UPDATE_INTERVAL_MS);
+        clamp_setting(&mut issues, "Network graph window (s)", &mut self.network_graph_seconds, &NETWORK_GRAPH_SECONDS);
+        clamp_setting(&mut issues, "Temperature graph window (min)", &mut self.temp_graph_minutes, &TEMP_GRAPH_MINUTES);
+        clamp_setting(&mut issues, "Calendar refresh (min)", &mut self.calendar_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Calendar events", &mut self.calendar_max_events, &CALENDAR_MAX_EVENTS);
+        clamp_setting(&mut issues, "System health refresh (min)", &mut self.health_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Low space warning (% free)", &mut self.storage_min_free_percent, &STORAGE_MIN_FREE_PERCENT);
+        clamp_setting(&mut issues, "Inode warning (% used)", &mut self.storage_max_inode_percent, &STORAGE_MAX_INODE_PERCENT);
+        clamp_setting(&mut issues, "Drive health refresh (min)", &mut self.smart_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Storage pools refresh (min)", &mut self.storage_pools_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Containers refresh (min)", &mut self.containers_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Virtual machines refresh (min)", &mut self.vms_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Feeds refresh (min)", &mut self.feeds_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Ticker refresh (min)", &mut self.ticker_refresh_minutes, &REFRESH_MINUTES);
+        clamp_setting(&mut issues, "Ticker symbols", &mut self.ticker_max_symbols, &TICKER_MAX_SYMBOLS);
+        clamp_setting(&mut issues, "Max notifications", &mut self.max_notifications, &MAX_NOTIFICATIONS);
+        clamp_setting(&mut issues, "Notification history size", &mut self.notification_history_count, &NOTIFICATION_HISTORY_COUNT);
+        clamp_setting(&mut issues, "Notification history days", &mut self.notification_history_days, &NOTIFICATION_HISTORY_DAYS);
+        clamp_setting(&mut issues, "Snap grid (px)", &mut self.snap_grid, &SNAP_GRID);
+        clamp_setting(&mut issues, "History interval (s)", &mut self.record_interval_secs, &RECORD_INTERVAL_SECS);
+        clamp_setting(&mut issues, "Columns", &mut self.columns, &COLUMNS);
+        clamp_setting(&mut issues, "Marquee speed (px/s)", &mut self.marquee_speed, &MARQUEE_SPEED);
+        clamp_setting(&mut issues, "Marquee pause (ms)", &mut self.marquee_pause_ms, &MARQUEE_PAUSE_MS);
+        for command in &mut self.custom_commands {
+            let name = format!("Interval of custom command \"{}\" (s)", command.label);
+            clamp_setting(&mut issues, &name, &mut command.interval_secs, &CUSTOM_INTERVAL_SECS);
+        }
+        for role in TextRole::ALL {
+            let name = format!("{} font size (pt)", role.label());
+            clamp_setting(&mut issues, &name, &mut self.fonts.get_mut(role).size, &FONT_SIZE);
+        }
+        for card in &mut self.section_cards {
+            let name = format!("{} card opacity (%)", card.section.label());
+            clamp_setting(&mut issues, &name, &mut card.opacity, &CARD_OPACITY);
+        }
+        
+        for (name, pattern) in [("Clock format", &mut self.clock_format), ("Date format", &mut self.date_format)] {
+            if !pattern.trim().is_empty() && !is_valid_time_format(pattern.trim()) {
+                issues.push(format!("{}: \"{}\" is not a valid strftime pattern, using the default", name, pattern));
+                pattern.clear();
+            }
+        }
+        
+        let default_colors = BarColors::default();
+        for level in Level::ALL {
+            let color = self.bar_colors.get_mut(level);
+            if parse_hex_color(color).is_none() {
+                let default = default_colors.get(level);
+                issues.push(format!("{} color: \"{}\" is not #RRGGBB, using {}", level.label(), color, default));
+                *color = default.to_string();
+            }
+        }
+        
+        for metric in ThresholdMetric::ALL {
+            let thresholds = self.thresholds.get_mut(metric);
+            let range = 0..=MAX_THRESHOLD;
+            clamp_setting(&mut issues, &format!("{} warning threshold", metric.label()), &mut thresholds.warning, &range);
+            clamp_setting(&mut issues, &format!("{} critical threshold", metric.label()), &mut thresholds.critical, &range);
+            if thresholds.warning > thresholds.critical {
+                issues.push(format!(
+                    "{} thresholds: warning {} is above critical {}, swapped",
+                    metric.label(),
+                    thresholds.warning,
+                    thresholds.critical
+                ));
+                std::mem::swap(&mut thresholds.warning, &mut thresholds.critical);
+            }
+        }
+        issues
+    }
+}
+
+/// Clamp `value` into `range`, noting the change in `issues`.
+fn clamp_setting<T: PartialOrd + Copy + Display>(issues: &mut Vec<String>, name: &str, value: &mut T, range: &RangeInclusive<T>) {
+    let clamped = if *value < *range.start() {
+        *range.start()
+    } else if *value > *range.end() {
+        *range.end()
+    } else {
+        return;
+    };
+    issues.push(format!("{}: {} is out of range ({}-{}), using {}", name, value, range.start(), range.end(), clamped));
+    *value = clamped;
+}
+
+// ============================================================================
+// Migration
+// ============================================================================
+
+impl Config {
+    /// Add sections missing from `section_order` and drop duplicates.
+    ///
+    /// When new sections are added to the app, existing configs won't have
+    /// them. Missing sections are inserted where they used to be rendered
+    /// before becoming reorderable, so existing layouts look the same.
+    /// Applied by both the widget and the settings app when loading.
+    pub fn migrate_section_order(&mut self) {
+        let order = &mut self.section_order;
+        let mut seen = Vec::new();
+        order.retain(|section| {
+            let first = !seen.contains(section);
+            seen.push(*section);
+            first
+        });
+        
+        // Clock was always drawn above all sections
+        if !order.contains(&WidgetSection::Clock) {
+            order.insert(0, WidgetSection::Clock);
+        }
+        
+        // Battery belongs after Storage (added in v1.x)
+        if !order.contains(&WidgetSection::Battery) {
+            if let Some(storage_pos) = order.iter().position(|s| *s == WidgetSection::Storage) {
+                order.insert(storage_pos + 1, WidgetSection::Battery);
+            } else if let Some(weather_pos) = order.iter().position(|s| *s == WidgetSection::Weather) {
+                order.insert(weather_pos, WidgetSection::Battery);
+            } else {
+                order.push(WidgetSection::Battery);
+            }
+        }
+        
+        // Everything else goes to the end; Network and Disk were always
+        // drawn below all other sections
+        for section in [
+            WidgetSection::Utilization,
+            WidgetSection::Temperatures,
+            WidgetSection::Storage,
+            WidgetSection::Weather,
+            WidgetSection::Notifications,
+            WidgetSection::Media,
+            WidgetSection::Audio,
+            WidgetSection::Custom,
+            WidgetSection::SystemHealth,
+            WidgetSection::Feeds,
+            WidgetSection::Ticker,
+            WidgetSection::Plugins,
+            WidgetSection::Caffeine,
+            WidgetSection::StoragePools,
+            WidgetSection::Containers,
+            WidgetSection::VirtualMachines,
+            WidgetSection::System,
+            WidgetSection::Network,
+            WidgetSection::Disk,
+        ] {
+            if !order.contains(&section) {
+                order.push(section);
+            }
+        }
+    }
+}
+
+/// Stored config keys of one version: field name → value as RON.
+pub type StoredKeys = BTreeMap<String, String>;
+
+/// Turns the stored keys of version `from` into those of `from + 1`.
+pub struct Migration {
+    /// Version the step starts from
+    pub from: u64,
+    /// Rename, convert or drop keys
+    pub apply: fn(&mut StoredKeys),
+}
+
+/// Steps between config versions, one per version bump.
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// Fill the current config version from an older one, if it's empty.
+///
+/// Returns the version migrated from, `None` if there was nothing to do.
+/// Called by each binary before loading the config.
+///
+/// # Errors
+///
+/// Returns the error reading the old or writing the new keys.
+pub fn migrate_stored() -> io::Result<Option<u64>> {
+    let root = dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("cosmic")
+        .join(CONFIG_ID);
+    migrate_dir(&root, Config::VERSION, MIGRATIONS)
+}
+
+/// Log the outcome of [`migrate_stored`], once logging is set up.
+pub fn log_migration(result: &io::Result<Option<u64>>) {
+    match result {
+        Ok(Some(from)) => log::info!("Migrated the stored configuration from v{} to v{}", from, Config::VERSION),
+        Ok(None) => {}
+        Err(e) => log::warn!("Could not migrate the stored configuration: {}", e),
+    }
+}
+
+/// [`migrate_stored`] for the versions below `root`.
+fn migrate_dir(root: &Path, version: u64, steps: &[Migration]) -> io::Result<Option<u64>> {
+    let has_keys = |dir: &Path| fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some());
+    if has_keys(&version_dir(root, version)) {
+        return Ok(None);
+    }
+    let Some(from) = (1..version).rev().find(|old| has_keys(&version_dir(root, *old))) else {
+        return Ok(None);
+    };
+    
+    let mut keys = StoredKeys::new();
+    for entry in fs::read_dir(version_dir(root, from))? {
+        let path = entry?.path();
+        if let (true, Some(name)) = (path.is_file(), path.file_name().and_then(|name| name.to_str())) {
+            keys.insert(name.to_string(), fs::read_to_string(&path)?);
+        }
+    }
+    migrate_keys(&mut keys, from, version, steps);
+    
+    let dir = version_dir(root, version);
+    fs::create_dir_all(&dir)?;
+    for (name, value) in &keys {
+        fs::write(dir.join(name), value)?;
+    }
+    Ok(Some(from))
+}
+
+/// Apply the steps taking `keys` from version `from` to `to`.
+pub fn migrate_keys(keys: &mut StoredKeys, from: u64, to: u64, steps: &[Migration]) {
+    for version in from..to {
+        if let Some(step) = steps.iter().find(|step| step.from == version) {
+            (step.apply)(keys);
+        }
+    }
+}
+
+/// Directory of one config version (`<root>/v<version>`).
+fn version_dir(root: &Path, version: u64) -> PathBuf {
+    root.join(format!("v{}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config read from stored keys, like cosmic-config does.
+    fn config_from_keys(keys: &StoredKeys) -> Config {
+        let fields: Vec<String> = keys.iter().map(|(name, value)| format!("{}: {}", name, value)).collect();
+        ron::from_str(&format!("({})", fields.join(", "))).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_stored_v1_config() {
+        let root = std::env::temp_dir().join(format!("cosmic-monitor-config-test-{}", std::process::id()));
+        let v1 = version_dir(&root, 1);
+        fs::create_dir_all(&v1).unwrap();
+        // As cosmic-config stored them, with a field and a variant renamed since
+        for (name, value) in [
+            ("show_cpu", "false"),
+            ("weather_location", "\"Berlin\""),
+            ("circular_temps", "false"),
+            ("section_order", "[Clock, Utilisation, Weather]"),
+        ] {
+            fs::write(v1.join(name), value).unwrap();
+        }
+        fn rename_v1(keys: &mut StoredKeys) {
+            if let Some(value) = keys.remove("circular_temps") {
+                keys.insert(String::from("use_circular_temp_display"), value);
+            }
+            if let Some(order) = keys.get_mut("section_order") {
+                *order = order.replace("Utilisation", "Utilization");
+            }
+        }
+        let steps = [Migration { from: 1, apply: rename_v1 }];
+
+        assert_eq!(migrate_dir(&root, 3, &steps).unwrap(), Some(1));
+        let keys: StoredKeys = fs::read_dir(version_dir(&root, 3))
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap())
+            })
+            .collect();
+        let config = config_from_keys(&keys);
+        assert!(!config.show_cpu);
+        assert_eq!(config.weather_location, "Berlin");
+        assert!(!config.use_circular_temp_display);
+        assert_eq!(config.section_order, vec![WidgetSection::Clock, WidgetSection::Utilization, WidgetSection::Weather]);
+        assert_eq!(config.show_gpu, Config::default().show_gpu);
+
+        // Already migrated: left alone
+        assert_eq!(migrate_dir(&root, 3, &steps).unwrap(), None);
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(migrate_dir(&root, 3, &steps).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_section_order_keeps_old_layout() {
+        let mut config = Config::default();
+        config.section_order = vec![
+            WidgetSection::Weather,
+            WidgetSection::Storage,
+            WidgetSection::Weather,
+            WidgetSection::Utilization,
+        ];
+        config.migrate_section_order();
+
+        assert_eq!(&config.section_order[..5], &[
+            WidgetSection::Clock,
+            WidgetSection::Weather,
+            WidgetSection::Storage,
+            WidgetSection::Battery,
+            WidgetSection::Utilization,
+        ]);
+        assert_eq!(&config.section_order[config.section_order.len() - 2..], &[WidgetSection::Network, WidgetSection::Disk]);
+        assert_eq!(config.section_order.len(), Config::default().section_order.len());
+    }
+
+    #[test]
+    fn test_uses_24hour_time() {
+        assert!(!uses_24hour_time("en_US.UTF-8"));
+        assert!(!uses_24hour_time("en_CA.UTF-8"));
+        assert!(uses_24hour_time("fr_CA.UTF-8"));
+        assert!(uses_24hour_time("de_DE@euro"));
+        assert!(uses_24hour_time("en_GB.UTF-8"));
+        assert!(uses_24hour_time("C.UTF-8"));
+    }
+
+    #[test]
+    fn test_chrono_locale() {
+        assert_eq!(chrono_locale("de_DE.UTF-8"), Some(chrono::Locale::de_DE));
+        assert_eq!(chrono_locale("fr_CA@latin"), Some(chrono::Locale::fr_CA));
+        assert_eq!(chrono_locale("C.UTF-8"), None);
+    }
+
+    #[test]
+    fn test_time_formats() {
+        assert!(is_valid_time_format("%Y-%m-%d, week %V"));
+        assert!(!is_valid_time_format("%H:%Q"));
+
+        let mut config = Config::default();
+        assert_eq!(config.clock_pattern(), None);
+        assert_eq!(config.date_pattern(), DEFAULT_DATE_FORMAT);
+
+        config.clock_format = String::from("%H:%M:%S");
+        config.date_format = String::from("%Q");
+        assert_eq!(config.clock_pattern(), Some("%H:%M:%S"));
+        assert_eq!(config.date_pattern(), DEFAULT_DATE_FORMAT);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = Config::default();
+        assert!(config.validate().is_empty());
+
+        config.update_interval_ms = 5;
+        config.columns = 0;
+        config.clock_format = String::from("%H:%Q");
+        config.bar_colors.warning = String::from("yellow");
+        config.thresholds.cpu = Thresholds::new(90, 60);
+        config.thresholds.temperature.critical = 5000;
+        assert_eq!(config.validate().len(), 6);
+        assert_eq!(config.update_interval_ms, *UPDATE_INTERVAL_MS.start());
+        assert_eq!(config.columns, 1);
+        assert!(config.clock_format.is_empty());
+        assert_eq!(config.bar_colors, BarColors::default());
+        assert_eq!(config.thresholds.cpu, Thresholds::new(60, 90));
+        assert_eq!(config.thresholds.temperature, Thresholds::new(50, MAX_THRESHOLD));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn test_thresholds_and_colors() {
+        let temps = Thresholds::new(70, 85);
+        assert_eq!(temps.level(69.9), Level::Normal);
+        assert_eq!(temps.level(70.0), Level::Warning);
+        assert_eq!(temps.level(91.0), Level::Critical);
+
+        assert_eq!(parse_hex_color("#FF0000"), Some((1.0, 0.0, 0.0)));
+        assert_eq!(parse_hex_color("00ff00"), Some((0.0, 1.0, 0.0)));
+        assert_eq!(parse_hex_color("#F00"), None);
+        assert_eq!(parse_hex_color("#ÿÿÿ"), None);
+
+        // Invalid colors fall back to the default
+        let colors = BarColors {
+            warning: String::from("yellow"),
+            ..Default::default()
+        };
+        assert_eq!(colors.rgb(Level::Warning), parse_hex_color("#E6E666").unwrap());
+    }
+}
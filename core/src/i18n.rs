@@ -4,6 +4,8 @@
 //!
 //! This module provides localization support using the Fluent translation system.
 //! Translations are stored in the `i18n/` directory in `.ftl` (Fluent) files.
+//! It is at the workspace root, shared by the binaries and this crate
+//! (`core/i18n.toml` points there and keeps the binaries' domain).
 //!
 //! # Directory Structure
 //!
@@ -19,8 +21,8 @@
 //!
 //! Use the `fl!()` macro to request localized strings:
 //!
-//! ```rust
-//! use crate::fl;
+//! ```ignore
+//! use cosmic_monitor_core::fl;
 //!
 //! // Simple string lookup
 //! let title = fl!("app-title");
@@ -59,7 +61,7 @@ use rust_embed::RustEmbed;
 ///   typically from `i18n_embed::DesktopLanguageRequester::requested_languages()`
 pub fn init(requested_languages: &[LanguageIdentifier]) {
     if let Err(why) = localizer().select(requested_languages) {
-        eprintln!("error while loading fluent localizations: {why}");
+        log::error!("error while loading fluent localizations: {why}");
     }
 }
 
@@ -76,7 +78,7 @@ pub fn localizer() -> Box<dyn Localizer> {
 /// The `RustEmbed` derive macro embeds all `.ftl` files at compile time,
 /// so the binary doesn't need external translation files at runtime.
 #[derive(RustEmbed)]
-#[folder = "i18n/"]
+#[folder = "../i18n/"]
 struct Localizations;
 
 /// Global Fluent language loader instance.
@@ -98,7 +100,7 @@ pub static LANGUAGE_LOADER: LazyLock<FluentLanguageLoader> = LazyLock::new(|| {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```ignore
 /// // Simple string lookup
 /// let text = fl!("show-cpu");  // Returns "Show CPU" or translated equivalent
 ///
@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! COSMIC Monitor Core
+//!
+//! Code shared by the three binaries, so they collect and read everything
+//! the same way:
+//!
+//! ```text
+//!                      cosmic_monitor_core
+//!          ┌──────────┬──────────┬──────────┬──────────┐
+//!          │  config  │   i18n   │ logging  │  widget  │
+//!          └──────────┴──────────┴──────────┴──────────┘
+//!                │                │                 │
+//!   cosmic-monitor-applet  cosmic-monitor-widget  cosmic-monitor-settings
+//!   (panel readout, popup) (layer-shell surface)  (pages, live preview)
+//! ```
+//!
+//! - [`config`]: The cosmic-config entry, its migration and validation
+//! - [`i18n`]: Fluent translations and the [`fl!`] macro
+//! - [`logging`]: Log level and the rotating log file
+//! - [`widget`]: Monitors (CPU, memory, GPU, temperatures, network, storage,
+//!   ...), the renderer and layout of the widget surface
+//!
+//! The binaries only hold their own UI: the applet in `app.rs`, the
+//! Wayland surface and event loop in `widget_main.rs`, and the settings
+//! pages in `settings.rs`.
+
+pub mod config;
+pub mod i18n;
+pub mod logging;
+pub mod widget;
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Logging
+//!
+//! All three binaries log through the `log` facade, set up here with
+//! env_logger:
+//!
+//! ```text
+//! log::warn!(...) ──► env_logger ──┬──► stderr (journal when autostarted)
+//!                                  └──► $XDG_STATE_HOME/cosmic-monitor/<binary>.log
+//!                                       (only with `enable_logging`)
+//! ```
+//!
+//! The level comes from the config's `log_level` (Warnings by default).
+//! `--verbose` raises it to Debug, and `RUST_LOG` overrides both, including
+//! per-module filters (`RUST_LOG=cosmic_monitor_widget::widget::temperature=trace`).
+//!
+//! Log files rotate at 1 MiB, keeping three old files (`<binary>.log.1`
+//! being the newest), so leaving file logging on never fills the disk.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use log::LevelFilter;
+
+use crate::config::LogLevel;
+
+/// Size at which a log file is rotated (1 MiB).
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Number of rotated files kept next to the current one.
+const ROTATED_FILES: usize = 3;
+
+/// Whether `--verbose` (or `-v`) was passed; removes it from `args`.
+pub fn take_verbose_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != "--verbose" && arg != "-v");
+    args.len() != before
+}
+
+/// Level to log at for the configured level and `--verbose`.
+pub fn level_filter(level: LogLevel, verbose: bool) -> LevelFilter {
+    let configured = match level {
+        LogLevel::Error => LevelFilter::Error,
+        LogLevel::Warn => LevelFilter::Warn,
+        LogLevel::Info => LevelFilter::Info,
+        LogLevel::Debug => LevelFilter::Debug,
+        LogLevel::Trace => LevelFilter::Trace,
+    };
+    if verbose {
+        configured.max(LevelFilter::Debug)
+    } else {
+        configured
+    }
+}
+
+/// Where `binary` writes its log file (`None` without a home directory).
+pub fn log_file_path(binary: &str) -> Option<PathBuf> {
+    Some(dirs::state_dir()?.join("cosmic-monitor").join(format!("{}.log", binary)))
+}
+
+/// Set up the logger for `binary`.
+///
+/// Logs go to stderr and, with `to_file`, to the binary's rotating log
+/// file. A log file that can't be opened only costs the file output.
+pub fn init(binary: &str, level: LevelFilter, to_file: bool) {
+    let file = if to_file {
+        match log_file_path(binary).map(RotatingFile::open) {
+            Some(Ok(file)) => Some(file),
+            Some(Err(e)) => {
+                eprintln!("{}: cannot open log file, logging to stderr only: {}", binary, e);
+                None
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level).parse_env("RUST_LOG");
+    if let Some(file) = file {
+        builder.target(env_logger::Target::Pipe(Box::new(Tee { file })));
+    }
+    // Only the first call wins (e.g., CLI modes that set up logging early)
+    if builder.try_init().is_err() {
+        return;
+    }
+
+    log::debug!("Logging at {} (file output: {})", log::max_level(), to_file);
+}
+
+/// Copies every record to stderr and the log file.
+struct Tee {
+    file: RotatingFile,
+}
+
+impl Write for Tee {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // stderr may be closed when started from the panel; the file still counts
+        let _ = io::stderr().write_all(buf);
+        self.file.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let _ = io::stderr().flush();
+        self.file.flush()
+    }
+}
+
+/// Log file that moves itself aside once it grows past [`MAX_LOG_BYTES`].
+struct RotatingFile {
+    /// Path of the current file
+    path: PathBuf,
+    /// Open handle (appending)
+    file: File,
+    /// Bytes in the current file
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Open (or create) the log file at `path`, creating its directory.
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    /// Shift `<path>.1` .. `<path>.N` up by one and start a fresh file.
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..ROTATED_FILES).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Path of the `index`th rotated file (`widget.log` → `widget.log.2`).
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbose_flag_and_level() {
+        let mut args = vec![String::from("--verbose"), String::from("--replace")];
+        assert!(take_verbose_flag(&mut args));
+        assert_eq!(args, vec![String::from("--replace")]);
+        assert!(!take_verbose_flag(&mut args));
+
+        assert_eq!(level_filter(LogLevel::Warn, false), LevelFilter::Warn);
+        assert_eq!(level_filter(LogLevel::Warn, true), LevelFilter::Debug);
+        assert_eq!(level_filter(LogLevel::Trace, true), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = std::env::temp_dir().join(format!("cosmic-monitor-log-test-{}", std::process::id()));
+        let path = dir.join("test.log");
+        let mut file = RotatingFile::open(path.clone()).unwrap();
+
+        let line = vec![b'x'; MAX_LOG_BYTES as usize / 2 + 1];
+        for _ in 0..6 {
+            file.write_all(&line).unwrap();
+        }
+        assert!(rotated_path(&path, ROTATED_FILES).exists());
+        assert!(!rotated_path(&path, ROTATED_FILES + 1).exists());
+        assert_eq!(fs::metadata(&path).unwrap().len(), line.len() as u64);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Air Quality
+//!
+//! Air quality index at the weather station's coordinates, drawn as a
+//! colored badge next to the weather. The coordinates come from the last
+//! weather fetch, so the badge follows the weather location:
+//!
+//! ```text
+//! WeatherMonitor ──coordinates()──► set_coordinates()
+//!                                        │
+//! Open-Meteo ──/v1/air-quality?current=us_aqi──┐
+//!                                              ├──► reading() ◄── draw ("AQI 42 · Good")
+//! OpenWeatherMap ──/data/2.5/air_pollution─────┘
+//!                   refresher thread, every 30 minutes
+//! ```
+//!
+//! Open-Meteo reports the US AQI (0-500) and needs no API key.
+//! OpenWeatherMap reports its own 1-5 index and uses the weather API key.
+//! Both are folded into three levels: Good, Moderate and Unhealthy.
+//!
+//! Air quality changes slowly and both free APIs rate limit, so refreshes
+//! happen every 30 minutes. Failed refreshes keep the previous reading and
+//! retry with exponential backoff (1 min doubling up to 30 min, honoring
+//! 429 `Retry-After`).
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::http::{get_json, Backoff, HttpResult};
+use crate::config::{AirQualityProvider, Config};
+use crate::fl;
+
+/// How often the refresher checks whether a refresh is due.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Longest a request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time between regular refreshes (30 minutes).
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1800);
+
+/// First retry delay after a failed refresh.
+const RETRY_BASE: Duration = Duration::from_secs(60);
+
+/// Upper bound for the retry delay after repeated failures (30 minutes).
+const RETRY_MAX: Duration = Duration::from_secs(1800);
+
+/// Moving the station by less than this (in degrees, about 1 km) doesn't
+/// trigger a refresh.
+const COORDINATE_EPSILON: f64 = 0.01;
+
+/// Open-Meteo's air quality API.
+const OPEN_METEO_URL: &str = "https://air-quality-api.open-meteo.com/v1/air-quality";
+
+/// OpenWeatherMap's Air Pollution API.
+const OPENWEATHERMAP_URL: &str = "https://api.openweathermap.org/data/2.5/air_pollution";
+
+/// What the refresher fetches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirQualitySettings {
+    /// Where the index comes from
+    pub provider: AirQualityProvider,
+    /// OpenWeatherMap API key (shared with the weather section)
+    pub api_key: String,
+}
+
+/// Settings for a configuration (`None` while the badge or the weather
+/// section is hidden).
+pub fn air_quality_settings(config: &Config) -> Option<AirQualitySettings> {
+    (config.show_weather && config.show_air_quality).then(|| AirQualitySettings {
+        provider: config.air_quality_provider,
+        api_key: config.weather_api_key.trim_matches('"').to_string(),
+    })
+}
+
+/// How healthy the air is, coarsely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AqiLevel {
+    /// US AQI up to 50, OpenWeatherMap 1-2
+    Good,
+    /// US AQI 51-100, OpenWeatherMap 3
+    Moderate,
+    /// US AQI above 100, OpenWeatherMap 4-5
+    Unhealthy,
+}
+
+impl AqiLevel {
+    /// Localized name for the badge.
+    pub fn label(&self) -> String {
+        match self {
+            AqiLevel::Good => fl!("aqi-good"),
+            AqiLevel::Moderate => fl!("aqi-moderate"),
+            AqiLevel::Unhealthy => fl!("aqi-unhealthy"),
+        }
+    }
+}
+
+/// One air quality reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AirQuality {
+    /// Index on the provider's own scale
+    pub index: u16,
+    /// Level the index falls in
+    pub level: AqiLevel,
+}
+
+/// Fetches the air quality on a background thread.
+pub struct AirQualityMonitor {
+    /// What to fetch (`None` while hidden)
+    settings: Arc<Mutex<Option<AirQualitySettings>>>,
+    /// Where to fetch it for (latitude, longitude)
+    coordinates: Arc<Mutex<Option<(f64, f64)>>>,
+    /// Latest reading
+    reading: Arc<Mutex<Option<AirQuality>>>,
+    /// Why the last refresh failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl AirQualityMonitor {
+    /// Create the monitor and start its refresher thread.
+    pub fn new(settings: Option<AirQualitySettings>) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let coordinates = Arc::new(Mutex::new(None));
+        let reading = Arc::new(Mutex::new(None));
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_settings = Arc::clone(&settings);
+        let thread_coordinates = Arc::clone(&coordinates);
+        let thread_reading = Arc::clone(&reading);
+        let thread_error = Arc::clone(&error);
+        std::thread::spawn(move || {
+            super::priority::enter_background("air-quality");
+            let mut last_request: Option<(AirQualitySettings, (f64, f64))> = None;
+            // When the next refresh is due (None: right away)
+            let mut next_refresh: Option<Instant> = None;
+            let mut backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+            loop {
+                // Stop once the monitor was dropped
+                if Arc::strong_count(&thread_settings) == 1 {
+                    break;
+                }
+
+                let settings = thread_settings.lock().unwrap().clone();
+                let coordinates = *thread_coordinates.lock().unwrap();
+                let (Some(settings), Some(coordinates)) = (settings, coordinates) else {
+                    last_request = None;
+                    *thread_reading.lock().unwrap() = None;
+                    *thread_error.lock().unwrap() = None;
+                    std::thread::sleep(TICK);
+                    continue;
+                };
+
+                // Refresh right away for another provider or a new location;
+                // the old reading is on another scale or for another place
+                let changed = last_request.as_ref().is_none_or(|(last_settings, last_coordinates)| {
+                    *last_settings != settings || !same_place(*last_coordinates, coordinates)
+                });
+                if changed {
+                    if last_request.is_some() {
+                        *thread_reading.lock().unwrap() = None;
+                    }
+                    last_request = Some((settings.clone(), coordinates));
+                    next_refresh = None;
+                    backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+                }
+
+                let due = next_refresh.is_none_or(|at| Instant::now() >= at);
+                if due && backoff.ready() {
+                    match fetch(&settings, coordinates) {
+                        Ok(reading) => {
+                            backoff.record_success();
+                            next_refresh = Some(Instant::now() + REFRESH_INTERVAL);
+                            *thread_error.lock().unwrap() =
+                                reading.is_none().then(|| String::from("No air quality index in the reply"));
+                            log::debug!("Air quality refreshed: {:?}", reading);
+                            *thread_reading.lock().unwrap() = reading;
+                        }
+                        Err(e) => {
+                            // Keep the previous reading; the backoff decides when to retry
+                            let delay = backoff.record_failure(&e);
+                            log::warn!("Air quality refresh failed, retrying in {:?}: {}", delay, e);
+                            *thread_error.lock().unwrap() = Some(e.to_string());
+                        }
+                    }
+                }
+
+                std::thread::sleep(TICK);
+            }
+        });
+
+        Self { settings, coordinates, reading, error }
+    }
+
+    /// Replace what the refresher fetches (e.g., after a config change).
+    pub fn set_settings(&self, settings: Option<AirQualitySettings>) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    /// Follow the weather station (called after every weather update).
+    pub fn set_coordinates(&self, coordinates: Option<(f64, f64)>) {
+        *self.coordinates.lock().unwrap() = coordinates;
+    }
+
+    /// Latest reading, if one arrived.
+    pub fn reading(&self) -> Option<AirQuality> {
+        *self.reading.lock().unwrap()
+    }
+
+    /// Why the badge can't be shown, if it can't.
+    pub fn error(&self) -> Option<String> {
+        let settings = self.settings.lock().unwrap().clone()?;
+        if settings.provider == AirQualityProvider::OpenWeatherMap && settings.api_key.is_empty() {
+            return Some(String::from("No OpenWeatherMap API key configured"));
+        }
+        self.error.lock().unwrap().clone()
+    }
+}
+
+/// Whether two coordinates are close enough to share a reading.
+fn same_place(a: (f64, f64), b: (f64, f64)) -> bool {
+    (a.0 - b.0).abs() < COORDINATE_EPSILON && (a.1 - b.1).abs() < COORDINATE_EPSILON
+}
+
+/// Fetch the current index from the configured provider.
+fn fetch(settings: &AirQualitySettings, (lat, lon): (f64, f64)) -> HttpResult<Option<AirQuality>> {
+    match settings.provider {
+        AirQualityProvider::OpenMeteo => {
+            let url = format!("{}?latitude={:.4}&longitude={:.4}&current=us_aqi", OPEN_METEO_URL, lat, lon);
+            let reply: Value = get_json(&url, &[], REQUEST_TIMEOUT)?;
+            Ok(parse_open_meteo(&reply))
+        }
+        AirQualityProvider::OpenWeatherMap => {
+            if settings.api_key.is_empty() {
+                return Ok(None);
+            }
+            let url = format!("{}?lat={:.4}&lon={:.4}&appid={}", OPENWEATHERMAP_URL, lat, lon, settings.api_key);
+            let reply: Value = get_json(&url, &[], REQUEST_TIMEOUT)?;
+            Ok(parse_openweathermap(&reply))
+        }
+    }
+}
+
+/// Reading in an Open-Meteo reply (`{"current": {"us_aqi": 42}}`).
+fn parse_open_meteo(reply: &Value) -> Option<AirQuality> {
+    let index = reply.pointer("/current/us_aqi")?.as_f64()?.round().max(0.0) as u16;
+    let level = match index {
+        0..=50 => AqiLevel::Good,
+        51..=100 => AqiLevel::Moderate,
+        _ => AqiLevel::Unhealthy,
+    };
+    Some(AirQuality { index, level })
+}
+
+/// Reading in an OpenWeatherMap reply (`{"list": [{"main": {"aqi": 2}}]}`).
+fn parse_openweathermap(reply: &Value) -> Option<AirQuality> {
+    let index = reply.pointer("/list/0/main/aqi")?.as_u64()?;
+    let level = match index {
+        1 | 2 => AqiLevel::Good,
+        3 => AqiLevel::Moderate,
+        4 | 5 => AqiLevel::Unhealthy,
+        _ => return None,
+    };
+    Some(AirQuality { index: index as u16, level })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_readings() {
+        let reply = serde_json::json!({"current": {"time": "2024-05-01T12:00", "us_aqi": 73.4}});
+        assert_eq!(parse_open_meteo(&reply), Some(AirQuality { index: 73, level: AqiLevel::Moderate }));
+        assert_eq!(parse_open_meteo(&serde_json::json!({"current": {}})), None);
+
+        let reply = serde_json::json!({"list": [{"main": {"aqi": 2}, "components": {"pm2_5": 4.1}}]});
+        assert_eq!(parse_openweathermap(&reply), Some(AirQuality { index: 2, level: AqiLevel::Good }));
+        let reply = serde_json::json!({"list": [{"main": {"aqi": 5}}]});
+        assert_eq!(parse_openweathermap(&reply).map(|reading| reading.level), Some(AqiLevel::Unhealthy));
+        assert_eq!(parse_openweathermap(&serde_json::json!({"list": []})), None);
+
+        assert!(same_place((51.5085, -0.1257), (51.51, -0.12)));
+        assert!(!same_place((51.5085, -0.1257), (48.85, 2.35)));
+    }
+}
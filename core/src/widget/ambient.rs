@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Ambient Status
+//!
+//! Tints the clock outline or the widget background with the system's
+//! state, so its health can be read at a glance without reading numbers.
+//! The color follows the same thresholds and bar colors as the usage bars,
+//! but blends between them instead of stepping:
+//!
+//! ```text
+//! value     0 ········ warning-10 ──── warning ········ critical-10 ──── critical
+//! color     normal ─── normal ──blend── warning ─── warning ──blend── critical
+//! severity  0          0                1           1                 2
+//! ```
+//!
+//! With `AmbientSource::Load` the busiest of CPU, memory and GPU usage
+//! (against their own thresholds) drives the color; with
+//! `AmbientSource::Temperature`, the hottest of the CPU, GPU and hottest
+//! core temperatures. Values come from the animator, so the color eases
+//! between samples like the bars do.
+
+use crate::config::{BarColors, Level, Thresholds};
+
+/// Width of the blend below each threshold (percent, or °C).
+const BLEND_RANGE: f32 = 10.0;
+
+/// How far `value` is into the warning (0-1) and critical (1-2) ranges.
+pub fn severity(value: f32, thresholds: Thresholds) -> f32 {
+    let ramp = |threshold: u32| ((value - (threshold as f32 - BLEND_RANGE)) / BLEND_RANGE).clamp(0.0, 1.0);
+    ramp(thresholds.warning) + ramp(thresholds.critical)
+}
+
+/// Color for a severity, blended between the bar colors.
+pub fn severity_color(severity: f32, colors: &BarColors) -> (f64, f64, f64) {
+    let lerp = |a: (f64, f64, f64), b: (f64, f64, f64), t: f64| {
+        (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t, a.2 + (b.2 - a.2) * t)
+    };
+    let severity = severity.clamp(0.0, 2.0) as f64;
+    if severity <= 1.0 {
+        lerp(colors.rgb(Level::Normal), colors.rgb(Level::Warning), severity)
+    } else {
+        lerp(colors.rgb(Level::Warning), colors.rgb(Level::Critical), severity - 1.0)
+    }
+}
+
+/// Severity of the worst `(value, thresholds)` reading (0 without readings).
+pub fn worst_severity(readings: impl IntoIterator<Item = (f32, Thresholds)>) -> f32 {
+    readings
+        .into_iter()
+        .map(|(value, thresholds)| severity(value, thresholds))
+        .fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_ramps() {
+        let thresholds = Thresholds::new(50, 80);
+        assert_eq!(severity(20.0, thresholds), 0.0);
+        assert_eq!(severity(45.0, thresholds), 0.5);
+        assert_eq!(severity(60.0, thresholds), 1.0);
+        assert_eq!(severity(75.0, thresholds), 1.5);
+        assert_eq!(severity(95.0, thresholds), 2.0);
+        assert_eq!(worst_severity([(20.0, thresholds), (75.0, thresholds)]), 1.5);
+        assert_eq!(worst_severity([]), 0.0);
+
+        let colors = BarColors::default();
+        assert_eq!(severity_color(0.0, &colors), colors.rgb(Level::Normal));
+        assert_eq!(severity_color(2.0, &colors), colors.rgb(Level::Critical));
+    }
+}
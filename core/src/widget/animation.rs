@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Value Transitions and Fades
+//!
+//! Monitors are sampled about once per second, so drawn values would
+//! otherwise jump from one sample to the next. The [`Animator`] eases every
+//! animated value (bar fills, temperature arcs, the widget's opacity) from
+//! its currently displayed value to the new target over [`DURATION`].
+//!
+//! ```text
+//! sample ──target──► Tween ──value(now)──► FrameData ──► render_widget
+//!                      │
+//!                      └── running? ──► wl_surface.frame() ──► frame() ──► draw()
+//! ```
+//!
+//! While any tween is running, `draw()` requests a frame callback so the
+//! compositor paces the intermediate frames; once everything has settled the
+//! widget goes back to redrawing only on clock ticks and new samples. No
+//! frames are produced for a surface the compositor isn't showing.
+//!
+//! With animations disabled every value jumps straight to its target.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Length of every transition.
+pub const DURATION: Duration = Duration::from_millis(300);
+
+// ============================================================================
+// Tween
+// ============================================================================
+
+/// One value easing from its previous target to its current target.
+#[derive(Debug, Clone, Copy)]
+struct Tween {
+    /// Displayed value when the transition started
+    from: f32,
+    /// Value the transition ends at
+    to: f32,
+    /// Start of the running transition (`None` = settled at `to`)
+    start: Option<Instant>,
+}
+
+impl Tween {
+    /// A tween settled at `value`.
+    fn new(value: f32) -> Self {
+        Self { from: value, to: value, start: None }
+    }
+
+    /// Start easing towards a new target from the currently displayed value.
+    ///
+    /// Non-finite values (sensor not read yet) jump without a transition.
+    fn set(&mut self, target: f32, now: Instant) {
+        if target == self.to || (target.is_nan() && self.to.is_nan()) {
+            return;
+        }
+        let current = self.value(now);
+        if !target.is_finite() || !current.is_finite() {
+            *self = Self::new(target);
+            return;
+        }
+        self.from = current;
+        self.to = target;
+        self.start = Some(now);
+    }
+
+    /// Displayed value at `now`.
+    fn value(&self, now: Instant) -> f32 {
+        match self.start {
+            Some(start) => {
+                let t = now.saturating_duration_since(start).as_secs_f32() / DURATION.as_secs_f32();
+                if t >= 1.0 {
+                    self.to
+                } else {
+                    self.from + (self.to - self.from) * ease_out_cubic(t)
+                }
+            }
+            None => self.to,
+        }
+    }
+
+    /// Whether the transition is still in progress at `now`.
+    fn is_running(&self, now: Instant) -> bool {
+        self.start.is_some_and(|start| now.saturating_duration_since(start) < DURATION)
+    }
+}
+
+/// Ease-out cubic: fast start, gentle landing on the new value.
+fn ease_out_cubic(t: f32) -> f32 {
+    let inv = 1.0 - t.clamp(0.0, 1.0);
+    1.0 - inv * inv * inv
+}
+
+// ============================================================================
+// Animator
+// ============================================================================
+
+/// All animated values of the widget, keyed by name.
+#[derive(Debug)]
+pub struct Animator {
+    /// Whether transitions are enabled (else values jump)
+    enabled: bool,
+    /// Animated values (e.g., "cpu", "disk:/home")
+    values: HashMap<String, Tween>,
+    /// Opacity of the whole widget (0.0 - 1.0)
+    opacity: Tween,
+}
+
+impl Animator {
+    /// Create an animator with a fully opaque widget.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            values: HashMap::new(),
+            opacity: Tween::new(1.0),
+        }
+    }
+
+    /// Enable or disable transitions; disabling settles everything at once.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            for tween in self.values.values_mut() {
+                *tween = Tween::new(tween.to);
+            }
+            self.opacity = Tween::new(self.opacity.to);
+        }
+    }
+
+    /// Displayed value for `key`, easing towards `target`.
+    ///
+    /// The first call for a key shows the target right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Stable name of the value (e.g., "cpu", "disk:/home")
+    /// * `target` - Latest sampled value
+    /// * `now` - Frame time
+    pub fn value(&mut self, key: &str, target: f32, now: Instant) -> f32 {
+        if !self.enabled {
+            return target;
+        }
+        match self.values.get_mut(key) {
+            Some(tween) => {
+                tween.set(target, now);
+                tween.value(now)
+            }
+            None => {
+                self.values.insert(key.to_string(), Tween::new(target));
+                target
+            }
+        }
+    }
+
+    /// Current widget opacity.
+    pub fn opacity(&self, now: Instant) -> f64 {
+        self.opacity.value(now).clamp(0.0, 1.0) as f64
+    }
+
+    /// Fade a freshly created surface in from fully transparent.
+    pub fn fade_in_new_surface(&mut self, now: Instant) {
+        if self.enabled {
+            self.opacity = Tween::new(0.0);
+        }
+        self.fade_in(now);
+    }
+
+    /// Fade towards fully opaque from the current opacity.
+    pub fn fade_in(&mut self, now: Instant) {
+        self.fade_to(1.0, now);
+    }
+
+    /// Fade towards fully transparent from the current opacity.
+    pub fn fade_out(&mut self, now: Instant) {
+        self.fade_to(0.0, now);
+    }
+
+    /// Whether a fade-out is still in progress.
+    pub fn fading_out(&self, now: Instant) -> bool {
+        self.opacity.to == 0.0 && self.opacity.is_running(now)
+    }
+
+    /// Whether any transition is in progress (more frames needed).
+    pub fn is_running(&self, now: Instant) -> bool {
+        self.opacity.is_running(now) || self.values.values().any(|tween| tween.is_running(now))
+    }
+
+    /// Forget values that weren't drawn in the last frame (e.g., unmounted disks).
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.values.retain(|key, _| keep(key));
+    }
+
+    fn fade_to(&mut self, target: f32, now: Instant) {
+        if self.enabled {
+            self.opacity.set(target, now);
+        } else {
+            self.opacity = Tween::new(target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_eases_to_target() {
+        let start = Instant::now();
+        let mut animator = Animator::new(true);
+        assert_eq!(animator.value("cpu", 10.0, start), 10.0);
+
+        assert_eq!(animator.value("cpu", 50.0, start), 10.0);
+        let halfway = animator.value("cpu", 50.0, start + DURATION / 2);
+        assert!(halfway > 30.0 && halfway < 50.0, "ease-out is past the midpoint: {}", halfway);
+        assert!(animator.is_running(start + DURATION / 2));
+
+        assert_eq!(animator.value("cpu", 50.0, start + DURATION), 50.0);
+        assert!(!animator.is_running(start + DURATION));
+    }
+
+    #[test]
+    fn test_disabled_jumps_and_fades_instantly() {
+        let now = Instant::now();
+        let mut animator = Animator::new(false);
+        animator.value("cpu", 10.0, now);
+        assert_eq!(animator.value("cpu", 90.0, now), 90.0);
+
+        animator.fade_in_new_surface(now);
+        assert_eq!(animator.opacity(now), 1.0);
+        animator.fade_out(now);
+        assert_eq!(animator.opacity(now), 0.0);
+        assert!(!animator.fading_out(now));
+    }
+}
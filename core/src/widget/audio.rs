@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Audio Output Monitoring
+//!
+//! Volume, mute state and name of the default output device, read with
+//! `pactl` so it works with both PulseAudio and PipeWire (through
+//! `pipewire-pulse`):
+//!
+//! ```text
+//! poll thread (1s) ──pactl get-default-sink──► sink name
+//!                  ──pactl --format=json list sinks──► volume, mute, description
+//!                                                     │
+//!                             state() ◄── Arc<Mutex<Option<AudioState>>>
+//! ```
+//!
+//! Clicking the section toggles mute and scrolling changes the volume
+//! ([`AudioMonitor::toggle_mute`], [`AudioMonitor::change_volume`]); both
+//! act on `@DEFAULT_SINK@` and refresh the state right away instead of
+//! waiting for the next poll.
+//!
+//! JSON output needs pactl 16 or newer. Scrolling up stops at 100%, unless
+//! the volume was already boosted above it elsewhere.
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::exec::{self, CommandExt};
+use super::watchdog::Heartbeat;
+
+/// Volume change of one scroll step, in percent.
+pub const VOLUME_STEP: u32 = 5;
+
+/// How often the default sink is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default output device as last read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioState {
+    /// Human-readable device name (e.g., "Built-in Audio Analog Stereo")
+    pub device: String,
+    /// Volume in percent, averaged over the channels (may exceed 100)
+    pub volume: u32,
+    /// Output is muted
+    pub muted: bool,
+}
+
+/// Polls the default output device on a background thread.
+pub struct AudioMonitor {
+    /// Poll only while the section is shown
+    enabled: Arc<AtomicBool>,
+    /// Latest state (None until the first successful poll)
+    state: Arc<Mutex<Option<AudioState>>>,
+    /// Why the last poll failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+    /// Stamped by the poll thread after every poll
+    heartbeat: Heartbeat,
+}
+
+impl AudioMonitor {
+    /// Create the monitor and start its poll thread.
+    pub fn new(enabled: bool) -> Self {
+        let enabled = Arc::new(AtomicBool::new(enabled));
+        let state = Arc::new(Mutex::new(None));
+        let error = Arc::new(Mutex::new(None));
+        let heartbeat = Heartbeat::new();
+
+        let thread_enabled = Arc::clone(&enabled);
+        let thread_state = Arc::clone(&state);
+        let thread_error = Arc::clone(&error);
+        let thread_heartbeat = heartbeat.clone();
+        std::thread::spawn(move || {
+            let _exit_guard = thread_heartbeat.guard();
+            super::priority::enter_background("audio");
+            loop {
+                // Stop once the monitor was dropped (e.g., restarted by the watchdog)
+                if Arc::strong_count(&thread_enabled) == 1 {
+                    break;
+                }
+                if thread_enabled.load(Ordering::Relaxed) {
+                    store(poll(), &thread_state, &thread_error);
+                }
+                thread_heartbeat.beat();
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        Self {
+            enabled,
+            state,
+            error,
+            heartbeat,
+        }
+    }
+
+    /// Start or stop polling (e.g., after the section was toggled).
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Heartbeat of the poll thread.
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+
+    /// Latest state of the default output device.
+    pub fn state(&self) -> Option<AudioState> {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Whether a first poll succeeded.
+    pub fn has_sample(&self) -> bool {
+        self.state.lock().unwrap().is_some()
+    }
+
+    /// Why the last poll failed, if it did.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Change the volume by `steps` scroll steps of [`VOLUME_STEP`]
+    /// (positive is louder).
+    pub fn change_volume(&self, steps: i32) -> bool {
+        let Some(current) = self.state().map(|state| state.volume) else {
+            return false;
+        };
+        let target = step_volume(current, steps);
+        if target == current {
+            return false;
+        }
+        self.run_action(&["set-sink-volume", "@DEFAULT_SINK@", &format!("{}%", target)])
+    }
+
+    /// Mute or unmute the default output device.
+    pub fn toggle_mute(&self) -> bool {
+        self.run_action(&["set-sink-mute", "@DEFAULT_SINK@", "toggle"])
+    }
+
+    /// Run a pactl command and refresh the state on success.
+    fn run_action(&self, args: &[&str]) -> bool {
+        match Command::new("pactl").args(args).bounded_output(exec::QUICK_TIMEOUT) {
+            Ok(output) if output.status.success() => {
+                store(poll(), &self.state, &self.error);
+                true
+            }
+            Ok(output) => {
+                log::warn!("pactl {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+                false
+            }
+            Err(e) => {
+                log::warn!("Failed to run pactl: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Store a poll result, keeping the last good state on failure.
+fn store(result: Result<AudioState, String>, state: &Mutex<Option<AudioState>>, error: &Mutex<Option<String>>) {
+    match result {
+        Ok(new_state) => {
+            *state.lock().unwrap() = Some(new_state);
+            *error.lock().unwrap() = None;
+        }
+        Err(e) => {
+            log::debug!("Audio poll failed: {}", e);
+            *error.lock().unwrap() = Some(e);
+        }
+    }
+}
+
+/// Read the default sink's state.
+fn poll() -> Result<AudioState, String> {
+    let default_sink = pactl(&["get-default-sink"])?;
+    let sinks = pactl(&["--format=json", "list", "sinks"])?;
+    parse_sink(&sinks, default_sink.trim()).ok_or_else(|| format!("Default output '{}' not found", default_sink.trim()))
+}
+
+/// Run pactl and return its standard output.
+fn pactl(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("pactl")
+        .args(args)
+        .bounded_output(exec::QUICK_TIMEOUT)
+        .map_err(|e| format!("pactl not available: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("pactl failed: {}", stderr.lines().next().unwrap_or_default().trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Find sink `name` in `pactl --format=json list sinks` output.
+fn parse_sink(json: &str, name: &str) -> Option<AudioState> {
+    let sinks: serde_json::Value = serde_json::from_str(json).ok()?;
+    let sink = sinks.as_array()?.iter().find(|sink| sink["name"] == name)?;
+
+    // Channels report e.g. "value_percent": "45%"
+    let percents: Vec<u32> = sink["volume"]
+        .as_object()?
+        .values()
+        .filter_map(|channel| channel["value_percent"].as_str()?.trim_end_matches('%').trim().parse().ok())
+        .collect();
+    if percents.is_empty() {
+        return None;
+    }
+    let volume = (percents.iter().sum::<u32>() as f32 / percents.len() as f32).round() as u32;
+
+    let device = sink["description"].as_str().filter(|d| !d.is_empty()).unwrap_or(name).to_string();
+    Some(AudioState {
+        device,
+        volume,
+        muted: sink["mute"].as_bool().unwrap_or(false),
+    })
+}
+
+/// Volume after `steps` scroll steps, in percent.
+///
+/// Stops at 100% going up, or stays where it is if already boosted above.
+pub fn step_volume(current: u32, steps: i32) -> u32 {
+    let target = current as i64 + steps as i64 * VOLUME_STEP as i64;
+    if steps > 0 {
+        target.min(current.max(100) as i64) as u32
+    } else {
+        target.max(0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sink() {
+        let json = r#"[
+            {"name": "hdmi", "description": "HDMI", "mute": false,
+             "volume": {"front-left": {"value": 65536, "value_percent": "100%"}}},
+            {"name": "alsa_output.analog", "description": "Built-in Audio", "mute": true,
+             "volume": {"front-left": {"value_percent": "40%"}, "front-right": {"value_percent": "45%"}}}
+        ]"#;
+        assert_eq!(
+            parse_sink(json, "alsa_output.analog"),
+            Some(AudioState { device: String::from("Built-in Audio"), volume: 43, muted: true })
+        );
+        assert_eq!(parse_sink(json, "usb"), None);
+        assert_eq!(parse_sink("not json", "hdmi"), None);
+    }
+
+    #[test]
+    fn test_step_volume() {
+        assert_eq!(step_volume(40, 1), 45);
+        assert_eq!(step_volume(97, 1), 100);
+        assert_eq!(step_volume(120, 2), 120);
+        assert_eq!(step_volume(120, -1), 115);
+        assert_eq!(step_volume(3, -1), 0);
+    }
+}
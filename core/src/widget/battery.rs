@@ -39,12 +39,14 @@
 //! ## Error Handling
 //!
 //! All external tool failures are silently ignored to maintain stability:
-//! - Tool not installed → empty device list
+//! - No tool installed → cached devices kept, error exposed via `error()`
 //! - Parse failure → keep previous snapshot
 //! - Device disconnected → device shows as not connected
 
 use std::process::Command;
+use super::exec::{self, CommandExt};
 use std::sync::{Arc, Mutex};
+use super::watchdog::Heartbeat;
 use std::time::{Duration, Instant};
 
 // ============================================================================
@@ -111,6 +113,10 @@ pub struct BatteryMonitor {
     refresh_interval: Duration,
     /// Flag to signal background thread that an update is needed
     update_requested: Arc<Mutex<bool>>,
+    /// Last query error, set by background thread (None = OK)
+    error: Arc<Mutex<Option<String>>>,
+    /// Stamped by the background thread after every check (for the watchdog)
+    heartbeat: Heartbeat,
 }
 
 impl BatteryMonitor {
@@ -151,38 +157,49 @@ impl BatteryMonitor {
         
         let devices = Arc::new(Mutex::new(cached_devices));
         let update_requested = Arc::new(Mutex::new(true)); // Request initial update immediately
+        let error = Arc::new(Mutex::new(None));
         
         // Spawn background thread for battery updates
         // This avoids blocking the main render loop on slow CLI tools
         let devices_clone = Arc::clone(&devices);
         let update_requested_clone = Arc::clone(&update_requested);
+        let error_clone = Arc::clone(&error);
+        let heartbeat = Heartbeat::new();
+        let heartbeat_clone = heartbeat.clone();
         
         std::thread::spawn(move || {
+            let _exit_guard = heartbeat_clone.guard();
+            super::priority::enter_background("battery");
             let mut is_first_update = true;
             
             // Perform immediate first update on startup
             match query_solaar() {
                 Ok(new_devices) => {
                     *devices_clone.lock().unwrap() = new_devices.clone();
+                    *error_clone.lock().unwrap() = None;
                     
                     // Update cache after first successful update
                     if is_first_update && !new_devices.is_empty() {
-                        let mut cache = super::cache::WidgetCache::load();
-                        cache.update_battery_devices(&new_devices);
+                        super::cache::WidgetCache::update_battery_devices(&new_devices);
                         is_first_update = false;
                     }
                 }
-                Err(_) => {
+                Err(e) => {
                     // On error, keep cached data - tool may not be installed
+                    *error_clone.lock().unwrap() = Some(e);
                 }
             }
             
             // Clear the initial update request flag
             *update_requested_clone.lock().unwrap() = false;
+            heartbeat_clone.beat();
             
             // Main background loop - check for update requests every 5 seconds
             loop {
                 std::thread::sleep(Duration::from_secs(5));
+                if heartbeat_clone.stopped() {
+                    break;
+                }
                 
                 // Check if update is needed (atomic check-and-clear)
                 let requested = {
@@ -199,19 +216,21 @@ impl BatteryMonitor {
                     match query_solaar() {
                         Ok(new_devices) => {
                             *devices_clone.lock().unwrap() = new_devices.clone();
+                            *error_clone.lock().unwrap() = None;
                             
                             // Update cache after first successful update
                             if is_first_update && !new_devices.is_empty() {
-                                let mut cache = super::cache::WidgetCache::load();
-                                cache.update_battery_devices(&new_devices);
+                                super::cache::WidgetCache::update_battery_devices(&new_devices);
                                 is_first_update = false;
                             }
                         }
-                        Err(_) => {
+                        Err(e) => {
                             // On error, keep previous data
+                            *error_clone.lock().unwrap() = Some(e);
                         }
                     }
                 }
+                heartbeat_clone.beat();
             }
         });
             
@@ -220,8 +239,15 @@ impl BatteryMonitor {
             last_update,
             refresh_interval: Duration::from_secs(30),
             update_requested,
+            error,
+            heartbeat,
         }
     }
+    
+    /// Heartbeat of the background query thread (for the watchdog).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
 
     /// Get current snapshot of battery devices.
     ///
@@ -231,6 +257,14 @@ impl BatteryMonitor {
         self.devices.lock().unwrap().clone()
     }
 
+    /// Error from the last battery query, if any.
+    ///
+    /// Currently only reported when neither Solaar nor HeadsetControl is
+    /// installed; individual device failures show as disconnected instead.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
     /// Request a battery update if refresh interval has elapsed.
     ///
     /// This is rate-limited to once per 30 seconds. The actual update runs
@@ -254,6 +288,12 @@ impl BatteryMonitor {
     }
 }
 
+impl Drop for BatteryMonitor {
+    fn drop(&mut self) {
+        self.heartbeat.stop();
+    }
+}
+
 // ============================================================================
 // External Tool Query Functions
 // ============================================================================
@@ -267,9 +307,11 @@ impl BatteryMonitor {
 ///
 /// # Returns
 ///
-/// Combined list of all discovered devices, or empty list on failure.
+/// Combined list of all discovered devices (empty if none are connected),
+/// or an error if neither tool could be run.
 fn query_solaar() -> Result<Vec<BatteryDevice>, String> {
     let mut all_devices = Vec::new();
+    let mut tool_found = false;
     
     // ========================================================================
     // Solaar Query (Logitech devices)
@@ -277,7 +319,7 @@ fn query_solaar() -> Result<Vec<BatteryDevice>, String> {
     
     // Try JSON output if available (newer Solaar versions)
     // JSON is more reliable and structured than text output
-    if let Ok(output) = Command::new("solaar").arg("show").arg("--json").output() {
+    if let Some(output) = run_tool(Command::new("solaar").arg("show").arg("--json"), &mut tool_found) {
         if output.status.success() {
             if let Ok(text) = String::from_utf8(output.stdout) {
                 if let Ok(devices) = parse_solaar_json(&text) {
@@ -290,7 +332,7 @@ fn query_solaar() -> Result<Vec<BatteryDevice>, String> {
     // Fallback: plain-text `solaar show` if JSON didn't give us devices
     // Older Solaar versions don't support JSON output
     if all_devices.is_empty() {
-        if let Ok(output) = Command::new("solaar").arg("show").output() {
+        if let Some(output) = run_tool(Command::new("solaar").arg("show"), &mut tool_found) {
             if output.status.success() {
                 if let Ok(text) = String::from_utf8(output.stdout) {
                     all_devices.extend(parse_solaar_text(&text));
@@ -305,7 +347,7 @@ fn query_solaar() -> Result<Vec<BatteryDevice>, String> {
     
     // HeadsetControl supports many gaming headset brands
     // -b: battery only, -o json: JSON output format
-    if let Ok(output) = Command::new("headsetcontrol").arg("-b").arg("-o").arg("json").output() {
+    if let Some(output) = run_tool(Command::new("headsetcontrol").arg("-b").arg("-o").arg("json"), &mut tool_found) {
         if output.status.success() {
             if let Ok(text) = String::from_utf8(output.stdout) {
                 if let Ok(headset_devices) = parse_headsetcontrol_json(&text) {
@@ -315,9 +357,32 @@ fn query_solaar() -> Result<Vec<BatteryDevice>, String> {
         }
     }
     
+    if !tool_found {
+        return Err(String::from("Neither solaar nor headsetcontrol is installed"));
+    }
+    
     Ok(all_devices)
 }
 
+/// Run a battery tool with the slow-tool timeout.
+///
+/// Sets `tool_found` unless the tool isn't installed, so a tool that hung
+/// and was killed isn't reported as missing.
+fn run_tool(command: &mut Command, tool_found: &mut bool) -> Option<std::process::Output> {
+    match command.bounded_output(exec::SLOW_TIMEOUT) {
+        Ok(output) => {
+            *tool_found = true;
+            Some(output)
+        }
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                *tool_found = true;
+            }
+            None
+        }
+    }
+}
+
 // ============================================================================
 // Solaar JSON Parsing
 // ============================================================================
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Persistent Cache for Widget Data
+//!
+//! This module provides a JSON-based caching mechanism for device information
+//! discovered by the widget. The cache serves two purposes:
+//!
+//! 1. **Quick startup**: Display cached data immediately while fresh data loads
+//! 2. **Settings integration**: The settings app reads this cache to show device
+//!    lists without needing direct hardware access
+//!
+//! # Cache Location
+//!
+//! The cache is stored at `~/.cache/cosmic-monitor-applet/widget_cache.json`
+//!
+//! # Data Stored
+//!
+//! - **Disk information**: Name and mount point of discovered disks
+//! - **Battery devices**: Name and type of discovered battery sources
+//! - **Weather**: Last successful weather fetch and the location it was for,
+//!   shown on startup (if the location still matches) until the first fresh
+//!   fetch completes
+//! - **Outputs**: Connected screens and their layout, drawn by the settings
+//!   app's position picker
+//!
+//! # Thread Safety
+//!
+//! Several widget threads (storage, battery, weather, outputs) and the
+//! settings app each update their own part of the cache. Every write goes
+//! through [`WidgetCache::modify`], which reloads the file, applies the
+//! change and saves it while holding an exclusive `flock` on
+//! `widget_cache.lock`, so no writer overwrites another's change with a
+//! stale copy. Saves go to a temporary file that is renamed over the cache,
+//! so readers never see a half-written file.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
+
+// ============================================================================
+// Cache Data Structures
+// ============================================================================
+
+/// Cached information about a mounted disk.
+///
+/// This is a simplified version of `DiskInfo` for serialization.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CachedDiskInfo {
+    /// Disk device name (e.g., "nvme0n1p2")
+    pub name: String,
+    /// Mount point path (e.g., "/home")
+    pub mount_point: String,
+}
+
+/// Cached information about a battery device.
+///
+/// Includes both system batteries and Solaar-managed Bluetooth devices.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CachedBatteryDevice {
+    /// Device name (e.g., "MX Master 3" or "BAT0")
+    pub name: String,
+    /// Device type if known (e.g., "Mouse", "Keyboard", None for system battery)
+    pub kind: Option<String>,
+}
+
+/// Cached information about a connected output (screen).
+///
+/// Position and size are in logical (scaled) pixels of the compositor's
+/// global space, the same units as the widget margins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CachedOutput {
+    /// Connector name (e.g., "DP-1", "eDP-1")
+    pub name: String,
+    /// Human-readable description (e.g., "Dell Inc. U2720Q"), may be empty
+    #[serde(default)]
+    pub description: String,
+    /// Left edge in the global space
+    pub x: i32,
+    /// Top edge in the global space
+    pub y: i32,
+    /// Logical width
+    pub width: i32,
+    /// Logical height
+    pub height: i32,
+}
+
+/// Main cache structure containing all cached device information.
+///
+/// Serialized to JSON and stored in the user's cache directory.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WidgetCache {
+    /// All discovered mounted disks
+    pub disks: Vec<CachedDiskInfo>,
+    /// All discovered battery sources
+    pub battery_devices: Vec<CachedBatteryDevice>,
+    /// Last successfully fetched weather (includes its fetch timestamp)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather: Option<super::weather::WeatherData>,
+    /// Location query `weather` was fetched for
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub weather_location: String,
+    /// Connected outputs, as last seen by the widget
+    #[serde(default)]
+    pub outputs: Vec<CachedOutput>,
+}
+
+// ============================================================================
+// Cache Operations
+// ============================================================================
+
+impl WidgetCache {
+    /// Returns the path to the cache file.
+    ///
+    /// Creates the parent directory if it doesn't exist.
+    /// Falls back to `/tmp` if cache directory cannot be determined.
+    fn cache_path() -> PathBuf {
+        let mut path = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        path.push("cosmic-monitor-applet");
+        fs::create_dir_all(&path).ok();
+        path.push("widget_cache.json");
+        path
+    }
+
+    /// Load the cache from disk.
+    ///
+    /// Returns `Default` if the file doesn't exist or cannot be parsed.
+    /// This ensures the widget always has a valid cache to work with.
+    pub fn load() -> Self {
+        let path = Self::cache_path();
+        if let Ok(content) = fs::read_to_string(&path) {
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Save the cache to disk.
+    ///
+    /// Uses pretty-printed JSON for easier debugging. Writes a temporary
+    /// file and renames it over the cache, so readers see either the old
+    /// or the new cache. Silently ignores write errors (cache is
+    /// non-critical).
+    fn save(&self) {
+        let path = Self::cache_path();
+        let temp_path = path.with_extension("json.tmp");
+        let Ok(json) = serde_json::to_string_pretty(self) else {
+            return;
+        };
+        if fs::write(&temp_path, json).is_ok() {
+            fs::rename(&temp_path, &path).ok();
+        }
+    }
+
+    /// Load the cache, apply `change` and save it, under the cache lock.
+    ///
+    /// The only way to write the cache, so that concurrent writers (widget
+    /// threads, the settings app) each change the latest version.
+    pub fn modify(change: impl FnOnce(&mut Self)) {
+        let _lock = CacheLock::acquire();
+        let mut cache = Self::load();
+        change(&mut cache);
+        cache.save();
+    }
+
+    /// The cached weather, if it was fetched for `location`.
+    pub fn weather_for(self, location: &str) -> Option<super::weather::WeatherData> {
+        self.weather.filter(|_| self.weather_location == location)
+    }
+
+    /// Update cached disk information from fresh data.
+    ///
+    /// Replaces all cached disks and saves immediately.
+    pub fn update_disks(disks: &[super::storage::DiskInfo]) {
+        let disks = disks
+            .iter()
+            .map(|d| CachedDiskInfo {
+                name: d.name.clone(),
+                mount_point: d.mount_point.clone(),
+            })
+            .collect();
+        Self::modify(|cache| cache.disks = disks);
+    }
+
+    /// Update cached battery device information from fresh data.
+    ///
+    /// Replaces all cached devices and saves immediately.
+    pub fn update_battery_devices(devices: &[super::battery::BatteryDevice]) {
+        let devices = devices
+            .iter()
+            .map(|d| CachedBatteryDevice {
+                name: d.name.clone(),
+                kind: d.kind.clone(),
+            })
+            .collect();
+        Self::modify(|cache| cache.battery_devices = devices);
+    }
+
+    /// Update the cached weather data from a successful fetch for `location`.
+    ///
+    /// Replaces the previous entry and saves immediately.
+    pub fn update_weather(location: &str, data: &super::weather::WeatherData) {
+        Self::modify(|cache| {
+            cache.weather = Some(data.clone());
+            cache.weather_location = location.to_string();
+        });
+    }
+
+    /// Update the cached output layout.
+    ///
+    /// Saves only if the layout changed, since outputs report their
+    /// properties one event at a time.
+    pub fn update_outputs(outputs: Vec<CachedOutput>) {
+        if Self::load().outputs != outputs {
+            Self::modify(|cache| cache.outputs = outputs);
+        }
+    }
+}
+
+/// Exclusive lock on the cache, released when dropped.
+///
+/// `flock` locks belong to the open file, so this excludes other threads of
+/// the same process as well as other processes.
+struct CacheLock {
+    _file: File,
+}
+
+impl CacheLock {
+    /// Wait for the lock. Returns `None` if the lock file can't be used, in
+    /// which case the cache is written unlocked (it is non-critical).
+    fn acquire() -> Option<Self> {
+        let path = WidgetCache::cache_path().with_extension("lock");
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(path).ok()?;
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return None;
+        }
+        Some(Self { _file: file })
+    }
+}
@@ -0,0 +1,800 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Calendar Events
+//!
+//! Upcoming appointments shown as small rows under the date, read from one
+//! of two sources ([`CalendarSource`]):
+//!
+//! - **ICS file**: a local `.ics` file (an export, or a file kept in sync
+//!   by a tool like vdirsyncer), parsed here.
+//! - **Evolution Data Server**: the calendars set up in Evolution or GNOME
+//!   Online Accounts, queried over D-Bus with `busctl`.
+//!
+//! ```text
+//! .ics file ──read──────────┐
+//!                           ├──► VEVENTs ──expand RRULEs──► events() ◄── draw (next N rows)
+//! EDS ──busctl GetObjectList┘   refresher thread, every calendar_refresh_minutes
+//! ```
+//!
+//! Only the parts of iCalendar needed for a list of upcoming events are
+//! understood: `DTSTART`, `DTEND`/`DURATION` (dates, UTC, `TZID` and
+//! floating times), `SUMMARY`, `STATUS:CANCELLED`, `EXDATE`,
+//! `RECURRENCE-ID`, and `RRULE`s with `FREQ`, `INTERVAL`, `COUNT`, `UNTIL`
+//! and weekly `BYDAY`. Other `BY*` parts are ignored, so such rules repeat
+//! on the start's weekday or day of the month.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, Local, Locale, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde_json::Value;
+
+use super::exec::{CommandExt, QUICK_TIMEOUT, SLOW_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::{CalendarSource, Config};
+use crate::fl;
+
+/// How far ahead events are looked up.
+const LOOKAHEAD_DAYS: i64 = 30;
+
+/// Most occurrences expanded per recurring event (a daily event started
+/// years ago still reaches today).
+const MAX_OCCURRENCES: usize = 100_000;
+
+/// EDS service listing the configured sources (accounts, calendars, ...).
+const EDS_SOURCES: &str = "org.gnome.evolution.dataserver.Sources5";
+const EDS_SOURCE_MANAGER_PATH: &str = "/org/gnome/evolution/dataserver/SourceManager";
+const EDS_SOURCE_INTERFACE: &str = "org.gnome.evolution.dataserver.Source";
+
+/// EDS service opening calendar backends.
+const EDS_CALENDAR_FACTORY: &str = "org.gnome.evolution.dataserver.Calendar8";
+const EDS_CALENDAR_FACTORY_PATH: &str = "/org/gnome/evolution/dataserver/CalendarFactory";
+const EDS_CALENDAR_FACTORY_INTERFACE: &str = "org.gnome.evolution.dataserver.CalendarFactory";
+const EDS_CALENDAR_INTERFACE: &str = "org.gnome.evolution.dataserver.Calendar";
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// One occurrence of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    /// Event summary
+    pub title: String,
+    /// Start of this occurrence
+    pub start: DateTime<Local>,
+    /// End of this occurrence (the next midnight for all-day events)
+    pub end: DateTime<Local>,
+    /// Date-only event without a time
+    pub all_day: bool,
+}
+
+/// An event formatted for drawing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRow {
+    /// Day and time ("Now", "Today 14:00", "Tomorrow", "Fri 09:30")
+    pub when: String,
+    /// Event summary
+    pub title: String,
+}
+
+/// Rows for the next `max` events that haven't ended at `now`.
+///
+/// Day and month names are in `locale`, the other labels in the UI language.
+pub fn event_rows(
+    events: &[CalendarEvent],
+    now: DateTime<Local>,
+    use_24hour_time: bool,
+    locale: Locale,
+    max: usize,
+) -> Vec<EventRow> {
+    events
+        .iter()
+        .filter(|event| event.end > now)
+        .take(max)
+        .map(|event| EventRow {
+            when: when_label(event, now, use_24hour_time, locale),
+            title: if event.title.is_empty() {
+                fl!("calendar-untitled")
+            } else {
+                event.title.clone()
+            },
+        })
+        .collect()
+}
+
+/// Day (and time) an event starts, relative to `now`.
+fn when_label(event: &CalendarEvent, now: DateTime<Local>, use_24hour_time: bool, locale: Locale) -> String {
+    if !event.all_day && event.start <= now {
+        return fl!("calendar-now");
+    }
+    let days = (event.start.date_naive() - now.date_naive()).num_days();
+    let day = match days {
+        ..=0 => fl!("calendar-today"),
+        1 => fl!("calendar-tomorrow"),
+        2..=6 => event.start.format_localized("%a", locale).to_string(),
+        _ => event.start.format_localized("%-d %b", locale).to_string(),
+    };
+    if event.all_day {
+        day
+    } else if use_24hour_time {
+        format!("{} {}", day, event.start.format("%H:%M"))
+    } else {
+        format!("{} {}", day, event.start.format("%-I:%M %p"))
+    }
+}
+
+// ============================================================================
+// Monitor
+// ============================================================================
+
+/// What the refresher reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarSettings {
+    /// ICS file or Evolution Data Server
+    pub source: CalendarSource,
+    /// Path of the `.ics` file (`~/` is expanded)
+    pub ics_path: String,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the events are hidden).
+pub fn active_settings(config: &Config) -> Option<CalendarSettings> {
+    config.show_calendar.then(|| CalendarSettings {
+        source: config.calendar_source,
+        ics_path: config.calendar_ics_path.clone(),
+        refresh_minutes: config.calendar_refresh_minutes,
+    })
+}
+
+/// Refreshes upcoming events on a background thread.
+pub type CalendarMonitor = Refresher<CalendarSettings, Vec<CalendarEvent>>;
+
+impl RefreshSettings for CalendarSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl CalendarMonitor {
+    /// Start reading events from the configured source.
+    pub fn new(settings: Option<CalendarSettings>) -> Self {
+        Refresher::spawn("calendar", settings, |settings: &CalendarSettings| {
+            match fetch(settings, Local::now()) {
+                Ok(events) => {
+                    log::debug!("Calendar refreshed: {} upcoming events", events.len());
+                    (Some(events), None)
+                }
+                // Keep showing the events of the last good refresh
+                Err(e) => {
+                    log::debug!("Calendar refresh failed: {}", e);
+                    (None, Some(e))
+                }
+            }
+        })
+    }
+
+    /// Events of the last refresh, sorted by start.
+    ///
+    /// Events that ended since are still included; see [`event_rows`].
+    pub fn events(&self) -> Vec<CalendarEvent> {
+        self.value().unwrap_or_default()
+    }
+}
+
+/// Read the events from `now` until the lookahead ends.
+fn fetch(settings: &CalendarSettings, now: DateTime<Local>) -> Result<Vec<CalendarEvent>, String> {
+    let until = now + TimeDelta::days(LOOKAHEAD_DAYS);
+    let text = match settings.source {
+        CalendarSource::IcsFile => read_ics(&settings.ics_path)?,
+        CalendarSource::EvolutionDataServer => eds_objects(now, until)?,
+    };
+    Ok(upcoming_events(&text, now, until))
+}
+
+/// Contents of the configured `.ics` file.
+fn read_ics(path: &str) -> Result<String, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(String::from("no calendar file set"));
+    }
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    };
+    fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// ============================================================================
+// Evolution Data Server
+// ============================================================================
+
+/// VEVENTs of every enabled EDS calendar between `from` and `until`.
+///
+/// Calendars that fail to open are skipped; an error is only returned if
+/// none could be read.
+fn eds_objects(from: DateTime<Local>, until: DateTime<Local>) -> Result<String, String> {
+    let sources = busctl(
+        &[EDS_SOURCES, EDS_SOURCE_MANAGER_PATH, "org.freedesktop.DBus.ObjectManager", "GetManagedObjects"],
+        QUICK_TIMEOUT,
+    )
+    .map_err(|e| format!("Evolution Data Server not available ({})", e))?;
+
+    let query = format!(
+        "(occur-in-time-range? (make-time \"{}\") (make-time \"{}\"))",
+        from.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ"),
+        until.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ"),
+    );
+    let mut objects = String::new();
+    let mut read = 0;
+    let mut last_error = None;
+    for uid in calendar_uids(&sources) {
+        match eds_calendar_objects(&uid, &query) {
+            Ok(list) => {
+                read += 1;
+                for object in list {
+                    objects.push_str(&object);
+                    objects.push('\n');
+                }
+            }
+            Err(e) => {
+                log::debug!("Skipping EDS calendar {}: {}", uid, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    match last_error {
+        Some(e) if read == 0 => Err(e),
+        _ => Ok(objects),
+    }
+}
+
+/// VEVENTs of one EDS calendar matching `query`.
+fn eds_calendar_objects(uid: &str, query: &str) -> Result<Vec<String>, String> {
+    let reply = busctl(
+        &[EDS_CALENDAR_FACTORY, EDS_CALENDAR_FACTORY_PATH, EDS_CALENDAR_FACTORY_INTERFACE, "OpenCalendar", "s", uid],
+        SLOW_TIMEOUT,
+    )?;
+    let [Value::String(path), Value::String(bus), ..] = reply.as_slice() else {
+        return Err(String::from("unexpected OpenCalendar reply"));
+    };
+
+    busctl(&[bus, path, EDS_CALENDAR_INTERFACE, "Open"], SLOW_TIMEOUT)?;
+    let reply = busctl(&[bus, path, EDS_CALENDAR_INTERFACE, "GetObjectList", "s", query], SLOW_TIMEOUT)?;
+    Ok(reply
+        .first()
+        .and_then(Value::as_array)
+        .map(|objects| objects.iter().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default())
+}
+
+/// UIDs of the enabled calendar sources in a `GetManagedObjects` reply.
+///
+/// Every source carries its settings as a key file in its `Data` property;
+/// calendars have a `[Calendar]` group.
+fn calendar_uids(reply: &[Value]) -> Vec<String> {
+    let Some(objects) = reply.first().and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    objects
+        .values()
+        .filter_map(|interfaces| interfaces.get(EDS_SOURCE_INTERFACE))
+        .filter_map(|properties| {
+            let uid = properties.pointer("/UID/data")?.as_str()?;
+            let data = properties.pointer("/Data/data")?.as_str()?;
+            let calendar = data.lines().any(|line| line.trim() == "[Calendar]");
+            let enabled = key_file_value(data, "Data Source", "Enabled") != Some("false");
+            let selected = key_file_value(data, "Calendar", "Selected") != Some("false");
+            (calendar && enabled && selected).then(|| uid.to_string())
+        })
+        .collect()
+}
+
+/// Value of `key` in the `[group]` of a key file.
+fn key_file_value<'a>(data: &'a str, group: &str, key: &str) -> Option<&'a str> {
+    let mut in_group = false;
+    for line in data.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            in_group = name == group;
+        } else if let Some((_, value)) = line.split_once('=').filter(|(name, _)| in_group && name.trim() == key) {
+            return Some(value.trim());
+        }
+    }
+    None
+}
+
+/// `busctl --user call`, returning the arguments of the reply.
+fn busctl(args: &[&str], timeout: Duration) -> Result<Vec<Value>, String> {
+    let output = Command::new("busctl")
+        .args(["--user", "--json=short", "call"])
+        .args(args)
+        .bounded_output(timeout)
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("busctl failed").trim().to_string());
+    }
+    // Methods without a return value print nothing
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Vec::new());
+    }
+    let reply: Value = serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    Ok(reply.get("data").and_then(Value::as_array).cloned().unwrap_or_default())
+}
+
+// ============================================================================
+// iCalendar
+// ============================================================================
+
+/// Time zone of an iCalendar time.
+#[derive(Debug, Clone, Copy)]
+enum IcsZone {
+    /// `...Z`
+    Utc,
+    /// `TZID=` with a known IANA name
+    Named(Tz),
+    /// No zone (or an unknown `TZID`): the local time zone
+    Floating,
+}
+
+/// A `DTSTART`/`DTEND`/`EXDATE` value before it is placed on the timeline.
+///
+/// Recurrences step the wall-clock time, so a weekly 09:00 meeting stays
+/// at 09:00 across DST changes.
+#[derive(Debug, Clone, Copy)]
+struct IcsTime {
+    /// Wall-clock time (midnight for dates)
+    naive: NaiveDateTime,
+    zone: IcsZone,
+    /// `VALUE=DATE`
+    all_day: bool,
+}
+
+impl IcsTime {
+    /// Parse a value with the parameters of its property line.
+    fn parse(head: &str, value: &str) -> Option<Self> {
+        let value = value.trim();
+        let is_date = param(head, "VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE"));
+        if is_date || value.len() == 8 {
+            let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+            return Some(Self {
+                naive: date.and_hms_opt(0, 0, 0)?,
+                zone: IcsZone::Floating,
+                all_day: true,
+            });
+        }
+
+        let (digits, utc) = match value.strip_suffix('Z') {
+            Some(digits) => (digits, true),
+            None => (value, false),
+        };
+        let zone = if utc {
+            IcsZone::Utc
+        } else {
+            param(head, "TZID").and_then(parse_tzid).map_or(IcsZone::Floating, IcsZone::Named)
+        };
+        Some(Self {
+            naive: NaiveDateTime::parse_from_str(digits, "%Y%m%dT%H%M%S").ok()?,
+            zone,
+            all_day: false,
+        })
+    }
+
+    /// The instant this time refers to (`None` inside a DST gap).
+    fn resolve(&self) -> Option<DateTime<Local>> {
+        match self.zone {
+            IcsZone::Utc => Some(Utc.from_utc_datetime(&self.naive).with_timezone(&Local)),
+            IcsZone::Named(tz) => tz.from_local_datetime(&self.naive).earliest().map(|t| t.with_timezone(&Local)),
+            IcsZone::Floating => Local.from_local_datetime(&self.naive).earliest(),
+        }
+    }
+}
+
+/// IANA zone of a `TZID`.
+///
+/// Evolution prefixes zones (`/freeassociation.sourceforge.net/Europe/Berlin`),
+/// so every suffix after a `/` is tried as well.
+fn parse_tzid(tzid: &str) -> Option<Tz> {
+    std::iter::once(tzid)
+        .chain(tzid.match_indices('/').map(|(index, _)| &tzid[index + 1..]))
+        .find_map(|name| name.parse().ok())
+}
+
+/// How often a recurring event repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed `RRULE`.
+#[derive(Debug, Clone)]
+struct RRule {
+    frequency: Frequency,
+    /// Periods between occurrences
+    interval: u32,
+    /// Total number of occurrences
+    count: Option<usize>,
+    /// Last possible start (inclusive)
+    until: Option<DateTime<Local>>,
+    /// Weekly `BYDAY` days, Monday first
+    weekdays: Vec<Weekday>,
+}
+
+impl RRule {
+    /// Rule of an event that doesn't repeat.
+    fn once() -> Self {
+        Self {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: Some(1),
+            until: None,
+            weekdays: Vec::new(),
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut rule = Self {
+            count: None,
+            ..Self::once()
+        };
+        let mut frequency = None;
+        for (key, value) in value.split(';').filter_map(|part| part.split_once('=')) {
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Some(Frequency::Daily),
+                        "WEEKLY" => Some(Frequency::Weekly),
+                        "MONTHLY" => Some(Frequency::Monthly),
+                        "YEARLY" => Some(Frequency::Yearly),
+                        _ => None,
+                    }
+                }
+                "INTERVAL" => rule.interval = value.parse().unwrap_or(1).max(1),
+                "COUNT" => rule.count = value.parse().ok(),
+                "UNTIL" => rule.until = IcsTime::parse("UNTIL", value).and_then(|t| t.resolve()),
+                "BYDAY" => {
+                    // Ordinals like "1MO" only matter for monthly rules, which ignore BYDAY
+                    rule.weekdays = value
+                        .split(',')
+                        .filter_map(|day| parse_weekday(day.get(day.len().saturating_sub(2)..)?))
+                        .collect();
+                    rule.weekdays.sort_by_key(Weekday::num_days_from_monday);
+                    rule.weekdays.dedup();
+                }
+                _ => {}
+            }
+        }
+        rule.frequency = frequency?;
+        if rule.frequency != Frequency::Weekly {
+            rule.weekdays.clear();
+        }
+        Some(rule)
+    }
+
+    /// Start times in order, beginning with `start`.
+    fn occurrences(&self, start: NaiveDateTime) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        (0u32..)
+            .map_while(move |n| self.period(start, n))
+            .flatten()
+            .filter(move |time| *time >= start)
+    }
+
+    /// Start times in the `n`th period (day, week, month or year) of the rule.
+    fn period(&self, start: NaiveDateTime, n: u32) -> Option<Vec<NaiveDateTime>> {
+        let steps = n.checked_mul(self.interval)?;
+        Some(match self.frequency {
+            Frequency::Daily => vec![start.checked_add_days(Days::new(steps.into()))?],
+            Frequency::Weekly if self.weekdays.is_empty() => {
+                vec![start.checked_add_days(Days::new(u64::from(steps) * 7))?]
+            }
+            Frequency::Weekly => {
+                let monday = start.checked_sub_days(Days::new(start.weekday().num_days_from_monday().into()))?;
+                let week = monday.checked_add_days(Days::new(u64::from(steps) * 7))?;
+                self.weekdays
+                    .iter()
+                    .filter_map(|day| week.checked_add_days(Days::new(day.num_days_from_monday().into())))
+                    .collect()
+            }
+            Frequency::Monthly => vec![start.checked_add_months(Months::new(steps))?],
+            Frequency::Yearly => vec![start.checked_add_months(Months::new(steps.checked_mul(12)?))?],
+        })
+    }
+}
+
+/// Weekday of a `BYDAY` code (`MO`, `TU`, ...).
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code.to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// A `VEVENT` as read from the calendar.
+#[derive(Debug, Default)]
+struct VEvent {
+    uid: String,
+    summary: String,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+    duration: Option<TimeDelta>,
+    rrule: Option<String>,
+    exdates: Vec<IcsTime>,
+    /// Set on a changed (or cancelled) instance of a recurring event
+    recurrence_id: Option<IcsTime>,
+    cancelled: bool,
+}
+
+impl VEvent {
+    /// Length of every occurrence.
+    fn length(&self, start: &IcsTime) -> TimeDelta {
+        let end = self.end.as_ref().and_then(IcsTime::resolve);
+        match (start.resolve(), end) {
+            (Some(start), Some(end)) if end > start => end - start,
+            _ => self.duration.unwrap_or(if start.all_day {
+                TimeDelta::days(1)
+            } else {
+                TimeDelta::zero()
+            }),
+        }
+    }
+}
+
+/// Occurrences of the events in `text` that haven't ended at `from` and
+/// start before `until`, sorted by start.
+fn upcoming_events(text: &str, from: DateTime<Local>, until: DateTime<Local>) -> Vec<CalendarEvent> {
+    let events = parse_events(text);
+
+    // Instances of recurring events that were changed or cancelled, per UID
+    let mut overridden: HashMap<&str, Vec<DateTime<Local>>> = HashMap::new();
+    for event in &events {
+        if let Some(id) = event.recurrence_id.as_ref().and_then(IcsTime::resolve) {
+            overridden.entry(event.uid.as_str()).or_default().push(id);
+        }
+    }
+
+    let mut upcoming = Vec::new();
+    for event in events.iter().filter(|event| !event.cancelled) {
+        let Some(start) = event.start else {
+            continue;
+        };
+        let mut excluded: Vec<DateTime<Local>> = event.exdates.iter().filter_map(IcsTime::resolve).collect();
+        let rule = match (&event.rrule, &event.recurrence_id) {
+            (Some(rrule), None) => {
+                excluded.extend(overridden.get(event.uid.as_str()).into_iter().flatten());
+                RRule::parse(rrule).unwrap_or_else(RRule::once)
+            }
+            _ => RRule::once(),
+        };
+        let length = event.length(&start);
+
+        for (index, naive) in rule.occurrences(start.naive).enumerate().take(MAX_OCCURRENCES) {
+            if rule.count.is_some_and(|count| index >= count) {
+                break;
+            }
+            let Some(begin) = IcsTime { naive, ..start }.resolve() else {
+                continue;
+            };
+            if begin >= until || rule.until.is_some_and(|last| begin > last) {
+                break;
+            }
+            if begin + length > from && !excluded.contains(&begin) {
+                upcoming.push(CalendarEvent {
+                    title: event.summary.clone(),
+                    start: begin,
+                    end: begin + length,
+                    all_day: start.all_day,
+                });
+            }
+        }
+    }
+
+    upcoming.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.title.cmp(&b.title)));
+    upcoming
+}
+
+/// Read every `VEVENT` (properties of nested components like `VALARM`
+/// are skipped).
+fn parse_events(text: &str) -> Vec<VEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<VEvent> = None;
+    let mut nested = 0;
+
+    for line in unfold(text) {
+        let Some((head, value)) = split_property(&line) else {
+            continue;
+        };
+        let name = head.split(';').next().unwrap_or_default().to_ascii_uppercase();
+        let Some(event) = current.as_mut() else {
+            if name == "BEGIN" && value.eq_ignore_ascii_case("VEVENT") {
+                current = Some(VEvent::default());
+                nested = 0;
+            }
+            continue;
+        };
+
+        match name.as_str() {
+            "BEGIN" => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            "END" => events.extend(current.take()),
+            _ if nested > 0 => {}
+            "UID" => event.uid = value.to_string(),
+            "SUMMARY" => event.summary = unescape(value),
+            "DTSTART" => event.start = IcsTime::parse(head, value),
+            "DTEND" => event.end = IcsTime::parse(head, value),
+            "DURATION" => event.duration = parse_duration(value),
+            "RRULE" => event.rrule = Some(value.to_string()),
+            "EXDATE" => event.exdates.extend(value.split(',').filter_map(|v| IcsTime::parse(head, v))),
+            "RECURRENCE-ID" => event.recurrence_id = IcsTime::parse(head, value),
+            "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Join folded lines (continuations start with a space or tab).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Split a content line into name with parameters, and value.
+fn split_property(line: &str) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => return Some((&line[..index], &line[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Value of a property parameter (`TZID`, `VALUE`).
+fn param<'a>(head: &'a str, key: &str) -> Option<&'a str> {
+    head.split(';')
+        .skip(1)
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.trim_matches('"'))
+}
+
+/// Undo iCalendar text escaping (newlines become spaces).
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push(' '),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text
+}
+
+/// A `DURATION` value (`PT1H30M`, `P1D`, `P2W`).
+fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let value = value.trim().trim_start_matches('+').strip_prefix('P')?;
+    let mut seconds = 0i64;
+    let mut number = String::new();
+    for c in value.chars() {
+        let unit = match c {
+            '0'..='9' => {
+                number.push(c);
+                continue;
+            }
+            'T' => continue,
+            'W' => 7 * 86_400,
+            'D' => 86_400,
+            'H' => 3_600,
+            'M' => 60,
+            'S' => 1,
+            _ => return None,
+        };
+        seconds += number.parse::<i64>().ok()? * unit;
+        number.clear();
+    }
+    Some(TimeDelta::seconds(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTART:20240101T090000Z\r
+DTEND:20240101T091500Z\r
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE\r
+EXDATE:20240117T090000Z\r
+SUMMARY:Stand\\, up\r
+  meeting\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:standup\r
+RECURRENCE-ID:20240122T090000Z\r
+DTSTART:20240122T100000Z\r
+DURATION:PT15M\r
+SUMMARY:Moved stand-up\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:holiday\r
+DTSTART;VALUE=DATE:20240116\r
+SUMMARY:Holiday\r
+BEGIN:VALARM\r
+SUMMARY:Reminder\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:dentist\r
+DTSTART;TZID=/freeassociation.sourceforge.net/Europe/Berlin:20240118T150000\r
+STATUS:CANCELLED\r
+SUMMARY:Dentist\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn test_upcoming_events() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 15, 8, 0, 0).unwrap().with_timezone(&Local);
+        let events = upcoming_events(CALENDAR, from, from + TimeDelta::days(10));
+
+        let titles: Vec<&str> = events.iter().map(|event| event.title.as_str()).collect();
+        assert_eq!(titles, ["Stand, up meeting", "Holiday", "Moved stand-up", "Stand, up meeting"]);
+        assert_eq!(events[0].start, Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap());
+        assert_eq!(events[0].end - events[0].start, TimeDelta::minutes(15));
+        assert!(events[1].all_day);
+        assert_eq!(events[3].start, Utc.with_ymd_and_hms(2024, 1, 24, 9, 0, 0).unwrap());
+        assert_eq!(parse_tzid("/freeassociation.sourceforge.net/Europe/Berlin"), Some(Tz::Europe__Berlin));
+    }
+
+    #[test]
+    fn test_event_rows() {
+        let at = |day, hour, minute| Local.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap();
+        let event = |title: &str, start, end, all_day| CalendarEvent {
+            title: title.to_string(),
+            start,
+            end,
+            all_day,
+        };
+        let events = [
+            event("Over", at(15, 10, 0), at(15, 11, 0), false),
+            event("Review", at(15, 11, 30), at(15, 12, 30), false),
+            event("Gym", at(16, 9, 0), at(16, 10, 0), false),
+            event("Trip", at(18, 0, 0), at(19, 0, 0), true),
+            event("Later", at(30, 9, 0), at(30, 10, 0), false),
+        ];
+        let rows = event_rows(&events, at(15, 12, 0), true, Locale::POSIX, 3);
+
+        let rows: Vec<(&str, &str)> = rows.iter().map(|row| (row.when.as_str(), row.title.as_str())).collect();
+        assert_eq!(rows, [("Now", "Review"), ("Tomorrow 09:00", "Gym"), ("Thu", "Trip")]);
+    }
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Clipboard
+//!
+//! Copies the plain-text stats summary to the Wayland clipboard, for pasting
+//! the system state into bug reports. Requested from the applet's popup menu
+//! or with `cosmic-monitor-widget --copy-stats`:
+//!
+//! ```text
+//! applet "Copy Stats" ─────────────────────────────► StatsSnapshot::to_plain()
+//! --copy-stats ──"copy-stats\n"──► control pipe ──► widget's last sample
+//!                                                          │ stdin
+//!                                                          ▼
+//!                                                  wl-copy (forks, serves the selection)
+//! ```
+//!
+//! Offering a selection means answering paste requests until another client
+//! takes the clipboard over, which a layer-shell surface without keyboard
+//! focus can't do through `wl_data_device`. `wl-copy` (wl-clipboard) stays
+//! behind in the background for that, using the data-control protocol where
+//! the compositor offers it.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Copy `text` to the clipboard on a background thread.
+pub fn copy_text(text: String) {
+    std::thread::spawn(move || match run_wl_copy(&text) {
+        Ok(()) => log::info!("Copied {} bytes of stats to the clipboard", text.len()),
+        Err(e) => log::warn!("Failed to copy stats to the clipboard: {}", e),
+    });
+}
+
+/// Hand `text` to `wl-copy` and wait for it to fork into the background.
+fn run_wl_copy(text: &str) -> Result<(), String> {
+    // The forked child keeps stdout/stderr open until the selection is
+    // replaced, so nothing is piped back from it
+    let child = Command::new("wl-copy")
+        .args(["--type", "text/plain;charset=utf-8"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(String::from("wl-copy not installed")),
+        Err(e) => return Err(e.to_string()),
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    match child.wait() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("wl-copy exited with {}", status)),
+        Err(e) => Err(e.to_string()),
+    }
+}
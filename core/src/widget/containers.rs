@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Containers
+//!
+//! Running Docker and Podman containers, and optionally the busiest one by
+//! CPU, checked on a long interval:
+//!
+//! ```text
+//! podman ps ──┐
+//! docker ps ──┴──► 3 running (nginx, postgres, redis)  ◄── hover lists them
+//! <engine> stats --no-stream ──► busiest: postgres 12.3%
+//!            refresher thread, every containers_refresh_minutes
+//! ```
+//!
+//! Both engines are asked through their command line tools, which talk to
+//! the engine's socket for us (rootless Podman runs per user, so no socket
+//! setup is needed). Containers listed by both, as with podman-docker's
+//! `docker` shim, are counted once. An engine that isn't installed is
+//! skipped; one that is installed but refuses the connection (e.g., the user
+//! isn't in the `docker` group) shows as the section's error.
+//!
+//! `stats --no-stream` samples for a moment before answering, so it only
+//! runs with the busiest container shown.
+
+use std::collections::HashSet;
+use std::io;
+use std::process::Command;
+
+use super::exec::{CommandExt, SLOW_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::Config;
+
+/// Engines asked, in order.
+const ENGINES: [&str; 2] = ["podman", "docker"];
+
+/// Length of the short container IDs both engines print.
+const SHORT_ID_LEN: usize = 12;
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerSettings {
+    /// Also find the busiest container (runs `stats`)
+    pub busiest: bool,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn container_settings(config: &Config) -> Option<ContainerSettings> {
+    config.show_containers.then_some(ContainerSettings {
+        busiest: config.containers_show_busiest,
+        refresh_minutes: config.containers_refresh_minutes,
+    })
+}
+
+/// The container using the most CPU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusiestContainer {
+    /// Container name
+    pub name: String,
+    /// CPU usage in percent of one core
+    pub cpu_percent: f32,
+}
+
+/// Result of one refresh.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerReport {
+    /// Names of the running containers
+    pub running: Vec<String>,
+    /// Busiest container (None while not asked for, or none is running)
+    pub busiest: Option<BusiestContainer>,
+}
+
+/// Refreshes the container report on a background thread.
+pub type ContainerMonitor = Refresher<ContainerSettings, ContainerReport>;
+
+impl RefreshSettings for ContainerSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl ContainerMonitor {
+    /// Start asking the container engines.
+    pub fn new(settings: Option<ContainerSettings>) -> Self {
+        Refresher::spawn("containers", settings, |settings: &ContainerSettings| {
+            let (report, error) = refresh(settings);
+            log::debug!(
+                "Containers refreshed: {} running, busiest {:?}",
+                report.running.len(),
+                report.busiest
+            );
+            (Some(report), error)
+        })
+    }
+
+    /// Report of the last refresh.
+    pub fn report(&self) -> Option<ContainerReport> {
+        self.value()
+    }
+}
+
+/// Ask every installed engine, collecting what went wrong.
+fn refresh(settings: &ContainerSettings) -> (ContainerReport, Option<String>) {
+    let mut report = ContainerReport::default();
+    let mut seen = HashSet::new();
+    let mut errors = Vec::new();
+
+    for engine in ENGINES {
+        let output = match run(engine, &["ps", "--format", "{{.ID}}\t{{.Names}}"]) {
+            Ok(Some(output)) => output,
+            Ok(None) => continue,
+            Err(e) => {
+                errors.push(format!("{}: {}", engine, e));
+                continue;
+            }
+        };
+        let mut listed = false;
+        for (id, name) in parse_ps(&output) {
+            if seen.insert(id) {
+                report.running.push(name);
+                listed = true;
+            }
+        }
+
+        if settings.busiest && listed {
+            match run(engine, &["stats", "--no-stream", "--format", "{{.Name}}\t{{.CPUPerc}}"]) {
+                Ok(Some(output)) => {
+                    for busiest in parse_stats(&output) {
+                        if report.busiest.as_ref().is_none_or(|current| busiest.cpu_percent > current.cpu_percent) {
+                            report.busiest = Some(busiest);
+                        }
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => errors.push(format!("{} stats: {}", engine, e)),
+            }
+        }
+    }
+
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    (report, error)
+}
+
+/// Output of `<engine> <args>`, `None` if the engine isn't installed.
+fn run(engine: &str, args: &[&str]) -> Result<Option<String>, String> {
+    let output = match Command::new(engine).args(args).bounded_output(SLOW_TIMEOUT) {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("failed").trim().to_string());
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// Short ID and name of each container in `ps --format '{{.ID}}\t{{.Names}}'`
+/// output (IDs cut to the 12 characters Docker prints).
+fn parse_ps(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, names) = line.split_once('\t')?;
+            let id = id.trim();
+            let name = names.split(',').next()?.trim();
+            if id.is_empty() || name.is_empty() {
+                return None;
+            }
+            Some((id.chars().take(SHORT_ID_LEN).collect(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Containers of `stats --format '{{.Name}}\t{{.CPUPerc}}'` output
+/// ("postgres\t12.34%").
+fn parse_stats(output: &str) -> Vec<BusiestContainer> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, cpu) = line.split_once('\t')?;
+            let cpu_percent = cpu.trim().trim_end_matches('%').parse().ok()?;
+            Some(BusiestContainer { name: name.trim().to_string(), cpu_percent })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ps_and_stats() {
+        let ps = "3f4e8a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f\tnginx\n9a8b7c6d5e4f\tpostgres,db\n\n";
+        assert_eq!(parse_ps(ps), [
+            (String::from("3f4e8a1b2c3d"), String::from("nginx")),
+            (String::from("9a8b7c6d5e4f"), String::from("postgres")),
+        ]);
+
+        let stats = parse_stats("nginx\t0.15%\npostgres\t12.34%\nbroken\t--\n");
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1], BusiestContainer { name: String::from("postgres"), cpu_percent: 12.34 });
+    }
+}
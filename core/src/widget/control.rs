@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Runtime Control Pipe
+//!
+//! Lets other processes show, hide, or toggle the running widget (or its
+//! debug overlay) without killing it. The widget listens on a named pipe (FIFO) and the
+//! `cosmic-monitor-widget --toggle` CLI mode writes a command into it, so a
+//! COSMIC custom keyboard shortcut can be bound to that command.
+//!
+//! # Pipe Location
+//!
+//! `$XDG_RUNTIME_DIR/cosmic-monitor-widget.fifo`
+//! (falls back to `/tmp/cosmic-monitor-widget-<uid>.fifo`)
+//!
+//! # Protocol
+//!
+//! One command per line: `toggle`, `show`, `hide`, `debug-overlay`,
+//! `clear-notifications` (sent by settings when the notification history
+//! is cleared), or `copy-stats`.
+//! Unknown lines are logged and ignored.
+//!
+//! ```text
+//! cosmic-monitor-widget --toggle ──"toggle\n"──► FIFO ──► listener thread
+//!                                                              │ mpsc
+//!                                                              ▼
+//!                                               main loop: unmap/recreate surface
+//! ```
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, MetadataExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// A command sent to the running widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// Hide the widget if shown, show it if hidden
+    Toggle,
+    /// Show the widget (no-op if already shown)
+    Show,
+    /// Hide the widget (no-op if already hidden)
+    Hide,
+    /// Show the debug overlay if hidden, hide it if shown (not saved)
+    DebugOverlay,
+    /// Dismiss all notifications (and empty the notification history)
+    ClearNotifications,
+    /// Copy a plain-text summary of the current metrics to the clipboard
+    CopyStats,
+}
+
+impl ControlCommand {
+    /// Wire representation written to the pipe.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ControlCommand::Toggle => "toggle",
+            ControlCommand::Show => "show",
+            ControlCommand::Hide => "hide",
+            ControlCommand::DebugOverlay => "debug-overlay",
+            ControlCommand::ClearNotifications => "clear-notifications",
+            ControlCommand::CopyStats => "copy-stats",
+        }
+    }
+
+    /// Parse a command from a pipe line.
+    pub fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "toggle" => Some(ControlCommand::Toggle),
+            "show" => Some(ControlCommand::Show),
+            "hide" => Some(ControlCommand::Hide),
+            "debug-overlay" => Some(ControlCommand::DebugOverlay),
+            "clear-notifications" => Some(ControlCommand::ClearNotifications),
+            "copy-stats" => Some(ControlCommand::CopyStats),
+            _ => None,
+        }
+    }
+
+    /// Parse a command from a CLI flag (e.g., `--toggle`).
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        flag.strip_prefix("--").and_then(Self::parse)
+    }
+}
+
+/// Path of the control pipe.
+pub fn control_path() -> PathBuf {
+    runtime_path("cosmic-monitor-widget", "fifo")
+}
+
+/// Per-user runtime file path: `$XDG_RUNTIME_DIR/<stem>.<ext>`.
+///
+/// Falls back to `/tmp/<stem>-<uid>.<ext>` when `XDG_RUNTIME_DIR` is unset.
+/// Also used for the published stats file (see [`super::stats`]).
+pub fn runtime_path(stem: &str, ext: &str) -> PathBuf {
+    match dirs::runtime_dir() {
+        Some(mut path) => {
+            path.push(format!("{}.{}", stem, ext));
+            path
+        }
+        None => {
+            let uid = unsafe { libc::getuid() };
+            PathBuf::from(format!("/tmp/{}-{}.{}", stem, uid, ext))
+        }
+    }
+}
+
+// ============================================================================
+// Listener (widget side)
+// ============================================================================
+
+/// Receives control commands from the pipe on a background thread.
+pub struct ControlListener {
+    /// Commands parsed by the listener thread
+    receiver: Receiver<ControlCommand>,
+}
+
+impl ControlListener {
+    /// Create the pipe (if needed) and start listening.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FIFO cannot be created, e.g. because a
+    /// non-FIFO file occupies the path and cannot be removed.
+    pub fn start() -> std::io::Result<Self> {
+        let path = control_path();
+        create_fifo(&path)?;
+
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            loop {
+                // Opening a FIFO for reading blocks until a writer connects
+                let file = match OpenOptions::new().read(true).open(&path) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        log::error!("Failed to open control pipe {}: {}", path.display(), e);
+                        return;
+                    }
+                };
+
+                // Reads until the writer closes, then reopen for the next one
+                for line in BufReader::new(file).lines().map_while(Result::ok) {
+                    match ControlCommand::parse(&line) {
+                        Some(command) => {
+                            log::info!("Control command received: {}", command.as_str());
+                            if sender.send(command).is_err() {
+                                // Widget is shutting down
+                                return;
+                            }
+                        }
+                        None => log::warn!("Ignoring unknown control command: {:?}", line),
+                    }
+                }
+            }
+        });
+
+        log::info!("Listening for control commands on {}", control_path().display());
+        Ok(Self { receiver })
+    }
+
+    /// Next pending command, if any (non-blocking).
+    pub fn try_recv(&self) -> Option<ControlCommand> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Create the FIFO at `path`, replacing any stale file.
+///
+/// An existing FIFO is only reused if it is ours and private (mode 0600):
+/// in the shared `/tmp` fallback another user could have created it to
+/// read our commands or send us theirs. Anything else is removed first,
+/// which fails (so no listener starts) if it belongs to someone else.
+fn create_fifo(path: &Path) -> std::io::Result<()> {
+    if let Ok(metadata) = fs::symlink_metadata(path) {
+        let owned = metadata.uid() == unsafe { libc::getuid() };
+        if metadata.file_type().is_fifo() && owned && metadata.mode() & 0o777 == 0o600 {
+            return Ok(());
+        }
+        fs::remove_file(path)?;
+    }
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Sender (CLI side)
+// ============================================================================
+
+/// Send a command to the running widget.
+///
+/// The pipe is opened non-blocking, so this fails immediately with
+/// `ENXIO` instead of hanging when no widget is listening.
+///
+/// # Errors
+///
+/// - `NotFound`: the widget has never run in this session
+/// - `ENXIO` (raw OS error): no widget is currently listening
+pub fn send_command(command: ControlCommand) -> std::io::Result<()> {
+    let mut pipe = OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(control_path())?;
+    writeln!(pipe, "{}", command.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_create_fifo_replaces_shared_pipe() {
+        let path = std::env::temp_dir().join(format!(
+            "cosmic-monitor-control-test-{}.fifo",
+            std::process::id()
+        ));
+        create_fifo(&path).unwrap();
+        let created = fs::symlink_metadata(&path).unwrap();
+        assert!(created.file_type().is_fifo());
+
+        // Reused while private
+        create_fifo(&path).unwrap();
+        assert_eq!(fs::symlink_metadata(&path).unwrap().ino(), created.ino());
+
+        // Recreated once others can write to it
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o622)).unwrap();
+        create_fifo(&path).unwrap();
+        let recreated = fs::symlink_metadata(&path).unwrap();
+        assert!(recreated.file_type().is_fifo());
+        assert_eq!(recreated.mode() & 0o777, 0o600);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
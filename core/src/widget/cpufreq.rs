@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! CPU Frequency and Governor
+//!
+//! Current clock speed of each CPU package and the active cpufreq governor,
+//! read from sysfs on every utilization update:
+//!
+//! | What              | Source                                                        |
+//! |-------------------|---------------------------------------------------------------|
+//! | Frequency         | `/sys/devices/system/cpu/cpu<N>/cpufreq/scaling_cur_freq`     |
+//! | Package           | `/sys/devices/system/cpu/cpu<N>/topology/physical_package_id` |
+//! | Governor          | `/sys/devices/system/cpu/cpufreq/policy<N>/scaling_governor`  |
+//!
+//! A package's frequency is the average over its online cores, so a
+//! dual-socket machine shows two values and everything else one.
+//!
+//! [`cycle_governor`] switches every policy to the next available governor.
+//! Writing the sysfs files needs root, so it goes through `pkexec tee`,
+//! which asks for authentication with the desktop's polkit agent. That can
+//! take as long as the user needs, so it runs on its own thread instead of
+//! through [`super::exec`]'s timeouts.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Where cpufreq and topology live.
+const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Frequencies and governor as last read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuFrequency {
+    /// Average frequency of each package in MHz, by package id
+    pub packages: Vec<f32>,
+    /// Active governor of the first policy (None without cpufreq)
+    pub governor: Option<String>,
+    /// Governors the driver offers
+    pub available_governors: Vec<String>,
+}
+
+impl CpuFrequency {
+    /// Read frequencies and the governor (None without cpufreq, e.g. in
+    /// most virtual machines).
+    pub fn read() -> Option<Self> {
+        let mut samples = Vec::new();
+        for entry in std::fs::read_dir(CPU_ROOT).ok()?.flatten() {
+            let name = entry.file_name();
+            let Some(index) = name.to_str().and_then(|name| name.strip_prefix("cpu")) else {
+                continue;
+            };
+            if !index.chars().all(|c| c.is_ascii_digit()) || index.is_empty() {
+                continue;
+            }
+            let path = entry.path();
+            // Offline cores have no cpufreq directory
+            let Some(khz) = read_number(&path.join("cpufreq/scaling_cur_freq")) else {
+                continue;
+            };
+            let package = read_number(&path.join("topology/physical_package_id")).unwrap_or(0);
+            samples.push((package, khz));
+        }
+        if samples.is_empty() {
+            return None;
+        }
+
+        let policy = first_policy();
+        let read_policy = |file: &str| {
+            let text = std::fs::read_to_string(policy.as_ref()?.join(file)).ok()?;
+            Some(text.trim().to_string())
+        };
+        Some(Self {
+            packages: package_averages(&samples),
+            governor: read_policy("scaling_governor"),
+            available_governors: read_policy("scaling_available_governors")
+                .map(|text| text.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The governor a click switches to, if there is another one.
+    pub fn next_governor(&self) -> Option<&str> {
+        next_governor(self.governor.as_deref()?, &self.available_governors)
+    }
+}
+
+/// Format a frequency in MHz as GHz ("3.42 GHz").
+pub fn format_ghz(mhz: f32) -> String {
+    format!("{:.2} GHz", mhz / 1000.0)
+}
+
+/// Switch every cpufreq policy to `governor` through pkexec, on a
+/// background thread.
+///
+/// # Returns
+///
+/// Whether the switch was started (`governor` is a plain governor name)
+pub fn cycle_governor(governor: &str) -> bool {
+    // Only ever pass plain names on to a root process
+    if governor.is_empty() || !governor.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        log::warn!("Refusing to set unexpected governor name {:?}", governor);
+        return false;
+    }
+    let files = governor_files();
+    if files.is_empty() {
+        return false;
+    }
+
+    let governor = governor.to_string();
+    std::thread::spawn(move || {
+        log::info!("Switching CPU governor to {}", governor);
+        let child = Command::new("pkexec")
+            .arg("tee")
+            .args(&files)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                log::warn!("Failed to run pkexec: {}", e);
+                return;
+            }
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            if let Err(e) = writeln!(stdin, "{}", governor) {
+                log::warn!("Failed to pass the governor to tee: {}", e);
+            }
+        }
+        match child.wait_with_output() {
+            Ok(output) if output.status.success() => log::info!("CPU governor set to {}", governor),
+            // 126: authentication dismissed, 127: not authorized
+            Ok(output) => log::warn!(
+                "Switching the CPU governor failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => log::warn!("Failed to wait for pkexec: {}", e),
+        }
+    });
+    true
+}
+
+/// Average frequency per package in MHz, from (package id, kHz) samples.
+fn package_averages(samples: &[(u32, u32)]) -> Vec<f32> {
+    let mut packages: BTreeMap<u32, (u64, u32)> = BTreeMap::new();
+    for (package, khz) in samples {
+        let (sum, count) = packages.entry(*package).or_default();
+        *sum += u64::from(*khz);
+        *count += 1;
+    }
+    packages
+        .values()
+        .map(|(sum, count)| *sum as f32 / *count as f32 / 1000.0)
+        .collect()
+}
+
+/// Governor after `current` in `available`, wrapping around.
+fn next_governor<'a>(current: &str, available: &'a [String]) -> Option<&'a str> {
+    let position = available.iter().position(|governor| governor == current);
+    let next = match position {
+        Some(index) => &available[(index + 1) % available.len()],
+        None => available.first()?,
+    };
+    (next != current).then_some(next.as_str())
+}
+
+/// The cpufreq policy directories, sorted (policy0 first).
+fn policies() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(Path::new(CPU_ROOT).join("cpufreq")) else {
+        return Vec::new();
+    };
+    let mut policies: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let index = entry.file_name().to_str()?.strip_prefix("policy")?.parse().ok()?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    policies.sort();
+    policies.into_iter().map(|(_, path)| path).collect()
+}
+
+/// The first cpufreq policy directory.
+fn first_policy() -> Option<PathBuf> {
+    policies().into_iter().next()
+}
+
+/// `scaling_governor` of every policy.
+fn governor_files() -> Vec<PathBuf> {
+    policies()
+        .into_iter()
+        .map(|policy| policy.join("scaling_governor"))
+        .filter(|file| file.exists())
+        .collect()
+}
+
+/// Read a sysfs file holding one number.
+fn read_number(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_averages() {
+        let samples = [(0, 3_000_000), (1, 1_200_000), (0, 4_000_000), (1, 1_000_000)];
+        assert_eq!(package_averages(&samples), [3500.0, 1100.0]);
+        assert_eq!(format_ghz(3456.0), "3.46 GHz");
+    }
+
+    #[test]
+    fn test_next_governor() {
+        let available: Vec<String> = ["performance", "powersave"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(next_governor("performance", &available), Some("powersave"));
+        assert_eq!(next_governor("powersave", &available), Some("performance"));
+        assert_eq!(next_governor("schedutil", &available), Some("performance"));
+        assert_eq!(next_governor("powersave", &available[1..]), None);
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Custom Command Monitoring
+//!
+//! Runs the user's [`CustomCommand`]s on a background thread and keeps the
+//! latest output of each, so arbitrary metrics (`uptime -p`, a script that
+//! counts pending updates, ...) can be shown without code changes.
+//!
+//! ```text
+//! config.custom_commands ──set_commands()──► runner thread (1s tick)
+//!                                                 │ due? sh -c <command>
+//!                                                 ▼
+//!                          outputs() ◄── Arc<Mutex<HashMap<command, CommandOutput>>>
+//! ```
+//!
+//! Every command has its own interval. Commands go through
+//! [`super::exec::CommandExt::bounded_output`], so a hanging script is
+//! killed after [`COMMAND_TIMEOUT`] and never blocks the other commands for
+//! longer than that.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::exec::CommandExt;
+use super::watchdog::Heartbeat;
+use crate::config::{Config, CustomCommand};
+
+/// Longest a single command may run before it is killed.
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the runner checks for due commands.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Latest result of one command.
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    /// Row label from the config
+    pub label: String,
+    /// Formatted row text (empty until the first run finished)
+    pub text: String,
+    /// Why the last run failed, if it did
+    pub error: Option<String>,
+}
+
+/// Runs custom commands on their intervals.
+pub struct CustomCommandMonitor {
+    /// Commands to run (empty while the section is hidden)
+    commands: Arc<Mutex<Vec<CustomCommand>>>,
+    /// Latest result per command string
+    outputs: Arc<Mutex<HashMap<String, CommandOutput>>>,
+    /// Stamped by the runner thread after every tick
+    heartbeat: Heartbeat,
+}
+
+impl CustomCommandMonitor {
+    /// Create the monitor and start its runner thread.
+    pub fn new(commands: Vec<CustomCommand>) -> Self {
+        let commands = Arc::new(Mutex::new(commands));
+        let outputs = Arc::new(Mutex::new(HashMap::new()));
+        let heartbeat = Heartbeat::new();
+
+        let thread_commands = Arc::clone(&commands);
+        let thread_outputs = Arc::clone(&outputs);
+        let thread_heartbeat = heartbeat.clone();
+        std::thread::spawn(move || {
+            let _exit_guard = thread_heartbeat.guard();
+            super::priority::enter_background("custom-commands");
+            let mut last_run: HashMap<String, Instant> = HashMap::new();
+            loop {
+                // Stop once the monitor was dropped (e.g., restarted by the watchdog)
+                if Arc::strong_count(&thread_commands) == 1 {
+                    break;
+                }
+                let commands = thread_commands.lock().unwrap().clone();
+
+                // Forget commands that were removed from the config
+                last_run.retain(|command, _| commands.iter().any(|c| &c.command == command));
+                thread_outputs
+                    .lock()
+                    .unwrap()
+                    .retain(|command, _| commands.iter().any(|c| &c.command == command));
+
+                for custom in commands.iter().filter(|c| !c.command.trim().is_empty()) {
+                    let interval = Duration::from_secs(custom.interval_secs.max(1) as u64);
+                    let due = last_run.get(&custom.command).is_none_or(|last| last.elapsed() >= interval);
+                    if !due {
+                        continue;
+                    }
+                    last_run.insert(custom.command.clone(), Instant::now());
+
+                    let output = run(custom);
+                    thread_outputs.lock().unwrap().insert(custom.command.clone(), output);
+                }
+
+                thread_heartbeat.beat();
+                std::thread::sleep(TICK);
+            }
+        });
+
+        Self {
+            commands,
+            outputs,
+            heartbeat,
+        }
+    }
+
+    /// Replace the commands to run (e.g., after a config change).
+    ///
+    /// Pass an empty list to stop running commands while the section is hidden.
+    pub fn set_commands(&self, commands: Vec<CustomCommand>) {
+        *self.commands.lock().unwrap() = commands;
+    }
+
+    /// Heartbeat of the runner thread.
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+
+    /// Latest output of every configured command, in config order.
+    ///
+    /// Commands that haven't finished their first run have empty text.
+    pub fn outputs(&self) -> Vec<CommandOutput> {
+        let outputs = self.outputs.lock().unwrap();
+        self.commands
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|custom| {
+                let mut output = outputs.get(&custom.command).cloned().unwrap_or_default();
+                output.label = custom.label.clone();
+                output
+            })
+            .collect()
+    }
+
+    /// Error to badge the section with: the first failing command, if any.
+    pub fn error(&self) -> Option<String> {
+        self.outputs()
+            .into_iter()
+            .find_map(|output| output.error.map(|e| format!("{}: {}", output.label, e)))
+    }
+}
+
+/// Commands that should run for a configuration (none while the section is hidden).
+pub fn active_commands(config: &Config) -> Vec<CustomCommand> {
+    if config.show_custom_commands {
+        config.custom_commands.clone()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Run one command and format its output.
+fn run(custom: &CustomCommand) -> CommandOutput {
+    let result = Command::new("sh")
+        .args(["-c", &custom.command])
+        .bounded_output(COMMAND_TIMEOUT);
+
+    match result {
+        Ok(output) if output.status.success() => CommandOutput {
+            label: custom.label.clone(),
+            text: format_output(&custom.format, &String::from_utf8_lossy(&output.stdout)),
+            error: None,
+        },
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().next().map(str::trim).filter(|l| !l.is_empty());
+            let message = match (output.status.code(), reason) {
+                (Some(code), Some(reason)) => format!("exit code {} ({})", code, reason),
+                (Some(code), None) => format!("exit code {}", code),
+                (None, _) => String::from("killed by a signal"),
+            };
+            log::debug!("Custom command '{}' failed: {}", custom.label, message);
+            CommandOutput {
+                label: custom.label.clone(),
+                text: String::new(),
+                error: Some(message),
+            }
+        }
+        Err(e) => CommandOutput {
+            label: custom.label.clone(),
+            text: String::new(),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Build the row text from the first line of the command's output.
+///
+/// # Arguments
+///
+/// * `format` - Row format with `{}` placeholders; empty shows the output as is
+/// * `stdout` - Raw command output
+fn format_output(format: &str, stdout: &str) -> String {
+    let value = stdout.trim().lines().next().unwrap_or_default().trim();
+    if format.is_empty() {
+        value.to_string()
+    } else {
+        format.replace("{}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_output() {
+        assert_eq!(format_output("{}", "  up 3 hours\n"), "up 3 hours");
+        assert_eq!(format_output("{} pending", "12\nsecond line\n"), "12 pending");
+        assert_eq!(format_output("", "\n\nvalue\n"), "value");
+    }
+}
@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Debug Overlay
+//!
+//! A small panel in the bottom-left corner of the widget with the numbers
+//! that matter for performance reports:
+//!
+//! ```text
+//! ┌────────────────────────────┐
+//! │ frame     16.7 ms (60 fps) │  time since the previous frame
+//! │ draw       2.3 ms          │  Cairo time of the previous frame
+//! │ buffer  370×812 (1.1 MiB)  │  shm buffer size
+//! │ sampler      4 ms          │  tick → snapshot received
+//! │ rss     38.2 MiB           │  widget process resident memory
+//! └────────────────────────────┘
+//! ```
+//!
+//! Shown with `show_debug_overlay` in the config, or toggled at runtime
+//! with `cosmic-monitor-widget --debug-overlay` (see [`super::control`]).
+//! Frame and draw times are those of the previous frame, since the overlay
+//! is part of the frame being drawn.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Timings and sizes shown by the overlay.
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    /// When the previous frame was drawn
+    last_frame: Option<Instant>,
+    /// Time between the two previous frames
+    frame_interval: Option<Duration>,
+    /// Cairo time of the previous frame
+    draw_duration: Option<Duration>,
+    /// Size of the shm buffer (width, height, bytes)
+    buffer: Option<(i32, i32, usize)>,
+    /// Delay between the latest sample tick and its arrival in the render loop
+    sampler_latency: Option<Duration>,
+    /// Resident memory of the widget process, refreshed with every sample
+    rss_bytes: Option<u64>,
+}
+
+impl DebugStats {
+    /// Record a frame that was drawn at `started` and took `draw` in Cairo.
+    pub fn record_frame(&mut self, started: Instant, draw: Duration, buffer: (i32, i32, usize)) {
+        if let Some(last) = self.last_frame {
+            self.frame_interval = Some(started.saturating_duration_since(last));
+        }
+        self.last_frame = Some(started);
+        self.draw_duration = Some(draw);
+        self.buffer = Some(buffer);
+    }
+
+    /// Record the arrival of the sample taken on the tick at `sample_ms`
+    /// (Unix time in milliseconds) and refresh the process memory.
+    pub fn record_sample(&mut self, sample_ms: u64) {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_millis() as u64)
+            .unwrap_or(sample_ms);
+        self.sampler_latency = Some(Duration::from_millis(now_ms.saturating_sub(sample_ms)));
+        self.rss_bytes = process_rss();
+    }
+
+    /// Overlay text, one entry per line.
+    pub fn lines(&self) -> Vec<String> {
+        let frame = self
+            .frame_interval
+            .filter(|interval| !interval.is_zero())
+            .map(|interval| format!("{:>6.1} ms ({:.0} fps)", millis(interval), 1.0 / interval.as_secs_f64()));
+        let draw = self.draw_duration.map(|draw| format!("{:>6.1} ms", millis(draw)));
+        let buffer = self
+            .buffer
+            .map(|(width, height, bytes)| format!("{}×{} ({})", width, height, format_bytes(bytes as u64)));
+        let sampler = self.sampler_latency.map(|latency| format!("{:>6.0} ms", millis(latency)));
+        let rss = self.rss_bytes.map(format_bytes);
+        [("frame", frame), ("draw", draw), ("buffer", buffer), ("sampler", sampler), ("rss", rss)]
+            .into_iter()
+            .map(|(label, value)| format!("{:<8}{}", label, value.unwrap_or_else(|| String::from("--"))))
+            .collect()
+    }
+}
+
+/// Duration in (fractional) milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Bytes in KiB or MiB, whichever reads better.
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.0} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Resident memory of this process, from `/proc/self/statm`.
+fn process_rss() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    parse_statm_rss(&statm, u64::try_from(page_size).ok()?)
+}
+
+/// Resident bytes in a `/proc/<pid>/statm` line (`size resident shared ...`, in pages).
+fn parse_statm_rss(statm: &str, page_size: u64) -> Option<u64> {
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_lines() {
+        assert_eq!(parse_statm_rss("45678 9782 4321 12 0 30123 0\n", 4096), Some(9782 * 4096));
+        assert_eq!(parse_statm_rss("", 4096), None);
+
+        let mut stats = DebugStats::default();
+        assert_eq!(stats.lines()[0], "frame   --");
+
+        let start = Instant::now();
+        stats.record_frame(start, Duration::from_micros(2300), (370, 812, 370 * 812 * 4));
+        stats.record_frame(start + Duration::from_millis(20), Duration::from_micros(1500), (370, 812, 370 * 812 * 4));
+        let lines = stats.lines();
+        assert_eq!(lines[0], "frame     20.0 ms (50 fps)");
+        assert_eq!(lines[1], "draw       1.5 ms");
+        assert_eq!(lines[2], "buffer  370×812 (1.1 MiB)");
+    }
+}
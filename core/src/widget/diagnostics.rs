@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Widget Diagnostics Status
+//!
+//! The widget periodically publishes a small JSON status snapshot so the
+//! settings app can show a diagnostics page without probing hardware itself.
+//! This answers the most common "it shows nothing" questions: is the widget
+//! running, which GPU vendor was detected and how its usage is read, which
+//! GPU tools are installed, which hwmon sensors exist (and which ones feed
+//! the CPU/GPU temperatures), which network interfaces are counted, did the
+//! last weather fetch work, can Cider be reached, and what went wrong recently.
+//!
+//! # Status Location
+//!
+//! Written next to the widget cache at
+//! `~/.cache/cosmic-monitor-applet/widget_status.json`
+//!
+//! # Recent Errors
+//!
+//! Monitors report failures via [`record_error`]. The last
+//! [`MAX_RECENT_ERRORS`] entries are kept in memory and included in every
+//! status snapshot.
+//!
+//! ```text
+//! WeatherMonitor ──record_error()──┐
+//! MediaMonitor ────record_error()──┼──► RECENT_ERRORS ──► WidgetStatus::save()
+//! render panic ────record_error()──┘                          │
+//!                                                              ▼
+//!                                                   settings diagnostics page
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ============================================================================
+// Recent Error Log
+// ============================================================================
+
+/// Number of recent errors kept for the diagnostics page.
+pub const MAX_RECENT_ERRORS: usize = 20;
+
+/// Ring buffer of recent errors, shared by all monitor threads.
+static RECENT_ERRORS: Mutex<VecDeque<RecordedError>> = Mutex::new(VecDeque::new());
+
+/// A single error reported by a monitor.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RecordedError {
+    /// Unix timestamp (seconds) when the error occurred
+    pub timestamp: u64,
+    /// Which component reported it (e.g., "weather", "render")
+    pub source: String,
+    /// Human-readable error message
+    pub message: String,
+}
+
+/// Record an error for display on the settings diagnostics page.
+///
+/// Consecutive duplicates (same source and message) only refresh the
+/// timestamp, so a failing poll loop doesn't flood the list.
+///
+/// # Arguments
+///
+/// * `source` - Short component name (e.g., "weather", "cider", "render")
+/// * `message` - Error description
+pub fn record_error(source: &str, message: impl std::fmt::Display) {
+    let message = message.to_string();
+    let timestamp = unix_now();
+
+    let mut errors = RECENT_ERRORS.lock().unwrap();
+    if let Some(last) = errors.back_mut() {
+        if last.source == source && last.message == message {
+            last.timestamp = timestamp;
+            return;
+        }
+    }
+
+    errors.push_back(RecordedError {
+        timestamp,
+        source: source.to_string(),
+        message,
+    });
+    while errors.len() > MAX_RECENT_ERRORS {
+        errors.pop_front();
+    }
+}
+
+/// Snapshot of recently recorded errors, oldest first.
+pub fn recent_errors() -> Vec<RecordedError> {
+    RECENT_ERRORS.lock().unwrap().iter().cloned().collect()
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// Status Snapshot
+// ============================================================================
+
+/// Status snapshot published by the widget for the settings app.
+///
+/// The settings app may read a snapshot written by a widget of another
+/// version, so fields must stay backwards compatible: add new ones with
+/// `#[serde(default)]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WidgetStatus {
+    /// Widget process ID
+    pub pid: u32,
+    /// Unix timestamp (seconds) when this snapshot was written
+    pub updated_at: u64,
+    /// Detected GPU vendor ("NVIDIA", "AMD", "Intel", or "None")
+    pub gpu_vendor: String,
+    /// Labels of all hardware temperature sensors found
+    pub sensors: Vec<String>,
+    /// Result of the last weather fetch ("OK: ..." or the error), if any
+    pub weather_last_result: Option<String>,
+    /// Seconds until the next weather retry while backing off
+    pub weather_next_retry_secs: Option<u64>,
+    /// Cider REST API connection status
+    pub cider_status: String,
+    /// Recent errors, oldest first
+    pub recent_errors: Vec<RecordedError>,
+    /// Heartbeat health of background collectors
+    #[serde(default)]
+    pub monitor_health: Vec<super::watchdog::MonitorHealth>,
+    /// Version of the running widget
+    #[serde(default)]
+    pub version: String,
+    /// Latest release found by the update check, if it ran
+    #[serde(default)]
+    pub latest_version: Option<String>,
+    /// How GPU usage is read (e.g., "nvidia-smi", "sysfs (gpu_busy_percent)")
+    #[serde(default)]
+    pub gpu_method: String,
+    /// DRM card GPU usage and temperature are read from ("card1 (amdgpu)")
+    #[serde(default)]
+    pub gpu_card: Option<String>,
+    /// External GPU tools the vendor detection looks for
+    #[serde(default)]
+    pub gpu_tools: Vec<ToolStatus>,
+    /// Every hwmon temperature input
+    #[serde(default)]
+    pub hwmon_readings: Vec<SensorReading>,
+    /// Sensor label used for the CPU temperature, if one matched
+    #[serde(default)]
+    pub cpu_sensor: Option<String>,
+    /// Sensor label used for the GPU temperature, if one matched
+    #[serde(default)]
+    pub gpu_sensor: Option<String>,
+    /// Network interfaces summed into the network rates
+    #[serde(default)]
+    pub network_interfaces: Vec<InterfaceStatus>,
+}
+
+/// One temperature input of a hwmon chip.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SensorReading {
+    /// Chip name from `hwmonN/name` (e.g., "k10temp", "amdgpu", "nvme")
+    pub chip: String,
+    /// Input label from `tempN_label`, or "tempN" if the chip has none
+    pub label: String,
+    /// Current temperature in Celsius, if the input could be read
+    pub temperature: Option<f32>,
+}
+
+/// Whether an external tool is installed where the widget looks for it.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolStatus {
+    /// Tool name (e.g., "nvidia-smi")
+    pub name: String,
+    /// Path that is checked
+    pub path: String,
+    /// Whether the file exists
+    pub found: bool,
+}
+
+/// Traffic totals of a network interface.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InterfaceStatus {
+    /// Interface name (e.g., "enp5s0", "wlan0", "lo")
+    pub name: String,
+    /// Bytes received since the interface came up
+    pub total_received: u64,
+    /// Bytes transmitted since the interface came up
+    pub total_transmitted: u64,
+}
+
+/// GPU tools checked by the vendor detection, with the paths it uses.
+const GPU_TOOLS: &[(&str, &str)] = &[
+    ("nvidia-smi", "/usr/bin/nvidia-smi"),
+    ("radeontop", "/usr/bin/radeontop"),
+    ("rocm-smi", "/opt/rocm/bin/rocm-smi"),
+    ("intel_gpu_top", "/usr/bin/intel_gpu_top"),
+];
+
+/// Presence of the GPU tools the widget can use.
+pub fn gpu_tools() -> Vec<ToolStatus> {
+    GPU_TOOLS
+        .iter()
+        .map(|(name, path)| ToolStatus {
+            name: name.to_string(),
+            path: path.to_string(),
+            found: std::path::Path::new(path).exists(),
+        })
+        .collect()
+}
+
+/// Read every temperature input under `/sys/class/hwmon`.
+///
+/// Unlike the sysinfo component list used for the temperature section, this
+/// keeps the chip name, so users can tell e.g. an NVMe "Composite" sensor
+/// from a CPU one. Sorted by chip, then label.
+pub fn hwmon_readings() -> Vec<SensorReading> {
+    let mut readings = Vec::new();
+    let Ok(chips) = fs::read_dir("/sys/class/hwmon") else {
+        return readings;
+    };
+
+    for chip in chips.flatten() {
+        let dir = chip.path();
+        let chip_name = read_trimmed(&dir.join("name")).unwrap_or_else(|| chip.file_name().to_string_lossy().to_string());
+        let Ok(files) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for file in files.flatten() {
+            let file_name = file.file_name().to_string_lossy().to_string();
+            let Some(input) = file_name.strip_prefix("temp").and_then(|rest| rest.strip_suffix("_input")) else {
+                continue;
+            };
+            let label = read_trimmed(&dir.join(format!("temp{}_label", input))).unwrap_or_else(|| format!("temp{}", input));
+            // Millidegrees Celsius
+            let temperature = read_trimmed(&file.path())
+                .and_then(|value| value.parse::<f32>().ok())
+                .map(|millidegrees| millidegrees / 1000.0);
+            readings.push(SensorReading {
+                chip: chip_name.clone(),
+                label,
+                temperature,
+            });
+        }
+    }
+
+    readings.sort_by(|a, b| (&a.chip, &a.label).cmp(&(&b.chip, &b.label)));
+    readings
+}
+
+/// Read a small sysfs file without the trailing newline.
+fn read_trimmed(path: &std::path::Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|content| content.trim().to_string())
+}
+
+impl WidgetStatus {
+    /// Snapshots older than this mean the widget has stopped updating (seconds).
+    pub const STALE_AFTER_SECS: u64 = 15;
+
+    /// Returns the path to the status file.
+    ///
+    /// Creates the parent directory if it doesn't exist.
+    fn status_path() -> PathBuf {
+        let mut path = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        path.push("cosmic-monitor-applet");
+        fs::create_dir_all(&path).ok();
+        path.push("widget_status.json");
+        path
+    }
+
+    /// Start a snapshot stamped with the current PID, time and recent errors.
+    ///
+    /// Monitor-specific fields are filled in by the caller.
+    pub fn capture() -> Self {
+        Self {
+            pid: std::process::id(),
+            updated_at: unix_now(),
+            version: super::update::CURRENT_VERSION.to_string(),
+            recent_errors: recent_errors(),
+            ..Default::default()
+        }
+    }
+
+    /// Save the status to disk.
+    ///
+    /// Silently ignores write errors (diagnostics are non-critical).
+    pub fn save(&self) {
+        let path = Self::status_path();
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            fs::write(&path, json).ok();
+        }
+    }
+
+    /// Load the status file, returning `None` if the widget never wrote one.
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(Self::status_path()).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Seconds since this snapshot was written.
+    pub fn age_secs(&self) -> u64 {
+        unix_now().saturating_sub(self.updated_at)
+    }
+}
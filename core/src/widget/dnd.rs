@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Do Not Disturb
+//!
+//! COSMIC's notification daemon keeps its do-not-disturb switch in its own
+//! cosmic-config entry, the same one the panel's notifications applet
+//! toggles:
+//!
+//! ```text
+//! ~/.config/cosmic/com.system76.CosmicNotifications/v1/do_not_disturb   true | false
+//! ```
+//!
+//! The bell button in the notifications header reads it on every sample
+//! tick and writes it when clicked; the daemon watches the entry and stops
+//! (or resumes) showing popups right away. A missing entry means DND was
+//! never switched on.
+
+use cosmic::cosmic_config::{self, ConfigGet, ConfigSet};
+
+/// Config entry of the COSMIC notification daemon.
+const NOTIFICATIONS_CONFIG_ID: &str = "com.system76.CosmicNotifications";
+
+/// Version of that entry.
+const NOTIFICATIONS_CONFIG_VERSION: u64 = 1;
+
+/// Key of the do-not-disturb switch.
+const DND_KEY: &str = "do_not_disturb";
+
+/// Do-not-disturb state of the COSMIC notification daemon.
+pub struct DoNotDisturb {
+    /// Daemon config (None if it can't be opened; the button is hidden)
+    config: Option<cosmic_config::Config>,
+    /// State as last read or written
+    enabled: bool,
+}
+
+impl DoNotDisturb {
+    /// Open the daemon's config and read the current state.
+    pub fn new() -> Self {
+        let config = cosmic_config::Config::new(NOTIFICATIONS_CONFIG_ID, NOTIFICATIONS_CONFIG_VERSION)
+            .map_err(|e| log::warn!("Notification daemon config not available, DND button hidden: {}", e))
+            .ok();
+        let mut dnd = Self { config, enabled: false };
+        dnd.refresh();
+        dnd
+    }
+
+    /// Current state, `None` if it can't be read or changed.
+    pub fn state(&self) -> Option<bool> {
+        self.config.as_ref().map(|_| self.enabled)
+    }
+
+    /// Read the state again (it may have been switched in the panel).
+    ///
+    /// # Returns
+    ///
+    /// Whether it changed
+    pub fn refresh(&mut self) -> bool {
+        let Some(config) = &self.config else {
+            return false;
+        };
+        let enabled = config.get::<bool>(DND_KEY).unwrap_or(false);
+        let changed = enabled != self.enabled;
+        self.enabled = enabled;
+        changed
+    }
+
+    /// Switch do-not-disturb on or off.
+    ///
+    /// # Returns
+    ///
+    /// Whether the state was written
+    pub fn toggle(&mut self) -> bool {
+        let Some(config) = &self.config else {
+            return false;
+        };
+        let enabled = !self.enabled;
+        match config.set(DND_KEY, enabled) {
+            Ok(()) => {
+                log::info!("Do not disturb {}", if enabled { "on" } else { "off" });
+                self.enabled = enabled;
+                true
+            }
+            Err(e) => {
+                log::warn!("Failed to switch do not disturb: {}", e);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Bounded External Tool Execution
+//!
+//! Several collectors shell out to external tools (`nvidia-smi`, `radeontop`,
+//! `intel_gpu_top`, `solaar`, `headsetcontrol`, `lsblk`, `dbus-send`). A plain
+//! `Command::output()` waits forever, so a hung tool (driver stuck in a call,
+//! unresponsive D-Bus peer) would block its monitor thread indefinitely, and
+//! retries from other threads would keep piling up more stuck processes.
+//!
+//! [`CommandExt::bounded_output`] runs a command with:
+//!
+//! - **A timeout**: the child is killed and reaped once it expires, so no
+//!   zombie or orphaned processes are left behind
+//! - **A concurrency limit**: at most [`MAX_CONCURRENT`] tools run at once
+//!   across all monitors; callers wait for a free slot (within their timeout)
+//!
+//! ```text
+//! GPU thread ─────┐                      ┌──► nvidia-smi   (killed after timeout)
+//! Battery thread ─┼──► slot pool (4) ────┼──► solaar
+//! Media thread ───┘                      └──► dbus-send
+//! ```
+//!
+//! Long-running children that are meant to stream forever (the `busctl
+//! monitor` used by the notification monitor) are not run through here.
+
+use std::io::{self, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Maximum number of external tools running at the same time.
+pub const MAX_CONCURRENT: usize = 4;
+
+/// Timeout for quick queries (GPU usage, D-Bus properties, lsblk).
+pub const QUICK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for slow tools that probe hardware (solaar, headsetcontrol).
+pub const SLOW_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often a running child is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// ============================================================================
+// Concurrency Slots
+// ============================================================================
+
+/// Number of tools currently running.
+static RUNNING: Mutex<usize> = Mutex::new(0);
+/// Signalled whenever a slot is released.
+static SLOT_FREED: Condvar = Condvar::new();
+
+/// A held execution slot, released on drop.
+struct Slot;
+
+impl Slot {
+    /// Wait until a slot is free or the deadline passes.
+    fn acquire(deadline: Instant) -> Option<Self> {
+        let mut running = RUNNING.lock().unwrap();
+        while *running >= MAX_CONCURRENT {
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            running = SLOT_FREED.wait_timeout(running, remaining).unwrap().0;
+        }
+        *running += 1;
+        Some(Slot)
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *RUNNING.lock().unwrap() -= 1;
+        SLOT_FREED.notify_one();
+    }
+}
+
+// ============================================================================
+// Command Extension
+// ============================================================================
+
+/// Timeout-aware replacement for [`Command::output`].
+pub trait CommandExt {
+    /// Run the command to completion, killing it if it exceeds `timeout`.
+    ///
+    /// The timeout covers both waiting for a free slot and the run itself.
+    /// Stdin is closed; stdout and stderr are captured.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorKind::TimedOut` if no slot became free or the tool
+    /// didn't exit in time, or the spawn error (e.g., `NotFound` when the
+    /// tool isn't installed).
+    fn bounded_output(&mut self, timeout: Duration) -> io::Result<Output>;
+}
+
+impl CommandExt for Command {
+    fn bounded_output(&mut self, timeout: Duration) -> io::Result<Output> {
+        let program = self.get_program().to_string_lossy().into_owned();
+        let deadline = Instant::now() + timeout;
+
+        let Some(_slot) = Slot::acquire(deadline) else {
+            log::warn!("No free slot to run {} within {:?}", program, timeout);
+            return Err(io::Error::new(io::ErrorKind::TimedOut, format!("{} queued too long", program)));
+        };
+
+        let mut child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Drain pipes on their own threads so a chatty tool can't block on
+        // a full pipe while we wait for it to exit
+        let stdout = child.stdout.take().map(drain);
+        let stderr = child.stderr.take().map(drain);
+
+        match wait_until(&mut child, deadline)? {
+            Some(status) => Ok(Output {
+                status,
+                stdout: stdout.map(collect).unwrap_or_default(),
+                stderr: stderr.map(collect).unwrap_or_default(),
+            }),
+            None => {
+                // Kill and reap so the process doesn't linger as a zombie.
+                // Reader threads finish on their own once the pipes close.
+                child.kill().ok();
+                child.wait().ok();
+                log::warn!("{} timed out after {:?}, killed", program, timeout);
+                super::diagnostics::record_error("exec", format!("{} timed out and was killed", program));
+                Err(io::Error::new(io::ErrorKind::TimedOut, format!("{} timed out", program)))
+            }
+        }
+    }
+}
+
+/// Poll a child until it exits or the deadline passes (`None`).
+fn wait_until(child: &mut Child, deadline: Instant) -> io::Result<Option<std::process::ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Read a pipe to the end on a background thread.
+fn drain<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        pipe.read_to_end(&mut buf).ok();
+        buf
+    })
+}
+
+/// Collect the output of a drain thread.
+fn collect(handle: JoinHandle<Vec<u8>>) -> Vec<u8> {
+    handle.join().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_output() {
+        let output = Command::new("sh")
+            .args(["-c", "echo hello"])
+            .bounded_output(QUICK_TIMEOUT)
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+    }
+
+    #[test]
+    fn test_hung_tool_is_killed() {
+        let start = Instant::now();
+        let err = Command::new("sleep")
+            .arg("30")
+            .bounded_output(Duration::from_millis(200))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Mail & Feeds
+//!
+//! Unread counts of IMAP mailboxes and the latest headline of RSS/Atom
+//! feeds ([`Feed`]), checked on a long interval:
+//!
+//! ```text
+//! imaps://… ──curl STATUS (UNSEEN)──────┐
+//!                                       ├──► rows() ◄── draw ("Mail: 3 unread")
+//! https://… ──GET, first item's title───┘
+//!                refresher thread, every feeds_refresh_minutes
+//! ```
+//!
+//! Mailboxes are checked with a single `STATUS` command through `curl`,
+//! which speaks IMAP over TLS; no connection is kept open (there is no IDLE).
+//! The password comes from `~/.netrc` (`machine imap.example.com login …
+//! password …`), so it never ends up in the config or on a command line.
+//!
+//! Feeds are fetched with the shared HTTP client. Only the first `<item>`
+//! (RSS) or `<entry>` (Atom) is looked at, which is the newest one in
+//! practically every feed.
+
+use std::process::Command;
+use std::time::Duration;
+
+use super::exec::CommandExt;
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::{Config, Feed, FeedKind};
+
+/// Longest a single mailbox or feed may take to answer.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedSettings {
+    /// Mailboxes and feeds with a URL
+    pub feeds: Vec<Feed>,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn feed_settings(config: &Config) -> Option<FeedSettings> {
+    config.show_feeds.then(|| FeedSettings {
+        feeds: config.feeds.iter().filter(|feed| !feed.url.trim().is_empty()).cloned().collect(),
+        refresh_minutes: config.feeds_refresh_minutes,
+    })
+}
+
+/// What a feed row shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedValue {
+    /// Unread messages in a mailbox
+    Unread(u32),
+    /// Title of the newest feed item
+    Headline(String),
+}
+
+/// Latest result of one mailbox or feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedRow {
+    /// Row label from the config
+    pub label: String,
+    /// What the row is read from
+    pub kind: FeedKind,
+    /// Result of the last check (None until the first one finished, or if it failed)
+    pub value: Option<FeedValue>,
+    /// Whether the last check failed
+    pub failed: bool,
+}
+
+impl FeedRow {
+    /// A row that hasn't been checked yet.
+    fn pending(feed: &Feed) -> Self {
+        Self {
+            label: feed.label.clone(),
+            kind: feed.kind,
+            value: None,
+            failed: false,
+        }
+    }
+}
+
+/// Checks mailboxes and feeds on a background thread.
+pub type FeedMonitor = Refresher<FeedSettings, Vec<FeedRow>>;
+
+impl RefreshSettings for FeedSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl FeedMonitor {
+    /// Start checking the configured feeds.
+    pub fn new(settings: Option<FeedSettings>) -> Self {
+        Refresher::spawn("feeds", settings, |settings: &FeedSettings| {
+            let (rows, error) = refresh(&settings.feeds);
+            log::debug!("Feeds refreshed: {} rows, error: {:?}", rows.len(), error);
+            (Some(rows), error)
+        })
+    }
+
+    /// One row per feed, in config order.
+    ///
+    /// Until the feeds of changed settings were checked, their rows show as
+    /// pending instead of the previous results.
+    pub fn rows(&self) -> Vec<FeedRow> {
+        if self.is_current() {
+            return self.value().unwrap_or_default();
+        }
+        self.settings()
+            .map(|settings| settings.feeds.iter().map(FeedRow::pending).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Check every feed, collecting what went wrong.
+fn refresh(feeds: &[Feed]) -> (Vec<FeedRow>, Option<String>) {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for feed in feeds {
+        let result = match feed.kind {
+            FeedKind::Imap => unread_count(feed.url.trim()).map(FeedValue::Unread),
+            FeedKind::Rss => latest_headline(feed.url.trim()).map(FeedValue::Headline),
+        };
+        let mut row = FeedRow::pending(feed);
+        match result {
+            Ok(value) => row.value = Some(value),
+            Err(e) => {
+                errors.push(format!("{}: {}", feed.label, e));
+                row.failed = true;
+            }
+        }
+        rows.push(row);
+    }
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    (rows, error)
+}
+
+/// Ask an IMAP server for the unread count of a mailbox.
+fn unread_count(url: &str) -> Result<u32, String> {
+    let (server, mailbox) = split_mailbox_url(url).ok_or_else(|| String::from("not an imap:// or imaps:// URL"))?;
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--netrc-optional", "--max-time"])
+        .arg(FETCH_TIMEOUT.as_secs().to_string())
+        .arg(&server)
+        .arg("--request")
+        .arg(format!("STATUS \"{}\" (UNSEEN)", mailbox.replace('\\', "\\\\").replace('"', "\\\"")))
+        .bounded_output(FETCH_TIMEOUT + Duration::from_secs(1))
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("curl failed").trim().to_string());
+    }
+    parse_unseen(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| String::from("no STATUS reply"))
+}
+
+/// Split a mailbox URL into the server URL and the mailbox name
+/// (`INBOX` when the URL has no path).
+fn split_mailbox_url(url: &str) -> Option<(String, String)> {
+    let rest = url.strip_prefix("imaps://").or_else(|| url.strip_prefix("imap://"))?;
+    let scheme = &url[..url.len() - rest.len()];
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if host.is_empty() {
+        return None;
+    }
+    let mailbox = path.trim_end_matches('/');
+    let mailbox = if mailbox.is_empty() { "INBOX" } else { mailbox };
+    Some((format!("{}{}/", scheme, host), mailbox.to_string()))
+}
+
+/// Unread count in a `STATUS` reply (`* STATUS "INBOX" (UNSEEN 3)`).
+fn parse_unseen(reply: &str) -> Option<u32> {
+    reply
+        .lines()
+        .filter(|line| line.starts_with("* STATUS"))
+        .find_map(|line| {
+            let (_, rest) = line.split_once("UNSEEN ")?;
+            rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+        })
+}
+
+/// Fetch a feed and return the title of its newest item.
+fn latest_headline(url: &str) -> Result<String, String> {
+    let body = super::http::get_text(url, &[], FETCH_TIMEOUT).map_err(|e| e.to_string())?;
+    first_item_title(&body).ok_or_else(|| String::from("no items in feed"))
+}
+
+/// Title of the first `<item>` (RSS) or `<entry>` (Atom) of a feed.
+fn first_item_title(xml: &str) -> Option<String> {
+    let start = [find_tag(xml, "item"), find_tag(xml, "entry")].into_iter().flatten().min()?;
+    let item = &xml[start..];
+    let open = find_tag(item, "title")?;
+    let content_start = open + item[open..].find('>')? + 1;
+    let content_end = content_start + item[content_start..].find("</title>")?;
+    let title = item[content_start..content_end].trim();
+    let title = match title.strip_prefix("<![CDATA[") {
+        Some(cdata) => cdata.strip_suffix("]]>").unwrap_or(cdata).to_string(),
+        None => decode_entities(title),
+    };
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// Position of the first `<name>` or `<name …>` tag (not `<names>`).
+fn find_tag(xml: &str, name: &str) -> Option<usize> {
+    let pattern = format!("<{}", name);
+    xml.match_indices(&pattern).map(|(index, _)| index).find(|index| {
+        xml[index + pattern.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c == '>' || c == '/' || c.is_whitespace())
+    })
+}
+
+/// Replace the XML entities found in titles.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#')?.parse().ok(),
+                };
+                char::from_u32(code?)
+            }
+        });
+        match (replacement, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unseen() {
+        assert_eq!(parse_unseen("* STATUS \"INBOX\" (UNSEEN 3)\r\n"), Some(3));
+        assert_eq!(parse_unseen("* STATUS Work/Lists (MESSAGES 120 UNSEEN 0)\r\n"), Some(0));
+        assert_eq!(parse_unseen("* OK ready\r\n"), None);
+        assert_eq!(
+            split_mailbox_url("imaps://me@imap.example.com"),
+            Some((String::from("imaps://me@imap.example.com/"), String::from("INBOX")))
+        );
+        assert_eq!(
+            split_mailbox_url("imap://localhost:143/Work/Lists/"),
+            Some((String::from("imap://localhost:143/"), String::from("Work/Lists")))
+        );
+        assert_eq!(split_mailbox_url("https://example.com/feed"), None);
+    }
+
+    #[test]
+    fn test_first_item_title() {
+        let rss = r#"<rss><channel><title>Site</title><items/>
+            <item><title><![CDATA[Kernel 6.12 <released>]]></title></item>
+            <item><title>Older</title></item></channel></rss>"#;
+        assert_eq!(first_item_title(rss).as_deref(), Some("Kernel 6.12 <released>"));
+
+        let atom = r#"<feed><title>Blog</title><entry><title type="html">Tips &amp; tricks
+            for &#8220;COSMIC&#x201D;</title></entry></feed>"#;
+        assert_eq!(first_item_title(atom).as_deref(), Some("Tips & tricks for “COSMIC”"));
+        assert_eq!(first_item_title("<rss><channel><title>Empty</title></channel></rss>"), None);
+        assert_eq!(decode_entities("AT&T &bogus; &lt;3"), "AT&T &bogus; <3");
+    }
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Fonts
+//!
+//! Family and size of each text role ([`TextRole`]): the clock, section
+//! headers, body rows and small labels. Drawing code asks for a font by
+//! role and the style and size the layout was designed for:
+//!
+//! ```text
+//! font(TextRole::Header, "Bold 14")
+//!   config: header = "Cantarell", 16 pt ──► "Cantarell Bold 16"
+//!   config: header = "Ubuntu", 14 pt    ──► "Ubuntu Bold 14"   (default)
+//! ```
+//!
+//! A role's configured size scales all of its text by the same factor, so
+//! a role keeps its internal proportions (the clock's seconds stay smaller
+//! than its hours). Section heights don't grow with the fonts; sizes far
+//! above the defaults crowd the rows.
+//!
+//! # Validation
+//!
+//! Families are checked against Pango's font enumeration when resolved
+//! (at startup and when the fonts change). A family that isn't installed
+//! is logged and replaced by [`DEFAULT_FONT_FAMILY`], so a typo or an
+//! uninstalled font never leaves Pango to pick an arbitrary fallback.
+//!
+//! The resolved fonts are set once per frame by the renderer with
+//! [`Fonts::activate`], like the text palette, so the many drawing helpers
+//! don't each need them passed in.
+
+use std::cell::RefCell;
+
+use pango::prelude::*;
+
+use crate::config::{FontSettings, TextRole, DEFAULT_FONT_FAMILY};
+
+/// Family and scale of one role, after validation.
+#[derive(Debug, Clone, PartialEq)]
+struct Face {
+    /// Installed family (or the default)
+    family: String,
+    /// Configured size over the design size
+    scale: f64,
+}
+
+/// Fonts of all roles, ready to draw with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fonts {
+    /// One face per role, in `TextRole::ALL` order
+    faces: Vec<Face>,
+}
+
+impl Default for Fonts {
+    fn default() -> Self {
+        Self::resolve_with(&FontSettings::default(), None)
+    }
+}
+
+thread_local! {
+    /// Fonts of the frame being drawn, set once per frame by the renderer.
+    static FONTS: RefCell<Fonts> = RefCell::new(Fonts::default());
+}
+
+impl Fonts {
+    /// Validate the configured families against the installed fonts.
+    pub fn resolve(settings: &FontSettings) -> Self {
+        Self::resolve_with(settings, Some(&installed_families()))
+    }
+
+    /// Resolve against `installed` families (`None` trusts every family).
+    fn resolve_with(settings: &FontSettings, installed: Option<&[String]>) -> Self {
+        let faces = TextRole::ALL
+            .iter()
+            .map(|role| {
+                let font = settings.get(*role);
+                let family = font.family.trim();
+                let family = if family.is_empty() {
+                    DEFAULT_FONT_FAMILY
+                } else if installed.is_some_and(|installed| !is_installed(family, installed)) {
+                    log::warn!("{} font \"{}\" is not installed, using {}", role.label(), family, DEFAULT_FONT_FAMILY);
+                    DEFAULT_FONT_FAMILY
+                } else {
+                    family
+                };
+                Face {
+                    family: family.to_string(),
+                    scale: f64::from(font.size.max(1)) / f64::from(role.design_size()),
+                }
+            })
+            .collect();
+        Self { faces }
+    }
+
+    /// Draw the following text with these fonts.
+    pub fn activate(&self) {
+        FONTS.with(|fonts| fonts.borrow_mut().clone_from(self));
+    }
+}
+
+/// Font of `role` for a style and size the layout was designed for
+/// (e.g., "Bold 14", "Italic 11", "12").
+pub fn font(role: TextRole, design: &str) -> pango::FontDescription {
+    let mut description = pango::FontDescription::from_string(design);
+    FONTS.with(|fonts| {
+        let fonts = fonts.borrow();
+        let face = &fonts.faces[role as usize];
+        description.set_family(&face.family);
+        description.set_size((f64::from(description.size()) * face.scale).round() as i32);
+    });
+    description
+}
+
+/// Names of the font families Pango can draw with.
+pub fn installed_families() -> Vec<String> {
+    pangocairo::FontMap::default()
+        .list_families()
+        .iter()
+        .map(|family| family.name().to_string())
+        .collect()
+}
+
+/// Whether `family` is one of `installed` (ignoring case, as fontconfig does).
+pub fn is_installed(family: &str, installed: &[String]) -> bool {
+    installed.iter().any(|name| name.eq_ignore_ascii_case(family.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_falls_back() {
+        let mut settings = FontSettings::default();
+        settings.header.family = String::from("cantarell");
+        settings.header.size = 21;
+        settings.body.family = String::from("No Such Font");
+        let installed = [String::from("Ubuntu"), String::from("Cantarell")];
+        let fonts = Fonts::resolve_with(&settings, Some(&installed));
+        assert_eq!(fonts.faces[TextRole::Header as usize], Face { family: String::from("cantarell"), scale: 1.5 });
+        assert_eq!(fonts.faces[TextRole::Body as usize].family, DEFAULT_FONT_FAMILY);
+
+        fonts.activate();
+        let header = font(TextRole::Header, "Bold 14");
+        assert_eq!(header.family().map(|family| family.to_string()), Some(String::from("cantarell")));
+        assert_eq!(header.size(), 21 * pango::SCALE);
+        assert_eq!(header.weight(), pango::Weight::Bold);
+    }
+}
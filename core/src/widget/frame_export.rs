@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Frame Export for Streaming Overlays
+//!
+//! When enabled, every rendered frame is also written to a PNG file so
+//! streamers can add the exact widget rendering to OBS as an Image source
+//! (which reloads the file when it changes) instead of screen-capturing a
+//! desktop region. The PNG keeps the transparent background.
+//!
+//! # File Location
+//!
+//! `$XDG_RUNTIME_DIR/cosmic-monitor-widget-frame.png`
+//! (falls back to `/tmp/cosmic-monitor-widget-frame-<uid>.png`)
+//!
+//! # Threading
+//!
+//! PNG encoding runs on a dedicated thread so it never delays the render
+//! loop. The channel holds a single frame; if the encoder is still busy,
+//! newer frames are dropped rather than queued.
+//!
+//! ```text
+//! draw() ──copy canvas──► sync_channel(1) ──► encoder thread
+//!                                                 ├── write  frame.png.tmp
+//!                                                 └── rename → frame.png
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+
+// ============================================================================
+// Frame Exporter
+// ============================================================================
+
+/// A copied ARGB32 frame waiting to be encoded.
+struct Frame {
+    /// Raw premultiplied ARGB32 pixels
+    data: Vec<u8>,
+    /// Width in pixels
+    width: i32,
+    /// Height in pixels
+    height: i32,
+}
+
+/// Writes rendered frames to a PNG file on a background thread.
+pub struct FrameExporter {
+    /// Hands frames to the encoder thread
+    sender: SyncSender<Frame>,
+}
+
+impl FrameExporter {
+    /// Start the encoder thread.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Frame>(1);
+
+        std::thread::spawn(move || {
+            super::priority::enter_background("frame-export");
+            let path = frame_path();
+            log::info!("Exporting widget frames to {}", path.display());
+
+            // Exits when the exporter (sender) is dropped
+            for frame in receiver {
+                if let Err(e) = write_png(&path, frame) {
+                    log::warn!("Failed to export frame: {}", e);
+                }
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Queue a copy of a rendered frame for export.
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - ARGB32 pixel buffer as rendered (stride = width * 4)
+    /// * `width` - Width in pixels
+    /// * `height` - Height in pixels
+    pub fn submit(&self, canvas: &[u8], width: i32, height: i32) {
+        let frame = Frame {
+            data: canvas.to_vec(),
+            width,
+            height,
+        };
+        match self.sender.try_send(frame) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => log::trace!("Frame export busy, dropping frame"),
+            Err(TrySendError::Disconnected(_)) => log::warn!("Frame export thread stopped"),
+        }
+    }
+}
+
+/// Path of the exported frame.
+pub fn frame_path() -> PathBuf {
+    super::control::runtime_path("cosmic-monitor-widget-frame", "png")
+}
+
+/// Encode a frame as PNG and atomically replace the output file.
+fn write_png(path: &Path, frame: Frame) -> Result<(), Box<dyn std::error::Error>> {
+    let surface = cairo::ImageSurface::create_for_data(
+        frame.data,
+        cairo::Format::ARgb32,
+        frame.width,
+        frame.height,
+        frame.width * 4,
+    )?;
+
+    // Write next to the target, then rename so OBS never reads a partial file
+    let tmp_path = path.with_extension("png.tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    surface.write_to_png(&mut file)?;
+    drop(file);
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! GPU Cards
+//!
+//! The DRM cards (`/sys/class/drm/card<N>`) and which of them the GPU usage
+//! and temperature describe. On a hybrid laptop both the integrated and the
+//! discrete GPU show up:
+//!
+//! ```text
+//! card0  i915    boot_vga=1   integrated
+//! card1  amdgpu  boot_vga=0   discrete     ◄ picked for an AMD GPU
+//! ```
+//!
+//! [`select`] picks the card driven by the detected vendor's driver,
+//! preferring one the firmware didn't boot on (the discrete GPU). Usage is
+//! then read from that card's sysfs files, and the temperature from the
+//! hwmon chip under the same PCI device (`card<N>/device/hwmon/hwmon<M>`),
+//! so both describe the same GPU instead of whichever sensor looks like a
+//! GPU first.
+//!
+//! The same card also gives the fan speed and clocks of the GPU details row
+//! ([`GpuCard::details`]): amdgpu lists its clock levels with the current
+//! one marked (`pp_dpm_sclk`, `pp_dpm_mclk`) and drives the fan through
+//! hwmon `pwm1`; i915/xe only report the current core clock.
+
+use std::path::{Path, PathBuf};
+
+/// Where the DRM cards are listed.
+const DRM_ROOT: &str = "/sys/class/drm";
+
+/// Fan speed and clocks of a GPU; each is `None` where the driver doesn't
+/// report it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GpuDetails {
+    /// Fan speed in percent of its maximum
+    pub fan_percent: Option<f32>,
+    /// Current core (shader) clock in MHz
+    pub core_mhz: Option<f32>,
+    /// Current memory clock in MHz
+    pub memory_mhz: Option<f32>,
+}
+
+impl GpuDetails {
+    /// Whether any value is known.
+    pub fn is_empty(&self) -> bool {
+        self.fan_percent.is_none() && self.core_mhz.is_none() && self.memory_mhz.is_none()
+    }
+}
+
+/// A DRM card and the kernel driver behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuCard {
+    /// Card name ("card1")
+    pub name: String,
+    /// Card directory (`/sys/class/drm/card1`)
+    pub path: PathBuf,
+    /// Kernel driver ("amdgpu", "i915", "xe", "nvidia", ...)
+    pub driver: String,
+    /// Whether the firmware used this GPU for the boot console
+    pub boot_vga: bool,
+}
+
+impl GpuCard {
+    /// Card and driver for diagnostics ("card1 (amdgpu)").
+    pub fn description(&self) -> String {
+        format!("{} ({})", self.name, self.driver)
+    }
+
+    /// The card's hwmon directory, if its driver registers one (the
+    /// proprietary NVIDIA driver doesn't).
+    fn hwmon(&self) -> Option<PathBuf> {
+        std::fs::read_dir(self.path.join("device/hwmon"))
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .min()
+    }
+
+    /// Name of the card's hwmon chip ("amdgpu"), as sensor labels start with it.
+    pub fn hwmon_chip(&self) -> Option<String> {
+        read_trimmed(&self.hwmon()?.join("name"))
+    }
+
+    /// Temperature of the card in Celsius, with the label of the input it
+    /// came from ("amdgpu edge (card1)").
+    pub fn temperature(&self) -> Option<(f32, String)> {
+        let hwmon = self.hwmon()?;
+        let inputs: Vec<(u32, String)> = std::fs::read_dir(&hwmon)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let index = name.to_str()?.strip_prefix("temp")?.strip_suffix("_input")?.parse().ok()?;
+                let label = read_trimmed(&hwmon.join(format!("temp{}_label", index))).unwrap_or_else(|| format!("temp{}", index));
+                Some((index, label))
+            })
+            .collect();
+        let (index, label) = preferred_input(&inputs)?;
+        let millidegrees: f32 = read_trimmed(&hwmon.join(format!("temp{}_input", index)))?.parse().ok()?;
+        let chip = read_trimmed(&hwmon.join("name")).unwrap_or_else(|| self.driver.clone());
+        Some((millidegrees / 1000.0, format!("{} {} ({})", chip, label, self.name)))
+    }
+
+    /// Fan speed and clocks read from sysfs (the proprietary NVIDIA driver
+    /// has none; those come from nvidia-smi).
+    pub fn details(&self) -> GpuDetails {
+        let device = self.path.join("device");
+        let fan_percent = self.hwmon().and_then(|hwmon| {
+            let pwm: f32 = read_trimmed(&hwmon.join("pwm1"))?.parse().ok()?;
+            // pwm1_max is optional and defaults to 255
+            let max: f32 = read_trimmed(&hwmon.join("pwm1_max")).and_then(|max| max.parse().ok()).unwrap_or(255.0);
+            (max > 0.0).then(|| pwm / max * 100.0)
+        });
+        let core_mhz = std::fs::read_to_string(device.join("pp_dpm_sclk"))
+            .ok()
+            .and_then(|levels| current_dpm_level(&levels))
+            .or_else(|| read_trimmed(&self.path.join("gt/gt0/rps_cur_freq_mhz"))?.parse().ok());
+        let memory_mhz = std::fs::read_to_string(device.join("pp_dpm_mclk"))
+            .ok()
+            .and_then(|levels| current_dpm_level(&levels));
+        GpuDetails { fan_percent, core_mhz, memory_mhz }
+    }
+}
+
+/// All DRM cards by card number (connectors such as `card1-DP-1` are left out).
+pub fn cards() -> Vec<GpuCard> {
+    let Ok(entries) = std::fs::read_dir(DRM_ROOT) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<(u32, GpuCard)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let number = name.strip_prefix("card")?.parse().ok()?;
+            let path = entry.path();
+            let driver = std::fs::read_link(path.join("device/driver")).ok()?;
+            let driver = driver.file_name()?.to_string_lossy().to_string();
+            let boot_vga = read_trimmed(&path.join("device/boot_vga")).as_deref() == Some("1");
+            Some((number, GpuCard { name, path, driver, boot_vga }))
+        })
+        .collect();
+    cards.sort_by_key(|(number, _)| *number);
+    cards.into_iter().map(|(_, card)| card).collect()
+}
+
+/// The card of a GPU driven by one of `drivers`, preferring one the firmware
+/// didn't boot on (the discrete GPU of a hybrid laptop), then the lowest
+/// card number.
+pub fn select(cards: &[GpuCard], drivers: &[&str]) -> Option<GpuCard> {
+    cards
+        .iter()
+        .filter(|card| drivers.contains(&card.driver.as_str()))
+        .min_by_key(|card| card.boot_vga)
+        .cloned()
+}
+
+/// The temperature input to show: amdgpu's `edge` (the one its tools
+/// report), otherwise the lowest numbered input.
+fn preferred_input(inputs: &[(u32, String)]) -> Option<(u32, String)> {
+    inputs
+        .iter()
+        .find(|(_, label)| label.eq_ignore_ascii_case("edge"))
+        .or_else(|| inputs.iter().min_by_key(|(index, _)| *index))
+        .cloned()
+}
+
+/// Clock of the level marked current in an amdgpu DPM table:
+///
+/// ```text
+/// 0: 500Mhz
+/// 1: 1850Mhz *
+/// ```
+fn current_dpm_level(levels: &str) -> Option<f32> {
+    let line = levels.lines().find(|line| line.trim_end().ends_with('*'))?;
+    let (_, clock) = line.split_once(':')?;
+    let clock = clock.trim().trim_end_matches('*').trim_end();
+    let mhz = clock.strip_suffix("Mhz").or_else(|| clock.strip_suffix("MHz"))?;
+    mhz.trim().parse().ok()
+}
+
+/// Read a sysfs file holding one value.
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(name: &str, driver: &str, boot_vga: bool) -> GpuCard {
+        GpuCard {
+            name: name.to_string(),
+            path: Path::new(DRM_ROOT).join(name),
+            driver: driver.to_string(),
+            boot_vga,
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let cards = [card("card0", "amdgpu", true), card("card1", "amdgpu", false), card("card2", "nvidia", false)];
+        assert_eq!(select(&cards, &["amdgpu"]).unwrap().name, "card1");
+        assert_eq!(select(&cards, &["nvidia"]).unwrap().name, "card2");
+        assert_eq!(select(&cards[..1], &["amdgpu", "radeon"]).unwrap().name, "card0");
+        assert_eq!(select(&cards, &["i915", "xe"]), None);
+    }
+
+    #[test]
+    fn test_preferred_input() {
+        let inputs = [(3, "mem".to_string()), (2, "junction".to_string()), (1, "edge".to_string())];
+        assert_eq!(preferred_input(&inputs), Some((1, "edge".to_string())));
+        assert_eq!(preferred_input(&inputs[..2]), Some((2, "junction".to_string())));
+        assert_eq!(preferred_input(&[]), None);
+    }
+
+    #[test]
+    fn test_current_dpm_level() {
+        assert_eq!(current_dpm_level("0: 500Mhz\n1: 1850Mhz *\n2: 2100Mhz\n"), Some(1850.0));
+        assert_eq!(current_dpm_level("0: 96Mhz \n1: 1000Mhz\n"), None);
+        assert_eq!(current_dpm_level("S: 19Mhz *\n"), Some(19.0));
+    }
+}
@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! GPU Processes
+//!
+//! The processes using the GPU the most, listed under the GPU bar. Read on
+//! the GPU polling thread (see [`super::utilization`]) only while the list
+//! is shown, since both sources are much more expensive than the usage
+//! itself:
+//!
+//! | Vendor       | Source                                                   |
+//! |--------------|----------------------------------------------------------|
+//! | NVIDIA       | `nvidia-smi pmon -c 1 -s u` (SM usage, ~1s sample)       |
+//! | AMD, Intel   | `drm-engine-*` busy times in `/proc/<pid>/fdinfo/<fd>`   |
+//!
+//! fdinfo only holds cumulative busy times, so [`FdinfoSampler`] keeps the
+//! previous reading of every DRM client and turns the difference into a
+//! percentage of the time in between. A client's usage is its busiest
+//! engine; a process with several clients (one per opened GPU) adds them
+//! up. Processes of other users can't be read without root and are left
+//! out.
+
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+use std::time::Instant;
+
+use super::exec::{self, CommandExt};
+
+/// Most processes listed under the GPU bar.
+pub const MAX_GPU_PROCESSES: usize = 3;
+
+/// A process using the GPU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuProcess {
+    /// Process ID
+    pub pid: u32,
+    /// Command name (`/proc/<pid>/comm` or nvidia-smi's command column)
+    pub name: String,
+    /// GPU usage in percent (0-100)
+    pub usage: f32,
+}
+
+/// The busiest `MAX_GPU_PROCESSES` processes, idle ones left out.
+pub fn top_processes(mut processes: Vec<GpuProcess>) -> Vec<GpuProcess> {
+    processes.retain(|process| process.usage > 0.0);
+    processes.sort_by(|a, b| b.usage.total_cmp(&a.usage).then(a.pid.cmp(&b.pid)));
+    processes.truncate(MAX_GPU_PROCESSES);
+    processes
+}
+
+// ============================================================================
+// NVIDIA
+// ============================================================================
+
+/// Per-process SM usage from `nvidia-smi pmon`.
+pub fn nvidia_processes() -> Option<Vec<GpuProcess>> {
+    let output = Command::new("nvidia-smi")
+        .args(["pmon", "-c", "1", "-s", "u"])
+        .bounded_output(exec::QUICK_TIMEOUT)
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(top_processes(parse_pmon(&String::from_utf8_lossy(&output.stdout))))
+}
+
+/// Parse `nvidia-smi pmon -s u` output.
+///
+/// Columns are found by the `# gpu pid type sm ...` header, since newer
+/// drivers add columns (jpg, ofa). Idle processes show `-` for their
+/// usage; a process on several GPUs is added up.
+fn parse_pmon(text: &str) -> Vec<GpuProcess> {
+    let Some(header) = text.lines().find(|line| line.starts_with('#')) else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = header.trim_start_matches('#').split_whitespace().collect();
+    let (Some(pid_col), Some(sm_col), Some(name_col)) = (
+        columns.iter().position(|c| *c == "pid"),
+        columns.iter().position(|c| *c == "sm"),
+        columns.iter().position(|c| *c == "command"),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut processes: Vec<GpuProcess> = Vec::new();
+    for line in text.lines().filter(|line| !line.starts_with('#')) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < columns.len() {
+            continue;
+        }
+        let Ok(pid) = fields[pid_col].parse::<u32>() else {
+            continue;
+        };
+        let usage = fields[sm_col].parse::<f32>().unwrap_or(0.0);
+        match processes.iter_mut().find(|process| process.pid == pid) {
+            Some(process) => process.usage = (process.usage + usage).min(100.0),
+            None => processes.push(GpuProcess {
+                pid,
+                // Command names may contain spaces
+                name: fields[name_col..].join(" "),
+                usage,
+            }),
+        }
+    }
+    processes
+}
+
+// ============================================================================
+// DRM fdinfo (AMD, Intel)
+// ============================================================================
+
+/// Cumulative engine busy times of one DRM client, in nanoseconds.
+type EngineTimes = HashMap<String, u64>;
+
+/// Turns fdinfo busy times into usage between two samples.
+#[derive(Debug, Default)]
+pub struct FdinfoSampler {
+    /// Busy times of every client at the last sample, by (pid, client key)
+    last: HashMap<(u32, String), EngineTimes>,
+    /// When the last sample was taken
+    last_at: Option<Instant>,
+}
+
+impl FdinfoSampler {
+    /// Read every process's DRM clients and return their usage since the
+    /// previous call (empty on the first call).
+    pub fn sample(&mut self) -> Vec<GpuProcess> {
+        let now = Instant::now();
+        let clients = read_clients();
+        let elapsed_ns = self.last_at.map(|at| now.duration_since(at).as_nanos() as f64);
+
+        let mut usage_by_pid: HashMap<u32, f32> = HashMap::new();
+        if let Some(elapsed_ns) = elapsed_ns.filter(|ns| *ns > 0.0) {
+            for (key, engines) in &clients {
+                if let Some(previous) = self.last.get(key) {
+                    let usage = client_usage(previous, engines, elapsed_ns);
+                    *usage_by_pid.entry(key.0).or_default() += usage;
+                }
+            }
+        }
+
+        self.last = clients;
+        self.last_at = Some(now);
+
+        top_processes(
+            usage_by_pid
+                .into_iter()
+                .map(|(pid, usage)| GpuProcess {
+                    pid,
+                    name: process_name(pid),
+                    usage: usage.min(100.0),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Usage of the busiest engine between two readings of a client.
+fn client_usage(previous: &EngineTimes, current: &EngineTimes, elapsed_ns: f64) -> f32 {
+    current
+        .iter()
+        .filter_map(|(engine, busy)| {
+            let before = previous.get(engine)?;
+            Some((busy.saturating_sub(*before) as f64 / elapsed_ns * 100.0) as f32)
+        })
+        .fold(0.0, f32::max)
+        .min(100.0)
+}
+
+/// DRM clients of all readable processes, keyed by (pid, client key).
+///
+/// A client opened once but shared (dup'ed fds, inherited by children) is
+/// counted for the first process it is found in.
+fn read_clients() -> HashMap<(u32, String), EngineTimes> {
+    let mut clients = HashMap::new();
+    let mut seen = HashSet::new();
+    let Ok(procs) = std::fs::read_dir("/proc") else {
+        return clients;
+    };
+    for entry in procs.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fdinfo")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(text) = std::fs::read_to_string(fd.path()) else {
+                continue;
+            };
+            if let Some((client, engines)) = parse_fdinfo(&text) {
+                if seen.insert(client.clone()) {
+                    clients.insert((pid, client), engines);
+                }
+            }
+        }
+    }
+    clients
+}
+
+/// Client key (`drm-pdev` + `drm-client-id`) and engine busy times of one
+/// fdinfo file, or None if it isn't a DRM client.
+fn parse_fdinfo(text: &str) -> Option<(String, EngineTimes)> {
+    let mut pdev = "";
+    let mut client_id = None;
+    let mut engines = EngineTimes::new();
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "drm-pdev" => pdev = value,
+            "drm-client-id" => client_id = Some(value),
+            key => {
+                // "drm-engine-gfx:	123456789 ns" (capacity lines have no unit)
+                if let Some(engine) = key.strip_prefix("drm-engine-").filter(|engine| *engine != "capacity") {
+                    if let Some(busy) = value.strip_suffix("ns").and_then(|ns| ns.trim().parse().ok()) {
+                        engines.insert(engine.to_string(), busy);
+                    }
+                }
+            }
+        }
+    }
+    let client_id = client_id?;
+    (!engines.is_empty()).then(|| (format!("{}/{}", pdev, client_id), engines))
+}
+
+/// Command name of a process.
+fn process_name(pid: u32) -> String {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|comm| comm.trim().to_string())
+        .unwrap_or_else(|_| pid.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pmon() {
+        let text = "\
+# gpu         pid   type     sm    mem    enc    dec    jpg    ofa    command
+# Idx           #    C/G      %      %      %      %      %      %    name
+    0       2214     G     12      4      -      -      -      -    Xorg
+    0       5120     C     61     20      -      -      -      -    python3
+    0       7001     G      -      -      -      -      -      -    firefox
+";
+        let processes = top_processes(parse_pmon(text));
+        assert_eq!(processes.len(), 2);
+        assert_eq!((processes[0].pid, processes[0].name.as_str(), processes[0].usage), (5120, "python3", 61.0));
+        assert_eq!(processes[1].name, "Xorg");
+    }
+
+    #[test]
+    fn test_fdinfo_usage() {
+        let before = "pos:\t0\nflags:\t02100002\ndrm-driver:\tamdgpu\ndrm-pdev:\t0000:03:00.0\n\
+                      drm-client-id:\t42\ndrm-engine-gfx:\t1000000000 ns\ndrm-engine-compute:\t0 ns\n";
+        let after = "drm-driver:\tamdgpu\ndrm-pdev:\t0000:03:00.0\n\
+                     drm-client-id:\t42\ndrm-engine-gfx:\t1250000000 ns\ndrm-engine-compute:\t100000000 ns\n";
+        let (key, previous) = parse_fdinfo(before).unwrap();
+        let (_, current) = parse_fdinfo(after).unwrap();
+        assert_eq!(key, "0000:03:00.0/42");
+
+        // 250 ms of gfx in 500 ms: the busiest engine counts
+        assert_eq!(client_usage(&previous, &current, 500_000_000.0), 50.0);
+        assert!(parse_fdinfo("pos:\t0\nflags:\t02\n").is_none());
+    }
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Hardware Info
+//!
+//! CPU model, core and thread count, and GPU name, drawn under the
+//! Utilization header so screenshots of the widget identify the machine:
+//!
+//! ```text
+//! Utilization
+//! AMD Ryzen 7 5800X · 8C/16T
+//! AMD Radeon RX 6800/6800 XT / 6900 XT
+//! ```
+//!
+//! The CPU comes from `/proc/cpuinfo` (`model name`, or `Model` on ARM
+//! boards), with the trademark marks, "CPU @ 3.70GHz" and "8-Core
+//! Processor" suffixes dropped. Cores are the distinct `physical id` /
+//! `core id` pairs, threads the `processor` entries.
+//!
+//! The GPU is the DRM card [`super::gpu_card`] would pick (the discrete one
+//! on hybrid laptops), named from udev's hardware database
+//! (`/run/udev/data/+pci:<slot>`, `ID_MODEL_FROM_DATABASE`). Without a
+//! udev entry only the vendor is known.
+//!
+//! None of this changes while the widget runs, so it's read once at startup.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::gpu_card::{self, GpuCard};
+use crate::fl;
+
+/// Where udev keeps the properties of each device.
+const UDEV_DATA: &str = "/run/udev/data";
+
+/// What the machine is built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardwareInfo {
+    /// Cleaned up CPU model ("AMD Ryzen 7 5800X")
+    pub cpu_model: Option<String>,
+    /// Physical cores (`None` when cpuinfo has no core ids)
+    pub cores: Option<usize>,
+    /// Logical processors
+    pub threads: usize,
+    /// GPU name ("AMD Radeon RX 6800/6800 XT / 6900 XT")
+    pub gpu_name: Option<String>,
+}
+
+impl HardwareInfo {
+    /// Read the CPU and GPU of this machine.
+    pub fn detect() -> Self {
+        let mut info = std::fs::read_to_string("/proc/cpuinfo")
+            .map(|cpuinfo| parse_cpuinfo(&cpuinfo))
+            .unwrap_or_default();
+        let cards = gpu_card::cards();
+        // Same preference as the usage monitor: not the boot GPU if there's another
+        info.gpu_name = cards.iter().min_by_key(|card| card.boot_vga).map(gpu_name);
+        log::debug!("Hardware: {:?}", info);
+        info
+    }
+
+    /// Header text, one entry per line (CPU, then GPU).
+    pub fn lines(&self) -> Vec<String> {
+        let cpu = self.cpu_model.clone().unwrap_or_else(|| fl!("widget-hardware-unknown-cpu"));
+        let counts = match self.cores {
+            Some(cores) => format!("{}C/{}T", cores, self.threads),
+            None => format!("{}T", self.threads),
+        };
+        let gpu = self.gpu_name.clone().unwrap_or_else(|| fl!("widget-hardware-no-gpu"));
+        vec![format!("{} · {}", cpu, counts), gpu]
+    }
+}
+
+/// CPU model and counts in the text of `/proc/cpuinfo`.
+fn parse_cpuinfo(cpuinfo: &str) -> HardwareInfo {
+    let mut model = None;
+    let mut board_model = None;
+    let mut threads = 0;
+    let mut cores = HashSet::new();
+    // Physical package of the processor entry being read
+    let mut package = None;
+    for line in cpuinfo.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "processor" => {
+                threads += 1;
+                package = None;
+            }
+            "model name" if model.is_none() => model = Some(value.to_string()),
+            "Model" => board_model = Some(value.to_string()),
+            "physical id" => package = Some(value.to_string()),
+            "core id" => {
+                cores.insert((package.clone(), value.to_string()));
+            }
+            _ => {}
+        }
+    }
+    HardwareInfo {
+        cpu_model: model.or(board_model).map(|model| clean_cpu_model(&model)).filter(|model| !model.is_empty()),
+        cores: (!cores.is_empty()).then_some(cores.len()),
+        threads,
+        gpu_name: None,
+    }
+}
+
+/// CPU model without marketing noise.
+///
+/// `Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz` → `Intel Core i7-8700K`,
+/// `AMD Ryzen 7 5800X 8-Core Processor` → `AMD Ryzen 7 5800X`.
+fn clean_cpu_model(model: &str) -> String {
+    let model = model.split(" @ ").next().unwrap_or(model);
+    let model = model.replace("(R)", "").replace("(r)", "").replace("(TM)", "").replace("(tm)", "");
+    let mut words: Vec<&str> = model.split_whitespace().collect();
+    if words.last().is_some_and(|word| *word == "Processor") {
+        words.pop();
+        // "8-Core", "Six-Core", "Quad-Core"
+        if words.last().is_some_and(|word| word.ends_with("-Core")) {
+            words.pop();
+        }
+    }
+    if words.last().is_some_and(|word| *word == "CPU") {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Name of the GPU behind `card`, from udev's hardware database.
+fn gpu_name(card: &GpuCard) -> String {
+    let vendor = match card.driver.as_str() {
+        "amdgpu" | "radeon" => "AMD",
+        "nvidia" | "nouveau" => "NVIDIA",
+        "i915" | "xe" => "Intel",
+        driver => driver,
+    };
+    let model = std::fs::canonicalize(card.path.join("device"))
+        .ok()
+        .and_then(|device| Some(device.file_name()?.to_str()?.to_string()))
+        .and_then(|slot| std::fs::read_to_string(Path::new(UDEV_DATA).join(format!("+pci:{}", slot))).ok())
+        .and_then(|properties| udev_model(&properties));
+    match model {
+        Some(model) => format!("{} {}", vendor, model),
+        None => format!("{} GPU", vendor),
+    }
+}
+
+/// Marketing name in udev's properties of a PCI device.
+///
+/// The database names the chip first and the products in brackets
+/// (`Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]`); the products read better.
+fn udev_model(properties: &str) -> Option<String> {
+    let model = properties.lines().find_map(|line| line.strip_prefix("E:ID_MODEL_FROM_DATABASE="))?.trim();
+    let products = model
+        .split_once('[')
+        .and_then(|(_, rest)| rest.rsplit_once(']'))
+        .map(|(products, _)| products.trim())
+        .filter(|products| !products.is_empty());
+    Some(products.unwrap_or(model).to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        let cpuinfo = "processor\t: 0\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+                       processor\t: 1\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\nphysical id\t: 0\ncore id\t\t: 1\n\n\
+                       processor\t: 2\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\nphysical id\t: 0\ncore id\t\t: 0\n";
+        let info = parse_cpuinfo(cpuinfo);
+        assert_eq!(info.cpu_model.as_deref(), Some("AMD Ryzen 7 5800X"));
+        assert_eq!((info.cores, info.threads), (Some(2), 3));
+
+        let info = parse_cpuinfo("processor\t: 0\nprocessor\t: 1\nModel\t\t: Raspberry Pi 4 Model B Rev 1.4\n");
+        assert_eq!(info.cpu_model.as_deref(), Some("Raspberry Pi 4 Model B Rev 1.4"));
+        assert_eq!((info.cores, info.threads), (None, 2));
+
+        assert_eq!(clean_cpu_model("Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz"), "Intel Core i7-8700K");
+    }
+
+    #[test]
+    fn test_udev_model() {
+        let properties = "I:123\nE:ID_PCI_CLASS_FROM_DATABASE=Display controller\n\
+                          E:ID_MODEL_FROM_DATABASE=Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]\n";
+        assert_eq!(udev_model(properties).as_deref(), Some("Radeon RX 6800/6800 XT / 6900 XT"));
+        assert_eq!(udev_model("E:ID_MODEL_FROM_DATABASE=Virtio 1.0 GPU\n").as_deref(), Some("Virtio 1.0 GPU"));
+        assert_eq!(udev_model("E:ID_VENDOR_FROM_DATABASE=Intel Corporation\n"), None);
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! System Health
+//!
+//! Failed systemd units and, optionally, pending package updates, checked
+//! on a long interval:
+//!
+//! ```text
+//! systemd (system + user) ──busctl ListUnitsFiltered "failed"──┐
+//!                                                               ├──► report() ◄── draw (badges)
+//! update command (e.g. checkupdates) ──one update per line──────┘
+//!                        refresher thread, every health_refresh_minutes
+//! ```
+//!
+//! Units are listed over D-Bus with `busctl`, the same as `systemctl
+//! --failed` and `systemctl --user --failed` would. A missing user manager
+//! is not an error; a missing system manager is.
+//!
+//! The update command is any shell command printing one pending update per
+//! line (`checkupdates`, `dnf check-update -q`, `apt list --upgradable`
+//! with its header filtered out, ...). A non-zero exit without output on
+//! stderr counts as "no updates", since that is how `checkupdates` and
+//! `dnf check-update` report it.
+
+use std::process::Command;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use super::exec::{CommandExt, QUICK_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::Config;
+
+/// Longest an update command may run (it may sync package databases first).
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthSettings {
+    /// Shell command listing pending updates (empty to skip)
+    pub update_command: String,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn health_settings(config: &Config) -> Option<HealthSettings> {
+    config.show_system_health.then(|| HealthSettings {
+        update_command: config.health_update_command.trim().to_string(),
+        refresh_minutes: config.health_refresh_minutes,
+    })
+}
+
+/// Result of one refresh.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthReport {
+    /// Names of failed units, system units first
+    pub failed_units: Vec<String>,
+    /// Pending updates (None without an update command, or if it failed)
+    pub updates: Option<usize>,
+}
+
+/// Refreshes the system health report on a background thread.
+pub type HealthMonitor = Refresher<HealthSettings, HealthReport>;
+
+impl RefreshSettings for HealthSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl HealthMonitor {
+    /// Start checking units, updates and reboots.
+    pub fn new(settings: Option<HealthSettings>) -> Self {
+        Refresher::spawn("system-health", settings, |settings: &HealthSettings| {
+            let (report, error) = refresh(settings);
+            log::debug!(
+                "System health refreshed: {} failed units, {:?} updates",
+                report.failed_units.len(),
+                report.updates
+            );
+            (Some(report), error)
+        })
+    }
+
+    /// Report of the last refresh.
+    pub fn report(&self) -> Option<HealthReport> {
+        self.value()
+    }
+}
+
+/// Check units and updates, collecting what went wrong.
+fn refresh(settings: &HealthSettings) -> (HealthReport, Option<String>) {
+    let mut report = HealthReport::default();
+    let mut errors = Vec::new();
+
+    match failed_units("--system") {
+        Ok(units) => report.failed_units = units,
+        Err(e) => errors.push(format!("systemd not reachable ({})", e)),
+    }
+    match failed_units("--user") {
+        Ok(units) => report.failed_units.extend(units),
+        Err(e) => log::debug!("No user systemd instance: {}", e),
+    }
+
+    if !settings.update_command.is_empty() {
+        match count_updates(&settings.update_command) {
+            Ok(count) => report.updates = Some(count),
+            Err(e) => errors.push(format!("Update command: {}", e)),
+        }
+    }
+
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    (report, error)
+}
+
+/// Names of the failed units of the system (`--system`) or user (`--user`)
+/// manager.
+fn failed_units(bus: &str) -> Result<Vec<String>, String> {
+    let output = Command::new("busctl")
+        .args([bus, "--json=short", "call"])
+        .args([
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+            "ListUnitsFiltered",
+            "as",
+            "1",
+            "failed",
+        ])
+        .bounded_output(QUICK_TIMEOUT)
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("busctl failed").trim().to_string());
+    }
+    let reply: Value = serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    Ok(unit_names(&reply))
+}
+
+/// Unit names in a `ListUnitsFiltered` reply.
+///
+/// The reply is `a(ssssssouso)`: one array per unit, its name first.
+fn unit_names(reply: &Value) -> Vec<String> {
+    reply
+        .get("data")
+        .and_then(|data| data.get(0))
+        .and_then(Value::as_array)
+        .map(|units| {
+            units
+                .iter()
+                .filter_map(|unit| unit.get(0).and_then(Value::as_str).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Run the update command and count the updates it lists.
+fn count_updates(command: &str) -> Result<usize, String> {
+    let output = Command::new("sh")
+        .args(["-c", command])
+        .bounded_output(UPDATE_TIMEOUT)
+        .map_err(|e| e.to_string())?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() && stdout.trim().is_empty() && !stderr.trim().is_empty() {
+        return Err(stderr.lines().next().unwrap_or_default().trim().to_string());
+    }
+    Ok(count_lines(&stdout))
+}
+
+/// Number of non-blank lines.
+fn count_lines(text: &str) -> usize {
+    text.lines().filter(|line| !line.trim().is_empty()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_names() {
+        let reply: Value = serde_json::from_str(
+            r#"{"type":"a(ssssssouso)","data":[[
+                ["foo.service","Foo","loaded","failed","failed","","/org/freedesktop/systemd1/unit/foo_2eservice",0,"","/"],
+                ["bar.mount","Bar","loaded","failed","failed","","/org/freedesktop/systemd1/unit/bar_2emount",0,"","/"]
+            ]]}"#,
+        )
+        .unwrap();
+        assert_eq!(unit_names(&reply), ["foo.service", "bar.mount"]);
+
+        let empty: Value = serde_json::from_str(r#"{"type":"a(ssssssouso)","data":[[]]}"#).unwrap();
+        assert!(unit_names(&empty).is_empty());
+        assert_eq!(count_lines("linux 6.9.1 -> 6.9.2\n\nmesa 24.1 -> 24.2\n"), 2);
+    }
+}
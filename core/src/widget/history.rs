@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Metric History
+//!
+//! Recent samples of one metric, kept in memory for the small graphs drawn
+//! under a section (unlike [`super::recorder`], nothing is written to disk).
+//!
+//! Samples older than the window, measured from the newest sample, are
+//! dropped on every push, so a history holds at most one window of data:
+//!
+//! ```text
+//!  window ──────────────────────────►
+//! │ ·  ·  ·  ·  ·  ·  ·  ·  ·  ·  · │  ← push(t, v) appends here
+//! ▲ dropped once older than the window
+//! ```
+
+use std::collections::VecDeque;
+
+/// Samples of one metric within a time window.
+#[derive(Debug, Clone)]
+pub struct History {
+    /// Length of the window in milliseconds
+    window_ms: u64,
+    /// `(timestamp_ms, value)` pairs, oldest first
+    samples: VecDeque<(u64, f64)>,
+}
+
+impl History {
+    /// Create an empty history covering `window_secs` seconds.
+    pub fn new(window_secs: u32) -> Self {
+        Self {
+            window_ms: window_secs.max(1) as u64 * 1000,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Change the window length, dropping samples that no longer fit.
+    pub fn set_window(&mut self, window_secs: u32) {
+        self.window_ms = window_secs.max(1) as u64 * 1000;
+        self.trim();
+    }
+
+    /// Length of the window in milliseconds.
+    pub fn window_ms(&self) -> u64 {
+        self.window_ms
+    }
+
+    /// Append a sample taken at `timestamp_ms` (Unix ms).
+    ///
+    /// A sample older than the newest one (clock stepped back) clears the
+    /// history instead of drawing a line back in time.
+    pub fn push(&mut self, timestamp_ms: u64, value: f64) {
+        if self.samples.back().is_some_and(|(last, _)| *last > timestamp_ms) {
+            self.samples.clear();
+        }
+        self.samples.push_back((timestamp_ms, value));
+        self.trim();
+    }
+
+    /// `(timestamp_ms, value)` pairs, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = (u64, f64)> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// Timestamp of the newest sample.
+    pub fn newest(&self) -> Option<u64> {
+        self.samples.back().map(|(timestamp, _)| *timestamp)
+    }
+
+    /// Largest value in the window (0 when empty).
+    pub fn max(&self) -> f64 {
+        self.samples.iter().map(|(_, value)| *value).fold(0.0, f64::max)
+    }
+
+    /// Lowest and highest samples in the window (the first of equal ones).
+    pub fn extremes(&self) -> Option<((u64, f64), (u64, f64))> {
+        let first = *self.samples.front()?;
+        Some(self.samples.iter().fold((first, first), |(low, high), &sample| {
+            (if sample.1 < low.1 { sample } else { low }, if sample.1 > high.1 { sample } else { high })
+        }))
+    }
+
+    /// Mean of the values in the window.
+    pub fn average(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().map(|(_, value)| *value).sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// Whether there are at least two samples to draw a line between.
+    pub fn is_drawable(&self) -> bool {
+        self.samples.len() >= 2
+    }
+
+    /// Drop samples older than the window, measured from the newest one.
+    fn trim(&mut self) {
+        let Some(newest) = self.newest() else {
+            return;
+        };
+        let oldest = newest.saturating_sub(self.window_ms);
+        while self.samples.front().is_some_and(|(timestamp, _)| *timestamp < oldest) {
+            self.samples.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_window() {
+        let mut history = History::new(10);
+        for second in 0..=20 {
+            history.push(second * 1000, second as f64);
+        }
+        // 10..=20 s remain
+        assert_eq!(history.samples().count(), 11);
+        assert_eq!(history.samples().next(), Some((10_000, 10.0)));
+        assert_eq!(history.max(), 20.0);
+        assert_eq!(history.extremes(), Some(((10_000, 10.0), (20_000, 20.0))));
+        assert_eq!(history.average(), Some(15.0));
+
+        history.set_window(5);
+        assert_eq!(history.samples().next(), Some((15_000, 15.0)));
+
+        // Clock stepped back: start over
+        history.push(1000, 1.0);
+        assert_eq!(history.samples().collect::<Vec<_>>(), [(1000, 1.0)]);
+        assert!(!history.is_drawable());
+        assert_eq!(History::new(10).average(), None);
+    }
+}
@@ -0,0 +1,363 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Shared HTTP Client
+//!
+//! A single process-wide HTTP client used by every monitor that talks to a
+//! web API (OpenWeatherMap, the Cider REST API, album art downloads).
+//!
+//! ## Why a Shared Client?
+//!
+//! The media monitor used to spawn a `curl` process for every poll (twice per
+//! second) and every playback command. A shared `reqwest` client instead:
+//!
+//! - Reuses connections (keep-alive) to `localhost:10767`
+//! - Avoids fork/exec overhead on every poll
+//! - Returns structured errors instead of opaque exit codes
+//! - Enforces per-request timeouts consistently
+//!
+//! ## Threading
+//!
+//! The client uses `reqwest::blocking`, which runs its own small runtime
+//! internally. All calls are made from monitor background threads, never from
+//! the render loop, so blocking is fine.
+//!
+//! ```text
+//! WeatherMonitor thread ──┐
+//!                         ├──► client() ──► reqwest runtime ──► network
+//! MediaMonitor thread ────┘
+//! ```
+//!
+//! ## Retry & Backoff
+//!
+//! [`Backoff`] tracks consecutive failures for one provider and computes when
+//! the next attempt is allowed: the delay doubles on every failure up to a
+//! cap, and HTTP 429 responses honor the server's `Retry-After` header.
+
+use serde::de::DeserializeOwned;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Client Configuration
+// ============================================================================
+
+/// Default timeout for requests that don't specify their own.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connection establishment timeout.
+///
+/// Kept short because most endpoints are either local (Cider) or should
+/// respond quickly; a slow connect usually means the network is down.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Lazily-initialized shared client.
+static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+
+/// Get the shared HTTP client, building it on first use.
+fn client() -> &'static reqwest::blocking::Client {
+    CLIENT.get_or_init(|| {
+        reqwest::blocking::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .user_agent(concat!("cosmic-monitor-widget/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_else(|e| {
+                log::error!("Failed to build HTTP client, using defaults: {}", e);
+                reqwest::blocking::Client::new()
+            })
+    })
+}
+
+// ============================================================================
+// Error Type
+// ============================================================================
+
+/// Errors returned by the HTTP helpers.
+#[derive(Debug)]
+pub enum HttpError {
+    /// Connection failure, timeout, or body decoding error
+    Request(reqwest::Error),
+    /// Server answered with a non-2xx status code
+    Status(u16),
+    /// Server answered 429 Too Many Requests, with the parsed `Retry-After` delay
+    RateLimited(Option<Duration>),
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::Request(e) if e.is_timeout() => write!(f, "request timed out"),
+            HttpError::Request(e) if e.is_connect() => write!(f, "connection failed: {}", e),
+            HttpError::Request(e) => write!(f, "request failed: {}", e),
+            HttpError::Status(code) => write!(f, "HTTP status {}", code),
+            HttpError::RateLimited(Some(delay)) => {
+                write!(f, "rate limited (retry after {}s)", delay.as_secs())
+            }
+            HttpError::RateLimited(None) => write!(f, "rate limited"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(e: reqwest::Error) -> Self {
+        HttpError::Request(e)
+    }
+}
+
+/// Result type for HTTP helpers.
+pub type HttpResult<T> = Result<T, HttpError>;
+
+// ============================================================================
+// Request Helpers
+// ============================================================================
+
+/// Send a prepared request and reject non-success status codes.
+fn send(request: reqwest::blocking::RequestBuilder) -> HttpResult<reqwest::blocking::Response> {
+    let response = request.send()?;
+    let status = response.status();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        return Err(HttpError::RateLimited(retry_after));
+    }
+    if !status.is_success() {
+        return Err(HttpError::Status(status.as_u16()));
+    }
+    Ok(response)
+}
+
+/// Parse a `Retry-After` header value as a number of seconds.
+///
+/// The HTTP-date form is rarely used by APIs and is ignored (the caller
+/// falls back to its own exponential backoff).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Apply extra headers to a request.
+fn with_headers(
+    mut request: reqwest::blocking::RequestBuilder,
+    headers: &[(&str, &str)],
+) -> reqwest::blocking::RequestBuilder {
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    request
+}
+
+/// GET a URL and deserialize the JSON body.
+///
+/// # Arguments
+///
+/// * `url` - Full request URL
+/// * `headers` - Extra headers as (name, value) pairs
+/// * `timeout` - Total request timeout
+pub fn get_json<T: DeserializeOwned>(url: &str, headers: &[(&str, &str)], timeout: Duration) -> HttpResult<T> {
+    let request = with_headers(client().get(url), headers).timeout(timeout);
+    Ok(send(request)?.json()?)
+}
+
+/// GET a URL and return the body as text.
+///
+/// # Arguments
+///
+/// * `url` - Full request URL
+/// * `headers` - Extra headers as (name, value) pairs
+/// * `timeout` - Total request timeout
+pub fn get_text(url: &str, headers: &[(&str, &str)], timeout: Duration) -> HttpResult<String> {
+    let request = with_headers(client().get(url), headers).timeout(timeout);
+    Ok(send(request)?.text()?)
+}
+
+/// GET a URL and return the raw body bytes.
+///
+/// Follows redirects (used for album art CDNs).
+///
+/// # Arguments
+///
+/// * `url` - Full request URL
+/// * `timeout` - Total request timeout
+pub fn get_bytes(url: &str, timeout: Duration) -> HttpResult<Vec<u8>> {
+    let request = client().get(url).timeout(timeout);
+    Ok(send(request)?.bytes()?.to_vec())
+}
+
+/// POST to a URL, optionally with a JSON body, ignoring the response body.
+///
+/// # Arguments
+///
+/// * `url` - Full request URL
+/// * `headers` - Extra headers as (name, value) pairs
+/// * `json_body` - Optional JSON value sent with `Content-Type: application/json`
+/// * `timeout` - Total request timeout
+pub fn post(
+    url: &str,
+    headers: &[(&str, &str)],
+    json_body: Option<&serde_json::Value>,
+    timeout: Duration,
+) -> HttpResult<()> {
+    let mut request = with_headers(client().post(url), headers).timeout(timeout);
+    if let Some(body) = json_body {
+        request = request.json(body);
+    }
+    send(request)?;
+    Ok(())
+}
+
+// ============================================================================
+// Retry Backoff
+// ============================================================================
+
+/// Exponential backoff state for a single HTTP provider.
+///
+/// Not thread-safe by itself; wrap in `Arc<Mutex<..>>` when the request
+/// scheduling and the fetch happen on different threads.
+///
+/// # Delay Schedule
+///
+/// ```text
+/// failure 1: base
+/// failure 2: base × 2
+/// failure 3: base × 4    ... capped at `max`
+/// HTTP 429:  max(Retry-After, exponential delay)
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    /// Delay after the first failure
+    base: Duration,
+    /// Upper bound for the exponential delay
+    max: Duration,
+    /// Consecutive failures since the last success
+    failures: u32,
+    /// Earliest time the next attempt is allowed (None = no restriction)
+    next_attempt: Option<Instant>,
+}
+
+impl Backoff {
+    /// Create a backoff with the given initial delay and cap.
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            failures: 0,
+            next_attempt: None,
+        }
+    }
+
+    /// Whether a request may be made now.
+    pub fn ready(&self) -> bool {
+        self.next_attempt.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Number of consecutive failures since the last success.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Time remaining until the next attempt is allowed, if backing off.
+    pub fn retry_in(&self) -> Option<Duration> {
+        self.next_attempt
+            .map(|at| at.saturating_duration_since(Instant::now()))
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Allow the next attempt immediately, keeping the failure count.
+    ///
+    /// Used after resume from suspend, when a pending delay (measured in
+    /// monotonic time) would otherwise keep running as if no time had passed.
+    pub fn retry_now(&mut self) {
+        self.next_attempt = None;
+    }
+
+    /// Reset after a successful request.
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+        self.next_attempt = None;
+    }
+
+    /// Record a failed request and schedule the next attempt.
+    ///
+    /// # Returns
+    ///
+    /// The delay until the next attempt is allowed.
+    pub fn record_failure(&mut self, error: &HttpError) -> Duration {
+        self.failures = self.failures.saturating_add(1);
+
+        // base × 2^(failures - 1), saturating well before overflow
+        let exponent = (self.failures - 1).min(16);
+        let mut delay = self.base.saturating_mul(1 << exponent).min(self.max);
+
+        // The server knows best when it is rate limiting us
+        if let HttpError::RateLimited(Some(retry_after)) = error {
+            delay = delay.max(*retry_after);
+        }
+
+        self.next_attempt = Some(Instant::now() + delay);
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Duration = Duration::from_secs(10);
+    const MAX: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let mut backoff = Backoff::new(BASE, MAX);
+        assert!(backoff.ready());
+
+        let delays: Vec<u64> = (0..5).map(|_| backoff.record_failure(&HttpError::Status(500)).as_secs()).collect();
+        assert_eq!(delays, vec![10, 20, 40, 60, 60]);
+        assert_eq!(backoff.failures(), 5);
+        assert!(!backoff.ready());
+    }
+
+    #[test]
+    fn test_backoff_honors_retry_after() {
+        // A longer Retry-After wins, even above the cap
+        let mut backoff = Backoff::new(BASE, MAX);
+        let delay = backoff.record_failure(&HttpError::RateLimited(Some(Duration::from_secs(120))));
+        assert_eq!(delay, Duration::from_secs(120));
+
+        // A shorter one doesn't undercut the exponential delay
+        let mut backoff = Backoff::new(BASE, MAX);
+        let delay = backoff.record_failure(&HttpError::RateLimited(Some(Duration::from_secs(1))));
+        assert_eq!(delay, BASE);
+
+        // Without one, 429 backs off like any other failure
+        let mut backoff = Backoff::new(BASE, MAX);
+        assert_eq!(backoff.record_failure(&HttpError::RateLimited(None)), BASE);
+    }
+
+    #[test]
+    fn test_backoff_resets_after_success() {
+        let mut backoff = Backoff::new(BASE, MAX);
+        backoff.record_failure(&HttpError::Status(500));
+        backoff.record_failure(&HttpError::Status(500));
+
+        backoff.record_success();
+        assert_eq!(backoff.failures(), 0);
+        assert!(backoff.ready());
+        assert_eq!(backoff.retry_in(), None);
+        assert_eq!(backoff.record_failure(&HttpError::Status(500)), BASE);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after(""), None);
+        assert_eq!(parse_retry_after("-1"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+        // HTTP-date form falls back to the exponential delay
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Machine Identity
+//!
+//! An optional line at the very top of the widget naming the machine and
+//! the logged-in user, for multi-machine setups and for streamers showing
+//! the widget on stream:
+//!
+//! ```text
+//! show_hostname  show_username  identity_label   line
+//! ✓              ✓              ""               alice@workstation
+//! ✓              ✗              ""               workstation
+//! ✗              ✓              ""               alice
+//! ✓ (either)                    "Streaming PC"   Streaming PC
+//! ```
+//!
+//! The custom label replaces the detected names (e.g., to keep a hostname
+//! off stream) but is only shown while one of the toggles is on. The line
+//! spans all columns and is left out of the compact strip.
+
+use crate::config::Config;
+
+/// Hostname and user name, read once at startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Identity {
+    /// Hostname ("workstation")
+    pub hostname: String,
+    /// Login name of the user running the widget ("alice")
+    pub username: String,
+}
+
+impl Identity {
+    /// Read the hostname and the user of this session.
+    pub fn detect() -> Self {
+        let hostname = sysinfo::System::host_name().unwrap_or_default();
+        let username = std::env::var("USER")
+            .or_else(|_| std::env::var("LOGNAME"))
+            .unwrap_or_default();
+        Self { hostname, username }
+    }
+
+    /// Text of the identity line, `None` while it's hidden or empty.
+    pub fn text(&self, config: &Config) -> Option<String> {
+        if !config.shows_identity() {
+            return None;
+        }
+        let label = config.identity_label.trim();
+        if !label.is_empty() {
+            return Some(label.to_string());
+        }
+        let hostname = Some(self.hostname.as_str()).filter(|name| config.show_hostname && !name.is_empty());
+        let username = Some(self.username.as_str()).filter(|name| config.show_username && !name.is_empty());
+        match (username, hostname) {
+            (Some(user), Some(host)) => Some(format!("{}@{}", user, host)),
+            (Some(name), None) | (None, Some(name)) => Some(name.to_string()),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_text() {
+        let identity = Identity { hostname: String::from("workstation"), username: String::from("alice") };
+        let mut config = Config::default();
+        assert_eq!(identity.text(&config), None);
+
+        config.show_hostname = true;
+        assert_eq!(identity.text(&config).as_deref(), Some("workstation"));
+        config.show_username = true;
+        assert_eq!(identity.text(&config).as_deref(), Some("alice@workstation"));
+        config.show_hostname = false;
+        assert_eq!(identity.text(&config).as_deref(), Some("alice"));
+
+        config.identity_label = String::from(" Streaming PC ");
+        assert_eq!(identity.text(&config).as_deref(), Some("Streaming PC"));
+        config.show_username = false;
+        assert_eq!(identity.text(&config), None);
+
+        let unknown = Identity::default();
+        config.identity_label.clear();
+        config.show_hostname = true;
+        assert_eq!(unknown.text(&config), None);
+    }
+}
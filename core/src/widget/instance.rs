@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Single-Instance Lock
+//!
+//! Only one widget may run per user: a second one would draw a second,
+//! overlapping surface. The running widget owns an abstract Unix socket
+//! (`@cosmic-monitor-widget-<uid>`), which the kernel releases as soon as
+//! the process exits, so a crash never leaves a stale lock behind.
+//!
+//! # Handshake
+//!
+//! A starting widget that finds the name taken connects and sends one line:
+//!
+//! - `ping`: the running widget answers with its PID, and the new one exits
+//!   with a message.
+//! - `replace` (`cosmic-monitor-widget --replace`): the running widget
+//!   answers with its PID and shuts down; the new one takes the name over
+//!   once it is released.
+//!
+//! ```text
+//! new widget ──bind──► taken ──connect──► "replace\n" ──► running widget
+//!      ▲                                      "<pid>\n" ◄──┘     │ mpsc
+//!      └─────────── retry bind until released ◄─────────────── exits
+//! ```
+//!
+//! Abstract sockets have no file permissions, so any local user could
+//! connect. The running widget checks the peer's credentials
+//! (`SO_PEERCRED`) and refuses requests from other users without replying.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::fd::AsRawFd;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixListener, UnixStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long `--replace` waits for the running widget to shut down.
+const REPLACE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Read/write timeout of one handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Held by the running widget for its whole lifetime.
+pub struct InstanceLock {
+    /// Signalled when another widget asked to replace this one
+    receiver: Receiver<()>,
+}
+
+impl InstanceLock {
+    /// Become the running widget.
+    ///
+    /// With `replace`, a widget that is already running is asked to shut
+    /// down first.
+    ///
+    /// # Errors
+    ///
+    /// - `AddrInUse`: another widget is running (and `replace` is false, or
+    ///   it didn't shut down in time); the message includes its PID
+    /// - other socket errors
+    pub fn acquire(replace: bool) -> io::Result<Self> {
+        let addr = socket_addr()?;
+        let deadline = Instant::now() + REPLACE_TIMEOUT;
+        let mut asked = false;
+
+        loop {
+            match UnixListener::bind_addr(&addr) {
+                Ok(listener) => return Ok(Self::listen(listener)),
+                Err(e) if e.kind() != io::ErrorKind::AddrInUse => return Err(e),
+                Err(_) => {}
+            }
+
+            // Waiting for the replaced widget to exit
+            if asked {
+                if Instant::now() >= deadline {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        "the running widget did not shut down in time",
+                    ));
+                }
+                thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+
+            let request = if replace { "replace" } else { "ping" };
+            match handshake(&addr, request) {
+                Ok(pid) if replace => {
+                    log::info!("Replacing running widget (PID {})", pid);
+                    asked = true;
+                }
+                Ok(pid) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        format!("another widget is already running (PID {}); use --replace to restart it", pid),
+                    ));
+                }
+                // It exited between our bind and connect: try again
+                Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Answer handshakes on a background thread.
+    fn listen(listener: UnixListener) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let owner = unsafe { libc::getuid() };
+        thread::spawn(move || {
+            for stream in listener.incoming().map_while(Result::ok) {
+                if let Err(e) = answer(stream, owner, &sender) {
+                    log::warn!("Instance handshake failed: {}", e);
+                }
+            }
+        });
+        Self { receiver }
+    }
+
+    /// Whether another widget asked this one to shut down (non-blocking).
+    pub fn replace_requested(&self) -> bool {
+        self.receiver.try_recv().is_ok()
+    }
+}
+
+/// Abstract socket name, per user.
+fn socket_addr() -> io::Result<SocketAddr> {
+    let uid = unsafe { libc::getuid() };
+    SocketAddr::from_abstract_name(format!("cosmic-monitor-widget-{}", uid))
+}
+
+/// Running widget side: reply with our PID and act on the request.
+///
+/// Requests from a process of another user than `owner` are refused
+/// (`PermissionDenied`) before reading them.
+fn answer(stream: UnixStream, owner: libc::uid_t, sender: &Sender<()>) -> io::Result<()> {
+    let peer = peer_uid(&stream)?;
+    if peer != owner {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("refused request from uid {}", peer),
+        ));
+    }
+
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut request = String::new();
+    BufReader::new(&stream).read_line(&mut request)?;
+    writeln!(&stream, "{}", std::process::id())?;
+
+    match request.trim() {
+        "ping" => log::info!("Another widget tried to start; told it we're running"),
+        "replace" => {
+            log::info!("Another widget is replacing this one, shutting down");
+            let _ = sender.send(());
+        }
+        other => log::warn!("Ignoring unknown instance request: {:?}", other),
+    }
+    Ok(())
+}
+
+/// User ID of the process at the other end of `stream`.
+fn peer_uid(stream: &UnixStream) -> io::Result<libc::uid_t> {
+    let mut cred = libc::ucred { pid: 0, uid: 0, gid: 0 };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut cred as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(cred.uid)
+}
+
+/// Starting widget side: send `request` and return the running widget's PID.
+fn handshake(addr: &SocketAddr, request: &str) -> io::Result<String> {
+    let stream = UnixStream::connect_addr(addr)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    writeln!(&stream, "{}", request)?;
+    let mut pid = String::new();
+    BufReader::new(&stream).read_line(&mut pid)?;
+    Ok(pid.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Send `request` over a socket pair and let [`answer`] handle it as
+    /// the running widget of `owner`; returns its result and the reply.
+    fn exchange(request: &str, owner: libc::uid_t, sender: &Sender<()>) -> (io::Result<()>, String) {
+        let (client, server) = UnixStream::pair().unwrap();
+        writeln!(&client, "{}", request).unwrap();
+        let result = answer(server, owner, sender);
+        // A refused request is closed unread, which resets the connection
+        let mut reply = String::new();
+        BufReader::new(&client).read_line(&mut reply).ok();
+        (result, reply)
+    }
+
+    #[test]
+    fn test_ping_answers_pid() {
+        let (sender, receiver) = mpsc::channel();
+        let (result, reply) = exchange("ping", unsafe { libc::getuid() }, &sender);
+        assert!(result.is_ok());
+        assert_eq!(reply.trim(), std::process::id().to_string());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_replace_requests_shutdown() {
+        let (sender, receiver) = mpsc::channel();
+        let (result, reply) = exchange("replace", unsafe { libc::getuid() }, &sender);
+        assert!(result.is_ok());
+        assert_eq!(reply.trim(), std::process::id().to_string());
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_other_user_refused() {
+        // A socket pair's peer is this process, so a different owner
+        // stands in for a connection from another user
+        let (sender, receiver) = mpsc::channel();
+        let other = unsafe { libc::getuid() }.wrapping_add(1);
+        let (result, reply) = exchange("replace", other, &sender);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert!(reply.is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Kernel Statistics
+//!
+//! Process and thread counts and the context switch rate for the System
+//! section, read on every sample:
+//!
+//! ```text
+//! /proc/[pid]/ ─── count ─────────────► processes   412
+//! /proc/loadavg "0.52 0.61 0.70 3/1893 ..." ──► threads 1893
+//! /proc/stat "ctxt 9876543" ── Δ / Δt ──► context switches 12.4k/s
+//! ```
+//!
+//! A runaway workload usually shows here before it shows in CPU usage: a
+//! fork loop drives the process count up, a thread leak the thread count,
+//! and lock contention or a busy-polling program the context switches.
+
+use std::fs;
+use std::time::Instant;
+
+/// Counts of one sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KernelStats {
+    /// Processes (thread groups) alive
+    pub processes: usize,
+    /// Kernel scheduling entities (every thread of every process)
+    pub threads: usize,
+    /// Context switches per second since the previous sample (None on the first)
+    pub context_switches_per_sec: Option<f64>,
+}
+
+/// Turns the kernel's counters into per-sample stats.
+#[derive(Debug, Default)]
+pub struct KernelStatsMonitor {
+    /// Context switches since boot at the previous sample, and when
+    previous: Option<(u64, Instant)>,
+    /// Stats of the latest sample
+    stats: Option<KernelStats>,
+}
+
+impl KernelStatsMonitor {
+    /// Read the counters.
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let switches = fs::read_to_string("/proc/stat").ok().and_then(|text| context_switches(&text));
+        let context_switches_per_sec = match (switches, self.previous) {
+            (Some(switches), Some((previous, at))) => {
+                let seconds = now.duration_since(at).as_secs_f64();
+                (seconds > 0.0).then(|| switches.saturating_sub(previous) as f64 / seconds)
+            }
+            _ => None,
+        };
+        self.previous = switches.map(|switches| (switches, now));
+
+        self.stats = Some(KernelStats {
+            processes: process_count(),
+            threads: fs::read_to_string("/proc/loadavg").ok().and_then(|text| thread_count(&text)).unwrap_or(0),
+            context_switches_per_sec,
+        });
+    }
+
+    /// Stats of the latest sample (None before the first).
+    pub fn stats(&self) -> Option<KernelStats> {
+        self.stats
+    }
+}
+
+/// Short count with a k/M suffix ("850", "12.4k", "1.2M").
+pub fn format_count(count: f64) -> String {
+    if count >= 1_000_000.0 {
+        format!("{:.1}M", count / 1_000_000.0)
+    } else if count >= 1_000.0 {
+        format!("{:.1}k", count / 1_000.0)
+    } else {
+        format!("{:.0}", count)
+    }
+}
+
+/// Number of processes: the numeric entries of /proc.
+fn process_count() -> usize {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.bytes().all(|b| b.is_ascii_digit())))
+        .count()
+}
+
+/// Thread count of /proc/loadavg (the total after the slash in "3/1893").
+fn thread_count(loadavg: &str) -> Option<usize> {
+    loadavg.split_whitespace().nth(3)?.split_once('/')?.1.parse().ok()
+}
+
+/// Context switches since boot from the `ctxt` line of /proc/stat.
+fn context_switches(stat: &str) -> Option<u64> {
+    stat.lines().find_map(|line| line.strip_prefix("ctxt ")?.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_counters() {
+        assert_eq!(thread_count("0.52 0.61 0.70 3/1893 40213\n"), Some(1893));
+        assert_eq!(thread_count("0.52 0.61\n"), None);
+        assert_eq!(context_switches("cpu  10 20 30\nintr 5 1 2\nctxt 9876543\nbtime 1700000000\n"), Some(9876543));
+
+        assert_eq!(format_count(850.4), "850");
+        assert_eq!(format_count(12_400.0), "12.4k");
+        assert_eq!(format_count(1_234_567.0), "1.2M");
+    }
+}
@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Widget Layout Calculations
+//!
+//! This module calculates the dynamic height of the widget based on which
+//! sections are enabled and how much content each section has.
+//!
+//! # Why Dynamic Height?
+//!
+//! The widget displays variable amounts of content:
+//! - Storage section grows with each mounted disk
+//! - Battery section grows with each device (system + Solaar)
+//! - Notifications section grows up to a maximum count
+//!
+//! Rather than allocating a fixed maximum height (which would waste space),
+//! we calculate exactly how much vertical space is needed.
+//!
+//! # Calculation Approach
+//!
+//! Each section measures itself (see [`super::sections`]), contributing a
+//! fixed header height plus per-item heights:
+//!
+//! ```text
+//! Section Height = Header (35px) + (Item Count × Item Height)
+//! ```
+//!
+//! The final height is the sum of all enabled sections plus padding.
+//!
+//! # Layout Modes
+//!
+//! Sections are always drawn for a fixed column width. The vertical mode
+//! stacks them in one column, or in two columns of similar height when
+//! `columns` is 2; the horizontal mode gives every visible
+//! section its own column, so the widget becomes one row suited to a
+//! screen edge. The compact mode replaces sections with a single row of
+//! short metric slots ([`CompactSlot`]), like a status bar.
+
+use super::sections;
+use crate::config::{Config, LayoutMode, WidgetSection};
+
+// ============================================================================
+// Height Constants (in pixels)
+// ============================================================================
+
+// These constants should ideally be shared with renderer.rs, but are
+// currently duplicated. Changes here must be mirrored in the renderer.
+
+const BASE_PADDING: u32 = 10;
+const BOTTOM_PADDING: u32 = 20;
+/// Spacing above a section with a header.
+pub const SECTION_SPACING: u32 = 10;
+/// Height of a section header.
+pub const HEADER_HEIGHT: u32 = 35;
+/// Height of the hostname/user line above the sections (see [`super::identity`]).
+pub const IDENTITY_HEIGHT: u32 = 24;
+const MINIMUM_HEIGHT: u32 = 100;
+
+/// Width of one column of sections (the section renderers draw for this width).
+pub const COLUMN_WIDTH: u32 = 370;
+/// Most columns the vertical layout can be split into.
+pub const MAX_COLUMNS: u32 = *crate::config::COLUMNS.end();
+/// Height of the single-row compact strip.
+pub const COMPACT_HEIGHT: u32 = 40;
+/// Width of one metric slot in the compact strip.
+pub const COMPACT_SLOT_WIDTH: u32 = 120;
+
+// ============================================================================
+// Layout Types
+// ============================================================================
+
+/// Item counts of the sections whose height depends on their content.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentCounts {
+    /// Mounted disks in the storage section
+    pub disks: usize,
+    /// Battery devices (system + Solaar)
+    pub batteries: usize,
+    /// Notifications (capped at max_notifications)
+    pub notifications: usize,
+    /// Media players (for pagination dots)
+    pub players: usize,
+    /// Total height of the loaded plugin blocks (see [`super::plugins::total_height`])
+    pub plugin_height: u32,
+    /// Upcoming calendar events (capped at calendar_max_events)
+    pub events: usize,
+    /// Processes listed under the GPU bar (at most [`super::gpu_processes::MAX_GPU_PROCESSES`])
+    pub gpu_processes: usize,
+    /// Drives with a health row in the storage section
+    pub drives: usize,
+    /// ZFS pools and btrfs filesystems in the storage pools section
+    pub pools: usize,
+    /// Running virtual machines in the virtual machines section
+    pub vms: usize,
+}
+
+/// One metric in the compact single-row layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactSlot {
+    /// Current time
+    Clock,
+    /// CPU usage
+    Cpu,
+    /// Memory usage
+    Memory,
+    /// GPU usage
+    Gpu,
+    /// CPU temperature
+    CpuTemp,
+    /// GPU temperature
+    GpuTemp,
+    /// Download rate
+    NetworkDown,
+    /// Upload rate
+    NetworkUp,
+    /// Current weather temperature
+    Weather,
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Calculate widget height (legacy API, assumes no batteries).
+///
+/// Use [`calculate_widget_height_with_all`] for full control.
+pub fn calculate_widget_height(config: &Config, disk_count: usize) -> u32 {
+    calculate_widget_height_with_batteries(config, disk_count, 0)
+}
+
+/// Calculate widget height with battery count (legacy API).
+///
+/// Use [`calculate_widget_height_with_all`] for full control.
+pub fn calculate_widget_height_with_batteries(config: &Config, disk_count: usize, battery_count: usize) -> u32 {
+    calculate_widget_height_with_all(config, disk_count, battery_count, 0, 0)
+}
+
+/// Calculate the required widget height based on enabled sections and content counts.
+///
+/// This is the height of the vertical layout; see [`calculate_widget_size`]
+/// for the other layout modes.
+///
+/// # Arguments
+///
+/// * `config` - Current configuration with enabled/disabled sections
+/// * `disk_count` - Number of mounted disks to display
+/// * `battery_count` - Number of battery devices (system + Solaar)
+/// * `notification_count` - Number of notifications (capped at max_notifications)
+/// * `player_count` - Number of media players (for pagination dots)
+///
+/// # Returns
+///
+/// Height in pixels, minimum 100px
+pub fn calculate_widget_height_with_all(config: &Config, disk_count: usize, battery_count: usize, notification_count: usize, player_count: usize) -> u32 {
+    let counts = ContentCounts {
+        disks: disk_count,
+        batteries: battery_count,
+        notifications: notification_count,
+        players: player_count,
+        ..Default::default()
+    };
+    column_height(config, &config.section_order, counts)
+}
+
+/// Calculate the widget size for the configured layout mode.
+///
+/// ```text
+/// Vertical:    one column, sections stacked      370 × Σ heights
+///              (two columns: 740 × taller column)
+/// Horizontal:  one column per visible section    n·370 × tallest section
+/// Compact:     single row of metric slots        n·120 × 40
+/// ```
+///
+/// # Returns
+///
+/// (width, height) in pixels
+pub fn calculate_widget_size(config: &Config, counts: ContentCounts) -> (u32, u32) {
+    if config.layout_mode == LayoutMode::Compact {
+        let slots = compact_slots(config).len().max(1) as u32;
+        return (slots * COMPACT_SLOT_WIDTH + 2 * BASE_PADDING, COMPACT_HEIGHT);
+    }
+    
+    let columns = section_columns(config, counts);
+    let height = columns
+        .iter()
+        .map(|column| column_height(config, column, counts))
+        .max()
+        .unwrap_or(MINIMUM_HEIGHT);
+    (columns.len().max(1) as u32 * COLUMN_WIDTH, height)
+}
+
+/// Split the section order into the columns drawn side by side.
+///
+/// Empty for the compact mode, which draws metric slots instead.
+pub fn section_columns(config: &Config, counts: ContentCounts) -> Vec<Vec<WidgetSection>> {
+    match config.layout_mode {
+        LayoutMode::Vertical if config.columns.clamp(1, MAX_COLUMNS) > 1 => split_balanced(config, counts),
+        LayoutMode::Vertical => vec![config.section_order.clone()],
+        LayoutMode::Horizontal => config
+            .section_order
+            .iter()
+            .filter(|section| section_height(config, **section, counts) > 0)
+            .map(|section| vec![*section])
+            .collect(),
+        LayoutMode::Compact => Vec::new(),
+    }
+}
+
+/// Metric slots of the compact strip, in section order.
+pub fn compact_slots(config: &Config) -> Vec<CompactSlot> {
+    let mut slots = Vec::new();
+    for section in &config.section_order {
+        match section {
+            WidgetSection::Clock if config.show_clock => slots.push(CompactSlot::Clock),
+            WidgetSection::Utilization => {
+                if config.show_cpu {
+                    slots.push(CompactSlot::Cpu);
+                }
+                if config.show_memory {
+                    slots.push(CompactSlot::Memory);
+                }
+                if config.show_gpu {
+                    slots.push(CompactSlot::Gpu);
+                }
+            }
+            WidgetSection::Temperatures => {
+                if config.show_cpu_temp {
+                    slots.push(CompactSlot::CpuTemp);
+                }
+                if config.show_gpu_temp {
+                    slots.push(CompactSlot::GpuTemp);
+                }
+            }
+            WidgetSection::Network if config.show_network => {
+                slots.push(CompactSlot::NetworkDown);
+                slots.push(CompactSlot::NetworkUp);
+            }
+            WidgetSection::Weather if config.show_weather => slots.push(CompactSlot::Weather),
+            // Lists (disks, batteries, notifications, media) don't fit a single row
+            _ => {}
+        }
+    }
+    slots
+}
+
+/// Height of one section including the spacing before it, 0 if hidden.
+///
+/// Measured by the section itself (see [`super::sections::Section::measure`]).
+pub fn section_height(config: &Config, section: WidgetSection, counts: ContentCounts) -> u32 {
+    sections::section(section).measure(config, counts)
+}
+
+/// Split the visible sections in order into two columns of similar height.
+///
+/// Picks the split point that minimizes the taller column, so e.g.
+/// utilization ends up on the left and temperatures and weather on the right.
+fn split_balanced(config: &Config, counts: ContentCounts) -> Vec<Vec<WidgetSection>> {
+    let visible: Vec<WidgetSection> = config
+        .section_order
+        .iter()
+        .copied()
+        .filter(|section| section_height(config, *section, counts) > 0)
+        .collect();
+    if visible.len() < 2 {
+        return vec![visible];
+    }
+
+    let split = (1..visible.len())
+        .min_by_key(|&split| {
+            column_height(config, &visible[..split], counts).max(column_height(config, &visible[split..], counts))
+        })
+        .unwrap_or(1);
+    vec![visible[..split].to_vec(), visible[split..].to_vec()]
+}
+
+/// Height of a column of sections including padding and the identity
+/// line above all columns, minimum 100px.
+fn column_height(config: &Config, sections: &[WidgetSection], counts: ContentCounts) -> u32 {
+    let content: u32 = sections
+        .iter()
+        .map(|section| section_height(config, *section, counts))
+        .sum();
+    let identity = if config.shows_identity() { IDENTITY_HEIGHT } else { 0 };
+    (BASE_PADDING + identity + content + BOTTOM_PADDING).max(MINIMUM_HEIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_horizontal_uses_one_column_per_visible_section() {
+        let mut config = Config::default();
+        config.show_clock = false;
+        config.show_date = false;
+        let counts = ContentCounts { disks: 2, ..Default::default() };
+
+        let (_, vertical_height) = calculate_widget_size(&config, counts);
+        assert_eq!(vertical_height, calculate_widget_height_with_all(&config, 2, 0, 0, 0));
+
+        config.layout_mode = LayoutMode::Horizontal;
+        let columns = section_columns(&config, counts);
+        assert_eq!(columns, vec![vec![WidgetSection::Utilization], vec![WidgetSection::Storage]]);
+        let (width, height) = calculate_widget_size(&config, counts);
+        assert_eq!(width, 2 * COLUMN_WIDTH);
+        assert!(height < vertical_height);
+    }
+
+    #[test]
+    fn test_two_columns_balance_height() {
+        let mut config = Config::default();
+        config.show_gpu = true;
+        config.show_cpu_temp = true;
+        config.show_weather = true;
+        config.section_order = vec![WidgetSection::Utilization, WidgetSection::Temperatures, WidgetSection::Weather];
+        config.columns = 2;
+        let counts = ContentCounts::default();
+
+        let columns = section_columns(&config, counts);
+        assert_eq!(columns, vec![
+            vec![WidgetSection::Utilization],
+            vec![WidgetSection::Temperatures, WidgetSection::Weather],
+        ]);
+        let (width, _) = calculate_widget_size(&config, counts);
+        assert_eq!(width, 2 * COLUMN_WIDTH);
+    }
+}
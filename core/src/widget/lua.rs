@@ -0,0 +1,394 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Lua Runtime for Plugin Sections
+//!
+//! Loads and runs the scripts described in [`super::plugins`]. Only built
+//! with the `lua-plugins` cargo feature.
+//!
+//! Each script gets its own sandboxed Lua state (no `os` or `io`, capped in
+//! run time and memory) with a `draw` table whose functions append
+//! [`DrawOp`]s to a shared list. [`PluginHost::update`]
+//! clears that list, calls the script's `render(metrics)` and keeps the
+//! recorded ops as the plugin's [`PluginFrame`].
+
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+use mlua::{Function, HookTriggers, Lua, LuaOptions, LuaSerdeExt, SerializeOptions, StdLib, Table, VmState};
+
+use super::plugins::{plugin_dir, DrawOp, PluginFrame};
+use super::stats::StatsSnapshot;
+
+/// Height used when a script doesn't set `height`.
+const DEFAULT_HEIGHT: u32 = 60;
+
+/// Tallest block a plugin may reserve.
+const MAX_HEIGHT: u32 = 600;
+
+/// Most drawing calls kept per `render` (the rest are dropped).
+const MAX_OPS: usize = 500;
+
+/// Longest a script may run for loading or one `render` call.
+const TIME_BUDGET: Duration = Duration::from_millis(50);
+
+/// Most memory one script's Lua state may allocate.
+const MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+/// Instructions between two checks of the time budget.
+const HOOK_INSTRUCTIONS: u32 = 10_000;
+
+/// How often the plugin directory is checked for added, changed or removed files.
+const RELOAD_CHECK: Duration = Duration::from_secs(2);
+
+/// Default color of drawing calls (opaque white).
+const WHITE: (f64, f64, f64, f64) = (1.0, 1.0, 1.0, 1.0);
+
+// ============================================================================
+// Single Plugin
+// ============================================================================
+
+/// One loaded script and its Lua state.
+struct LuaPlugin {
+    /// Script file
+    path: PathBuf,
+    /// Modification time when loaded (for hot reload)
+    modified: Option<SystemTime>,
+    /// The script's own Lua state
+    lua: Lua,
+    /// Ops recorded by the `draw` functions during the current call
+    ops: Rc<RefCell<Vec<DrawOp>>>,
+    /// Deadline of the running call, checked by the instruction hook
+    deadline: Rc<Cell<Instant>>,
+    /// Result of the last successful `render`
+    frame: PluginFrame,
+    /// Error of the last `render`, if it failed
+    error: Option<String>,
+}
+
+impl LuaPlugin {
+    /// Load a script and run its top level once.
+    ///
+    /// # Errors
+    ///
+    /// Returns a readable message if the file can't be read, doesn't
+    /// parse, times out, or defines no `render` function.
+    fn load(path: &Path) -> Result<Self, String> {
+        let source = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let file_name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let lua = Lua::new_with(StdLib::MATH | StdLib::STRING | StdLib::TABLE | StdLib::UTF8, LuaOptions::default())
+            .map_err(|e| e.to_string())?;
+        lua.set_memory_limit(MEMORY_LIMIT).map_err(|e| e.to_string())?;
+        let ops = Rc::new(RefCell::new(Vec::new()));
+        let deadline = Rc::new(Cell::new(Instant::now() + TIME_BUDGET));
+
+        // Abort runaway scripts (infinite loops) once the budget is spent
+        let hook_deadline = Rc::clone(&deadline);
+        lua.set_hook(HookTriggers::new().every_nth_instruction(HOOK_INSTRUCTIONS), move |_lua, _debug| {
+            if Instant::now() > hook_deadline.get() {
+                Err(mlua::Error::runtime("script exceeded its time budget"))
+            } else {
+                Ok(VmState::Continue)
+            }
+        });
+
+        let draw = draw_table(&lua, &ops).map_err(|e| e.to_string())?;
+        lua.globals().set("draw", draw).map_err(|e| e.to_string())?;
+        lua.load(&source)
+            .set_name(format!("={}", file_name))
+            .exec()
+            .map_err(|e| e.to_string())?;
+
+        let globals = lua.globals();
+        let name: Option<String> = globals.get("name").map_err(|e| e.to_string())?;
+        let height: Option<u32> = globals.get("height").map_err(|e| e.to_string())?;
+        let render: Option<Function> = globals.get("render").map_err(|e| e.to_string())?;
+        if render.is_none() {
+            return Err(String::from("script defines no render(metrics) function"));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified: modified_time(path),
+            lua,
+            ops,
+            deadline,
+            frame: PluginFrame {
+                name: name.unwrap_or(file_name),
+                height: height.unwrap_or(DEFAULT_HEIGHT).clamp(1, MAX_HEIGHT),
+                ops: Vec::new(),
+            },
+            error: None,
+        })
+    }
+
+    /// Call the script's `render` with the latest metrics.
+    ///
+    /// On failure the previous frame is kept and the error is remembered.
+    fn render(&mut self, metrics: &StatsSnapshot) {
+        self.ops.borrow_mut().clear();
+        self.deadline.set(Instant::now() + TIME_BUDGET);
+
+        let result = (|| -> mlua::Result<()> {
+            let options = SerializeOptions::new().serialize_none_to_null(false);
+            let metrics = self.lua.to_value_with(metrics, options)?;
+            let render: Function = self.lua.globals().get("render")?;
+            render.call::<()>(metrics)
+        })();
+
+        match result {
+            Ok(()) => {
+                self.frame.ops = std::mem::take(&mut *self.ops.borrow_mut());
+                self.error = None;
+            }
+            Err(e) => {
+                let message = e.to_string();
+                if self.error.as_ref() != Some(&message) {
+                    log::warn!("Plugin '{}' failed: {}", self.frame.name, message);
+                    super::diagnostics::record_error("plugins", format!("{}: {}", self.frame.name, message));
+                }
+                self.error = Some(message);
+            }
+        }
+    }
+}
+
+/// Build the `draw` table whose functions record into `ops`.
+#[allow(clippy::type_complexity)]
+fn draw_table(lua: &Lua, ops: &Rc<RefCell<Vec<DrawOp>>>) -> mlua::Result<Table> {
+    let draw = lua.create_table()?;
+
+    let rect_ops = Rc::clone(ops);
+    draw.set(
+        "rect",
+        lua.create_function(move |_, (x, y, width, height, r, g, b, a): (f64, f64, f64, f64, Option<f64>, Option<f64>, Option<f64>, Option<f64>)| {
+            push(&rect_ops, DrawOp::Rect { x, y, width, height, color: color(r, g, b, a) });
+            Ok(())
+        })?,
+    )?;
+
+    let arc_ops = Rc::clone(ops);
+    draw.set(
+        "arc",
+        lua.create_function(
+            move |_, (cx, cy, radius, start, end, line_width, r, g, b, a): (f64, f64, f64, f64, f64, f64, Option<f64>, Option<f64>, Option<f64>, Option<f64>)| {
+                push(
+                    &arc_ops,
+                    DrawOp::Arc {
+                        cx,
+                        cy,
+                        radius,
+                        start: start.to_radians(),
+                        end: end.to_radians(),
+                        line_width,
+                        color: color(r, g, b, a),
+                    },
+                );
+                Ok(())
+            },
+        )?,
+    )?;
+
+    let text_ops = Rc::clone(ops);
+    draw.set(
+        "text",
+        lua.create_function(move |_, (x, y, text, size, r, g, b, a): (f64, f64, String, f64, Option<f64>, Option<f64>, Option<f64>, Option<f64>)| {
+            push(&text_ops, DrawOp::Text { x, y, text, size: size.clamp(4.0, 96.0), color: color(r, g, b, a) });
+            Ok(())
+        })?,
+    )?;
+
+    Ok(draw)
+}
+
+/// Record an op unless the per-render limit is reached.
+fn push(ops: &Rc<RefCell<Vec<DrawOp>>>, op: DrawOp) {
+    let mut ops = ops.borrow_mut();
+    if ops.len() < MAX_OPS {
+        ops.push(op);
+    }
+}
+
+/// Color from optional script arguments, defaulting to opaque white.
+fn color(r: Option<f64>, g: Option<f64>, b: Option<f64>, a: Option<f64>) -> (f64, f64, f64, f64) {
+    (
+        r.unwrap_or(WHITE.0).clamp(0.0, 1.0),
+        g.unwrap_or(WHITE.1).clamp(0.0, 1.0),
+        b.unwrap_or(WHITE.2).clamp(0.0, 1.0),
+        a.unwrap_or(WHITE.3).clamp(0.0, 1.0),
+    )
+}
+
+/// Modification time of a file, if available.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// ============================================================================
+// Plugin Host
+// ============================================================================
+
+/// All plugins of the plugin directory, reloaded when their files change.
+#[derive(Default)]
+pub struct PluginHost {
+    /// Loaded plugins, sorted by file name
+    plugins: Vec<LuaPlugin>,
+    /// Scripts that failed to load: (path, modification time, error)
+    failed: Vec<(PathBuf, Option<SystemTime>, String)>,
+    /// Frames of all loaded plugins, in file name order
+    frames: Vec<PluginFrame>,
+    /// Last scan of the plugin directory
+    last_scan: Option<Instant>,
+}
+
+impl PluginHost {
+    /// Create the host; scripts are loaded on the first update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reload changed scripts and render every plugin with the latest metrics.
+    pub fn update(&mut self, metrics: &StatsSnapshot) {
+        if self.last_scan.is_none_or(|last| last.elapsed() >= RELOAD_CHECK) {
+            self.last_scan = Some(Instant::now());
+            self.scan();
+        }
+        for plugin in &mut self.plugins {
+            plugin.render(metrics);
+        }
+        self.frames = self.plugins.iter().map(|plugin| plugin.frame.clone()).collect();
+    }
+
+    /// Frames of all loaded plugins.
+    pub fn frames(&self) -> &[PluginFrame] {
+        &self.frames
+    }
+
+    /// First load or render error, for the section's error badge.
+    pub fn error(&self) -> Option<String> {
+        let load_error = self.failed.iter().map(|(path, _, error)| {
+            let file = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+            format!("{}: {}", file, error)
+        });
+        let render_error = self
+            .plugins
+            .iter()
+            .filter_map(|plugin| plugin.error.as_ref().map(|error| format!("{}: {}", plugin.frame.name, error)));
+        load_error.chain(render_error).next()
+    }
+
+    /// Load new and changed scripts and drop removed ones.
+    fn scan(&mut self) {
+        let mut paths: Vec<PathBuf> = fs::read_dir(plugin_dir())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        let mut plugins = Vec::new();
+        let mut failed = Vec::new();
+        for path in paths {
+            let modified = modified_time(&path);
+
+            // Unchanged scripts keep their state (and their last error)
+            if let Some(index) = self.plugins.iter().position(|p| p.path == path && p.modified == modified) {
+                plugins.push(self.plugins.swap_remove(index));
+                continue;
+            }
+            if let Some(index) = self.failed.iter().position(|(p, m, _)| *p == path && *m == modified) {
+                failed.push(self.failed.swap_remove(index));
+                continue;
+            }
+
+            match LuaPlugin::load(&path) {
+                Ok(plugin) => {
+                    log::info!("Loaded plugin '{}' from {:?}", plugin.frame.name, path);
+                    plugins.push(plugin);
+                }
+                Err(error) => {
+                    log::warn!("Failed to load plugin {:?}: {}", path, error);
+                    super::diagnostics::record_error("plugins", format!("{}: {}", path.display(), error));
+                    failed.push((path, modified, error));
+                }
+            }
+        }
+
+        self.plugins = plugins;
+        self.failed = failed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load `source` as a plugin script named `name`.
+    fn load_script(name: &str, source: &str) -> Result<LuaPlugin, String> {
+        let path = std::env::temp_dir().join(format!("cosmic-monitor-lua-test-{}-{}.lua", std::process::id(), name));
+        fs::write(&path, source).unwrap();
+        let plugin = LuaPlugin::load(&path);
+        fs::remove_file(&path).unwrap();
+        plugin
+    }
+
+    #[test]
+    fn test_sandbox_has_no_os_or_io() {
+        let plugin = load_script("sandbox", "function render(metrics) end").unwrap();
+        let escaped: bool = plugin
+            .lua
+            .load("return (os and os.execute) ~= nil or (io and io.open) ~= nil")
+            .eval()
+            .unwrap();
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn test_infinite_loop_is_aborted() {
+        let error = load_script("load-loop", "while true do end").err().unwrap();
+        assert!(error.contains("time budget"), "{}", error);
+
+        let mut plugin = load_script("render-loop", "function render(metrics) while true do end end").unwrap();
+        plugin.render(&StatsSnapshot::default());
+        assert!(plugin.error.as_ref().unwrap().contains("time budget"));
+    }
+
+    #[test]
+    fn test_memory_is_limited() {
+        let mut plugin = load_script("memory", "function render(metrics) local s = string.rep('x', 64 * 1024 * 1024) end").unwrap();
+        plugin.render(&StatsSnapshot::default());
+        assert!(plugin.error.as_ref().unwrap().contains("memory"));
+    }
+
+    #[test]
+    fn test_draw_calls_are_recorded() {
+        let source = r#"
+            name = "Test"
+            function render(metrics)
+                draw.rect(1, 2, 3, 4, 1, 0, 0)
+                draw.text(5, 6, "hi", 200)
+            end
+        "#;
+        let mut plugin = load_script("draw", source).unwrap();
+        plugin.render(&StatsSnapshot::default());
+        assert_eq!(plugin.error, None);
+        assert_eq!(plugin.frame.name, "Test");
+        assert_eq!(plugin.frame.height, DEFAULT_HEIGHT);
+        assert_eq!(
+            plugin.frame.ops,
+            vec![
+                DrawOp::Rect { x: 1.0, y: 2.0, width: 3.0, height: 4.0, color: (1.0, 0.0, 0.0, 1.0) },
+                DrawOp::Text { x: 5.0, y: 6.0, text: String::from("hi"), size: 96.0, color: WHITE },
+            ]
+        );
+    }
+}
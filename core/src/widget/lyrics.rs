@@ -0,0 +1,300 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Lyrics
+//!
+//! Synced lyrics of the playing track from LRCLIB (lrclib.net), drawn one
+//! line at a time under the track info in the media section:
+//!
+//! ```text
+//! draw() ──set_track(artist, title, album, duration)──► wanted track
+//!                                                           │
+//!          fetcher thread: cached? ──no──► GET lrclib.net/api/get
+//!                                                           │
+//! draw() ◄──current()──── cache (last 32 tracks) ◄── parsed [mm:ss.xx] lines
+//!   └── line_at(position)
+//! ```
+//!
+//! LRCLIB needs no API key and matches on all four fields (the duration
+//! within a couple of seconds), so tracks without a known length aren't
+//! looked up. Tracks it doesn't know, or only has plain lyrics for, are
+//! cached as "no lyrics" so they aren't asked for again.
+//!
+//! LRCLIB is run by volunteers, so requests are spaced at least 5 seconds
+//! apart (skipping through a playlist only fetches the track that stays),
+//! and failures back off from 30 seconds up to 30 minutes.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use super::http::{self, Backoff, HttpError, HttpResult};
+use super::media::MediaInfo;
+
+/// How often the fetcher checks for a new track.
+const TICK: Duration = Duration::from_secs(1);
+
+/// LRCLIB's exact-match endpoint.
+const LRCLIB_URL: &str = "https://lrclib.net/api/get";
+
+/// Longest a request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shortest time between two requests.
+const MIN_REQUEST_GAP: Duration = Duration::from_secs(5);
+
+/// First retry delay after a failed request.
+const RETRY_BASE: Duration = Duration::from_secs(30);
+
+/// Upper bound for the retry delay (30 minutes).
+const RETRY_MAX: Duration = Duration::from_secs(1800);
+
+/// Tracks whose lyrics (or lack of them) are kept.
+const CACHE_SIZE: usize = 32;
+
+/// What LRCLIB looks lyrics up by.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrackKey {
+    /// Artist name
+    pub artist: String,
+    /// Track title
+    pub title: String,
+    /// Album name (may be empty)
+    pub album: String,
+    /// Track length in whole seconds
+    pub duration_secs: u64,
+}
+
+impl TrackKey {
+    /// Key of the track a player is on, `None` if it can't be looked up
+    /// (nothing playing, no artist, or unknown length).
+    pub fn from_media(info: &MediaInfo) -> Option<Self> {
+        let duration_secs = (info.duration + 500) / 1000;
+        (info.is_active() && !info.artist.is_empty() && duration_secs > 0).then(|| Self {
+            artist: info.artist.clone(),
+            title: info.title.clone(),
+            album: info.album.clone(),
+            duration_secs,
+        })
+    }
+}
+
+/// Timed lines of a track, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lyrics {
+    /// (start in milliseconds, text)
+    lines: Vec<(u64, String)>,
+}
+
+impl Lyrics {
+    /// Line being sung at `position_ms`, `None` before the first line and
+    /// in instrumental breaks.
+    pub fn line_at(&self, position_ms: u64) -> Option<&str> {
+        let index = self.lines.partition_point(|(start, _)| *start <= position_ms);
+        let (_, text) = self.lines.get(index.checked_sub(1)?)?;
+        Some(text.as_str()).filter(|text| !text.is_empty())
+    }
+}
+
+/// Reply of `GET /api/get` (only the fields used).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibTrack {
+    /// LRC text (`[mm:ss.xx] line`), `null` without synced lyrics
+    synced_lyrics: Option<String>,
+}
+
+/// Lyrics of the last few tracks; `None` for tracks without synced lyrics.
+#[derive(Debug, Default)]
+struct LyricsCache {
+    entries: HashMap<TrackKey, Option<Arc<Lyrics>>>,
+    /// Keys from oldest to newest
+    order: VecDeque<TrackKey>,
+}
+
+impl LyricsCache {
+    fn get(&self, key: &TrackKey) -> Option<&Option<Arc<Lyrics>>> {
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, key: TrackKey, lyrics: Option<Arc<Lyrics>>) {
+        if self.entries.insert(key.clone(), lyrics).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > CACHE_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Fetches lyrics of the playing track on a background thread.
+pub struct LyricsMonitor {
+    /// Track to show lyrics for (`None`: lyrics off or nothing playing)
+    wanted: Arc<Mutex<Option<TrackKey>>>,
+    /// Fetched lyrics
+    cache: Arc<Mutex<LyricsCache>>,
+    /// Why the last request failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl LyricsMonitor {
+    /// Create the monitor and start its fetcher thread.
+    pub fn new() -> Self {
+        let wanted = Arc::new(Mutex::new(None));
+        let cache = Arc::new(Mutex::new(LyricsCache::default()));
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_wanted = Arc::clone(&wanted);
+        let thread_cache = Arc::clone(&cache);
+        let thread_error = Arc::clone(&error);
+        std::thread::spawn(move || {
+            super::priority::enter_background("lyrics");
+            let mut last_request: Option<Instant> = None;
+            let mut backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+            loop {
+                // Stop once the monitor was dropped
+                if Arc::strong_count(&thread_wanted) == 1 {
+                    break;
+                }
+                std::thread::sleep(TICK);
+
+                let Some(key) = thread_wanted.lock().unwrap().clone() else {
+                    continue;
+                };
+                if thread_cache.lock().unwrap().get(&key).is_some() {
+                    continue;
+                }
+                let spaced = last_request.is_none_or(|at| at.elapsed() >= MIN_REQUEST_GAP);
+                if !spaced || !backoff.ready() {
+                    continue;
+                }
+
+                last_request = Some(Instant::now());
+                match fetch(&key) {
+                    Ok(lyrics) => {
+                        backoff.record_success();
+                        *thread_error.lock().unwrap() = None;
+                        log::debug!(
+                            "Lyrics for {} - {}: {} lines",
+                            key.artist,
+                            key.title,
+                            lyrics.as_ref().map_or(0, |lyrics| lyrics.lines.len())
+                        );
+                        thread_cache.lock().unwrap().insert(key, lyrics.map(Arc::new));
+                    }
+                    Err(e) => {
+                        let delay = backoff.record_failure(&e);
+                        log::warn!("Lyrics request failed, retrying in {:?}: {}", delay, e);
+                        *thread_error.lock().unwrap() = Some(e.to_string());
+                    }
+                }
+            }
+        });
+
+        Self { wanted, cache, error }
+    }
+
+    /// Follow the playing track (`None` stops fetching).
+    pub fn set_track(&self, key: Option<TrackKey>) {
+        *self.wanted.lock().unwrap() = key;
+    }
+
+    /// Lyrics of the followed track, once fetched (`None` also while
+    /// LRCLIB has no synced lyrics for it).
+    pub fn current(&self) -> Option<Arc<Lyrics>> {
+        let key = self.wanted.lock().unwrap().clone()?;
+        self.cache.lock().unwrap().get(&key).cloned().flatten()
+    }
+
+    /// Why lyrics can't be fetched, if they can't.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+}
+
+/// Look up the synced lyrics of a track (`Ok(None)` if LRCLIB has none).
+fn fetch(key: &TrackKey) -> HttpResult<Option<Lyrics>> {
+    let duration = key.duration_secs.to_string();
+    let url = reqwest::Url::parse_with_params(
+        LRCLIB_URL,
+        [
+            ("artist_name", key.artist.as_str()),
+            ("track_name", key.title.as_str()),
+            ("album_name", key.album.as_str()),
+            ("duration", duration.as_str()),
+        ],
+    )
+    .expect("LRCLIB URL is valid");
+    match http::get_json::<LrclibTrack>(url.as_str(), &[], REQUEST_TIMEOUT) {
+        Ok(track) => Ok(track.synced_lyrics.map(|text| parse_lrc(&text)).filter(|lyrics| !lyrics.lines.is_empty())),
+        // Unknown track
+        Err(HttpError::Status(404)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Timed lines of LRC text.
+///
+/// A line may carry several timestamps (`[00:12.00][01:30.50]chorus`);
+/// tags without a time (`[ar:Artist]`) and untimed lines are skipped.
+fn parse_lrc(text: &str) -> Lyrics {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut rest = line.trim();
+        let mut starts = Vec::new();
+        while let Some((tag, after)) = rest.strip_prefix('[').and_then(|tag| tag.split_once(']')) {
+            let Some(start) = parse_timestamp(tag) else {
+                break;
+            };
+            starts.push(start);
+            rest = after;
+        }
+        for start in starts {
+            lines.push((start, rest.trim().to_string()));
+        }
+    }
+    lines.sort_by_key(|(start, _)| *start);
+    Lyrics { lines }
+}
+
+/// Milliseconds of an LRC timestamp (`mm:ss`, `mm:ss.xx` or `mm:ss.xxx`).
+fn parse_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    (0.0..60.0).contains(&seconds).then(|| minutes * 60_000 + (seconds * 1000.0).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lrc() {
+        let lyrics = parse_lrc("[ar:Someone]\n[00:01.50]First line\n[00:04.00][00:10.25] Chorus \n[00:07.000]\nno time\n");
+        assert_eq!(lyrics.line_at(0), None);
+        assert_eq!(lyrics.line_at(1500), Some("First line"));
+        assert_eq!(lyrics.line_at(5000), Some("Chorus"));
+        // Instrumental break
+        assert_eq!(lyrics.line_at(8000), None);
+        assert_eq!(lyrics.line_at(60_000), Some("Chorus"));
+
+        assert_eq!(parse_timestamp("02:03.45"), Some(123_450));
+        assert_eq!(parse_timestamp("ti:Title"), None);
+    }
+
+    #[test]
+    fn test_lyrics_cache() {
+        let key = |n: usize| TrackKey { artist: String::from("A"), title: n.to_string(), album: String::new(), duration_secs: 200 };
+        let mut cache = LyricsCache::default();
+        for n in 0..=CACHE_SIZE {
+            cache.insert(key(n), None);
+        }
+        assert!(cache.get(&key(0)).is_none());
+        assert!(cache.get(&key(CACHE_SIZE)).is_some());
+        assert_eq!(cache.entries.len(), CACHE_SIZE);
+    }
+}
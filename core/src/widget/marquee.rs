@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Marquee Text
+//!
+//! Text wider than its space (long track titles) scrolls back and forth
+//! instead of being cut off at the widget edge:
+//!
+//! ```text
+//! time    │ pause │ scroll ───► │ pause │ ◄─── scroll │ pause │ ...
+//! shown   │Bohemian Rhapsody - Re│       │ody - Remastered 2011│
+//! ```
+//!
+//! The text waits at each end for `marquee_pause_ms`, and moves at
+//! `marquee_speed` pixels per second in between. The offset is worked out
+//! from the time since the text first appeared, so a new track starts at
+//! the beginning again. Text that fits is drawn as is.
+//!
+//! While text scrolls, `draw()` in `widget_main.rs` keeps requesting frame
+//! callbacks, like it does for animations.
+
+use std::time::Duration;
+
+use super::theme::{set_text_fill, set_text_outline};
+
+/// Timing of a marquee.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marquee {
+    /// Time since the text appeared
+    pub elapsed: Duration,
+    /// Scroll speed in pixels per second (0 = don't scroll)
+    pub speed: u32,
+    /// Wait at each end
+    pub pause: Duration,
+}
+
+impl Marquee {
+    /// How far text `overflow` pixels wider than its space is scrolled to
+    /// the left (0 up to `overflow`).
+    pub fn offset(&self, overflow: f64) -> f64 {
+        if overflow <= 0.0 || self.speed == 0 {
+            return 0.0;
+        }
+        let pause = self.pause.as_secs_f64();
+        let travel = overflow / self.speed as f64;
+        let cycle = 2.0 * (pause + travel);
+        let t = self.elapsed.as_secs_f64() % cycle;
+        if t < pause {
+            0.0
+        } else if t < pause + travel {
+            (t - pause) * self.speed as f64
+        } else if t < 2.0 * pause + travel {
+            overflow
+        } else {
+            overflow - (t - 2.0 * pause - travel) * self.speed as f64
+        }
+    }
+}
+
+/// Draw the text of `layout` at (x, y), clipped to `width` and scrolled by
+/// `marquee` if it doesn't fit.
+///
+/// Outlined and filled with the active text palette like the other widget
+/// text. Without a marquee, text that doesn't fit is cut off at `width`.
+///
+/// # Returns
+///
+/// Whether the text is scrolling (and needs further frames).
+pub fn draw_marquee_text(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    x: f64,
+    y: f64,
+    width: f64,
+    marquee: Option<&Marquee>,
+) -> bool {
+    let (text_width, text_height) = layout.pixel_size();
+    let overflow = text_width as f64 - width;
+    let offset = marquee.map_or(0.0, |marquee| marquee.offset(overflow));
+
+    cr.save().ok();
+    // Leave room for the outline above and below
+    cr.rectangle(x, y - 2.0, width, text_height as f64 + 4.0);
+    cr.clip();
+    cr.move_to(x - offset, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    cr.restore().ok();
+
+    overflow > 0.0 && marquee.is_some_and(|marquee| marquee.speed > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marquee_offset() {
+        let at = |millis: u64| Marquee { elapsed: Duration::from_millis(millis), speed: 20, pause: Duration::from_secs(1) };
+        // 40px overflow at 20px/s: 1s pause, 2s out, 1s pause, 2s back
+        assert_eq!(at(500).offset(40.0), 0.0);
+        assert_eq!(at(2000).offset(40.0), 20.0);
+        assert_eq!(at(3500).offset(40.0), 40.0);
+        assert_eq!(at(5000).offset(40.0), 20.0);
+        assert_eq!(at(6500).offset(40.0), 0.0);
+
+        assert_eq!(at(2000).offset(-10.0), 0.0);
+        let stopped = Marquee { speed: 0, ..at(2000) };
+        assert_eq!(stopped.offset(40.0), 0.0);
+    }
+}
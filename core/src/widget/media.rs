@@ -33,11 +33,129 @@
 //! 2. Enumerate MPRIS players via D-Bus
 //! 3. Query each player's metadata and status
 //! 4. Update shared state with all players
+//!
+//! Cider and album art requests go through the shared [`super::http`] client;
+//! MPRIS still uses `dbus-send`. While Cider is unreachable, its polling backs
+//! off exponentially (2s up to 15s) instead of hitting the API every second.
+//!
+//! ## Volume, Shuffle and Loop
+//!
+//! Each poll also reads the player's volume, shuffle and loop mode, which
+//! the media section shows as a volume bar and two toggle icons:
+//!
+//! ```text
+//!            MPRIS property        Cider now-playing
+//! volume     Volume (0.0-1.0)      GET /volume
+//! shuffle    Shuffle (bool)        shuffleMode (0/1)
+//! loop       LoopStatus            repeatMode (0 off, 1 track, 2 all)
+//! ```
+//!
+//! Players that don't expose a property leave it `None` and its control is
+//! not drawn. Clicking a toggle shuffles or steps the loop mode (off → all
+//! → track), and the change shows right away instead of on the next poll.
 
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::collections::HashMap;
 use std::process::Command;
+use super::exec::{self, CommandExt};
+
+use serde::Deserialize;
+
+use super::http::{self, Backoff, HttpResult};
+use super::watchdog::Heartbeat;
+
+// ============================================================================
+// Cider REST API
+// ============================================================================
+
+/// Base URL of Cider's local REST API.
+const CIDER_API_BASE: &str = "http://localhost:10767/api/v1/playback";
+
+/// Timeout for Cider requests (local API, should answer instantly).
+const CIDER_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Volume change of one scroll step (5%).
+pub const VOLUME_STEP: f64 = 0.05;
+
+/// First polling delay after Cider stops answering.
+const CIDER_RETRY_BASE: Duration = Duration::from_secs(2);
+
+/// Maximum polling delay while Cider is unreachable (keeps startup detection snappy).
+const CIDER_RETRY_MAX: Duration = Duration::from_secs(15);
+
+/// Build the authentication header list for Cider requests.
+fn cider_headers(token: Option<&str>) -> Vec<(&'static str, &str)> {
+    token.map(|t| vec![("apptoken", t)]).unwrap_or_default()
+}
+
+/// Response from `GET /api/v1/playback/now-playing`.
+///
+/// ```text
+/// {"status":"ok","info":{"name":"...","artistName":"...","albumName":"...",
+///   "artwork":{"width":600,"height":600,"url":"https://.../{w}x{h}bb.jpg"},
+///   "durationInMillis":215000,"currentPlaybackTime":12.34,
+///   "shuffleMode":0,"repeatMode":0, ...}}
+/// ```
+///
+/// Unknown fields are ignored; everything except `status` is optional so
+/// partial payloads (e.g. nothing loaded yet) still deserialize.
+#[derive(Debug, Deserialize)]
+struct CiderNowPlaying {
+    /// "ok" on success, "error" otherwise
+    status: String,
+    /// Track details (absent when nothing is loaded)
+    #[serde(default)]
+    info: Option<CiderTrack>,
+}
+
+/// Track details inside a Cider now-playing response.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct CiderTrack {
+    /// Track title
+    name: String,
+    /// Artist name
+    artist_name: String,
+    /// Album name
+    album_name: String,
+    /// Artwork descriptor with a `{w}x{h}` URL template
+    artwork: Option<CiderArtwork>,
+    /// Track length in milliseconds (Cider sometimes sends it as a float)
+    duration_in_millis: Option<f64>,
+    /// Current position in seconds
+    current_playback_time: Option<f64>,
+    /// 0 off, 1 on
+    shuffle_mode: Option<u8>,
+    /// 0 off, 1 repeat track, 2 repeat all
+    repeat_mode: Option<u8>,
+}
+
+/// Artwork descriptor from the Cider API.
+#[derive(Debug, Deserialize)]
+struct CiderArtwork {
+    /// Apple Music CDN URL with `{w}` and `{h}` size placeholders
+    url: String,
+}
+
+/// Response from `GET /api/v1/playback/is-playing`.
+#[derive(Debug, Deserialize)]
+struct CiderIsPlaying {
+    /// "ok" on success
+    status: String,
+    /// Whether playback is active
+    #[serde(default)]
+    is_playing: bool,
+}
+
+/// Response from `GET /api/v1/playback/volume`.
+#[derive(Debug, Deserialize)]
+struct CiderVolume {
+    /// "ok" on success
+    status: String,
+    /// Volume from 0.0 to 1.0
+    volume: Option<f64>,
+}
 
 // ============================================================================
 // Album Art Cache
@@ -119,6 +237,54 @@ impl Default for PlaybackStatus {
     }
 }
 
+/// Whether the player repeats, and what.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Playback stops at the end of the playlist
+    None,
+    /// The current track repeats
+    Track,
+    /// The playlist repeats
+    Playlist,
+}
+
+impl LoopMode {
+    /// Mode the loop toggle switches to (off → all → track → off).
+    pub fn next(self) -> Self {
+        match self {
+            LoopMode::None => LoopMode::Playlist,
+            LoopMode::Playlist => LoopMode::Track,
+            LoopMode::Track => LoopMode::None,
+        }
+    }
+
+    /// Value of the MPRIS `LoopStatus` property.
+    fn mpris_name(self) -> &'static str {
+        match self {
+            LoopMode::None => "None",
+            LoopMode::Track => "Track",
+            LoopMode::Playlist => "Playlist",
+        }
+    }
+
+    /// Parse an MPRIS `LoopStatus` value.
+    fn from_mpris(name: &str) -> Option<Self> {
+        [LoopMode::None, LoopMode::Track, LoopMode::Playlist]
+            .into_iter()
+            .find(|mode| mode.mpris_name() == name)
+    }
+
+    /// Parse Cider's `repeatMode` (0 off, 1 track, 2 all).
+    fn from_cider(mode: u8) -> Option<Self> {
+        match mode {
+            0 => Some(LoopMode::None),
+            1 => Some(LoopMode::Track),
+            2 => Some(LoopMode::Playlist),
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // Player Identity
 // ============================================================================
@@ -200,6 +366,12 @@ pub struct MediaInfo {
     /// Whether seeking is supported
     #[allow(dead_code)]
     pub can_seek: bool,
+    /// Player volume from 0.0 to 1.0 (`None` without volume control)
+    pub volume: Option<f64>,
+    /// Whether shuffle is on (`None` if the player can't shuffle)
+    pub shuffle: Option<bool>,
+    /// Loop mode (`None` if the player can't repeat)
+    pub loop_mode: Option<LoopMode>,
 }
 
 impl MediaInfo {
@@ -309,6 +481,10 @@ pub struct MediaMonitor {
     artwork_cache: Arc<Mutex<ArtworkCache>>,
     /// Currently selected player ID (persists across updates)
     selected_player: Arc<Mutex<Option<PlayerId>>>,
+    /// Cider REST API connection status (for diagnostics)
+    cider_status: Arc<Mutex<String>>,
+    /// Stamped by the polling thread after every pass (for the watchdog)
+    heartbeat: Heartbeat,
 }
 
 impl MediaMonitor {
@@ -319,15 +495,21 @@ impl MediaMonitor {
         let cider_token = Arc::new(Mutex::new(token));
         let artwork_cache = Arc::new(Mutex::new(ArtworkCache::new(20)));
         let selected_player = Arc::new(Mutex::new(None));
+        let cider_status = Arc::new(Mutex::new(String::from("Not checked yet")));
         
         // Spawn background thread to monitor all players
         let state_clone = Arc::clone(&player_state);
         let token_clone = Arc::clone(&cider_token);
         let cache_clone = Arc::clone(&artwork_cache);
         let selected_clone = Arc::clone(&selected_player);
+        let cider_status_clone = Arc::clone(&cider_status);
+        let heartbeat = Heartbeat::new();
+        let heartbeat_clone = heartbeat.clone();
         
         std::thread::spawn(move || {
-            Self::monitor_loop(state_clone, token_clone, cache_clone, selected_clone);
+            let _exit_guard = heartbeat_clone.guard();
+            super::priority::enter_background("media");
+            Self::monitor_loop(state_clone, token_clone, cache_clone, selected_clone, cider_status_clone, heartbeat_clone);
         });
         
         Self {
@@ -335,25 +517,71 @@ impl MediaMonitor {
             cider_token,
             artwork_cache,
             selected_player,
+            cider_status,
+            heartbeat,
         }
     }
     
+    /// Heartbeat of the player polling thread (for the watchdog).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+    
     /// Main background monitoring loop.
     fn monitor_loop(
         player_state: Arc<Mutex<MultiPlayerState>>,
         cider_token: Arc<Mutex<Option<String>>>,
         artwork_cache: Arc<Mutex<ArtworkCache>>,
         selected_player: Arc<Mutex<Option<PlayerId>>>,
+        cider_status: Arc<Mutex<String>>,
+        heartbeat: Heartbeat,
     ) {
         log::info!("Starting multi-player media monitor");
         let mut last_art_urls: HashMap<PlayerId, String> = HashMap::new();
         
+        // Back off polling while Cider isn't reachable (usually: not running)
+        let mut cider_backoff = Backoff::new(CIDER_RETRY_BASE, CIDER_RETRY_MAX);
+        let mut last_token: Option<String> = None;
+        
         loop {
             let mut players: Vec<(PlayerId, MediaInfo)> = Vec::new();
             
             // 1. Try Cider API
             let token = cider_token.lock().unwrap().clone();
-            if let Some(mut info) = Self::try_cider_api(token.as_deref()) {
+            if token != last_token {
+                // New token from settings: retry immediately
+                cider_backoff.record_success();
+                last_token = token.clone();
+            }
+            
+            let cider_info = if cider_backoff.ready() {
+                match Self::try_cider_api(token.as_deref()) {
+                    Ok(info) => {
+                        cider_backoff.record_success();
+                        *cider_status.lock().unwrap() = String::from("Connected");
+                        info
+                    }
+                    Err(e) => {
+                        let delay = cider_backoff.record_failure(&e);
+                        *cider_status.lock().unwrap() = format!("Unavailable: {}", e);
+                        // Auth failures are worth surfacing; connection refused just means Cider is closed
+                        if matches!(e, http::HttpError::Status(401 | 403)) {
+                            super::diagnostics::record_error("cider", &e);
+                        }
+                        // First failure is logged at debug level: Cider simply not running is normal
+                        if cider_backoff.failures() == 1 {
+                            log::debug!("Cider API unavailable: {} (next retry in {}s)", e, delay.as_secs());
+                        } else {
+                            log::trace!("Cider API unavailable: {} (next retry in {}s)", e, delay.as_secs());
+                        }
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            
+            if let Some(mut info) = cider_info {
                 // Load artwork if needed
                 if let Some(ref url) = info.art_url {
                     let needs_load = last_art_urls.get(&PlayerId::Cider) != Some(url);
@@ -440,7 +668,11 @@ impl MediaMonitor {
                 state.current_index = new_index.min(state.players.len().saturating_sub(1));
             }
             
+            heartbeat.beat();
             std::thread::sleep(Duration::from_secs(1));
+            if heartbeat.stopped() {
+                break;
+            }
         }
     }
     
@@ -457,7 +689,7 @@ impl MediaMonitor {
                 "/org/freedesktop/DBus",
                 "org.freedesktop.DBus.ListNames",
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .ok()?;
         
         if !output.status.success() {
@@ -491,7 +723,7 @@ impl MediaMonitor {
                 "string:org.mpris.MediaPlayer2.Player",
                 "string:Metadata",
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .ok()?;
         
         // Get playback status
@@ -504,7 +736,7 @@ impl MediaMonitor {
                 "string:org.mpris.MediaPlayer2.Player",
                 "string:PlaybackStatus",
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .ok()?;
         
         // Get position
@@ -517,7 +749,7 @@ impl MediaMonitor {
                 "string:org.mpris.MediaPlayer2.Player",
                 "string:Position",
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .ok()?;
         
         let metadata_str = String::from_utf8_lossy(&metadata_output.stdout);
@@ -562,6 +794,13 @@ impl MediaMonitor {
             return None;
         }
         
+        // Optional properties: players without them answer with an error
+        let volume = Self::mpris_property(bus_name, "Volume").and_then(|o| Self::extract_dbus_double(&o));
+        let shuffle = Self::mpris_property(bus_name, "Shuffle").and_then(|o| Self::extract_dbus_boolean(&o));
+        let loop_mode = Self::mpris_property(bus_name, "LoopStatus")
+            .and_then(|o| Self::extract_dbus_string(&o))
+            .and_then(|name| LoopMode::from_mpris(&name));
+        
         Some(MediaInfo {
             player_name,
             title,
@@ -577,9 +816,49 @@ impl MediaMonitor {
             can_go_next: true,
             can_go_previous: true,
             can_seek: true,
+            volume,
+            shuffle,
+            loop_mode,
         })
     }
     
+    /// Read a property of an MPRIS player's `Player` interface.
+    ///
+    /// Returns the `dbus-send` reply, or `None` if the player doesn't have it.
+    fn mpris_property(bus_name: &str, property: &str) -> Option<String> {
+        let output = Command::new("dbus-send")
+            .args([
+                "--print-reply",
+                &format!("--dest={}", bus_name),
+                "/org/mpris/MediaPlayer2",
+                "org.freedesktop.DBus.Properties.Get",
+                "string:org.mpris.MediaPlayer2.Player",
+                &format!("string:{}", property),
+            ])
+            .bounded_output(exec::QUICK_TIMEOUT)
+            .ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+    
+    /// Set a property of an MPRIS player's `Player` interface.
+    ///
+    /// `value` is in `dbus-send` syntax, e.g. `variant:boolean:true`.
+    fn set_mpris_property(bus_name: &str, property: &str, value: &str) -> bool {
+        Command::new("dbus-send")
+            .args([
+                "--print-reply",
+                &format!("--dest={}", bus_name),
+                "/org/mpris/MediaPlayer2",
+                "org.freedesktop.DBus.Properties.Set",
+                "string:org.mpris.MediaPlayer2.Player",
+                &format!("string:{}", property),
+                value,
+            ])
+            .bounded_output(exec::QUICK_TIMEOUT)
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+    
     /// Extract string from D-Bus metadata by key.
     fn extract_dbus_metadata_string(output: &str, key: &str) -> Option<String> {
         let key_pattern = format!("\"{}\"", key);
@@ -647,6 +926,30 @@ impl MediaMonitor {
         None
     }
     
+    /// Extract a double from a D-Bus property response (`variant double 0.5`).
+    fn extract_dbus_double(output: &str) -> Option<f64> {
+        let start = output.find("double ")? + 7;
+        let rest = &output[start..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest[..end].parse().ok()
+    }
+    
+    /// Extract a boolean from a D-Bus property response (`variant boolean true`).
+    fn extract_dbus_boolean(output: &str) -> Option<bool> {
+        let start = output.find("boolean ")? + 8;
+        let rest = &output[start..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest[..end].parse().ok()
+    }
+    
+    /// Extract a string from a D-Bus property response (`variant string "Track"`).
+    fn extract_dbus_string(output: &str) -> Option<String> {
+        let start = output.find("string \"")? + 8;
+        let rest = &output[start..];
+        let end = rest.find('"')?;
+        Some(rest[..end].to_string())
+    }
+    
     /// Extract thumbnail URL from a webpage URL (e.g., YouTube video ID -> thumbnail).
     ///
     /// Supports:
@@ -831,7 +1134,7 @@ impl MediaMonitor {
     
     /// Download and decode album artwork from URL.
     ///
-    /// Downloads the image using curl, then decodes it using the `image` crate.
+    /// Downloads the image via the shared HTTP client, then decodes it using the `image` crate.
     /// Resizes to a reasonable size for the widget display.
     /// Handles both http(s):// and file:// URLs.
     fn download_artwork(url: &str) -> Option<AlbumArt> {
@@ -844,17 +1147,17 @@ impl MediaMonitor {
             let path = url.strip_prefix("file://")?;
             std::fs::read(path).ok()?
         } else {
-            let output = Command::new("curl")
-                .args(&["-s", "--max-time", "5", "-L"])
-                .arg(url)
-                .output()
-                .ok()?;
-            
-            if !output.status.success() || output.stdout.is_empty() {
-                log::warn!("Failed to download album art");
-                return None;
+            match http::get_bytes(url, Duration::from_secs(5)) {
+                Ok(bytes) if !bytes.is_empty() => bytes,
+                Ok(_) => {
+                    log::warn!("Failed to download album art: empty response");
+                    return None;
+                }
+                Err(e) => {
+                    log::warn!("Failed to download album art: {}", e);
+                    return None;
+                }
             }
-            output.stdout
         };
         
         // Decode image
@@ -892,64 +1195,47 @@ impl MediaMonitor {
     
     /// Query Cider API for current track info.
     ///
-    /// Uses `curl` for HTTP requests to avoid pulling in reqwest for
-    /// a simple local API call.
-    ///
     /// # Returns
     ///
-    /// `Some(MediaInfo)` if Cider is running and playing
-    /// `None` if Cider is not running or no track is loaded
-    fn try_cider_api(token: Option<&str>) -> Option<MediaInfo> {
-        use std::process::Command;
-        
-        // Build curl command for now-playing endpoint
-        let mut cmd = Command::new("curl");
-        cmd.args(&["-s", "--max-time", "1"]);  // Silent, 1 second timeout
-        
-        // Add authentication header if token provided
-        if let Some(t) = token {
-            cmd.args(&["-H", &format!("apptoken: {}", t)]);
-        }
-        
-        cmd.arg("http://localhost:10767/api/v1/playback/now-playing");
-        
-        let output = cmd.output().ok()?;
-        
-        if !output.status.success() {
-            return None;
-        }
-        
-        let json_str = String::from_utf8_lossy(&output.stdout);
+    /// `Ok(Some(MediaInfo))` if Cider is running and has a track loaded
+    /// `Ok(None)` if Cider is running but nothing is loaded
+    /// `Err` if Cider is unreachable or rejected the request
+    fn try_cider_api(token: Option<&str>) -> HttpResult<Option<MediaInfo>> {
+        let headers = cider_headers(token);
+        let url = format!("{}/now-playing", CIDER_API_BASE);
         
-        // Check for error response
-        if json_str.contains("\"error\"") {
-            return None;
-        }
+        let json_str = http::get_text(&url, &headers, CIDER_TIMEOUT)?;
         
         // Also query the is-playing endpoint for accurate playback status
         let is_playing = Self::check_is_playing(token);
         
         // Parse JSON response
-        Self::parse_cider_response(&json_str, is_playing)
+        Ok(Self::parse_cider_response(&json_str, is_playing).map(|mut info| {
+            info.volume = Self::query_cider_volume(token);
+            info
+        }))
+    }
+    
+    /// Current Cider volume (0.0 - 1.0), `None` if the query failed.
+    fn query_cider_volume(token: Option<&str>) -> Option<f64> {
+        let url = format!("{}/volume", CIDER_API_BASE);
+        match http::get_text(&url, &cider_headers(token), CIDER_TIMEOUT) {
+            Ok(json) => Self::parse_cider_volume(&json),
+            Err(e) => {
+                log::debug!("Cider volume query failed: {}", e);
+                None
+            }
+        }
     }
     
     /// Check if media is currently playing via is-playing endpoint.
     fn check_is_playing(token: Option<&str>) -> bool {
-        use std::process::Command;
+        let headers = cider_headers(token);
+        let url = format!("{}/is-playing", CIDER_API_BASE);
         
-        let mut cmd = Command::new("curl");
-        cmd.args(&["-s", "--max-time", "1"]);
-        
-        if let Some(t) = token {
-            cmd.args(&["-H", &format!("apptoken: {}", t)]);
-        }
-        
-        cmd.arg("http://localhost:10767/api/v1/playback/is-playing");
-        
-        if let Ok(output) = cmd.output() {
-            if output.status.success() {
-                let json_str = String::from_utf8_lossy(&output.stdout);
-                return json_str.contains("\"is_playing\":true");
+        if let Ok(json_str) = http::get_text(&url, &headers, CIDER_TIMEOUT) {
+            if let Some(is_playing) = Self::parse_cider_is_playing(&json_str) {
+                return is_playing;
             }
         }
         
@@ -957,14 +1243,45 @@ impl MediaMonitor {
         true
     }
     
+    /// Parse the is-playing endpoint response.
+    ///
+    /// Returns `None` for error responses or malformed JSON.
+    fn parse_cider_is_playing(json: &str) -> Option<bool> {
+        let response: CiderIsPlaying = serde_json::from_str(json).ok()?;
+        (response.status == "ok").then_some(response.is_playing)
+    }
+    
+    /// Parse the volume response into 0.0 - 1.0.
+    fn parse_cider_volume(json: &str) -> Option<f64> {
+        let response: CiderVolume = serde_json::from_str(json).ok()?;
+        response.volume.filter(|_| response.status == "ok")
+    }
+    
     /// Parse Cider API JSON response into MediaInfo.
     ///
-    /// Uses simple string parsing to avoid JSON dependency overhead.
-    /// Extracts: name, artistName, albumName, artwork.url, durationInMillis,
-    /// currentPlaybackTime.
+    /// Deserializes into [`CiderNowPlaying`], so escaped quotes and Unicode in
+    /// titles are handled correctly. Extracts: name, artistName, albumName,
+    /// artwork.url, durationInMillis, currentPlaybackTime.
+    ///
+    /// # Returns
+    ///
+    /// `None` for error responses, malformed JSON, or when no track is loaded.
     fn parse_cider_response(json: &str, is_playing: bool) -> Option<MediaInfo> {
-        // Check if status is ok
-        if !json.contains("\"status\":\"ok\"") {
+        let response: CiderNowPlaying = match serde_json::from_str(json) {
+            Ok(response) => response,
+            Err(e) => {
+                log::debug!("Failed to parse Cider now-playing response: {}", e);
+                return None;
+            }
+        };
+        
+        if response.status != "ok" {
+            return None;
+        }
+        let track = response.info?;
+        
+        // Check if we got meaningful data
+        if track.name.is_empty() {
             return None;
         }
         
@@ -975,92 +1292,37 @@ impl MediaMonitor {
             PlaybackStatus::Paused
         };
         
-        let mut info = MediaInfo {
+        Some(MediaInfo {
             player_name: "Cider".to_string(),
+            title: track.name,
+            artist: track.artist_name,
+            album: track.album_name,
+            // Replace {w}x{h} placeholders with actual size
+            art_url: track.artwork.map(|a| a.url.replace("{w}", "300").replace("{h}", "300")),
+            duration: track.duration_in_millis.map(|d| d.max(0.0) as u64).unwrap_or(0),
+            // Playback time is in seconds → milliseconds
+            position: track.current_playback_time.map(|p| (p.max(0.0) * 1000.0) as u64).unwrap_or(0),
             can_play: true,
             can_pause: true,
             can_go_next: true,
             can_go_previous: true,
             can_seek: true,
             status: playback_status,
+            shuffle: track.shuffle_mode.map(|mode| mode != 0),
+            loop_mode: track.repeat_mode.and_then(LoopMode::from_cider),
             ..Default::default()
-        };
-        
-        // Extract title (name field in Cider API)
-        if let Some(name) = Self::extract_json_string(json, "\"name\":\"") {
-            info.title = name;
-        }
-        
-        // Extract artist
-        if let Some(artist) = Self::extract_json_string(json, "\"artistName\":\"") {
-            info.artist = artist;
-        }
-        
-        // Extract album
-        if let Some(album) = Self::extract_json_string(json, "\"albumName\":\"") {
-            info.album = album;
-        }
-        
-        // Extract artwork URL from within the artwork object
-        // The response has: "artwork":{"width":...,"height":...,"url":"https://..."}
-        if let Some(artwork_start) = json.find("\"artwork\":{") {
-            let artwork_section = &json[artwork_start..];
-            // Find url within the artwork object
-            if let Some(url) = Self::extract_json_string(artwork_section, "\"url\":\"") {
-                // Replace {w}x{h} placeholders with actual size
-                let artwork_url = url
-                    .replace("{w}", "300")
-                    .replace("{h}", "300");
-                info.art_url = Some(artwork_url);
-            }
-        }
-        
-        // Extract duration in milliseconds
-        if let Some(duration_str) = Self::extract_json_number(json, "\"durationInMillis\":") {
-            if let Ok(duration) = duration_str.parse::<u64>() {
-                info.duration = duration;
-            }
-        }
-        
-        // Extract current playback time (seconds → milliseconds)
-        if let Some(pos_str) = Self::extract_json_number(json, "\"currentPlaybackTime\":") {
-            if let Ok(pos) = pos_str.parse::<f64>() {
-                info.position = (pos * 1000.0) as u64;
-            }
-        }
-        
-        // Check if we got meaningful data
-        if info.title.is_empty() {
-            return None;
-        }
-        
-        Some(info)
-    }
-    
-    /// Extract a string value from JSON by key.
-    ///
-    /// Simple parsing: finds key, then extracts until next quote.
-    fn extract_json_string(json: &str, key: &str) -> Option<String> {
-        let start = json.find(key)? + key.len();
-        let rest = &json[start..];
-        let end = rest.find('"')?;
-        Some(rest[..end].to_string())
-    }
-    
-    /// Extract a numeric value from JSON by key.
-    ///
-    /// Simple parsing: finds key, then extracts until delimiter.
-    fn extract_json_number(json: &str, key: &str) -> Option<String> {
-        let start = json.find(key)? + key.len();
-        let rest = &json[start..];
-        let end = rest.find(|c: char| c == ',' || c == '}' || c == ']')?;
-        Some(rest[..end].trim().to_string())
+        })
     }
     
     // ========================================================================
     // Public API
     // ========================================================================
     
+    /// Cider REST API connection status ("Connected" or the last error).
+    pub fn cider_status(&self) -> String {
+        self.cider_status.lock().unwrap().clone()
+    }
+    
     /// Get the multi-player state snapshot.
     pub fn get_player_state(&self) -> MultiPlayerState {
         self.player_state.lock().unwrap().clone()
@@ -1154,6 +1416,118 @@ impl MediaMonitor {
         }
     }
     
+    /// Change the current player's volume by `delta` (-1.0 to 1.0),
+    /// clamped to 0 - 100%.
+    pub fn change_volume(&self, delta: f64) -> bool {
+        let state = self.player_state.lock().unwrap();
+        if let Some((player_id, _)) = state.current_player() {
+            let player_id = player_id.clone();
+            drop(state);
+            
+            // Read it fresh: scrolling is faster than the one-second poll
+            let current = match &player_id {
+                PlayerId::Cider => {
+                    let token = self.cider_token.lock().unwrap().clone();
+                    Self::query_cider_volume(token.as_deref())
+                }
+                PlayerId::Mpris(bus_name) => Self::mpris_property(bus_name, "Volume")
+                    .and_then(|o| Self::extract_dbus_double(&o)),
+            };
+            let Some(current) = current else {
+                log::debug!("{} has no volume control", player_id.display_name());
+                return false;
+            };
+            self.set_volume(current + delta)
+        } else {
+            false
+        }
+    }
+    
+    /// Set the current player's volume (0.0 to 1.0, clamped).
+    pub fn set_volume(&self, volume: f64) -> bool {
+        let state = self.player_state.lock().unwrap();
+        let Some((player_id, _)) = state.current_player() else {
+            return false;
+        };
+        let player_id = player_id.clone();
+        drop(state);
+        
+        let volume = volume.clamp(0.0, 1.0);
+        let changed = match &player_id {
+            PlayerId::Cider => self.cider_set_volume(volume),
+            PlayerId::Mpris(bus_name) => {
+                Self::set_mpris_property(bus_name, "Volume", &format!("variant:double:{}", volume))
+            }
+        };
+        if changed {
+            self.update_player(&player_id, |info| info.volume = Some(volume));
+        }
+        changed
+    }
+    
+    /// Turn shuffle on or off on the current player.
+    ///
+    /// Does nothing (and returns false) if the player can't shuffle.
+    pub fn toggle_shuffle(&self) -> bool {
+        let state = self.player_state.lock().unwrap();
+        let Some((player_id, info)) = state.current_player() else {
+            return false;
+        };
+        let (player_id, shuffle) = (player_id.clone(), info.shuffle);
+        drop(state);
+        
+        let Some(shuffle) = shuffle else {
+            return false;
+        };
+        let changed = match &player_id {
+            PlayerId::Cider => self.send_cider_command("toggle-shuffle"),
+            PlayerId::Mpris(bus_name) => {
+                Self::set_mpris_property(bus_name, "Shuffle", &format!("variant:boolean:{}", !shuffle))
+            }
+        };
+        if changed {
+            self.update_player(&player_id, |info| info.shuffle = Some(!shuffle));
+        }
+        changed
+    }
+    
+    /// Step the current player's loop mode (off → all → track → off).
+    ///
+    /// Does nothing (and returns false) if the player can't repeat.
+    pub fn cycle_loop_mode(&self) -> bool {
+        let state = self.player_state.lock().unwrap();
+        let Some((player_id, info)) = state.current_player() else {
+            return false;
+        };
+        let (player_id, loop_mode) = (player_id.clone(), info.loop_mode);
+        drop(state);
+        
+        let Some(loop_mode) = loop_mode else {
+            return false;
+        };
+        let next = loop_mode.next();
+        let changed = match &player_id {
+            // Cider steps through its own modes; the next poll shows where it landed
+            PlayerId::Cider => self.send_cider_command("toggle-repeat"),
+            PlayerId::Mpris(bus_name) => {
+                Self::set_mpris_property(bus_name, "LoopStatus", &format!("variant:string:{}", next.mpris_name()))
+            }
+        };
+        if changed {
+            self.update_player(&player_id, |info| info.loop_mode = Some(next));
+        }
+        changed
+    }
+    
+    /// Apply a change to a player's info right away, so the widget doesn't
+    /// show the old state until the next poll.
+    fn update_player(&self, player_id: &PlayerId, update: impl FnOnce(&mut MediaInfo)) {
+        let mut state = self.player_state.lock().unwrap();
+        if let Some((_, info)) = state.players.iter_mut().find(|(id, _)| id == player_id) {
+            update(info);
+        }
+    }
+    
     /// Seek to position based on progress (0.0 to 1.0).
     pub fn seek_to_progress(&self, progress: f64) -> bool {
         let state = self.player_state.lock().unwrap();
@@ -1179,17 +1553,15 @@ impl MediaMonitor {
     
     fn send_cider_command(&self, endpoint: &str) -> bool {
         let token = self.cider_token.lock().unwrap().clone();
+        let url = format!("{}/{}", CIDER_API_BASE, endpoint);
         
-        let mut cmd = Command::new("curl");
-        cmd.args(&["-s", "-X", "POST", "--max-time", "1"]);
-        
-        if let Some(t) = token {
-            cmd.args(&["-H", &format!("apptoken: {}", t)]);
+        match http::post(&url, &cider_headers(token.as_deref()), None, CIDER_TIMEOUT) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Cider command '{}' failed: {}", endpoint, e);
+                false
+            }
         }
-        
-        cmd.arg(&format!("http://localhost:10767/api/v1/playback/{}", endpoint));
-        
-        cmd.output().map(|o| o.status.success()).unwrap_or(false)
     }
     
     fn cider_play_pause(&self) {
@@ -1216,19 +1588,30 @@ impl MediaMonitor {
     
     fn cider_seek(&self, position_seconds: f64) -> bool {
         let token = self.cider_token.lock().unwrap().clone();
-        
-        let mut cmd = Command::new("curl");
-        cmd.args(&["-s", "-X", "POST", "--max-time", "1"]);
-        cmd.args(&["-H", "Content-Type: application/json"]);
-        
-        if let Some(t) = token {
-            cmd.args(&["-H", &format!("apptoken: {}", t)]);
+        let url = format!("{}/seek", CIDER_API_BASE);
+        let body = serde_json::json!({ "position": position_seconds as u64 });
+        
+        match http::post(&url, &cider_headers(token.as_deref()), Some(&body), CIDER_TIMEOUT) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Cider seek failed: {}", e);
+                false
+            }
+        }
+    }
+    
+    fn cider_set_volume(&self, volume: f64) -> bool {
+        let token = self.cider_token.lock().unwrap().clone();
+        let url = format!("{}/volume", CIDER_API_BASE);
+        
+        let body = serde_json::json!({ "volume": volume });
+        match http::post(&url, &cider_headers(token.as_deref()), Some(&body), CIDER_TIMEOUT) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Cider volume change failed: {}", e);
+                false
+            }
         }
-        
-        cmd.args(&["-d", &format!("{{\"position\": {}}}", position_seconds as u64)]);
-        cmd.arg("http://localhost:10767/api/v1/playback/seek");
-        
-        cmd.output().map(|o| o.status.success()).unwrap_or(false)
     }
     
     // ========================================================================
@@ -1243,7 +1626,7 @@ impl MediaMonitor {
                 "/org/mpris/MediaPlayer2",
                 "org.mpris.MediaPlayer2.Player.PlayPause",
             ])
-            .output();
+            .bounded_output(exec::QUICK_TIMEOUT);
     }
     
     fn mpris_next(&self, bus_name: &str) {
@@ -1254,7 +1637,7 @@ impl MediaMonitor {
                 "/org/mpris/MediaPlayer2",
                 "org.mpris.MediaPlayer2.Player.Next",
             ])
-            .output();
+            .bounded_output(exec::QUICK_TIMEOUT);
     }
     
     fn mpris_previous(&self, bus_name: &str) {
@@ -1265,7 +1648,7 @@ impl MediaMonitor {
                 "/org/mpris/MediaPlayer2",
                 "org.mpris.MediaPlayer2.Player.Previous",
             ])
-            .output();
+            .bounded_output(exec::QUICK_TIMEOUT);
     }
     
     fn mpris_seek(&self, bus_name: &str, position_us: u64) -> bool {
@@ -1279,7 +1662,7 @@ impl MediaMonitor {
                 "string:org.mpris.MediaPlayer2.Player",
                 "string:Position",
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .ok();
         
         let current_pos = output
@@ -1296,8 +1679,89 @@ impl MediaMonitor {
                 "org.mpris.MediaPlayer2.Player.Seek",
                 &format!("int64:{}", offset),
             ])
-            .output()
+            .bounded_output(exec::QUICK_TIMEOUT)
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 }
+
+impl Drop for MediaMonitor {
+    fn drop(&mut self) {
+        self.heartbeat.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed now-playing payload captured from Cider 2.x
+    const NOW_PLAYING: &str = r#"{"status":"ok","info":{"albumName":"Skin","artistName":"Flume","artwork":{"width":3000,"height":3000,"url":"https://is1-ssl.mzstatic.com/image/thumb/Music115/v4/2c/9d/0a/2c9d0a0e/source/{w}x{h}bb.jpg","bgColor":"1a1a1a","hasP3":false},"audioLocale":"en-US","durationInMillis":263000,"genreNames":["Electronic","Music"],"hasLyrics":true,"isrc":"AUFL01600001","name":"Say It (feat. Tove Lo)","playParams":{"id":"1719861213","kind":"song"},"releaseDate":"2016-05-27T12:00:00Z","trackNumber":3,"url":"https://music.apple.com/us/album/say-it/1719861200?i=1719861213","currentPlaybackTime":33.52,"remainingTime":229.48,"inFavorites":false,"inLibrary":true,"shuffleMode":0,"repeatMode":0}}"#;
+
+    #[test]
+    fn test_parse_now_playing() {
+        let info = MediaMonitor::parse_cider_response(NOW_PLAYING, true).expect("track parsed");
+        assert_eq!(info.title, "Say It (feat. Tove Lo)");
+        assert_eq!(info.artist, "Flume");
+        assert_eq!(info.album, "Skin");
+        assert_eq!(info.duration, 263000);
+        assert_eq!(info.position, 33520);
+        assert_eq!(info.status, PlaybackStatus::Playing);
+        assert_eq!(
+            info.art_url.as_deref(),
+            Some("https://is1-ssl.mzstatic.com/image/thumb/Music115/v4/2c/9d/0a/2c9d0a0e/source/300x300bb.jpg")
+        );
+    }
+
+    #[test]
+    fn test_parse_escaped_quotes_and_unicode() {
+        let json = r#"{"status":"ok","info":{"name":"The \"Real\" Slim Shady","artistName":"Beyoncé & Sigur Rós","albumName":"日本語 アルバム","durationInMillis":1.5e5,"currentPlaybackTime":0}}"#;
+        let info = MediaMonitor::parse_cider_response(json, false).expect("track parsed");
+        assert_eq!(info.title, "The \"Real\" Slim Shady");
+        assert_eq!(info.artist, "Beyoncé & Sigur Rós");
+        assert_eq!(info.album, "日本語 アルバム");
+        assert_eq!(info.duration, 150000);
+        assert_eq!(info.status, PlaybackStatus::Paused);
+        assert!(info.art_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_error_and_empty_responses() {
+        assert!(MediaMonitor::parse_cider_response(r#"{"status":"error","error":"Unauthorized"}"#, true).is_none());
+        assert!(MediaMonitor::parse_cider_response(r#"{"status":"ok","info":{}}"#, true).is_none());
+        assert!(MediaMonitor::parse_cider_response("not json", true).is_none());
+    }
+
+    #[test]
+    fn test_parse_is_playing() {
+        assert_eq!(MediaMonitor::parse_cider_is_playing(r#"{"status":"ok","is_playing":true}"#), Some(true));
+        assert_eq!(MediaMonitor::parse_cider_is_playing(r#"{"status":"ok","is_playing":false}"#), Some(false));
+        assert_eq!(MediaMonitor::parse_cider_is_playing(r#"{"status":"error"}"#), None);
+    }
+
+    #[test]
+    fn test_parse_volume() {
+        assert_eq!(MediaMonitor::parse_cider_volume(r#"{"status":"ok","volume":0.35}"#), Some(0.35));
+        assert_eq!(MediaMonitor::parse_cider_volume(r#"{"status":"error","volume":0.35}"#), None);
+
+        let reply = "method return time=1700000000.1 sender=:1.42 -> destination=:1.99 serial=7 reply_serial=2\n   variant       double 0.62\n";
+        assert_eq!(MediaMonitor::extract_dbus_double(reply), Some(0.62));
+        assert_eq!(MediaMonitor::extract_dbus_double("   variant       int64 5"), None);
+    }
+
+    #[test]
+    fn test_parse_shuffle_and_loop() {
+        let info = MediaMonitor::parse_cider_response(NOW_PLAYING, true).expect("track parsed");
+        assert_eq!((info.shuffle, info.loop_mode), (Some(false), Some(LoopMode::None)));
+        let repeating = NOW_PLAYING.replace("\"shuffleMode\":0,\"repeatMode\":0", "\"shuffleMode\":1,\"repeatMode\":1");
+        let info = MediaMonitor::parse_cider_response(&repeating, true).expect("track parsed");
+        assert_eq!((info.shuffle, info.loop_mode), (Some(true), Some(LoopMode::Track)));
+
+        assert_eq!(MediaMonitor::extract_dbus_boolean("   variant       boolean true\n"), Some(true));
+        let reply = "   variant       string \"Playlist\"\n";
+        let mode = MediaMonitor::extract_dbus_string(reply).and_then(|name| LoopMode::from_mpris(&name));
+        assert_eq!(mode, Some(LoopMode::Playlist));
+        assert_eq!(LoopMode::from_mpris("Sometimes"), None);
+        assert_eq!(LoopMode::None.next().next().next(), LoopMode::None);
+    }
+}
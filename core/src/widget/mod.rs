@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Widget Monitoring Modules
+//!
+//! This module organizes all the monitoring and rendering functionality for the
+//! COSMIC Monitor desktop widget. Each submodule handles a specific monitoring
+//! feature or rendering aspect.
+//!
+//! # Module Overview
+//!
+//! ## Monitoring Modules
+//! These modules collect system information:
+//!
+//! - [`utilization`]: CPU, Memory, and GPU usage monitoring via sysinfo/nvidia-smi
+//! - [`cpufreq`]: CPU package frequencies and cpufreq governor (sysfs, pkexec)
+//! - [`psi`]: CPU, memory and I/O pressure stall information from /proc/pressure
+//! - [`kernel_stats`]: Process and thread counts and context switches per second (/proc)
+//! - [`gpu_processes`]: Busiest GPU processes (nvidia-smi pmon, DRM fdinfo)
+//! - [`gpu_card`]: DRM card of the detected GPU, shared by usage and temperature
+//! - [`identity`]: Hostname and user name line at the top of the widget
+//! - [`hardware`]: CPU model, core/thread count and GPU name for the Utilization header
+//! - [`temperature`]: CPU and GPU temperature readings from hwmon sensors
+//! - [`throttle`]: Thermal throttling from Intel throttle counters and CPU hwmon alarms
+//! - [`network`]: Network interface bandwidth monitoring
+//! - [`storage`]: Disk space usage for mounted filesystems
+//! - [`space_alert`]: Low free space and inode warnings, with optional desktop alerts
+//! - [`smart`]: SMART health verdict and temperature of each drive (smartctl, hwmon)
+//! - [`pools`]: Health, scrub status and capacity of ZFS pools and btrfs filesystems
+//! - [`battery`]: System battery and Solaar (Logitech) device battery levels
+//! - [`power_profile`]: Active power-profiles-daemon profile, switched from the Battery header
+//! - [`weather`]: OpenWeatherMap API integration for current conditions
+//! - [`air_quality`]: Air quality index at the weather station (Open-Meteo or OpenWeatherMap)
+//! - [`notifications`]: D-Bus desktop notification monitoring
+//! - [`notification_history`]: Notifications kept on disk across restarts
+//! - [`dnd`]: Do-not-disturb switch of the COSMIC notification daemon
+//! - [`media`]: Cider (Apple Music client) now-playing information
+//! - [`lyrics`]: Synced lyrics of the playing track from LRCLIB
+//! - [`custom`]: User-defined shell commands run on their own intervals
+//! - [`plugins`]: Sections drawn by Lua scripts (runtime in `lua`, only with the `lua-plugins` feature)
+//! - [`world_clock`]: Extra time zones shown under the clock (chrono-tz)
+//! - [`calendar`]: Upcoming events from an .ics file or Evolution Data Server
+//! - [`audio`]: Volume, mute state and name of the default output device via pactl
+//! - [`health`]: Failed systemd units and pending package updates
+//! - [`containers`]: Running Docker/Podman containers and the busiest one by CPU
+//! - [`vms`]: Running libvirt virtual machines with their vCPUs and memory (virsh)
+//! - [`feeds`]: Unread IMAP mail counts (curl) and latest RSS/Atom headlines
+//! - [`ticker`]: Crypto and stock prices from CoinGecko or a JSON endpoint
+//!
+//! ## Rendering Modules
+//! These modules handle visual output:
+//!
+//! - [`renderer`]: Cairo-based drawing of all widget sections
+//! - [`sections`]: `Section` trait and one implementation per widget section
+//! - [`layout`]: Dynamic height calculation based on enabled sections
+//! - [`theme`]: COSMIC desktop theme integration (accent color, dark/light mode)
+//! - [`fonts`]: Configurable font family and size per text role (clock, headers, body, small labels)
+//! - [`toplevel`]: Fullscreen/maximized window tracking for auto-hide
+//! - [`animation`]: Eased value transitions and fade-in/out between samples
+//! - [`history`]: Recent samples in memory for the small graphs under a section
+//! - [`scroll`]: Mouse wheel and touchpad scrolling turned into whole steps
+//! - [`tooltip`]: Delayed hover tooltips with the details behind a metric
+//! - [`ambient`]: Clock outline or background tinted with the system load or temperature
+//! - [`debug_overlay`]: Frame time, draw time and memory use drawn in a corner for performance reports
+//! - [`marquee`]: Long text (track titles) scrolled back and forth instead of cut off
+//!
+//! ## Utility Modules
+//!
+//! - [`cache`]: JSON-based caching for device discovery (shared with settings app)
+//! - [`http`]: Shared HTTP client for weather, Cider, and album art requests
+//! - [`exec`]: External tool execution with timeouts and a concurrency limit
+//! - [`watchdog`]: Stall detection and restart of background collectors
+//! - [`refresher`]: Background thread re-reading a section's slow source every few minutes
+//! - [`priority`]: Low CPU/IO priority and E-core pinning for collector threads
+//! - [`diagnostics`]: Status snapshot and recent errors for the settings diagnostics page
+//! - [`control`]: Named pipe for runtime show/hide/toggle commands
+//! - [`instance`]: Single-instance lock, with `--replace` to take over from a running widget
+//! - [`stats`]: Latest metrics published as JSON for scripts and overlays
+//! - [`clipboard`]: Plain-text stats summary copied to the Wayland clipboard (via `wl-copy`)
+//! - [`frame_export`]: Rendered frames written to PNG for OBS overlays
+//! - [`sampler`]: Sampler thread owning the system monitors, on wall-clock aligned ticks independent of rendering
+//! - [`recorder`]: Metric history appended to rotating daily CSV files
+//! - [`update`]: Optional daily check for a newer GitHub release
+//! - [`schedule`]: Time ranges that dim the widget or hide sections
+//! - [`snap`]: Edge, center and grid snapping while the widget is dragged
+//! - `prometheus`: Prometheus exporter mode (only with the `prometheus` feature)
+//!
+//! # Usage
+//!
+//! The main widget (`widget_main.rs`) creates instances of each monitor and calls
+//! their `update()` methods periodically. The collected data is then passed to
+//! the renderer for display.
+
+// === Monitoring Module Declarations ===
+pub mod utilization;
+pub mod gpu_processes;
+pub mod gpu_card;
+pub mod hardware;
+pub mod identity;
+pub mod cpufreq;
+pub mod psi;
+pub mod kernel_stats;
+pub mod snap;
+pub mod schedule;
+pub mod temperature;
+pub mod throttle;
+pub mod network;
+pub mod weather;
+pub mod air_quality;
+pub mod storage;
+pub mod space_alert;
+pub mod smart;
+pub mod pools;
+pub mod battery;
+pub mod power_profile;
+pub mod notifications;
+pub mod notification_history;
+pub mod dnd;
+pub mod media;
+pub mod lyrics;
+pub mod custom;
+pub mod plugins;
+pub mod world_clock;
+pub mod calendar;
+pub mod audio;
+pub mod health;
+pub mod containers;
+pub mod vms;
+pub mod feeds;
+pub mod ticker;
+
+// === Rendering Module Declarations ===
+pub mod renderer;
+pub mod sections;
+pub mod layout;
+pub mod theme;
+pub mod fonts;
+pub mod toplevel;
+pub mod animation;
+pub mod history;
+pub mod scroll;
+pub mod tooltip;
+pub mod ambient;
+pub mod debug_overlay;
+pub mod marquee;
+
+// === Utility Module Declarations ===
+pub mod cache;
+pub mod http;
+pub mod exec;
+pub mod watchdog;
+pub mod refresher;
+pub mod priority;
+pub mod diagnostics;
+pub mod control;
+pub mod instance;
+pub mod stats;
+pub mod clipboard;
+pub mod frame_export;
+pub mod sampler;
+pub mod recorder;
+pub mod update;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+#[cfg(feature = "lua-plugins")]
+pub mod lua;
+
+// === Public Re-exports ===
+// These make the main types available as `widget::TypeName` instead of
+// `widget::module::TypeName` for cleaner imports in widget_main.rs
+
+/// CPU, Memory, and GPU usage monitoring
+pub use utilization::UtilizationMonitor;
+
+/// CPU and GPU temperature monitoring
+pub use temperature::TemperatureMonitor;
+
+/// Network bandwidth monitoring
+pub use network::NetworkMonitor;
+
+/// Weather data from OpenWeatherMap
+pub use weather::{WeatherMonitor, load_weather_font};
+
+/// Disk space monitoring
+pub use storage::StorageMonitor;
+
+/// Battery level monitoring (system + Solaar)
+pub use battery::{BatteryMonitor, BatteryDevice};
+
+/// Device discovery cache
+pub use cache::WidgetCache;
+
+/// Desktop notification monitoring
+pub use notifications::NotificationMonitor;
+
+/// Cider media player integration
+pub use media::{MediaMonitor, MediaInfo, PlaybackStatus};
+
+/// COSMIC theme integration
+pub use theme::CosmicTheme;
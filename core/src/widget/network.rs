@@ -24,19 +24,35 @@
 //!
 //! ## Display Format
 //!
-//! Rates are converted to human-readable units in the renderer:
-//! - KB/s for speeds < 1 MB/s
-//! - MB/s for speeds ≥ 1 MB/s
+//! Rates are scaled to the largest fitting unit by [`format_rate`]:
+//! - Bytes: B/s, KB/s, MB/s, GB/s (steps of 1024)
+//! - Bits (`network_rate_bits`): bit/s, kbit/s, Mbit/s, Gbit/s (steps of 1000,
+//!   as link speeds are quoted)
 //!
 //! ## Edge Cases Handled
 //!
 //! - **Counter reset**: Kernel updates or interface restarts reset counters to 0
 //! - **First update**: No previous data, so rate starts at 0
 //! - **Interface changes**: New interfaces are automatically included on refresh
+//!
+//! Each interface's own rate is kept too ([`InterfaceRate`]), for the
+//! network tooltip.
 
 use sysinfo::Networks;
+use std::collections::HashMap;
 use std::time::Instant;
 
+/// Download and upload rate of one interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceRate {
+    /// Interface name (`wlan0`, `enp3s0`)
+    pub name: String,
+    /// Download rate in bytes per second
+    pub rx_rate: f64,
+    /// Upload rate in bytes per second
+    pub tx_rate: f64,
+}
+
 // ============================================================================
 // Network Monitor Struct
 // ============================================================================
@@ -72,8 +88,14 @@ pub struct NetworkMonitor {
     pub network_rx_rate: f64,
     /// Current upload rate in bytes per second
     pub network_tx_rate: f64,
+    /// Previous (received, transmitted) totals of each interface
+    interface_bytes: HashMap<String, (u64, u64)>,
+    /// Current rates of each interface except loopback, sorted by name
+    pub interface_rates: Vec<InterfaceRate>,
     /// Timestamp of last update for elapsed time calculation
     last_update: Instant,
+    /// Number of updates so far (rates need two)
+    samples: u32,
 }
 
 impl NetworkMonitor {
@@ -89,7 +111,10 @@ impl NetworkMonitor {
             network_tx_bytes: 0,
             network_rx_rate: 0.0,
             network_tx_rate: 0.0,
+            interface_bytes: HashMap::new(),
+            interface_rates: Vec::new(),
             last_update: Instant::now(),
+            samples: 0,
         }
     }
 
@@ -138,9 +163,95 @@ impl NetworkMonitor {
             self.network_tx_rate = 0.0;
         }
         
+        // Per-interface rates, with the same reset handling
+        let mut interface_bytes = HashMap::new();
+        self.interface_rates.clear();
+        for (name, network) in &self.networks {
+            let rx = network.total_received();
+            let tx = network.total_transmitted();
+            if let Some(&(previous_rx, previous_tx)) = self.interface_bytes.get(name) {
+                if name != "lo" && elapsed > 0.0 {
+                    self.interface_rates.push(InterfaceRate {
+                        name: name.clone(),
+                        rx_rate: rx.saturating_sub(previous_rx) as f64 / elapsed,
+                        tx_rate: tx.saturating_sub(previous_tx) as f64 / elapsed,
+                    });
+                }
+            }
+            interface_bytes.insert(name.clone(), (rx, tx));
+        }
+        self.interface_rates.sort_by(|a, b| a.name.cmp(&b.name));
+        self.interface_bytes = interface_bytes;
+        
         // Store current values for next update's delta calculation
         self.network_rx_bytes = total_rx;
         self.network_tx_bytes = total_tx;
         self.last_update = now;
+        self.samples = self.samples.saturating_add(1);
+    }
+    
+    /// Whether the rates are based on a real delta yet.
+    pub fn has_sample(&self) -> bool {
+        self.samples >= 2
+    }
+    
+    /// Every interface with its total traffic, for the diagnostics page.
+    pub fn interfaces(&self) -> Vec<super::diagnostics::InterfaceStatus> {
+        let mut interfaces: Vec<_> = self
+            .networks
+            .iter()
+            .map(|(name, network)| super::diagnostics::InterfaceStatus {
+                name: name.clone(),
+                total_received: network.total_received(),
+                total_transmitted: network.total_transmitted(),
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+}
+
+// ============================================================================
+// Rate Formatting
+// ============================================================================
+
+/// Format a rate in bytes per second with the largest unit below it
+/// (`512 B/s`, `1.2 KB/s`, `112.3 MB/s`), or in bits per second.
+pub fn format_rate(bytes_per_sec: f64, bits: bool) -> String {
+    let (mut value, step, units) = if bits {
+        (bytes_per_sec * 8.0, 1000.0, ["bit/s", "kbit/s", "Mbit/s", "Gbit/s"])
+    } else {
+        (bytes_per_sec, 1024.0, ["B/s", "KB/s", "MB/s", "GB/s"])
+    };
+    value = value.max(0.0);
+    let mut unit = 0;
+    while value >= step && unit < units.len() - 1 {
+        value /= step;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, units[unit])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(0.0, false), "0 B/s");
+        assert_eq!(format_rate(512.0, false), "512 B/s");
+        assert_eq!(format_rate(1536.0, false), "1.5 KB/s");
+        assert_eq!(format_rate(112_000.0 * 1024.0, false), "109.4 MB/s");
+        assert_eq!(format_rate(3.0 * 1024.0 * 1024.0 * 1024.0, false), "3.0 GB/s");
+        assert_eq!(format_rate(5000.0 * 1024.0 * 1024.0 * 1024.0, false), "5000.0 GB/s");
+        assert_eq!(format_rate(-1.0, false), "0 B/s");
+
+        assert_eq!(format_rate(100.0, true), "800 bit/s");
+        assert_eq!(format_rate(125_000_000.0, true), "1.0 Gbit/s");
+        assert_eq!(format_rate(11_750_000.0, true), "94.0 Mbit/s");
     }
 }
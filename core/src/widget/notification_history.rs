@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Notification History
+//!
+//! With `notification_history` on, captured notifications are kept in a
+//! JSON lines file and reloaded at startup, so restarting the widget
+//! doesn't wipe the list:
+//!
+//! ```text
+//! ~/.local/state/cosmic-monitor/notifications.jsonl
+//! {"app_name":"Files","summary":"Copy finished","body":"","timestamp":1792143000,...}   ← newest first
+//! {"app_name":"System","summary":"Updates installed",...}
+//! ```
+//!
+//! The file mirrors the notification list: it is rewritten whenever a
+//! notification arrives or is dismissed, and holds at most
+//! `notification_history_count` entries. Entries older than
+//! `notification_history_days` (0 = no age limit) are dropped when the file
+//! is loaded or written. Lines that don't parse are skipped.
+//!
+//! Turning the history off deletes the file, as does "Clear Notification
+//! History" in settings (which also clears the running widget's list over
+//! the control pipe).
+
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+
+use super::notifications::Notification;
+use crate::config::Config;
+
+/// Seconds in a day, for the age limit.
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How much history is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistorySettings {
+    /// Most notifications kept (also the size of the live list)
+    pub max_count: usize,
+    /// Oldest notifications kept, in days (0 = no limit)
+    pub max_age_days: u32,
+}
+
+/// Settings for a configuration (`None` while history is off).
+pub fn history_settings(config: &Config) -> Option<HistorySettings> {
+    config.notification_history.then_some(HistorySettings {
+        max_count: config.notification_history_count,
+        max_age_days: config.notification_history_days,
+    })
+}
+
+/// Path of the history file (`None` without a home directory).
+pub fn history_path() -> Option<PathBuf> {
+    Some(dirs::state_dir()?.join("cosmic-monitor").join("notifications.jsonl"))
+}
+
+/// Notifications stored by an earlier run, newest first.
+pub fn load(settings: &HistorySettings, now_secs: u64) -> Vec<Notification> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+    match fs::read_to_string(&path) {
+        Ok(text) => {
+            let notifications = parse(&text, settings, now_secs);
+            log::info!("Loaded {} notifications from {}", notifications.len(), path.display());
+            notifications
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            log::warn!("Failed to read notification history {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Replace the stored history with `notifications` (newest first).
+pub fn save(notifications: &[Notification], settings: &HistorySettings, now_secs: u64) -> io::Result<()> {
+    let path = history_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut text = String::new();
+    for notification in notifications
+        .iter()
+        .filter(|n| is_recent(n, settings, now_secs))
+        .take(settings.max_count)
+    {
+        text.push_str(&serde_json::to_string(notification).map_err(io::Error::other)?);
+        text.push('\n');
+    }
+    // Written whole and renamed, so a crash never leaves half a file.
+    // Notifications can be private, so only the user may read them.
+    let temp = path.with_extension("jsonl.tmp");
+    // The mode only applies to new files, so don't reuse a leftover one
+    let _ = fs::remove_file(&temp);
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&temp)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, &path)
+}
+
+/// Delete the stored history.
+pub fn clear() -> io::Result<()> {
+    let Some(path) = history_path() else {
+        return Ok(());
+    };
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Notifications of a history file within the limits.
+fn parse(text: &str, settings: &HistorySettings, now_secs: u64) -> Vec<Notification> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<Notification>(line) {
+            Ok(notification) => Some(notification),
+            Err(e) => {
+                log::warn!("Skipping unreadable notification history line: {}", e);
+                None
+            }
+        })
+        .filter(|n| is_recent(n, settings, now_secs))
+        .take(settings.max_count)
+        .collect()
+}
+
+/// Whether a notification is within the age limit.
+fn is_recent(notification: &Notification, settings: &HistorySettings, now_secs: u64) -> bool {
+    settings.max_age_days == 0
+        || now_secs.saturating_sub(notification.timestamp) <= settings.max_age_days as u64 * SECS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::notifications::Urgency;
+
+    #[test]
+    fn test_parse_history() {
+        let notification = |summary: &str, timestamp| Notification {
+            app_name: String::from("App"),
+            summary: summary.to_string(),
+            body: String::new(),
+            timestamp,
+            urgency: Urgency::Normal,
+            actions: Vec::new(),
+            desktop_entry: None,
+        };
+        let now = 100 * SECS_PER_DAY;
+        let lines: Vec<String> = [
+            notification("today", now - 60),
+            notification("yesterday", now - SECS_PER_DAY),
+            notification("last month", now - 30 * SECS_PER_DAY),
+        ]
+        .iter()
+        .map(|n| serde_json::to_string(n).unwrap())
+        .collect();
+        let text = format!("{}\nnot json\n{}\n{}\n", lines[0], lines[1], lines[2]);
+
+        let week = HistorySettings { max_count: 10, max_age_days: 7 };
+        let summaries = |list: Vec<Notification>| list.into_iter().map(|n| n.summary).collect::<Vec<_>>();
+        assert_eq!(summaries(parse(&text, &week, now)), ["today", "yesterday"]);
+
+        let forever = HistorySettings { max_count: 10, max_age_days: 0 };
+        assert_eq!(parse(&text, &forever, now).len(), 3);
+
+        let one = HistorySettings { max_count: 1, max_age_days: 0 };
+        assert_eq!(summaries(parse(&text, &one, now)), ["today"]);
+    }
+}
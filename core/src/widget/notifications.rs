@@ -0,0 +1,697 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Notification Monitoring Module
+//!
+//! This module captures desktop notifications via D-Bus and displays them
+//! in the widget. Uses `busctl` to monitor the `org.freedesktop.Notifications`
+//! interface for incoming notification calls.
+//!
+//! ## D-Bus Interface
+//!
+//! Monitors the standard FreeDesktop Notifications specification:
+//! ```text
+//! Interface: org.freedesktop.Notifications
+//! Method: Notify(app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout)
+//! ```
+//!
+//! ## Data Flow
+//!
+//! ```text
+//! ┌──────────────┐    ┌─────────────┐    ┌───────────────┐
+//! │ Desktop App  │───►│ D-Bus       │───►│ busctl        │
+//! │ (notify-send)│    │ Notify call │    │ monitor       │
+//! └──────────────┘    └─────────────┘    └───────┬───────┘
+//!                                                 │
+//!                     ┌───────────────┐          │ stdout
+//!                     │ Main Thread   │◄─────────┘
+//!                     │ (reads list)  │    ┌───────────────┐
+//!                     └───────────────┘    │ Background    │
+//!                                          │ Thread        │
+//!                                          │ (parses)      │
+//!                                          └───────────────┘
+//! ```
+//!
+//! ## busctl Output Parsing
+//!
+//! The `busctl monitor` command outputs D-Bus messages in a text format.
+//! We parse the arguments of Notify method calls, following the nesting of
+//! the `{ ... };` blocks:
+//!
+//! ```text
+//! Type=method_call  Member=Notify
+//!   MESSAGE "susssasa{sv}i" {
+//!     STRING "app_name";        # Application name
+//!     UINT32 0;                 # replaces_id
+//!     STRING "";                # App icon (usually empty)
+//!     STRING "Summary text";    # Notification title
+//!     STRING "Body text";       # Notification body
+//!     ARRAY "s" {               # Actions: key, label, key, label, ...
+//!       STRING "default"; STRING "Open";
+//!     };
+//!     ARRAY "{sv}" {            # Hints
+//!       DICT_ENTRY "sv" { STRING "urgency"; VARIANT "y" { BYTE 2; }; };
+//!       DICT_ENTRY "sv" { STRING "desktop-entry"; VARIANT "s" { STRING "org.gnome.Nautilus"; }; };
+//!     };
+//!     INT32 -1;                 # expire_timeout
+//!   };
+//! ```
+//!
+//! ## Urgency and Actions
+//!
+//! Critical notifications (urgency 2) get a red accent and are pinned: they
+//! stay at the top of the list and are the last to be dropped when it's full.
+//!
+//! Clicking a notification with a default action runs it through the
+//! sending app's `org.freedesktop.Application` D-Bus interface, found via
+//! its `desktop-entry` hint. Only the notification daemon may emit
+//! `ActionInvoked`, so this works for D-Bus activatable apps (most GTK and
+//! GNOME apps) and the notification is dismissed afterwards:
+//!
+//! ```text
+//! action key    call
+//! "default"     Activate({})                    # raise the app
+//! "app.open"    ActivateAction("open", [], {})  # GNotification action
+//! ```
+//!
+//! ## Notification Management
+//!
+//! - New notifications are inserted at the front (newest first, critical ones before the rest)
+//! - List is capped at `max_notifications` to prevent unbounded growth
+//! - Provides methods to clear all, clear by app, or remove specific notifications
+
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::exec::{self, CommandExt};
+use super::notification_history::{self, HistorySettings};
+use super::watchdog::Heartbeat;
+
+// ============================================================================
+// Urgency
+// ============================================================================
+
+/// Urgency level of a notification (the `urgency` hint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Urgency {
+    /// Background information (e.g., a finished download)
+    Low,
+    /// Most notifications
+    #[default]
+    Normal,
+    /// Needs attention (e.g., low battery); pinned and drawn in red
+    Critical,
+}
+
+impl Urgency {
+    /// Level of an `urgency` hint byte (0, 1 or 2; anything else is normal).
+    fn from_hint(level: u8) -> Self {
+        match level {
+            0 => Self::Low,
+            2 => Self::Critical,
+            _ => Self::Normal,
+        }
+    }
+}
+
+// ============================================================================
+// Notification Struct
+// ============================================================================
+
+/// A captured desktop notification.
+///
+/// Contains the essential fields from a D-Bus Notify method call,
+/// plus a timestamp for ordering and identification. Serialized into the
+/// notification history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    /// Application that sent the notification (e.g., "Firefox", "System")
+    pub app_name: String,
+    /// Notification title/headline
+    pub summary: String,
+    /// Notification body text (may be empty)
+    pub body: String,
+    /// Unix timestamp when notification was captured (seconds since epoch)
+    pub timestamp: u64,
+    /// Urgency level from the `urgency` hint
+    #[serde(default)]
+    pub urgency: Urgency,
+    /// Offered actions as (key, label) pairs, in order
+    #[serde(default)]
+    pub actions: Vec<(String, String)>,
+    /// Desktop entry of the sending app (`desktop-entry` hint, without ".desktop")
+    #[serde(default)]
+    pub desktop_entry: Option<String>,
+}
+
+impl Notification {
+    /// Whether the notification is critical (pinned, drawn in red).
+    pub fn is_critical(&self) -> bool {
+        self.urgency == Urgency::Critical
+    }
+
+    /// Key of the action run by clicking the notification, if it has one
+    /// that can be sent to its app.
+    pub fn default_action(&self) -> Option<&str> {
+        self.desktop_entry.as_ref()?;
+        self.actions
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .find(|key| *key == "default")
+    }
+}
+
+/// Insert a new notification at the front of `list`, keeping critical
+/// notifications first, and drop the oldest beyond `max_count` (normal ones
+/// before critical ones).
+fn insert_notification(list: &mut Vec<Notification>, notification: Notification, max_count: usize) {
+    list.insert(0, notification);
+    // Stable, so each part stays newest first
+    list.sort_by_key(|n| !n.is_critical());
+    list.truncate(max_count);
+}
+
+/// Most notifications kept: the history size while history is on.
+fn capacity(max_notifications: usize, history: Option<&HistorySettings>) -> usize {
+    history.map_or(max_notifications, |settings| settings.max_count)
+}
+
+/// Write the list to the history file, if history is on.
+fn persist(notifications: &[Notification], history: Option<&HistorySettings>) {
+    let Some(settings) = history else {
+        return;
+    };
+    if let Err(e) = notification_history::save(notifications, settings, now_secs()) {
+        log::warn!("Failed to save notification history: {}", e);
+    }
+}
+
+/// Current Unix time in seconds.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// ============================================================================
+// Notify Call Parsing
+// ============================================================================
+
+/// Argument block of a Notify call being read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum NotifyBlock {
+    /// The plain arguments
+    #[default]
+    Arguments,
+    /// `ARRAY "s"`: the actions
+    Actions,
+    /// `ARRAY "{sv}"`: the hints
+    Hints,
+    /// Any other nested value
+    Other,
+}
+
+/// Reads Notify calls from `busctl monitor` output, one line at a time.
+#[derive(Debug, Default)]
+struct NotifyParser {
+    /// Inside the MESSAGE of a Notify call
+    in_call: bool,
+    /// Nesting depth of `{ ... };` blocks (1 = the call's arguments)
+    depth: usize,
+    /// Block at depth 2 being read
+    block: NotifyBlock,
+    /// STRING arguments seen (app_name, app_icon, summary, body)
+    strings: Vec<String>,
+    /// Action keys and labels, alternating
+    actions: Vec<String>,
+    /// Key of the hint whose value comes next
+    hint: Option<String>,
+    urgency: Urgency,
+    desktop_entry: Option<String>,
+}
+
+impl NotifyParser {
+    /// Read one line of output; returns the notification once its call ended.
+    /// Notifications without a summary are skipped.
+    fn feed(&mut self, line: &str) -> Option<Notification> {
+        let trimmed = line.trim();
+        if trimmed.contains("Member=Notify") {
+            *self = Self { in_call: true, ..Self::default() };
+            return None;
+        }
+        if !self.in_call {
+            return None;
+        }
+
+        if trimmed.ends_with('{') {
+            self.depth += 1;
+            if self.depth == 2 {
+                self.block = if trimmed.starts_with("ARRAY \"s\"") {
+                    NotifyBlock::Actions
+                } else if trimmed.starts_with("ARRAY \"{sv}\"") {
+                    NotifyBlock::Hints
+                } else {
+                    NotifyBlock::Other
+                };
+            }
+            return None;
+        }
+        if trimmed == "};" {
+            self.depth = self.depth.saturating_sub(1);
+            if self.depth == 1 {
+                self.block = NotifyBlock::Arguments;
+            }
+            if self.depth > 0 {
+                return None;
+            }
+            // End of the call
+            self.in_call = false;
+            // STRING arguments: app_name, app_icon, summary, body
+            let mut strings = std::mem::take(&mut self.strings).into_iter();
+            let app_name = strings.next().unwrap_or_default();
+            let summary = strings.nth(1).filter(|summary| !summary.is_empty())?;
+            let body = strings.next().unwrap_or_default();
+            let mut words = std::mem::take(&mut self.actions).into_iter();
+            let mut actions = Vec::new();
+            while let (Some(key), Some(label)) = (words.next(), words.next()) {
+                actions.push((key, label));
+            }
+            let timestamp = now_secs();
+            return Some(Notification {
+                app_name: if app_name.is_empty() {
+                    "System".to_string()  // Fallback for empty app_name
+                } else {
+                    app_name
+                },
+                summary,
+                body,
+                timestamp,
+                urgency: self.urgency,
+                actions,
+                desktop_entry: self.desktop_entry.take(),
+            });
+        }
+
+        match (self.block, self.depth) {
+            (NotifyBlock::Arguments, 1) => self.strings.extend(string_value(trimmed)),
+            (NotifyBlock::Actions, 2) => self.actions.extend(string_value(trimmed)),
+            // Hint key inside DICT_ENTRY
+            (NotifyBlock::Hints, 3) => self.hint = string_value(trimmed).or(self.hint.take()),
+            // Hint value inside VARIANT
+            (NotifyBlock::Hints, 4) => match self.hint.as_deref() {
+                Some("urgency") => {
+                    if let Some(level) = trimmed.strip_prefix("BYTE ").and_then(|v| v.trim_end_matches(';').parse().ok()) {
+                        self.urgency = Urgency::from_hint(level);
+                    }
+                }
+                Some("desktop-entry") => {
+                    if let Some(entry) = string_value(trimmed).filter(|entry| !entry.is_empty()) {
+                        self.desktop_entry = Some(entry.trim_end_matches(".desktop").to_string());
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Value of a `STRING "value";` line.
+fn string_value(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("STRING \"")?;
+    let end = rest.rfind('"')?;
+    Some(rest[..end].to_string())
+}
+
+// ============================================================================
+// Notification Monitor Struct
+// ============================================================================
+
+/// Monitors D-Bus for desktop notifications.
+///
+/// Spawns a background thread running `busctl monitor` to capture incoming
+/// notifications. The notification list is shared via Arc<Mutex> for
+/// thread-safe access from the main render thread.
+///
+/// # Threading Model
+///
+/// - Background thread: Runs `busctl monitor`, parses output, updates list
+/// - Main thread: Reads notification list for rendering
+/// - Shared state: `notifications` Vec protected by Mutex
+///
+/// With history on, the list is loaded from the history file at startup
+/// and written back whenever it changes (see [`notification_history`]).
+///
+/// # Resource Usage
+///
+/// - Spawns one persistent background thread
+/// - Spawns one `busctl` child process
+/// - Both run for the lifetime of the application
+pub struct NotificationMonitor {
+    /// Shared notification list, newest first
+    notifications: Arc<Mutex<Vec<Notification>>>,
+    /// Maximum number of notifications to keep without history (prevents unbounded growth)
+    max_notifications: usize,
+    /// History limits (None = history off)
+    history: Arc<Mutex<Option<HistorySettings>>>,
+    /// Set by the background thread if monitoring stops (None = running)
+    error: Arc<Mutex<Option<String>>>,
+    /// Never beats (the listener waits for D-Bus traffic); tells the
+    /// watchdog when the listener thread ends
+    heartbeat: Heartbeat,
+}
+
+impl NotificationMonitor {
+    /// Create a new notification monitor with background D-Bus listener.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_notifications` - Maximum notifications to keep without history (oldest are dropped)
+    /// * `history` - History limits, `None` to keep notifications in memory only
+    ///
+    /// # Background Thread
+    ///
+    /// Immediately spawns a background thread that:
+    /// 1. Starts `busctl monitor` to watch D-Bus
+    /// 2. Parses Notify method calls from stdout
+    /// 3. Extracts app_name, summary, and body
+    /// 4. Updates the shared notification list
+    pub fn new(max_notifications: usize, history: Option<HistorySettings>) -> Self {
+        let restored = history
+            .as_ref()
+            .map(|settings| notification_history::load(settings, now_secs()))
+            .unwrap_or_default();
+        let notifications = Arc::new(Mutex::new(restored));
+        let history = Arc::new(Mutex::new(history));
+        let error = Arc::new(Mutex::new(None));
+        let heartbeat = Heartbeat::new();
+        
+        // Spawn background thread to monitor D-Bus
+        // This runs for the lifetime of the application
+        let notifications_clone = Arc::clone(&notifications);
+        let history_clone = Arc::clone(&history);
+        let error_clone = Arc::clone(&error);
+        let max_count = max_notifications;
+        let exit_guard = heartbeat.guard();
+        
+        std::thread::spawn(move || {
+            let _exit_guard = exit_guard;
+            super::priority::enter_background("notifications");
+            let message = match Self::monitor_notifications(notifications_clone, max_count, history_clone) {
+                Ok(()) => String::from("Notification monitor stopped (busctl exited)"),
+                Err(e) => format!("Notification monitoring failed: {}", e),
+            };
+            log::error!("{}", message);
+            super::diagnostics::record_error("notifications", &message);
+            *error_clone.lock().unwrap() = Some(message);
+        });
+        
+        Self {
+            notifications,
+            max_notifications,
+            history,
+            error,
+            heartbeat,
+        }
+    }
+    
+    /// Main D-Bus monitoring loop (runs in background thread).
+    ///
+    /// Uses `busctl monitor` to watch for Notify method calls on the
+    /// user session bus. Parses the text output to extract notification
+    /// fields.
+    ///
+    /// # busctl Command
+    ///
+    /// ```bash
+    /// busctl monitor --user \
+    ///   --match "type=method_call,interface=org.freedesktop.Notifications,member=Notify"
+    /// ```
+    ///
+    /// # Parsing Strategy
+    ///
+    /// 1. Watch for lines containing "Member=Notify" to start new notification
+    /// 2. Track the `{ ... };` nesting to tell arguments, actions and hints apart
+    /// 3. Extract values between double quotes (and the urgency BYTE)
+    /// 4. When the call's MESSAGE block closes, save the notification
+    ///
+    /// # Error Handling
+    ///
+    /// Returns error if busctl cannot be spawned. Parsing errors within
+    /// the loop are logged but don't stop monitoring.
+    fn monitor_notifications(
+        notifications: Arc<Mutex<Vec<Notification>>>,
+        max_count: usize,
+        history: Arc<Mutex<Option<HistorySettings>>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use std::process::{Command, Stdio};
+        use std::io::{BufRead, BufReader};
+        
+        log::info!("Starting notification monitor via busctl");
+        
+        // Use busctl to monitor D-Bus for Notify calls
+        // --user: Watch user session bus (not system bus)
+        // --match: Filter for only Notify method calls
+        let mut child = Command::new("busctl")
+            .args(&[
+                "monitor",
+                "--user",
+                "--match",
+                "type=method_call,interface=org.freedesktop.Notifications,member=Notify",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())  // Suppress busctl stderr noise
+            .spawn()?;
+        
+        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+        let reader = BufReader::new(stdout);
+        
+        // Process busctl output line by line
+        let mut parser = NotifyParser::default();
+        for line in reader.lines() {
+            let line = line?;
+            if let Some(notification) = parser.feed(&line) {
+                log::info!("Captured notification: {} - {} ({:?}, {} actions)",
+                    notification.app_name, notification.summary, notification.urgency, notification.actions.len());
+                
+                // Insert at front (newest first) and truncate if needed
+                let history = *history.lock().unwrap();
+                let mut notifs = notifications.lock().unwrap();
+                insert_notification(&mut notifs, notification, capacity(max_count, history.as_ref()));
+                persist(&notifs, history.as_ref());
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Get a snapshot of current notifications (newest first).
+    ///
+    /// Returns a clone of the notification list for safe iteration
+    /// without holding the lock.
+    pub fn get_notifications(&self) -> Vec<Notification> {
+        self.notifications.lock().unwrap().clone()
+    }
+    
+    /// Error if the D-Bus listener is not running, if any.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+    
+    /// Heartbeat of the listener thread (for the watchdog, which only
+    /// looks at whether the thread ended).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+    
+    /// Change the history limits (`None` turns history off and deletes
+    /// the history file).
+    pub fn set_history(&self, history: Option<HistorySettings>) {
+        *self.history.lock().unwrap() = history;
+        let mut notifs = self.notifications.lock().unwrap();
+        notifs.truncate(capacity(self.max_notifications, history.as_ref()));
+        match history {
+            Some(settings) => persist(&notifs, Some(&settings)),
+            None => {
+                if let Err(e) = notification_history::clear() {
+                    log::warn!("Failed to delete notification history: {}", e);
+                }
+            }
+        }
+    }
+    
+    /// Write the list to the history file after a change, if history is on.
+    fn save_history(&self, notifications: &[Notification]) {
+        persist(notifications, self.history.lock().unwrap().as_ref());
+    }
+    
+    /// Clear all notifications.
+    ///
+    /// Removes all notifications from the list (and the history). Does not
+    /// affect the underlying D-Bus monitoring (new notifications will still appear).
+    pub fn clear(&self) {
+        let mut notifs = self.notifications.lock().unwrap();
+        notifs.clear();
+        self.save_history(&notifs);
+        log::info!("Cleared all notifications");
+    }
+    
+    /// Clear all notifications from a specific application.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_name` - Application name to filter (exact match)
+    pub fn clear_app(&self, app_name: &str) {
+        let mut notifs = self.notifications.lock().unwrap();
+        notifs.retain(|n| n.app_name != app_name);
+        self.save_history(&notifs);
+        log::info!("Cleared notifications for app: {}", app_name);
+    }
+    
+    /// Run the default action of a notification and dismiss it.
+    ///
+    /// The action is sent to the app in the background; apps that aren't
+    /// D-Bus activatable ignore it (logged).
+    ///
+    /// # Returns
+    ///
+    /// Whether the notification had a default action.
+    pub fn activate(&self, app_name: &str, timestamp: u64) -> bool {
+        let target = self
+            .notifications
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|n| n.app_name == app_name && n.timestamp == timestamp)
+            .and_then(|n| Some((n.desktop_entry.clone()?, n.default_action()?.to_string())));
+        let Some((desktop_entry, action)) = target else {
+            return false;
+        };
+
+        std::thread::spawn(move || {
+            let path = application_path(&desktop_entry);
+            let mut command = Command::new("busctl");
+            command.args(["--user", "call", &desktop_entry, &path, "org.freedesktop.Application"]);
+            match action.strip_prefix("app.") {
+                Some(name) => command.args(["ActivateAction", "sava{sv}", name, "0", "0"]),
+                None => command.args(["Activate", "a{sv}", "0"]),
+            };
+            log::info!("Activating {} for notification action {:?}", desktop_entry, action);
+            match command.bounded_output(exec::SLOW_TIMEOUT) {
+                Ok(output) if output.status.success() => {}
+                Ok(output) => log::warn!(
+                    "{} did not take the notification action: {}",
+                    desktop_entry,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(e) => log::warn!("Failed to run busctl: {}", e),
+            }
+        });
+        self.remove_notification(app_name, timestamp);
+        true
+    }
+    
+    /// Remove a specific notification by app name and timestamp.
+    ///
+    /// Used when the user clicks the X button on a specific notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_name` - Application name of the notification
+    /// * `timestamp` - Unix timestamp when notification was captured
+    pub fn remove_notification(&self, app_name: &str, timestamp: u64) {
+        let mut notifs = self.notifications.lock().unwrap();
+        notifs.retain(|n| !(n.app_name == app_name && n.timestamp == timestamp));
+        self.save_history(&notifs);
+        log::info!("Removed notification: {} at {}", app_name, timestamp);
+    }
+}
+
+/// D-Bus object path of an activatable app (`org.gnome.Nautilus` →
+/// `/org/gnome/Nautilus`, dashes become underscores).
+fn application_path(desktop_entry: &str) -> String {
+    format!("/{}", desktop_entry.replace('.', "/").replace('-', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `busctl monitor` output of `notify-send -u critical -A default=Open`
+    const NOTIFY_CALL: &str = r#"‣ Type=method_call  Endian=l  Flags=0  Version=1 Cookie=12  Timestamp="Fri 2026-10-16 10:00:00.000000 UTC"
+  Sender=:1.88  Destination=org.freedesktop.Notifications  Path=/org/freedesktop/Notifications  Interface=org.freedesktop.Notifications  Member=Notify
+  UniqueName=:1.88
+  MESSAGE "susssasa{sv}i" {
+          STRING "Files";
+          UINT32 0;
+          STRING "";
+          STRING "Copy failed";
+          STRING "Disk is full";
+          ARRAY "s" {
+                  STRING "default";
+                  STRING "Open";
+          };
+          ARRAY "{sv}" {
+                  DICT_ENTRY "sv" {
+                          STRING "urgency";
+                          VARIANT "y" {
+                                  BYTE 2;
+                          };
+                  };
+                  DICT_ENTRY "sv" {
+                          STRING "x";
+                          VARIANT "i" {
+                                  INT32 10;
+                          };
+                  };
+                  DICT_ENTRY "sv" {
+                          STRING "desktop-entry";
+                          VARIANT "s" {
+                                  STRING "org.gnome.Nautilus";
+                          };
+                  };
+          };
+          INT32 -1;
+  };
+"#;
+
+    #[test]
+    fn test_parse_notify_call() {
+        let mut parser = NotifyParser::default();
+        let parsed: Vec<_> = NOTIFY_CALL.lines().filter_map(|line| parser.feed(line)).collect();
+        assert_eq!(parsed.len(), 1);
+        let notification = &parsed[0];
+        assert_eq!(notification.app_name, "Files");
+        assert_eq!(notification.summary, "Copy failed");
+        assert_eq!(notification.body, "Disk is full");
+        assert!(notification.is_critical());
+        assert_eq!(notification.actions, vec![(String::from("default"), String::from("Open"))]);
+        assert_eq!(notification.desktop_entry.as_deref(), Some("org.gnome.Nautilus"));
+        assert_eq!(notification.default_action(), Some("default"));
+        assert_eq!(application_path("org.gnome.Nautilus"), "/org/gnome/Nautilus");
+    }
+
+    #[test]
+    fn test_critical_notifications_pinned() {
+        let notification = |summary: &str, urgency| Notification {
+            app_name: String::from("App"),
+            summary: summary.to_string(),
+            body: String::new(),
+            timestamp: 0,
+            urgency,
+            actions: Vec::new(),
+            desktop_entry: None,
+        };
+        let mut list = Vec::new();
+        insert_notification(&mut list, notification("battery", Urgency::Critical), 3);
+        for summary in ["a", "b", "c"] {
+            insert_notification(&mut list, notification(summary, Urgency::Normal), 3);
+        }
+        let summaries: Vec<_> = list.iter().map(|n| n.summary.as_str()).collect();
+        assert_eq!(summaries, ["battery", "c", "b"]);
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Scripted Plugin Sections
+//!
+//! Custom commands (see [`super::custom`]) can only show a line of text.
+//! Plugins go further: each `*.lua` file in `~/.config/cosmic-monitor/plugins`
+//! draws its own block of the Plugins section with a small drawing API and
+//! the same metrics snapshot that is published for other tools.
+//!
+//! Only built with the `lua-plugins` cargo feature; without it the Plugins
+//! section never has anything to show.
+//!
+//! ```bash
+//! cargo build --release --features lua-plugins
+//! ```
+//!
+//! # Writing a Plugin
+//!
+//! ```lua
+//! -- ~/.config/cosmic-monitor/plugins/cpu_ring.lua
+//! name = "CPU Ring"   -- shown in error badges (defaults to the file name)
+//! height = 80         -- pixels reserved for the plugin (max 600)
+//!
+//! function render(metrics)
+//!   local cpu = metrics.cpu_usage or 0
+//!   draw.arc(40, 40, 30, 0, 360, 6, 0.3, 0.3, 0.3)
+//!   draw.arc(40, 40, 30, -90, -90 + cpu * 3.6, 6, 0.2, 0.7, 1.0)
+//!   draw.text(90, 28, string.format("CPU %.0f%%", cpu), 14)
+//! end
+//! ```
+//!
+//! `render` is called once per sample with the metrics snapshot
+//! (`cpu_usage`, `memory_usage`, `cpu_temp`, `network.rx_bytes_per_sec`,
+//! `disks[i].used_percentage`, ...; see [`super::stats::StatsSnapshot`]).
+//! Metrics that aren't collected are `nil`. Coordinates are relative to the
+//! plugin's block, which is 370px wide; drawing outside it is clipped.
+//!
+//! | Function | Draws |
+//! |----------|-------|
+//! | `draw.rect(x, y, w, h, [r, g, b, a])` | Filled rectangle |
+//! | `draw.arc(cx, cy, radius, start, end, width, [r, g, b, a])` | Stroked arc, angles in degrees clockwise from 3 o'clock |
+//! | `draw.text(x, y, text, size, [r, g, b, a])` | Outlined text like the rest of the widget |
+//!
+//! Colors are 0.0 - 1.0 and default to opaque white.
+//!
+//! # Sandboxing
+//!
+//! Scripts only get Lua's `math`, `string`, `table` and `utf8` libraries
+//! (no `io`, `os` or `require`), each runs in its own Lua state, and a
+//! `render` call that takes longer than 50ms is aborted. Files are reloaded
+//! when they change.
+//!
+//! ```text
+//! plugins/*.lua ──load──► LuaPlugin (own Lua state)
+//!                              │ render(metrics) once per sample
+//!                              ▼
+//!                       Vec<DrawOp> ──PluginFrame──► FrameData ──► render_plugins()
+//! ```
+//!
+//! Scripts never touch Cairo: they record [`DrawOp`]s which the renderer
+//! replays, so a misbehaving script can't break the frame around it.
+
+use std::path::PathBuf;
+
+#[cfg(feature = "lua-plugins")]
+pub use super::lua::PluginHost;
+
+/// Gap between two plugins in the section.
+pub const PLUGIN_SPACING: u32 = 10;
+
+/// One recorded drawing call of a plugin, in plugin-local coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawOp {
+    /// Filled rectangle
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: (f64, f64, f64, f64),
+    },
+    /// Stroked arc (angles in radians)
+    Arc {
+        cx: f64,
+        cy: f64,
+        radius: f64,
+        start: f64,
+        end: f64,
+        line_width: f64,
+        color: (f64, f64, f64, f64),
+    },
+    /// Outlined text
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        size: f64,
+        color: (f64, f64, f64, f64),
+    },
+}
+
+/// What one plugin drew for the latest sample.
+#[derive(Debug, Clone, Default)]
+pub struct PluginFrame {
+    /// Plugin name (from the script, or its file name)
+    pub name: String,
+    /// Pixels reserved for the plugin
+    pub height: u32,
+    /// Drawing calls of the last successful `render`
+    pub ops: Vec<DrawOp>,
+}
+
+/// Directory plugins are loaded from.
+///
+/// `~/.config/cosmic-monitor/plugins`
+pub fn plugin_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("cosmic-monitor")
+        .join("plugins")
+}
+
+/// Total height of a set of plugin frames including the gaps between them.
+pub fn total_height(frames: &[PluginFrame]) -> u32 {
+    let gaps = frames.len().saturating_sub(1) as u32 * PLUGIN_SPACING;
+    frames.iter().map(|frame| frame.height).sum::<u32>() + gaps
+}
+
+/// Stand-in used when the widget is built without the `lua-plugins` feature.
+///
+/// Never loads anything, so the Plugins section stays hidden.
+#[cfg(not(feature = "lua-plugins"))]
+#[derive(Default)]
+pub struct PluginHost;
+
+#[cfg(not(feature = "lua-plugins"))]
+impl PluginHost {
+    /// Create the (empty) host.
+    pub fn new() -> Self {
+        log::debug!("Built without the lua-plugins feature, not loading {:?}", plugin_dir());
+        Self
+    }
+
+    /// Nothing to render.
+    pub fn update(&mut self, _metrics: &super::stats::StatsSnapshot) {}
+
+    /// No frames.
+    pub fn frames(&self) -> &[PluginFrame] {
+        &[]
+    }
+
+    /// No errors.
+    pub fn error(&self) -> Option<String> {
+        None
+    }
+}
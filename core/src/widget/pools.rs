@@ -0,0 +1,485 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Storage Pools
+//!
+//! Health, scrub status and capacity of ZFS pools and btrfs filesystems,
+//! one row per pool in the Storage Pools section, checked on a long
+//! interval:
+//!
+//! ```text
+//! zpool list -Hp ─────────────► tank  ONLINE  1.2 / 4.0 TiB
+//! zpool status -jp ───────────► scrub finished Oct 13, 0 errors
+//! /proc/mounts (btrfs) ──► /sys/fs/btrfs/<uuid>/devinfo ──► missing devices, error counters
+//!                          btrfs filesystem usage -b ────► capacity
+//!                          btrfs scrub status ───────────► scrub
+//!                     refresher thread, every storage_pools_refresh_minutes
+//! ```
+//!
+//! A btrfs filesystem mounted several times (subvolumes) is listed once,
+//! under its label or first mount point. It is faulted while a device is
+//! missing and degraded once a device logged read, write or checksum
+//! errors.
+//!
+//! Machines without `zpool` or `btrfs` simply list no pools of that kind.
+//! The JSON output of `zpool status` needs OpenZFS 2.3; with older
+//! versions ZFS pools show without a scrub status.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::Value;
+
+use super::exec::{CommandExt, QUICK_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::Config;
+
+/// Where mounted btrfs filesystems are described.
+const BTRFS_SYSFS: &str = "/sys/fs/btrfs";
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSettings {
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn pool_settings(config: &Config) -> Option<PoolSettings> {
+    config.show_storage_pools.then_some(PoolSettings {
+        refresh_minutes: config.storage_pools_refresh_minutes,
+    })
+}
+
+/// Filesystem a pool belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolKind {
+    Zfs,
+    Btrfs,
+}
+
+impl PoolKind {
+    /// Name shown in the row.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoolKind::Zfs => "ZFS",
+            PoolKind::Btrfs => "btrfs",
+        }
+    }
+}
+
+/// Overall health of a pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolHealth {
+    /// Every device working
+    Online,
+    /// Still working, but a device failed or logged errors
+    Degraded,
+    /// Not usable, or missing devices
+    Faulted,
+}
+
+/// Last or running scrub of a pool.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrubStatus {
+    /// Never scrubbed (or unknown)
+    Never,
+    /// Scrubbing, with the share done if known
+    Running(Option<f32>),
+    /// Finished, with the errors found and when
+    Finished { errors: u64, date: Option<String> },
+    /// Canceled or interrupted
+    Canceled,
+}
+
+/// One pool as last checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolStatus {
+    /// Pool name, btrfs label or mount point
+    pub name: String,
+    /// ZFS or btrfs
+    pub kind: PoolKind,
+    /// Overall health
+    pub health: PoolHealth,
+    /// Used and total bytes, if they could be read
+    pub capacity: Option<(u64, u64)>,
+    /// Last or running scrub
+    pub scrub: ScrubStatus,
+}
+
+/// Refreshes the pool status on a background thread.
+pub type PoolMonitor = Refresher<PoolSettings, Vec<PoolStatus>>;
+
+impl RefreshSettings for PoolSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl PoolMonitor {
+    /// Start reading the ZFS pools and btrfs filesystems.
+    pub fn new(settings: Option<PoolSettings>) -> Self {
+        Refresher::spawn("pools", settings, |_: &PoolSettings| {
+            let (pools, error) = refresh();
+            log::debug!("Storage pools refreshed: {:?}", pools);
+            (Some(pools), error)
+        })
+    }
+
+    /// Pools of the last refresh (empty before the first one).
+    pub fn pools(&self) -> Vec<PoolStatus> {
+        self.value().unwrap_or_default()
+    }
+}
+
+/// Check ZFS pools and btrfs filesystems, collecting what went wrong.
+fn refresh() -> (Vec<PoolStatus>, Option<String>) {
+    let mut pools = Vec::new();
+    let mut errors = Vec::new();
+    match zfs_pools() {
+        Ok(zfs) => pools.extend(zfs),
+        Err(e) => errors.push(format!("zpool: {}", e)),
+    }
+    pools.extend(btrfs_pools());
+
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    (pools, error)
+}
+
+// ============================================================================
+// ZFS
+// ============================================================================
+
+/// Pools of `zpool list`, with the scrubs of `zpool status -jp`.
+///
+/// A missing `zpool` command is no error, just no ZFS.
+fn zfs_pools() -> Result<Vec<PoolStatus>, String> {
+    let output = match Command::new("zpool")
+        .args(["list", "-Hp", "-o", "name,size,alloc,health"])
+        .bounded_output(QUICK_TIMEOUT)
+    {
+        Ok(output) => output,
+        Err(e) => {
+            log::debug!("zpool not available: {}", e);
+            return Ok(Vec::new());
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("zpool list failed").trim().to_string());
+    }
+    let mut pools = parse_zpool_list(&String::from_utf8_lossy(&output.stdout));
+    if pools.is_empty() {
+        return Ok(pools);
+    }
+
+    match Command::new("zpool").args(["status", "-jp"]).bounded_output(QUICK_TIMEOUT) {
+        Ok(output) if output.status.success() => {
+            let status: Value = serde_json::from_slice(&output.stdout).unwrap_or(Value::Null);
+            for pool in &mut pools {
+                if let Some(scrub) = status.pointer(&format!("/pools/{}/scan_stats", pool.name)) {
+                    pool.scrub = parse_zfs_scrub(scrub);
+                }
+            }
+        }
+        _ => log::debug!("zpool status -j not supported, ZFS scrubs not shown"),
+    }
+    Ok(pools)
+}
+
+/// Pools of `zpool list -Hp -o name,size,alloc,health` output.
+fn parse_zpool_list(output: &str) -> Vec<PoolStatus> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.trim();
+            let size = fields.next()?.trim().parse::<u64>().ok();
+            let alloc = fields.next()?.trim().parse::<u64>().ok();
+            let health = match fields.next()?.trim() {
+                "ONLINE" => PoolHealth::Online,
+                "DEGRADED" => PoolHealth::Degraded,
+                _ => PoolHealth::Faulted,
+            };
+            Some(PoolStatus {
+                name: name.to_string(),
+                kind: PoolKind::Zfs,
+                health,
+                capacity: alloc.zip(size),
+                scrub: ScrubStatus::Never,
+            })
+        })
+        .filter(|pool| !pool.name.is_empty())
+        .collect()
+}
+
+/// Scrub of a pool's `scan_stats` in `zpool status -jp` output.
+fn parse_zfs_scrub(scan: &Value) -> ScrubStatus {
+    let text = |key: &str| scan.get(key).and_then(Value::as_str).unwrap_or_default();
+    let number = |key: &str| match scan.get(key) {
+        Some(Value::Number(n)) => n.as_u64(),
+        Some(Value::String(s)) => s.parse().ok(),
+        _ => None,
+    };
+    if text("function") != "SCRUB" {
+        return ScrubStatus::Never;
+    }
+    match text("state") {
+        "SCANNING" => {
+            let done = number("issued")
+                .zip(number("to_examine"))
+                .filter(|(_, total)| *total > 0)
+                .map(|(issued, total)| issued as f32 / total as f32 * 100.0);
+            ScrubStatus::Running(done)
+        }
+        "FINISHED" => ScrubStatus::Finished {
+            errors: number("errors").unwrap_or(0),
+            date: short_date(text("end_time")),
+        },
+        "CANCELED" => ScrubStatus::Canceled,
+        _ => ScrubStatus::Never,
+    }
+}
+
+// ============================================================================
+// btrfs
+// ============================================================================
+
+/// Mounted btrfs filesystems, one per filesystem UUID.
+fn btrfs_pools() -> Vec<PoolStatus> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    let mut pools = Vec::new();
+    for (source, mount_point) in btrfs_mounts(&mounts) {
+        let Some(sysfs) = btrfs_sysfs_dir(&source) else {
+            continue;
+        };
+        if !seen.insert(sysfs.clone()) {
+            continue;
+        }
+        let label = read_trimmed(&sysfs.join("label")).filter(|label| !label.is_empty());
+        pools.push(PoolStatus {
+            name: label.unwrap_or_else(|| mount_point.clone()),
+            kind: PoolKind::Btrfs,
+            health: btrfs_health(&sysfs),
+            capacity: btrfs_capacity(&mount_point),
+            scrub: btrfs_scrub(&mount_point),
+        });
+    }
+    pools
+}
+
+/// Source device and mount point of each btrfs line in `/proc/mounts`.
+fn btrfs_mounts(mounts: &str) -> Vec<(String, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?;
+            let mount_point = fields.next()?;
+            (fields.next()? == "btrfs").then(|| (unescape_mount(source), unescape_mount(mount_point)))
+        })
+        .collect()
+}
+
+/// Undo the octal escapes of `/proc/mounts` ("My\040Disk" → "My Disk").
+fn unescape_mount(field: &str) -> String {
+    let mut text = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        text.push_str(&rest[..index]);
+        let code = rest.get(index + 1..index + 4).and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match code {
+            Some(byte) => {
+                text.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                text.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// `/sys/fs/btrfs/<uuid>` of the filesystem on `source`, found by its
+/// kernel device name under `devices/`.
+fn btrfs_sysfs_dir(source: &str) -> Option<PathBuf> {
+    let device = std::fs::canonicalize(source).ok()?;
+    let device_name = device.file_name()?;
+    std::fs::read_dir(BTRFS_SYSFS)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|dir| dir.join("devices").join(device_name).exists())
+}
+
+/// Faulted while a device is missing, degraded once one logged errors.
+fn btrfs_health(sysfs: &Path) -> PoolHealth {
+    let Ok(devices) = std::fs::read_dir(sysfs.join("devinfo")) else {
+        return PoolHealth::Online;
+    };
+    let mut health = PoolHealth::Online;
+    for device in devices.flatten().map(|entry| entry.path()) {
+        if read_trimmed(&device.join("missing")).as_deref() == Some("1") {
+            return PoolHealth::Faulted;
+        }
+        let errors = read_trimmed(&device.join("error_stats")).map(|stats| error_count(&stats)).unwrap_or(0);
+        if errors > 0 {
+            health = PoolHealth::Degraded;
+        }
+    }
+    health
+}
+
+/// Sum of the counters in a `devinfo/<id>/error_stats` file
+/// ("write_errs 0" per line).
+fn error_count(stats: &str) -> u64 {
+    stats
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1)?.parse::<u64>().ok())
+        .sum()
+}
+
+/// Used and total bytes of `btrfs filesystem usage -b`.
+fn btrfs_capacity(mount_point: &str) -> Option<(u64, u64)> {
+    let output = Command::new("btrfs")
+        .args(["filesystem", "usage", "-b", mount_point])
+        .bounded_output(QUICK_TIMEOUT)
+        .ok()?;
+    parse_btrfs_usage(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// "Used" and "Device size" of the Overall block of `btrfs filesystem usage -b`.
+fn parse_btrfs_usage(output: &str) -> Option<(u64, u64)> {
+    let value = |key: &str| {
+        output.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix(key)?.strip_prefix(':')?;
+            rest.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    Some((value("Used")?, value("Device size")?))
+}
+
+/// Scrub of `btrfs scrub status`.
+fn btrfs_scrub(mount_point: &str) -> ScrubStatus {
+    match Command::new("btrfs")
+        .args(["scrub", "status", mount_point])
+        .bounded_output(QUICK_TIMEOUT)
+    {
+        Ok(output) => parse_btrfs_scrub(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => ScrubStatus::Never,
+    }
+}
+
+/// Scrub of `btrfs scrub status` output.
+fn parse_btrfs_scrub(output: &str) -> ScrubStatus {
+    let value = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix(':').map(str::trim))
+    };
+    match value("Status") {
+        Some("running") => {
+            // "Bytes scrubbed:   512.00GiB  (41.58%)"
+            let done = value("Bytes scrubbed")
+                .and_then(|text| text.split('(').nth(1))
+                .and_then(|percent| percent.trim_end_matches([')', '%']).parse().ok());
+            ScrubStatus::Running(done)
+        }
+        Some("finished") => {
+            let summary = value("Error summary").unwrap_or_default();
+            let errors = if summary.contains("no errors") {
+                0
+            } else {
+                summary
+                    .split_whitespace()
+                    .filter_map(|counter| counter.split_once('=')?.1.parse::<u64>().ok())
+                    .sum()
+            };
+            ScrubStatus::Finished { errors, date: value("Scrub started").and_then(short_date) }
+        }
+        Some("aborted" | "interrupted" | "cancelled") => ScrubStatus::Canceled,
+        _ => ScrubStatus::Never,
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// "Oct 13" of a ctime-style date ("Sun Oct 13 00:50:15 2024"), the text
+/// itself if it doesn't parse.
+fn short_date(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() || text == "-" {
+        return None;
+    }
+    let short = chrono::NaiveDateTime::parse_from_str(text, "%a %b %e %H:%M:%S %Y")
+        .map(|date| date.format("%b %-d").to_string())
+        .unwrap_or_else(|_| text.to_string());
+    Some(short)
+}
+
+/// "1.2 TiB" or "512.0 GiB".
+pub fn format_capacity(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let gib = bytes as f64 / GIB;
+    if gib >= 1024.0 { format!("{:.1} TiB", gib / 1024.0) } else { format!("{:.1} GiB", gib) }
+}
+
+/// Read a sysfs file holding one value.
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zfs() {
+        let pools = parse_zpool_list("tank\t3985729650688\t1099511627776\tONLINE\nbackup\t-\t-\tUNAVAIL\n");
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].capacity, Some((1099511627776, 3985729650688)));
+        assert_eq!(pools[0].health, PoolHealth::Online);
+        assert_eq!((pools[1].capacity, pools[1].health), (None, PoolHealth::Faulted));
+
+        let finished: Value = serde_json::from_str(
+            r#"{"function":"SCRUB","state":"FINISHED","end_time":"Sun Oct 13 00:50:15 2024","errors":"0"}"#,
+        )
+        .unwrap();
+        assert_eq!(parse_zfs_scrub(&finished), ScrubStatus::Finished { errors: 0, date: Some(String::from("Oct 13")) });
+        let running: Value =
+            serde_json::from_str(r#"{"function":"SCRUB","state":"SCANNING","issued":"250","to_examine":"1000"}"#).unwrap();
+        assert_eq!(parse_zfs_scrub(&running), ScrubStatus::Running(Some(25.0)));
+        let resilver: Value = serde_json::from_str(r#"{"function":"RESILVER","state":"FINISHED"}"#).unwrap();
+        assert_eq!(parse_zfs_scrub(&resilver), ScrubStatus::Never);
+    }
+
+    #[test]
+    fn test_parse_btrfs() {
+        let mounts = "/dev/nvme0n1p2 / btrfs rw,subvol=/@ 0 0\n/dev/nvme0n1p2 /home btrfs rw,subvol=/@home 0 0\n/dev/sdb1 /mnt/My\\040Disk btrfs rw 0 0\ntmpfs /tmp tmpfs rw 0 0\n";
+        assert_eq!(btrfs_mounts(mounts), [
+            (String::from("/dev/nvme0n1p2"), String::from("/")),
+            (String::from("/dev/nvme0n1p2"), String::from("/home")),
+            (String::from("/dev/sdb1"), String::from("/mnt/My Disk")),
+        ]);
+
+        let usage = "Overall:\n    Device size:\t\t 1000204886016\n    Device allocated:\t\t  214748364800\n    Used:\t\t\t  161061273600\n    Free (estimated):\t\t  830000000000\t(min: 420000000000)\n";
+        assert_eq!(parse_btrfs_usage(usage), Some((161061273600, 1000204886016)));
+        assert_eq!(error_count("write_errs 0\nread_errs 2\ncorruption_errs 1\n"), 3);
+
+        let finished = "UUID:             1234\nScrub started:    Sun Oct 13 00:24:01 2024\nStatus:           finished\nError summary:    no errors found\n";
+        assert_eq!(parse_btrfs_scrub(finished), ScrubStatus::Finished { errors: 0, date: Some(String::from("Oct 13")) });
+        let errors = "Status:           finished\nError summary:    csum=3 verify=1\n";
+        assert_eq!(parse_btrfs_scrub(errors), ScrubStatus::Finished { errors: 4, date: None });
+        let running = "Status:           running\nBytes scrubbed:   512.00GiB  (41.58%)\n";
+        assert_eq!(parse_btrfs_scrub(running), ScrubStatus::Running(Some(41.58)));
+        assert_eq!(parse_btrfs_scrub("UUID: 1234\n\tno stats available\n"), ScrubStatus::Never);
+    }
+}
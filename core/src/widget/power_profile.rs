@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Power Profile
+//!
+//! The active power profile of power-profiles-daemon (power-saver,
+//! balanced, performance), shown in the Battery section header and
+//! switched by clicking it:
+//!
+//! ```text
+//! poll thread (2s) ──busctl get-property ActiveProfile──► "balanced"
+//!                  ──busctl get-property Profiles──────► power-saver, balanced, performance
+//!                                                        │
+//!                        state() ◄── Arc<Mutex<Option<PowerProfiles>>>
+//! click ──cycle()──busctl set-property ActiveProfile s performance
+//! ```
+//!
+//! The daemon is reached on the system bus under its current name
+//! (`org.freedesktop.UPower.PowerProfiles`, 0.20 and newer), falling back to
+//! the older `net.hadess.PowerProfiles`. Desktop users may switch profiles
+//! without a password through the daemon's polkit policy, so the switch
+//! runs with the usual short timeout. Machines without the daemon (or
+//! without a `performance` profile) simply offer fewer profiles.
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use super::exec::{self, CommandExt};
+
+/// How often the active profile is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Bus names, object paths and interfaces of the daemon, newest first.
+const SERVICES: [(&str, &str); 2] = [
+    ("org.freedesktop.UPower.PowerProfiles", "/org/freedesktop/UPower/PowerProfiles"),
+    ("net.hadess.PowerProfiles", "/net/hadess/PowerProfiles"),
+];
+
+/// Profiles as last read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerProfiles {
+    /// Active profile ("balanced")
+    pub active: String,
+    /// Profiles the machine offers, in the daemon's order
+    pub available: Vec<String>,
+}
+
+impl PowerProfiles {
+    /// Profile a click switches to: the next one offered, wrapping around.
+    pub fn next(&self) -> Option<&str> {
+        let position = self.available.iter().position(|profile| *profile == self.active);
+        let next = match position {
+            Some(index) => &self.available[(index + 1) % self.available.len()],
+            None => self.available.first()?,
+        };
+        (*next != self.active).then_some(next.as_str())
+    }
+}
+
+/// Display name of a profile ("power-saver" → "Power Saver").
+pub fn profile_label(profile: &str) -> String {
+    profile
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Polls power-profiles-daemon on a background thread.
+pub struct PowerProfileMonitor {
+    /// Poll only while the profile is shown
+    enabled: Arc<AtomicBool>,
+    /// Latest profiles (None until the first successful poll)
+    state: Arc<Mutex<Option<PowerProfiles>>>,
+    /// Why the last poll failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl PowerProfileMonitor {
+    /// Create the monitor and start its poll thread.
+    pub fn new(enabled: bool) -> Self {
+        let enabled = Arc::new(AtomicBool::new(enabled));
+        let state = Arc::new(Mutex::new(None));
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_enabled = Arc::clone(&enabled);
+        let thread_state = Arc::clone(&state);
+        let thread_error = Arc::clone(&error);
+        std::thread::spawn(move || {
+            super::priority::enter_background("power-profile");
+            loop {
+                // Stop once the monitor was dropped
+                if Arc::strong_count(&thread_enabled) == 1 {
+                    break;
+                }
+                if thread_enabled.load(Ordering::Relaxed) {
+                    store(poll(), &thread_state, &thread_error);
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        Self { enabled, state, error }
+    }
+
+    /// Start or stop polling (e.g., after the option was toggled).
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            *self.state.lock().unwrap() = None;
+            *self.error.lock().unwrap() = None;
+        }
+    }
+
+    /// Latest profiles.
+    pub fn state(&self) -> Option<PowerProfiles> {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Why the last poll failed, if it did.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Switch to the next profile and refresh the state.
+    ///
+    /// # Returns
+    ///
+    /// Whether the profile was switched
+    pub fn cycle(&self) -> bool {
+        let Some(next) = self.state().and_then(|state| state.next().map(String::from)) else {
+            return false;
+        };
+        let result = SERVICES.iter().find_map(|(service, path)| {
+            let output = Command::new("busctl")
+                .args(["--system", "set-property", service, path, service, "ActiveProfile", "s", &next])
+                .bounded_output(exec::QUICK_TIMEOUT)
+                .ok()?;
+            output.status.success().then_some(())
+        });
+        if result.is_none() {
+            log::warn!("Failed to switch power profile to {}", next);
+            return false;
+        }
+        log::info!("Switched power profile to {}", next);
+        store(poll(), &self.state, &self.error);
+        true
+    }
+}
+
+/// Store a poll result, keeping the last good state on failure.
+fn store(result: Result<PowerProfiles, String>, state: &Mutex<Option<PowerProfiles>>, error: &Mutex<Option<String>>) {
+    match result {
+        Ok(profiles) => {
+            *state.lock().unwrap() = Some(profiles);
+            *error.lock().unwrap() = None;
+        }
+        Err(e) => {
+            log::debug!("Power profile poll failed: {}", e);
+            *error.lock().unwrap() = Some(e);
+        }
+    }
+}
+
+/// Read the active and offered profiles from whichever daemon name answers.
+fn poll() -> Result<PowerProfiles, String> {
+    let mut last_error = String::from("power-profiles-daemon not running");
+    for (service, path) in SERVICES {
+        let active = match get_property(service, path, "ActiveProfile") {
+            Ok(reply) => reply,
+            Err(e) => {
+                last_error = e;
+                continue;
+            }
+        };
+        let active = active.get("data").and_then(Value::as_str).ok_or("Unexpected ActiveProfile reply")?.to_string();
+        let available = get_property(service, path, "Profiles").map(|reply| profile_names(&reply)).unwrap_or_default();
+        return Ok(PowerProfiles { active, available });
+    }
+    Err(last_error)
+}
+
+/// `busctl --system get-property` of the daemon, as JSON.
+fn get_property(service: &str, path: &str, property: &str) -> Result<Value, String> {
+    let output = Command::new("busctl")
+        .args(["--system", "--json=short", "get-property", service, path, service, property])
+        .bounded_output(exec::QUICK_TIMEOUT)
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("busctl failed").trim().to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+/// Profile names in a `Profiles` reply.
+///
+/// The reply is `aa{sv}`: one dictionary per profile, its name under
+/// `Profile`.
+fn profile_names(reply: &Value) -> Vec<String> {
+    reply
+        .get("data")
+        .and_then(Value::as_array)
+        .map(|profiles| {
+            profiles
+                .iter()
+                .filter_map(|profile| profile.get("Profile")?.get("data")?.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names() {
+        let reply: Value = serde_json::from_str(
+            r#"{"type":"aa{sv}","data":[
+                {"Profile":{"type":"s","data":"power-saver"},"Driver":{"type":"s","data":"amd_pmf"}},
+                {"Profile":{"type":"s","data":"balanced"},"Driver":{"type":"s","data":"amd_pmf"}},
+                {"Profile":{"type":"s","data":"performance"},"Driver":{"type":"s","data":"amd_pmf"}}
+            ]}"#,
+        )
+        .unwrap();
+        let available = profile_names(&reply);
+        assert_eq!(available, ["power-saver", "balanced", "performance"]);
+
+        let profiles = |active: &str| PowerProfiles { active: active.to_string(), available: available.clone() };
+        assert_eq!(profiles("balanced").next(), Some("performance"));
+        assert_eq!(profiles("performance").next(), Some("power-saver"));
+        assert_eq!(PowerProfiles { active: String::from("balanced"), available: Vec::new() }.next(), None);
+        assert_eq!(profile_label("power-saver"), "Power Saver");
+    }
+}
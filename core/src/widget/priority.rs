@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Background Thread Priority
+//!
+//! The widget measures the system it runs on, so its collectors shouldn't
+//! compete with the workloads being measured. Every collector thread calls
+//! [`enter_background`] when it starts, which (depending on the config):
+//!
+//! - Sets the thread's nice level to 19
+//! - Sets its I/O priority to the lowest best-effort level (like
+//!   `ionice -c 2 -n 7`)
+//! - With idle priority enabled, sets its scheduling policy to `SCHED_IDLE`
+//!   and its I/O priority to the idle class (like `ionice -c 3`) instead,
+//!   so it only runs when a CPU would otherwise be idle
+//! - Pins it to the efficiency cores on hybrid CPUs
+//!
+//! The idle classes are opt-in: they give a thread no time at all while the
+//! system is busy, so the collectors would stop under exactly the load
+//! worth watching. A nice 19 thread still gets a small share of a busy CPU.
+//!
+//! External tools spawned from these threads (`nvidia-smi`, `solaar`, ...)
+//! inherit all of the above. The render loop keeps normal priority so the
+//! widget stays responsive.
+//!
+//! # Efficiency Core Detection
+//!
+//! ```text
+//! Intel hybrid (Alder Lake+):  /sys/devices/cpu_atom/cpus            "16-23"
+//! ARM big.LITTLE:              /sys/devices/system/cpu/cpuN/cpu_capacity (lowest)
+//! ```
+//!
+//! On CPUs without distinct core types, pinning is skipped.
+//!
+//! Lowering priority is one-way for unprivileged processes, so settings are
+//! read once at startup (see [`configure`]); changes apply after a restart.
+
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether collector threads lower their CPU and I/O priority.
+static LOW_PRIORITY: AtomicBool = AtomicBool::new(true);
+/// Whether low priority collector threads use the idle scheduling classes.
+static IDLE_PRIORITY: AtomicBool = AtomicBool::new(false);
+/// Whether collector threads pin themselves to efficiency cores.
+static PIN_EFFICIENCY_CORES: AtomicBool = AtomicBool::new(false);
+
+/// Lowest scheduling priority (highest nice value).
+const NICE_LOWEST: libc::c_int = 19;
+
+/// `ioprio_set` constants (from linux/ioprio.h, not exported by libc).
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_BE: libc::c_int = 2;
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+/// Lowest level of the best-effort class.
+const IOPRIO_BE_LOWEST: libc::c_int = 7;
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Set the priority policy for collector threads started from now on.
+///
+/// # Arguments
+///
+/// * `low_priority` - Use nice 19 and the lowest best-effort I/O priority
+/// * `idle_priority` - With `low_priority`, use `SCHED_IDLE` and idle I/O
+///   priority instead
+/// * `pin_efficiency_cores` - Restrict threads to efficiency cores
+pub fn configure(low_priority: bool, idle_priority: bool, pin_efficiency_cores: bool) {
+    LOW_PRIORITY.store(low_priority, Ordering::Relaxed);
+    IDLE_PRIORITY.store(idle_priority, Ordering::Relaxed);
+    PIN_EFFICIENCY_CORES.store(pin_efficiency_cores, Ordering::Relaxed);
+}
+
+/// Apply the configured priority policy to the calling thread.
+///
+/// Failures are logged and otherwise ignored; a collector running at
+/// normal priority still works.
+///
+/// # Arguments
+///
+/// * `name` - Collector name for log messages (e.g., "gpu")
+pub fn enter_background(name: &str) {
+    if LOW_PRIORITY.load(Ordering::Relaxed) {
+        lower_priority(name, IDLE_PRIORITY.load(Ordering::Relaxed));
+    }
+    if PIN_EFFICIENCY_CORES.load(Ordering::Relaxed) {
+        pin_to_efficiency_cores(name);
+    }
+}
+
+// ============================================================================
+// Implementation
+// ============================================================================
+
+/// Lower CPU scheduling and I/O priority of the calling thread.
+///
+/// # Arguments
+///
+/// * `name` - Collector name for log messages
+/// * `idle` - Use the idle classes instead of the lowest normal levels
+fn lower_priority(name: &str, idle: bool) {
+    // On Linux, nice, scheduling policy and I/O priority are per-thread;
+    // pid 0 / the thread id addresses only the calling thread
+    let tid = unsafe { libc::gettid() };
+
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, NICE_LOWEST) } != 0 {
+        log::debug!("{}: failed to set nice level: {}", name, std::io::Error::last_os_error());
+    }
+
+    if idle {
+        let param = libc::sched_param { sched_priority: 0 };
+        if unsafe { libc::sched_setscheduler(0, libc::SCHED_IDLE, &param) } != 0 {
+            log::debug!("{}: SCHED_IDLE not available: {}", name, std::io::Error::last_os_error());
+        }
+    }
+
+    let ioprio = if idle {
+        IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT
+    } else {
+        (IOPRIO_CLASS_BE << IOPRIO_CLASS_SHIFT) | IOPRIO_BE_LOWEST
+    };
+    if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, ioprio) } != 0 {
+        log::debug!("{}: failed to set low I/O priority: {}", name, std::io::Error::last_os_error());
+    }
+
+    log::debug!("{}: collector thread running at {} priority", name, if idle { "idle" } else { "low" });
+}
+
+/// Restrict the calling thread to efficiency cores, if the CPU has them.
+fn pin_to_efficiency_cores(name: &str) {
+    let Some(cpus) = efficiency_cores() else {
+        log::debug!("{}: no efficiency cores detected, not pinning", name);
+        return;
+    };
+
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        for &cpu in &cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            log::warn!("{}: failed to pin to efficiency cores: {}", name, std::io::Error::last_os_error());
+            return;
+        }
+    }
+    log::debug!("{}: pinned to efficiency cores {:?}", name, cpus);
+}
+
+/// Detect the efficiency cores of a hybrid CPU.
+///
+/// Returns `None` if all cores are the same type.
+fn efficiency_cores() -> Option<Vec<usize>> {
+    // Intel hybrid CPUs expose their E-cores as a separate PMU
+    if let Ok(list) = fs::read_to_string("/sys/devices/cpu_atom/cpus") {
+        let cpus = parse_cpu_list(&list);
+        if !cpus.is_empty() {
+            return Some(cpus);
+        }
+    }
+
+    // ARM: cores with the lowest relative capacity are the little cores
+    let mut capacities = Vec::new();
+    for entry in fs::read_dir("/sys/devices/system/cpu").ok()?.flatten() {
+        let name = entry.file_name();
+        let Some(cpu) = name.to_str().and_then(|n| n.strip_prefix("cpu")).and_then(|n| n.parse::<usize>().ok()) else {
+            continue;
+        };
+        if let Ok(capacity) = fs::read_to_string(entry.path().join("cpu_capacity")) {
+            if let Ok(capacity) = capacity.trim().parse::<u32>() {
+                capacities.push((cpu, capacity));
+            }
+        }
+    }
+
+    let min = capacities.iter().map(|&(_, c)| c).min()?;
+    let max = capacities.iter().map(|&(_, c)| c).max()?;
+    if min == max {
+        return None;
+    }
+    let mut cpus: Vec<usize> = capacities.into_iter().filter(|&(_, c)| c == min).map(|(cpu, _)| cpu).collect();
+    cpus.sort_unstable();
+    Some(cpus)
+}
+
+/// Parse a kernel CPU list such as `"0-3,8,10-11"`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("16-19\n"), vec![16, 17, 18, 19]);
+        assert_eq!(parse_cpu_list("0,2-3,8"), vec![0, 2, 3, 8]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+}
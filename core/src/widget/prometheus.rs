@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Prometheus Exporter Mode
+//!
+//! `cosmic-monitor-widget --export-prometheus <port>` runs the local monitors
+//! without a Wayland surface and serves their readings in the Prometheus text
+//! exposition format, so desktop stats can be scraped into Grafana.
+//!
+//! Only built with the `prometheus` cargo feature:
+//!
+//! ```bash
+//! cargo build --release --features prometheus
+//! cosmic-monitor-widget --export-prometheus 9273            # 127.0.0.1:9273
+//! cosmic-monitor-widget --export-prometheus 0.0.0.0:9273    # all interfaces
+//! ```
+//!
+//! # Architecture
+//!
+//! ```text
+//! sampler thread (SampleClock) ──► LocalMonitors::snapshot() ──► Arc<Mutex<StatsSnapshot>>
+//!                                                                        │
+//! TcpListener ──► GET /metrics ──► to_prometheus() ◄─────────────────────┘
+//! ```
+//!
+//! The HTTP side is intentionally minimal: one request per connection,
+//! handled sequentially, which is plenty for a scraper polling every few
+//! seconds.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::sampler::SampleClock;
+use super::stats::{LocalMonitors, StatsSnapshot};
+
+/// Sampling interval for the exporter (independent of the widget config).
+const SAMPLE_INTERVAL_MS: u64 = 1000;
+
+/// Timeout for reading a request from a scraper.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Exporter
+// ============================================================================
+
+/// Parse the `--export-prometheus` argument.
+///
+/// Accepts a bare port (binds to localhost) or a full `host:port` address.
+pub fn parse_listen_addr(arg: &str) -> Option<SocketAddr> {
+    if let Ok(port) = arg.parse::<u16>() {
+        return Some(SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+    arg.parse().ok()
+}
+
+/// Run the exporter until the process is killed.
+///
+/// # Errors
+///
+/// Returns an error if the listen address cannot be bound.
+pub fn run(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    log::info!("Serving Prometheus metrics on http://{}/metrics", addr);
+
+    let latest = Arc::new(Mutex::new(StatsSnapshot::capture()));
+
+    // Sample on aligned ticks in the background
+    let latest_clone = Arc::clone(&latest);
+    std::thread::spawn(move || {
+        super::priority::enter_background("prometheus-sampler");
+        let mut monitors = LocalMonitors::new();
+        let mut clock = SampleClock::new(SAMPLE_INTERVAL_MS);
+        loop {
+            if let Some(sample_ms) = clock.poll() {
+                monitors.update();
+                *latest_clone.lock().unwrap() = monitors.snapshot(sample_ms);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    });
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream, &latest) {
+                    log::debug!("Prometheus request failed: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to accept connection: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Answer a single HTTP request.
+fn handle_connection(mut stream: TcpStream, latest: &Mutex<StatsSnapshot>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+
+    // Only the request line matters: "GET /metrics HTTP/1.1"
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, content_type, body) = match path {
+        "/metrics" => {
+            let body = to_prometheus(&latest.lock().unwrap());
+            ("200 OK", "text/plain; version=0.0.4; charset=utf-8", body)
+        }
+        "/" => (
+            "200 OK",
+            "text/plain; charset=utf-8",
+            String::from("cosmic-monitor-widget exporter: metrics at /metrics\n"),
+        ),
+        _ => ("404 Not Found", "text/plain; charset=utf-8", String::from("not found\n")),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// ============================================================================
+// Text Exposition Format
+// ============================================================================
+
+/// Render a snapshot in the Prometheus text exposition format.
+///
+/// Metrics without a value (no GPU, no sensor) are omitted entirely rather
+/// than reported as 0.
+pub fn to_prometheus(snapshot: &StatsSnapshot) -> String {
+    let mut out = String::new();
+
+    let mut gauge = |name: &str, help: &str, samples: &[(String, f64)]| {
+        if samples.is_empty() {
+            return;
+        }
+        out.push_str(&format!("# HELP cosmic_monitor_{} {}\n", name, help));
+        out.push_str(&format!("# TYPE cosmic_monitor_{} gauge\n", name));
+        for (labels, value) in samples {
+            out.push_str(&format!("cosmic_monitor_{}{} {}\n", name, labels, value));
+        }
+    };
+
+    let single = |value: Option<f64>| -> Vec<(String, f64)> {
+        value.map(|v| vec![(String::new(), v)]).unwrap_or_default()
+    };
+
+    gauge("cpu_usage_percent", "CPU usage percentage.", &single(snapshot.cpu_usage.map(f64::from)));
+    gauge("memory_usage_percent", "Memory usage percentage.", &single(snapshot.memory_usage.map(f64::from)));
+    gauge("memory_used_bytes", "Used memory in bytes.", &single(snapshot.memory_used.map(|v| v as f64)));
+    gauge("memory_total_bytes", "Total memory in bytes.", &single(snapshot.memory_total.map(|v| v as f64)));
+
+    let gpu_labels = labels(&[("vendor", snapshot.gpu_vendor.unwrap_or("None"))]);
+    let gpu: Vec<(String, f64)> = snapshot
+        .gpu_usage
+        .map(|v| vec![(gpu_labels, f64::from(v))])
+        .unwrap_or_default();
+    gauge("gpu_usage_percent", "GPU usage percentage.", &gpu);
+
+    gauge("cpu_temperature_celsius", "CPU temperature in Celsius.", &single(snapshot.cpu_temp.map(f64::from)));
+    gauge("gpu_temperature_celsius", "GPU temperature in Celsius.", &single(snapshot.gpu_temp.map(f64::from)));
+
+    if let Some(network) = &snapshot.network {
+        gauge("network_receive_bytes_per_second", "Download rate across all interfaces.", &single(Some(network.rx_bytes_per_sec)));
+        gauge("network_transmit_bytes_per_second", "Upload rate across all interfaces.", &single(Some(network.tx_bytes_per_sec)));
+    }
+
+    let disk_samples = |value: fn(&super::stats::DiskStats) -> f64| -> Vec<(String, f64)> {
+        snapshot
+            .disks
+            .iter()
+            .map(|d| (labels(&[("mount", &d.mount_point), ("name", &d.name)]), value(d)))
+            .collect()
+    };
+    gauge("disk_used_percent", "Disk space used percentage.", &disk_samples(|d| f64::from(d.used_percentage)));
+    gauge("disk_total_bytes", "Disk capacity in bytes.", &disk_samples(|d| d.total_bytes as f64));
+    gauge("disk_available_bytes", "Disk free space in bytes.", &disk_samples(|d| d.available_bytes as f64));
+
+    out
+}
+
+/// Format a label set, escaping values per the exposition format.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let inner: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| {
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{}=\"{}\"", name, escaped)
+        })
+        .collect();
+    format!("{{{}}}", inner.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listen_addr() {
+        assert_eq!(parse_listen_addr("9273"), Some(SocketAddr::from(([127, 0, 0, 1], 9273))));
+        assert_eq!(parse_listen_addr("0.0.0.0:9000"), Some(SocketAddr::from(([0, 0, 0, 0], 9000))));
+        assert_eq!(parse_listen_addr("not-a-port"), None);
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        assert_eq!(labels(&[("name", "My \"Disk\"")]), "{name=\"My \\\"Disk\\\"\"}");
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Pressure Stall Information
+//!
+//! Share of the last 10 seconds in which at least one task was stalled
+//! waiting for CPU, memory or I/O, read from the kernel's PSI files on every
+//! sample:
+//!
+//! ```text
+//! /proc/pressure/cpu     some avg10=2.04 avg60=...  ──┐
+//! /proc/pressure/memory  some avg10=0.00 avg60=...  ──┼──► Pressure  CPU [▆  ] 2%  MEM [   ] 0%  IO [███] 15%
+//! /proc/pressure/io      some avg10=15.31 avg60=... ──┘
+//! ```
+//!
+//! Unlike usage percentages, pressure only rises when work is actually
+//! waiting: a CPU at 100% with nothing queued shows no pressure, while a
+//! system thrashing swap shows high memory and I/O pressure at low CPU use.
+//!
+//! Kernels built without `CONFIG_PSI` (or booted with `psi=0`) have no
+//! files; the row then shows the resources as unavailable.
+
+use std::fs;
+
+use crate::config::Level;
+
+/// Directory of the PSI files.
+const PRESSURE_DIR: &str = "/proc/pressure";
+
+/// Pressure from which a resource is drawn as a warning (percent).
+const WARNING_PERCENT: f32 = 10.0;
+
+/// Pressure from which a resource is drawn as critical (percent).
+const CRITICAL_PERCENT: f32 = 40.0;
+
+/// `some avg10` pressure of each resource (percent, None if unreadable).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pressure {
+    /// Tasks waiting for a CPU
+    pub cpu: Option<f32>,
+    /// Tasks waiting for memory (reclaim, swap-in, thrashing)
+    pub memory: Option<f32>,
+    /// Tasks waiting for I/O
+    pub io: Option<f32>,
+}
+
+impl Pressure {
+    /// Read the current pressure of every resource.
+    pub fn read() -> Self {
+        let read = |resource: &str| {
+            fs::read_to_string(format!("{}/{}", PRESSURE_DIR, resource))
+                .ok()
+                .and_then(|text| some_avg10(&text))
+        };
+        Self {
+            cpu: read("cpu"),
+            memory: read("memory"),
+            io: read("io"),
+        }
+    }
+}
+
+/// Bar color level of a pressure.
+pub fn pressure_level(percent: f32) -> Level {
+    if percent >= CRITICAL_PERCENT {
+        Level::Critical
+    } else if percent >= WARNING_PERCENT {
+        Level::Warning
+    } else {
+        Level::Normal
+    }
+}
+
+/// `avg10` of the `some` line of a PSI file
+/// ("some avg10=2.04 avg60=0.75 avg300=0.20 total=123456").
+fn some_avg10(text: &str) -> Option<f32> {
+    let line = text.lines().find(|line| line.starts_with("some "))?;
+    line.split_whitespace().find_map(|field| field.strip_prefix("avg10=")?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_some_avg10() {
+        let text = "some avg10=15.31 avg60=4.02 avg300=1.10 total=987654\nfull avg10=9.00 avg60=2.00 avg300=0.50 total=123456\n";
+        assert_eq!(some_avg10(text), Some(15.31));
+        assert_eq!(some_avg10("full avg10=1.00 avg60=0.00 avg300=0.00 total=0\n"), None);
+
+        assert_eq!(pressure_level(2.0), Level::Normal);
+        assert_eq!(pressure_level(15.31), Level::Warning);
+        assert_eq!(pressure_level(40.0), Level::Critical);
+    }
+}
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Metric History Recorder
+//!
+//! Appends sampled metrics to daily CSV files for long-term analysis of
+//! thermals and usage (spreadsheets, pandas, gnuplot, ...).
+//!
+//! # File Layout
+//!
+//! ```text
+//! ~/.local/share/cosmic-monitor-applet/history/
+//! ├── metrics-2026-10-14.csv
+//! ├── metrics-2026-10-15.csv
+//! └── metrics-2026-10-16.csv   ← current day, appended every interval
+//! ```
+//!
+//! One file per local day keeps rotation trivial: files older than the
+//! retention limit are deleted when the recorder starts and whenever the
+//! day rolls over.
+//!
+//! # Columns
+//!
+//! `timestamp_ms,local_time,cpu_usage,memory_usage,memory_used,memory_total,
+//! gpu_usage,cpu_temp,gpu_temp,net_rx_bytes_per_sec,net_tx_bytes_per_sec,disks`
+//!
+//! Values that weren't collected are left empty. `disks` is a
+//! semicolon-separated list of `mount=used_percent` pairs.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use super::stats::StatsSnapshot;
+
+/// CSV header, written when a new daily file is created.
+const CSV_HEADER: &str = "timestamp_ms,local_time,cpu_usage,memory_usage,memory_used,memory_total,\
+gpu_usage,cpu_temp,gpu_temp,net_rx_bytes_per_sec,net_tx_bytes_per_sec,disks";
+
+/// Prefix of daily history file names.
+const FILE_PREFIX: &str = "metrics-";
+
+// ============================================================================
+// Recorder
+// ============================================================================
+
+/// Appends snapshots to daily CSV files at a fixed interval.
+pub struct MetricRecorder {
+    /// History directory
+    dir: PathBuf,
+    /// Minimum time between recorded rows, in milliseconds
+    interval_ms: u64,
+    /// Number of daily files to keep (0 = keep forever)
+    retention_days: u32,
+    /// Interval slot of the last recorded row (timestamp_ms / interval_ms)
+    last_slot: Option<u64>,
+    /// Day of the last pruning pass
+    last_pruned: Option<NaiveDate>,
+}
+
+impl MetricRecorder {
+    /// Create a recorder writing to the default history directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval_secs` - Seconds between recorded rows (minimum 1)
+    /// * `retention_days` - Daily files to keep (0 = keep forever)
+    pub fn new(interval_secs: u64, retention_days: u32) -> Self {
+        let mut dir = dirs::data_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        dir.push("cosmic-monitor-applet");
+        dir.push("history");
+        log::info!("Recording metric history to {}", dir.display());
+
+        Self {
+            dir,
+            interval_ms: interval_secs.max(1) * 1000,
+            retention_days,
+            last_slot: None,
+            last_pruned: None,
+        }
+    }
+
+    /// Whether the recorder matches the given settings (else recreate it).
+    pub fn matches(&self, interval_secs: u64, retention_days: u32) -> bool {
+        self.interval_ms == interval_secs.max(1) * 1000 && self.retention_days == retention_days
+    }
+
+    /// Record a snapshot if a new interval slot has started.
+    ///
+    /// Samples arrive every update interval; only the first sample in each
+    /// recording interval is written, so rows stay aligned and uniform.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors from creating the directory or appending the row.
+    pub fn record(&mut self, snapshot: &StatsSnapshot) -> std::io::Result<()> {
+        let slot = snapshot.timestamp_ms / self.interval_ms;
+        if self.last_slot == Some(slot) {
+            return Ok(());
+        }
+        self.last_slot = Some(slot);
+
+        let local = Local
+            .timestamp_millis_opt(snapshot.timestamp_ms as i64)
+            .single()
+            .unwrap_or_else(Local::now);
+        let today = local.date_naive();
+
+        fs::create_dir_all(&self.dir)?;
+        if self.last_pruned != Some(today) {
+            self.prune(today);
+            self.last_pruned = Some(today);
+        }
+
+        let path = self.dir.join(format!("{}{}.csv", FILE_PREFIX, today.format("%Y-%m-%d")));
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if is_new {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+        writeln!(file, "{}", csv_row(snapshot, &local.format("%Y-%m-%d %H:%M:%S").to_string()))
+    }
+
+    /// Delete daily files older than the retention limit.
+    fn prune(&self, today: NaiveDate) {
+        if self.retention_days == 0 {
+            return;
+        }
+        let Some(cutoff) = today.checked_sub_days(chrono::Days::new(self.retention_days as u64 - 1)) else {
+            return;
+        };
+
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(date) = name
+                .to_str()
+                .and_then(|n| n.strip_prefix(FILE_PREFIX))
+                .and_then(|n| n.strip_suffix(".csv"))
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+            if date < cutoff {
+                log::info!("Removing expired metric history {}", entry.path().display());
+                fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+}
+
+// ============================================================================
+// CSV Formatting
+// ============================================================================
+
+/// Format one CSV row for a snapshot.
+fn csv_row(snapshot: &StatsSnapshot, local_time: &str) -> String {
+    fn opt<T: std::fmt::Display>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+    fn opt_f32(value: Option<f32>) -> String {
+        value.map(|v| format!("{:.1}", v)).unwrap_or_default()
+    }
+
+    let (rx, tx) = snapshot
+        .network
+        .as_ref()
+        .map(|n| (format!("{:.0}", n.rx_bytes_per_sec), format!("{:.0}", n.tx_bytes_per_sec)))
+        .unwrap_or_default();
+
+    // Mount points never contain ';' or '=' in practice; strip commas so
+    // the column stays parseable without quoting
+    let disks: Vec<String> = snapshot
+        .disks
+        .iter()
+        .map(|d| format!("{}={:.1}", d.mount_point.replace(',', "_"), d.used_percentage))
+        .collect();
+
+    [
+        snapshot.timestamp_ms.to_string(),
+        local_time.to_string(),
+        opt_f32(snapshot.cpu_usage),
+        opt_f32(snapshot.memory_usage),
+        opt(snapshot.memory_used),
+        opt(snapshot.memory_total),
+        opt_f32(snapshot.gpu_usage),
+        opt_f32(snapshot.cpu_temp),
+        opt_f32(snapshot.gpu_temp),
+        rx,
+        tx,
+        disks.join(";"),
+    ]
+    .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::stats::DiskStats;
+
+    #[test]
+    fn test_csv_row_leaves_missing_values_empty() {
+        let mut snapshot = StatsSnapshot::capture_at(1_000);
+        snapshot.cpu_usage = Some(12.34);
+        snapshot.disks.push(DiskStats {
+            name: String::from("root"),
+            mount_point: String::from("/"),
+            used_percentage: 50.0,
+            total_bytes: 0,
+            available_bytes: 0,
+        });
+
+        let row = csv_row(&snapshot, "2026-01-01 00:00:01");
+        assert_eq!(row, "1000,2026-01-01 00:00:01,12.3,,,,,,,,,/=50.0");
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+    }
+}
@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Background Refresher
+//!
+//! Sections that read slow sources (CLI tools, HTTP, D-Bus) on an interval
+//! of minutes each run a [`Refresher`]: one background thread that owns the
+//! loop, while the section's module only supplies its settings type and a
+//! refresh function.
+//!
+//! ```text
+//! set_settings(Some(s)) ──► thread, checks every second:
+//!                             settings changed, or refresh_minutes elapsed?
+//!                               └── refresh(&s) ──► value + error ──► getters
+//! set_settings(None)    ──► value and error cleared (section hidden)
+//! ```
+//!
+//! The thread stops once the refresher is dropped. Module-specific
+//! constructors and getters are added in each module, e.g.
+//! `impl CalendarMonitor { pub fn events(&self) ... }` on
+//! `type CalendarMonitor = Refresher<CalendarSettings, Vec<CalendarEvent>>`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often the thread checks whether a refresh is due.
+#[cfg(not(test))]
+const TICK: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const TICK: Duration = Duration::from_millis(10);
+
+/// Settings of a refreshed section.
+pub trait RefreshSettings: Clone + PartialEq + Send + 'static {
+    /// Minutes between refreshes (at least 1 is used).
+    fn refresh_minutes(&self) -> u32;
+}
+
+/// Value of a finished refresh.
+struct Refreshed<S, T> {
+    /// Settings the value was read with
+    settings: S,
+    value: T,
+}
+
+/// Refreshes a value on a background thread.
+pub struct Refresher<S, T> {
+    /// What to refresh (`None` while the section is hidden)
+    settings: Arc<Mutex<Option<S>>>,
+    /// Last refresh (None until the first one finished)
+    latest: Arc<Mutex<Option<Refreshed<S, T>>>>,
+    /// Why the last refresh (partly) failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl<S: RefreshSettings, T: Send + 'static> Refresher<S, T> {
+    /// Start the refresher thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Thread name for logs (e.g., "calendar")
+    /// * `settings` - What to refresh, `None` while the section is hidden
+    /// * `refresh` - Reads the value; returns it (`None` keeps the previous
+    ///   one) and why the refresh (partly) failed, if it did
+    pub fn spawn<F>(name: &'static str, settings: Option<S>, mut refresh: F) -> Self
+    where
+        F: FnMut(&S) -> (Option<T>, Option<String>) + Send + 'static,
+    {
+        let settings = Arc::new(Mutex::new(settings));
+        let latest = Arc::new(Mutex::new(None));
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_settings = Arc::clone(&settings);
+        let thread_latest = Arc::clone(&latest);
+        let thread_error = Arc::clone(&error);
+        std::thread::spawn(move || {
+            super::priority::enter_background(name);
+            let mut last_refresh: Option<(S, Instant)> = None;
+            // Stop once the refresher was dropped
+            while Arc::strong_count(&thread_settings) > 1 {
+                let Some(settings) = thread_settings.lock().unwrap().clone() else {
+                    last_refresh = None;
+                    *thread_latest.lock().unwrap() = None;
+                    *thread_error.lock().unwrap() = None;
+                    std::thread::sleep(TICK);
+                    continue;
+                };
+
+                let now = Instant::now();
+                if refresh_due(last_refresh.as_ref(), &settings, now) {
+                    last_refresh = Some((settings.clone(), now));
+                    let (value, error) = refresh(&settings);
+                    if let Some(value) = value {
+                        *thread_latest.lock().unwrap() = Some(Refreshed { settings, value });
+                    }
+                    *thread_error.lock().unwrap() = error;
+                }
+
+                std::thread::sleep(TICK);
+            }
+        });
+
+        Self { settings, latest, error }
+    }
+
+    /// Replace what the refresher reads (e.g., after a config change).
+    pub fn set_settings(&self, settings: Option<S>) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    /// Current settings (`None` while the section is hidden).
+    pub fn settings(&self) -> Option<S> {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// Value of the last refresh (None before the first one finished).
+    pub fn value(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.latest.lock().unwrap().as_ref().map(|latest| latest.value.clone())
+    }
+
+    /// Whether the last refresh read the current settings (false until the
+    /// refresh after a settings change finished).
+    pub fn is_current(&self) -> bool {
+        let settings = self.settings.lock().unwrap();
+        self.latest
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|latest| settings.as_ref() == Some(&latest.settings))
+    }
+
+    /// Whether a first refresh finished.
+    pub fn has_sample(&self) -> bool {
+        self.latest.lock().unwrap().is_some()
+    }
+
+    /// Why the last refresh (partly) failed, if it did.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+}
+
+/// Whether a refresh is due: right away for the first refresh and when the
+/// settings changed, otherwise once `refresh_minutes` elapsed.
+fn refresh_due<S: RefreshSettings>(last_refresh: Option<&(S, Instant)>, settings: &S, now: Instant) -> bool {
+    let interval = Duration::from_secs(u64::from(settings.refresh_minutes().max(1)) * 60);
+    last_refresh.is_none_or(|(previous, at)| previous != settings || now.duration_since(*at) >= interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TestSettings {
+        source: &'static str,
+        minutes: u32,
+    }
+
+    impl RefreshSettings for TestSettings {
+        fn refresh_minutes(&self) -> u32 {
+            self.minutes
+        }
+    }
+
+    fn settings(source: &'static str) -> TestSettings {
+        TestSettings { source, minutes: 5 }
+    }
+
+    /// Poll until `done` holds (the refresher thread ticks every 10ms in tests).
+    fn wait_for(done: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "refresher thread did not catch up");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_refresh_due_on_interval() {
+        let start = Instant::now();
+        let last = (settings("a"), start);
+        assert!(!refresh_due(Some(&last), &settings("a"), start + Duration::from_secs(4 * 60)));
+        assert!(refresh_due(Some(&last), &settings("a"), start + Duration::from_secs(5 * 60)));
+
+        // An interval of 0 minutes still waits one minute
+        let last = (TestSettings { source: "a", minutes: 0 }, start);
+        assert!(!refresh_due(Some(&last), &last.0, start + Duration::from_secs(30)));
+        assert!(refresh_due(Some(&last), &last.0, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_refresh_forced_without_previous_or_on_change() {
+        let start = Instant::now();
+        assert!(refresh_due(None, &settings("a"), start));
+
+        let last = (settings("a"), start);
+        assert!(refresh_due(Some(&last), &settings("b"), start));
+        assert!(refresh_due(Some(&last), &TestSettings { source: "a", minutes: 10 }, start));
+    }
+
+    #[test]
+    fn test_settings_change_refreshes() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let thread_count = Arc::clone(&count);
+        let refresher: Refresher<TestSettings, String> = Refresher::spawn("test", Some(settings("a")), move |s| {
+            thread_count.fetch_add(1, Ordering::SeqCst);
+            match s.source {
+                "broken" => (None, Some(String::from("unreachable"))),
+                source => (Some(source.to_string()), None),
+            }
+        });
+
+        wait_for(|| refresher.has_sample());
+        assert_eq!(refresher.value().as_deref(), Some("a"));
+        assert!(refresher.is_current());
+
+        // Not refreshed again before the interval
+        std::thread::sleep(TICK * 5);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        refresher.set_settings(Some(settings("b")));
+        wait_for(|| refresher.is_current());
+        assert_eq!(refresher.value().as_deref(), Some("b"));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // A failed refresh keeps the previous value
+        refresher.set_settings(Some(settings("broken")));
+        wait_for(|| refresher.error().is_some());
+        assert_eq!(refresher.value().as_deref(), Some("b"));
+        assert!(!refresher.is_current());
+
+        // Hiding the section clears everything; showing it refreshes right away
+        refresher.set_settings(None);
+        wait_for(|| !refresher.has_sample() && refresher.error().is_none());
+        refresher.set_settings(Some(settings("b")));
+        wait_for(|| refresher.is_current());
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+}
@@ -0,0 +1,5090 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Widget Rendering Module
+//!
+//! This is the core rendering module for the COSMIC Monitor Widget. It uses
+//! Cairo for 2D graphics and Pango for text rendering to draw all widget
+//! sections onto an ARGB32 image surface.
+//!
+//! ## Architecture
+//!
+//! The renderer operates on raw pixel buffers provided by the Wayland compositor.
+//! It creates a Cairo ImageSurface wrapping the buffer and draws using Cairo's
+//! immediate-mode API.
+//!
+//! ```text
+//! ┌─────────────────────┐
+//! │  Wayland Buffer     │  Raw ARGB32 pixel data
+//! │  (shared memory)    │
+//! └──────────┬──────────┘
+//!            │
+//! ┌──────────▼──────────┐
+//! │  Cairo ImageSurface │  Wraps buffer with unsafe lifetime extension
+//! │  (Format::ARgb32)   │
+//! └──────────┬──────────┘
+//!            │
+//! ┌──────────▼──────────┐
+//! │  Cairo Context      │  Drawing operations
+//! │  + Pango Layout     │  Text rendering
+//! └──────────┬──────────┘
+//!            │
+//!     ┌──────┴──────┬───────────┬───────────┬─────────┐
+//!     ▼             ▼           ▼           ▼         ▼
+//! DateTime    Utilization   Temperature   Storage   Weather
+//!   Section    (CPU/GPU)     (Gauges)     (Disks)   (Icons)
+//!     ▼             ▼           ▼           ▼         ▼
+//!  Battery    Notifications    Media      Network   (Legacy)
+//! ```
+//!
+//! ## Rendering Pipeline
+//!
+//! 1. Create Cairo surface from raw buffer (unsafe lifetime extension)
+//! 2. Clear background to transparent (ARGB 0,0,0,0)
+//! 3. Iterate through configured section order (split into columns)
+//! 4. Each [`super::sections::Section`] renders at current Y position, returns new Y
+//! 5. Flush surface to ensure all operations complete
+//! 6. Return click bounds for interactive elements
+//!
+//! ## Text Rendering Strategy
+//!
+//! All text is rendered with an outline for visibility on any background:
+//! 1. Create Pango layout with text content
+//! 2. Convert layout to Cairo path (`pangocairo::functions::layout_path`)
+//! 3. Stroke path with the outline color (black, or white for dark text)
+//! 4. Fill path with the text color or a level color (text body)
+//!
+//! The text and outline colors come from the frame's
+//! [`TextPalette`](super::theme::TextPalette) (see
+//! [`crate::config::TextContrast`]), through `set_text_fill` and
+//! `set_text_outline`.
+//!
+//! ## Interactive Element Bounds
+//!
+//! The renderer returns bounding boxes for clickable elements:
+//! - Notification groups (expand/collapse)
+//! - Notification clear buttons (per-notification and per-group)
+//! - Clear All button
+//! - Media playback controls (prev/play/pause/next)
+//! - Section error badges (hover shows the error message)
+//! - Metrics with a detail tooltip (see [`super::tooltip`])
+//!
+//! These bounds are used by widget_main.rs to handle click events.
+//!
+//! ## Drawing Failures
+//!
+//! Cairo errors are sticky: once an operation fails (e.g., out of memory),
+//! every later operation on the same context is a no-op. The drawing helpers
+//! therefore ignore individual results, and the render functions check the
+//! context status once at the end, returning a [`RenderError`] so the caller
+//! can skip the frame instead of crashing the widget.
+//!
+//! ## Error Badges
+//!
+//! When a monitor reports an error (e.g., no GPU detected, weather fetch
+//! failing), its section header gets a small warning glyph on the right
+//! instead of silently showing zeros. Hovering the glyph draws a tooltip
+//! with the message on top of everything else.
+
+use cairo;
+use pango;
+use pangocairo;
+
+use super::utilization::{draw_cpu_icon, draw_ram_icon, draw_gpu_icon, draw_progress_bar, memory_label_text};
+use super::temperature::{draw_temp_circle, CoreReading};
+use super::air_quality::{AirQuality, AqiLevel};
+use super::weather::{draw_moon_icon, draw_sun_event_icon, draw_weather_icon, moon_phase_name, wind_direction_label, SunTimes};
+use super::history::History;
+use super::ambient::{severity_color, worst_severity};
+use super::network::format_rate;
+use super::psi::{pressure_level, Pressure};
+use super::kernel_stats::{format_count, KernelStats};
+use super::stats::format_gib;
+use super::storage::DiskInfo;
+use super::space_alert::{badge_text, low_space, space_limits};
+use super::smart::{DriveHealth, SmartVerdict};
+use super::pools::{format_capacity, PoolHealth, PoolStatus, ScrubStatus};
+use super::battery::BatteryDevice;
+use super::power_profile::profile_label;
+use super::notifications::Notification;
+use super::media::MediaInfo;
+use super::marquee::{draw_marquee_text, Marquee};
+use super::custom::CommandOutput;
+use super::audio::AudioState;
+use super::cpufreq::{format_ghz, CpuFrequency};
+use super::gpu_card::GpuDetails;
+use super::gpu_processes::GpuProcess;
+use super::snap::{Guide, SnapGuides};
+use super::health::HealthReport;
+use super::containers::ContainerReport;
+use super::vms::VmInfo;
+use super::feeds::{FeedRow, FeedValue};
+use super::ticker::{format_price, TickerRow};
+use super::world_clock::WorldClockRow;
+use super::calendar::EventRow;
+use super::plugins::{DrawOp, PluginFrame, PLUGIN_SPACING};
+use super::theme::{set_text_fill, set_text_muted, set_text_outline, CosmicTheme, TextPalette};
+use super::fonts::{font, Fonts};
+use super::layout::{CompactSlot, COLUMN_WIDTH, COMPACT_HEIGHT, COMPACT_SLOT_WIDTH, IDENTITY_HEIGHT};
+use super::sections::{self, RenderContext, SectionBounds};
+use super::tooltip::{HoverTarget, HoverTargetBounds};
+use crate::config::{AmbientSource, AmbientTarget, Config, FeedKind, LayoutMode, Level, TempGraphMode, TextRole, ThresholdMetric, WidgetSection, WorldClock};
+use crate::fl;
+
+// ============================================================================
+// Frame Data Struct
+// ============================================================================
+
+/// Snapshot of everything one frame shows.
+///
+/// Created fresh each frame with the current monitor readings and UI state.
+/// What is shown and how (section flags, order, formats, colors) is read
+/// from the [`Config`] passed next to it, never copied in here, so the
+/// layout and the draw loop agree on it.
+///
+/// # Data Sources
+///
+/// - **Utilization**: CPU, memory, GPU percentages from UtilizationMonitor
+/// - **Temperature**: CPU/GPU temps from TemperatureMonitor
+/// - **Network**: RX/TX rates from NetworkMonitor
+/// - **Storage**: Disk info array from StorageMonitor
+/// - **Battery**: Device array from BatteryMonitor
+/// - **Weather**: Data from WeatherMonitor
+/// - **Notifications**: Grouped notifications from NotificationMonitor
+/// - **Media**: Playback info from MediaMonitor
+///
+/// # Section Columns
+///
+/// `section_columns` holds the configured section order split into the
+/// columns of the current layout mode.
+pub struct FrameData<'a> {
+    /// Surface width in pixels
+    pub width: i32,
+    /// Surface height in pixels
+    pub height: i32,
+
+    // Utilization data
+    /// CPU usage percentage (0.0 - 100.0)
+    pub cpu_usage: f32,
+    /// Memory usage percentage (0.0 - 100.0)
+    pub memory_usage: f32,
+    /// Used memory in bytes
+    pub memory_used: u64,
+    /// Total memory in bytes (0 before the first sample)
+    pub memory_total: u64,
+    /// GPU usage percentage (0.0 - 100.0)
+    pub gpu_usage: f32,
+
+    // Temperature data
+    /// CPU temperature in Celsius
+    pub cpu_temp: f32,
+    /// GPU temperature in Celsius
+    pub gpu_temp: f32,
+
+    // Network data
+    /// Network download rate in bytes per second
+    pub network_rx_rate: f64,
+    /// Network upload rate in bytes per second
+    pub network_tx_rate: f64,
+    /// Recent download rates (bytes/s) for the graph
+    pub network_rx_history: &'a History,
+    /// Recent upload rates (bytes/s) for the graph
+    pub network_tx_history: &'a History,
+
+    // System details
+    /// Package frequencies and governor (None until read, or without cpufreq)
+    pub cpu_frequency: Option<&'a CpuFrequency>,
+    /// Hardware header lines (see [`super::hardware`]), `None` while hidden
+    pub hardware_info: Option<&'a [String]>,
+    /// Busiest GPU processes, listed under the GPU bar (empty when off)
+    pub gpu_processes: &'a [GpuProcess],
+    /// Pressure of the last sample (None before the first one)
+    pub pressure: Option<Pressure>,
+    /// Process/thread counts and context switches of the last sample
+    pub kernel_stats: Option<KernelStats>,
+    /// GPU fan speed and clocks (None until read, or if unreadable)
+    pub gpu_details: Option<&'a GpuDetails>,
+    /// Recent CPU temperatures (°C) for the graph
+    pub cpu_temp_history: &'a History,
+    /// Recent GPU temperatures (°C) for the graph
+    pub gpu_temp_history: &'a History,
+    /// Hottest per-core sensor (None without per-core sensors)
+    pub hottest_core: Option<&'a CoreReading>,
+    /// Whether the CPU is thermally throttled
+    pub throttling: bool,
+    /// Hostname/user line above the sections (see [`super::identity`]), `None` while hidden
+    pub identity: Option<&'a str>,
+    /// Caffeine is on (the screen is kept from blanking)
+    pub caffeine_active: bool,
+    /// Active power profile ("balanced"), drawn in the Battery header;
+    /// `None` while hidden or without power-profiles-daemon
+    pub power_profile: Option<&'a str>,
+
+    // Weather data
+    /// Current temperature from weather API
+    pub weather_temp: f32,
+    /// Weather description (e.g., "Partly cloudy")
+    pub weather_desc: &'a str,
+    /// Location name from weather API
+    pub weather_location: &'a str,
+    /// Weather icon code (e.g., "01d", "10n")
+    pub weather_icon: &'a str,
+    /// Status marker shown after the location (e.g., "stale (2 h)" or
+    /// "updated 5 min ago"), empty when the data is fresh
+    pub weather_status_label: &'a str,
+    /// "Feels like" temperature in Celsius
+    pub weather_feels_like: f32,
+    /// Current minimum temperature in Celsius
+    pub weather_temp_min: f32,
+    /// Current maximum temperature in Celsius
+    pub weather_temp_max: f32,
+    /// Humidity percentage (0-100)
+    pub weather_humidity: u8,
+    /// Wind speed in meters/second
+    pub weather_wind_speed: f32,
+    /// Wind direction in degrees (0 = north)
+    pub weather_wind_deg: u16,
+    /// Today's sunrise and sunset, None without weather data
+    pub weather_sun: Option<SunTimes>,
+    /// Moon phase (0.0 new, 0.5 full) while the sun is down and the moon
+    /// phase is enabled
+    pub weather_moon_phase: Option<f64>,
+    /// Air quality badge next to the temperature, None while disabled or
+    /// not fetched yet
+    pub air_quality: Option<AirQuality>,
+
+    // Complex data references
+    /// Array of disk information for storage section
+    pub disk_info: &'a [DiskInfo],
+    /// SMART health of each drive, under the filesystems (empty when off)
+    pub drive_health: &'a [DriveHealth],
+    /// ZFS pools and btrfs filesystems of the storage pools section
+    pub storage_pools: &'a [PoolStatus],
+    /// Array of battery device information
+    pub battery_devices: &'a [BatteryDevice],
+    /// Pre-grouped notifications (app_name, notifications)
+    pub grouped_notifications: &'a [(String, Vec<Notification>)],
+    /// Set of collapsed notification group names
+    pub collapsed_groups: &'a std::collections::HashSet<String>,
+    /// How far each notification's body is shown (0.0 - 1.0), keyed
+    /// "app_name:timestamp"; missing notifications show it fully
+    pub notification_expansion: &'a std::collections::HashMap<String, f64>,
+    /// Do-not-disturb state for the bell button (`None` hides the button)
+    pub do_not_disturb: Option<bool>,
+    /// Notifications skipped at the top of each group (scrolled to older ones)
+    pub notification_scroll: usize,
+    /// Current media playback information
+    pub media_info: &'a MediaInfo,
+    /// Scrolling of a track title too long for the panel (`None` cuts it off)
+    pub media_marquee: Option<Marquee>,
+    /// Lyrics line at the playback position (`None` without lyrics or between lines)
+    pub lyrics_line: Option<&'a str>,
+    /// Latest output of each custom command, in config order
+    pub custom_outputs: &'a [CommandOutput],
+    /// Drawing of each loaded plugin, in file name order
+    pub plugin_frames: &'a [PluginFrame],
+    /// Default audio output, None until it could be read
+    pub audio: Option<&'a AudioState>,
+    /// Failed units and pending updates of the last health check
+    pub health: Option<&'a HealthReport>,
+    /// Running containers of the last container check
+    pub containers: Option<&'a ContainerReport>,
+    /// Running libvirt domains of the last check
+    pub vms: &'a [VmInfo],
+    /// Latest result of each mailbox and feed, in config order
+    pub feed_rows: &'a [FeedRow],
+    /// Latest quote of each ticker symbol, in config order
+    pub ticker_rows: &'a [TickerRow],
+    /// Number of available media players
+    pub player_count: usize,
+    /// Index of currently selected player
+    pub current_player_index: usize,
+    /// Sections per column, drawn side by side (see [`super::layout::section_columns`])
+    pub section_columns: &'a [Vec<WidgetSection>],
+    /// Metric slots for the compact layout
+    pub compact_slots: &'a [CompactSlot],
+    /// Current local time for clock/date display
+    pub current_time: chrono::DateTime<chrono::Local>,
+    /// Locale of month and day names (see [`crate::config::time_locale`])
+    pub locale: chrono::Locale,
+    /// Extra time zones drawn under the clock
+    pub world_clocks: &'a [WorldClockRow],
+    /// World clock shown by the large clock instead of local time
+    /// (cycled by scrolling over the clock)
+    pub clock_zone: Option<&'a WorldClock>,
+    /// Upcoming calendar events drawn under the date
+    pub calendar_events: &'a [EventRow],
+    /// Font of each text role (clock, headers, body, small labels)
+    pub fonts: &'a Fonts,
+
+    // Error state
+    /// Sections whose monitor reported an error, with the message
+    pub section_errors: &'a [(WidgetSection, String)],
+    /// Section whose error badge is under the pointer (draws its tooltip)
+    pub hovered_error: Option<WidgetSection>,
+
+    /// Guides the dragged widget snapped to (drawn along its edges)
+    pub snap_guides: SnapGuides,
+
+    // Tooltip state
+    /// Metric whose detail tooltip is shown, with its lines
+    pub tooltip: Option<(HoverTarget, &'a [String])>,
+
+    // Loading state
+    /// Sections whose monitor has no first sample yet (drawn as "Loading…")
+    pub loading_sections: &'a [WidgetSection],
+
+    // Update state
+    /// Version of a newer release, badged in the bottom corner
+    pub update_available: Option<&'a str>,
+
+    // Animation state
+    /// Opacity of the whole widget (0.0 - 1.0), below 1.0 while fading
+    pub opacity: f64,
+
+    // Debug state
+    /// Debug overlay lines (see [`super::debug_overlay`]), `None` while hidden
+    pub debug_overlay: Option<&'a [String]>,
+}
+
+impl RenderContext<'_> {
+    /// Bar or gauge color of `value` against the thresholds of `metric`.
+    fn level_color(&self, metric: ThresholdMetric, value: f32) -> (f64, f64, f64) {
+        self.config.bar_colors.rgb(self.config.thresholds.get(metric).level(value))
+    }
+
+    /// Ambient status color of this frame, `None` while the tint is off.
+    pub(super) fn ambient_color(&self) -> Option<(f64, f64, f64)> {
+        let (config, data) = (self.config, self.data);
+        let readings: Vec<(f32, ThresholdMetric)> = match config.ambient_source {
+            AmbientSource::Off => return None,
+            AmbientSource::Load => [
+                (config.show_cpu, data.cpu_usage, ThresholdMetric::Cpu),
+                (config.show_memory, data.memory_usage, ThresholdMetric::Memory),
+                (config.show_gpu, data.gpu_usage, ThresholdMetric::Gpu),
+            ]
+            .into_iter()
+            .filter(|(shown, ..)| *shown)
+            .map(|(_, value, metric)| (value, metric))
+            .collect(),
+            AmbientSource::Temperature => [
+                (config.show_cpu_temp, data.cpu_temp),
+                (config.show_gpu_temp, data.gpu_temp),
+                (data.hottest_core.is_some(), data.hottest_core.map_or(0.0, |core| core.temp)),
+            ]
+            .into_iter()
+            .filter(|(shown, ..)| *shown)
+            .map(|(_, value)| (value, ThresholdMetric::Temperature))
+            .collect(),
+        };
+        let severity = worst_severity(readings.into_iter().map(|(value, metric)| (value, config.thresholds.get(metric))));
+        Some(severity_color(severity, &config.bar_colors))
+    }
+}
+
+// ============================================================================
+// Type Aliases
+// ============================================================================
+
+/// Media button hit-test bounds: (button_name, x_start, y_start, x_end, y_end)
+///
+/// Used for detecting clicks on media playback controls.
+/// Button names: "previous", "play_pause", "next", "progress_bar", "shuffle",
+/// "loop", "volume"
+/// For progress_bar, x_start and x_end define the clickable area width.
+pub type MediaButtonBounds = Vec<(String, f64, f64, f64, f64)>;
+
+/// Error badge hit-test bounds: (section, x_start, y_start, x_end, y_end)
+///
+/// Used for showing the error tooltip when the pointer hovers a badge.
+pub type ErrorBadgeBounds = Vec<(WidgetSection, f64, f64, f64, f64)>;
+
+/// Area of each drawn section: (section, x_start, y_start, x_end, y_end)
+///
+/// Used for routing scroll events to the section under the pointer.
+pub type SectionAreaBounds = Vec<(WidgetSection, f64, f64, f64, f64)>;
+
+/// Hit-test bounds of everything interactive in a frame, in surface
+/// coordinates.
+///
+/// Used by widget_main.rs to route clicks, hovers and scrolls.
+#[derive(Default)]
+pub struct WidgetBounds {
+    /// Rectangle (x1, y1, x2, y2) of the notification section
+    pub notifications: Option<(f64, f64, f64, f64)>,
+    /// Notification group headers (app_name, y_start, y_end)
+    pub notification_groups: Vec<(String, f64, f64)>,
+    /// Notification X buttons (id, x1, y1, x2, y2)
+    pub notification_clear: Vec<(String, f64, f64, f64, f64)>,
+    /// "Clear All" button
+    pub clear_all: Option<(f64, f64, f64, f64)>,
+    /// Media control buttons
+    pub media_buttons: MediaButtonBounds,
+    /// Section error badges
+    pub error_badges: ErrorBadgeBounds,
+    /// "Update available" badge
+    pub update_badge: Option<(f64, f64, f64, f64)>,
+    /// Area of each drawn section
+    pub section_areas: SectionAreaBounds,
+    /// Metrics with a detail tooltip
+    pub hover_targets: HoverTargetBounds,
+    /// Whether text is scrolling (the widget keeps drawing frames)
+    pub marquee_running: bool,
+}
+
+/// Why a frame could not be drawn.
+#[derive(Debug)]
+pub enum RenderError {
+    /// The canvas could not be wrapped in a Cairo surface
+    Surface(cairo::Error),
+    /// No Cairo context could be created for the surface
+    Context(cairo::Error),
+    /// A drawing operation failed; the frame is incomplete
+    Draw(cairo::Error),
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Surface(e) => write!(f, "failed to create cairo surface: {}", e),
+            RenderError::Context(e) => write!(f, "failed to create cairo context: {}", e),
+            RenderError::Draw(e) => write!(f, "drawing failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+// ============================================================================
+// Main Rendering Functions
+// ============================================================================
+
+/// Main rendering function for the complete widget.
+///
+/// Renders all enabled sections onto the provided pixel buffer and returns
+/// bounds for all interactive elements (notifications and media controls).
+///
+/// # Arguments
+///
+/// * `canvas` - Mutable ARGB32 pixel buffer (width * height * 4 bytes)
+/// * `config` - What to show and how
+/// * `theme` - COSMIC desktop theme (colors, dark/light mode)
+/// * `data` - Monitor readings and UI state of this frame
+///
+/// # Returns
+///
+/// Bounds of the interactive elements, or a [`RenderError`] if the frame
+/// could not be drawn.
+///
+/// # Safety
+///
+/// Uses unsafe to extend the lifetime of the canvas buffer for Cairo.
+/// This is safe because:
+/// 1. The ImageSurface is dropped before the function returns
+/// 2. The canvas buffer outlives all Cairo operations
+/// 3. The surface is flushed before returning
+pub fn render_widget(canvas: &mut [u8], config: &Config, theme: &CosmicTheme, data: FrameData) -> Result<WidgetBounds, RenderError> {
+    // Use unsafe to extend the lifetime for Cairo
+    // This is safe because the surface doesn't outlive the canvas buffer
+    let surface = unsafe {
+        let ptr = canvas.as_mut_ptr();
+        let len = canvas.len();
+        let static_slice: &'static mut [u8] = std::slice::from_raw_parts_mut(ptr, len);
+
+        cairo::ImageSurface::create_for_data(
+            static_slice,
+            cairo::Format::ARgb32,
+            data.width,
+            data.height,
+            data.width * 4,
+        )
+        .map_err(RenderError::Surface)?
+    };
+
+    let mut widget_bounds = WidgetBounds::default();
+
+    {
+        let cr = cairo::Context::new(&surface).map_err(RenderError::Context)?;
+
+        // Clear background to fully transparent
+        cr.save().ok();
+        cr.set_operator(cairo::Operator::Source);
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().ok();
+        cr.restore().ok();
+
+        theme.text_palette(config.text_contrast).activate();
+        data.fonts.activate();
+
+        // While fading, draw into a group and composite it with the opacity
+        let fading = data.opacity < 1.0;
+        if fading {
+            cr.push_group();
+        }
+
+        // Set up Pango for text rendering
+        let layout = pangocairo::functions::create_layout(&cr);
+        let ctx = RenderContext { cr: &cr, layout: &layout, config, theme, data: &data };
+
+        if config.ambient_target == AmbientTarget::Background {
+            if let Some(color) = ctx.ambient_color() {
+                render_ambient_background(&cr, color, data.width as f64, data.height as f64);
+            }
+        }
+
+        if config.layout_mode == LayoutMode::Compact {
+            render_compact(&ctx);
+        }
+
+        // The identity line spans all columns; the compact strip has no room for it
+        let mut sections_top = 10.0;
+        if let Some(identity) = data.identity.filter(|_| config.layout_mode != LayoutMode::Compact) {
+            render_identity(&cr, &layout, identity, data.width as f64);
+            sections_top += IDENTITY_HEIGHT as f64;
+        }
+
+        // Render each column of sections in the configured order. Columns are
+        // drawn side by side; sections always draw at x = 0, so each section
+        // is translated to its column
+        for (column_index, column) in data.section_columns.iter().enumerate() {
+            let x_offset = column_index as f64 * COLUMN_WIDTH as f64;
+            let mut y_pos = sections_top;
+            let mut first_in_column = true;
+            
+            for kind in column {
+                let section = sections::section(*kind);
+                if !section.visible(&ctx) {
+                    continue;
+                }
+                // Side-by-side columns start flush at the top; a single
+                // column keeps its spacing above the first section
+                let spacing = if first_in_column && data.section_columns.len() > 1 {
+                    0.0
+                } else {
+                    section.spacing()
+                };
+                first_in_column = false;
+                let header_y = y_pos + spacing;
+                
+                cr.save().ok();
+                cr.translate(x_offset, 0.0);
+                // A carded section is drawn into a group first, as its
+                // height is only known once drawn
+                let card = config.section_cards.iter().find(|card| card.section == *kind);
+                if card.is_some() {
+                    cr.push_group();
+                }
+                let mut bounds = SectionBounds::default();
+                y_pos = if data.loading_sections.contains(kind) {
+                    section.render_loading(&ctx, header_y)
+                } else {
+                    section.render(&ctx, header_y, &mut bounds)
+                };
+                
+                // Badge the section header if its monitor reported an error
+                if data.section_errors.iter().any(|(s, _)| s == kind) {
+                    let (x1, y1, x2, y2) = render_error_badge(&cr, COLUMN_WIDTH as f64 - 30.0, header_y + 2.0);
+                    widget_bounds.error_badges.push((*kind, x1 + x_offset, y1, x2 + x_offset, y2));
+                }
+                if let Some(card) = card {
+                    let section = cr.pop_group().ok();
+                    render_section_card(&cr, theme, header_y, y_pos, card.opacity);
+                    if let Some(section) = section {
+                        cr.set_source(&section).ok();
+                        cr.paint().ok();
+                    }
+                }
+                cr.restore().ok();
+                
+                // Hit-test bounds are returned in surface coordinates
+                widget_bounds.section_areas.push((*kind, x_offset, header_y, x_offset + COLUMN_WIDTH as f64, y_pos));
+                if let Some((y_start, y_end)) = bounds.notifications {
+                    widget_bounds.notifications = Some((x_offset, y_start, x_offset + COLUMN_WIDTH as f64, y_end));
+                    widget_bounds.notification_groups = bounds.notification_groups;
+                    widget_bounds.notification_clear = bounds
+                        .notification_clear
+                        .into_iter()
+                        .map(|(key, x1, y1, x2, y2)| (key, x1 + x_offset, y1, x2 + x_offset, y2))
+                        .collect();
+                    widget_bounds.clear_all = bounds.clear_all.map(|(x1, y1, x2, y2)| (x1 + x_offset, y1, x2 + x_offset, y2));
+                }
+                widget_bounds.media_buttons.extend(
+                    bounds
+                        .media_buttons
+                        .into_iter()
+                        .map(|(name, x1, y1, x2, y2)| (name, x1 + x_offset, y1, x2 + x_offset, y2)),
+                );
+                widget_bounds.hover_targets.extend(
+                    bounds
+                        .hover_targets
+                        .into_iter()
+                        .map(|(target, x1, y1, x2, y2)| (target, x1 + x_offset, y1, x2 + x_offset, y2)),
+                );
+                widget_bounds.marquee_running |= bounds.marquee_running;
+            }
+        }
+
+        // The compact strip has no spare room below its slots
+        if let Some(version) = data.update_available {
+            if config.layout_mode != LayoutMode::Compact {
+                widget_bounds.update_badge = Some(render_update_badge(&cr, &layout, version, theme, data.width as f64, data.height as f64));
+            }
+        }
+
+        if data.snap_guides.any() {
+            render_snap_guides(&cr, data.snap_guides, theme, data.width as f64, data.height as f64);
+        }
+
+        if let Some(lines) = data.debug_overlay {
+            render_debug_overlay(&cr, &layout, lines, data.height as f64);
+        }
+
+        // Tooltip last so it draws on top of the sections below the badge
+        if let Some(hovered) = data.hovered_error {
+            let badge = widget_bounds.error_badges.iter().find(|(s, ..)| *s == hovered);
+            let message = data.section_errors.iter().find(|(s, _)| *s == hovered);
+            if let (Some(&(_, x1, y1, x2, y2)), Some((_, message))) = (badge, message) {
+                render_error_tooltip(&cr, &layout, (x1, y1, x2, y2), message, data.width as f64, data.height as f64);
+            }
+        } else if let Some((hovered, lines)) = data.tooltip {
+            let target = widget_bounds.hover_targets.iter().find(|(t, ..)| *t == hovered);
+            if let Some(&(_, x1, y1, x2, y2)) = target {
+                render_hover_tooltip(&cr, &layout, (x1, y1, x2, y2), lines, theme, data.width as f64, data.height as f64);
+            }
+        }
+
+        if fading {
+            cr.pop_group_to_source().ok();
+            cr.paint_with_alpha(data.opacity).ok();
+        }
+
+        cr.status().map_err(RenderError::Draw)?;
+    }
+
+    // Ensure Cairo surface is flushed
+    surface.flush();
+
+    Ok(widget_bounds)
+}
+
+/// Draw the hostname/user line at the top of the widget.
+///
+/// ```text
+/// alice@workstation
+/// 14:30 :45
+/// ```
+fn render_identity(cr: &cairo::Context, layout: &pango::Layout, text: &str, width: f64) {
+    let font_desc = font(TextRole::Body, "Bold 11");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_width(((width - 20.0) * pango::SCALE as f64) as i32);
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    layout.set_text(text);
+
+    cr.move_to(10.0, 8.0);
+    cr.set_line_width(2.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+}
+
+/// Wash the whole widget in the ambient status color.
+///
+/// A rounded panel, translucent enough that the wallpaper still shows and
+/// the outlined text stays readable on top of it.
+fn render_ambient_background(cr: &cairo::Context, (r, g, b): (f64, f64, f64), width: f64, height: f64) {
+    let radius = 12.0;
+    cr.save().ok();
+    cr.new_sub_path();
+    cr.arc(width - radius, radius, radius, -std::f64::consts::FRAC_PI_2, 0.0);
+    cr.arc(width - radius, height - radius, radius, 0.0, std::f64::consts::FRAC_PI_2);
+    cr.arc(radius, height - radius, radius, std::f64::consts::FRAC_PI_2, std::f64::consts::PI);
+    cr.arc(radius, radius, radius, std::f64::consts::PI, 3.0 * std::f64::consts::FRAC_PI_2);
+    cr.close_path();
+    cr.set_source_rgba(r, g, b, 0.22);
+    cr.fill().ok();
+    cr.restore().ok();
+}
+
+/// Space between a section card's edge and the section (pixels).
+const CARD_PADDING: f64 = 4.0;
+
+/// Draw the rounded card behind a section spanning `top` to `bottom`.
+///
+/// The card takes the text outline color, so it contrasts with the text
+/// whichever palette is active, and the theme's corner radius.
+fn render_section_card(cr: &cairo::Context, theme: &CosmicTheme, top: f64, bottom: f64, opacity: u32) {
+    let (left, right) = (CARD_PADDING, COLUMN_WIDTH as f64 - CARD_PADDING);
+    let (top, bottom) = (top - CARD_PADDING, bottom + CARD_PADDING);
+    let radius = theme.corner_radius.min((bottom - top) / 2.0).max(0.0);
+    cr.new_sub_path();
+    cr.arc(right - radius, top + radius, radius, -std::f64::consts::FRAC_PI_2, 0.0);
+    cr.arc(right - radius, bottom - radius, radius, 0.0, std::f64::consts::FRAC_PI_2);
+    cr.arc(left + radius, bottom - radius, radius, std::f64::consts::FRAC_PI_2, std::f64::consts::PI);
+    cr.arc(left + radius, top + radius, radius, std::f64::consts::PI, 3.0 * std::f64::consts::FRAC_PI_2);
+    cr.close_path();
+    let (r, g, b) = TextPalette::active().outline;
+    cr.set_source_rgba(r, g, b, f64::from(opacity.min(100)) / 100.0);
+    cr.fill().ok();
+}
+
+/// Draw the guides the dragged widget snapped to.
+///
+/// The surface only covers the widget, so a guide is drawn where it meets
+/// the widget: along the snapped edge, or through the middle for a center
+/// line (dashed).
+fn render_snap_guides(cr: &cairo::Context, guides: SnapGuides, theme: &CosmicTheme, width: f64, height: f64) {
+    let (r, g, b) = theme.accent_rgb();
+    cr.save().ok();
+    cr.set_source_rgba(r, g, b, 0.9);
+    cr.set_line_width(2.0);
+
+    let lines = [
+        guides.x.map(|guide| (guide, width, true)),
+        guides.y.map(|guide| (guide, height, false)),
+    ];
+    for (guide, extent, vertical) in lines.into_iter().flatten() {
+        let position = match guide {
+            Guide::Start => 1.0,
+            Guide::Center => extent / 2.0,
+            Guide::End => extent - 1.0,
+        };
+        let dashes: &[f64] = if guide == Guide::Center { &[6.0, 4.0] } else { &[] };
+        cr.set_dash(dashes, 0.0);
+        if vertical {
+            cr.move_to(position, 0.0);
+            cr.line_to(position, height);
+        } else {
+            cr.move_to(0.0, position);
+            cr.line_to(width, position);
+        }
+        cr.stroke().ok();
+    }
+
+    cr.restore().ok();
+}
+
+// ============================================================================
+// Compact Layout Rendering
+// ============================================================================
+
+/// Render the compact layout: one row of fixed-width metric slots.
+fn render_compact(ctx: &RenderContext) {
+    let RenderContext { cr, layout, data, .. } = *ctx;
+    let font_desc = font(TextRole::Body, "Bold 12");
+    layout.set_font_description(Some(&font_desc));
+    cr.set_line_width(2.0);
+
+    for (index, slot) in data.compact_slots.iter().enumerate() {
+        layout.set_text(&compact_slot_text(*slot, ctx));
+        let (_, text_height) = layout.pixel_size();
+        let x = 10.0 + index as f64 * COMPACT_SLOT_WIDTH as f64;
+        cr.move_to(x, (COMPACT_HEIGHT as f64 - text_height as f64) / 2.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+    }
+}
+
+/// Short text for one compact slot (e.g., "CPU 12%", "GPU 48°C").
+fn compact_slot_text(slot: CompactSlot, ctx: &RenderContext) -> String {
+    let RenderContext { config, data, .. } = *ctx;
+    let section = match slot {
+        CompactSlot::Clock => WidgetSection::Clock,
+        CompactSlot::Cpu | CompactSlot::Memory | CompactSlot::Gpu => WidgetSection::Utilization,
+        CompactSlot::CpuTemp | CompactSlot::GpuTemp => WidgetSection::Temperatures,
+        CompactSlot::NetworkDown | CompactSlot::NetworkUp => WidgetSection::Network,
+        CompactSlot::Weather => WidgetSection::Weather,
+    };
+    let loading = data.loading_sections.contains(&section);
+
+    match slot {
+        CompactSlot::Clock => {
+            let format = match config.clock_pattern() {
+                Some(format) => format,
+                None if config.use_24hour_time => "%H:%M",
+                None => "%-I:%M %p",
+            };
+            data.current_time.format_localized(format, data.locale).to_string()
+        }
+        CompactSlot::Cpu | CompactSlot::Memory | CompactSlot::Gpu | CompactSlot::CpuTemp | CompactSlot::GpuTemp if loading => {
+            let label = match slot {
+                CompactSlot::Memory => fl!("widget-ram"),
+                CompactSlot::Gpu | CompactSlot::GpuTemp => fl!("widget-gpu"),
+                _ => fl!("widget-cpu"),
+            };
+            format!("{} …", label)
+        }
+        CompactSlot::NetworkDown if loading => String::from("↓ …"),
+        CompactSlot::NetworkUp if loading => String::from("↑ …"),
+        CompactSlot::Weather if loading => String::from("…°C"),
+        CompactSlot::Cpu => format!("{} {:.0}%", fl!("widget-cpu"), data.cpu_usage),
+        CompactSlot::Memory => format!("{} {:.0}%", fl!("widget-ram"), data.memory_usage),
+        CompactSlot::Gpu => format!("{} {:.0}%", fl!("widget-gpu"), data.gpu_usage),
+        CompactSlot::CpuTemp => format!("{} {:.0}°C", fl!("widget-cpu"), data.cpu_temp),
+        CompactSlot::GpuTemp => format!("{} {:.0}°C", fl!("widget-gpu"), data.gpu_temp),
+        CompactSlot::NetworkDown => format!("↓ {}", format_rate(data.network_rx_rate, config.network_rate_bits)),
+        CompactSlot::NetworkUp => format!("↑ {}", format_rate(data.network_tx_rate, config.network_rate_bits)),
+        CompactSlot::Weather if data.weather_temp.is_nan() => String::from("--°C"),
+        CompactSlot::Weather => format!("{:.0}°C", data.weather_temp),
+    }
+}
+
+// ============================================================================
+// Error Badge Rendering
+// ============================================================================
+
+/// Draw a small warning triangle with an exclamation mark.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context
+/// * `x` - Left edge of the badge
+/// * `y` - Top edge of the badge
+///
+/// # Returns
+///
+/// Hit-test bounds (x_start, y_start, x_end, y_end), padded slightly so the
+/// small glyph is easy to hover.
+fn render_error_badge(cr: &cairo::Context, x: f64, y: f64) -> (f64, f64, f64, f64) {
+    let size = 18.0;
+
+    cr.save().ok();
+
+    // Triangle body (amber with black outline, like the text style)
+    cr.move_to(x + size / 2.0, y);
+    cr.line_to(x + size, y + size);
+    cr.line_to(x, y + size);
+    cr.close_path();
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(3.0);
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.stroke_preserve().ok();
+    cr.set_source_rgb(1.0, 0.75, 0.1);
+    cr.fill().ok();
+
+    // Exclamation mark
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(2.0);
+    cr.move_to(x + size / 2.0, y + 6.0);
+    cr.line_to(x + size / 2.0, y + size - 6.0);
+    cr.stroke().ok();
+    cr.arc(x + size / 2.0, y + size - 3.0, 1.2, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill().ok();
+
+    cr.restore().ok();
+
+    (x - 4.0, y - 4.0, x + size + 4.0, y + size + 4.0)
+}
+
+/// Draw the error message tooltip for a hovered badge.
+///
+/// The tooltip is right-aligned under the badge and clamped to stay
+/// inside the surface.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context
+/// * `layout` - Pango layout for text rendering
+/// * `badge` - Bounds of the hovered badge (x_start, y_start, x_end, y_end)
+/// * `message` - Error message to show
+/// * `surface_width` - Surface width in pixels
+/// * `surface_height` - Surface height in pixels
+fn render_error_tooltip(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    badge: (f64, f64, f64, f64),
+    message: &str,
+    surface_width: f64,
+    surface_height: f64,
+) {
+    let padding = 8.0;
+    let max_text_width = 240.0;
+
+    let font_desc = font(TextRole::Body, "11");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_width((max_text_width * pango::SCALE as f64) as i32);
+    layout.set_wrap(pango::WrapMode::WordChar);
+    layout.set_text(message);
+    let (text_width, text_height) = layout.pixel_size();
+
+    let box_width = text_width as f64 + padding * 2.0;
+    let box_height = text_height as f64 + padding * 2.0;
+    let box_x = (badge.2 - box_width).clamp(2.0, (surface_width - box_width - 2.0).max(2.0));
+    let box_y = badge.3.min(surface_height - box_height - 2.0).max(2.0);
+
+    // Background panel
+    cr.save().ok();
+    cr.rectangle(box_x, box_y, box_width, box_height);
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.92);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(1.0, 0.75, 0.1);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+    cr.restore().ok();
+
+    // Message text
+    cr.move_to(box_x + padding, box_y + padding);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.fill().ok();
+
+    // Reset wrapping so later users of the shared layout aren't affected
+    layout.set_width(-1);
+}
+
+/// Draw the debug overlay panel in the bottom-left corner.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context
+/// * `layout` - Pango layout for text rendering
+/// * `lines` - Overlay text, one entry per line
+/// * `surface_height` - Surface height in pixels
+fn render_debug_overlay(cr: &cairo::Context, layout: &pango::Layout, lines: &[String], surface_height: f64) {
+    let padding = 6.0;
+
+    let font_desc = pango::FontDescription::from_string("Monospace 8");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&lines.join("\n"));
+    let (text_width, text_height) = layout.pixel_size();
+
+    let box_width = text_width as f64 + padding * 2.0;
+    let box_height = text_height as f64 + padding * 2.0;
+    let box_x = 4.0;
+    let box_y = (surface_height - box_height - 4.0).max(2.0);
+
+    cr.save().ok();
+    cr.rectangle(box_x, box_y, box_width, box_height);
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.75);
+    cr.fill().ok();
+    cr.restore().ok();
+
+    // Plain text (no outline) so the numbers stay crisp at small sizes
+    cr.move_to(box_x + padding, box_y + padding);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(0.6, 1.0, 0.6);
+    cr.fill().ok();
+}
+
+/// Draw the detail tooltip of a hovered metric.
+///
+/// The tooltip sits under the metric, or above it when there is no room
+/// below, and is clamped to stay inside the surface. Lines are set in a
+/// monospace font so per-core and per-interface columns line up.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context
+/// * `layout` - Pango layout for text rendering
+/// * `target` - Bounds of the hovered metric (x_start, y_start, x_end, y_end)
+/// * `lines` - Tooltip text, one entry per line
+/// * `theme` - COSMIC theme (accent color for the outline)
+/// * `surface_width` - Surface width in pixels
+/// * `surface_height` - Surface height in pixels
+fn render_hover_tooltip(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    target: (f64, f64, f64, f64),
+    lines: &[String],
+    theme: &CosmicTheme,
+    surface_width: f64,
+    surface_height: f64,
+) {
+    if lines.is_empty() {
+        return;
+    }
+    let padding = 8.0;
+
+    let font_desc = pango::FontDescription::from_string("Monospace 9");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&lines.join("\n"));
+    let (text_width, text_height) = layout.pixel_size();
+
+    let box_width = text_width as f64 + padding * 2.0;
+    let box_height = text_height as f64 + padding * 2.0;
+    let box_x = (target.0 + 10.0).clamp(2.0, (surface_width - box_width - 2.0).max(2.0));
+    let box_y = if target.3 + box_height + 2.0 <= surface_height {
+        target.3
+    } else {
+        (target.1 - box_height).max(2.0)
+    };
+
+    // Background panel with an accent outline
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+    cr.save().ok();
+    cr.rectangle(box_x, box_y, box_width, box_height);
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.92);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(accent_r, accent_g, accent_b);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+    cr.restore().ok();
+
+    cr.move_to(box_x + padding, box_y + padding);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.fill().ok();
+}
+
+// ============================================================================
+// Update Badge Rendering
+// ============================================================================
+
+/// Draw the "update available" pill in the bottom-right corner.
+///
+/// Sits in the bottom padding of the widget, so it never covers a section.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context
+/// * `layout` - Pango layout for text rendering
+/// * `version` - Version of the newer release
+/// * `theme` - COSMIC theme (accent color for the outline)
+/// * `surface_width` - Surface width in pixels
+/// * `surface_height` - Surface height in pixels
+///
+/// # Returns
+///
+/// Hit-test bounds (x_start, y_start, x_end, y_end) for opening the release page
+fn render_update_badge(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    version: &str,
+    theme: &CosmicTheme,
+    surface_width: f64,
+    surface_height: f64,
+) -> (f64, f64, f64, f64) {
+    let padding = 6.0;
+
+    let font_desc = font(TextRole::Small, "9");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&fl!("widget-update-available", version = version));
+    let (text_width, text_height) = layout.pixel_size();
+
+    let box_width = text_width as f64 + padding * 2.0;
+    let box_height = text_height as f64 + 2.0;
+    let box_x = surface_width - box_width - 8.0;
+    let box_y = surface_height - box_height - 2.0;
+
+    // Dim pill with an accent outline
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+    cr.save().ok();
+    cr.new_sub_path();
+    let radius = box_height / 2.0;
+    cr.arc(box_x + box_width - radius, box_y + radius, radius, -std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
+    cr.arc(box_x + radius, box_y + radius, radius, std::f64::consts::FRAC_PI_2, 3.0 * std::f64::consts::FRAC_PI_2);
+    cr.close_path();
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.7);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(accent_r, accent_g, accent_b);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+    cr.restore().ok();
+
+    cr.move_to(box_x + padding, box_y + 1.0);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.fill().ok();
+
+    (box_x, box_y, box_x + box_width, box_y + box_height)
+}
+
+// ============================================================================
+// Custom Commands Section
+// ============================================================================
+
+/// Render one row per custom command: label on the left, output on the right.
+///
+/// Commands that haven't produced output yet show "…"; failed ones show
+/// "error" dimmed (the message is in the section's error badge).
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_custom_commands(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, outputs: &[CommandOutput]) -> f64 {
+    let mut y = y_start;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-custom"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0;
+
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+
+    if outputs.is_empty() {
+        layout.set_text(&fl!("widget-no-commands"));
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        return y + 25.0;
+    }
+
+    // Long labels and values are ellipsized to their column
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    let error_text = fl!("widget-command-error");
+    for output in outputs {
+        layout.set_width(120 * pango::SCALE);
+        layout.set_text(&output.label);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        let (text, dimmed) = match (&output.error, output.text.is_empty()) {
+            (Some(_), _) => (error_text.as_str(), true),
+            (None, true) => ("…", true),
+            (None, false) => (output.text.as_str(), false),
+        };
+        layout.set_width(220 * pango::SCALE);
+        layout.set_text(text);
+        cr.move_to(140.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        if dimmed {
+            set_text_muted(cr);
+        } else {
+            set_text_fill(cr);
+        }
+        cr.fill().ok();
+
+        y += 25.0;
+    }
+
+    // Reset so later users of the shared layout aren't affected
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+
+    y
+}
+
+// ============================================================================
+// Plugins Section
+// ============================================================================
+
+/// Replay the recorded drawing calls of every plugin, one block below the other.
+///
+/// Each block is clipped to its column width and reserved height, so a
+/// plugin can't draw over its neighbors.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_plugins(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, frames: &[PluginFrame]) -> f64 {
+    let mut y = y_start;
+
+    for (index, frame) in frames.iter().enumerate() {
+        if index > 0 {
+            y += PLUGIN_SPACING as f64;
+        }
+
+        cr.save().ok();
+        cr.translate(0.0, y);
+        cr.rectangle(0.0, 0.0, COLUMN_WIDTH as f64, frame.height as f64);
+        cr.clip();
+
+        for op in &frame.ops {
+            match op {
+                DrawOp::Rect { x, y, width, height, color } => {
+                    cr.rectangle(*x, *y, *width, *height);
+                    cr.set_source_rgba(color.0, color.1, color.2, color.3);
+                    cr.fill().ok();
+                }
+                DrawOp::Arc { cx, cy, radius, start, end, line_width, color } => {
+                    cr.new_sub_path();
+                    cr.arc(*cx, *cy, *radius, *start, *end);
+                    cr.set_source_rgba(color.0, color.1, color.2, color.3);
+                    cr.set_line_width(*line_width);
+                    cr.stroke().ok();
+                }
+                DrawOp::Text { x, y, text, size, color } => {
+                    let font_desc = font(TextRole::Body, &size.to_string());
+                    layout.set_font_description(Some(&font_desc));
+                    layout.set_text(text);
+                    cr.move_to(*x, *y);
+                    pangocairo::functions::layout_path(cr, layout);
+                    cr.set_source_rgba(0.0, 0.0, 0.0, color.3);
+                    cr.set_line_width(2.0);
+                    cr.stroke_preserve().ok();
+                    cr.set_source_rgba(color.0, color.1, color.2, color.3);
+                    cr.fill().ok();
+                }
+            }
+        }
+
+        cr.restore().ok();
+        y += frame.height as f64;
+    }
+
+    y
+}
+
+// ============================================================================
+// Audio Section
+// ============================================================================
+
+/// Draw the default audio output: its name, then a volume bar.
+///
+/// ```text
+/// [speaker] Built-in Audio Analog Stereo
+///           [=========          ]  45%     ← muted: grey bar, "Muted"
+/// ```
+///
+/// Clicks (mute) and scrolling (volume) anywhere on the section are
+/// handled by widget_main.rs through the section's area.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_audio(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, audio: Option<&AudioState>, theme: &CosmicTheme) -> f64 {
+    let y = y_start;
+    let muted = audio.is_none_or(|audio| audio.muted);
+
+    draw_speaker_icon(cr, 10.0, y, 20.0, muted);
+
+    // Device name, leaving room for the error badge
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_width(((COLUMN_WIDTH as f64 - 90.0) * pango::SCALE as f64) as i32);
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    layout.set_text(&audio.map_or_else(|| fl!("widget-audio-no-output"), |audio| audio.device.clone()));
+    cr.move_to(40.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+
+    let Some(audio) = audio else {
+        return y + 50.0;
+    };
+
+    // Volume bar, in the accent color unless muted
+    let color = if audio.muted { (0.5, 0.5, 0.5) } else { theme.accent_rgb() };
+    draw_progress_bar(cr, 40.0, y + 28.0, 240.0, 12.0, audio.volume.min(100) as f32, color);
+
+    let text = if audio.muted { fl!("widget-audio-muted") } else { format!("{}%", audio.volume) };
+    layout.set_text(&text);
+    let (text_width, _) = layout.pixel_size();
+    cr.move_to(COLUMN_WIDTH as f64 - 10.0 - text_width as f64, y + 24.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    if audio.muted {
+        set_text_muted(cr);
+    } else {
+        set_text_fill(cr);
+    }
+    cr.fill().ok();
+
+    y + 50.0
+}
+
+/// Draw a speaker, with sound waves or crossed out when muted.
+fn draw_speaker_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, muted: bool) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.set_line_cap(cairo::LineCap::Round);
+
+    // Speaker body and cone
+    cr.move_to(x + 2.0 * s, y + 7.0 * s);
+    cr.line_to(x + 6.0 * s, y + 7.0 * s);
+    cr.line_to(x + 11.0 * s, y + 2.0 * s);
+    cr.line_to(x + 11.0 * s, y + 18.0 * s);
+    cr.line_to(x + 6.0 * s, y + 13.0 * s);
+    cr.line_to(x + 2.0 * s, y + 13.0 * s);
+    cr.close_path();
+    set_text_fill(cr);
+    cr.fill_preserve().ok();
+    set_text_outline(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+
+    // Waves, or a cross while muted
+    if muted {
+        cr.move_to(x + 14.0 * s, y + 7.0 * s);
+        cr.line_to(x + 19.0 * s, y + 13.0 * s);
+        cr.move_to(x + 19.0 * s, y + 7.0 * s);
+        cr.line_to(x + 14.0 * s, y + 13.0 * s);
+    } else {
+        for radius in [4.0, 7.5] {
+            cr.new_sub_path();
+            cr.arc(x + 11.0 * s, y + 10.0 * s, radius * s, -0.8, 0.8);
+        }
+    }
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+// ============================================================================
+// System Health Section
+// ============================================================================
+
+/// Draw failed systemd units and pending updates as count badges.
+///
+/// ```text
+/// [⚠] Failed units            (2)   ← red while any unit failed, green at 0
+/// [↓] Updates                (14)   ← only with an update command set
+/// ```
+///
+/// The badges stop short of the right edge to leave room for the error
+/// badge. Hovering the failed units row lists the units in a tooltip.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_system_health(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+    let report = data.health.cloned().unwrap_or_default();
+    let badge_right = COLUMN_WIDTH as f64 - 40.0;
+
+    // Failed units
+    let failed = report.failed_units.len();
+    draw_health_icon(cr, 10.0, y, 20.0, failed > 0);
+    draw_row_label(cr, layout, 40.0, y, &fl!("widget-health-failed-units"));
+    let level = if failed > 0 { Level::Critical } else { Level::Normal };
+    draw_badge(cr, layout, badge_right, y + 2.0, &failed.to_string(), config.bar_colors.rgb(level));
+    targets.push((HoverTarget::SystemHealth, 0.0, y - 2.0, COLUMN_WIDTH as f64, y + 25.0));
+    y += 25.0;
+
+    // Pending updates
+    if !config.health_update_command.trim().is_empty() {
+        draw_updates_icon(cr, 10.0, y, 20.0);
+        draw_row_label(cr, layout, 40.0, y, &fl!("widget-health-updates"));
+        let (text, level) = match report.updates {
+            Some(0) => (String::from("0"), Level::Normal),
+            Some(count) => (count.to_string(), Level::Warning),
+            None => (String::from("?"), Level::Normal),
+        };
+        draw_badge(cr, layout, badge_right, y + 2.0, &text, config.bar_colors.rgb(level));
+        y += 25.0;
+    }
+
+    y + 5.0
+}
+
+/// Draw outlined row text in the regular font.
+fn draw_row_label(cr: &cairo::Context, layout: &pango::Layout, x: f64, y: f64, text: &str) {
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(text);
+    cr.move_to(x, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+}
+
+/// Draw a warning triangle (units failed) or a check mark (all running).
+fn draw_health_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, failed: bool) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.set_line_cap(cairo::LineCap::Round);
+    if failed {
+        cr.move_to(x + 10.0 * s, y + 2.0 * s);
+        cr.line_to(x + 19.0 * s, y + 18.0 * s);
+        cr.line_to(x + 1.0 * s, y + 18.0 * s);
+        cr.close_path();
+        cr.move_to(x + 10.0 * s, y + 8.0 * s);
+        cr.line_to(x + 10.0 * s, y + 12.0 * s);
+        cr.move_to(x + 10.0 * s, y + 15.0 * s);
+        cr.line_to(x + 10.0 * s, y + 15.5 * s);
+    } else {
+        cr.move_to(x + 3.0 * s, y + 10.0 * s);
+        cr.line_to(x + 8.0 * s, y + 15.0 * s);
+        cr.line_to(x + 17.0 * s, y + 4.0 * s);
+    }
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+/// Draw a download arrow into a tray.
+fn draw_updates_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.set_line_cap(cairo::LineCap::Round);
+    cr.move_to(x + 10.0 * s, y + 2.0 * s);
+    cr.line_to(x + 10.0 * s, y + 12.0 * s);
+    cr.move_to(x + 6.0 * s, y + 8.0 * s);
+    cr.line_to(x + 10.0 * s, y + 12.0 * s);
+    cr.line_to(x + 14.0 * s, y + 8.0 * s);
+    cr.move_to(x + 3.0 * s, y + 13.0 * s);
+    cr.line_to(x + 3.0 * s, y + 17.0 * s);
+    cr.line_to(x + 17.0 * s, y + 17.0 * s);
+    cr.line_to(x + 17.0 * s, y + 13.0 * s);
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+// ============================================================================
+// System Section
+// ============================================================================
+
+/// Height of the kernel statistics row.
+pub(super) const KERNEL_STATS_ROW_HEIGHT: f64 = 40.0;
+
+/// Draw the process and thread counts and context switches per second,
+/// small muted labels over the values.
+///
+/// ```text
+/// Processes     Threads       Context Switches
+/// 412           1893          12.4k/s
+/// ```
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_system(ctx: &RenderContext, y: f64) -> f64 {
+    let RenderContext { cr, layout, data, .. } = *ctx;
+    let label_font = font(TextRole::Small, "9");
+    let value_font = font(TextRole::Body, "Bold 12");
+    let stats = data.kernel_stats;
+    let cell_width = (COLUMN_WIDTH as f64 - 20.0) / 3.0;
+    let cells = [
+        (fl!("widget-kernel-processes"), stats.map(|stats| stats.processes.to_string())),
+        (fl!("widget-kernel-threads"), stats.map(|stats| stats.threads.to_string())),
+        (
+            fl!("widget-kernel-context-switches"),
+            stats
+                .and_then(|stats| stats.context_switches_per_sec)
+                .map(|rate| fl!("widget-kernel-per-second", count = format_count(rate))),
+        ),
+    ];
+
+    cr.set_line_width(2.0);
+    for (index, (label, value)) in cells.into_iter().enumerate() {
+        let x = 10.0 + index as f64 * cell_width;
+        let value = value.unwrap_or_else(|| String::from("–"));
+        for (text, font, text_y, muted) in [(&label, &label_font, y, true), (&value, &value_font, y + 14.0, false)] {
+            layout.set_font_description(Some(font));
+            layout.set_text(text);
+            cr.move_to(x, text_y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            if muted {
+                set_text_muted(cr);
+            } else {
+                set_text_fill(cr);
+            }
+            cr.fill().ok();
+        }
+    }
+
+    y + KERNEL_STATS_ROW_HEIGHT
+}
+
+// ============================================================================
+// Containers Section
+// ============================================================================
+
+/// Draw the running container count and, optionally, the busiest container.
+///
+/// ```text
+/// [▤] Running containers        (3)
+/// [▤] Busiest: postgres     (12.3%)   ← colored like the CPU bar
+/// ```
+///
+/// Hovering the count lists the containers in a tooltip.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_containers(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+    let report = data.containers.cloned().unwrap_or_default();
+    let badge_right = COLUMN_WIDTH as f64 - 40.0;
+
+    // Running count
+    draw_container_icon(cr, 10.0, y, 20.0);
+    draw_row_label(cr, layout, 40.0, y, &fl!("widget-containers-running"));
+    let count = report.running.len().to_string();
+    draw_badge(cr, layout, badge_right, y + 2.0, &count, config.bar_colors.rgb(Level::Normal));
+    targets.push((HoverTarget::Containers, 0.0, y - 2.0, COLUMN_WIDTH as f64, y + 25.0));
+    y += 25.0;
+
+    // Busiest container
+    if config.containers_show_busiest {
+        draw_container_icon(cr, 10.0, y, 20.0);
+        match &report.busiest {
+            Some(busiest) => {
+                let text = format!("{:.1}%", busiest.cpu_percent);
+                let color = ctx.level_color(ThresholdMetric::Cpu, busiest.cpu_percent);
+                let badge_left = draw_badge(cr, layout, badge_right, y + 2.0, &text, color);
+                layout.set_width(((badge_left - 50.0) * pango::SCALE as f64) as i32);
+                layout.set_ellipsize(pango::EllipsizeMode::End);
+                draw_row_label(cr, layout, 40.0, y, &fl!("widget-containers-busiest", name = busiest.name.clone()));
+                layout.set_width(-1);
+                layout.set_ellipsize(pango::EllipsizeMode::None);
+            }
+            None => draw_row_label(cr, layout, 40.0, y, &fl!("widget-containers-idle")),
+        }
+        y += 25.0;
+    }
+
+    y + 5.0
+}
+
+/// Draw a container: a box with three slats.
+fn draw_container_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.set_line_cap(cairo::LineCap::Round);
+    cr.rectangle(x + 2.0 * s, y + 5.0 * s, 16.0 * s, 11.0 * s);
+    for slat in [6.0, 10.0, 14.0] {
+        cr.move_to(x + slat * s, y + 8.0 * s);
+        cr.line_to(x + slat * s, y + 13.0 * s);
+    }
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+// ============================================================================
+// Mail & Feeds Section
+// ============================================================================
+
+/// Draw one row per mailbox or feed.
+///
+/// ```text
+/// [✉] Mail: 3 unread
+/// [◝] News: Kernel 6.12 released, with…   ← ellipsized before the error badge
+/// ```
+///
+/// Mailboxes without unread mail are dimmed. Rows not checked yet show
+/// "…"; failed ones show "error" dimmed (the message is in the section's
+/// error badge).
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_feeds(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, rows: &[FeedRow]) -> f64 {
+    let mut y = y_start;
+
+    if rows.is_empty() {
+        draw_row_label(cr, layout, 10.0, y, &fl!("widget-no-feeds"));
+        return y + 30.0;
+    }
+
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    layout.set_width(((COLUMN_WIDTH as f64 - 80.0) * pango::SCALE as f64) as i32);
+    for row in rows {
+        match row.kind {
+            FeedKind::Imap => draw_mail_icon(cr, 10.0, y, 20.0),
+            FeedKind::Rss => draw_feed_icon(cr, 10.0, y, 20.0),
+        }
+
+        let (value, dimmed) = match &row.value {
+            Some(FeedValue::Unread(count)) => (fl!("widget-feed-unread", count = count), *count == 0),
+            Some(FeedValue::Headline(title)) => (title.clone(), false),
+            None if row.failed => (fl!("widget-command-error"), true),
+            None => (String::from("…"), true),
+        };
+        let text = if row.label.is_empty() { value } else { format!("{}: {}", row.label, value) };
+        layout.set_text(&text);
+        cr.move_to(40.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        if dimmed {
+            set_text_muted(cr);
+        } else {
+            set_text_fill(cr);
+        }
+        cr.fill().ok();
+        y += 25.0;
+    }
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+
+    y + 5.0
+}
+
+/// Draw an envelope.
+fn draw_mail_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.set_line_cap(cairo::LineCap::Round);
+    cr.rectangle(x + 2.0 * s, y + 4.0 * s, 16.0 * s, 12.0 * s);
+    cr.move_to(x + 2.0 * s, y + 4.0 * s);
+    cr.line_to(x + 10.0 * s, y + 11.0 * s);
+    cr.line_to(x + 18.0 * s, y + 4.0 * s);
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+/// Draw the feed symbol: a dot with two arcs around it.
+fn draw_feed_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let s = size / 20.0;
+    cr.save().ok();
+    cr.set_line_cap(cairo::LineCap::Round);
+    for radius in [7.0, 13.0] {
+        cr.new_sub_path();
+        cr.arc(x + 4.0 * s, y + 16.0 * s, radius * s, -std::f64::consts::FRAC_PI_2, 0.0);
+    }
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.arc(x + 4.0 * s, y + 16.0 * s, 2.0 * s, 0.0, 2.0 * std::f64::consts::PI);
+    set_text_fill(cr);
+    cr.fill_preserve().ok();
+    set_text_outline(cr);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+    cr.restore().ok();
+}
+
+// ============================================================================
+// Ticker Section
+// ============================================================================
+
+/// Draw one row per symbol: symbol, price and 24h change.
+///
+/// ```text
+/// bitcoin      67012 USD      +1.2%   ← change green when up, red when down
+/// AAPL         189.50 USD     -0.8%
+/// ```
+///
+/// Symbols without a quote yet show "…". The change stops short of the
+/// right edge to leave room for the error badge.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_ticker(ctx: &RenderContext, y_start: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+
+    if data.ticker_rows.is_empty() {
+        draw_row_label(cr, layout, 10.0, y, &fl!("widget-no-symbols"));
+        return y + 30.0;
+    }
+
+    let currency = config.ticker_currency.trim().to_uppercase();
+    let change_right = COLUMN_WIDTH as f64 - 40.0;
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    for row in data.ticker_rows {
+        // Symbol
+        layout.set_width(120 * pango::SCALE);
+        layout.set_text(&row.symbol);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // Price
+        let price = match row.quote {
+            Some(quote) if currency.is_empty() => format_price(quote.price),
+            Some(quote) => format!("{} {}", format_price(quote.price), currency),
+            None => String::from("…"),
+        };
+        layout.set_width(150 * pango::SCALE);
+        layout.set_text(&price);
+        cr.move_to(140.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        if row.quote.is_some() {
+            set_text_fill(cr);
+        } else {
+            set_text_muted(cr);
+        }
+        cr.fill().ok();
+
+        // 24h change, right-aligned
+        if let Some(change) = row.quote.and_then(|quote| quote.change_percent) {
+            layout.set_width(-1);
+            layout.set_text(&format!("{:+.1}%", change));
+            let (text_width, _) = layout.pixel_size();
+            cr.move_to(change_right - text_width as f64, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            let level = if change < 0.0 { Level::Critical } else { Level::Normal };
+            let (r, g, b) = config.bar_colors.rgb(level);
+            cr.set_source_rgb(r, g, b);
+            cr.fill().ok();
+        }
+        y += 25.0;
+    }
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+
+    y + 5.0
+}
+
+// ============================================================================
+// Caffeine Section
+// ============================================================================
+
+/// Draw the caffeine toggle: a coffee cup and whether the screen is kept on.
+///
+/// ```text
+/// ≋
+/// [cup] Screen stays on     ← on: accent-filled cup with steam
+/// [cup] Screen may sleep    ← off: empty outline
+/// ```
+///
+/// Clicks anywhere on the row are handled by widget_main.rs through the
+/// section's area.
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_caffeine(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, active: bool, theme: &CosmicTheme) -> f64 {
+    let y = y_start;
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+
+    // Cup body, outlined in black like the text; filled only while on
+    cr.save().ok();
+    cr.set_line_width(2.0);
+    cr.set_line_join(cairo::LineJoin::Round);
+    cr.move_to(12.0, y + 6.0);
+    cr.line_to(28.0, y + 6.0);
+    cr.line_to(26.0, y + 20.0);
+    cr.line_to(14.0, y + 20.0);
+    cr.close_path();
+    if active {
+        cr.set_source_rgb(accent_r, accent_g, accent_b);
+        cr.fill_preserve().ok();
+    }
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+
+    // Handle
+    cr.new_sub_path();
+    cr.arc(28.0, y + 12.0, 4.0, -std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
+    set_text_outline(cr);
+    cr.set_line_width(3.5);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+
+    // Steam while on
+    if active {
+        for x in [17.0, 23.0] {
+            cr.move_to(x, y + 3.0);
+            cr.curve_to(x - 2.0, y + 1.0, x + 2.0, y - 1.0, x, y - 3.0);
+        }
+        cr.set_source_rgb(accent_r, accent_g, accent_b);
+        cr.set_line_width(1.5);
+        cr.stroke().ok();
+    }
+    cr.restore().ok();
+
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&if active { fl!("widget-caffeine-on") } else { fl!("widget-caffeine-off") });
+    cr.move_to(42.0, y + 2.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    if active {
+        set_text_fill(cr);
+    } else {
+        set_text_muted(cr);
+    }
+    cr.fill().ok();
+
+    y + 30.0
+}
+
+// ============================================================================
+// Loading Placeholder
+// ============================================================================
+
+/// Draw a section's header (if it has one) and a dimmed "Loading…" line.
+///
+/// Used until the section's monitor reports its first sample, so startup
+/// doesn't show zeros that look like real readings.
+///
+/// # Returns
+///
+/// Y position below the placeholder
+pub(super) fn render_loading_placeholder(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, header: Option<&str>) -> f64 {
+    let mut y = y_start;
+    cr.set_line_width(2.0);
+
+    if let Some(header) = header {
+        let header_font = font(TextRole::Header, "Bold 14");
+        layout.set_font_description(Some(&header_font));
+        layout.set_text(header);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 35.0;
+    }
+
+    let font_desc = font(TextRole::Body, "Italic 12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&fl!("widget-loading"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_muted(cr);
+    cr.fill().ok();
+
+    y + 25.0
+}
+
+// ============================================================================
+// Alternative Rendering Functions (Unused but kept for split-surface architecture)
+// ============================================================================
+
+/// Render main widget WITHOUT notifications (for split surface architecture).
+///
+/// This function was designed for a split-surface approach where notifications
+/// would be rendered on a separate surface. Currently unused but kept for
+/// potential future use.
+///
+/// # Note
+///
+/// This is marked as dead code by the compiler. The current implementation
+/// uses a single surface for all rendering.
+#[allow(dead_code)]
+pub fn render_main_widget(canvas: &mut [u8], config: &Config, theme: &CosmicTheme, data: FrameData) -> Result<(Vec<(String, f64, f64)>, Vec<(String, f64, f64, f64, f64)>, Option<(f64, f64, f64, f64)>), RenderError> {
+    // Use unsafe to extend the lifetime for Cairo
+    let surface = unsafe {
+        let ptr = canvas.as_mut_ptr();
+        let len = canvas.len();
+        let static_slice: &'static mut [u8] = std::slice::from_raw_parts_mut(ptr, len);
+
+        cairo::ImageSurface::create_for_data(
+            static_slice,
+            cairo::Format::ARgb32,
+            data.width,
+            data.height,
+            data.width * 4,
+        )
+        .map_err(RenderError::Surface)?
+    };
+
+    let mut notification_bounds = (Vec::new(), Vec::new(), None);
+
+    {
+        let cr = cairo::Context::new(&surface).map_err(RenderError::Context)?;
+
+        // Clear background to fully transparent
+        cr.save().ok();
+        cr.set_operator(cairo::Operator::Source);
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().ok();
+        cr.restore().ok();
+
+        // Set up Pango for text rendering
+        let layout = pangocairo::functions::create_layout(&cr);
+
+        // Track vertical position
+        let mut y_pos = 10.0;
+
+        // Render sections in the configured order
+        let ctx = RenderContext { cr: &cr, layout: &layout, config, theme, data: &data };
+        for kind in &config.section_order {
+            let section = sections::section(*kind);
+            if !section.visible(&ctx) {
+                continue;
+            }
+            let mut bounds = SectionBounds::default();
+            y_pos = if data.loading_sections.contains(kind) {
+                section.render_loading(&ctx, y_pos + section.spacing())
+            } else {
+                section.render(&ctx, y_pos + section.spacing(), &mut bounds)
+            };
+            if bounds.notifications.is_some() {
+                notification_bounds = (bounds.notification_groups, bounds.notification_clear, bounds.clear_all);
+            }
+        }
+
+        cr.status().map_err(RenderError::Draw)?;
+    }
+
+    surface.flush();
+    Ok(notification_bounds)
+}
+
+/// Render ONLY notifications on separate surface (for split surface architecture).
+///
+/// This function was designed for a split-surface approach where notifications
+/// would be rendered independently. Currently unused but kept for potential
+/// future use.
+///
+/// # Note
+///
+/// This is marked as dead code by the compiler. The current implementation
+/// uses a single surface for all rendering.
+#[allow(dead_code)]
+pub fn render_notification_surface(
+    canvas: &mut [u8], 
+    width: i32,
+    height: i32,
+    grouped_notifications: &[(String, Vec<Notification>)],
+    collapsed_groups: &std::collections::HashSet<String>,
+) -> Result<(Vec<(String, f64, f64)>, Vec<(String, f64, f64, f64, f64)>, Option<(f64, f64, f64, f64)>), RenderError> {
+    let surface = unsafe {
+        let ptr = canvas.as_mut_ptr();
+        let len = canvas.len();
+        let static_slice: &'static mut [u8] = std::slice::from_raw_parts_mut(ptr, len);
+
+        cairo::ImageSurface::create_for_data(
+            static_slice,
+            cairo::Format::ARgb32,
+            width,
+            height,
+            width * 4,
+        )
+        .map_err(RenderError::Surface)?
+    };
+
+    let mut notification_group_bounds: Vec<(String, f64, f64)> = Vec::new();
+    let mut notification_clear_bounds: Vec<(String, f64, f64, f64, f64)> = Vec::new();
+    let mut clear_all_bounds: Option<(f64, f64, f64, f64)> = None;
+
+    {
+        let cr = cairo::Context::new(&surface).map_err(RenderError::Context)?;
+
+        // Clear background to fully transparent
+        cr.save().ok();
+        cr.set_operator(cairo::Operator::Source);
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().ok();
+        cr.restore().ok();
+
+        // Set up Pango for text rendering
+        let layout = pangocairo::functions::create_layout(&cr);
+
+        // Use default theme for standalone notification surface
+        let theme = CosmicTheme::default();
+
+        // Render notifications starting from top
+        let (_new_y, _bounds, groups, clear_bounds, clear_all, _dnd) = render_notifications(
+            &cr, 
+            &layout, 
+            10.0,  // Start at top with small padding
+            grouped_notifications,
+            collapsed_groups,
+            &std::collections::HashMap::new(),
+            0,
+            None,
+            &theme,
+        );
+
+        notification_group_bounds = groups;
+        notification_clear_bounds = clear_bounds;
+        clear_all_bounds = clear_all;
+
+        cr.status().map_err(RenderError::Draw)?;
+    }
+
+    surface.flush();
+
+    Ok((notification_group_bounds, notification_clear_bounds, clear_all_bounds))
+}
+
+// ============================================================================
+// DateTime Section
+// ============================================================================
+
+/// Render date and time display at the top of the widget.
+///
+/// The clock is rendered with a large font (48pt) for hours and minutes,
+/// with seconds in a smaller font (28pt) to the right. For 12-hour format,
+/// AM/PM is appended after seconds.
+///
+/// # Clock Format Examples
+///
+/// - 24-hour: `14:30:45`
+/// - 12-hour: `2:30:45 PM`
+/// - custom `clock_format`: drawn as is in the large font (`%H:%M:%S` → `14:30:45`)
+///
+/// # Date Format
+///
+/// `date_format`, by default full weekday, day, month, year: `Wednesday, 15 January 2025`
+///
+/// # Time Zone
+///
+/// With `zone_label`, `now` is in a world clock's zone (scrolled to over
+/// the clock); the label goes under the seconds, or after the date when the
+/// clock is hidden.
+///
+/// # Outline
+///
+/// `outline` (the ambient status color) replaces the theme's outline color
+/// of the clock digits; the date keeps the theme's.
+///
+/// # Visual Layout
+///
+/// ```text
+/// 14:30 :45      ← Clock (large + small seconds)
+///       Tokyo    ← Zone label (only while showing a world clock)
+/// Wednesday, 15 January 2025  ← Date
+/// ```
+pub(super) fn render_datetime(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    show_clock: bool,
+    show_date: bool,
+    use_24hour_time: bool,
+    clock_format: Option<&str>,
+    date_format: &str,
+    locale: chrono::Locale,
+    now: &chrono::DateTime<chrono::FixedOffset>,
+    zone_label: Option<&str>,
+    outline: Option<(f64, f64, f64)>,
+) -> f64 {
+    let mut y_pos = y_start;
+    // The ambient status tint replaces the clock's outline color
+    let set_clock_outline = |cr: &cairo::Context| match outline {
+        Some((r, g, b)) => cr.set_source_rgb(r, g, b),
+        None => set_text_outline(cr),
+    };
+
+    if let Some(format) = clock_format.filter(|_| show_clock) {
+        // Custom pattern: one line in the large font, no separate seconds
+        let font_desc = font(TextRole::Clock, "Bold 48");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&now.format_localized(format, locale).to_string());
+
+        cr.move_to(10.0, y_pos);
+        cr.set_line_width(3.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_clock_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        if let Some(label) = zone_label {
+            let (text_width, _) = layout.pixel_size();
+            draw_zone_label(cr, layout, 14.0 + text_width as f64, y_pos + 40.0, label);
+        }
+
+        y_pos += 70.0; // Move down after clock
+    } else if show_clock {
+        // Draw large time (HH:MM or h:MM based on format)
+        let time_str = if use_24hour_time {
+            now.format("%H:%M").to_string()
+        } else {
+            now.format("%-I:%M").to_string()
+        };
+        let font_desc = font(TextRole::Clock, "Bold 48");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&time_str);
+
+        // Text with outline
+        set_text_fill(cr);
+        cr.move_to(10.0, y_pos);
+
+        // Draw outline
+        cr.set_line_width(3.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_clock_outline(cr);
+        cr.stroke_preserve().ok();
+
+        // Fill with the text color
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // Get width of the time text to position seconds correctly
+        let (time_width, _) = layout.pixel_size();
+
+        // Draw seconds (:SS) slightly smaller and raised
+        let seconds_str = now.format(":%S").to_string();
+        let font_desc = font(TextRole::Clock, "Bold 28");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&seconds_str);
+
+        cr.move_to(10.0 + time_width as f64, y_pos + 5.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_clock_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // For 12-hour format, add AM/PM indicator
+        if !use_24hour_time {
+            let ampm_str = now.format(" %p").to_string();
+            let font_desc = font(TextRole::Clock, "Bold 20");
+            layout.set_font_description(Some(&font_desc));
+            layout.set_text(&ampm_str);
+            
+            let (seconds_width, _) = layout.pixel_size();
+            cr.move_to(10.0 + time_width as f64 + seconds_width as f64, y_pos + 10.0);
+            pangocairo::functions::layout_path(cr, layout);
+            set_clock_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_fill(cr);
+            cr.fill().ok();
+        }
+
+        if let Some(label) = zone_label {
+            draw_zone_label(cr, layout, 12.0 + time_width as f64, y_pos + 40.0, label);
+        }
+
+        y_pos += 70.0; // Move down after clock
+    }
+
+    if show_date {
+        // Draw date below with more spacing
+        let mut date_str = now.format_localized(date_format, locale).to_string();
+        if let Some(label) = zone_label.filter(|_| !show_clock) {
+            date_str = format!("{} · {}", date_str, label);
+        }
+        let font_desc = font(TextRole::Clock, "16");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&date_str);
+
+        cr.move_to(10.0, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        y_pos += 35.0; // Move down after date
+    }
+
+    y_pos
+}
+
+/// Draw the name of the zone the large clock is showing.
+fn draw_zone_label(cr: &cairo::Context, layout: &pango::Layout, x: f64, y: f64, label: &str) {
+    let font_desc = font(TextRole::Body, "Bold 12");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(label);
+    // Thinner outline than the clock's, restored for the date
+    cr.save().ok();
+    cr.set_line_width(2.0);
+    cr.move_to(x, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    cr.restore().ok();
+}
+
+/// Render the upcoming calendar events below the date.
+///
+/// The day and time share one column sized to the longest entry; titles
+/// are ellipsized to the rest of the widget width.
+pub(super) fn render_calendar_events(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, rows: &[EventRow]) -> f64 {
+    let mut y = y_start;
+    let font_desc = font(TextRole::Body, "13");
+    layout.set_font_description(Some(&font_desc));
+
+    let when_width = rows
+        .iter()
+        .map(|row| {
+            layout.set_text(&row.when);
+            layout.pixel_size().0
+        })
+        .max()
+        .unwrap_or(0) as f64;
+    let title_x = 26.0 + when_width;
+    let title_width = (COLUMN_WIDTH as f64 - title_x - 10.0).max(40.0);
+
+    for row in rows {
+        // Day and time dimmed, title ellipsized to the column
+        for (x, text, width, brightness) in [(10.0, row.when.as_str(), -1, 0.7), (title_x, row.title.as_str(), (title_width * pango::SCALE as f64) as i32, 1.0)] {
+            layout.set_width(width);
+            layout.set_ellipsize(if width < 0 { pango::EllipsizeMode::None } else { pango::EllipsizeMode::End });
+            layout.set_text(text);
+            cr.move_to(x, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.set_line_width(2.0);
+            cr.stroke_preserve().ok();
+            cr.set_source_rgb(brightness, brightness, brightness);
+            cr.fill().ok();
+        }
+        y += 24.0;
+    }
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+
+    y
+}
+
+/// Render the world clock rows below the clock and date.
+///
+/// Labels share one column sized to the longest label; times of unknown
+/// zones are dimmed.
+pub(super) fn render_world_clocks(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, rows: &[WorldClockRow]) -> f64 {
+    let mut y = y_start;
+    let font_desc = font(TextRole::Body, "13");
+    layout.set_font_description(Some(&font_desc));
+
+    let label_width = rows
+        .iter()
+        .map(|row| {
+            layout.set_text(&row.label);
+            layout.pixel_size().0
+        })
+        .max()
+        .unwrap_or(0) as f64;
+
+    for row in rows {
+        for (x, text, dimmed) in [(10.0, row.label.as_str(), false), (26.0 + label_width, row.time.as_str(), row.invalid)] {
+            layout.set_text(text);
+            cr.move_to(x, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.set_line_width(2.0);
+            cr.stroke_preserve().ok();
+            if dimmed {
+                set_text_muted(cr);
+            } else {
+                set_text_fill(cr);
+            }
+            cr.fill().ok();
+        }
+        y += 24.0;
+    }
+
+    y
+}
+
+// ============================================================================
+// Section Rendering Functions
+// ============================================================================
+// Each function renders a specific section of the widget and returns the
+// Y position after rendering (for vertical stacking).
+
+/// Render CPU, RAM, and GPU utilization bars.
+///
+/// Displays each enabled resource with:
+/// - Icon (CPU chip, RAM stick, GPU card)
+/// - Label text
+/// - Progress bar filled with its level color (see [`crate::config::MetricThresholds`])
+/// - Optional percentage text
+/// - With `temps_on_usage_bars`, the CPU/GPU temperature as a colored badge
+///
+/// # Layout
+///
+/// ```text
+/// Utilization
+/// [CPU icon] CPU: [████████░░░░] 75.2%
+/// [RAM icon] RAM: [██████░░░░░░] 52.1%
+/// [GPU icon] GPU: [██░░░░░░░░░░] 23.5%
+///
+/// [CPU icon] CPU: [██████░░] 75.2% (67°)   ← temperature badge
+/// ```
+///
+/// Each row, and each temperature badge, is added to `targets` for its
+/// hover tooltip.
+pub(super) fn render_utilization(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+
+    // Draw section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-utilization"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    y += 35.0;
+    if let Some(lines) = data.hardware_info {
+        y = render_hardware_info(cr, layout, y, lines);
+    }
+    cr.set_line_width(2.0);
+
+    // Temperatures on the bars only in merged mode, and only once known
+    let badge = |show: bool, temp: f32| {
+        (config.temps_on_usage_bars && show && temp > 0.0)
+            .then(|| (temp, ctx.level_color(ThresholdMetric::Temperature, temp)))
+    };
+    // Badges first, so they win over the row they sit on
+    let mut push_row = |target: HoverTarget, y: f64, badge: Option<(HoverTarget, (f64, f64, f64, f64))>| {
+        if let Some((badge_target, (x1, y1, x2, y2))) = badge {
+            targets.push((badge_target, x1, y1, x2, y2));
+        }
+        targets.push((target, 0.0, y - 5.0, COLUMN_WIDTH as f64, y + 25.0));
+    };
+
+    if config.show_cpu {
+        let value = config.show_percentages.then(|| format!("{:.1}%", data.cpu_usage));
+        let temp = badge(config.show_cpu_temp, data.cpu_temp);
+        let color = ctx.level_color(ThresholdMetric::Cpu, data.cpu_usage);
+        let badge_bounds = render_usage_row(cr, layout, y, draw_cpu_icon, &fl!("widget-cpu"), data.cpu_usage, color, value, temp);
+        push_row(HoverTarget::Cpu, y, badge_bounds.map(|bounds| (HoverTarget::CpuTemp, bounds)));
+        y += 30.0;
+        if config.show_cpu_frequency || config.show_cpu_governor {
+            targets.push((HoverTarget::CpuFrequency, 0.0, y - 5.0, COLUMN_WIDTH as f64, y + 13.0));
+            y = render_cpu_frequency(ctx, y);
+        }
+    }
+
+    if config.show_memory {
+        let value = memory_label_text(
+            config.memory_label,
+            config.show_percentages,
+            data.memory_used,
+            data.memory_total,
+            data.memory_usage,
+        );
+        let color = ctx.level_color(ThresholdMetric::Memory, data.memory_usage);
+        render_usage_row(cr, layout, y, draw_ram_icon, &fl!("widget-ram"), data.memory_usage, color, value, None);
+        push_row(HoverTarget::Memory, y, None);
+        y += 30.0;
+    }
+
+    if config.show_gpu {
+        let value = config.show_percentages.then(|| format!("{:.1}%", data.gpu_usage));
+        let temp = badge(config.show_gpu_temp, data.gpu_temp);
+        let color = ctx.level_color(ThresholdMetric::Gpu, data.gpu_usage);
+        let badge_bounds = render_usage_row(cr, layout, y, draw_gpu_icon, &fl!("widget-gpu"), data.gpu_usage, color, value, temp);
+        push_row(HoverTarget::Gpu, y, badge_bounds.map(|bounds| (HoverTarget::GpuTemp, bounds)));
+        y += 30.0;
+        if config.show_gpu_details {
+            y = render_gpu_details(cr, layout, y, data.gpu_details);
+        }
+        y = render_gpu_processes(cr, layout, y, data.gpu_processes);
+    }
+
+    if config.show_psi {
+        y = render_pressure(ctx, y);
+    }
+
+    y
+}
+
+/// Height of one hardware line under the Utilization header.
+pub(super) const HARDWARE_ROW_HEIGHT: f64 = 18.0;
+
+/// Draw the CPU model and GPU name under the Utilization header.
+///
+/// ```text
+/// Utilization
+/// AMD Ryzen 7 5800X · 8C/16T
+/// AMD Radeon RX 6800/6800 XT / 6900 XT
+/// [CPU] CPU: [=======     ]  45.0%
+/// ```
+///
+/// # Returns
+///
+/// Y position below the lines
+fn render_hardware_info(cr: &cairo::Context, layout: &pango::Layout, y: f64, lines: &[String]) -> f64 {
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+    // Long model names are ellipsized to the column
+    layout.set_width(((COLUMN_WIDTH as f64 - 20.0) * pango::SCALE as f64) as i32);
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+
+    let mut y = y - 8.0;
+    cr.set_line_width(2.0);
+    for line in lines {
+        layout.set_text(line);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += HARDWARE_ROW_HEIGHT;
+    }
+
+    layout.set_width(-1);
+    layout.set_ellipsize(pango::EllipsizeMode::None);
+    y + 8.0
+}
+
+/// Draw the package frequencies and governor under the CPU bar.
+///
+/// ```text
+/// [CPU] CPU: [=======     ]  45.0%
+///       3.42 GHz · 2.10 GHz      powersave
+/// ```
+///
+/// # Returns
+///
+/// Y position below the row
+fn render_cpu_frequency(ctx: &RenderContext, y: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+
+    let (left, right) = match data.cpu_frequency {
+        Some(frequency) => (
+            config.show_cpu_frequency.then(|| {
+                frequency.packages.iter().map(|mhz| format_ghz(*mhz)).collect::<Vec<_>>().join(" · ")
+            }),
+            frequency.governor.clone().filter(|_| config.show_cpu_governor),
+        ),
+        None => (Some(fl!("widget-cpu-frequency-unavailable")), None),
+    };
+
+    let mut texts = Vec::new();
+    if let Some(right) = right {
+        layout.set_text(&right);
+        texts.push((COLUMN_WIDTH as f64 - 10.0 - layout.pixel_size().0 as f64, right));
+    }
+    if let Some(left) = left {
+        texts.push((40.0, left));
+    }
+    for (x, text) in texts {
+        layout.set_text(&text);
+        cr.move_to(x, y - 4.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+    }
+
+    y + 18.0
+}
+
+/// Draw the fan speed and clocks under the GPU bar; values the driver
+/// doesn't report are left out.
+///
+/// ```text
+/// [GPU] GPU: [=======     ]  45.0%
+///       Fan 38% · Core 1845 MHz · Mem 7001 MHz
+/// ```
+///
+/// # Returns
+///
+/// Y position below the row
+fn render_gpu_details(cr: &cairo::Context, layout: &pango::Layout, y: f64, details: Option<&GpuDetails>) -> f64 {
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+
+    let text = match details.filter(|details| !details.is_empty()) {
+        Some(details) => {
+            let mut parts = Vec::new();
+            if let Some(fan) = details.fan_percent {
+                parts.push(fl!("widget-gpu-fan", percent = format!("{:.0}", fan)));
+            }
+            if let Some(core) = details.core_mhz {
+                parts.push(fl!("widget-gpu-core-clock", mhz = format!("{:.0}", core)));
+            }
+            if let Some(memory) = details.memory_mhz {
+                parts.push(fl!("widget-gpu-memory-clock", mhz = format!("{:.0}", memory)));
+            }
+            parts.join(" · ")
+        }
+        None => fl!("widget-gpu-details-unavailable"),
+    };
+
+    layout.set_text(&text);
+    cr.move_to(40.0, y - 4.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_muted(cr);
+    cr.fill().ok();
+
+    y + 18.0
+}
+
+/// Height of the pressure row at the bottom of the Utilization section.
+pub(super) const PSI_ROW_HEIGHT: f64 = 22.0;
+
+/// Draw the `some avg10` pressure of CPU, memory and I/O as three small
+/// bars, colored by how much work was stalled; unavailable resources get
+/// an empty bar and "–".
+///
+/// ```text
+/// [GPU] GPU: [=======     ]  45.0%
+/// CPU [▆    ]  2%   MEM [     ]  0%   IO [███  ] 15%
+/// ```
+///
+/// # Returns
+///
+/// Y position below the row
+fn render_pressure(ctx: &RenderContext, y: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+
+    let pressure = data.pressure.unwrap_or_default();
+    let cell_width = (COLUMN_WIDTH as f64 - 20.0) / 3.0;
+    let resources = [
+        (fl!("widget-psi-cpu"), pressure.cpu),
+        (fl!("widget-psi-memory"), pressure.memory),
+        (fl!("widget-psi-io"), pressure.io),
+    ];
+    for (index, (label, value)) in resources.into_iter().enumerate() {
+        let mut x = 10.0 + index as f64 * cell_width;
+
+        // Label
+        layout.set_text(&label);
+        cr.move_to(x, y - 4.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+        x += layout.pixel_size().0 as f64 + 6.0;
+
+        // Bar
+        let percent = value.unwrap_or(0.0);
+        draw_progress_bar(cr, x, y, 40.0, 8.0, percent, config.bar_colors.rgb(pressure_level(percent)));
+        x += 46.0;
+
+        // Value
+        let text = value.map(|percent| format!("{:.0}%", percent)).unwrap_or_else(|| String::from("–"));
+        layout.set_text(&text);
+        cr.move_to(x, y - 4.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+    }
+
+    y + PSI_ROW_HEIGHT
+}
+
+/// Draw one small row per busy GPU process under the GPU bar.
+///
+/// ```text
+/// [GPU] GPU: [=======     ]  45.0%
+///       python3 (5120)        31%
+///       Xorg (2214)           12%
+/// ```
+///
+/// # Returns
+///
+/// Y position below the rows
+fn render_gpu_processes(cr: &cairo::Context, layout: &pango::Layout, y_start: f64, processes: &[GpuProcess]) -> f64 {
+    let mut y = y_start;
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+
+    for process in processes {
+        let usage = format!("{:.0}%", process.usage);
+        layout.set_text(&usage);
+        let usage_x = COLUMN_WIDTH as f64 - 10.0 - layout.pixel_size().0 as f64;
+
+        // Name ellipsized to leave room for the usage
+        layout.set_width(((usage_x - 50.0) * pango::SCALE as f64) as i32);
+        layout.set_ellipsize(pango::EllipsizeMode::End);
+        for (x, text) in [(40.0, format!("{} ({})", process.name, process.pid)), (usage_x, usage)] {
+            layout.set_text(&text);
+            cr.move_to(x, y - 4.0);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.set_line_width(2.0);
+            cr.stroke_preserve().ok();
+            set_text_muted(cr);
+            cr.fill().ok();
+        }
+        layout.set_width(-1);
+        layout.set_ellipsize(pango::EllipsizeMode::None);
+        y += 18.0;
+    }
+
+    y
+}
+
+/// Draw one utilization row: icon, label, bar, value text and an optional
+/// temperature badge at the right edge.
+///
+/// The value sits at x = 300 like it always has; text too wide for that
+/// (used / total memory) or a badge moves it left and shortens the bar.
+///
+/// # Returns
+///
+/// Bounds of the temperature badge, if one was drawn
+#[allow(clippy::too_many_arguments)]
+fn render_usage_row(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y: f64,
+    draw_icon: fn(&cairo::Context, f64, f64, f64),
+    label: &str,
+    percentage: f32,
+    color: (f64, f64, f64),
+    value: Option<String>,
+    temp: Option<(f32, (f64, f64, f64))>,
+) -> Option<(f64, f64, f64, f64)> {
+    let icon_size = 20.0;
+    let bar_width = 200.0;
+    let bar_height = 12.0;
+
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+
+    draw_icon(cr, 10.0, y - 2.0, icon_size);
+
+    layout.set_text(&format!("{}:", label));
+    cr.move_to(10.0 + icon_size + 10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    // Right edge available to the value text, left of the badge
+    let mut right = COLUMN_WIDTH as f64 - 10.0;
+    let mut badge = None;
+    if let Some((temp, temp_color)) = temp {
+        let left = draw_temp_badge(cr, layout, right, y - 1.0, temp, temp_color);
+        badge = Some((left - 3.0, y - 5.0, right + 3.0, y + 20.0));
+        right = left - 6.0;
+        layout.set_font_description(Some(&font_desc));
+    }
+
+    let mut text_x = right;
+    if let Some(text) = &value {
+        layout.set_text(text);
+        let (text_width, _) = layout.pixel_size();
+        text_x = f64::min(300.0, right - text_width as f64);
+    }
+    draw_progress_bar(cr, 90.0, y, bar_width.min(text_x - 100.0), bar_height, percentage, color);
+
+    if let Some(text) = &value {
+        layout.set_text(text);
+        cr.move_to(text_x, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+    }
+
+    badge
+}
+
+/// Draw a temperature badge ("67°") ending at `right`, filled with the
+/// temperature's level color.
+///
+/// # Returns
+///
+/// Left edge of the badge
+fn draw_temp_badge(cr: &cairo::Context, layout: &pango::Layout, right: f64, y: f64, temp: f32, color: (f64, f64, f64)) -> f64 {
+    draw_badge(cr, layout, right, y, &format!("{:.0}°", temp), color)
+}
+
+/// Draw a pill with short text (a temperature, a count) ending at `right`,
+/// filled with `color`.
+///
+/// # Returns
+///
+/// Left edge of the badge
+fn draw_badge(cr: &cairo::Context, layout: &pango::Layout, right: f64, y: f64, text: &str, color: (f64, f64, f64)) -> f64 {
+    let font_desc = font(TextRole::Small, "Bold 10");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(text);
+    let (text_width, text_height) = layout.pixel_size();
+
+    let width = text_width as f64 + 10.0;
+    let height = text_height as f64 + 2.0;
+    let left = right - width;
+    let radius = height / 2.0;
+
+    let (r, g, b) = color;
+    cr.save().ok();
+    cr.new_sub_path();
+    cr.arc(right - radius, y + radius, radius, -std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
+    cr.arc(left + radius, y + radius, radius, std::f64::consts::FRAC_PI_2, 3.0 * std::f64::consts::FRAC_PI_2);
+    cr.close_path();
+    cr.set_source_rgb(r, g, b);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+    cr.restore().ok();
+
+    // Dark text on the light fill
+    cr.move_to(left + 5.0, y + 1.0);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(0.1, 0.1, 0.1);
+    cr.fill().ok();
+
+    left
+}
+
+/// Render temperature section (CPU and GPU temps).
+///
+/// Supports two display modes controlled by `use_circular_temp_display`:
+/// - **Circular**: Animated gauge rings with color-coded fill
+/// - **Text**: Simple text display "CPU: 45.2°C"
+///
+/// # Layout (Circular Mode)
+///
+/// ```text
+/// Temperatures
+///  ╭───╮  ╭───╮
+/// │ 45°│ │ 52°│
+///  ╰───╯  ╰───╯
+///   CPU    GPU
+/// ```
+///
+/// Each gauge or line is added to `targets` for its sensor tooltip. With
+/// the temperature graph on, it follows (or replaces) the gauges.
+pub(super) fn render_temperatures(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, .. } = *ctx;
+    let mut y = y_start;
+
+    // Draw section header
+    let font_desc = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&fl!("widget-temperatures"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0;
+
+    // Delegate to circular or text renderer based on settings
+    let replaced = config.shows_temp_graph() && config.temp_graph_mode == TempGraphMode::Replace;
+    if replaced {
+        // The graph below stands in for the gauges
+    } else if config.use_circular_temp_display {
+        y = render_circular_temps(ctx, y, targets);
+    } else {
+        y = render_text_temps(ctx, y, targets);
+    }
+
+    if config.shows_temp_graph() {
+        y = render_temp_graph(ctx, y);
+    }
+
+    if config.show_hottest_core || config.show_throttling {
+        y = render_thermal_row(ctx, y);
+    }
+
+    y
+}
+
+/// Height of the temperature graph, without the summary rows below it.
+pub(super) const TEMP_GRAPH_HEIGHT: f64 = 60.0;
+
+/// Height of one min/avg/max row under the temperature graph.
+pub(super) const TEMP_SUMMARY_ROW_HEIGHT: f64 = 18.0;
+
+/// Draw the CPU/GPU temperature history with its lowest and highest points
+/// marked, and a min/avg/max row per temperature below it.
+///
+/// The Y axis spans the range in the window plus a margin, rounded to
+/// 10°C, with the bounds written in the left corners:
+///
+/// ```text
+/// 90° ┌──────────────────────────────┐
+///     │          ▲max      ___       │  ← CPU (accent)
+///     │_____╱╲__╱  ╲_____╱   ╲_▼min__│  ← GPU (white)
+/// 40° └──────────────────────────────┘
+///     ━ CPU  min 45° · avg 58° · max 83°
+///     ━ GPU  min 41° · avg 50° · max 66°
+/// ```
+///
+/// # Returns
+///
+/// Y position below the summary rows
+fn render_temp_graph(ctx: &RenderContext, y_start: f64) -> f64 {
+    let RenderContext { cr, layout, config, theme, data } = *ctx;
+    let x = 10.0;
+    let width = COLUMN_WIDTH as f64 - 20.0;
+    let height = TEMP_GRAPH_HEIGHT;
+    let y = y_start;
+
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+    let mut series: Vec<(String, &History, (f64, f64, f64))> = Vec::new();
+    if config.show_cpu_temp {
+        series.push((fl!("widget-cpu"), data.cpu_temp_history, (accent_r, accent_g, accent_b)));
+    }
+    if config.show_gpu_temp {
+        series.push((fl!("widget-gpu"), data.gpu_temp_history, (1.0, 1.0, 1.0)));
+    }
+
+    // Dim background with a thin border, like the network graph
+    cr.save().ok();
+    cr.rectangle(x, y, width, height);
+    cr.set_source_rgba(0.2, 0.2, 0.2, 0.5);
+    cr.fill_preserve().ok();
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.4);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+
+    // Shared scale over all lines, at least 10°C tall so steady temps stay flat
+    let extremes: Vec<_> = series.iter().filter_map(|(_, history, _)| history.extremes()).collect();
+    let lowest = extremes.iter().map(|(low, _)| low.1).fold(f64::INFINITY, f64::min);
+    let highest = extremes.iter().map(|(_, high)| high.1).fold(f64::NEG_INFINITY, f64::max);
+    let (scale_min, scale_max) = if extremes.is_empty() {
+        (30.0, 90.0)
+    } else {
+        let scale_min = ((lowest - 5.0) / 10.0).floor().max(0.0) * 10.0;
+        let scale_max = (((highest + 5.0) / 10.0).ceil() * 10.0).max(scale_min + 10.0);
+        (scale_min, scale_max)
+    };
+    let newest = series.iter().filter_map(|(_, history, _)| history.newest()).max().unwrap_or(0);
+    let window_ms = data.cpu_temp_history.window_ms().max(1) as f64;
+    let point = |(timestamp, value): (u64, f64)| {
+        let age = newest.saturating_sub(timestamp) as f64;
+        (
+            x + width - width * (age / window_ms).min(1.0),
+            y + height - 1.0 - (height - 2.0) * ((value - scale_min) / (scale_max - scale_min)).clamp(0.0, 1.0),
+        )
+    };
+
+    for (_, history, (r, g, b)) in &series {
+        if !history.is_drawable() {
+            continue;
+        }
+        for (index, (px, py)) in history.samples().map(point).enumerate() {
+            if index == 0 {
+                cr.move_to(px, py);
+            } else {
+                cr.line_to(px, py);
+            }
+        }
+        cr.set_source_rgb(*r, *g, *b);
+        cr.set_line_width(1.5);
+        cr.stroke().ok();
+
+        // Lowest and highest points, ringed so they stand out on the line
+        if let Some((low, high)) = history.extremes() {
+            for sample in [low, high] {
+                let (px, py) = point(sample);
+                cr.arc(px, py, 3.0, 0.0, 2.0 * std::f64::consts::PI);
+                cr.set_source_rgb(*r, *g, *b);
+                cr.fill_preserve().ok();
+                cr.set_source_rgb(0.0, 0.0, 0.0);
+                cr.set_line_width(1.0);
+                cr.stroke().ok();
+            }
+        }
+    }
+    cr.restore().ok();
+
+    // Scale bounds inside the left corners and the summary rows, in a small
+    // font; the rows below keep drawing in the font they inherited
+    let previous_font = layout.font_description();
+    let font_desc = font(TextRole::Small, "9");
+    layout.set_font_description(Some(&font_desc));
+    let draw_text = |text: &str, text_x: f64, text_y: f64| {
+        layout.set_text(text);
+        cr.move_to(text_x, text_y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+    };
+    draw_text(&format!("{:.0}°", scale_max), x + 4.0, y + 2.0);
+    layout.set_text(&format!("{:.0}°", scale_min));
+    let (_, label_height) = layout.pixel_size();
+    draw_text(&format!("{:.0}°", scale_min), x + 4.0, y + height - label_height as f64 - 2.0);
+
+    let mut row_y = y + height + 6.0;
+    for (label, history, (r, g, b)) in &series {
+        // Swatch in the line's color
+        cr.rectangle(x + 5.0, row_y + 7.0, 12.0, 3.0);
+        cr.set_source_rgb(*r, *g, *b);
+        cr.fill().ok();
+
+        let text = match (history.extremes(), history.average()) {
+            (Some((low, high)), Some(average)) => fl!(
+                "widget-temp-graph-summary",
+                label = label.as_str(),
+                min = format!("{:.0}", low.1),
+                avg = format!("{:.0}", average),
+                max = format!("{:.0}", high.1)
+            ),
+            _ => format!("{}  …", label),
+        };
+        draw_text(&text, x + 23.0, row_y);
+        row_y += TEMP_SUMMARY_ROW_HEIGHT;
+    }
+    layout.set_font_description(previous_font.as_ref());
+
+    row_y + 4.0
+}
+
+/// Draw the hottest core and, while throttled, a badge on the right.
+///
+/// ```text
+///   Hottest: Core 3 · 92°C          [THROTTLING]
+/// ```
+///
+/// # Returns
+///
+/// Y position below the row
+fn render_thermal_row(ctx: &RenderContext, y: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    if config.show_throttling && data.throttling {
+        let color = config.bar_colors.rgb(Level::Critical);
+        draw_badge(cr, layout, COLUMN_WIDTH as f64 - 10.0, y + 2.0, &fl!("widget-throttling"), color);
+    }
+
+    if config.show_hottest_core {
+        let font_desc = font(TextRole::Body, "11");
+        layout.set_font_description(Some(&font_desc));
+        let text = match data.hottest_core {
+            Some(core) => fl!("widget-hottest-core", core = core.label.as_str(), temp = format!("{:.0}", core.temp)),
+            None => fl!("widget-hottest-core-unavailable"),
+        };
+        layout.set_text(&text);
+        cr.move_to(15.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        match data.hottest_core {
+            Some(core) => {
+                let (r, g, b) = ctx.level_color(ThresholdMetric::Temperature, core.temp);
+                cr.set_source_rgb(r, g, b);
+            }
+            None => set_text_muted(cr),
+        }
+        cr.fill().ok();
+    }
+
+    y + 22.0
+}
+
+/// Render circular temperature gauges side by side.
+///
+/// Draws hollow ring gauges that fill based on temperature (full at
+/// 100°C). The ring color follows the temperature thresholds, by default:
+/// - Green: < 50°C
+/// - Yellow: 50-80°C
+/// - Red: >= 80°C
+fn render_circular_temps(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let y = y_start;
+    let circle_radius = 25.0;
+    let circle_diameter = circle_radius * 2.0;
+    let spacing = 20.0;
+    let mut x_offset = 15.0;
+    let max_temp = 100.0;
+
+    if config.show_cpu_temp {
+        let color = ctx.level_color(ThresholdMetric::Temperature, data.cpu_temp);
+        draw_temp_circle(cr, x_offset, y, circle_radius, data.cpu_temp, max_temp, color);
+        targets.push((HoverTarget::CpuTemp, x_offset, y, x_offset + circle_diameter, y + circle_diameter + 20.0));
+
+        // Temperature value in center
+        let temp_text = if data.cpu_temp > 0.0 {
+            format!("{:.0}°", data.cpu_temp)
+        } else {
+            fl!("widget-not-available")
+        };
+        let font_desc = font(TextRole::Body, "Bold 12");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&temp_text);
+        let (text_width, text_height) = layout.pixel_size();
+        cr.move_to(
+            x_offset + circle_radius - text_width as f64 / 2.0,
+            y + circle_radius - text_height as f64 / 2.0
+        );
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // "CPU" label below circle
+        let label_font = font(TextRole::Small, "10");
+        layout.set_font_description(Some(&label_font));
+        layout.set_text(&fl!("widget-cpu"));
+        let (label_width, _) = layout.pixel_size();
+        cr.move_to(
+            x_offset + circle_radius - label_width as f64 / 2.0,
+            y + circle_diameter + 6.0
+        );
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        x_offset += circle_diameter + spacing;
+    }
+
+    if config.show_gpu_temp {
+        let color = ctx.level_color(ThresholdMetric::Temperature, data.gpu_temp);
+        draw_temp_circle(cr, x_offset, y, circle_radius, data.gpu_temp, max_temp, color);
+        targets.push((HoverTarget::GpuTemp, x_offset, y, x_offset + circle_diameter, y + circle_diameter + 20.0));
+
+        // Temperature value in center
+        let temp_text = if data.gpu_temp > 0.0 {
+            format!("{:.0}°", data.gpu_temp)
+        } else {
+            fl!("widget-not-available")
+        };
+        let font_desc = font(TextRole::Body, "Bold 12");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&temp_text);
+        let (text_width, text_height) = layout.pixel_size();
+        cr.move_to(
+            x_offset + circle_radius - text_width as f64 / 2.0,
+            y + circle_radius - text_height as f64 / 2.0
+        );
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // "GPU" label below circle
+        let label_font = font(TextRole::Small, "10");
+        layout.set_font_description(Some(&label_font));
+        layout.set_text(&fl!("widget-gpu"));
+        let (label_width, _) = layout.pixel_size();
+        cr.move_to(
+            x_offset + circle_radius - label_width as f64 / 2.0,
+            y + circle_diameter + 6.0
+        );
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+    }
+
+    y + circle_diameter + 15.0
+}
+
+/// Render text-based temperatures
+fn render_text_temps(
+    ctx: &RenderContext,
+    y_start: f64,
+    targets: &mut HoverTargetBounds,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+    let font_desc = font(TextRole::Body, "14");
+    layout.set_font_description(Some(&font_desc));
+
+    if config.show_cpu_temp {
+        if data.cpu_temp > 0.0 {
+            layout.set_text(&format!("  {}: {:.1}°C", fl!("widget-cpu"), data.cpu_temp));
+        } else {
+            layout.set_text(&format!("  {}: {}", fl!("widget-cpu"), fl!("widget-not-available")));
+        }
+        targets.push((HoverTarget::CpuTemp, 0.0, y, COLUMN_WIDTH as f64, y + 25.0));
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 25.0;
+    }
+
+    if config.show_gpu_temp {
+        if data.gpu_temp > 0.0 {
+            layout.set_text(&format!("  {}: {:.1}°C", fl!("widget-gpu"), data.gpu_temp));
+        } else {
+            layout.set_text(&format!("  {}: {}", fl!("widget-gpu"), fl!("widget-not-available")));
+        }
+        targets.push((HoverTarget::GpuTemp, 0.0, y, COLUMN_WIDTH as f64, y + 25.0));
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 25.0;
+    }
+
+    y
+}
+
+/// Render network stats
+pub(super) fn render_network(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    rx_rate: f64,
+    tx_rate: f64,
+    bits: bool,
+) -> f64 {
+    let mut y = y_start;
+
+    layout.set_text(&format!("{} ↓: {}", fl!("widget-network"), format_rate(rx_rate, bits)));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 25.0;
+
+    layout.set_text(&format!("{} ↑: {}", fl!("widget-network"), format_rate(tx_rate, bits)));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 25.0;
+
+    y
+}
+
+/// Height of the network graph, without the spacing below it.
+pub(super) const NETWORK_GRAPH_HEIGHT: f64 = 60.0;
+
+/// Render the download/upload history under the network rates.
+///
+/// Download is drawn in the accent color over a fading accent fill, upload
+/// as a white line on top. The Y axis scales to the highest rate in the
+/// window, which is written in the top-left corner:
+///
+/// ```text
+/// 12.4 MB/s ┌─────────────────────────────┐
+///           │        ╱╲         ___       │  ← upload (line)
+///           │▁▁▁▁▁▁▁╱▓▓╲▁▁▁▁▁▁▁╱▓▓▓╲▁▁▁▁▁▁│  ← download (filled)
+///           └─────────────────────────────┘
+///            -2 min                     now
+/// ```
+///
+/// # Returns
+///
+/// Y position below the graph
+pub(super) fn render_network_graph(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    rx_history: &History,
+    tx_history: &History,
+    bits: bool,
+    theme: &CosmicTheme,
+) -> f64 {
+    let x = 10.0;
+    let width = COLUMN_WIDTH as f64 - 20.0;
+    let height = NETWORK_GRAPH_HEIGHT;
+    let y = y_start;
+
+    // Dim background with a thin border, like the progress bars
+    cr.save().ok();
+    cr.rectangle(x, y, width, height);
+    cr.set_source_rgba(0.2, 0.2, 0.2, 0.5);
+    cr.fill_preserve().ok();
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.4);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+
+    // Both lines share one scale; at least 1 KB/s so idle links stay flat
+    let scale = rx_history.max().max(tx_history.max()).max(1024.0);
+    let newest = rx_history.newest().max(tx_history.newest()).unwrap_or(0);
+    let window_ms = rx_history.window_ms().max(1) as f64;
+    let point = |(timestamp, value): (u64, f64)| {
+        let age = newest.saturating_sub(timestamp) as f64;
+        (
+            x + width - width * (age / window_ms).min(1.0),
+            y + height - (height - 2.0) * (value / scale).clamp(0.0, 1.0),
+        )
+    };
+    let trace = |history: &History| {
+        for (index, (px, py)) in history.samples().map(point).enumerate() {
+            if index == 0 {
+                cr.move_to(px, py);
+            } else {
+                cr.line_to(px, py);
+            }
+        }
+    };
+
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+    if rx_history.is_drawable() {
+        // Fill under the download line, fading towards the bottom
+        trace(rx_history);
+        let (last_x, _) = rx_history.samples().last().map(point).unwrap_or((x, y));
+        let (first_x, _) = rx_history.samples().next().map(point).unwrap_or((x, y));
+        cr.line_to(last_x, y + height);
+        cr.line_to(first_x, y + height);
+        cr.close_path();
+        let gradient = cairo::LinearGradient::new(0.0, y, 0.0, y + height);
+        gradient.add_color_stop_rgba(0.0, accent_r, accent_g, accent_b, 0.6);
+        gradient.add_color_stop_rgba(1.0, accent_r, accent_g, accent_b, 0.05);
+        cr.set_source(&gradient).ok();
+        cr.fill().ok();
+
+        trace(rx_history);
+        cr.set_source_rgb(accent_r, accent_g, accent_b);
+        cr.set_line_width(1.5);
+        cr.stroke().ok();
+    }
+    if tx_history.is_drawable() {
+        trace(tx_history);
+        cr.set_source_rgba(1.0, 1.0, 1.0, 0.85);
+        cr.set_line_width(1.5);
+        cr.stroke().ok();
+    }
+    cr.restore().ok();
+
+    // Scale label inside the top-left corner, in a small font; the rows
+    // below keep drawing in the font they inherited
+    let previous_font = layout.font_description();
+    let font_desc = font(TextRole::Small, "9");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&format_rate(scale, bits));
+    cr.move_to(x + 4.0, y + 2.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    layout.set_font_description(previous_font.as_ref());
+
+    y + height + 10.0
+}
+
+/// Render disk stats
+pub(super) fn render_disk(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+) -> f64 {
+    let mut y = y_start;
+
+    layout.set_text(&format!("{}: 0.0 KB/s", fl!("widget-disk-read")));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 25.0;
+
+    layout.set_text(&format!("{}: 0.0 KB/s", fl!("widget-disk-write")));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 25.0;
+
+    y
+}
+
+/// Draw the active power profile as a button at the right of the Battery
+/// header.
+///
+/// ```text
+/// Battery                     [⚡ Balanced]
+/// ```
+///
+/// # Returns
+///
+/// Bounds of the button
+pub(super) fn render_power_profile(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y: f64,
+    profile: &str,
+    theme: &CosmicTheme,
+) -> (f64, f64, f64, f64) {
+    let (text_r, text_g, text_b) = theme.text_color();
+    let (panel_r, panel_g, panel_b, panel_a) = theme.panel_background();
+    let (border_r, border_g, border_b, border_a) = theme.border_color();
+
+    let font_desc = font(TextRole::Small, "Bold 9");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&format!("⚡ {}", profile_label(profile)));
+    let (text_width, text_height) = layout.pixel_size();
+
+    let button_width = text_width as f64 + 16.0;
+    let button_height = 18.0;
+    let button_x = COLUMN_WIDTH as f64 - 15.0 - button_width;
+    let button_y = y + 3.0;
+
+    cr.set_source_rgba(panel_r, panel_g, panel_b, panel_a);
+    cr.rectangle(button_x, button_y, button_width, button_height);
+    cr.fill().ok();
+    cr.set_source_rgba(border_r, border_g, border_b, border_a);
+    cr.set_line_width(1.0);
+    cr.rectangle(button_x, button_y, button_width, button_height);
+    cr.stroke().ok();
+
+    cr.move_to(button_x + 8.0, button_y + (button_height - text_height as f64) / 2.0);
+    pangocairo::functions::layout_path(cr, layout);
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    cr.set_source_rgb(text_r, text_g, text_b);
+    cr.fill().ok();
+
+    (button_x, button_y, button_x + button_width, button_y + button_height)
+}
+
+/// Temporary battery section placeholder until Solaar integration is implemented
+pub(super) fn render_battery_section(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    devices: &[BatteryDevice],
+    enable_solaar_integration: bool,
+) -> f64 {
+    let mut y = y_start;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-battery"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0;
+
+    // Simple text to indicate Solaar integration state
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+
+    if !enable_solaar_integration {
+        layout.set_text(&fl!("widget-solaar-disabled"));
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 25.0;
+        return y;
+    }
+
+    if devices.is_empty() {
+        layout.set_text(&fl!("widget-no-solaar-devices"));
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 25.0;
+        return y;
+    }
+
+    let icon_size = 24.0;
+
+    for device in devices {
+        // Draw device name
+        layout.set_text(&device.name);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        y += 28.0;
+
+        if !device.is_connected {
+            // Device is disconnected - show disconnected icon
+            draw_disconnected_icon(cr, 10.0, y - 2.0, icon_size);
+            
+            // Draw "Disconnected" text
+            layout.set_text(&fl!("widget-disconnected"));
+            cr.move_to(10.0 + icon_size + 8.0, y - 2.0);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_muted(cr);
+            cr.fill().ok();
+            
+            y += 38.0;
+        } else if device.is_loading {
+            // Device is connected but loading - show disconnected icon with "Connecting..." text
+            draw_disconnected_icon(cr, 10.0, y - 2.0, icon_size);
+            
+            // Draw "Connecting..." text
+            layout.set_text(&fl!("widget-connecting"));
+            cr.move_to(10.0 + icon_size + 8.0, y - 2.0);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_muted(cr);
+            cr.fill().ok();
+            
+            y += 38.0;
+        } else if let Some(level) = device.level {
+            // Check if device is charging (use lowercase and check for "recharging" or starts with "charging")
+            let is_charging = device.status.as_deref()
+                .map(|s| {
+                    let lower = s.to_lowercase();
+                    lower.starts_with("charging") || lower.starts_with("recharging")
+                })
+                .unwrap_or(false);
+            
+            // Draw vertical battery icon
+            draw_battery_icon(cr, 10.0, y - 2.0, icon_size, level);
+            
+            // If charging, draw a lightning bolt overlay
+            if is_charging {
+                draw_charging_indicator(cr, 10.0, y - 2.0, icon_size);
+            }
+
+            // Draw percentage text next to battery with charging indicator
+            let percentage_text = if is_charging {
+                format!("{}% ⚡", level)
+            } else {
+                format!("{}%", level)
+            };
+            layout.set_text(&percentage_text);
+            cr.move_to(10.0 + icon_size + 8.0, y - 2.0);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_fill(cr);
+            cr.fill().ok();
+
+            y += 38.0; // Increased spacing between devices
+        } else {
+            // No battery level available
+            layout.set_text(&format!("  {}: {}", fl!("widget-battery"), fl!("widget-not-available")));
+            cr.move_to(10.0, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_fill(cr);
+            cr.fill().ok();
+            y += 38.0; // Increased spacing between devices
+        }
+    }
+
+    y
+}
+
+/// Draw a vertical battery icon with fill level
+fn draw_battery_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, level: u8) {
+    let (r, g, b) = get_battery_color(level);
+    let body_height = size;
+    let body_width = size * 0.6;
+    let terminal_height = size * 0.1;
+    let terminal_width = body_width * 0.4;
+
+    // Battery terminal (small rectangle on top)
+    let terminal_x = x + (body_width - terminal_width) / 2.0;
+    cr.rectangle(terminal_x, y, terminal_width, terminal_height);
+    cr.set_source_rgb(0.6, 0.6, 0.6);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+
+    // Battery body (vertical rectangle)
+    let body_y = y + terminal_height;
+    cr.rectangle(x, body_y, body_width, body_height);
+    cr.set_source_rgb(0.2, 0.2, 0.2);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+
+    // Fill level indicator inside battery (from bottom up)
+    if level > 0 {
+        let fill_height = (body_height - 4.0) * (level as f64 / 100.0);
+        let fill_y = body_y + body_height - 2.0 - fill_height;
+        cr.rectangle(x + 2.0, fill_y, body_width - 4.0, fill_height);
+        cr.set_source_rgb(r, g, b);
+        cr.fill().ok();
+    }
+}
+
+/// Draw a disconnected/loading icon for battery devices
+fn draw_disconnected_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    // Draw a battery outline in gray with a slash through it
+    let body_height = size;
+    let body_width = size * 0.6;
+    let terminal_height = size * 0.1;
+    let terminal_width = body_width * 0.4;
+
+    // Battery terminal (gray)
+    let terminal_x = x + (body_width - terminal_width) / 2.0;
+    cr.rectangle(terminal_x, y, terminal_width, terminal_height);
+    cr.set_source_rgb(0.5, 0.5, 0.5);
+    cr.fill_preserve().ok();
+    cr.set_source_rgb(0.3, 0.3, 0.3);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+
+    // Battery body (gray outline, no fill)
+    let body_y = y + terminal_height;
+    cr.rectangle(x, body_y, body_width, body_height);
+    cr.set_source_rgb(0.5, 0.5, 0.5);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+
+    // Draw diagonal slash to indicate disconnected
+    cr.move_to(x, body_y);
+    cr.line_to(x + body_width, body_y + body_height);
+    cr.set_source_rgb(0.8, 0.3, 0.3);
+    cr.set_line_width(2.0);
+    cr.stroke().ok();
+}
+
+/// Draw a charging indicator (lightning bolt) overlay on battery icon
+fn draw_charging_indicator(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let body_width = size * 0.6;
+    let body_height = size;
+    let terminal_height = size * 0.1;
+    let body_y = y + terminal_height;
+
+    // Draw lightning bolt in center of battery
+    let bolt_x = x + body_width / 2.0;
+    let bolt_y = body_y + body_height * 0.2;
+    let bolt_height = body_height * 0.6;
+    let bolt_width = body_width * 0.4;
+
+    cr.save().ok();
+    cr.set_source_rgba(1.0, 1.0, 0.0, 0.9); // Yellow with slight transparency
+    cr.set_line_width(2.0);
+
+    // Draw lightning bolt shape
+    cr.move_to(bolt_x, bolt_y);
+    cr.line_to(bolt_x - bolt_width / 3.0, bolt_y + bolt_height / 2.0);
+    cr.line_to(bolt_x, bolt_y + bolt_height / 2.0);
+    cr.line_to(bolt_x - bolt_width / 3.0, bolt_y + bolt_height);
+    cr.stroke().ok();
+
+    cr.move_to(bolt_x, bolt_y + bolt_height / 2.0);
+    cr.line_to(bolt_x + bolt_width / 3.0, bolt_y);
+    cr.stroke().ok();
+
+    cr.restore().ok();
+}
+
+/// Get RGB color based on battery level
+fn get_battery_color(level: u8) -> (f64, f64, f64) {
+    if level > 60 {
+        (0.0, 0.8, 0.0) // Green
+    } else if level > 30 {
+        (1.0, 0.8, 0.0) // Yellow/Orange
+    } else if level > 15 {
+        (1.0, 0.5, 0.0) // Orange
+    } else {
+        (1.0, 0.0, 0.0) // Red
+    }
+}
+
+/// Render weather section
+pub(super) fn render_weather(
+    ctx: &RenderContext,
+    y_start: f64,
+) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-weather"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 40.0;  // More space after header to prevent icon overlap
+
+    // Draw weather icon (offset from left edge to prevent clipping)
+    let icon_size = 40.0;
+    draw_weather_icon(cr, 20.0, y, icon_size, data.weather_icon);
+
+    // Weather info to the right of icon
+    let info_x = 80.0;
+    let font_desc = font(TextRole::Body, "14");
+    layout.set_font_description(Some(&font_desc));
+
+    // Temperature
+    if !data.weather_temp.is_nan() {
+        layout.set_text(&format!("{:.1}°C", data.weather_temp));
+    } else {
+        layout.set_text(&fl!("widget-not-available"));
+    }
+    cr.move_to(info_x, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    // Description
+    layout.set_text(data.weather_desc);
+    cr.move_to(info_x, y + 20.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    // Location
+    let location_font = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&location_font));
+    layout.set_text(data.weather_location);
+    cr.move_to(info_x, y + 45.0);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_muted(cr);
+    cr.fill().ok();
+
+    // Status marker (restored from cache / stale while offline), dimmer than the location
+    if !data.weather_status_label.is_empty() {
+        let (location_width, _) = layout.pixel_size();
+        layout.set_text(&format!("· {}", data.weather_status_label));
+        cr.move_to(info_x + location_width as f64 + 6.0, y + 45.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        cr.set_source_rgb(0.55, 0.55, 0.55);
+        cr.fill().ok();
+    }
+
+    // Air quality badge, right-aligned on the temperature line
+    if let Some(air_quality) = data.air_quality {
+        let level = match air_quality.level {
+            AqiLevel::Good => Level::Normal,
+            AqiLevel::Moderate => Level::Warning,
+            AqiLevel::Unhealthy => Level::Critical,
+        };
+        let text = fl!("widget-aqi", index = air_quality.index.to_string(), level = air_quality.level.label());
+        draw_badge(cr, layout, COLUMN_WIDTH as f64 - 10.0, y + 2.0, &text, config.bar_colors.rgb(level));
+    }
+
+    y += 70.0;
+
+    // Detailed layout: two extra rows spanning the full width
+    if config.weather_detailed && !data.weather_temp.is_nan() {
+        let detail_font = font(TextRole::Body, "12");
+        layout.set_font_description(Some(&detail_font));
+
+        let detail_lines = [
+            fl!(
+                "widget-weather-feels-like",
+                temp = format!("{:.1}", data.weather_feels_like),
+                high = format!("{:.0}", data.weather_temp_max),
+                low = format!("{:.0}", data.weather_temp_min)
+            ),
+            fl!(
+                "widget-weather-humidity-wind",
+                humidity = data.weather_humidity.to_string(),
+                speed = format!("{:.1}", data.weather_wind_speed),
+                direction = wind_direction_label(data.weather_wind_deg)
+            ),
+        ];
+
+        for line in &detail_lines {
+            layout.set_text(line);
+            cr.move_to(20.0, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_fill(cr);
+            cr.fill().ok();
+            y += 25.0;
+        }
+    }
+
+    if config.weather_sun_times {
+        render_sun_row(ctx, y);
+        y += 25.0;
+    }
+
+    y // Return updated y position
+}
+
+/// Draw the sunrise/sunset row of the weather section.
+///
+/// ```text
+/// [↑] 05:12   [↓] 21:04   [◐] Waxing gibbous   ← moon only while the sun is down
+/// ```
+fn render_sun_row(ctx: &RenderContext, y: f64) {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    let pattern = if config.use_24hour_time { "%H:%M" } else { "%-I:%M %p" };
+    let format = |unix: i64| {
+        chrono::DateTime::from_timestamp(unix, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format(pattern).to_string())
+            .unwrap_or_default()
+    };
+
+    let mut x = 20.0;
+    let draw_text = |text: &str, x: f64, muted: bool| -> f64 {
+        layout.set_text(text);
+        cr.move_to(x, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.set_line_width(2.0);
+        cr.stroke_preserve().ok();
+        if muted {
+            set_text_muted(cr);
+        } else {
+            set_text_fill(cr);
+        }
+        cr.fill().ok();
+        x + layout.pixel_size().0 as f64
+    };
+    match data.weather_sun {
+        Some(SunTimes::Normal { sunrise, sunset }) => {
+            draw_sun_event_icon(cr, x, y - 2.0, 22.0, true);
+            x = draw_text(&format(sunrise), x + 26.0, false) + 16.0;
+            draw_sun_event_icon(cr, x, y - 2.0, 22.0, false);
+            x = draw_text(&format(sunset), x + 26.0, false) + 16.0;
+        }
+        Some(SunTimes::PolarDay) => x = draw_text(&fl!("widget-polar-day"), x, false) + 16.0,
+        Some(SunTimes::PolarNight) => x = draw_text(&fl!("widget-polar-night"), x, false) + 16.0,
+        None => x = draw_text(&fl!("widget-not-available"), x, true) + 16.0,
+    }
+
+    if let Some(phase) = data.weather_moon_phase {
+        draw_moon_icon(cr, x, y - 2.0, 22.0, phase);
+        draw_text(&moon_phase_name(phase), x + 26.0, false);
+    }
+}
+
+/// Render storage/disk usage section
+pub(super) fn render_storage(ctx: &RenderContext, y: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y;
+    let bar_width = 200.0;
+    let bar_height = 12.0;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-storage"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0; // Spacing after header
+
+    // Draw each disk
+    let font_desc = font(TextRole::Body, "12");
+    layout.set_font_description(Some(&font_desc));
+    cr.set_line_width(2.0);
+
+    let limits = space_limits(config);
+    for disk in data.disk_info {
+        // Low filesystems get a badge on the name line and a red bar
+        let low = low_space(disk, &limits);
+        let mut name_right = COLUMN_WIDTH as f64 - 10.0;
+        if let Some(low) = low {
+            let badge = badge_text(disk, low);
+            name_right = draw_badge(cr, layout, name_right, y + 2.0, &badge, config.bar_colors.rgb(Level::Critical)) - 10.0;
+            layout.set_font_description(Some(&font_desc));
+            cr.set_line_width(2.0);
+        }
+
+        // Draw disk name/mount point
+        layout.set_text(&disk.name);
+        layout.set_width(((name_right - 10.0) * pango::SCALE as f64) as i32);
+        layout.set_ellipsize(pango::EllipsizeMode::End);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        layout.set_width(-1);
+        layout.set_ellipsize(pango::EllipsizeMode::None);
+        y += 20.0; // Space between name and bar
+
+        // Draw progress bar (empty if loading, normal if ready)
+        let percentage = if disk.is_loading { 0.0 } else { disk.used_percentage };
+        let color = match low {
+            Some(_) => config.bar_colors.rgb(Level::Critical),
+            None => ctx.level_color(ThresholdMetric::Disk, percentage),
+        };
+        draw_progress_bar(cr, 10.0, y, bar_width, bar_height, percentage, color);
+
+        // Draw percentage if enabled
+        if config.show_percentages {
+            let percentage_text = if disk.is_loading {
+                fl!("widget-loading")
+            } else {
+                format!("{:.1}%", disk.used_percentage)
+            };
+            layout.set_text(&percentage_text);
+            cr.move_to(220.0, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            set_text_fill(cr);
+            cr.fill().ok();
+        }
+
+        y += 25.0; // Space after bar before next disk
+    }
+
+    render_drive_health(ctx, y)
+}
+
+/// Height of one drive health row under the filesystems.
+pub(super) const DRIVE_ROW_HEIGHT: f64 = 22.0;
+
+/// Draw one row per drive with its temperature and SMART verdict.
+///
+/// ```text
+/// nvme0n1 · Samsung SSD 980 PRO        41°C  PASSED
+/// sda · ST2000DM008                    35°C  No access
+/// ```
+///
+/// # Returns
+///
+/// Y position below the rows
+fn render_drive_health(ctx: &RenderContext, y_start: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+    let font_desc = font(TextRole::Small, "10");
+    layout.set_font_description(Some(&font_desc));
+    cr.set_line_width(2.0);
+
+    for drive in data.drive_health {
+        let (verdict, color) = match drive.verdict {
+            SmartVerdict::Passed => (fl!("widget-smart-passed"), Some(config.bar_colors.rgb(Level::Normal))),
+            SmartVerdict::Failing => (fl!("widget-smart-failing"), Some(config.bar_colors.rgb(Level::Critical))),
+            SmartVerdict::NoAccess => (fl!("widget-smart-no-access"), None),
+            SmartVerdict::Unknown => (String::new(), None),
+        };
+        let temperature = drive.temperature.map(|temp| format!("{:.0}°C", temp)).unwrap_or_default();
+
+        // Right-aligned verdict, the temperature left of it
+        let mut x = COLUMN_WIDTH as f64 - 10.0;
+        let mut texts = Vec::new();
+        for (text, color) in [(verdict, color), (temperature, None)] {
+            if text.is_empty() {
+                continue;
+            }
+            layout.set_text(&text);
+            x -= layout.pixel_size().0 as f64;
+            texts.push((x, text, color));
+            x -= 10.0;
+        }
+
+        // Name ellipsized to leave room for them
+        let name = match &drive.model {
+            Some(model) => format!("{} · {}", drive.name, model),
+            None => drive.name.clone(),
+        };
+        layout.set_width(((x - 20.0) * pango::SCALE as f64) as i32);
+        layout.set_ellipsize(pango::EllipsizeMode::End);
+        texts.push((10.0, name, None));
+
+        for (x, text, color) in texts {
+            layout.set_text(&text);
+            cr.move_to(x, y);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            match color {
+                Some((r, g, b)) => cr.set_source_rgb(r, g, b),
+                None => set_text_muted(cr),
+            }
+            cr.fill().ok();
+        }
+        layout.set_width(-1);
+        layout.set_ellipsize(pango::EllipsizeMode::None);
+        y += DRIVE_ROW_HEIGHT;
+    }
+
+    y
+}
+
+/// Height of one storage pool row.
+pub(super) const POOL_ROW_HEIGHT: f64 = 40.0;
+
+/// Render the storage pools section: one row per ZFS pool or btrfs
+/// filesystem, its health dot colored like the bars.
+///
+/// ```text
+/// Storage Pools
+/// ● tank                          27% of 3.6 TiB
+///   ZFS · Online · Scrubbed Oct 13, no errors
+/// ● data                          61% of 931.5 GiB
+///   btrfs · Degraded · Scrubbing 42%
+/// ```
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_storage_pools(ctx: &RenderContext, y_start: f64) -> f64 {
+    let RenderContext { cr, layout, config, data, .. } = *ctx;
+    let mut y = y_start;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-storage-pools"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0;
+
+    if data.storage_pools.is_empty() {
+        draw_row_label(cr, layout, 10.0, y, &fl!("widget-no-pools"));
+        return y + 25.0;
+    }
+
+    let font_desc = font(TextRole::Body, "12");
+    let detail_font = font(TextRole::Small, "10");
+    for pool in data.storage_pools {
+        let (health, level) = match pool.health {
+            PoolHealth::Online => (fl!("widget-pool-online"), Level::Normal),
+            PoolHealth::Degraded => (fl!("widget-pool-degraded"), Level::Warning),
+            PoolHealth::Faulted => (fl!("widget-pool-faulted"), Level::Critical),
+        };
+
+        // Health dot
+        let (r, g, b) = config.bar_colors.rgb(level);
+        cr.new_sub_path();
+        cr.arc(16.0, y + 9.0, 5.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.set_source_rgb(r, g, b);
+        cr.fill_preserve().ok();
+        cr.set_source_rgb(0.0, 0.0, 0.0);
+        cr.set_line_width(1.5);
+        cr.stroke().ok();
+        cr.set_line_width(2.0);
+
+        // Capacity, right-aligned, and the name ellipsized left of it
+        layout.set_font_description(Some(&font_desc));
+        let capacity = pool
+            .capacity
+            .filter(|(_, size)| *size > 0)
+            .map(|(used, size)| {
+                let percent = format!("{:.0}", used as f64 / size as f64 * 100.0);
+                fl!("widget-pool-capacity", percent = percent, size = format_capacity(size))
+            })
+            .unwrap_or_default();
+        layout.set_text(&capacity);
+        let capacity_x = COLUMN_WIDTH as f64 - 10.0 - layout.pixel_size().0 as f64;
+        cr.move_to(capacity_x, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        layout.set_text(&pool.name);
+        layout.set_width(((capacity_x - 38.0) * pango::SCALE as f64) as i32);
+        layout.set_ellipsize(pango::EllipsizeMode::End);
+        cr.move_to(28.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        // Kind, health and scrub below
+        let scrub = match &pool.scrub {
+            ScrubStatus::Never => fl!("widget-pool-scrub-never"),
+            ScrubStatus::Running(Some(done)) => fl!("widget-pool-scrub-running", percent = format!("{:.0}", done)),
+            ScrubStatus::Running(None) => fl!("widget-pool-scrubbing"),
+            ScrubStatus::Finished { errors, date } => {
+                let found = match *errors {
+                    0 => fl!("widget-pool-scrub-clean"),
+                    errors => fl!("widget-pool-scrub-errors", errors = errors.to_string()),
+                };
+                match date {
+                    Some(date) => fl!("widget-pool-scrubbed-on", date = date.clone(), found = found),
+                    None => fl!("widget-pool-scrubbed", found = found),
+                }
+            }
+            ScrubStatus::Canceled => fl!("widget-pool-scrub-canceled"),
+        };
+        layout.set_font_description(Some(&detail_font));
+        layout.set_width(((COLUMN_WIDTH as f64 - 38.0) * pango::SCALE as f64) as i32);
+        layout.set_text(&format!("{} · {} · {}", pool.kind.label(), health, scrub));
+        cr.move_to(28.0, y + 19.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+        layout.set_width(-1);
+        layout.set_ellipsize(pango::EllipsizeMode::None);
+
+        y += POOL_ROW_HEIGHT;
+    }
+
+    y
+}
+
+/// Height of one virtual machine row.
+pub(super) const VM_ROW_HEIGHT: f64 = 25.0;
+
+/// Render the virtual machines section: one row per running libvirt domain
+/// with its vCPUs and current memory.
+///
+/// ```text
+/// Virtual Machines
+/// router                        1 vCPU · 0.5 GiB
+/// win11                         4 vCPU · 8.0 GiB
+/// ```
+///
+/// # Returns
+///
+/// Y position below the section
+pub(super) fn render_vms(ctx: &RenderContext, y_start: f64) -> f64 {
+    let RenderContext { cr, layout, data, .. } = *ctx;
+    let mut y = y_start;
+
+    // Section header
+    let header_font = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&header_font));
+    layout.set_text(&fl!("widget-vms"));
+    cr.move_to(10.0, y);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    y += 35.0;
+
+    if data.vms.is_empty() {
+        draw_row_label(cr, layout, 10.0, y, &fl!("widget-no-vms"));
+        return y + 25.0;
+    }
+
+    let font_desc = font(TextRole::Body, "12");
+    for vm in data.vms {
+        // Allocation, right-aligned and muted, and the name ellipsized left of it
+        let vcpus = vm.vcpus.map(|count| fl!("widget-vm-vcpus", count = count.to_string())).unwrap_or_default();
+        let memory = vm.memory.map(format_gib).unwrap_or_default();
+        let allocation = [vcpus, memory].into_iter().filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" · ");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&allocation);
+        let allocation_x = COLUMN_WIDTH as f64 - 10.0 - layout.pixel_size().0 as f64;
+        cr.move_to(allocation_x, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+
+        layout.set_text(&vm.name);
+        layout.set_width(((allocation_x - 20.0) * pango::SCALE as f64) as i32);
+        layout.set_ellipsize(pango::EllipsizeMode::End);
+        cr.move_to(10.0, y);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        layout.set_width(-1);
+        layout.set_ellipsize(pango::EllipsizeMode::None);
+
+        y += VM_ROW_HEIGHT;
+    }
+
+    y
+}
+
+/// Render notifications section with theme-aware colors.
+///
+/// Uses the COSMIC theme for panel backgrounds and text colors. Each group
+/// shows up to 5 notifications, starting `scroll` notifications down (so
+/// groups with fewer left still show their oldest 5). Critical notifications
+/// get a red bar at their left edge. Bodies are drawn as far as `expansion`
+/// says (keyed "app_name:timestamp", missing = fully), sliding open and
+/// closed as the widget animates it.
+///
+/// Besides the X buttons ("app_name" and "app_name:timestamp"), the clear
+/// button bounds hold every notification row as "row:app_name:timestamp".
+///
+/// With a `do_not_disturb` state, a bell button (crossed out while DND is
+/// on) is drawn left of "Clear All" and its bounds are returned last.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_notifications(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    grouped_notifications: &[(String, Vec<Notification>)],
+    collapsed_groups: &std::collections::HashSet<String>,
+    expansion: &std::collections::HashMap<String, f64>,
+    scroll: usize,
+    do_not_disturb: Option<bool>,
+    theme: &CosmicTheme,
+) -> (f64, (f64, f64), Vec<(String, f64, f64)>, Vec<(String, f64, f64, f64, f64)>, Option<(f64, f64, f64, f64)>, Option<(f64, f64, f64, f64)>) {  
+    // Returns (new_y_pos, (section_y_start, section_y_end), group_bounds, clear_button_bounds, clear_all_bounds, dnd_bounds)
+
+    let section_start = y_start;
+    let mut y_pos = y_start;
+    let mut group_bounds = Vec::new();
+    let mut clear_button_bounds = Vec::new();
+    let mut clear_all_bounds = None;
+    let mut dnd_bounds = None;
+
+    // Get theme colors
+    let (text_r, text_g, text_b) = theme.text_color();
+    let (panel_r, panel_g, panel_b, panel_a) = theme.panel_background();
+    let (border_r, border_g, border_b, border_a) = theme.border_color();
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+
+    // Draw section header
+    let font_desc = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&fl!("widget-notifications"));
+
+    // Get header height for vertical alignment
+    let (_, header_height) = layout.pixel_size();
+
+    cr.move_to(10.0, y_pos);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    // Draw "Clear All" button aligned vertically with header
+    if !grouped_notifications.is_empty() {
+        let button_width = 70.0;
+        let button_height = 18.0;
+        let button_x = 285.0;
+        // Vertically center with header text
+        let button_y = y_pos + (header_height as f64 - button_height) / 2.0;
+
+        // Draw button background
+        cr.set_source_rgba(0.8, 0.2, 0.2, 0.7); // Red with transparency
+        cr.rectangle(button_x, button_y, button_width, button_height);
+        cr.fill().ok();
+
+        // Draw button border
+        cr.set_source_rgb(1.0, 0.3, 0.3); // Lighter red border
+        cr.set_line_width(1.0);
+        cr.rectangle(button_x, button_y, button_width, button_height);
+        cr.stroke().ok();
+
+        // Draw button text
+        let font_desc_small = font(TextRole::Small, "Bold 9");
+        layout.set_font_description(Some(&font_desc_small));
+        layout.set_text(&fl!("widget-clear-all"));
+
+        cr.move_to(button_x + 10.0, button_y + 3.0);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+
+        clear_all_bounds = Some((button_x, button_y, button_x + button_width, button_y + button_height));
+    }
+
+    // Do-not-disturb bell, left of "Clear All"
+    if let Some(enabled) = do_not_disturb {
+        let button_size = 18.0;
+        let button_x = 259.0;
+        let button_y = y_pos + (header_height as f64 - button_size) / 2.0;
+
+        if enabled {
+            cr.set_source_rgba(accent_r, accent_g, accent_b, 0.7);
+        } else {
+            cr.set_source_rgba(panel_r, panel_g, panel_b, panel_a);
+        }
+        cr.rectangle(button_x, button_y, button_size, button_size);
+        cr.fill().ok();
+        cr.set_source_rgba(border_r, border_g, border_b, border_a);
+        cr.set_line_width(1.0);
+        cr.rectangle(button_x, button_y, button_size, button_size);
+        cr.stroke().ok();
+
+        cr.set_source_rgb(text_r, text_g, text_b);
+        draw_bell_icon(cr, button_x + 2.0, button_y + 2.0, button_size - 4.0, enabled);
+
+        dnd_bounds = Some((button_x, button_y, button_x + button_size, button_y + button_size));
+    }
+
+    y_pos += 35.0; // More space after header before groups
+
+    // Render each notification group
+    if grouped_notifications.is_empty() {
+        // Show "No notifications" message
+        let font_desc = font(TextRole::Body, "Italic 11");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&fl!("widget-no-notifications"));
+
+        cr.move_to(15.0, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+
+        y_pos += 25.0;
+    } else {
+        // Render each pre-grouped notification group (already sorted)
+        for (app_name, group_notifs) in grouped_notifications.iter() {
+            let group_y_start = y_pos;
+            let is_collapsed = collapsed_groups.contains(app_name);
+            let first = scroll.min(group_notifs.len().saturating_sub(5));
+            // Height of the shown part of a notification's body
+            let body_height = |notification: &Notification| {
+                if notification.body.is_empty() {
+                    return 0.0;
+                }
+                let key = format!("{}:{}", app_name, notification.timestamp);
+                14.0 * expansion.get(&key).copied().unwrap_or(1.0).clamp(0.0, 1.0)
+            };
+            
+            // Calculate total height of this group for background
+            let mut temp_y = y_pos + 22.0; // Header height
+            if !is_collapsed {
+                for notification in group_notifs.iter().skip(first).take(5) {
+                    temp_y += 20.0; // Summary line with X button
+                    temp_y += body_height(notification); // Body
+                    temp_y += 4.0; // Spacing
+                }
+            }
+            let group_height = temp_y - group_y_start;
+            
+            // Draw semi-transparent background for the group (theme-aware)
+            cr.set_source_rgba(panel_r, panel_g, panel_b, panel_a);
+            cr.rectangle(10.0, group_y_start - 8.0, 360.0, group_height + 16.0);
+            cr.fill().ok();
+            
+            // Draw border around the group (theme-aware)
+            cr.set_source_rgba(border_r, border_g, border_b, border_a);
+            cr.set_line_width(1.5);
+            cr.rectangle(10.0, group_y_start - 8.0, 360.0, group_height + 16.0);
+            cr.stroke().ok();
+            
+            // Draw group header (app name with count and expand/collapse indicator)
+            let font_desc_bold = font(TextRole::Body, "Bold 11");
+            layout.set_font_description(Some(&font_desc_bold));
+            
+            let indicator = if is_collapsed { "▶" } else { "▼" };
+            let header_text = format!("{} {} ({})", indicator, app_name, group_notifs.len());
+            layout.set_text(&header_text);
+            
+            cr.move_to(15.0, y_pos);
+            pangocairo::functions::layout_path(cr, layout);
+            set_text_outline(cr);
+            cr.stroke_preserve().ok();
+            // Use accent color for app name header
+            cr.set_source_rgb(accent_r * 1.2, accent_g * 1.2, accent_b * 1.2); // Slightly brighter accent
+            cr.fill().ok();
+            
+            // Draw X button to clear this group
+            let x_button_size = 14.0;
+            let x_button_x = 340.0; // Right side of the group
+            let x_button_y = y_pos;
+            
+            // Draw X button background circle
+            cr.set_source_rgba(0.8, 0.2, 0.2, 0.6); // Semi-transparent red
+            cr.arc(x_button_x, x_button_y + 7.0, x_button_size / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+            cr.fill().ok();
+            
+            // Draw X button border
+            cr.set_source_rgb(1.0, 0.3, 0.3); // Lighter red border
+            cr.set_line_width(1.0);
+            cr.arc(x_button_x, x_button_y + 7.0, x_button_size / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+            cr.stroke().ok();
+            
+            // Draw X symbol
+            let x_size = 4.0;
+            let x_center_x = x_button_x;
+            let x_center_y = y_pos + 7.0;
+            
+            cr.set_source_rgb(1.0, 1.0, 1.0); // White X
+            cr.set_line_width(1.5);
+            cr.move_to(x_center_x - x_size, x_center_y - x_size);
+            cr.line_to(x_center_x + x_size, x_center_y + x_size);
+            cr.stroke().ok();
+            
+            cr.move_to(x_center_x + x_size, x_center_y - x_size);
+            cr.line_to(x_center_x - x_size, x_center_y + x_size);
+            cr.stroke().ok();
+            
+            // Record X button bounds for click detection (group clear)
+            clear_button_bounds.push((
+                app_name.clone(),
+                x_button_x - x_button_size / 2.0,
+                x_button_y,
+                x_button_x + x_button_size / 2.0,
+                x_button_y + 14.0,
+            ));
+            
+            y_pos += 22.0;
+            let group_y_end = y_pos;
+            
+            // Record group header bounds for click detection
+            group_bounds.push((app_name.clone(), group_y_start, group_y_end));
+            
+            // If not collapsed, show notifications in this group
+            if !is_collapsed {
+                let font_desc = font(TextRole::Body, "11");
+                
+                for notification in group_notifs.iter().skip(first).take(5) {
+                    let row_y = y_pos;
+                    let body_height = body_height(notification);
+                    let row_height = 20.0 + body_height;
+                    
+                    // Red accent bar for critical notifications
+                    if notification.is_critical() {
+                        cr.set_source_rgb(0.9, 0.2, 0.2);
+                        cr.rectangle(17.0, row_y, 3.0, row_height - 2.0);
+                        cr.fill().ok();
+                    }
+                    
+                    // Summary text (indented)
+                    layout.set_font_description(Some(&font_desc));
+                    
+                    // Truncate summary if too long (leave room for X button)
+                    let summary = ellipsize(&notification.summary, 38, 35);
+                    layout.set_text(&summary);
+                    
+                    cr.move_to(25.0, y_pos); // Indent notifications
+                    pangocairo::functions::layout_path(cr, layout);
+                    set_text_outline(cr);
+                    cr.stroke_preserve().ok();
+                    set_text_fill(cr);
+                    cr.fill().ok();
+                    
+                    // Draw individual dismiss X button for this notification
+                    let notif_x_size = 10.0;
+                    let notif_x_x = 340.0;
+                    let notif_x_y = y_pos + 2.0;
+                    
+                    // Draw small X button background
+                    cr.set_source_rgba(0.6, 0.2, 0.2, 0.5); // Subtle red
+                    cr.arc(notif_x_x, notif_x_y + 5.0, notif_x_size / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+                    cr.fill().ok();
+                    
+                    // Draw X symbol (smaller)
+                    let nx_size = 3.0;
+                    cr.set_source_rgb(1.0, 1.0, 1.0);
+                    cr.set_line_width(1.0);
+                    cr.move_to(notif_x_x - nx_size, notif_x_y + 5.0 - nx_size);
+                    cr.line_to(notif_x_x + nx_size, notif_x_y + 5.0 + nx_size);
+                    cr.stroke().ok();
+                    cr.move_to(notif_x_x + nx_size, notif_x_y + 5.0 - nx_size);
+                    cr.line_to(notif_x_x - nx_size, notif_x_y + 5.0 + nx_size);
+                    cr.stroke().ok();
+                    
+                    // Record individual notification X button bounds
+                    // Format: "app_name:timestamp" to identify the specific notification
+                    let notif_id = format!("{}:{}", app_name, notification.timestamp);
+                    clear_button_bounds.push((
+                        notif_id,
+                        notif_x_x - notif_x_size / 2.0,
+                        notif_x_y,
+                        notif_x_x + notif_x_size / 2.0,
+                        notif_x_y + notif_x_size,
+                    ));
+                    
+                    y_pos += 20.0;
+                    
+                    // Body text (if present and not too long), cut off and
+                    // faded while it slides open
+                    if body_height > 0.0 {
+                        let body = ellipsize(&notification.body, 45, 42);
+                        let shown = body_height / 14.0;
+                        
+                        let font_desc_small = font(TextRole::Small, "9");
+                        layout.set_font_description(Some(&font_desc_small));
+                        layout.set_text(&body);
+                        
+                        cr.save().ok();
+                        cr.rectangle(20.0, y_pos - 2.0, 320.0, body_height + 2.0);
+                        cr.clip();
+                        cr.move_to(25.0, y_pos); // Indent body text
+                        pangocairo::functions::layout_path(cr, layout);
+                        let palette = TextPalette::active();
+                        let (outline_r, outline_g, outline_b) = palette.outline;
+                        cr.set_source_rgba(outline_r, outline_g, outline_b, shown);
+                        cr.stroke_preserve().ok();
+                        let (muted_r, muted_g, muted_b) = palette.muted;
+                        cr.set_source_rgba(muted_r, muted_g, muted_b, shown); // Secondary color for body
+                        cr.fill().ok();
+                        cr.restore().ok();
+                        
+                        y_pos += body_height;
+                    }
+                    
+                    // The rest of the row: hovering shows the body, clicking
+                    // runs the default action (or keeps the body open)
+                    clear_button_bounds.push((
+                        format!("row:{}:{}", app_name, notification.timestamp),
+                        20.0,
+                        row_y,
+                        notif_x_x - notif_x_size,
+                        row_y + row_height,
+                    ));
+                    
+                    y_pos += 4.0; // Small space between notifications in group
+                }
+            }
+            
+            y_pos += 8.0; // Space between groups
+        }
+    }
+
+    y_pos += 10.0; // Section padding
+    (y_pos, (section_start, y_pos), group_bounds, clear_button_bounds, clear_all_bounds, dnd_bounds)
+}
+
+/// `text` cut to its first `keep` characters and "..." if it's longer than
+/// `max` characters. Counts characters, not bytes, so it never splits one.
+fn ellipsize(text: &str, max: usize, keep: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some(_) => {
+            let end = text.char_indices().nth(keep).map_or(text.len(), |(index, _)| index);
+            format!("{}...", &text[..end])
+        }
+        None => text.to_string(),
+    }
+}
+
+/// Draw a bell in the `size` square at (x, y) with the current source
+/// color, crossed out if `muted`.
+fn draw_bell_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, muted: bool) {
+    let center = x + size / 2.0;
+    let (top, bottom) = (y + size * 0.15, y + size * 0.75);
+    let radius = size * 0.25;
+
+    // Dome and flared sides
+    cr.move_to(x + size * 0.12, bottom);
+    cr.curve_to(center - radius, bottom - size * 0.15, center - radius, top + radius, center - radius, top + radius);
+    cr.arc(center, top + radius, radius, std::f64::consts::PI, 0.0);
+    cr.curve_to(center + radius, top + radius, center + radius, bottom - size * 0.15, x + size * 0.88, bottom);
+    cr.close_path();
+    cr.fill().ok();
+
+    // Clapper
+    cr.arc(center, bottom + size * 0.1, size * 0.1, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill().ok();
+
+    if muted {
+        cr.set_line_width(1.8);
+        cr.move_to(x, y + size);
+        cr.line_to(x + size, y);
+        cr.stroke().ok();
+    }
+}
+
+/// Height of the lyrics row in the media panel.
+pub(super) const LYRICS_ROW_HEIGHT: f64 = 18.0;
+
+/// Render media player section with theme-aware colors.
+///
+/// Uses the COSMIC theme accent color for the progress bar and play button.
+/// Displays album artwork if available, alongside track info and controls.
+/// Shows pagination dots when multiple players are available. With a
+/// `marquee`, a title too long for the panel scrolls instead of being cut off.
+/// With `show_lyrics`, a row under the track info shows `lyrics_line`.
+/// Returns (y_position, button_bounds, title_scrolls) where button_bounds is Vec<(button_name, x_start, y_start, x_end, y_end)>
+pub(super) fn render_media(
+    cr: &cairo::Context,
+    layout: &pango::Layout,
+    y_start: f64,
+    media_info: &MediaInfo,
+    marquee: Option<&Marquee>,
+    show_lyrics: bool,
+    lyrics_line: Option<&str>,
+    theme: &CosmicTheme,
+    player_count: usize,
+    current_player_index: usize,
+) -> (f64, MediaButtonBounds, bool) {
+    use super::media::{LoopMode, PlaybackStatus};
+
+    let mut y_pos = y_start;
+    let mut button_bounds: MediaButtonBounds = Vec::new();
+
+    // Get theme colors
+    let (panel_r, panel_g, panel_b, panel_a) = theme.panel_background();
+    let (border_r, border_g, border_b, border_a) = theme.border_color();
+    let (accent_r, accent_g, accent_b) = theme.accent_rgb();
+
+    // Draw section header
+    let font_desc = font(TextRole::Header, "Bold 14");
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&fl!("widget-now-playing"));
+
+    cr.move_to(10.0, y_pos);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+
+    y_pos += 28.0;  // More space after header
+
+    // Check if there's an active player
+    if !media_info.is_active() {
+        let font_desc = font(TextRole::Body, "Italic 11");
+        layout.set_font_description(Some(&font_desc));
+        layout.set_text(&fl!("widget-no-media"));
+
+        cr.move_to(15.0, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+
+        return (y_pos + 25.0, button_bounds, false);
+    }
+
+    // Draw background panel (theme-aware)
+    // Increase height if there are pagination dots
+    let mut base_panel_height = 145.0;  // Base panel height
+    if show_lyrics {
+        base_panel_height += LYRICS_ROW_HEIGHT;
+    }
+    let panel_height = if player_count > 1 {
+        base_panel_height + 36.0  // Extra space for pagination dots
+    } else {
+        base_panel_height
+    };
+    let panel_y = y_pos;
+    cr.set_source_rgba(panel_r, panel_g, panel_b, panel_a);
+    cr.rectangle(10.0, panel_y, 360.0, panel_height);
+    cr.fill().ok();
+
+    cr.set_source_rgba(border_r, border_g, border_b, border_a);
+    cr.set_line_width(1.5);
+    cr.rectangle(10.0, panel_y, 360.0, panel_height);
+    cr.stroke().ok();
+
+    // Content starts inside the panel with padding
+    y_pos += 10.0;
+
+    // Album art dimensions and position
+    let art_size = 64.0;
+    let art_x = 20.0;
+    let art_y = y_pos;
+    let has_art = media_info.album_art.is_some();
+
+    // Draw album art if available
+    if let Some(ref album_art) = media_info.album_art {
+        // Draw a background/border for the art
+        cr.set_source_rgba(0.2, 0.2, 0.2, 0.8);
+        cr.rectangle(art_x - 2.0, art_y - 2.0, art_size + 4.0, art_size + 4.0);
+        cr.fill().ok();
+
+        // Create an ImageSurface from the album art data
+        if album_art.width > 0 && album_art.height > 0 {
+            // Create a new surface and copy the pixel data
+            if let Ok(mut art_surface) = cairo::ImageSurface::create(
+                cairo::Format::ARgb32,
+                album_art.width as i32,
+                album_art.height as i32,
+            ) {
+                // Copy pixel data to the surface
+                if let Ok(mut data) = art_surface.data() {
+                    let src_len = album_art.data.len().min(data.len());
+                    data[..src_len].copy_from_slice(&album_art.data[..src_len]);
+                }
+                
+                // Scale and draw the art
+                cr.save().ok();
+                cr.translate(art_x, art_y);
+                let scale_x = art_size / album_art.width as f64;
+                let scale_y = art_size / album_art.height as f64;
+                cr.scale(scale_x, scale_y);
+                cr.set_source_surface(&art_surface, 0.0, 0.0).ok();
+                cr.paint().ok();
+                cr.restore().ok();
+            }
+        }
+
+        // Draw border around the art
+        cr.set_source_rgba(0.4, 0.4, 0.4, 0.8);
+        cr.set_line_width(1.0);
+        cr.rectangle(art_x, art_y, art_size, art_size);
+        cr.stroke().ok();
+    }
+
+    // Adjust text position based on whether we have artwork
+    let text_x = if has_art { art_x + art_size + 10.0 } else { 20.0 };
+    let max_title_chars = if has_art { 28 } else { 40 };
+    let max_artist_chars = if has_art { 33 } else { 45 };
+    let max_album_chars = if has_art { 38 } else { 50 };
+
+    // Draw track title
+    let font_desc_bold = font(TextRole::Body, "Bold 12");
+    layout.set_font_description(Some(&font_desc_bold));
+
+    let title_scrolls = if marquee.is_some() {
+        // Measured and clipped at the panel's inner edge
+        layout.set_text(&media_info.title);
+        draw_marquee_text(cr, layout, text_x, y_pos, 360.0 - text_x, marquee)
+    } else {
+        let title = if media_info.title.len() > max_title_chars {
+            format!("{}...", &media_info.title[..max_title_chars.saturating_sub(3)])
+        } else {
+            media_info.title.clone()
+        };
+        layout.set_text(&title);
+
+        cr.move_to(text_x, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_fill(cr);
+        cr.fill().ok();
+        false
+    };
+
+    // Draw artist
+    if !media_info.artist.is_empty() {
+        y_pos += 18.0;
+
+        let font_desc = font(TextRole::Body, "11");
+        layout.set_font_description(Some(&font_desc));
+
+        let artist = if media_info.artist.len() > max_artist_chars {
+            format!("{}...", &media_info.artist[..max_artist_chars.saturating_sub(3)])
+        } else {
+            media_info.artist.clone()
+        };
+        layout.set_text(&artist);
+
+        cr.move_to(text_x, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+    }
+
+    // Draw album (if present)
+    if !media_info.album.is_empty() {
+        y_pos += 16.0;
+
+        let font_desc_small = font(TextRole::Small, "Italic 10");
+        layout.set_font_description(Some(&font_desc_small));
+
+        let album = if media_info.album.len() > max_album_chars {
+            format!("{}...", &media_info.album[..max_album_chars.saturating_sub(3)])
+        } else {
+            media_info.album.clone()
+        };
+        layout.set_text(&album);
+
+        cr.move_to(text_x, y_pos);
+        pangocairo::functions::layout_path(cr, layout);
+        set_text_outline(cr);
+        cr.stroke_preserve().ok();
+        set_text_muted(cr);
+        cr.fill().ok();
+    }
+
+    // Draw progress bar (full width, positioned below both art and text)
+    // Reset y_pos to be below the album art if it was taller
+    let content_bottom = if has_art {
+        (art_y + art_size).max(y_pos + 16.0)
+    } else {
+        y_pos + 18.0
+    };
+    y_pos = content_bottom + 6.0;  // Space between album art and progress bar
+
+    // Current lyrics line, full width under the track info
+    if show_lyrics {
+        if let Some(line) = lyrics_line {
+            let font_desc = font(TextRole::Body, "11");
+            layout.set_font_description(Some(&font_desc));
+            layout.set_text(line);
+            draw_marquee_text(cr, layout, 20.0, y_pos, 340.0, None);
+        }
+        y_pos += LYRICS_ROW_HEIGHT;
+    }
+
+    let bar_x = 20.0;
+    let bar_width = 330.0;
+    let bar_height = 6.0;
+
+    // Background bar
+    cr.set_source_rgba(0.3, 0.3, 0.3, 0.8);
+    cr.rectangle(bar_x, y_pos, bar_width, bar_height);
+    cr.fill().ok();
+
+    // Progress fill (using theme accent color)
+    let progress = media_info.progress();
+    if progress > 0.0 {
+        cr.set_source_rgba(accent_r, accent_g, accent_b, 0.9);
+        cr.rectangle(bar_x, y_pos, bar_width * progress, bar_height);
+        cr.fill().ok();
+    }
+
+    // Progress bar border
+    cr.set_source_rgba(0.5, 0.5, 0.5, 0.8);
+    cr.set_line_width(1.0);
+    cr.rectangle(bar_x, y_pos, bar_width, bar_height);
+    cr.stroke().ok();
+
+    // Record progress bar bounds for seek interaction
+    // We use a slightly larger hit area for easier clicking
+    button_bounds.push(("progress_bar".to_string(), bar_x, y_pos - 4.0, bar_x + bar_width, y_pos + bar_height + 4.0));
+
+    // Draw time on left and player name on right (below progress bar)
+    y_pos += 10.0;
+    let font_desc_time = font(TextRole::Small, "9");
+    layout.set_font_description(Some(&font_desc_time));
+
+    let time_str = format!("{} / {}", media_info.position_str(), media_info.duration_str());
+    layout.set_text(&time_str);
+
+    cr.move_to(bar_x, y_pos);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_muted(cr);
+    cr.fill().ok();
+
+    // Draw player name on the right
+    layout.set_text(&media_info.player_name);
+    let (text_width, _) = layout.pixel_size();
+    cr.move_to(bar_x + bar_width - text_width as f64, y_pos);
+    pangocairo::functions::layout_path(cr, layout);
+    set_text_outline(cr);
+    cr.stroke_preserve().ok();
+    set_text_muted(cr);
+    cr.fill().ok();
+
+    // Draw playback controls (Previous, Play/Pause, Next) - centered below progress
+    y_pos += 16.0;
+    let button_size = 24.0;
+    let button_spacing = 20.0;
+    let total_controls_width = button_size * 3.0 + button_spacing * 2.0;
+    let controls_start_x = (370.0 - total_controls_width) / 2.0;
+
+    // Previous button (<<)
+    let prev_x = controls_start_x;
+    let prev_y = y_pos;
+
+    // Draw previous button background (hover effect area)
+    cr.set_source_rgba(0.3, 0.3, 0.4, 0.5);
+    cr.arc(prev_x + button_size / 2.0, prev_y + button_size / 2.0, button_size / 2.0 + 2.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill().ok();
+
+    // Draw previous icon (two triangles pointing left)
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    let tri_size = 8.0;
+    // First triangle
+    cr.move_to(prev_x + button_size / 2.0 - 2.0, prev_y + button_size / 2.0);
+    cr.line_to(prev_x + button_size / 2.0 + tri_size - 2.0, prev_y + button_size / 2.0 - tri_size);
+    cr.line_to(prev_x + button_size / 2.0 + tri_size - 2.0, prev_y + button_size / 2.0 + tri_size);
+    cr.close_path();
+    cr.fill().ok();
+    // Second triangle
+    cr.move_to(prev_x + button_size / 2.0 - tri_size - 2.0, prev_y + button_size / 2.0);
+    cr.line_to(prev_x + button_size / 2.0 - 2.0, prev_y + button_size / 2.0 - tri_size);
+    cr.line_to(prev_x + button_size / 2.0 - 2.0, prev_y + button_size / 2.0 + tri_size);
+    cr.close_path();
+    cr.fill().ok();
+
+    button_bounds.push(("previous".to_string(), prev_x - 2.0, prev_y - 2.0, prev_x + button_size + 2.0, prev_y + button_size + 2.0));
+
+    // Play/Pause button
+    let play_x = prev_x + button_size + button_spacing;
+    let play_y = y_pos;
+
+    // Draw play/pause button background (larger, highlighted with accent color)
+    cr.set_source_rgba(accent_r, accent_g, accent_b, 0.6);
+    cr.arc(play_x + button_size / 2.0, play_y + button_size / 2.0, button_size / 2.0 + 4.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill().ok();
+
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    match media_info.status {
+        PlaybackStatus::Playing => {
+            // Draw pause icon (two vertical bars)
+            let bar_width = 4.0;
+            let bar_height = 14.0;
+            let bar_y = play_y + (button_size - bar_height) / 2.0;
+            cr.rectangle(play_x + button_size / 2.0 - bar_width - 2.0, bar_y, bar_width, bar_height);
+            cr.fill().ok();
+            cr.rectangle(play_x + button_size / 2.0 + 2.0, bar_y, bar_width, bar_height);
+            cr.fill().ok();
+        }
+        PlaybackStatus::Paused | PlaybackStatus::Stopped => {
+            // Draw play icon (triangle)
+            let tri_size = 10.0;
+            cr.move_to(play_x + button_size / 2.0 - tri_size / 2.0, play_y + button_size / 2.0 - tri_size);
+            cr.line_to(play_x + button_size / 2.0 - tri_size / 2.0, play_y + button_size / 2.0 + tri_size);
+            cr.line_to(play_x + button_size / 2.0 + tri_size, play_y + button_size / 2.0);
+            cr.close_path();
+            cr.fill().ok();
+        }
+    }
+
+    button_bounds.push(("play_pause".to_string(), play_x - 4.0, play_y - 4.0, play_x + button_size + 4.0, play_y + button_size + 4.0));
+
+    // Next button (>>)
+    let next_x = play_x + button_size + button_spacing;
+    let next_y = y_pos;
+
+    // Draw next button background
+    cr.set_source_rgba(0.3, 0.3, 0.4, 0.5);
+    cr.arc(next_x + button_size / 2.0, next_y + button_size / 2.0, button_size / 2.0 + 2.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill().ok();
+
+    // Draw next icon (two triangles pointing right)
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    // First triangle
+    cr.move_to(next_x + button_size / 2.0 + 2.0, next_y + button_size / 2.0);
+    cr.line_to(next_x + button_size / 2.0 - tri_size + 2.0, next_y + button_size / 2.0 - tri_size);
+    cr.line_to(next_x + button_size / 2.0 - tri_size + 2.0, next_y + button_size / 2.0 + tri_size);
+    cr.close_path();
+    cr.fill().ok();
+    // Second triangle
+    cr.move_to(next_x + button_size / 2.0 + tri_size + 2.0, next_y + button_size / 2.0);
+    cr.line_to(next_x + button_size / 2.0 + 2.0, next_y + button_size / 2.0 - tri_size);
+    cr.line_to(next_x + button_size / 2.0 + 2.0, next_y + button_size / 2.0 + tri_size);
+    cr.close_path();
+    cr.fill().ok();
+
+    button_bounds.push(("next".to_string(), next_x - 2.0, next_y - 2.0, next_x + button_size + 2.0, next_y + button_size + 2.0));
+
+    // Shuffle (left of previous) and loop (right of next) toggles, only for
+    // players that support them; accent colored while on
+    let toggle_size = 20.0;
+    let toggle_y = y_pos + (button_size - toggle_size) / 2.0;
+    let toggle_color = |on: bool| if on { (accent_r, accent_g, accent_b) } else { (0.5, 0.5, 0.5) };
+
+    if let Some(shuffle) = media_info.shuffle {
+        let shuffle_x = prev_x - button_spacing - toggle_size;
+        let (r, g, b) = toggle_color(shuffle);
+        cr.set_source_rgb(r, g, b);
+        draw_shuffle_icon(cr, shuffle_x, toggle_y, toggle_size);
+        button_bounds.push(("shuffle".to_string(), shuffle_x - 2.0, toggle_y - 2.0, shuffle_x + toggle_size + 2.0, toggle_y + toggle_size + 2.0));
+    }
+
+    if let Some(loop_mode) = media_info.loop_mode {
+        let loop_x = next_x + button_size + button_spacing;
+        let (r, g, b) = toggle_color(loop_mode != LoopMode::None);
+        cr.set_source_rgb(r, g, b);
+        draw_loop_icon(cr, loop_x, toggle_y, toggle_size);
+        if loop_mode == LoopMode::Track {
+            // "1" badge for repeating the current track
+            let font_desc = font(TextRole::Small, "Bold 7");
+            layout.set_font_description(Some(&font_desc));
+            layout.set_text("1");
+            let (one_width, one_height) = layout.pixel_size();
+            cr.move_to(loop_x + (toggle_size - one_width as f64) / 2.0, toggle_y + (toggle_size - one_height as f64) / 2.0);
+            pangocairo::functions::show_layout(cr, layout);
+        }
+        button_bounds.push(("loop".to_string(), loop_x - 2.0, toggle_y - 2.0, loop_x + toggle_size + 2.0, toggle_y + toggle_size + 2.0));
+    }
+
+    // Volume bar at the right end of the controls row (click to set)
+    if let Some(volume) = media_info.volume {
+        let volume_width = 55.0;
+        let volume_height = 4.0;
+        let volume_x = bar_x + bar_width - volume_width;
+        let volume_y = y_pos + (button_size - volume_height) / 2.0;
+
+        cr.set_source_rgba(0.3, 0.3, 0.3, 0.8);
+        cr.rectangle(volume_x, volume_y, volume_width, volume_height);
+        cr.fill().ok();
+        cr.set_source_rgba(accent_r, accent_g, accent_b, 0.9);
+        cr.rectangle(volume_x, volume_y, volume_width * volume.clamp(0.0, 1.0), volume_height);
+        cr.fill().ok();
+
+        button_bounds.push(("volume".to_string(), volume_x, volume_y - 6.0, volume_x + volume_width, volume_y + volume_height + 6.0));
+    }
+
+    // Draw pagination dots if multiple players
+    if player_count > 1 {
+        y_pos += button_size + 24.0;  // Space between controls and dots
+
+        let dot_radius = 4.0;
+        let dot_spacing = 12.0;
+        let total_dots_width = (player_count as f64) * dot_spacing;
+        let dots_start_x = (370.0 - total_dots_width) / 2.0 + dot_radius;
+
+        for i in 0..player_count {
+            let dot_x = dots_start_x + (i as f64) * dot_spacing;
+            let dot_y = y_pos;
+            
+            if i == current_player_index {
+                // Active dot - filled with accent color
+                cr.set_source_rgba(accent_r, accent_g, accent_b, 1.0);
+            } else {
+                // Inactive dot - outline only
+                cr.set_source_rgba(0.5, 0.5, 0.5, 0.8);
+            }
+            
+            cr.arc(dot_x, dot_y, dot_radius, 0.0, 2.0 * std::f64::consts::PI);
+            
+            if i == current_player_index {
+                cr.fill().ok();
+            } else {
+                cr.set_line_width(1.5);
+                cr.stroke().ok();
+            }
+            
+            // Add clickable bounds for each dot
+            button_bounds.push((
+                format!("player_dot_{}", i),
+                dot_x - dot_radius - 4.0,
+                dot_y - dot_radius - 4.0,
+                dot_x + dot_radius + 4.0,
+                dot_y + dot_radius + 4.0,
+            ));
+        }
+    }
+
+    // Return position after the panel with some padding
+    (panel_y + panel_height + 15.0, button_bounds, title_scrolls)
+}
+
+/// Draw a shuffle icon (two crossing arrows) in the `size` square at (x, y)
+/// with the current source color.
+fn draw_shuffle_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let (left, right) = (x + size * 0.1, x + size * 0.8);
+    let (top, bottom) = (y + size * 0.3, y + size * 0.7);
+    let head = size * 0.18;
+
+    cr.set_line_width(1.8);
+    cr.move_to(left, top);
+    cr.line_to(right, bottom);
+    cr.move_to(left, bottom);
+    cr.line_to(right, top);
+    cr.stroke().ok();
+
+    // Arrow heads at the right ends
+    for end_y in [top, bottom] {
+        cr.move_to(right + head, end_y);
+        cr.line_to(right - head * 0.3, end_y - head);
+        cr.line_to(right - head * 0.3, end_y + head);
+        cr.close_path();
+        cr.fill().ok();
+    }
+}
+
+/// Draw a loop icon (rounded loop with an arrow on top) in the `size`
+/// square at (x, y) with the current source color.
+fn draw_loop_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    let (left, right) = (x + size * 0.15, x + size * 0.85);
+    let (top, bottom) = (y + size * 0.25, y + size * 0.75);
+    let radius = size * 0.15;
+    let head = size * 0.18;
+
+    cr.set_line_width(1.8);
+    cr.new_sub_path();
+    cr.arc(right - radius, top + radius, radius, -std::f64::consts::FRAC_PI_2, 0.0);
+    cr.arc(right - radius, bottom - radius, radius, 0.0, std::f64::consts::FRAC_PI_2);
+    cr.arc(left + radius, bottom - radius, radius, std::f64::consts::FRAC_PI_2, std::f64::consts::PI);
+    cr.arc(left + radius, top + radius, radius, std::f64::consts::PI, 3.0 * std::f64::consts::FRAC_PI_2);
+    cr.close_path();
+    cr.stroke().ok();
+
+    // Arrow head on the top edge, pointing right
+    let head_x = x + size * 0.55;
+    cr.move_to(head_x + head, top);
+    cr.line_to(head_x - head * 0.3, top - head);
+    cr.line_to(head_x - head * 0.3, top + head);
+    cr.close_path();
+    cr.fill().ok();
+}
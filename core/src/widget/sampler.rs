@@ -0,0 +1,546 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Sampler Thread and Interval-Aligned Sample Clock
+//!
+//! Monitor sampling used to piggyback on `draw()`: stats were refreshed
+//! whenever a redraw happened to run after the update interval had elapsed,
+//! so samples drifted and were unevenly spaced. [`SampleClock`] instead
+//! schedules samples on wall-clock multiples of the interval, independent of
+//! rendering, and stamps each sample with its nominal tick time.
+//!
+//! ```text
+//! interval = 1000 ms
+//!
+//! wall clock   12:00:00.000   12:00:01.000   12:00:02.000
+//!                   │              │              │
+//! samples           ●──────────────●──────────────●     (tick = aligned time)
+//! main loop    ─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─┬─   (~16 ms iterations)
+//! ```
+//!
+//! Missed ticks (e.g., after a stall or suspend) are skipped rather than
+//! replayed in a burst, and a backwards clock jump re-aligns immediately.
+//!
+//! ## Sampler Thread
+//!
+//! The [`Sampler`] owns the system monitors (utilization, temperatures,
+//! network, storage, batteries) on a thread of its own. On every tick it
+//! refreshes the enabled ones and sends a [`MetricsSnapshot`] to the render
+//! loop, so the render loop never touches sysinfo or the monitors' locks:
+//!
+//! ```text
+//!                 SamplerCommand (settings, sample now, restart)
+//! render loop ───────────────────────────────────────────────► sampler thread
+//!      ▲                                                            │ SampleClock tick
+//!      │                  MetricsSnapshot                           ▼
+//!      └────────────────────────────────────────────── update monitors, snapshot
+//! ```
+//!
+//! The thread stops once the [`Sampler`] is dropped (its channels close).
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::battery::{BatteryDevice, BatteryMonitor};
+use super::cpufreq::CpuFrequency;
+use super::diagnostics::InterfaceStatus;
+use super::gpu_card::GpuDetails;
+use super::gpu_processes::GpuProcess;
+use super::kernel_stats::{KernelStats, KernelStatsMonitor};
+use super::network::{InterfaceRate, NetworkMonitor};
+use super::psi::Pressure;
+use super::stats::StatsSnapshot;
+use super::storage::{DiskInfo, StorageMonitor};
+use super::temperature::{CoreReading, TemperatureMonitor};
+use super::utilization::UtilizationMonitor;
+use super::watchdog::{Heartbeat, WatchedMonitor};
+use crate::config::Config;
+
+/// Schedules samples on wall-clock multiples of an interval.
+#[derive(Debug, Clone)]
+pub struct SampleClock {
+    /// Sampling interval in milliseconds (at least 1)
+    interval_ms: u64,
+    /// Unix time (ms) of the next due tick; 0 = sample immediately
+    next_due_ms: u64,
+}
+
+impl SampleClock {
+    /// Create a clock that fires on the first poll, then on aligned ticks.
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            next_due_ms: 0,
+        }
+    }
+
+    /// Change the interval (e.g., after a config change) and re-align.
+    pub fn set_interval(&mut self, interval_ms: u64) {
+        let interval_ms = interval_ms.max(1);
+        if interval_ms != self.interval_ms {
+            self.interval_ms = interval_ms;
+            self.next_due_ms = 0;
+        }
+    }
+
+    /// Force a sample on the next poll (e.g., a section was just enabled).
+    pub fn request_now(&mut self) {
+        self.next_due_ms = 0;
+    }
+
+    /// Check whether a sample is due now.
+    ///
+    /// # Returns
+    ///
+    /// The sample's timestamp (Unix ms, aligned down to the interval) if a
+    /// sample should be taken, otherwise `None`.
+    pub fn poll(&mut self) -> Option<u64> {
+        self.poll_at(unix_now_ms())
+    }
+
+    /// [`poll`](Self::poll) with an explicit current time (Unix ms).
+    pub fn poll_at(&mut self, now_ms: u64) -> Option<u64> {
+        // Clock jumped backwards by more than an interval: re-align
+        if self.next_due_ms > now_ms + self.interval_ms {
+            self.next_due_ms = 0;
+        }
+
+        if now_ms < self.next_due_ms {
+            return None;
+        }
+
+        let tick = now_ms - now_ms % self.interval_ms;
+        self.next_due_ms = tick + self.interval_ms;
+        Some(tick)
+    }
+}
+
+/// Current Unix time in milliseconds.
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// Sampler Thread
+// ============================================================================
+
+/// How long the sampler thread waits for commands between clock polls.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Which monitors the sampler refreshes, and how often.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamplerSettings {
+    /// Sampling interval in milliseconds
+    pub interval_ms: u64,
+    /// CPU usage is shown
+    pub cpu: bool,
+    /// Memory usage is shown
+    pub memory: bool,
+    /// GPU usage is shown
+    pub gpu: bool,
+    /// The busiest GPU processes are listed
+    pub gpu_processes: bool,
+    /// The GPU fan speed and clocks are shown
+    pub gpu_details: bool,
+    /// The CPU frequency row is shown
+    pub cpu_frequency: bool,
+    /// The pressure row is shown
+    pub psi: bool,
+    /// The kernel statistics row is shown
+    pub kernel_stats: bool,
+    /// CPU temperature is shown
+    pub cpu_temp: bool,
+    /// GPU temperature is shown
+    pub gpu_temp: bool,
+    /// CPU throttling is detected
+    pub throttling: bool,
+    /// Network rates are shown
+    pub network: bool,
+    /// Disk usage is shown
+    pub storage: bool,
+    /// Peripheral batteries are queried (Solaar integration)
+    pub battery: bool,
+}
+
+/// Sampler settings for a configuration.
+pub fn sampler_settings(config: &Config) -> SamplerSettings {
+    SamplerSettings {
+        interval_ms: config.update_interval_ms,
+        cpu: config.show_cpu,
+        memory: config.show_memory,
+        gpu: config.show_gpu,
+        gpu_processes: config.show_gpu && config.show_gpu_processes,
+        gpu_details: config.show_gpu && config.show_gpu_details,
+        cpu_frequency: config.shows_cpu_frequency_row(),
+        psi: config.shows_psi_row(),
+        kernel_stats: config.show_kernel_stats,
+        cpu_temp: config.show_cpu_temp,
+        gpu_temp: config.show_gpu_temp,
+        throttling: config.shows_thermal_row() && config.show_throttling,
+        network: config.show_network,
+        storage: config.show_storage,
+        battery: config.show_battery && config.enable_solaar_integration,
+    }
+}
+
+/// Everything the render loop reads from the system monitors, taken at
+/// one tick.
+///
+/// Values of monitors that aren't enabled keep their last reading (or the
+/// default before the first one).
+#[derive(Default)]
+pub struct MetricsSnapshot {
+    /// Aligned sample time (Unix ms)
+    pub sample_ms: u64,
+
+    /// Whether CPU and memory usage hold a real sample yet
+    pub utilization_ready: bool,
+    /// CPU usage percentage (0-100)
+    pub cpu_usage: f32,
+    /// Usage percentage of each logical core
+    pub core_usage: Vec<f32>,
+    /// Package frequencies and governor (None while not shown)
+    pub cpu_frequency: Option<CpuFrequency>,
+    /// CPU, memory and I/O pressure (None while not shown)
+    pub pressure: Option<Pressure>,
+    /// Process and thread counts, context switches (None while not shown)
+    pub kernel_stats: Option<KernelStats>,
+    /// Memory usage percentage (0-100)
+    pub memory_usage: f32,
+    /// Used memory in bytes
+    pub memory_used: u64,
+    /// Total memory in bytes
+    pub memory_total: u64,
+    /// GPU usage percentage (0-100)
+    pub gpu_usage: f32,
+    /// Busiest GPU processes (empty while not listed)
+    pub gpu_processes: Vec<GpuProcess>,
+    /// GPU fan speed and clocks (None while not shown, or unreadable)
+    pub gpu_details: Option<GpuDetails>,
+    /// Detected GPU vendor ("AMD")
+    pub gpu_vendor: &'static str,
+    /// How GPU usage is read ("sysfs gpu_busy_percent")
+    pub gpu_method: &'static str,
+    /// DRM card of the GPU ("card1 (amdgpu)")
+    pub gpu_card: Option<String>,
+    /// Last GPU polling error
+    pub gpu_error: Option<String>,
+    /// Heartbeat of the GPU polling thread (for the watchdog)
+    pub gpu_heartbeat: Option<Heartbeat>,
+
+    /// Whether sensors have been read yet
+    pub temperatures_ready: bool,
+    /// CPU temperature in Celsius
+    pub cpu_temp: f32,
+    /// GPU temperature in Celsius
+    pub gpu_temp: f32,
+    /// Hottest per-core sensor
+    pub hottest_core: Option<CoreReading>,
+    /// Whether the CPU throttled since the previous sample
+    pub throttling: bool,
+    /// Missing-sensor error for the shown temperatures
+    pub temperature_error: Option<String>,
+    /// Labels of all hardware sensors (for diagnostics)
+    pub sensors: Vec<String>,
+    /// Sensor picked for the CPU temperature
+    pub cpu_sensor: Option<String>,
+    /// Sensor picked for the GPU temperature
+    pub gpu_sensor: Option<String>,
+
+    /// Whether the rates are based on a real delta yet
+    pub network_ready: bool,
+    /// Download rate in bytes per second
+    pub network_rx_rate: f64,
+    /// Upload rate in bytes per second
+    pub network_tx_rate: f64,
+    /// Rates of each interface (for the tooltip)
+    pub interface_rates: Vec<InterfaceRate>,
+    /// Every interface with its total traffic (for diagnostics)
+    pub network_interfaces: Vec<InterfaceStatus>,
+
+    /// Mounted disks
+    pub disks: Vec<DiskInfo>,
+    /// Heartbeat of the disk model lookup thread
+    pub storage_heartbeat: Option<Heartbeat>,
+
+    /// Peripheral batteries
+    pub batteries: Vec<BatteryDevice>,
+    /// Error from the last battery query
+    pub battery_error: Option<String>,
+    /// Heartbeat of the battery query thread
+    pub battery_heartbeat: Option<Heartbeat>,
+
+    /// The same readings for `--stats`-style publishing, filled in for the
+    /// enabled monitors only
+    pub stats: StatsSnapshot,
+}
+
+/// A request from the render loop to the sampler thread.
+enum SamplerCommand {
+    /// Follow a config change
+    Configure(SamplerSettings),
+    /// Sample on the next poll (e.g., a section was just enabled)
+    SampleNow,
+    /// Replace a stalled collector with a fresh instance
+    Restart(WatchedMonitor),
+}
+
+/// Samples the system monitors on a background thread.
+pub struct Sampler {
+    /// Requests to the sampler thread
+    commands: Sender<SamplerCommand>,
+    /// Snapshots from the sampler thread, one per tick
+    snapshots: Receiver<MetricsSnapshot>,
+    /// Stamped by the sampler thread on every poll (for the watchdog)
+    heartbeat: Heartbeat,
+}
+
+impl Sampler {
+    /// Create the monitors and start sampling them.
+    pub fn new(settings: SamplerSettings) -> Self {
+        let (commands, command_receiver) = mpsc::channel();
+        let (snapshot_sender, snapshots) = mpsc::channel();
+        let heartbeat = Heartbeat::new();
+        let thread_heartbeat = heartbeat.clone();
+
+        std::thread::spawn(move || {
+            let _exit_guard = thread_heartbeat.guard();
+            let mut monitors = Monitors::new(&settings);
+            let mut settings = settings;
+            let mut clock = SampleClock::new(settings.interval_ms);
+            loop {
+                match command_receiver.recv_timeout(POLL_INTERVAL) {
+                    Ok(SamplerCommand::Configure(new_settings)) => {
+                        monitors.configure(&new_settings);
+                        clock.set_interval(new_settings.interval_ms);
+                        settings = new_settings;
+                    }
+                    Ok(SamplerCommand::SampleNow) => clock.request_now(),
+                    Ok(SamplerCommand::Restart(monitor)) => monitors.restart(monitor),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    // The sampler was dropped
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                if let Some(sample_ms) = clock.poll() {
+                    monitors.update(&settings);
+                    if snapshot_sender.send(monitors.snapshot(sample_ms, &settings)).is_err() {
+                        break;
+                    }
+                }
+                thread_heartbeat.beat();
+            }
+            log::debug!("Sampler thread stopped");
+        });
+
+        Self { commands, snapshots, heartbeat }
+    }
+
+    /// Follow a config change.
+    pub fn set_settings(&self, settings: SamplerSettings) {
+        let _ = self.commands.send(SamplerCommand::Configure(settings));
+    }
+
+    /// Sample right away instead of waiting for the next tick.
+    pub fn sample_now(&self) {
+        let _ = self.commands.send(SamplerCommand::SampleNow);
+    }
+
+    /// Replace a stalled GPU, storage or battery collector.
+    pub fn restart(&self, monitor: WatchedMonitor) {
+        let _ = self.commands.send(SamplerCommand::Restart(monitor));
+    }
+
+    /// Newest snapshot that arrived since the last call, if any (non-blocking).
+    pub fn latest(&self) -> Option<MetricsSnapshot> {
+        self.snapshots.try_iter().last()
+    }
+
+    /// Heartbeat of the sampler thread (for the watchdog).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+}
+
+/// The monitors owned by the sampler thread.
+struct Monitors {
+    /// CPU, memory and GPU usage
+    utilization: UtilizationMonitor,
+    /// CPU and GPU temperatures, throttling
+    temperature: TemperatureMonitor,
+    /// Upload/download rates
+    network: NetworkMonitor,
+    /// Disk space of mounted filesystems
+    storage: StorageMonitor,
+    /// Solaar/HeadsetControl batteries
+    battery: BatteryMonitor,
+    /// Latest pressure reading (None while not shown)
+    pressure: Option<Pressure>,
+    /// Process/thread counts and context switch rate
+    kernel_stats: KernelStatsMonitor,
+}
+
+impl Monitors {
+    /// Create every monitor (spawns their background threads).
+    fn new(settings: &SamplerSettings) -> Self {
+        let mut monitors = Self {
+            utilization: UtilizationMonitor::new(),
+            temperature: TemperatureMonitor::new(),
+            network: NetworkMonitor::new(),
+            storage: StorageMonitor::new(),
+            battery: BatteryMonitor::new(),
+            pressure: None,
+            kernel_stats: KernelStatsMonitor::default(),
+        };
+        monitors.temperature.follow_gpu(&monitors.utilization);
+        monitors.configure(settings);
+        monitors
+    }
+
+    /// Turn optional readings on or off.
+    fn configure(&mut self, settings: &SamplerSettings) {
+        self.utilization.set_show_processes(settings.gpu_processes);
+        self.utilization.set_show_details(settings.gpu_details);
+        self.utilization.set_show_frequency(settings.cpu_frequency);
+        self.temperature.set_detect_throttling(settings.throttling);
+    }
+
+    /// Replace a stalled collector; the stuck thread is told to stop.
+    fn restart(&mut self, monitor: WatchedMonitor) {
+        match monitor {
+            // Only the GPU thread, CPU and memory sampling keep going
+            WatchedMonitor::Gpu => {
+                self.utilization.restart_gpu();
+                self.temperature.follow_gpu(&self.utilization);
+            }
+            WatchedMonitor::Storage => self.storage = StorageMonitor::new(),
+            WatchedMonitor::Battery => self.battery = BatteryMonitor::new(),
+            _ => {}
+        }
+    }
+
+    /// Refresh the enabled monitors.
+    fn update(&mut self, settings: &SamplerSettings) {
+        if settings.cpu || settings.memory || settings.gpu {
+            self.utilization.update();
+        }
+        if settings.cpu_temp || settings.gpu_temp {
+            self.temperature.update();
+        }
+        if settings.network {
+            self.network.update();
+        }
+        if settings.storage {
+            self.storage.update();
+        }
+        if settings.battery {
+            self.battery.update();
+        }
+        self.pressure = settings.psi.then(Pressure::read);
+        if settings.kernel_stats {
+            self.kernel_stats.update();
+        } else {
+            // Don't average the first rate over the time it was hidden
+            self.kernel_stats = KernelStatsMonitor::default();
+        }
+    }
+
+    /// Snapshot of the latest readings.
+    fn snapshot(&self, sample_ms: u64, settings: &SamplerSettings) -> MetricsSnapshot {
+        let utilization = &self.utilization;
+        let temperature = &self.temperature;
+        let (cpu_sensor, gpu_sensor) = temperature.matched_sensors();
+        let batteries = self.battery.devices();
+
+        let mut stats = StatsSnapshot::capture_at(sample_ms);
+        stats.set_utilization(utilization, settings.cpu, settings.memory, settings.gpu);
+        stats.set_temperatures(temperature, settings.cpu_temp, settings.gpu_temp);
+        if settings.network {
+            stats.set_network(&self.network);
+        }
+        if settings.storage {
+            stats.set_disks(&self.storage.disk_info);
+        }
+        if settings.battery {
+            stats.set_batteries(&batteries);
+        }
+
+        MetricsSnapshot {
+            sample_ms,
+            utilization_ready: utilization.has_sample(),
+            cpu_usage: utilization.cpu_usage,
+            core_usage: utilization.core_usage.clone(),
+            cpu_frequency: utilization.cpu_frequency.clone(),
+            pressure: self.pressure,
+            kernel_stats: self.kernel_stats.stats(),
+            memory_usage: utilization.memory_usage,
+            memory_used: utilization.memory_used,
+            memory_total: utilization.memory_total,
+            gpu_usage: utilization.get_gpu_usage(),
+            gpu_processes: if settings.gpu_processes { utilization.gpu_processes() } else { Vec::new() },
+            gpu_details: if settings.gpu_details { utilization.gpu_details() } else { None },
+            gpu_vendor: utilization.gpu_vendor_name(),
+            gpu_method: utilization.gpu_method(),
+            gpu_card: utilization.gpu_card().map(|card| card.description()),
+            gpu_error: utilization.gpu_error(),
+            gpu_heartbeat: utilization.gpu_heartbeat().cloned(),
+            temperatures_ready: temperature.has_sample(),
+            cpu_temp: temperature.cpu_temp,
+            gpu_temp: temperature.gpu_temp,
+            hottest_core: temperature.hottest_core.clone(),
+            throttling: temperature.throttling,
+            temperature_error: temperature.error(settings.cpu_temp, settings.gpu_temp),
+            sensors: temperature.sensor_labels(),
+            cpu_sensor,
+            gpu_sensor,
+            network_ready: self.network.has_sample(),
+            network_rx_rate: self.network.network_rx_rate,
+            network_tx_rate: self.network.network_tx_rate,
+            interface_rates: self.network.interface_rates.clone(),
+            network_interfaces: self.network.interfaces(),
+            disks: self.storage.disk_info.clone(),
+            storage_heartbeat: Some(self.storage.heartbeat().clone()),
+            batteries,
+            battery_error: self.battery.error(),
+            battery_heartbeat: Some(self.battery.heartbeat().clone()),
+            stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticks_align_to_interval() {
+        let mut clock = SampleClock::new(1000);
+
+        // First poll fires immediately, stamped with the aligned tick
+        assert_eq!(clock.poll_at(10_250), Some(10_000));
+        assert_eq!(clock.poll_at(10_900), None);
+        assert_eq!(clock.poll_at(11_016), Some(11_000));
+        assert_eq!(clock.poll_at(11_500), None);
+    }
+
+    #[test]
+    fn test_missed_ticks_are_skipped() {
+        let mut clock = SampleClock::new(1000);
+        assert_eq!(clock.poll_at(10_000), Some(10_000));
+
+        // Stalled for several intervals: one sample, not a burst
+        assert_eq!(clock.poll_at(15_300), Some(15_000));
+        assert_eq!(clock.poll_at(15_400), None);
+    }
+
+    #[test]
+    fn test_backwards_clock_jump_realigns() {
+        let mut clock = SampleClock::new(1000);
+        assert_eq!(clock.poll_at(50_000), Some(50_000));
+
+        // Clock set back a minute: sample right away instead of waiting
+        assert_eq!(clock.poll_at(40_100), Some(40_000));
+    }
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Schedules
+//!
+//! Daily time ranges ([`Schedule`]) that dim the widget or hide some of
+//! its sections, e.g. notifications and media at night or the weather
+//! during work hours:
+//!
+//! ```text
+//! 22:00 ──────────── 07:00   "Night": 40% opacity, hide Notifications, Media
+//!        09:00 ── 17:00      "Work":  hide Weather
+//! ```
+//!
+//! The main loop evaluates them next to the redraw timer
+//! ([`ScheduleEffect::at`]) and redraws when the effect changes. Ranges
+//! ending before they start run past midnight. Where ranges overlap the
+//! lowest opacity wins and their hidden sections add up. Monitors keep
+//! running for hidden sections, so they show current data right away when
+//! the range ends.
+
+use std::borrow::Cow;
+
+use chrono::NaiveTime;
+
+use crate::config::{Config, Schedule, WidgetSection};
+
+/// Lowest opacity a schedule can set, in percent (keeps the widget findable).
+pub const MIN_OPACITY: u32 = 10;
+
+/// What the schedules active right now do to the widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleEffect {
+    /// Opacity in percent (100 when no schedule dims the widget)
+    pub opacity: u32,
+    /// Sections left out of the layout
+    pub hidden_sections: Vec<WidgetSection>,
+}
+
+impl Default for ScheduleEffect {
+    fn default() -> Self {
+        Self {
+            opacity: 100,
+            hidden_sections: Vec::new(),
+        }
+    }
+}
+
+impl ScheduleEffect {
+    /// Combined effect of the schedules active at `time`.
+    pub fn at(schedules: &[Schedule], time: NaiveTime) -> Self {
+        let mut effect = Self::default();
+        for schedule in schedules.iter().filter(|schedule| is_active(schedule, time)) {
+            effect.opacity = effect.opacity.min(schedule.opacity.clamp(MIN_OPACITY, 100));
+            for section in &schedule.hidden_sections {
+                if !effect.hidden_sections.contains(section) {
+                    effect.hidden_sections.push(*section);
+                }
+            }
+        }
+        effect
+    }
+
+    /// Opacity factor (0.1 to 1.0) to multiply the widget's opacity with.
+    pub fn opacity_factor(&self) -> f64 {
+        f64::from(self.opacity) / 100.0
+    }
+
+    /// The configuration to lay the widget out with: `config` without the
+    /// hidden sections in its section order.
+    pub fn layout_config<'a>(&self, config: &'a Config) -> Cow<'a, Config> {
+        if self.hidden_sections.is_empty() {
+            return Cow::Borrowed(config);
+        }
+        let mut config = config.clone();
+        config.section_order.retain(|section| !self.hidden_sections.contains(section));
+        Cow::Owned(config)
+    }
+}
+
+/// Parse a schedule time ("22:00").
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+/// Whether `schedule` covers `time`.
+///
+/// The start is included and the end isn't; a range ending before it
+/// starts runs past midnight. Invalid or equal times never match.
+pub fn is_active(schedule: &Schedule, time: NaiveTime) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&schedule.start), parse_time(&schedule.end)) else {
+        return false;
+    };
+    if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(start: &str, end: &str, opacity: u32, hidden: &[WidgetSection]) -> Schedule {
+        Schedule {
+            start: start.to_string(),
+            end: end.to_string(),
+            opacity,
+            hidden_sections: hidden.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_schedule_effect() {
+        let time = |text: &str| parse_time(text).unwrap();
+        let schedules = [
+            schedule("22:00", "07:00", 40, &[WidgetSection::Notifications, WidgetSection::Media]),
+            schedule("06:00", "17:00", 100, &[WidgetSection::Weather, WidgetSection::Media]),
+            schedule("25:00", "07:00", 10, &[WidgetSection::Clock]),
+        ];
+
+        assert_eq!(ScheduleEffect::at(&schedules, time("12:00")).hidden_sections, [WidgetSection::Weather, WidgetSection::Media]);
+        assert_eq!(ScheduleEffect::at(&schedules, time("17:00")), ScheduleEffect::default());
+        assert_eq!(
+            ScheduleEffect::at(&schedules, time("06:30")),
+            ScheduleEffect {
+                opacity: 40,
+                hidden_sections: vec![WidgetSection::Notifications, WidgetSection::Media, WidgetSection::Weather],
+            }
+        );
+        assert!(is_active(&schedules[0], time("23:59")));
+        assert!(!is_active(&schedule("08:00", "08:00", 50, &[]), time("08:00")));
+    }
+}
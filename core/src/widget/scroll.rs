@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Scroll Wheel Steps
+//!
+//! Turns Wayland pointer axis events into whole scroll steps for the
+//! per-section scroll bindings ([`crate::config::ScrollAction`]).
+//!
+//! A mouse wheel reports one discrete step per notch. Touchpads only report
+//! continuous distances, which are added up until they make a full step, so
+//! a slow two-finger scroll moves one notification at a time instead of
+//! one per event.
+//!
+//! Positive steps are downwards (away from the user), as on Wayland.
+
+/// Scroll distance of one wheel notch, in surface units.
+const STEP_DISTANCE: f64 = 15.0;
+
+/// Adds up continuous scroll distances into whole steps.
+#[derive(Debug, Default)]
+pub struct ScrollSteps {
+    /// Distance scrolled since the last whole step
+    pending: f64,
+}
+
+impl ScrollSteps {
+    /// Steps of one vertical axis event (`discrete` notches, or
+    /// `absolute` distance when the device has no notches).
+    pub fn push(&mut self, discrete: i32, absolute: f64) -> i32 {
+        if discrete != 0 {
+            self.pending = 0.0;
+            return discrete;
+        }
+        // Changing direction drops what was scrolled the other way
+        if self.pending * absolute < 0.0 {
+            self.pending = 0.0;
+        }
+        self.pending += absolute;
+        let steps = (self.pending / STEP_DISTANCE).trunc();
+        self.pending -= steps * STEP_DISTANCE;
+        steps as i32
+    }
+
+    /// Forget a partial step (the pointer left the widget or scrolling
+    /// stopped).
+    pub fn reset(&mut self) {
+        self.pending = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_steps() {
+        let mut steps = ScrollSteps::default();
+        // Wheel notches pass through
+        assert_eq!(steps.push(-2, -30.0), -2);
+
+        // Touchpad: 10 + 10 makes one step with 5 left over
+        assert_eq!(steps.push(0, 10.0), 0);
+        assert_eq!(steps.push(0, 10.0), 1);
+        assert_eq!(steps.push(0, 10.0), 1);
+
+        // Reversing starts from zero
+        assert_eq!(steps.push(0, -10.0), 0);
+        assert_eq!(steps.push(0, -40.0), -3);
+    }
+}
@@ -0,0 +1,856 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Widget Sections
+//!
+//! Every entry of the section order is implemented by a [`Section`], which
+//! knows three things about itself:
+//!
+//! - **measure**: how tall it is for a configuration and content counts,
+//!   used by [`super::layout`] to size the surface and split columns
+//! - **visible**: whether it has anything to draw this frame
+//! - **render**: how to draw itself at x = 0 from a given y, returning
+//!   the y below it (or a "Loading…" placeholder until its monitor has a
+//!   first sample)
+//!
+//! ```text
+//! section_order ──section(kind)──► &dyn Section ──measure()──► layout (size, columns)
+//!                                        │
+//!                                        └──render(ctx, y)──► renderer (draw loop)
+//! ```
+//!
+//! The draw loop in `render_widget` no longer knows about individual
+//! sections: it asks [`section`] for each entry, skips invisible ones, and
+//! translates each one into its column. Adding a section means adding a
+//! `WidgetSection` variant and one impl here.
+
+use super::feeds::feed_settings;
+use super::layout::{ContentCounts, COLUMN_WIDTH, HEADER_HEIGHT, SECTION_SPACING};
+use super::renderer::{
+    render_audio, render_battery_section, render_caffeine, render_calendar_events, render_containers, render_custom_commands, render_datetime, render_disk, render_feeds, render_loading_placeholder, render_media,
+    render_network, render_network_graph, render_notifications, render_plugins, render_power_profile, render_storage, render_storage_pools, render_system, render_system_health, render_temperatures, render_ticker, render_utilization, render_vms,
+    render_weather, render_world_clocks, FrameData, MediaButtonBounds, DRIVE_ROW_HEIGHT, HARDWARE_ROW_HEIGHT, KERNEL_STATS_ROW_HEIGHT, LYRICS_ROW_HEIGHT, NETWORK_GRAPH_HEIGHT, POOL_ROW_HEIGHT, PSI_ROW_HEIGHT, TEMP_GRAPH_HEIGHT, TEMP_SUMMARY_ROW_HEIGHT, VM_ROW_HEIGHT,
+};
+use super::theme::CosmicTheme;
+use super::ticker::ticker_settings;
+use super::tooltip::{HoverTarget, HoverTargetBounds};
+use super::world_clock::{active_clocks, clock_label};
+use crate::config::{AmbientTarget, Config, TempGraphMode, WidgetSection};
+use crate::fl;
+
+// ============================================================================
+// Section Trait
+// ============================================================================
+
+/// Everything a section needs to draw itself.
+///
+/// What to show comes from `config` only, the same configuration
+/// [`Section::measure`] sizes the section for.
+pub struct RenderContext<'a> {
+    /// Cairo context, translated so the section's column starts at x = 0
+    pub cr: &'a cairo::Context,
+    /// Shared Pango layout for text
+    pub layout: &'a pango::Layout,
+    /// What to show and how (section flags, formats, colors)
+    pub config: &'a Config,
+    /// COSMIC desktop theme (accent color, corner radius, dark/light mode)
+    pub theme: &'a CosmicTheme,
+    /// Monitor readings and UI state of this frame
+    pub data: &'a FrameData<'a>,
+}
+
+/// Hit-test bounds produced by one section, in section-local coordinates.
+#[derive(Default)]
+pub struct SectionBounds {
+    /// Y range of the notification section
+    pub notifications: Option<(f64, f64)>,
+    /// Notification group headers (app_name, y_start, y_end)
+    pub notification_groups: Vec<(String, f64, f64)>,
+    /// Notification X buttons (id, x1, y1, x2, y2)
+    pub notification_clear: Vec<(String, f64, f64, f64, f64)>,
+    /// "Clear All" button
+    pub clear_all: Option<(f64, f64, f64, f64)>,
+    /// Media control buttons
+    pub media_buttons: MediaButtonBounds,
+    /// Metrics with a hover tooltip
+    pub hover_targets: HoverTargetBounds,
+    /// Whether a marquee is scrolling (the widget keeps drawing frames)
+    pub marquee_running: bool,
+}
+
+/// One measurable, renderable part of the widget.
+pub trait Section: Sync {
+    /// Height in pixels including the spacing above, 0 if hidden.
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32;
+
+    /// Whether the section has anything to draw this frame.
+    fn visible(&self, ctx: &RenderContext) -> bool;
+
+    /// Spacing drawn above the section (sections with a header get 10px).
+    fn spacing(&self) -> f64 {
+        10.0
+    }
+
+    /// Draw the section at x = 0, starting at `y`.
+    ///
+    /// # Returns
+    ///
+    /// Y position below the section. Interactive bounds are stored in `bounds`.
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64;
+
+    /// Draw a placeholder while the section's monitor has no first sample.
+    ///
+    /// Sections with a header override this to keep their header visible.
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, None)
+    }
+}
+
+/// The implementation of a section order entry.
+pub fn section(kind: WidgetSection) -> &'static dyn Section {
+    match kind {
+        WidgetSection::Clock => &ClockSection,
+        WidgetSection::Utilization => &UtilizationSection,
+        WidgetSection::Temperatures => &TemperaturesSection,
+        WidgetSection::Storage => &StorageSection,
+        WidgetSection::Battery => &BatterySection,
+        WidgetSection::Weather => &WeatherSection,
+        WidgetSection::Notifications => &NotificationsSection,
+        WidgetSection::Media => &MediaSection,
+        WidgetSection::Audio => &AudioSection,
+        WidgetSection::Custom => &CustomSection,
+        WidgetSection::SystemHealth => &SystemHealthSection,
+        WidgetSection::Feeds => &FeedsSection,
+        WidgetSection::Ticker => &TickerSection,
+        WidgetSection::Plugins => &PluginsSection,
+        WidgetSection::Caffeine => &CaffeineSection,
+        WidgetSection::StoragePools => &StoragePoolsSection,
+        WidgetSection::Containers => &ContainersSection,
+        WidgetSection::VirtualMachines => &VirtualMachinesSection,
+        WidgetSection::System => &SystemSection,
+        WidgetSection::Network => &NetworkSection,
+        WidgetSection::Disk => &DiskSection,
+    }
+}
+
+// ============================================================================
+// Clock & Date
+// ============================================================================
+
+/// Large clock, the date below it, upcoming events and the world clocks
+/// below that.
+struct ClockSection;
+
+impl Section for ClockSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        let mut height = 0;
+        if config.show_clock {
+            height += 70; // Large clock text
+        }
+        if config.show_date {
+            height += 35; // Date text below clock
+        }
+        let events = counts.events as u32;
+        height += events * 24; // One small row per upcoming event
+        let world_clocks = active_clocks(&config.world_clocks).count() as u32;
+        height += world_clocks * 24; // One small row per extra time zone
+        if config.show_clock || config.show_date || events > 0 || world_clocks > 0 {
+            height += 20; // Spacing after clock/date
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_clock || ctx.config.show_date || !ctx.data.calendar_events.is_empty() || !ctx.data.world_clocks.is_empty()
+    }
+
+    fn spacing(&self) -> f64 {
+        0.0
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        let (config, data) = (ctx.config, ctx.data);
+        // A scrolled-to world clock (left on local time if its zone is invalid)
+        let zone = data
+            .clock_zone
+            .and_then(|clock| clock.zone.trim().parse::<chrono_tz::Tz>().ok().map(|tz| (clock_label(clock), tz)));
+        let now = match &zone {
+            Some((_, tz)) => data.current_time.with_timezone(tz).fixed_offset(),
+            None => data.current_time.fixed_offset(),
+        };
+        let y = render_datetime(
+            ctx.cr,
+            ctx.layout,
+            y,
+            config.show_clock,
+            config.show_date,
+            config.use_24hour_time,
+            config.clock_pattern(),
+            config.date_pattern(),
+            data.locale,
+            &now,
+            zone.as_ref().map(|(label, _)| label.as_str()),
+            ctx.ambient_color().filter(|_| config.ambient_target == AmbientTarget::ClockOutline),
+        );
+        let y = render_calendar_events(ctx.cr, ctx.layout, y, data.calendar_events);
+        let y = render_world_clocks(ctx.cr, ctx.layout, y, data.world_clocks);
+        y + 20.0 // Spacing after datetime
+    }
+}
+
+// ============================================================================
+// Utilization
+// ============================================================================
+
+/// CPU, memory, and GPU usage bars.
+struct UtilizationSection;
+
+impl Section for UtilizationSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !(config.show_cpu || config.show_memory || config.show_gpu) {
+            return 0;
+        }
+        let mut height = HEADER_HEIGHT; // "Utilization" header
+        if config.show_hardware_info {
+            height += 2 * HARDWARE_ROW_HEIGHT as u32; // CPU and GPU lines under it
+        }
+        if config.show_cpu {
+            height += 30; // CPU bar + label
+            if config.show_cpu_frequency || config.show_cpu_governor {
+                height += 18; // Frequency/governor row under it
+            }
+        }
+        if config.show_memory {
+            height += 30; // RAM bar + label
+        }
+        if config.show_gpu {
+            height += 30; // GPU bar + label
+            if config.show_gpu_details {
+                height += 18; // Fan/clock row under it
+            }
+            height += counts.gpu_processes as u32 * 18; // Process rows under it
+        }
+        if config.show_psi {
+            height += PSI_ROW_HEIGHT as u32; // Pressure row at the bottom
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_cpu || ctx.config.show_memory || ctx.config.show_gpu
+    }
+
+    fn spacing(&self) -> f64 {
+        0.0
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        render_utilization(ctx, y, &mut bounds.hover_targets)
+    }
+
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, Some(&fl!("widget-utilization")))
+    }
+}
+
+// ============================================================================
+// Temperatures
+// ============================================================================
+
+/// CPU and GPU temperatures as gauges or text (hidden while they are drawn
+/// on the usage bars instead).
+struct TemperaturesSection;
+
+impl Section for TemperaturesSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !(config.show_cpu_temp || config.show_gpu_temp) || config.temps_on_usage_bars {
+            return 0;
+        }
+        let mut height = SECTION_SPACING + HEADER_HEIGHT; // "Temperatures" header
+        if config.shows_temp_graph() && config.temp_graph_mode == TempGraphMode::Replace {
+            // Only the graph below
+        } else if config.use_circular_temp_display {
+            // Circular gauges are larger
+            height += 60;
+        } else {
+            // Simple text display
+            if config.show_cpu_temp {
+                height += 25;
+            }
+            if config.show_gpu_temp {
+                height += 25;
+            }
+        }
+        if config.shows_temp_graph() {
+            // Graph, one min/avg/max row per temperature, and the spacing around them
+            let rows = config.show_cpu_temp as u32 + config.show_gpu_temp as u32;
+            height += TEMP_GRAPH_HEIGHT as u32 + 10 + rows * TEMP_SUMMARY_ROW_HEIGHT as u32;
+        }
+        if config.shows_thermal_row() {
+            // Hottest core / throttling row
+            height += 22;
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        (ctx.config.show_cpu_temp || ctx.config.show_gpu_temp) && !ctx.config.temps_on_usage_bars
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        render_temperatures(ctx, y, &mut bounds.hover_targets)
+    }
+
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, Some(&fl!("widget-temperatures")))
+    }
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+/// Usage bar per mounted disk.
+struct StorageSection;
+
+impl Section for StorageSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_storage || (counts.disks == 0 && counts.drives == 0) {
+            return 0;
+        }
+        // Each disk: name (20px) + bar (12px) + spacing (13px) = 45px
+        SECTION_SPACING + HEADER_HEIGHT + counts.disks as u32 * 45
+            + counts.drives as u32 * DRIVE_ROW_HEIGHT as u32 // Drive health rows under them
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_storage
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_storage(ctx, y)
+    }
+}
+
+// ============================================================================
+// Storage Pools
+// ============================================================================
+
+/// One row per ZFS pool or btrfs filesystem with a health dot.
+struct StoragePoolsSection;
+
+impl Section for StoragePoolsSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_storage_pools {
+            return 0;
+        }
+        // One row per pool, or the "No pools" placeholder
+        let rows = if counts.pools > 0 { counts.pools as u32 * POOL_ROW_HEIGHT as u32 } else { 25 };
+        SECTION_SPACING + HEADER_HEIGHT + rows
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_storage_pools
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_storage_pools(ctx, y)
+    }
+
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, Some(&fl!("widget-storage-pools")))
+    }
+}
+
+// ============================================================================
+// Battery
+// ============================================================================
+
+/// System battery and peripheral devices.
+struct BatterySection;
+
+impl Section for BatterySection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_battery {
+            return 0;
+        }
+        let height = SECTION_SPACING + HEADER_HEIGHT; // "Battery" header
+        if counts.batteries > 0 {
+            // Each device: name (28px) + icon/percentage (38px) = 66px
+            height + counts.batteries as u32 * 66
+        } else {
+            // "No devices" placeholder
+            height + 25
+        }
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_battery
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        if let Some(profile) = ctx.data.power_profile {
+            let (x1, y1, x2, y2) = render_power_profile(ctx.cr, ctx.layout, y, profile, ctx.theme);
+            bounds.hover_targets.push((HoverTarget::PowerProfile, x1, y1, x2, y2));
+        }
+        render_battery_section(ctx.cr, ctx.layout, y, ctx.data.battery_devices, ctx.config.enable_solaar_integration)
+    }
+}
+
+// ============================================================================
+// Weather
+// ============================================================================
+
+/// Current conditions with icon, optionally detailed.
+struct WeatherSection;
+
+impl Section for WeatherSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_weather {
+            return 0;
+        }
+        let mut height = SECTION_SPACING + HEADER_HEIGHT; // "Weather" header
+        height += 70; // Icon and text content
+        if config.weather_detailed {
+            height += 50; // Feels-like/min/max + humidity/wind rows
+        }
+        if config.weather_sun_times {
+            height += 25; // Sunrise/sunset row
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_weather
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_weather(ctx, y)
+    }
+
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, Some(&fl!("widget-weather")))
+    }
+}
+
+// ============================================================================
+// Notifications
+// ============================================================================
+
+/// Grouped desktop notifications with clear buttons.
+struct NotificationsSection;
+
+impl Section for NotificationsSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_notifications {
+            return 0;
+        }
+        let height = SECTION_SPACING + HEADER_HEIGHT; // "Notifications" header
+        if counts.notifications > 0 {
+            // Each notification: app (18px) + summary (20px) + body (18px) + spacing (5px) = 61px
+            // Plus some extra for grouped headers
+            let displayed_count = counts.notifications.min(5);
+            height + displayed_count as u32 * 63
+        } else {
+            // "No notifications" placeholder
+            height + 25
+        }
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_notifications
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        let data = ctx.data;
+        let (new_y, section_bounds, groups, clear_bounds, clear_all, dnd) = render_notifications(
+            ctx.cr,
+            ctx.layout,
+            y,
+            data.grouped_notifications,
+            data.collapsed_groups,
+            data.notification_expansion,
+            data.notification_scroll,
+            data.do_not_disturb,
+            ctx.theme,
+        );
+        bounds.notifications = Some(section_bounds);
+        bounds.notification_groups = groups;
+        bounds.notification_clear = clear_bounds;
+        bounds.clear_all = clear_all;
+        if let Some((x1, y1, x2, y2)) = dnd {
+            bounds.hover_targets.push((HoverTarget::DoNotDisturb, x1, y1, x2, y2));
+        }
+        new_y
+    }
+}
+
+// ============================================================================
+// Media
+// ============================================================================
+
+/// Now playing panel with playback controls.
+struct MediaSection;
+
+impl Section for MediaSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_media {
+            return 0;
+        }
+        let mut height = SECTION_SPACING;
+        height += 28; // "Now Playing" header (smaller)
+        height += 145; // Panel: title, artist, album, progress, controls
+        if config.show_lyrics {
+            height += LYRICS_ROW_HEIGHT as u32;
+        }
+        if counts.players > 1 {
+            height += 36; // Extra space for pagination dots
+        }
+        height + 15 // Bottom padding after panel
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_media
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        let data = ctx.data;
+        let (new_y, buttons, title_scrolls) = render_media(
+            ctx.cr,
+            ctx.layout,
+            y,
+            data.media_info,
+            data.media_marquee.as_ref(),
+            ctx.config.show_lyrics,
+            data.lyrics_line,
+            ctx.theme,
+            data.player_count,
+            data.current_player_index,
+        );
+        bounds.media_buttons = buttons;
+        bounds.marquee_running = title_scrolls;
+        new_y
+    }
+}
+
+// ============================================================================
+// Audio Output
+// ============================================================================
+
+/// Name and volume of the default output; clicking mutes, scrolling
+/// changes the volume.
+struct AudioSection;
+
+impl Section for AudioSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if config.show_audio { SECTION_SPACING + 50 } else { 0 }
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_audio
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_audio(ctx.cr, ctx.layout, y, ctx.data.audio, ctx.theme)
+    }
+}
+
+// ============================================================================
+// Custom Commands
+// ============================================================================
+
+/// One row of output per user-defined command.
+struct CustomSection;
+
+impl Section for CustomSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_custom_commands {
+            return 0;
+        }
+        // One 25px row per command, or the "No commands" placeholder
+        let rows = config.custom_commands.len().max(1) as u32;
+        SECTION_SPACING + HEADER_HEIGHT + rows * 25
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_custom_commands
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_custom_commands(ctx.cr, ctx.layout, y, ctx.data.custom_outputs)
+    }
+}
+
+// ============================================================================
+// System Health
+// ============================================================================
+
+/// Count badges for failed systemd units and pending updates.
+struct SystemHealthSection;
+
+impl Section for SystemHealthSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_system_health {
+            return 0;
+        }
+        let mut height = SECTION_SPACING + 30; // Failed units row
+        if !config.health_update_command.trim().is_empty() {
+            height += 25; // Updates row
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_system_health
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        render_system_health(ctx, y, &mut bounds.hover_targets)
+    }
+}
+
+// ============================================================================
+// System
+// ============================================================================
+
+/// Kernel statistics: process and thread counts, context switches.
+struct SystemSection;
+
+impl Section for SystemSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_kernel_stats {
+            return 0;
+        }
+        SECTION_SPACING + KERNEL_STATS_ROW_HEIGHT as u32
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_kernel_stats
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_system(ctx, y)
+    }
+}
+
+// ============================================================================
+// Containers
+// ============================================================================
+
+/// Running container count and, optionally, the busiest container.
+struct ContainersSection;
+
+impl Section for ContainersSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_containers {
+            return 0;
+        }
+        let mut height = SECTION_SPACING + 30; // Running count row
+        if config.containers_show_busiest {
+            height += 25; // Busiest container row
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_containers
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        render_containers(ctx, y, &mut bounds.hover_targets)
+    }
+}
+
+// ============================================================================
+// Virtual Machines
+// ============================================================================
+
+/// One compact row per running libvirt domain.
+struct VirtualMachinesSection;
+
+impl Section for VirtualMachinesSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_vms {
+            return 0;
+        }
+        // One row per domain, or the "No virtual machines" placeholder
+        let rows = if counts.vms > 0 { counts.vms as u32 * VM_ROW_HEIGHT as u32 } else { 25 };
+        SECTION_SPACING + HEADER_HEIGHT + rows
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_vms
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_vms(ctx, y)
+    }
+
+    fn render_loading(&self, ctx: &RenderContext, y: f64) -> f64 {
+        render_loading_placeholder(ctx.cr, ctx.layout, y, Some(&fl!("widget-vms")))
+    }
+}
+
+// ============================================================================
+// Mail & Feeds
+// ============================================================================
+
+/// Unread counts of mailboxes and headlines of news feeds, one row each.
+struct FeedsSection;
+
+impl Section for FeedsSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        // One 25px row per feed, or the "No feeds" placeholder
+        let Some(settings) = feed_settings(config) else {
+            return 0;
+        };
+        SECTION_SPACING + settings.feeds.len().max(1) as u32 * 25 + 5
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_feeds
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_feeds(ctx.cr, ctx.layout, y, ctx.data.feed_rows)
+    }
+}
+
+// ============================================================================
+// Ticker
+// ============================================================================
+
+/// Price and 24h change of each configured symbol.
+struct TickerSection;
+
+impl Section for TickerSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        // One 25px row per symbol, or the "No symbols" placeholder
+        let Some(settings) = ticker_settings(config) else {
+            return 0;
+        };
+        SECTION_SPACING + settings.symbols.len().max(1) as u32 * 25 + 5
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_ticker
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_ticker(ctx, y)
+    }
+}
+
+// ============================================================================
+// Plugins
+// ============================================================================
+
+/// Blocks drawn by Lua plugin scripts, one below the other.
+///
+/// Hidden while no plugin is loaded (e.g., built without `lua-plugins`).
+struct PluginsSection;
+
+impl Section for PluginsSection {
+    fn measure(&self, config: &Config, counts: ContentCounts) -> u32 {
+        if !config.show_plugins || counts.plugin_height == 0 {
+            return 0;
+        }
+        SECTION_SPACING + counts.plugin_height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_plugins && !ctx.data.plugin_frames.is_empty()
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_plugins(ctx.cr, ctx.layout, y, ctx.data.plugin_frames)
+    }
+}
+
+// ============================================================================
+// Caffeine
+// ============================================================================
+
+/// Coffee cup toggling the idle inhibitor; the whole row is clickable.
+struct CaffeineSection;
+
+impl Section for CaffeineSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if config.show_caffeine { SECTION_SPACING + 30 } else { 0 }
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_caffeine
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_caffeine(ctx.cr, ctx.layout, y, ctx.data.caffeine_active, ctx.theme)
+    }
+}
+
+// ============================================================================
+// Network & Disk I/O
+// ============================================================================
+
+/// Download and upload rates, with an optional graph of their history.
+struct NetworkSection;
+
+impl Section for NetworkSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if !config.show_network {
+            return 0;
+        }
+        let mut height = 50; // Two lines: RX and TX
+        if config.show_network_graph {
+            height += NETWORK_GRAPH_HEIGHT as u32 + 10; // Graph and the spacing below it
+        }
+        height
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_network
+    }
+
+    fn spacing(&self) -> f64 {
+        0.0
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, bounds: &mut SectionBounds) -> f64 {
+        let (config, data) = (ctx.config, ctx.data);
+        let y_start = y;
+        let y = render_network(ctx.cr, ctx.layout, y, data.network_rx_rate, data.network_tx_rate, config.network_rate_bits);
+        bounds.hover_targets.push((HoverTarget::Network, 0.0, y_start, COLUMN_WIDTH as f64, y));
+        if !config.show_network_graph {
+            return y;
+        }
+        render_network_graph(
+            ctx.cr,
+            ctx.layout,
+            y,
+            data.network_rx_history,
+            data.network_tx_history,
+            config.network_rate_bits,
+            ctx.theme,
+        )
+    }
+}
+
+/// Disk read and write rates (separate from storage usage).
+struct DiskSection;
+
+impl Section for DiskSection {
+    fn measure(&self, config: &Config, _counts: ContentCounts) -> u32 {
+        if config.show_disk { 50 } else { 0 }
+    }
+
+    fn visible(&self, ctx: &RenderContext) -> bool {
+        ctx.config.show_disk
+    }
+
+    fn spacing(&self) -> f64 {
+        0.0
+    }
+
+    fn render(&self, ctx: &RenderContext, y: f64, _bounds: &mut SectionBounds) -> f64 {
+        render_disk(ctx.cr, ctx.layout, y)
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Drive Health
+//!
+//! SMART health verdict and temperature of each physical drive, drawn under
+//! the filesystems of the Storage section and checked on a long interval:
+//!
+//! ```text
+//! /sys/block/{sda,nvme0n1,...} ──device/hwmon temp1_input──► temperature
+//!                              ──smartctl --json -H -A────► PASSED / FAILING (+ temperature)
+//!                                   refresher thread, every smart_refresh_minutes
+//! ```
+//!
+//! NVMe drives and SATA drives with the `drivetemp` module loaded expose
+//! their temperature through hwmon, which anyone may read. The verdict needs
+//! `smartctl`, which has to open the raw device: as a normal user that
+//! fails with "Permission denied", and the drive shows "No access" instead
+//! of a verdict (granting smartctl `cap_sys_rawio,cap_sys_admin` lets the
+//! widget read it). Without smartctl installed only temperatures are shown.
+//!
+//! Virtual block devices (loop, zram, device mapper, RAID, optical) are left
+//! out.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+use super::exec::{CommandExt, SLOW_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::Config;
+
+/// Where block devices are listed.
+const BLOCK_ROOT: &str = "/sys/block";
+
+/// Block devices that aren't physical drives.
+const VIRTUAL_PREFIXES: [&str; 7] = ["loop", "ram", "zram", "dm-", "md", "sr", "nbd"];
+
+/// smartctl exit status bit set when the device couldn't be opened.
+const EXIT_OPEN_FAILED: i32 = 1 << 1;
+
+/// smartctl exit status bit set when the drive reports failing health.
+const EXIT_DISK_FAILING: i32 = 1 << 3;
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartSettings {
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while hidden).
+pub fn smart_settings(config: &Config) -> Option<SmartSettings> {
+    (config.show_storage && config.show_smart).then_some(SmartSettings {
+        refresh_minutes: config.smart_refresh_minutes,
+    })
+}
+
+/// SMART health verdict of a drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmartVerdict {
+    /// Overall health self-assessment passed
+    Passed,
+    /// The drive predicts its own failure
+    Failing,
+    /// smartctl isn't allowed to open the device
+    NoAccess,
+    /// No smartctl, or the drive has no SMART support
+    Unknown,
+}
+
+/// Health of one physical drive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveHealth {
+    /// Kernel name ("nvme0n1", "sda")
+    pub name: String,
+    /// Model reported by the drive, if any
+    pub model: Option<String>,
+    /// Temperature in Celsius
+    pub temperature: Option<f32>,
+    /// SMART verdict
+    pub verdict: SmartVerdict,
+}
+
+/// Refreshes the drive health on a background thread.
+pub type SmartMonitor = Refresher<SmartSettings, Vec<DriveHealth>>;
+
+impl RefreshSettings for SmartSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl SmartMonitor {
+    /// Start reading the health of every drive.
+    pub fn new(settings: Option<SmartSettings>) -> Self {
+        Refresher::spawn("smart", settings, |_: &SmartSettings| {
+            let drives = refresh();
+            log::debug!("Drive health refreshed: {:?}", drives);
+            (Some(drives), None)
+        })
+    }
+
+    /// Drives of the last refresh (empty before the first one).
+    pub fn drives(&self) -> Vec<DriveHealth> {
+        self.value().unwrap_or_default()
+    }
+}
+
+/// Check every physical drive.
+fn refresh() -> Vec<DriveHealth> {
+    let Ok(entries) = std::fs::read_dir(BLOCK_ROOT) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .filter(|name| !VIRTUAL_PREFIXES.iter().any(|prefix| name.starts_with(prefix)))
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let device = Path::new(BLOCK_ROOT).join(&name).join("device");
+            let model = read_trimmed(&device.join("model")).filter(|model| !model.is_empty());
+            let (verdict, smart_temperature) = smartctl(&name);
+            let temperature = hwmon_temperature(&device).or(smart_temperature);
+            DriveHealth { name, model, temperature, verdict }
+        })
+        .collect()
+}
+
+/// Temperature of the drive's hwmon chip: NVMe controllers hold it
+/// directly (`device/hwmon3`), drivetemp under `device/hwmon/hwmon3`.
+fn hwmon_temperature(device: &Path) -> Option<f32> {
+    let hwmon = [device.to_path_buf(), device.join("hwmon")]
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            name.strip_prefix("hwmon").is_some_and(|number| number.parse::<u32>().is_ok())
+        })
+        .min()?;
+    let millidegrees: f32 = read_trimmed(&hwmon.join("temp1_input"))?.parse().ok()?;
+    Some(millidegrees / 1000.0)
+}
+
+/// Verdict and temperature from `smartctl --json -H -A /dev/<name>`.
+fn smartctl(name: &str) -> (SmartVerdict, Option<f32>) {
+    let output = Command::new("smartctl")
+        .args(["--json", "-H", "-A"])
+        .arg(format!("/dev/{}", name))
+        .bounded_output(SLOW_TIMEOUT);
+    match output {
+        Ok(output) => {
+            let status = output.status.code().unwrap_or(EXIT_OPEN_FAILED);
+            parse_smartctl(&String::from_utf8_lossy(&output.stdout), status)
+        }
+        Err(e) => {
+            log::debug!("smartctl not available: {}", e);
+            (SmartVerdict::Unknown, None)
+        }
+    }
+}
+
+/// Verdict and temperature of a smartctl JSON report and its exit status.
+fn parse_smartctl(json: &str, status: i32) -> (SmartVerdict, Option<f32>) {
+    let report: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    let temperature = report.pointer("/temperature/current").and_then(Value::as_f64).map(|t| t as f32);
+
+    if status & EXIT_OPEN_FAILED != 0 {
+        let denied = report
+            .pointer("/smartctl/messages")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|message| message.get("string").and_then(Value::as_str))
+            .any(|message| message.contains("Permission denied") || message.contains("Operation not permitted"));
+        let verdict = if denied { SmartVerdict::NoAccess } else { SmartVerdict::Unknown };
+        return (verdict, temperature);
+    }
+
+    let verdict = match report.pointer("/smart_status/passed").and_then(Value::as_bool) {
+        Some(true) if status & EXIT_DISK_FAILING == 0 => SmartVerdict::Passed,
+        Some(_) => SmartVerdict::Failing,
+        None => SmartVerdict::Unknown,
+    };
+    (verdict, temperature)
+}
+
+/// Read a sysfs file holding one value.
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_smartctl() {
+        let passed = r#"{"smart_status":{"passed":true},"temperature":{"current":38}}"#;
+        assert_eq!(parse_smartctl(passed, 0), (SmartVerdict::Passed, Some(38.0)));
+
+        let failing = r#"{"smart_status":{"passed":false},"temperature":{"current":51}}"#;
+        assert_eq!(parse_smartctl(failing, EXIT_DISK_FAILING), (SmartVerdict::Failing, Some(51.0)));
+
+        let denied = r#"{"smartctl":{"messages":[{"string":"Smartctl open device: /dev/sda failed: Permission denied","severity":"error"}]}}"#;
+        assert_eq!(parse_smartctl(denied, EXIT_OPEN_FAILED), (SmartVerdict::NoAccess, None));
+
+        let unsupported = r#"{"smartctl":{"messages":[{"string":"/dev/sdb: Unknown USB bridge","severity":"error"}]}}"#;
+        assert_eq!(parse_smartctl(unsupported, EXIT_OPEN_FAILED), (SmartVerdict::Unknown, None));
+        assert_eq!(parse_smartctl("", 0), (SmartVerdict::Unknown, None));
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Drag Snapping
+//!
+//! While the widget is dragged, its edges are pulled onto the output's
+//! edges and its center onto the output's center lines once they come
+//! within [`SNAP_DISTANCE`]; otherwise its top-left corner can be rounded
+//! to an N-pixel grid:
+//!
+//! ```text
+//!  ┌──────────────────────────────┐
+//!  │┌────┐          ┆             │   Start: left/top edge
+//!  ││    │ ◄ Start  ┆ ◄ Center    │   Center: output center line
+//!  │└────┘          ┆        End ►│   End: right/bottom edge
+//!  └──────────────────────────────┘
+//! ```
+//!
+//! Snapping only ever changes where the surface is put; the pointer's
+//! movement keeps accumulating, so dragging further pulls the widget off
+//! the guide again. Which guides caught the widget is returned with the
+//! position, for the renderer to draw them ([`SnapGuides`]).
+
+/// How close (in pixels) an edge or center must come to snap.
+pub const SNAP_DISTANCE: i32 = 16;
+
+/// Line of the output a widget axis snapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guide {
+    /// Left or top edge
+    Start,
+    /// Vertical or horizontal center line
+    Center,
+    /// Right or bottom edge
+    End,
+}
+
+/// Guides the widget snapped to, one per axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapGuides {
+    /// Horizontal snap (drawn as a vertical line)
+    pub x: Option<Guide>,
+    /// Vertical snap (drawn as a horizontal line)
+    pub y: Option<Guide>,
+}
+
+impl SnapGuides {
+    /// Whether any guide is active.
+    pub fn any(&self) -> bool {
+        self.x.is_some() || self.y.is_some()
+    }
+}
+
+/// Snap a widget's top-left corner `position` on an output.
+///
+/// `edges` turns edge and center snapping on, `grid` is the grid size in
+/// pixels (0 or 1 for none). Edges win over the grid.
+pub fn snap(position: (i32, i32), size: (i32, i32), output: (i32, i32), edges: bool, grid: u32) -> ((i32, i32), SnapGuides) {
+    let (x, guide_x) = snap_axis(position.0, size.0, output.0, edges, grid);
+    let (y, guide_y) = snap_axis(position.1, size.1, output.1, edges, grid);
+    ((x, y), SnapGuides { x: guide_x, y: guide_y })
+}
+
+/// Snap one axis: the widget starts at `start` and is `size` long on an
+/// output `output` long.
+fn snap_axis(start: i32, size: i32, output: i32, edges: bool, grid: u32) -> (i32, Option<Guide>) {
+    if edges {
+        let targets = [(0, Guide::Start), ((output - size) / 2, Guide::Center), (output - size, Guide::End)];
+        let nearest = targets
+            .into_iter()
+            .filter(|(target, _)| (start - target).abs() <= SNAP_DISTANCE)
+            .min_by_key(|(target, _)| (start - target).abs());
+        if let Some((target, guide)) = nearest {
+            return (target, Some(guide));
+        }
+    }
+    if grid > 1 {
+        let grid = grid as f64;
+        return (((start as f64 / grid).round() * grid) as i32, None);
+    }
+    (start, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap() {
+        let size = (320, 200);
+        let output = (1920, 1080);
+
+        // Near the left edge and the vertical center
+        let ((x, y), guides) = snap((10, 437), size, output, true, 0);
+        assert_eq!((x, y), (0, 440));
+        assert_eq!(guides, SnapGuides { x: Some(Guide::Start), y: Some(Guide::Center) });
+
+        // Near the bottom right corner
+        let (position, guides) = snap((1590, 875), size, output, true, 0);
+        assert_eq!(position, (1600, 880));
+        assert_eq!(guides, SnapGuides { x: Some(Guide::End), y: Some(Guide::End) });
+
+        // Away from every guide: only the grid applies
+        assert_eq!(snap((205, 314), size, output, true, 25), ((200, 325), SnapGuides::default()));
+        assert_eq!(snap((10, 437), size, output, false, 0), ((10, 437), SnapGuides::default()));
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Low-Space Warnings
+//!
+//! A shown filesystem is low when its free space drops below
+//! `storage_min_free_percent` or its inode use reaches
+//! `storage_max_inode_percent` (0 turns either check off). Low filesystems
+//! get a red bar and a badge in the Storage section, and with
+//! `storage_alerts` on a desktop notification when they cross a limit:
+//!
+//! ```text
+//! sample tick ──low_space(disk)──► Some(Space) ──► red bar, "4.1 GiB free" badge
+//!                                       │
+//!                    SpaceAlerts::check ┴─ newly low? ──busctl Notify──► "Home is almost full"
+//! ```
+//!
+//! A filesystem alerts once per crossing: it has to get back above the
+//! limits before it alerts again. Filesystems without a fixed inode count
+//! (btrfs) are only checked for space.
+//!
+//! The notification goes to the session's notification daemon like any
+//! other app's, so it also shows up in the Notifications section.
+
+use std::collections::HashSet;
+use std::process::Command;
+
+use super::exec::{self, CommandExt};
+use super::stats::format_gib;
+use super::storage::DiskInfo;
+use crate::config::Config;
+use crate::fl;
+
+/// Name the alerts are sent under.
+const APP_NAME: &str = "COSMIC Monitor";
+
+/// Icon of the alerts.
+const ALERT_ICON: &str = "drive-harddisk-symbolic";
+
+/// `urgency` hint value of critical notifications.
+const URGENCY_CRITICAL: &str = "2";
+
+/// Warning limits of the storage section.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpaceLimits {
+    /// Free space below which a filesystem is low (percent, 0 = off)
+    pub min_free_percent: u32,
+    /// Inode use at which a filesystem is low (percent, 0 = off)
+    pub max_inode_percent: u32,
+}
+
+/// Limits of a configuration.
+pub fn space_limits(config: &Config) -> SpaceLimits {
+    SpaceLimits {
+        min_free_percent: config.storage_min_free_percent,
+        max_inode_percent: config.storage_max_inode_percent,
+    }
+}
+
+/// Why a filesystem is low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LowSpace {
+    /// Less free space than allowed
+    Space,
+    /// Too many inodes in use
+    Inodes,
+}
+
+/// Whether `disk` crossed a limit (never while it is still loading).
+pub fn low_space(disk: &DiskInfo, limits: &SpaceLimits) -> Option<LowSpace> {
+    if disk.is_loading || disk.total_space == 0 {
+        return None;
+    }
+    let free_percent = disk.available_space as f64 / disk.total_space as f64 * 100.0;
+    if limits.min_free_percent > 0 && free_percent < limits.min_free_percent as f64 {
+        return Some(LowSpace::Space);
+    }
+    let inodes_full = disk
+        .inode_percentage
+        .is_some_and(|used| limits.max_inode_percent > 0 && used >= limits.max_inode_percent as f32);
+    inodes_full.then_some(LowSpace::Inodes)
+}
+
+/// Badge text of a low filesystem ("4.1 GiB free", "Inodes 97%").
+pub fn badge_text(disk: &DiskInfo, low: LowSpace) -> String {
+    match low {
+        LowSpace::Space => fl!("widget-storage-free", free = format_gib(disk.available_space)),
+        LowSpace::Inodes => fl!(
+            "widget-storage-inodes",
+            percent = format!("{:.0}", disk.inode_percentage.unwrap_or_default())
+        ),
+    }
+}
+
+/// Remembers which filesystems are low, to alert once per crossing.
+#[derive(Debug, Default)]
+pub struct SpaceAlerts {
+    /// Mount points low at the last check
+    low: HashSet<String>,
+}
+
+impl SpaceAlerts {
+    /// Filesystems that became low since the last check.
+    ///
+    /// Filesystems still loading keep their previous state.
+    pub fn check<'a>(&mut self, disks: &'a [DiskInfo], limits: &SpaceLimits) -> Vec<(&'a DiskInfo, LowSpace)> {
+        let mut crossed = Vec::new();
+        let mut low = HashSet::new();
+        for disk in disks {
+            if disk.is_loading {
+                if self.low.contains(&disk.mount_point) {
+                    low.insert(disk.mount_point.clone());
+                }
+                continue;
+            }
+            let Some(reason) = low_space(disk, limits) else {
+                continue;
+            };
+            if !self.low.contains(&disk.mount_point) {
+                crossed.push((disk, reason));
+            }
+            low.insert(disk.mount_point.clone());
+        }
+        self.low = low;
+        crossed
+    }
+}
+
+/// Send a desktop notification that `disk` is low, without waiting for it.
+pub fn send_alert(disk: &DiskInfo, low: LowSpace) {
+    let summary = fl!("storage-alert-title", name = disk.name.clone());
+    let body = match low {
+        LowSpace::Space => fl!(
+            "storage-alert-space",
+            mount = disk.mount_point.clone(),
+            free = format_gib(disk.available_space)
+        ),
+        LowSpace::Inodes => fl!(
+            "storage-alert-inodes",
+            mount = disk.mount_point.clone(),
+            percent = format!("{:.0}", disk.inode_percentage.unwrap_or_default())
+        ),
+    };
+    log::info!("{}: {}", summary, body);
+
+    std::thread::spawn(move || {
+        // Notify(app_name, replaces_id, icon, summary, body, actions, hints, timeout)
+        let output = Command::new("busctl")
+            .args([
+                "--user",
+                "call",
+                "org.freedesktop.Notifications",
+                "/org/freedesktop/Notifications",
+                "org.freedesktop.Notifications",
+                "Notify",
+                "susssasa{sv}i",
+                APP_NAME,
+                "0",
+                ALERT_ICON,
+                &summary,
+                &body,
+                "0",
+                "1",
+                "urgency",
+                "y",
+                URGENCY_CRITICAL,
+                "-1",
+            ])
+            .bounded_output(exec::QUICK_TIMEOUT);
+        match output {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => log::warn!(
+                "Failed to send low space alert: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => log::warn!("Failed to send low space alert: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_space_alerts() {
+        let disk = |mount: &str, available_space, inode_percentage| DiskInfo {
+            name: mount.to_string(),
+            mount_point: mount.to_string(),
+            used_percentage: 0.0,
+            total_space: 100,
+            available_space,
+            inode_percentage,
+            is_loading: false,
+        };
+        let limits = SpaceLimits { min_free_percent: 10, max_inode_percent: 95 };
+        assert_eq!(low_space(&disk("/", 50, Some(50.0)), &limits), None);
+        assert_eq!(low_space(&disk("/", 9, Some(50.0)), &limits), Some(LowSpace::Space));
+        assert_eq!(low_space(&disk("/", 50, Some(96.0)), &limits), Some(LowSpace::Inodes));
+        assert_eq!(low_space(&disk("/", 50, None), &limits), None);
+        assert_eq!(low_space(&disk("/", 9, None), &SpaceLimits::default()), None);
+
+        // Alerts once per crossing
+        let mut alerts = SpaceAlerts::default();
+        let full = [disk("/", 5, None), disk("/home", 50, None)];
+        let mounts = |crossed: Vec<(&DiskInfo, LowSpace)>| {
+            crossed.into_iter().map(|(disk, _)| disk.mount_point.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(mounts(alerts.check(&full, &limits)), ["/"]);
+        assert!(alerts.check(&full, &limits).is_empty());
+
+        let loading = [DiskInfo { is_loading: true, ..disk("/", 0, None) }];
+        assert!(alerts.check(&loading, &limits).is_empty());
+        assert!(alerts.check(&full, &limits).is_empty());
+
+        assert!(alerts.check(&[disk("/", 50, None)], &limits).is_empty());
+        assert_eq!(mounts(alerts.check(&full, &limits)), ["/"]);
+    }
+}
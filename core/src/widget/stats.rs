@@ -0,0 +1,558 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Published Stats Snapshot
+//!
+//! The widget already samples CPU, memory, GPU, temperatures, network,
+//! storage and more every cycle. This module publishes those same numbers
+//! as a small JSON file so scripts, status bars, or OBS overlays can read
+//! them without running their own collectors.
+//!
+//! # File Location
+//!
+//! `$XDG_RUNTIME_DIR/cosmic-monitor-widget-stats.json`
+//! (falls back to `/tmp/cosmic-monitor-widget-stats-<uid>.json`)
+//!
+//! # Atomic Updates
+//!
+//! Each snapshot is written to a temporary file next to the target and then
+//! renamed over it, so readers never see a half-written file:
+//!
+//! ```text
+//! StatsSnapshot::publish()
+//!   ├── write  cosmic-monitor-widget-stats.json.tmp
+//!   └── rename → cosmic-monitor-widget-stats.json   (atomic on the same fs)
+//! ```
+//!
+//! # Missing Values
+//!
+//! Metrics the widget is not collecting (section disabled, no sensor found,
+//! no GPU) are `null` rather than `0`, so consumers can tell "idle" from
+//! "unknown".
+//!
+//! ```bash
+//! jq .cpu_usage "$XDG_RUNTIME_DIR/cosmic-monitor-widget-stats.json"
+//! ```
+//!
+//! # One-Shot Mode
+//!
+//! `cosmic-monitor-widget --stats [json|plain]` uses [`StatsSnapshot::collect_once`]
+//! to take a single sample without a Wayland surface and print it to stdout.
+
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::battery::BatteryDevice;
+use super::media::{MediaInfo, PlaybackStatus};
+use super::network::{format_rate, NetworkMonitor};
+use super::storage::{DiskInfo, StorageMonitor};
+use super::temperature::TemperatureMonitor;
+use super::utilization::UtilizationMonitor;
+use super::weather::WeatherData;
+
+// ============================================================================
+// Snapshot Structures
+// ============================================================================
+
+/// Network transfer rates.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkStats {
+    /// Download rate in bytes per second
+    pub rx_bytes_per_sec: f64,
+    /// Upload rate in bytes per second
+    pub tx_bytes_per_sec: f64,
+}
+
+/// Usage of a single mounted disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskStats {
+    /// Display name (model name, "Home", "System", ...)
+    pub name: String,
+    /// Mount point path
+    pub mount_point: String,
+    /// Percentage of space used (0-100)
+    pub used_percentage: f32,
+    /// Total capacity in bytes
+    pub total_bytes: u64,
+    /// Free space in bytes
+    pub available_bytes: u64,
+}
+
+/// Battery state of a peripheral device.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatteryStats {
+    /// Device name
+    pub name: String,
+    /// Battery level (0-100), if reported
+    pub level: Option<u8>,
+    /// Charging status text, if reported
+    pub status: Option<String>,
+    /// Whether the device is currently connected
+    pub connected: bool,
+}
+
+/// Current weather conditions.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeatherStats {
+    /// Temperature in Celsius
+    pub temperature: f32,
+    /// Description (e.g., "Light rain")
+    pub description: String,
+    /// Location name from the API
+    pub location: String,
+    /// Unix timestamp (seconds) of the fetch
+    pub fetched_at: u64,
+}
+
+/// Now-playing information.
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaStats {
+    /// Player name (e.g., "Cider")
+    pub player: String,
+    /// Track title
+    pub title: String,
+    /// Artist name
+    pub artist: String,
+    /// Album name
+    pub album: String,
+    /// "playing", "paused", or "stopped"
+    pub status: &'static str,
+    /// Playback position in milliseconds
+    pub position_ms: u64,
+    /// Track duration in milliseconds
+    pub duration_ms: u64,
+}
+
+/// One sample of everything the widget collects.
+///
+/// Start with [`StatsSnapshot::capture`] and fill in the sections that are
+/// being collected; everything else stays `null`.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct StatsSnapshot {
+    /// Unix timestamp (seconds) when the snapshot was taken
+    pub timestamp: u64,
+    /// Same timestamp in milliseconds (aligned to the sampling interval
+    /// when published by the widget)
+    pub timestamp_ms: u64,
+    /// CPU usage percentage (0-100)
+    pub cpu_usage: Option<f32>,
+    /// Memory usage percentage (0-100)
+    pub memory_usage: Option<f32>,
+    /// Used memory in bytes
+    pub memory_used: Option<u64>,
+    /// Total memory in bytes
+    pub memory_total: Option<u64>,
+    /// GPU usage percentage (0-100)
+    pub gpu_usage: Option<f32>,
+    /// Detected GPU vendor ("NVIDIA", "AMD", "Intel", or "None")
+    pub gpu_vendor: Option<&'static str>,
+    /// CPU temperature in Celsius
+    pub cpu_temp: Option<f32>,
+    /// GPU temperature in Celsius
+    pub gpu_temp: Option<f32>,
+    /// Network transfer rates
+    pub network: Option<NetworkStats>,
+    /// Mounted disks
+    pub disks: Vec<DiskStats>,
+    /// Peripheral batteries
+    pub batteries: Vec<BatteryStats>,
+    /// Current weather
+    pub weather: Option<WeatherStats>,
+    /// Now playing
+    pub media: Option<MediaStats>,
+}
+
+// ============================================================================
+// Snapshot Construction
+// ============================================================================
+
+impl StatsSnapshot {
+    /// Start an empty snapshot stamped with the current time.
+    pub fn capture() -> Self {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Self::capture_at(now_ms)
+    }
+
+    /// Start an empty snapshot stamped with a given sample time.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp_ms` - Sample time as Unix milliseconds
+    pub fn capture_at(timestamp_ms: u64) -> Self {
+        Self {
+            timestamp: timestamp_ms / 1000,
+            timestamp_ms,
+            ..Default::default()
+        }
+    }
+
+    /// Fill in CPU, memory and GPU utilization.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - Utilization monitor with a recent `update()`
+    /// * `cpu`, `memory`, `gpu` - Which metrics are being collected
+    pub fn set_utilization(&mut self, monitor: &UtilizationMonitor, cpu: bool, memory: bool, gpu: bool) {
+        if cpu {
+            self.cpu_usage = Some(monitor.cpu_usage);
+        }
+        if memory {
+            self.memory_usage = Some(monitor.memory_usage);
+            self.memory_used = Some(monitor.memory_used);
+            self.memory_total = Some(monitor.memory_total);
+        }
+        self.gpu_vendor = Some(monitor.gpu_vendor_name());
+        if gpu && monitor.gpu_error().is_none() {
+            self.gpu_usage = Some(monitor.get_gpu_usage());
+        }
+    }
+
+    /// Fill in CPU and GPU temperatures (only for sensors that were found).
+    pub fn set_temperatures(&mut self, monitor: &TemperatureMonitor, cpu: bool, gpu: bool) {
+        if cpu {
+            self.cpu_temp = monitor.cpu_reading();
+        }
+        if gpu {
+            self.gpu_temp = monitor.gpu_reading();
+        }
+    }
+
+    /// Fill in network transfer rates.
+    pub fn set_network(&mut self, monitor: &NetworkMonitor) {
+        self.network = Some(NetworkStats {
+            rx_bytes_per_sec: monitor.network_rx_rate,
+            tx_bytes_per_sec: monitor.network_tx_rate,
+        });
+    }
+
+    /// Fill in disk usage, skipping cached placeholders that are still loading.
+    pub fn set_disks(&mut self, disks: &[DiskInfo]) {
+        self.disks = disks
+            .iter()
+            .filter(|d| !d.is_loading)
+            .map(|d| DiskStats {
+                name: d.name.clone(),
+                mount_point: d.mount_point.clone(),
+                used_percentage: d.used_percentage,
+                total_bytes: d.total_space,
+                available_bytes: d.available_space,
+            })
+            .collect();
+    }
+
+    /// Fill in peripheral batteries, skipping devices that are still loading.
+    pub fn set_batteries(&mut self, devices: &[BatteryDevice]) {
+        self.batteries = devices
+            .iter()
+            .filter(|d| !d.is_loading)
+            .map(|d| BatteryStats {
+                name: d.name.clone(),
+                level: d.level,
+                status: d.status.clone(),
+                connected: d.is_connected,
+            })
+            .collect();
+    }
+
+    /// Fill in the current weather.
+    pub fn set_weather(&mut self, data: &WeatherData) {
+        self.weather = Some(WeatherStats {
+            temperature: data.temperature,
+            description: data.description.clone(),
+            location: data.location.clone(),
+            fetched_at: data.fetched_at,
+        });
+    }
+
+    /// Fill in now-playing information (ignored when nothing is playing).
+    pub fn set_media(&mut self, info: &MediaInfo) {
+        if !info.is_active() {
+            return;
+        }
+        self.media = Some(MediaStats {
+            player: info.player_name.clone(),
+            title: info.title.clone(),
+            artist: info.artist.clone(),
+            album: info.album.clone(),
+            status: match info.status {
+                PlaybackStatus::Playing => "playing",
+                PlaybackStatus::Paused => "paused",
+                PlaybackStatus::Stopped => "stopped",
+            },
+            position_ms: info.position,
+            duration_ms: info.duration,
+        });
+    }
+}
+
+// ============================================================================
+// Publishing
+// ============================================================================
+
+/// Path of the published stats file.
+pub fn stats_path() -> PathBuf {
+    super::control::runtime_path("cosmic-monitor-widget-stats", "json")
+}
+
+impl StatsSnapshot {
+    /// Atomically replace the published stats file with this snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors from writing or renaming the file. Callers
+    /// usually log and ignore them (publishing is best-effort).
+    pub fn publish(&self) -> std::io::Result<()> {
+        let path = stats_path();
+        let tmp_path = path.with_extension("json.tmp");
+
+        let json = serde_json::to_vec(self)?;
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&json)?;
+        drop(file);
+
+        fs::rename(&tmp_path, &path)
+    }
+}
+
+// ============================================================================
+// One-Shot Sampling
+// ============================================================================
+
+/// How long to wait between the two samples of a one-shot collection.
+///
+/// CPU usage and network rates are deltas between two refreshes, and the
+/// GPU thread takes its first reading after one second.
+const ONE_SHOT_WINDOW: Duration = Duration::from_millis(1500);
+
+/// The monitors that can run without a Wayland surface.
+///
+/// Shared by the `--stats` one-shot mode and the Prometheus exporter.
+/// Covers CPU, memory, GPU, temperatures, network and storage; weather,
+/// batteries and media depend on widget configuration and are left out.
+pub struct LocalMonitors {
+    utilization: UtilizationMonitor,
+    temperature: TemperatureMonitor,
+    network: NetworkMonitor,
+    storage: StorageMonitor,
+}
+
+impl LocalMonitors {
+    /// Create all local monitors (spawns their background threads).
+    pub fn new() -> Self {
+        let utilization = UtilizationMonitor::new();
+        let mut temperature = TemperatureMonitor::new();
+        temperature.follow_gpu(&utilization);
+        Self {
+            utilization,
+            temperature,
+            network: NetworkMonitor::new(),
+            storage: StorageMonitor::new(),
+        }
+    }
+
+    /// Refresh every monitor.
+    pub fn update(&mut self) {
+        self.utilization.update();
+        self.network.update();
+        self.temperature.update();
+        self.storage.update();
+    }
+
+    /// Snapshot of the latest readings.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp_ms` - Sample time as Unix milliseconds
+    pub fn snapshot(&self, timestamp_ms: u64) -> StatsSnapshot {
+        let mut snapshot = StatsSnapshot::capture_at(timestamp_ms);
+        snapshot.set_utilization(&self.utilization, true, true, true);
+        snapshot.set_temperatures(&self.temperature, true, true);
+        snapshot.set_network(&self.network);
+        snapshot.set_disks(&self.storage.disk_info);
+        snapshot
+    }
+}
+
+impl StatsSnapshot {
+    /// Take a single sample from the local monitors (blocking, ~1.5 s).
+    ///
+    /// Used by the `--stats` CLI mode; see [`LocalMonitors`] for coverage.
+    pub fn collect_once() -> Self {
+        let mut monitors = LocalMonitors::new();
+
+        monitors.update();
+        std::thread::sleep(ONE_SHOT_WINDOW);
+        monitors.update();
+
+        monitors.snapshot(Self::capture().timestamp_ms)
+    }
+
+    /// Human-readable multi-line summary for the `--stats plain` output and
+    /// the clipboard (see [`super::clipboard`]).
+    ///
+    /// Missing values are printed as "n/a"; batteries, weather and media are
+    /// only listed when collected.
+    pub fn to_plain(&self) -> String {
+        fn percent(value: Option<f32>) -> String {
+            value.map_or_else(|| String::from("n/a"), |v| format!("{:.1}%", v))
+        }
+        fn celsius(value: Option<f32>) -> String {
+            value.map_or_else(|| String::from("n/a"), |v| format!("{:.1}°C", v))
+        }
+
+        let mut lines = Vec::new();
+        lines.push(format!("CPU:       {}", percent(self.cpu_usage)));
+
+        let mut memory = format!("Memory:    {}", percent(self.memory_usage));
+        if let (Some(used), Some(total)) = (self.memory_used, self.memory_total) {
+            memory.push_str(&format!(" ({} / {})", format_gib(used), format_gib(total)));
+        }
+        lines.push(memory);
+
+        let mut gpu = format!("GPU:       {}", percent(self.gpu_usage));
+        if let Some(vendor) = self.gpu_vendor {
+            gpu.push_str(&format!(" ({})", vendor));
+        }
+        lines.push(gpu);
+
+        lines.push(format!("CPU temp:  {}", celsius(self.cpu_temp)));
+        lines.push(format!("GPU temp:  {}", celsius(self.gpu_temp)));
+
+        if let Some(network) = &self.network {
+            lines.push(format!(
+                "Network:   ↓ {}  ↑ {}",
+                format_rate(network.rx_bytes_per_sec, false),
+                format_rate(network.tx_bytes_per_sec, false)
+            ));
+        }
+
+        for disk in &self.disks {
+            lines.push(format!(
+                "Disk:      {} ({}) {:.1}% of {}",
+                disk.name,
+                disk.mount_point,
+                disk.used_percentage,
+                format_gib(disk.total_bytes)
+            ));
+        }
+
+        for battery in &self.batteries {
+            let level = battery.level.map_or_else(|| String::from("n/a"), |level| format!("{}%", level));
+            let mut line = format!("Battery:   {} {}", battery.name, level);
+            if let Some(status) = &battery.status {
+                line.push_str(&format!(" ({})", status));
+            }
+            if !battery.connected {
+                line.push_str(" (disconnected)");
+            }
+            lines.push(line);
+        }
+
+        if let Some(weather) = &self.weather {
+            lines.push(format!(
+                "Weather:   {:.1}°C {} ({})",
+                weather.temperature, weather.description, weather.location
+            ));
+        }
+
+        if let Some(media) = &self.media {
+            lines.push(format!("Media:     {} - {} ({}, {})", media.artist, media.title, media.player, media.status));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Format a byte count as GiB with one decimal.
+pub fn format_gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_disks_and_plain() {
+        let disk = |name: &str, is_loading| DiskInfo {
+            name: name.to_string(),
+            mount_point: String::from("/"),
+            used_percentage: 42.0,
+            total_space: 512 * 1024 * 1024 * 1024,
+            available_space: 0,
+            inode_percentage: None,
+            is_loading,
+        };
+        let mut snapshot = StatsSnapshot::capture_at(1_700_000_000_500);
+        assert_eq!(snapshot.timestamp, 1_700_000_000);
+        snapshot.set_disks(&[disk("Samsung SSD", false), disk("Cached", true)]);
+        assert_eq!(snapshot.disks.len(), 1);
+
+        snapshot.cpu_usage = Some(12.34);
+        let plain = snapshot.to_plain();
+        assert!(plain.contains("CPU:       12.3%"));
+        assert!(plain.contains("GPU temp:  n/a"));
+        assert!(plain.contains("Disk:      Samsung SSD (/) 42.0% of 512.0 GiB"));
+        assert!(!plain.contains("Battery:"));
+    }
+
+    #[test]
+    fn test_plain_summary_format() {
+        let gib = 1024 * 1024 * 1024;
+        let mut snapshot = StatsSnapshot::capture_at(1_700_000_000_000);
+        snapshot.cpu_usage = Some(12.34);
+        snapshot.memory_usage = Some(50.0);
+        snapshot.memory_used = Some(8 * gib);
+        snapshot.memory_total = Some(16 * gib);
+        snapshot.gpu_usage = Some(5.0);
+        snapshot.gpu_vendor = Some("AMD");
+        snapshot.cpu_temp = Some(45.5);
+        snapshot.network = Some(NetworkStats { rx_bytes_per_sec: 2048.0, tx_bytes_per_sec: 512.0 });
+        snapshot.disks = vec![DiskStats {
+            name: String::from("nvme0n1p2"),
+            mount_point: String::from("/"),
+            used_percentage: 42.0,
+            total_bytes: 512 * gib,
+            available_bytes: 297 * gib,
+        }];
+        snapshot.batteries = vec![
+            BatteryStats { name: String::from("BAT0"), level: Some(80), status: Some(String::from("Charging")), connected: true },
+            BatteryStats { name: String::from("MX Master 3"), level: None, status: None, connected: false },
+        ];
+        snapshot.weather = Some(WeatherStats {
+            temperature: 18.0,
+            description: String::from("light rain"),
+            location: String::from("London"),
+            fetched_at: 1_700_000_000,
+        });
+        snapshot.media = Some(MediaStats {
+            player: String::from("Cider"),
+            title: String::from("Title"),
+            artist: String::from("Artist"),
+            album: String::from("Album"),
+            status: "Playing",
+            position_ms: 0,
+            duration_ms: 180_000,
+        });
+
+        let expected = [
+            "CPU:       12.3%",
+            "Memory:    50.0% (8.0 GiB / 16.0 GiB)",
+            "GPU:       5.0% (AMD)",
+            "CPU temp:  45.5°C",
+            "GPU temp:  n/a",
+            "Network:   ↓ 2.0 KB/s  ↑ 512 B/s",
+            "Disk:      nvme0n1p2 (/) 42.0% of 512.0 GiB",
+            "Battery:   BAT0 80% (Charging)",
+            "Battery:   MX Master 3 n/a (disconnected)",
+            "Weather:   18.0°C light rain (London)",
+            "Media:     Artist - Title (Cider, Playing)",
+        ];
+        assert_eq!(snapshot.to_plain(), expected.join("\n"));
+    }
+}
@@ -8,6 +8,8 @@
 //! ## Features
 //!
 //! - **Disk usage tracking**: Total, available, and used space percentages
+//! - **Inode usage**: Share of inodes in use (via `statvfs`), for the
+//!   low-space warnings in `space_alert`
 //! - **Smart filtering**: Only shows meaningful mounts (/, /home, external drives)
 //! - **Friendly names**: Uses `lsblk` to get vendor/model names instead of device paths
 //! - **Caching**: Shows cached disk list immediately while loading real data
@@ -40,8 +42,10 @@
 
 use sysinfo::Disks;
 use std::process::Command;
+use super::exec::{self, CommandExt};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use super::watchdog::Heartbeat;
 
 // ============================================================================
 // Disk Information Struct
@@ -63,6 +67,9 @@ pub struct DiskInfo {
     pub total_space: u64,
     /// Available free space in bytes
     pub available_space: u64,
+    /// Percentage of inodes in use (None for filesystems without a fixed
+    /// inode count, like btrfs)
+    pub inode_percentage: Option<f32>,
     /// True if showing cached data while loading real data
     pub is_loading: bool,
 }
@@ -96,6 +103,8 @@ pub struct StorageMonitor {
     disk_models: Arc<Mutex<HashMap<String, String>>>,
     /// Flag to track first update for cache saving
     is_first_update: bool,
+    /// Stamped by the model lookup thread after every poll (for the watchdog)
+    heartbeat: Heartbeat,
 }
 
 impl StorageMonitor {
@@ -124,6 +133,7 @@ impl StorageMonitor {
                 used_percentage: 0.0,  // Will be updated on first refresh
                 total_space: 0,
                 available_space: 0,
+                inode_percentage: None,
                 is_loading: true,  // Mark as loading until real data arrives
             })
             .collect();
@@ -131,15 +141,23 @@ impl StorageMonitor {
         // Spawn background thread to update disk models from lsblk
         // This avoids blocking the main thread on shell commands
         let disk_models_clone = Arc::clone(&disk_models);
+        let heartbeat = Heartbeat::new();
+        let heartbeat_clone = heartbeat.clone();
         std::thread::spawn(move || {
+            let _exit_guard = heartbeat_clone.guard();
+            super::priority::enter_background("storage");
             loop {
                 // Fetch disk models from lsblk
                 if let Some(models) = Self::fetch_disk_models() {
                     *disk_models_clone.lock().unwrap() = models;
                 }
+                heartbeat_clone.beat();
                 
                 // Refresh every 10 seconds (disk models don't change often)
                 std::thread::sleep(std::time::Duration::from_secs(10));
+                if heartbeat_clone.stopped() {
+                    break;
+                }
             }
         });
         
@@ -148,9 +166,15 @@ impl StorageMonitor {
             disk_info,
             disk_models,
             is_first_update: true,
+            heartbeat,
         }
     }
     
+    /// Heartbeat of the disk model lookup thread (for the watchdog).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+    
     /// Fetch disk model names from lsblk (called from background thread).
     ///
     /// Runs `lsblk -ndo NAME,VENDOR,MODEL` to get human-readable device names.
@@ -174,17 +198,9 @@ impl StorageMonitor {
         // -n: no header, -d: no partition info, -o: output columns
         if let Ok(output) = Command::new("lsblk")
             .args(&["-ndo", "NAME,VENDOR,MODEL"])
-            .output() {
+            .bounded_output(exec::QUICK_TIMEOUT) {
             if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                for line in stdout.lines() {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 2 {
-                        let device = parts[0].to_string();
-                        let vendor_and_model = parts[1..].join(" ");
-                        models.insert(device, vendor_and_model.trim().to_string());
-                    }
-                }
+                models = parse_lsblk(&String::from_utf8_lossy(&output.stdout));
             }
         }
         
@@ -232,30 +248,7 @@ impl StorageMonitor {
         
         for disk in &self.disks {
             let mount_point = disk.mount_point().to_string_lossy().to_string();
-            
-            // ================================================================
-            // Mount Point Filtering
-            // ================================================================
-            // Skip non-meaningful mount points
-            // Only show root, /home, and top-level /mnt or /media mounts
-            let is_root = mount_point == "/";
-            let is_home = mount_point == "/home";
-            let is_top_level_mount = mount_point.starts_with("/mnt/") || mount_point.starts_with("/media/");
-            
-            // Skip boot partitions, snap mounts, and other system partitions
-            if mount_point.starts_with("/boot") 
-                || mount_point.starts_with("/snap")
-                || mount_point.starts_with("/run")
-                || mount_point.starts_with("/sys")
-                || mount_point.starts_with("/proc")
-                || mount_point.starts_with("/dev")
-                || mount_point.starts_with("/tmp")
-                || mount_point.starts_with("/var/snap") {
-                continue;
-            }
-            
-            // Only include root, /home, or external mounts
-            if !is_root && !is_home && !is_top_level_mount {
+            if !is_shown_mount(&mount_point) {
                 continue;
             }
             
@@ -274,23 +267,9 @@ impl StorageMonitor {
             // ================================================================
             // Device Name Resolution
             // ================================================================
-            // Get the device name (e.g., sda, nvme0n1, sdb)
+            // Get the device name (e.g., /dev/sda1, /dev/nvme0n1p1)
             let device_name = disk.name().to_string_lossy().to_string();
-            
-            // Extract the base disk name (without partition number)
-            // e.g., /dev/sda1 -> sda, /dev/nvme0n1p1 -> nvme0n1
-            let base_device = if let Some(dev) = device_name.strip_prefix("/dev/") {
-                // Remove partition numbers
-                if dev.contains("nvme") || dev.contains("mmcblk") {
-                    // NVMe or MMC devices: nvme0n1p1 -> nvme0n1
-                    dev.split('p').next().unwrap_or(dev)
-                } else {
-                    // Regular devices: sda1 -> sda
-                    dev.trim_end_matches(|c: char| c.is_ascii_digit())
-                }
-            } else {
-                &device_name
-            };
+            let base_device = base_device(&device_name);
             
             // ================================================================
             // Display Name Resolution
@@ -323,6 +302,7 @@ impl StorageMonitor {
                 used_percentage,
                 total_space: total,
                 available_space: available,
+                inode_percentage: inode_percentage(&mount_point),
                 is_loading: false,
             });
         }
@@ -330,9 +310,111 @@ impl StorageMonitor {
         // Update cache after first successful update
         // This saves disk names for instant display on next startup
         if self.is_first_update && !self.disk_info.is_empty() {
-            let mut cache = super::cache::WidgetCache::load();
-            cache.update_disks(&self.disk_info);
+            super::cache::WidgetCache::update_disks(&self.disk_info);
             self.is_first_update = false;
         }
     }
 }
+
+impl Drop for StorageMonitor {
+    fn drop(&mut self) {
+        self.heartbeat.stop();
+    }
+}
+
+// ============================================================================
+// Parsing Helpers
+// ============================================================================
+
+/// Device → vendor and model from `lsblk -ndo NAME,VENDOR,MODEL` output.
+fn parse_lsblk(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let device = parts.next()?;
+            let vendor_and_model = parts.collect::<Vec<_>>().join(" ");
+            (!vendor_and_model.is_empty()).then(|| (device.to_string(), vendor_and_model))
+        })
+        .collect()
+}
+
+/// Percentage of inodes in use on a mount, `None` if the filesystem has
+/// no fixed inode count or can't be queried.
+fn inode_percentage(mount_point: &str) -> Option<f32> {
+    let path = std::ffi::CString::new(mount_point).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    inode_usage(stat.f_files, stat.f_ffree)
+}
+
+/// Used share of `total` inodes with `free` left (btrfs and some network
+/// filesystems report a total of 0).
+fn inode_usage(total: u64, free: u64) -> Option<f32> {
+    if total == 0 {
+        return None;
+    }
+    Some(total.saturating_sub(free) as f32 / total as f32 * 100.0)
+}
+
+/// Whether a mount point is listed: root, `/home`, and mounts under
+/// `/mnt` or `/media` (system mounts like `/boot` or `/run` are skipped).
+fn is_shown_mount(mount_point: &str) -> bool {
+    let is_root = mount_point == "/";
+    let is_home = mount_point == "/home";
+    let is_top_level_mount = mount_point.starts_with("/mnt/") || mount_point.starts_with("/media/");
+    is_root || is_home || is_top_level_mount
+}
+
+/// Disk of a partition device, for the model lookup.
+///
+/// `/dev/sda1` → `sda`, `/dev/nvme0n1p1` → `nvme0n1`, `/dev/mmcblk0p1` → `mmcblk0`.
+fn base_device(device_name: &str) -> &str {
+    let Some(dev) = device_name.strip_prefix("/dev/") else {
+        return device_name;
+    };
+    if dev.contains("nvme") || dev.contains("mmcblk") {
+        // NVMe or MMC devices: nvme0n1p1 -> nvme0n1
+        dev.split('p').next().unwrap_or(dev)
+    } else {
+        // Regular devices: sda1 -> sda
+        dev.trim_end_matches(|c: char| c.is_ascii_digit())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsblk() {
+        let models = parse_lsblk("nvme0n1      Samsung SSD 970 EVO Plus 1TB\nsda    ATA      WDC WD10EZEX-00B\nloop0\n");
+        assert_eq!(models.get("nvme0n1").map(String::as_str), Some("Samsung SSD 970 EVO Plus 1TB"));
+        assert_eq!(models.get("sda").map(String::as_str), Some("ATA WDC WD10EZEX-00B"));
+        assert!(!models.contains_key("loop0"));
+    }
+
+    #[test]
+    fn test_mounts_and_devices() {
+        assert!(is_shown_mount("/"));
+        assert!(is_shown_mount("/home"));
+        assert!(is_shown_mount("/media/alice/USB"));
+        assert!(!is_shown_mount("/boot/efi"));
+        assert!(!is_shown_mount("/run/media/alice/USB"));
+        assert!(!is_shown_mount("/home/alice"));
+
+        assert_eq!(base_device("/dev/sda1"), "sda");
+        assert_eq!(base_device("/dev/nvme0n1p2"), "nvme0n1");
+        assert_eq!(base_device("/dev/mmcblk0p1"), "mmcblk0");
+        assert_eq!(base_device("tmpfs"), "tmpfs");
+    }
+
+    #[test]
+    fn test_inode_usage() {
+        assert_eq!(inode_usage(1000, 250), Some(75.0));
+        assert_eq!(inode_usage(1000, 1000), Some(0.0));
+        assert_eq!(inode_usage(0, 0), None);
+    }
+}
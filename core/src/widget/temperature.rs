@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Temperature Monitoring Module
+//!
+//! This module monitors CPU and GPU temperatures using the `sysinfo` crate's
+//! hardware sensor interface. It provides real-time temperature readings and
+//! visual gauge rendering.
+//!
+//! ## Data Sources
+//!
+//! Temperature data comes from Linux hwmon subsystem via sysinfo:
+//! - **CPU**: Looks for sensors labeled "cpu", "package", "core", "tctl", or "tdie"
+//! - **GPU**: Looks for sensors labeled "gpu", "nvidia", "amd", "radeon", or "edge"
+//!
+//! Once it follows the GPU the usage is read from
+//! ([`TemperatureMonitor::follow_gpu`]), the GPU temperature comes from that
+//! card's own hwmon chip, or from nvidia-smi for the proprietary NVIDIA
+//! driver. Label matching is then only a fallback, and skips the chips of
+//! the other cards, so a hybrid laptop doesn't show its iGPU's temperature
+//! next to the discrete GPU's usage.
+//!
+//! ## Sensor Labels by Vendor
+//!
+//! - **Intel CPU**: "coretemp" driver, labels like "Package id 0", "Core 0"
+//! - **AMD CPU**: "k10temp" driver, labels like "Tctl", "Tdie", "Tccd1"
+//! - **NVIDIA GPU**: "nvidia" driver, label "GPU"
+//! - **AMD GPU**: "amdgpu" driver, label "edge"
+//!
+//! ## Hottest Core and Throttling
+//!
+//! Every update also keeps the hottest per-core sensor (Intel "Core N",
+//! AMD "TccdN"), which can run well above the package temperature under a
+//! single-threaded load. Throttle detection (see [`super::throttle`]) only
+//! runs while it is shown ([`TemperatureMonitor::set_detect_throttling`]).
+//!
+//! ## Visual Representation
+//!
+//! Temperatures are displayed as circular gauges with:
+//! - Hollow ring that fills based on temperature ratio
+//! - Color coding: Green (<50%), Yellow (50-80%), Red (>80%)
+//! - Outlined border (the text outline color) for visibility on any background
+
+use std::sync::{Arc, Mutex};
+
+use sysinfo::Components;
+
+use super::gpu_card::{self, GpuCard};
+use super::theme::set_text_outline;
+use super::throttle::ThrottleDetector;
+use super::utilization::UtilizationMonitor;
+
+// ============================================================================
+// Temperature Monitor Struct
+// ============================================================================
+
+/// Temperature of the hottest CPU core (or AMD CCD).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreReading {
+    /// Sensor label without the chip name ("Core 3", "Tccd1")
+    pub label: String,
+    /// Temperature in Celsius
+    pub temp: f32,
+}
+
+/// Monitors CPU and GPU temperatures via sysinfo.
+///
+/// Uses the sysinfo crate to query Linux hwmon sensors. The monitor maintains
+/// a list of all hardware components and searches for temperature sensors
+/// matching known CPU and GPU patterns.
+///
+/// # Example Labels Matched
+///
+/// - CPU: "Package id 0", "Core 0", "Tctl", "Tdie"
+/// - GPU: "nvidia", "edge", "radeon"
+///
+/// # Fields
+///
+/// - `components`: sysinfo's hardware component list
+/// - `cpu_temp`: Last read CPU temperature in Celsius
+/// - `gpu_temp`: Last read GPU temperature in Celsius
+pub struct TemperatureMonitor {
+    /// Hardware component list from sysinfo (includes all sensors)
+    components: Components,
+    /// Current CPU temperature in Celsius (0.0 if not found)
+    pub cpu_temp: f32,
+    /// Current GPU temperature in Celsius (0.0 if not found)
+    pub gpu_temp: f32,
+    /// Whether a CPU sensor matched on the last update
+    cpu_sensor_found: bool,
+    /// Whether a GPU sensor matched on the last update
+    gpu_sensor_found: bool,
+    /// Label of the sensor used for the CPU temperature
+    cpu_sensor_label: Option<String>,
+    /// Label of the sensor used for the GPU temperature
+    gpu_sensor_label: Option<String>,
+    /// Card the GPU usage is read from (see [`Self::follow_gpu`])
+    gpu_card: Option<GpuCard>,
+    /// GPU temperature nvidia-smi reported along with the usage
+    reported_gpu_temp: Arc<Mutex<Option<f32>>>,
+    /// hwmon chip names of the other cards, never used for the GPU
+    other_gpu_chips: Vec<String>,
+    /// Hottest per-core sensor (None on CPUs without per-core sensors)
+    pub hottest_core: Option<CoreReading>,
+    /// Whether the CPU throttled since the previous update
+    pub throttling: bool,
+    /// Throttle counter state (None while detection is off)
+    throttle: Option<ThrottleDetector>,
+    /// Whether `update()` has run at least once
+    sampled: bool,
+}
+
+impl TemperatureMonitor {
+    /// Create a new temperature monitor.
+    ///
+    /// Initializes sysinfo's component list with an immediate refresh.
+    /// This discovers all available hardware sensors on the system.
+    pub fn new() -> Self {
+        Self {
+            components: Components::new_with_refreshed_list(),
+            cpu_temp: 0.0,
+            gpu_temp: 0.0,
+            cpu_sensor_found: false,
+            gpu_sensor_found: false,
+            cpu_sensor_label: None,
+            gpu_sensor_label: None,
+            gpu_card: None,
+            reported_gpu_temp: Arc::new(Mutex::new(None)),
+            other_gpu_chips: Vec::new(),
+            hottest_core: None,
+            throttling: false,
+            throttle: None,
+            sampled: false,
+        }
+    }
+    
+    /// Start or stop detecting thermal throttling.
+    pub fn set_detect_throttling(&mut self, detect: bool) {
+        if detect != self.throttle.is_some() {
+            self.throttle = detect.then(ThrottleDetector::default);
+            self.throttling = false;
+        }
+    }
+    
+    /// Read the GPU temperature of the card `utilization` reads the GPU
+    /// usage from, so both describe the same GPU.
+    pub fn follow_gpu(&mut self, utilization: &UtilizationMonitor) {
+        self.gpu_card = utilization.gpu_card().cloned();
+        self.reported_gpu_temp = utilization.reported_gpu_temperature();
+        self.other_gpu_chips = match &self.gpu_card {
+            Some(selected) => gpu_card::cards()
+                .iter()
+                .filter(|card| card.name != selected.name)
+                .filter_map(GpuCard::hwmon_chip)
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+
+    /// Update temperature readings from hardware sensors.
+    ///
+    /// Refreshes sysinfo's component data, then searches for CPU and GPU
+    /// temperature sensors by matching against known label patterns.
+    ///
+    /// # CPU Detection Priority
+    ///
+    /// Matches first sensor containing (case-insensitive):
+    /// 1. "cpu" - Generic CPU label
+    /// 2. "package" - Intel package temperature
+    /// 3. "core" - Individual core temperature
+    /// 4. "tctl" - AMD Ryzen control temperature
+    /// 5. "tdie" - AMD Ryzen die temperature
+    ///
+    /// # GPU Detection Priority
+    ///
+    /// The followed card's hwmon chip, then the temperature nvidia-smi
+    /// reported for it, then the first sensor not belonging to another card
+    /// containing (case-insensitive):
+    /// 1. "gpu" - Generic GPU label
+    /// 2. "nvidia" - NVIDIA GPU
+    /// 3. "amd" - AMD GPU
+    /// 4. "radeon" - AMD Radeon (older naming)
+    /// 5. "edge" - AMD RDNA/Vega edge sensor
+    pub fn update(&mut self) {
+        // Refresh all component data from hwmon
+        self.components.refresh();
+        
+        // Try to find CPU temperature
+        // Search through all components for first matching CPU sensor
+        self.cpu_temp = 0.0;
+        self.cpu_sensor_found = false;
+        self.cpu_sensor_label = None;
+        for component in &self.components {
+            let label = component.label().to_lowercase();
+            if label.contains("cpu") || label.contains("package") || label.contains("core") 
+                || label.contains("tctl") || label.contains("tdie") {
+                self.cpu_temp = component.temperature();
+                self.cpu_sensor_found = true;
+                self.cpu_sensor_label = Some(component.label().to_string());
+                break;
+            }
+        }
+        
+        // Try to find GPU temperature
+        // Search through all components for first matching GPU sensor
+        self.gpu_temp = 0.0;
+        self.gpu_sensor_found = false;
+        self.gpu_sensor_label = None;
+        let card_reading = self.gpu_card.as_ref().and_then(|card| {
+            card.temperature().or_else(|| {
+                let reported = (*self.reported_gpu_temp.lock().unwrap())?;
+                Some((reported, format!("nvidia-smi ({})", card.name)))
+            })
+        });
+        if let Some((temp, label)) = card_reading {
+            self.gpu_temp = temp;
+            self.gpu_sensor_found = true;
+            self.gpu_sensor_label = Some(label);
+        } else {
+            for component in &self.components {
+                // Sensor labels start with the chip name ("amdgpu edge")
+                if self.other_gpu_chips.iter().any(|chip| component.label().starts_with(chip.as_str())) {
+                    continue;
+                }
+                let label = component.label().to_lowercase();
+                if label.contains("gpu") || label.contains("nvidia") || label.contains("amd") 
+                    || label.contains("radeon") || label.contains("edge") {
+                    self.gpu_temp = component.temperature();
+                    self.gpu_sensor_found = true;
+                    self.gpu_sensor_label = Some(component.label().to_string());
+                    break;
+                }
+            }
+        }
+        
+        self.hottest_core = hottest_core(self.components.iter().map(|c| (c.label(), c.temperature())));
+        if let Some(throttle) = &mut self.throttle {
+            self.throttling = throttle.update();
+        }
+        self.sampled = true;
+    }
+    
+    /// Whether sensors have been read yet (before that, missing sensors
+    /// aren't an error).
+    pub fn has_sample(&self) -> bool {
+        self.sampled
+    }
+    
+    /// CPU temperature in Celsius, or `None` if no CPU sensor was found.
+    pub fn cpu_reading(&self) -> Option<f32> {
+        self.cpu_sensor_found.then_some(self.cpu_temp)
+    }
+    
+    /// GPU temperature in Celsius, or `None` if no GPU sensor was found.
+    pub fn gpu_reading(&self) -> Option<f32> {
+        self.gpu_sensor_found.then_some(self.gpu_temp)
+    }
+    
+    /// Missing-sensor error for the enabled temperature readings, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `want_cpu` - CPU temperature display is enabled
+    /// * `want_gpu` - GPU temperature display is enabled
+    pub fn error(&self, want_cpu: bool, want_gpu: bool) -> Option<String> {
+        if !self.sampled {
+            return None;
+        }
+        match (want_cpu && !self.cpu_sensor_found, want_gpu && !self.gpu_sensor_found) {
+            (true, true) => Some(String::from("No CPU or GPU temperature sensor found")),
+            (true, false) => Some(String::from("No CPU temperature sensor found")),
+            (false, true) => Some(String::from("No GPU temperature sensor found")),
+            (false, false) => None,
+        }
+    }
+    
+    /// Labels of all hardware sensors discovered by sysinfo.
+    ///
+    /// Used by the diagnostics page to show why a temperature might be 0.
+    pub fn sensor_labels(&self) -> Vec<String> {
+        self.components.iter().map(|c| c.label().to_string()).collect()
+    }
+    
+    /// Labels of the sensors picked for the CPU and GPU temperature.
+    ///
+    /// Shown on the diagnostics page next to the full hwmon listing.
+    pub fn matched_sensors(&self) -> (Option<String>, Option<String>) {
+        (self.cpu_sensor_label.clone(), self.gpu_sensor_label.clone())
+    }
+}
+
+/// The hottest per-core sensor among `(label, temperature)` readings.
+///
+/// Matches coretemp's "Core N" and k10temp's "TccdN"; the chip name in
+/// front of the label ("coretemp Core 3") is left out.
+fn hottest_core<'a>(readings: impl Iterator<Item = (&'a str, f32)>) -> Option<CoreReading> {
+    readings
+        .filter_map(|(label, temp)| {
+            let sensor = label.split_once(' ').map_or(label, |(_, sensor)| sensor);
+            let lower = sensor.to_lowercase();
+            (lower.starts_with("core ") || lower.starts_with("tccd")).then(|| CoreReading {
+                label: sensor.to_string(),
+                temp,
+            })
+        })
+        .filter(|reading| reading.temp.is_finite())
+        .max_by(|a, b| a.temp.total_cmp(&b.temp))
+}
+
+// ============================================================================
+// Drawing Helper Function
+// ============================================================================
+
+/// Draw a circular temperature gauge with color-coded progress ring.
+///
+/// Renders a hollow circular gauge that fills based on the temperature
+/// relative to a maximum value. The ring is drawn in `color`, the level
+/// color the caller picked from the temperature thresholds (green, yellow
+/// or red by default).
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context for drawing
+/// * `x` - Left edge X coordinate
+/// * `y` - Top edge Y coordinate
+/// * `radius` - Radius of the gauge circle
+/// * `temp` - Current temperature in Celsius
+/// * `max_temp` - Maximum temperature for full circle (e.g., 100.0)
+/// * `color` - Ring color
+///
+/// # Visual Structure
+///
+/// ```text
+/// ┌─────────────────┐
+/// │    ╭─────╮      │  Outer border (black)
+/// │   ╱  ███  ╲     │  Background ring (dark gray)
+/// │  │  ███   │     │  Progress arc (green/yellow/red)
+/// │   ╲      ╱      │  Inner border (black)
+/// │    ╰─────╯      │
+/// └─────────────────┘
+/// ```
+pub fn draw_temp_circle(cr: &cairo::Context, x: f64, y: f64, radius: f64, temp: f32, max_temp: f32, color: (f64, f64, f64)) {
+    let center_x = x + radius;
+    let center_y = y + radius;
+    
+    let (r, g, b) = color;
+    
+    // Draw outer ring (background)
+    cr.arc(center_x, center_y, radius, 0.0, 2.0 * std::f64::consts::PI);
+    cr.set_source_rgba(0.2, 0.2, 0.2, 0.7);
+    cr.set_line_width(8.0);
+    cr.stroke().ok();
+    
+    // Draw inner colored ring based on temperature
+    let angle = (temp / max_temp).min(1.0) as f64 * 2.0 * std::f64::consts::PI;
+    cr.arc(center_x, center_y, radius, -std::f64::consts::PI / 2.0, -std::f64::consts::PI / 2.0 + angle);
+    cr.set_source_rgb(r, g, b);
+    cr.set_line_width(8.0);
+    cr.stroke().ok();
+    
+    // Draw border around the ring
+    cr.arc(center_x, center_y, radius + 4.0, 0.0, 2.0 * std::f64::consts::PI);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke().ok();
+    
+    cr.arc(center_x, center_y, radius - 4.0, 0.0, 2.0 * std::f64::consts::PI);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke().ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hottest_core() {
+        let readings = [("coretemp Package id 0", 70.0), ("coretemp Core 0", 64.0), ("coretemp Core 3", 88.0), ("nvme Composite", 95.0)];
+        assert_eq!(
+            hottest_core(readings.into_iter()),
+            Some(CoreReading { label: String::from("Core 3"), temp: 88.0 })
+        );
+        let amd = [("k10temp Tctl", 80.0), ("k10temp Tccd1", 76.5), ("k10temp Tccd2", 71.0)];
+        assert_eq!(hottest_core(amd.into_iter()).map(|core| core.label), Some(String::from("Tccd1")));
+        assert_eq!(hottest_core([("acpitz", 40.0)].into_iter()), None);
+    }
+}
@@ -11,6 +11,7 @@
 //! - `com.system76.CosmicTheme.Mode/v1/is_dark` - Boolean for dark/light mode
 //! - `com.system76.CosmicTheme.Dark/v1/accent` - Dark theme accent color
 //! - `com.system76.CosmicTheme.Light/v1/accent` - Light theme accent color
+//! - `com.system76.CosmicTheme.Dark/v1/corner_radii` - Corner radii (same for Light)
 //!
 //! ## Color Format
 //!
@@ -22,10 +23,14 @@
 //! If theme files cannot be read, sensible defaults are used:
 //! - Dark mode: true (matches COSMIC default)
 //! - Accent color: Blue (#6699FF / RGB 0.4, 0.6, 1.0)
+//! - Corner radius: 8px (COSMIC's default `radius_s`)
 
+use std::cell::Cell;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::config::TextContrast;
+
 /// RGBA color with components in 0.0-1.0 range
 #[derive(Debug, Clone, Copy)]
 pub struct ThemeColor {
@@ -47,6 +52,82 @@ impl Default for ThemeColor {
     }
 }
 
+/// Fill and outline of text drawn straight onto the wallpaper.
+///
+/// Picked from [`TextContrast`] and the theme mode by
+/// [`CosmicTheme::text_palette`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextPalette {
+    /// Glyph color
+    pub fill: (f64, f64, f64),
+    /// Glyph color of secondary text (dimmed rows, small details)
+    pub muted: (f64, f64, f64),
+    /// Outline stroked around the glyphs
+    pub outline: (f64, f64, f64),
+}
+
+impl TextPalette {
+    /// White text with a black outline (the classic widget look)
+    pub const LIGHT: Self = Self {
+        fill: (1.0, 1.0, 1.0),
+        muted: (0.75, 0.75, 0.75),
+        outline: (0.0, 0.0, 0.0),
+    };
+
+    /// Dark text with a white outline, for light wallpapers
+    pub const DARK: Self = Self {
+        fill: (0.1, 0.1, 0.1),
+        muted: (0.35, 0.35, 0.35),
+        outline: (1.0, 1.0, 1.0),
+    };
+}
+
+impl Default for TextPalette {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
+thread_local! {
+    /// Palette of the frame being drawn, set once per frame by the renderer
+    /// so the many small drawing helpers don't each need it passed in.
+    static TEXT_PALETTE: Cell<TextPalette> = const { Cell::new(TextPalette::LIGHT) };
+}
+
+impl TextPalette {
+    /// Draw the following text and icons with this palette.
+    pub fn activate(self) {
+        TEXT_PALETTE.set(self);
+    }
+
+    /// Palette of the frame being drawn, for text that needs its colors
+    /// with an alpha (e.g., fading in).
+    pub fn active() -> Self {
+        TEXT_PALETTE.get()
+    }
+}
+
+/// Set the source to the active palette's text color.
+pub fn set_text_fill(cr: &cairo::Context) {
+    let (r, g, b) = TEXT_PALETTE.get().fill;
+    cr.set_source_rgb(r, g, b);
+}
+
+/// Set the source to the active palette's secondary text color.
+pub fn set_text_muted(cr: &cairo::Context) {
+    let (r, g, b) = TEXT_PALETTE.get().muted;
+    cr.set_source_rgb(r, g, b);
+}
+
+/// Set the source to the active palette's outline color.
+pub fn set_text_outline(cr: &cairo::Context) {
+    let (r, g, b) = TEXT_PALETTE.get().outline;
+    cr.set_source_rgb(r, g, b);
+}
+
+/// Corner radius used when the theme's radii can't be read (pixels).
+const DEFAULT_CORNER_RADIUS: f64 = 8.0;
+
 /// Theme information read from COSMIC configuration
 #[derive(Debug, Clone)]
 pub struct CosmicTheme {
@@ -56,6 +137,8 @@ pub struct CosmicTheme {
     pub accent: ThemeColor,
     /// Accent color with reduced opacity for backgrounds
     pub accent_bg: ThemeColor,
+    /// Small corner radius (`radius_s`) in pixels, as used for cards
+    pub corner_radius: f64,
 }
 
 impl Default for CosmicTheme {
@@ -68,6 +151,7 @@ impl Default for CosmicTheme {
                 alpha: 0.6,
                 ..accent
             },
+            corner_radius: DEFAULT_CORNER_RADIUS,
         }
     }
 }
@@ -98,12 +182,15 @@ impl CosmicTheme {
             ..theme.accent
         };
         
+        theme.corner_radius = Self::read_corner_radius(&config_dir, theme.is_dark);
+        
         log::info!(
-            "Loaded COSMIC theme: is_dark={}, accent=({:.2}, {:.2}, {:.2})",
+            "Loaded COSMIC theme: is_dark={}, accent=({:.2}, {:.2}, {:.2}), corner radius={}",
             theme.is_dark,
             theme.accent.red,
             theme.accent.green,
-            theme.accent.blue
+            theme.accent.blue,
+            theme.corner_radius
         );
         
         theme
@@ -150,6 +237,50 @@ impl CosmicTheme {
         }
     }
     
+    /// Read the small corner radius from the appropriate theme config
+    fn read_corner_radius(config_dir: &PathBuf, is_dark: bool) -> f64 {
+        let theme_name = if is_dark {
+            "com.system76.CosmicTheme.Dark"
+        } else {
+            "com.system76.CosmicTheme.Light"
+        };
+        
+        let radii_path = config_dir
+            .join(theme_name)
+            .join("v1")
+            .join("corner_radii");
+        
+        match fs::read_to_string(&radii_path) {
+            Ok(content) => Self::parse_corner_radius(&content).unwrap_or(DEFAULT_CORNER_RADIUS),
+            Err(e) => {
+                log::debug!("Could not read corner radii from {:?}: {}", radii_path, e);
+                DEFAULT_CORNER_RADIUS
+            }
+        }
+    }
+    
+    /// Parse `radius_s` from the RON-format corner radii configuration.
+    ///
+    /// The format looks like:
+    /// ```ron
+    /// (
+    ///     radius_0: (0.0, 0.0, 0.0, 0.0),
+    ///     radius_xs: (4.0, 4.0, 4.0, 4.0),
+    ///     radius_s: (8.0, 8.0, 8.0, 8.0),
+    ///     ...
+    /// )
+    /// ```
+    ///
+    /// The four values are the corners; the first (top left) is used for
+    /// all of them.
+    fn parse_corner_radius(content: &str) -> Option<f64> {
+        let start = content.find("radius_s:")?;
+        let corners = &content[start..];
+        let paren = corners.find('(')?;
+        let first = corners[paren + 1..].split(',').next()?;
+        first.trim().parse::<f64>().ok().filter(|radius| *radius >= 0.0)
+    }
+    
     /// Parse the RON-format accent color configuration.
     ///
     /// The format looks like:
@@ -230,6 +361,18 @@ impl CosmicTheme {
         }
     }
     
+    /// Get the palette for text drawn straight onto the wallpaper.
+    ///
+    /// [`TextContrast::Auto`] follows the theme mode, assuming a light
+    /// wallpaper goes with light mode.
+    pub fn text_palette(&self, contrast: TextContrast) -> TextPalette {
+        if contrast.dark_text(self.is_dark) {
+            TextPalette::DARK
+        } else {
+            TextPalette::LIGHT
+        }
+    }
+    
     /// Get secondary/muted text color appropriate for the current theme mode.
     pub fn secondary_text_color(&self) -> (f64, f64, f64) {
         if self.is_dark {
@@ -305,10 +448,25 @@ mod tests {
         assert!((color.alpha - 1.0).abs() < 0.001);
     }
     
+    #[test]
+    fn test_parse_corner_radius() {
+        let content = "(\n    radius_0: (0.0, 0.0, 0.0, 0.0),\n    radius_xs: (4.0, 4.0, 4.0, 4.0),\n    radius_s: (2.0, 2.0, 2.0, 2.0),\n    radius_m: (16.0, 16.0, 16.0, 16.0),\n)";
+        assert_eq!(CosmicTheme::parse_corner_radius(content), Some(2.0));
+        assert_eq!(CosmicTheme::parse_corner_radius("()"), None);
+    }
+    
     #[test]
     fn test_default_theme() {
         let theme = CosmicTheme::default();
         assert!(theme.is_dark);
         assert!((theme.accent.red - 0.4).abs() < 0.001);
     }
+    
+    #[test]
+    fn test_text_palette() {
+        let light_mode = CosmicTheme { is_dark: false, ..CosmicTheme::default() };
+        assert_eq!(light_mode.text_palette(TextContrast::Auto), TextPalette::DARK);
+        assert_eq!(light_mode.text_palette(TextContrast::Light), TextPalette::LIGHT);
+        assert_eq!(CosmicTheme::default().text_palette(TextContrast::Auto), TextPalette::LIGHT);
+    }
 }
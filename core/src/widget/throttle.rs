@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Thermal Throttling
+//!
+//! Whether the CPU is being slowed down to keep its temperature in check,
+//! from two kernel sources:
+//!
+//! | Source                                                                 | Meaning                                   |
+//! |------------------------------------------------------------------------|-------------------------------------------|
+//! | `/sys/devices/system/cpu/cpu<N>/thermal_throttle/*_throttle_count`     | Intel: throttle events since boot         |
+//! | `/sys/class/hwmon/hwmon<N>/temp<M>_{max,crit}_alarm` of the CPU chip    | Set while a sensor is above its limit     |
+//!
+//! The Intel counters only ever grow, so the CPU counts as throttling when
+//! their sum went up since the previous read. AMD CPUs have no counters;
+//! there only the alarms of `k10temp`/`zenpower` apply, where the driver
+//! provides them.
+
+use std::path::Path;
+
+/// Where the per-CPU throttle counters live.
+const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Where the hwmon chips are listed.
+const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// hwmon chips of CPUs whose alarms count as throttling.
+const CPU_CHIPS: &[&str] = &["coretemp", "k10temp", "zenpower"];
+
+/// Tracks the throttle counters between reads.
+#[derive(Debug, Default)]
+pub struct ThrottleDetector {
+    /// Sum of all counters at the previous read (None without counters)
+    last_count: Option<u64>,
+}
+
+impl ThrottleDetector {
+    /// Read the counters and alarms.
+    ///
+    /// # Returns
+    ///
+    /// Whether the CPU throttled since the previous call
+    pub fn update(&mut self) -> bool {
+        let count = throttle_count();
+        let counted = counter_grew(self.last_count, count);
+        self.last_count = count;
+        counted || alarm_raised()
+    }
+}
+
+/// Whether the counter sum went up between two reads.
+fn counter_grew(last: Option<u64>, now: Option<u64>) -> bool {
+    matches!((last, now), (Some(last), Some(now)) if now > last)
+}
+
+/// Sum of every core and package throttle counter (None if there are none).
+fn throttle_count() -> Option<u64> {
+    let mut total = None;
+    for entry in std::fs::read_dir(CPU_ROOT).ok()?.flatten() {
+        let dir = entry.path().join("thermal_throttle");
+        for counter in ["core_throttle_count", "package_throttle_count"] {
+            if let Some(count) = read_number(&dir.join(counter)) {
+                *total.get_or_insert(0) += count;
+            }
+        }
+    }
+    total
+}
+
+/// Whether a CPU chip has a max or critical temperature alarm raised.
+fn alarm_raised() -> bool {
+    let Ok(chips) = std::fs::read_dir(HWMON_ROOT) else {
+        return false;
+    };
+    chips.flatten().any(|chip| {
+        let path = chip.path();
+        let is_cpu = std::fs::read_to_string(path.join("name")).is_ok_and(|name| CPU_CHIPS.contains(&name.trim()));
+        is_cpu
+            && std::fs::read_dir(&path).is_ok_and(|files| {
+                files.flatten().any(|file| {
+                    let name = file.file_name();
+                    let name = name.to_string_lossy();
+                    name.starts_with("temp")
+                        && (name.ends_with("_max_alarm") || name.ends_with("_crit_alarm"))
+                        && read_number(&file.path()) == Some(1)
+                })
+            })
+    })
+}
+
+/// Read a sysfs file holding one number.
+fn read_number(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_grew() {
+        assert!(counter_grew(Some(10), Some(12)));
+        assert!(!counter_grew(Some(12), Some(12)));
+        // First read, or the counters went away
+        assert!(!counter_grew(None, Some(12)));
+        assert!(!counter_grew(Some(12), None));
+    }
+}
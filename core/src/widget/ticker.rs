@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Ticker
+//!
+//! Prices and 24h changes of a few cryptocurrencies or stocks, refreshed on
+//! a configurable interval. Quotes come from a [`QuoteProvider`]:
+//!
+//! ```text
+//! CoinGecko ──/simple/price?ids=bitcoin,ethereum──────┐
+//!                                                     ├──► rows() ◄── draw ("bitcoin  67012 USD  +1.2%")
+//! JSON endpoint ──GET {symbol} URL, once per symbol───┘
+//!                   refresher thread, every ticker_refresh_minutes
+//! ```
+//!
+//! CoinGecko needs no API key and converts to any currency it knows. The
+//! JSON endpoint covers stock APIs: the URL has `{symbol}` in place of the
+//! symbol, and two JSON pointers say where price and change are in the
+//! reply (`/c` and `/dp` for Finnhub's `/quote`).
+//!
+//! Failed refreshes keep the previous quotes and retry with exponential
+//! backoff (1 min doubling up to 30 min, honoring 429 `Retry-After`), since
+//! the free APIs rate limit eagerly.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::http::{get_json, Backoff, HttpResult};
+use crate::config::{Config, TickerProvider};
+
+/// How often the refresher checks whether a refresh is due.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Longest a request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First retry delay after a failed refresh.
+const RETRY_BASE: Duration = Duration::from_secs(60);
+
+/// Upper bound for the retry delay after repeated failures (30 minutes).
+const RETRY_MAX: Duration = Duration::from_secs(1800);
+
+/// CoinGecko's price API.
+const COINGECKO_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+/// What the refresher fetches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickerSettings {
+    /// Where quotes come from
+    pub provider: TickerProvider,
+    /// Symbols in config order, at most `ticker_max_symbols`
+    pub symbols: Vec<String>,
+    /// Lowercase currency code ("usd")
+    pub currency: String,
+    /// JSON endpoint URL with `{symbol}`
+    pub endpoint: String,
+    /// JSON pointer to the price
+    pub price_path: String,
+    /// JSON pointer to the 24h change (empty for none)
+    pub change_path: String,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn ticker_settings(config: &Config) -> Option<TickerSettings> {
+    config.show_ticker.then(|| TickerSettings {
+        provider: config.ticker_provider,
+        symbols: parse_symbols(&config.ticker_symbols, config.ticker_max_symbols as usize),
+        currency: config.ticker_currency.trim().to_lowercase(),
+        endpoint: config.ticker_endpoint.trim().to_string(),
+        price_path: config.ticker_price_path.trim().to_string(),
+        change_path: config.ticker_change_path.trim().to_string(),
+        refresh_minutes: config.ticker_refresh_minutes,
+    })
+}
+
+/// Price of one symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    /// Price in the configured currency
+    pub price: f64,
+    /// Change over the last 24 hours in percent, if the provider has it
+    pub change_percent: Option<f64>,
+}
+
+/// One symbol and its latest quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickerRow {
+    /// Symbol as configured ("bitcoin", "AAPL")
+    pub symbol: String,
+    /// Latest quote (None until one arrived)
+    pub quote: Option<Quote>,
+}
+
+/// Fetches quotes on a background thread.
+pub struct TickerMonitor {
+    /// What to fetch (`None` while hidden)
+    settings: Arc<Mutex<Option<TickerSettings>>>,
+    /// Latest quote per symbol
+    quotes: Arc<Mutex<HashMap<String, Quote>>>,
+    /// Why the last refresh (partly) failed, if it did
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl TickerMonitor {
+    /// Create the monitor and start its refresher thread.
+    pub fn new(settings: Option<TickerSettings>) -> Self {
+        let settings = Arc::new(Mutex::new(settings));
+        let quotes = Arc::new(Mutex::new(HashMap::new()));
+        let error = Arc::new(Mutex::new(None));
+
+        let thread_settings = Arc::clone(&settings);
+        let thread_quotes = Arc::clone(&quotes);
+        let thread_error = Arc::clone(&error);
+        std::thread::spawn(move || {
+            super::priority::enter_background("ticker");
+            let mut last_settings: Option<TickerSettings> = None;
+            // When the next refresh is due (None: right away)
+            let mut next_refresh: Option<Instant> = None;
+            let mut backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+            loop {
+                // Stop once the monitor was dropped
+                if Arc::strong_count(&thread_settings) == 1 {
+                    break;
+                }
+
+                let Some(settings) = thread_settings.lock().unwrap().clone() else {
+                    last_settings = None;
+                    thread_quotes.lock().unwrap().clear();
+                    *thread_error.lock().unwrap() = None;
+                    std::thread::sleep(TICK);
+                    continue;
+                };
+
+                // Refresh right away when the settings changed; quotes of
+                // another provider may be in another currency
+                if last_settings.as_ref() != Some(&settings) {
+                    if last_settings.as_ref().is_some_and(|previous| previous.provider != settings.provider) {
+                        thread_quotes.lock().unwrap().clear();
+                    }
+                    last_settings = Some(settings.clone());
+                    next_refresh = None;
+                    backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+                }
+
+                let due = next_refresh.is_none_or(|at| Instant::now() >= at);
+                if due && backoff.ready() && !settings.symbols.is_empty() {
+                    match provider(settings.provider).quotes(&settings) {
+                        Ok(quotes) => {
+                            backoff.record_success();
+                            let interval = Duration::from_secs(u64::from(settings.refresh_minutes.max(1)) * 60);
+                            next_refresh = Some(Instant::now() + interval);
+                            let missing: Vec<&str> = settings
+                                .symbols
+                                .iter()
+                                .filter(|symbol| !quotes.contains_key(*symbol))
+                                .map(String::as_str)
+                                .collect();
+                            *thread_error.lock().unwrap() =
+                                (!missing.is_empty()).then(|| format!("No quote for {}", missing.join(", ")));
+                            log::debug!("Ticker refreshed: {} quotes", quotes.len());
+                            *thread_quotes.lock().unwrap() = quotes;
+                        }
+                        Err(e) => {
+                            // Keep the previous quotes; the backoff decides when to retry
+                            let delay = backoff.record_failure(&e);
+                            log::warn!("Ticker refresh failed, retrying in {:?}: {}", delay, e);
+                            *thread_error.lock().unwrap() = Some(e.to_string());
+                        }
+                    }
+                }
+
+                std::thread::sleep(TICK);
+            }
+        });
+
+        Self { settings, quotes, error }
+    }
+
+    /// Replace what the refresher fetches (e.g., after a config change).
+    pub fn set_settings(&self, settings: Option<TickerSettings>) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    /// One row per configured symbol, in config order.
+    pub fn rows(&self) -> Vec<TickerRow> {
+        let Some(settings) = self.settings.lock().unwrap().clone() else {
+            return Vec::new();
+        };
+        let quotes = self.quotes.lock().unwrap();
+        settings
+            .symbols
+            .into_iter()
+            .map(|symbol| TickerRow {
+                quote: quotes.get(&symbol).copied(),
+                symbol,
+            })
+            .collect()
+    }
+
+    /// Why the last refresh (partly) failed, if it did.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+}
+
+// ============================================================================
+// Providers
+// ============================================================================
+
+/// A source of quotes.
+trait QuoteProvider {
+    /// Latest quotes of `settings.symbols`, keyed by symbol. Symbols the
+    /// provider doesn't know are left out.
+    fn quotes(&self, settings: &TickerSettings) -> HttpResult<HashMap<String, Quote>>;
+}
+
+/// The provider behind a config choice.
+fn provider(kind: TickerProvider) -> &'static dyn QuoteProvider {
+    match kind {
+        TickerProvider::CoinGecko => &CoinGecko,
+        TickerProvider::JsonEndpoint => &JsonEndpoint,
+    }
+}
+
+/// CoinGecko's `/simple/price`, all symbols in one request.
+struct CoinGecko;
+
+impl QuoteProvider for CoinGecko {
+    fn quotes(&self, settings: &TickerSettings) -> HttpResult<HashMap<String, Quote>> {
+        let ids: Vec<String> = settings.symbols.iter().map(|symbol| symbol.to_lowercase()).collect();
+        let url = format!(
+            "{}?ids={}&vs_currencies={}&include_24hr_change=true",
+            COINGECKO_URL,
+            ids.join(","),
+            settings.currency
+        );
+        let reply: Value = get_json(&url, &[], REQUEST_TIMEOUT)?;
+        Ok(parse_coingecko(&reply, &settings.symbols, &settings.currency))
+    }
+}
+
+/// A user-configured JSON API, one request per symbol.
+struct JsonEndpoint;
+
+impl QuoteProvider for JsonEndpoint {
+    fn quotes(&self, settings: &TickerSettings) -> HttpResult<HashMap<String, Quote>> {
+        let mut quotes = HashMap::new();
+        if settings.endpoint.is_empty() {
+            return Ok(quotes);
+        }
+        for symbol in &settings.symbols {
+            let url = settings.endpoint.replace("{symbol}", symbol);
+            let reply: Value = get_json(&url, &[], REQUEST_TIMEOUT)?;
+            if let Some(quote) = parse_json_quote(&reply, &settings.price_path, &settings.change_path) {
+                quotes.insert(symbol.clone(), quote);
+            }
+        }
+        Ok(quotes)
+    }
+}
+
+/// Quotes in a CoinGecko reply
+/// (`{"bitcoin": {"usd": 67012.0, "usd_24h_change": 1.2}}`).
+fn parse_coingecko(reply: &Value, symbols: &[String], currency: &str) -> HashMap<String, Quote> {
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let coin = reply.get(symbol.to_lowercase())?;
+            let quote = Quote {
+                price: coin.get(currency)?.as_f64()?,
+                change_percent: coin.get(format!("{}_24h_change", currency)).and_then(Value::as_f64),
+            };
+            Some((symbol.clone(), quote))
+        })
+        .collect()
+}
+
+/// Quote at two JSON pointers of a reply (numbers, or numbers in strings
+/// as some stock APIs send them).
+fn parse_json_quote(reply: &Value, price_path: &str, change_path: &str) -> Option<Quote> {
+    let number = |path: &str| -> Option<f64> {
+        let path = if path.starts_with('/') { path.to_string() } else { format!("/{}", path) };
+        match reply.pointer(&path)? {
+            Value::Number(number) => number.as_f64(),
+            Value::String(text) => text.trim().trim_end_matches('%').parse().ok(),
+            _ => None,
+        }
+    };
+    Some(Quote {
+        price: number(price_path)?,
+        change_percent: (!change_path.is_empty()).then(|| number(change_path)).flatten(),
+    })
+}
+
+/// Symbols of a comma-separated list, without blanks and duplicates.
+fn parse_symbols(list: &str, max: usize) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for symbol in list.split(',').map(str::trim).filter(|symbol| !symbol.is_empty()) {
+        if !symbols.iter().any(|known| known.eq_ignore_ascii_case(symbol)) {
+            symbols.push(symbol.to_string());
+        }
+    }
+    symbols.truncate(max);
+    symbols
+}
+
+/// Price with as many decimals as its size calls for
+/// ("67012", "3.42", "0.0812").
+pub fn format_price(price: f64) -> String {
+    if price.abs() >= 1000.0 {
+        format!("{:.0}", price)
+    } else if price.abs() >= 1.0 {
+        format!("{:.2}", price)
+    } else {
+        format!("{:.4}", price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quotes() {
+        let symbols = parse_symbols("Bitcoin, ethereum,, bitcoin, dogecoin", 3);
+        assert_eq!(symbols, ["Bitcoin", "ethereum", "dogecoin"]);
+
+        let reply: Value = serde_json::from_str(
+            r#"{"bitcoin":{"usd":67012.5,"usd_24h_change":-1.25},"ethereum":{"usd":3420.1}}"#,
+        )
+        .unwrap();
+        let quotes = parse_coingecko(&reply, &symbols, "usd");
+        assert_eq!(quotes["Bitcoin"], Quote { price: 67012.5, change_percent: Some(-1.25) });
+        assert_eq!(quotes["ethereum"].change_percent, None);
+        assert!(!quotes.contains_key("dogecoin"));
+
+        let reply: Value = serde_json::from_str(r#"{"c":189.5,"dp":0.8,"quote":{"price":"189.50","change":"-0.8%"}}"#).unwrap();
+        assert_eq!(parse_json_quote(&reply, "/c", "/dp"), Some(Quote { price: 189.5, change_percent: Some(0.8) }));
+        assert_eq!(
+            parse_json_quote(&reply, "quote/price", "quote/change"),
+            Some(Quote { price: 189.5, change_percent: Some(-0.8) })
+        );
+        assert_eq!(parse_json_quote(&reply, "/missing", ""), None);
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(format_price(67012.5), "67012");
+        assert_eq!(format_price(3.421), "3.42");
+        assert_eq!(format_price(0.08123), "0.0812");
+    }
+}
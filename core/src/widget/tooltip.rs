@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Hover Tooltips
+//!
+//! Resting the pointer on a metric for a moment shows the details behind
+//! it in a small panel drawn on the widget surface, above everything else:
+//!
+//! | Hovered            | Tooltip                                    |
+//! |--------------------|--------------------------------------------|
+//! | CPU bar            | Usage of every core                        |
+//! | CPU frequency row  | Package frequencies and governors          |
+//! | RAM bar            | Used, available and total memory           |
+//! | GPU bar            | Vendor and how usage is read               |
+//! | Temperature gauge  | Matched hwmon sensor                       |
+//! | Network rates      | Download/upload rate of every interface    |
+//! | Failed units row   | Names of the failed systemd units          |
+//! | DND bell           | Whether do not disturb is on               |
+//!
+//! The renderer reports where each metric was drawn ([`HoverTargetBounds`]);
+//! [`HoverTracker`] waits [`HOVER_DELAY`] after the pointer settles on one
+//! before the tooltip is drawn, so moving across the widget doesn't flash
+//! panels.
+
+use std::time::{Duration, Instant};
+
+use super::cpufreq::{format_ghz, CpuFrequency};
+use super::network::{format_rate, InterfaceRate};
+use super::power_profile::{profile_label, PowerProfiles};
+use crate::fl;
+
+/// How long the pointer must rest on a metric before its tooltip shows.
+pub const HOVER_DELAY: Duration = Duration::from_millis(500);
+
+/// Metric with a tooltip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoverTarget {
+    /// CPU usage bar
+    Cpu,
+    /// Frequency/governor row under the CPU bar (clickable)
+    CpuFrequency,
+    /// RAM usage bar
+    Memory,
+    /// GPU usage bar
+    Gpu,
+    /// CPU temperature gauge, line or badge
+    CpuTemp,
+    /// GPU temperature gauge, line or badge
+    GpuTemp,
+    /// Network rates
+    Network,
+    /// Failed units row of the system health section
+    SystemHealth,
+    /// Running count row of the containers section
+    Containers,
+    /// Do-not-disturb bell in the notifications header (clickable)
+    DoNotDisturb,
+    /// Power profile button in the Battery header (clickable)
+    PowerProfile,
+}
+
+/// Hover regions of the drawn metrics: (target, x_start, y_start, x_end, y_end)
+///
+/// Earlier entries win where regions overlap (a temperature badge on a bar).
+pub type HoverTargetBounds = Vec<(HoverTarget, f64, f64, f64, f64)>;
+
+/// Delays the tooltip of the metric under the pointer.
+#[derive(Debug, Default)]
+pub struct HoverTracker {
+    /// Metric under the pointer and since when
+    hovered: Option<(HoverTarget, Instant)>,
+    /// Whether its tooltip is drawn
+    shown: bool,
+}
+
+impl HoverTracker {
+    /// The pointer is now over `target` (or no metric).
+    ///
+    /// # Returns
+    ///
+    /// Whether a drawn tooltip was hidden, so the widget needs a redraw
+    pub fn hover(&mut self, target: Option<HoverTarget>, now: Instant) -> bool {
+        if self.hovered.map(|(current, _)| current) == target {
+            return false;
+        }
+        self.hovered = target.map(|target| (target, now));
+        std::mem::take(&mut self.shown)
+    }
+
+    /// Show the tooltip once the pointer rested long enough.
+    ///
+    /// # Returns
+    ///
+    /// Whether the tooltip just became visible, so the widget needs a redraw
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let Some((_, since)) = self.hovered else {
+            return false;
+        };
+        if self.shown || now.duration_since(since) < HOVER_DELAY {
+            return false;
+        }
+        self.shown = true;
+        true
+    }
+
+    /// Metric whose tooltip is drawn.
+    pub fn shown(&self) -> Option<HoverTarget> {
+        self.hovered.filter(|_| self.shown).map(|(target, _)| target)
+    }
+}
+
+// ============================================================================
+// Tooltip Text
+// ============================================================================
+
+/// Usage of every core, two cores per line.
+pub fn core_lines(core_usage: &[f32]) -> Vec<String> {
+    let labels: Vec<String> = (0..core_usage.len()).map(|index| fl!("widget-tooltip-core", index = index)).collect();
+    let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+    let cells: Vec<String> = labels
+        .iter()
+        .zip(core_usage)
+        .map(|(label, usage)| format!("{:<width$} {:>5.1}%", label, usage, width = label_width))
+        .collect();
+    cells.chunks(2).map(|pair| pair.join("    ")).collect()
+}
+
+/// Used, available and total memory in GiB.
+pub fn memory_lines(used: u64, total: u64) -> Vec<String> {
+    let gib = |bytes: u64| format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0));
+    vec![
+        fl!("widget-tooltip-memory-used", amount = gib(used)),
+        fl!("widget-tooltip-memory-available", amount = gib(total.saturating_sub(used))),
+        fl!("widget-tooltip-memory-total", amount = gib(total)),
+    ]
+}
+
+/// Detected GPU vendor and how its usage is read.
+pub fn gpu_lines(vendor: &str, method: &str) -> Vec<String> {
+    vec![vendor.to_string(), fl!("widget-tooltip-gpu-method", method = method)]
+}
+
+/// Sensor a temperature is read from.
+pub fn sensor_lines(sensor: Option<&str>, temp: f32) -> Vec<String> {
+    let mut lines = vec![match sensor {
+        Some(sensor) => fl!("widget-tooltip-sensor", name = sensor),
+        None => fl!("widget-tooltip-no-sensor"),
+    }];
+    if temp > 0.0 {
+        lines.push(format!("{:.1}°C", temp));
+    }
+    lines
+}
+
+/// Download and upload rate of every interface.
+pub fn network_lines(rates: &[InterfaceRate], bits: bool) -> Vec<String> {
+    if rates.is_empty() {
+        return vec![fl!("widget-tooltip-no-interfaces")];
+    }
+    let name_width = rates.iter().map(|rate| rate.name.chars().count()).max().unwrap_or(0);
+    rates
+        .iter()
+        .map(|rate| {
+            format!(
+                "{:<width$}  ↓ {:>11}  ↑ {:>11}",
+                rate.name,
+                format_rate(rate.rx_rate, bits),
+                format_rate(rate.tx_rate, bits),
+                width = name_width,
+            )
+        })
+        .collect()
+}
+
+/// Frequency of each package, the governor and the ones available.
+pub fn cpu_frequency_lines(frequency: Option<&CpuFrequency>, click_enabled: bool) -> Vec<String> {
+    let Some(frequency) = frequency else {
+        return vec![fl!("widget-cpu-frequency-unavailable")];
+    };
+    let mut lines: Vec<String> = frequency
+        .packages
+        .iter()
+        .enumerate()
+        .map(|(index, mhz)| fl!("widget-tooltip-package", index = index, frequency = format_ghz(*mhz)))
+        .collect();
+    if let Some(governor) = &frequency.governor {
+        lines.push(fl!("widget-tooltip-governor", name = governor.as_str()));
+    }
+    if !frequency.available_governors.is_empty() {
+        lines.push(fl!("widget-tooltip-governors", names = frequency.available_governors.join(", ")));
+    }
+    if click_enabled && frequency.next_governor().is_some() {
+        lines.push(fl!("widget-tooltip-governor-click"));
+    }
+    lines
+}
+
+/// Names of the failed units.
+pub fn failed_unit_lines(units: &[String]) -> Vec<String> {
+    if units.is_empty() {
+        return vec![fl!("widget-tooltip-no-failed-units")];
+    }
+    units.to_vec()
+}
+
+/// Names of the running containers.
+pub fn container_lines(names: &[String]) -> Vec<String> {
+    if names.is_empty() {
+        return vec![fl!("widget-tooltip-no-containers")];
+    }
+    names.to_vec()
+}
+
+/// Do-not-disturb state and what clicking does.
+pub fn dnd_lines(enabled: bool) -> Vec<String> {
+    if enabled {
+        vec![fl!("widget-tooltip-dnd-on"), fl!("widget-tooltip-dnd-click-off")]
+    } else {
+        vec![fl!("widget-tooltip-dnd-off"), fl!("widget-tooltip-dnd-click-on")]
+    }
+}
+
+/// Active power profile and what clicking does.
+pub fn power_profile_lines(profiles: Option<&PowerProfiles>) -> Vec<String> {
+    let Some(profiles) = profiles else {
+        return vec![fl!("widget-tooltip-power-profile-unavailable")];
+    };
+    let mut lines = vec![fl!("widget-tooltip-power-profile", name = profile_label(&profiles.active))];
+    if let Some(next) = profiles.next() {
+        lines.push(fl!("widget-tooltip-power-profile-click", name = profile_label(next)));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hover_delay() {
+        let start = Instant::now();
+        let mut tracker = HoverTracker::default();
+
+        assert!(!tracker.hover(Some(HoverTarget::Cpu), start));
+        assert!(!tracker.poll(start + Duration::from_millis(300)));
+        assert_eq!(tracker.shown(), None);
+
+        // Shown once, after the delay
+        assert!(tracker.poll(start + HOVER_DELAY));
+        assert!(!tracker.poll(start + Duration::from_secs(2)));
+        assert_eq!(tracker.shown(), Some(HoverTarget::Cpu));
+
+        // Moving within the same metric keeps it
+        assert!(!tracker.hover(Some(HoverTarget::Cpu), start + Duration::from_secs(3)));
+        assert_eq!(tracker.shown(), Some(HoverTarget::Cpu));
+
+        // Moving to another metric hides it and restarts the delay
+        let moved = start + Duration::from_secs(4);
+        assert!(tracker.hover(Some(HoverTarget::Network), moved));
+        assert_eq!(tracker.shown(), None);
+        assert!(!tracker.poll(moved + Duration::from_millis(100)));
+        assert!(tracker.poll(moved + HOVER_DELAY));
+
+        assert!(tracker.hover(None, moved + Duration::from_secs(1)));
+        assert!(!tracker.poll(moved + Duration::from_secs(5)));
+    }
+}
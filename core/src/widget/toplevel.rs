@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Toplevel Window Tracking for Auto-Hide
+//!
+//! The widget binds `zwlr_foreign_toplevel_manager_v1` to learn about other
+//! windows: their state (maximized, minimized, fullscreen) and which outputs
+//! they are on. [`ToplevelTracker`] keeps that state and answers one
+//! question: is the widget's output currently covered by a fullscreen (or
+//! optionally maximized) window?
+//!
+//! ```text
+//! compositor ──toplevel events──► Dispatch impls (widget_main.rs)
+//!                                        │
+//!                                        ▼
+//!                                 ToplevelTracker ──covers(output)──► auto-hide widget
+//! ```
+//!
+//! The Wayland dispatch lives with the other protocol handlers in
+//! `widget_main.rs`; this module is plain state so it can be unit tested.
+//! It is generic over the toplevel key and output types for the same reason.
+//!
+//! Compositors without the protocol simply never report any toplevels, so
+//! auto-hide never triggers.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// `zwlr_foreign_toplevel_handle_v1.state` values.
+const STATE_MAXIMIZED: u32 = 0;
+const STATE_MINIMIZED: u32 = 1;
+const STATE_FULLSCREEN: u32 = 3;
+
+/// What the widget knows about one toplevel window.
+#[derive(Debug, Clone)]
+struct ToplevelInfo<O> {
+    /// Window is maximized
+    maximized: bool,
+    /// Window is minimized (never covers anything)
+    minimized: bool,
+    /// Window is fullscreen
+    fullscreen: bool,
+    /// Outputs the window is visible on
+    outputs: Vec<O>,
+}
+
+impl<O> Default for ToplevelInfo<O> {
+    fn default() -> Self {
+        Self {
+            maximized: false,
+            minimized: false,
+            fullscreen: false,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/// State of all toplevel windows reported by the compositor.
+#[derive(Debug)]
+pub struct ToplevelTracker<K, O> {
+    toplevels: HashMap<K, ToplevelInfo<O>>,
+}
+
+impl<K: Hash + Eq, O: PartialEq> ToplevelTracker<K, O> {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self {
+            toplevels: HashMap::new(),
+        }
+    }
+
+    /// A new toplevel was announced.
+    pub fn add(&mut self, key: K) {
+        self.toplevels.insert(key, ToplevelInfo::default());
+    }
+
+    /// A toplevel was closed.
+    pub fn remove(&mut self, key: &K) {
+        self.toplevels.remove(key);
+    }
+
+    /// Apply a `state` event (array of native-endian u32 state values).
+    pub fn set_state(&mut self, key: &K, raw: &[u8]) {
+        let Some(info) = self.toplevels.get_mut(key) else {
+            return;
+        };
+        let states: Vec<u32> = raw
+            .chunks_exact(4)
+            .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        info.maximized = states.contains(&STATE_MAXIMIZED);
+        info.minimized = states.contains(&STATE_MINIMIZED);
+        info.fullscreen = states.contains(&STATE_FULLSCREEN);
+    }
+
+    /// A toplevel became visible on an output.
+    pub fn output_enter(&mut self, key: &K, output: O) {
+        if let Some(info) = self.toplevels.get_mut(key) {
+            if !info.outputs.contains(&output) {
+                info.outputs.push(output);
+            }
+        }
+    }
+
+    /// A toplevel is no longer visible on an output.
+    pub fn output_leave(&mut self, key: &K, output: &O) {
+        if let Some(info) = self.toplevels.get_mut(key) {
+            info.outputs.retain(|o| o != output);
+        }
+    }
+
+    /// Whether a fullscreen (or maximized) window covers the given output.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The widget's output, or `None` if not known yet (then
+    ///   nothing counts, so the widget isn't hidden for a window that may
+    ///   be on another monitor)
+    /// * `include_maximized` - Treat maximized windows like fullscreen ones
+    pub fn covers(&self, output: Option<&O>, include_maximized: bool) -> bool {
+        self.toplevels.values().any(|info| {
+            let covering = info.fullscreen || (include_maximized && info.maximized);
+            let on_output = output.is_some_and(|output| info.outputs.contains(output));
+            covering && !info.minimized && on_output
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn states(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_ne_bytes()).collect()
+    }
+
+    #[test]
+    fn test_fullscreen_on_widget_output() {
+        let mut tracker: ToplevelTracker<u32, &str> = ToplevelTracker::new();
+        tracker.add(1);
+        tracker.output_enter(&1, "DP-1");
+        assert!(!tracker.covers(Some(&"DP-1"), false));
+
+        tracker.set_state(&1, &states(&[STATE_FULLSCREEN]));
+        assert!(tracker.covers(Some(&"DP-1"), false));
+        assert!(!tracker.covers(Some(&"HDMI-A-1"), false));
+        // The widget's output isn't known yet
+        assert!(!tracker.covers(None, false));
+
+        // Minimized fullscreen windows don't count
+        tracker.set_state(&1, &states(&[STATE_FULLSCREEN, STATE_MINIMIZED]));
+        assert!(!tracker.covers(Some(&"DP-1"), false));
+    }
+
+    #[test]
+    fn test_maximized_only_when_enabled() {
+        let mut tracker: ToplevelTracker<u32, &str> = ToplevelTracker::new();
+        tracker.add(1);
+        tracker.output_enter(&1, "DP-1");
+        tracker.set_state(&1, &states(&[STATE_MAXIMIZED]));
+        assert!(!tracker.covers(Some(&"DP-1"), false));
+        assert!(tracker.covers(Some(&"DP-1"), true));
+
+        tracker.remove(&1);
+        assert!(!tracker.covers(Some(&"DP-1"), true));
+    }
+}
@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Update Check
+//!
+//! With `check_for_updates` enabled, the widget asks GitHub for the latest
+//! release once a day and compares its tag with the running version. A newer
+//! release shows a small "update available" badge in the widget's bottom
+//! corner; clicking it opens the release page in the browser.
+//!
+//! ```text
+//! checker thread ──daily──► api.github.com/.../releases/latest
+//!        │                              │ tag_name, html_url
+//!        ▼                              ▼
+//!  update_check.json ◄──────── UpdateState ──available()──► FrameData
+//! ```
+//!
+//! The last result is kept at `~/.cache/cosmic-monitor-applet/update_check.json`,
+//! so restarting the widget doesn't query GitHub again. Only the release
+//! endpoint is requested; nothing about the system is sent. Failed checks
+//! are retried with backoff (1 hour doubling up to a day).
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::http::{self, Backoff};
+
+/// Version of the running binary.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// GitHub API endpoint for the newest published release.
+const RELEASES_URL: &str = "https://api.github.com/repos/ZlordHUN/cosmic-monitor-applet/releases/latest";
+
+/// Minimum time between successful checks (one day).
+const CHECK_INTERVAL_SECS: u64 = 86_400;
+
+/// How often the thread looks at whether a check is due.
+///
+/// Short enough that enabling the option in settings takes effect quickly.
+const TICK: Duration = Duration::from_secs(60);
+
+/// Request timeout for the release lookup.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First retry delay after a failed check.
+const RETRY_BASE: Duration = Duration::from_secs(3600);
+
+/// Upper bound for the retry delay.
+const RETRY_MAX: Duration = Duration::from_secs(86_400);
+
+/// Subset of the GitHub release response.
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    /// Release tag (e.g., "v0.2.0")
+    tag_name: String,
+    /// Release page in the browser
+    html_url: String,
+}
+
+/// A release newer than the running version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Release tag without the leading "v"
+    pub version: String,
+    /// Release page to open on click
+    pub url: String,
+}
+
+/// Result of the last successful check, persisted across restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct UpdateState {
+    /// Unix timestamp (seconds) of the last successful check
+    checked_at: u64,
+    /// Latest release version, without the leading "v"
+    latest_version: Option<String>,
+    /// Release page of the latest version
+    release_url: Option<String>,
+}
+
+impl UpdateState {
+    /// Returns the path to the state file, creating its directory.
+    fn path() -> PathBuf {
+        let mut path = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+        path.push("cosmic-monitor-applet");
+        fs::create_dir_all(&path).ok();
+        path.push("update_check.json");
+        path
+    }
+
+    /// Load the last result, or an empty state if there is none.
+    fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the result (non-critical, errors are ignored).
+    fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            fs::write(Self::path(), json).ok();
+        }
+    }
+}
+
+/// Periodically checks GitHub for a newer release.
+pub struct UpdateChecker {
+    /// Mirrors `config.check_for_updates`
+    enabled: Arc<AtomicBool>,
+    /// Last known latest release
+    state: Arc<Mutex<UpdateState>>,
+}
+
+impl UpdateChecker {
+    /// Create the checker and start its background thread.
+    ///
+    /// Nothing is requested while `enabled` is false.
+    pub fn new(enabled: bool) -> Self {
+        let enabled = Arc::new(AtomicBool::new(enabled));
+        let state = Arc::new(Mutex::new(UpdateState::load()));
+
+        let thread_enabled = Arc::clone(&enabled);
+        let thread_state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            super::priority::enter_background("update-check");
+            let mut backoff = Backoff::new(RETRY_BASE, RETRY_MAX);
+            loop {
+                let checked_at = thread_state.lock().unwrap().checked_at;
+                let due = unix_now().saturating_sub(checked_at) >= CHECK_INTERVAL_SECS;
+                if thread_enabled.load(Ordering::Relaxed) && due && backoff.ready() {
+                    match http::get_json::<GithubRelease>(RELEASES_URL, &[("Accept", "application/vnd.github+json")], REQUEST_TIMEOUT) {
+                        Ok(release) => {
+                            backoff.record_success();
+                            let version = release.tag_name.trim_start_matches('v').to_string();
+                            log::info!("Update check: latest release is {} (running {})", version, CURRENT_VERSION);
+                            let mut state = thread_state.lock().unwrap();
+                            *state = UpdateState {
+                                checked_at: unix_now(),
+                                latest_version: Some(version),
+                                release_url: Some(release.html_url),
+                            };
+                            state.save();
+                        }
+                        Err(e) => {
+                            let retry = backoff.record_failure(&e);
+                            log::warn!("Update check failed, retrying in {}s: {}", retry.as_secs(), e);
+                        }
+                    }
+                }
+                std::thread::sleep(TICK);
+            }
+        });
+
+        Self { enabled, state }
+    }
+
+    /// Turn checking on or off (e.g., after a config change).
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// The newer release to badge, if checking is enabled and one was found.
+    pub fn available(&self) -> Option<Release> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let state = self.state.lock().unwrap();
+        let version = state.latest_version.as_deref()?;
+        is_newer(version, CURRENT_VERSION).then(|| Release {
+            version: version.to_string(),
+            url: state.release_url.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Latest release version from the last successful check, if any.
+    pub fn latest_version(&self) -> Option<String> {
+        self.state.lock().unwrap().latest_version.clone()
+    }
+}
+
+/// Open a release page in the default browser.
+pub fn open_release_page(url: &str) {
+    if let Err(e) = std::process::Command::new("xdg-open").arg(url).spawn() {
+        log::warn!("Failed to open {}: {}", url, e);
+    }
+}
+
+/// Whether `latest` is a higher version than `current`.
+///
+/// Compares the numeric dot-separated components; a pre-release suffix
+/// (`-rc1`) is ignored. Unparseable versions never count as newer.
+fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Parse "v1.2.3" / "1.2.3-rc1" into its numeric components.
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let release = version.split(['-', '+']).next()?;
+    let mut parts: Vec<u64> = release.split('.').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+    // 1.2 == 1.2.0
+    while parts.len() > 1 && parts.last() == Some(&0) {
+        parts.pop();
+    }
+    Some(parts)
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("v0.2.0", "0.1.0"));
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(!is_newer("v0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1", "0.1.0"));
+        assert!(!is_newer("0.1.0-rc1", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+}
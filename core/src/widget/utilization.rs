@@ -0,0 +1,841 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! CPU, Memory, and GPU Utilization Monitoring
+//!
+//! This module provides real-time system resource utilization monitoring for:
+//! - **CPU**: Overall CPU usage percentage via sysinfo
+//! - **Memory**: Used/total RAM with percentage
+//! - **GPU**: Utilization for NVIDIA, AMD, and Intel GPUs
+//!
+//! # GPU Monitoring
+//!
+//! GPU utilization is monitored in a background thread to avoid blocking the UI.
+//! The detection order is:
+//!
+//! 1. **NVIDIA**: Uses `nvidia-smi` command if available
+//! 2. **AMD**: Reads from `/sys/class/drm/card*/device/gpu_busy_percent` (preferred)
+//!    or falls back to `radeontop`
+//! 3. **Intel**: Calculates from current/max frequency ratio in sysfs,
+//!    or falls back to `intel_gpu_top`
+//!
+//! The sysfs sources are read from the vendor's DRM card (see
+//! [`super::gpu_card`]), so a hybrid laptop reports its discrete GPU and
+//! the temperature monitor can follow the same card
+//! ([`UtilizationMonitor::gpu_card`]). `nvidia-smi` reports the GPU
+//! temperature along with the usage, as the proprietary driver has no
+//! hwmon chip.
+//!
+//! While the frequency row is shown ([`UtilizationMonitor::set_show_frequency`]),
+//! `update()` also reads the CPU clock speeds and governor (see
+//! [`super::cpufreq`]).
+//!
+//! While the details row is shown ([`UtilizationMonitor::set_show_details`]),
+//! the GPU thread also reads the fan speed and core/memory clocks: nvidia-smi
+//! reports them in the same query as the usage, other vendors read them
+//! from the card's sysfs files ([`GpuCard::details`]).
+//!
+//! While the process list is shown ([`UtilizationMonitor::set_show_processes`]),
+//! the same thread also reads the busiest GPU processes (see
+//! [`super::gpu_processes`]), which slows its polls to about two seconds on
+//! NVIDIA.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let mut monitor = UtilizationMonitor::new();
+//! 
+//! // Call periodically (e.g., every second)
+//! monitor.update();
+//! 
+//! println!("CPU: {:.1}%", monitor.cpu_usage);
+//! println!("RAM: {:.1}%", monitor.memory_usage);
+//! println!("GPU: {:.1}%", monitor.get_gpu_usage());
+//! ```
+//!
+//! # Thread Safety
+//!
+//! GPU usage is stored in an `Arc<Mutex<f32>>` and updated by a background thread.
+//! The `get_gpu_usage()` method safely reads the current value.
+
+use sysinfo::System;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use super::exec::{self, CommandExt};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use super::cpufreq::CpuFrequency;
+use super::gpu_card::{self, GpuCard, GpuDetails};
+use super::gpu_processes::{nvidia_processes, FdinfoSampler, GpuProcess};
+use super::theme::{set_text_fill, set_text_outline};
+use super::watchdog::Heartbeat;
+use crate::config::MemoryLabel;
+
+// ============================================================================
+// GPU Vendor Detection
+// ============================================================================
+
+/// Supported GPU vendors for utilization monitoring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GpuVendor {
+    /// NVIDIA GPU (uses nvidia-smi)
+    Nvidia,
+    /// AMD GPU (uses sysfs or radeontop)
+    Amd,
+    /// Intel integrated/discrete GPU (uses sysfs or intel_gpu_top)
+    Intel,
+    /// No supported GPU detected
+    None,
+}
+
+impl GpuVendor {
+    /// Human-readable vendor name (for diagnostics).
+    fn label(&self) -> &'static str {
+        match self {
+            GpuVendor::Nvidia => "NVIDIA",
+            GpuVendor::Amd => "AMD",
+            GpuVendor::Intel => "Intel",
+            GpuVendor::None => "None",
+        }
+    }
+    
+    /// Kernel drivers of the vendor's DRM cards.
+    fn drivers(&self) -> &'static [&'static str] {
+        match self {
+            GpuVendor::Nvidia => &["nvidia"],
+            GpuVendor::Amd => &["amdgpu", "radeon"],
+            GpuVendor::Intel => &["i915", "xe"],
+            GpuVendor::None => &[],
+        }
+    }
+}
+
+// ============================================================================
+// Main Monitor Structure
+// ============================================================================
+
+/// Monitors CPU, Memory, and GPU utilization.
+///
+/// CPU and Memory are updated synchronously via `update()`.
+/// GPU utilization is monitored by a background thread for better accuracy.
+pub struct UtilizationMonitor {
+    /// sysinfo system instance for CPU/Memory data
+    sys: System,
+    
+    /// Current CPU usage percentage (0-100)
+    pub cpu_usage: f32,
+    
+    /// Usage percentage of each logical core, for the CPU tooltip
+    pub core_usage: Vec<f32>,
+    
+    /// Package frequencies and governor (None while not shown, or without cpufreq)
+    pub cpu_frequency: Option<CpuFrequency>,
+    
+    /// Whether `update()` reads the CPU frequencies
+    show_frequency: bool,
+    
+    /// Current memory usage percentage (0-100)
+    pub memory_usage: f32,
+    
+    /// Total system memory in bytes
+    pub memory_total: u64,
+    
+    /// Used system memory in bytes
+    pub memory_used: u64,
+    
+    /// GPU usage percentage, updated by background thread
+    pub gpu_usage: Arc<Mutex<f32>>,
+    
+    /// Detected GPU vendor (determines monitoring method)
+    gpu_vendor: GpuVendor,
+    
+    /// DRM card of the detected vendor (None if no card matched)
+    gpu_card: Option<GpuCard>,
+    
+    /// GPU temperature reported by nvidia-smi, updated by background thread
+    gpu_temperature: Arc<Mutex<Option<f32>>>,
+    
+    /// Last GPU polling error, set by background thread (None = OK)
+    gpu_error: Arc<Mutex<Option<String>>>,
+    
+    /// Stamped by the GPU thread after every poll (for the watchdog)
+    gpu_heartbeat: Heartbeat,
+    
+    /// Whether the GPU thread reads the busiest processes
+    show_processes: Arc<AtomicBool>,
+    
+    /// Busiest GPU processes, updated by the GPU thread while shown
+    gpu_processes: Arc<Mutex<Vec<GpuProcess>>>,
+    
+    /// Whether the GPU thread reads the fan speed and clocks
+    show_details: Arc<AtomicBool>,
+    
+    /// Fan speed and clocks, updated by the GPU thread while shown
+    gpu_details: Arc<Mutex<Option<GpuDetails>>>,
+    
+    /// Whether `update()` has run (CPU usage needs a second refresh to be valid)
+    sampled: bool,
+}
+
+// ============================================================================
+// Implementation
+// ============================================================================
+
+impl UtilizationMonitor {
+    /// Create a new utilization monitor.
+    ///
+    /// Automatically detects GPU vendor and spawns a background thread
+    /// for GPU monitoring if a supported GPU is found.
+    pub fn new() -> Self {
+        // Detect which GPU monitoring method to use
+        let gpu_vendor = Self::detect_gpu_vendor();
+        let gpu_card = gpu_card::select(&gpu_card::cards(), gpu_vendor.drivers());
+        if let Some(card) = &gpu_card {
+            log::info!("Reading GPU usage from {}", card.description());
+        }
+        
+        let monitor = Self {
+            sys: System::new_all(),
+            cpu_usage: 0.0,
+            core_usage: Vec::new(),
+            cpu_frequency: None,
+            show_frequency: false,
+            memory_usage: 0.0,
+            memory_total: 0,
+            memory_used: 0,
+            gpu_usage: Arc::new(Mutex::new(0.0)),
+            gpu_vendor,
+            gpu_card,
+            gpu_temperature: Arc::new(Mutex::new(None)),
+            gpu_error: Arc::new(Mutex::new(None)),
+            gpu_heartbeat: Heartbeat::new(),
+            show_processes: Arc::new(AtomicBool::new(false)),
+            gpu_processes: Arc::new(Mutex::new(Vec::new())),
+            show_details: Arc::new(AtomicBool::new(false)),
+            gpu_details: Arc::new(Mutex::new(None)),
+            sampled: false,
+        };
+        monitor.spawn_gpu_thread();
+        monitor
+    }
+    
+    /// Replace a stalled GPU polling thread, keeping the CPU and memory
+    /// readings.
+    ///
+    /// The old thread is told to stop and keeps only its own copies of the
+    /// GPU readings, so it can't overwrite those of the new thread if its
+    /// stuck call ever returns. Followers of [`Self::reported_gpu_temperature`]
+    /// need to follow the new value.
+    pub fn restart_gpu(&mut self) {
+        self.gpu_heartbeat.stop();
+        self.gpu_usage = Arc::new(Mutex::new(self.get_gpu_usage()));
+        self.gpu_temperature = Arc::new(Mutex::new(*self.gpu_temperature.lock().unwrap()));
+        self.gpu_error = Arc::new(Mutex::new(None));
+        self.gpu_processes = Arc::new(Mutex::new(Vec::new()));
+        self.gpu_details = Arc::new(Mutex::new(None));
+        self.gpu_heartbeat = Heartbeat::new();
+        self.spawn_gpu_thread();
+    }
+    
+    /// Spawn the background thread polling the GPU, if one was detected.
+    fn spawn_gpu_thread(&self) {
+        let gpu_vendor = self.gpu_vendor;
+        if gpu_vendor == GpuVendor::None {
+            return;
+        }
+        let gpu_usage_clone = Arc::clone(&self.gpu_usage);
+        let gpu_temperature_clone = Arc::clone(&self.gpu_temperature);
+        let card_path = self.gpu_card.as_ref().map(|card| card.path.clone());
+        let gpu_error_clone = Arc::clone(&self.gpu_error);
+        let heartbeat = self.gpu_heartbeat.clone();
+        let show_processes_clone = Arc::clone(&self.show_processes);
+        let gpu_processes_clone = Arc::clone(&self.gpu_processes);
+        let show_details_clone = Arc::clone(&self.show_details);
+        let gpu_details_clone = Arc::clone(&self.gpu_details);
+        let details_card = self.gpu_card.clone();
+        std::thread::spawn(move || {
+            let _exit_guard = heartbeat.guard();
+            super::priority::enter_background("gpu");
+            let mut fdinfo = FdinfoSampler::default();
+            loop {
+                // Poll every second for smooth updates
+                std::thread::sleep(std::time::Duration::from_secs(1));
+                if heartbeat.stopped() {
+                    break;
+                }
+                
+                let show_details = show_details_clone.load(Ordering::Relaxed);
+                let mut details = None;
+                let usage = match gpu_vendor {
+                    GpuVendor::Nvidia => Self::fetch_nvidia_gpu_usage(card_path.as_deref()).map(|(usage, temperature, nvidia_details)| {
+                        *gpu_temperature_clone.lock().unwrap() = temperature;
+                        details = Some(nvidia_details);
+                        usage
+                    }),
+                    GpuVendor::Amd => Self::fetch_amd_gpu_usage(card_path.as_deref()),
+                    GpuVendor::Intel => Self::fetch_intel_gpu_usage(card_path.as_deref()),
+                    GpuVendor::None => None,
+                };
+                
+                match usage {
+                    Some(usage) => {
+                        *gpu_usage_clone.lock().unwrap() = usage;
+                        *gpu_error_clone.lock().unwrap() = None;
+                    }
+                    None => {
+                        let message = format!("Failed to read {} GPU usage", gpu_vendor.label());
+                        *gpu_error_clone.lock().unwrap() = Some(message);
+                    }
+                }
+                
+                let processes = if show_processes_clone.load(Ordering::Relaxed) {
+                    match gpu_vendor {
+                        GpuVendor::Nvidia => nvidia_processes().unwrap_or_default(),
+                        _ => fdinfo.sample(),
+                    }
+                } else {
+                    // Start over from a fresh reading when shown again
+                    fdinfo = FdinfoSampler::default();
+                    Vec::new()
+                };
+                *gpu_processes_clone.lock().unwrap() = processes;
+                
+                if gpu_vendor != GpuVendor::Nvidia {
+                    details = details_card.as_ref().map(GpuCard::details);
+                }
+                *gpu_details_clone.lock().unwrap() = details.filter(|_| show_details);
+                heartbeat.beat();
+            }
+        });
+    }
+
+    /// Update CPU and memory statistics.
+    ///
+    /// Should be called at the configured update interval (default: 1 second).
+    /// GPU usage is updated by the background thread, not here.
+    pub fn update(&mut self) {
+        // Refresh CPU usage (requires multiple calls for accurate averaging)
+        self.sys.refresh_cpu_all();
+        self.cpu_usage = self.sys.global_cpu_usage();
+        self.core_usage = self.sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        self.cpu_frequency = if self.show_frequency { CpuFrequency::read() } else { None };
+
+        // Refresh memory statistics
+        self.sys.refresh_memory();
+        self.memory_used = self.sys.used_memory();
+        self.memory_total = self.sys.total_memory();
+        self.memory_usage = if self.memory_total > 0 {
+            (self.memory_used as f32 / self.memory_total as f32) * 100.0
+        } else {
+            0.0
+        };
+        
+        // Note: GPU usage is updated in background thread
+        self.sampled = true;
+    }
+    
+    /// Whether CPU and memory usage hold a real sample yet.
+    ///
+    /// `System::new_all()` takes the first CPU snapshot, so the first
+    /// `update()` already yields a valid usage.
+    pub fn has_sample(&self) -> bool {
+        self.sampled
+    }
+    
+    /// Get current GPU usage percentage.
+    ///
+    /// Thread-safe read from the background-updated value.
+    /// Returns 0.0 if no GPU is detected or monitoring failed.
+    pub fn get_gpu_usage(&self) -> f32 {
+        *self.gpu_usage.lock().unwrap()
+    }
+    
+    /// DRM card the GPU usage is read from, if one matched the vendor.
+    pub fn gpu_card(&self) -> Option<&GpuCard> {
+        self.gpu_card.as_ref()
+    }
+    
+    /// Shared GPU temperature reported by nvidia-smi (None for other
+    /// vendors or before the first poll), for the temperature monitor.
+    pub fn reported_gpu_temperature(&self) -> Arc<Mutex<Option<f32>>> {
+        Arc::clone(&self.gpu_temperature)
+    }
+    
+    /// Start or stop reading the CPU frequencies and governor.
+    pub fn set_show_frequency(&mut self, show: bool) {
+        self.show_frequency = show;
+        if !show {
+            self.cpu_frequency = None;
+        }
+    }
+    
+    /// Start or stop reading the busiest GPU processes.
+    pub fn set_show_processes(&self, show: bool) {
+        self.show_processes.store(show, Ordering::Relaxed);
+    }
+    
+    /// Start or stop reading the GPU fan speed and clocks.
+    pub fn set_show_details(&self, show: bool) {
+        self.show_details.store(show, Ordering::Relaxed);
+    }
+    
+    /// GPU fan speed and clocks of the last poll (None while not shown, or
+    /// if the last poll failed).
+    pub fn gpu_details(&self) -> Option<GpuDetails> {
+        *self.gpu_details.lock().unwrap()
+    }
+    
+    /// Busiest GPU processes of the last poll (empty while not shown).
+    pub fn gpu_processes(&self) -> Vec<GpuProcess> {
+        self.gpu_processes.lock().unwrap().clone()
+    }
+    
+    /// Heartbeat of the GPU polling thread, if one is running.
+    pub fn gpu_heartbeat(&self) -> Option<&Heartbeat> {
+        (self.gpu_vendor != GpuVendor::None).then_some(&self.gpu_heartbeat)
+    }
+    
+    /// Current GPU monitoring error, if any.
+    ///
+    /// Shown as a warning badge on the Utilization section instead of
+    /// silently rendering a 0% GPU bar.
+    pub fn gpu_error(&self) -> Option<String> {
+        if self.gpu_vendor == GpuVendor::None {
+            return Some(String::from("No supported GPU detected"));
+        }
+        self.gpu_error.lock().unwrap().clone()
+    }
+    
+    /// Name of the detected GPU vendor ("NVIDIA", "AMD", "Intel", or "None").
+    pub fn gpu_vendor_name(&self) -> &'static str {
+        self.gpu_vendor.label()
+    }
+    
+    /// How GPU usage is read for the detected vendor (for diagnostics).
+    ///
+    /// Mirrors the order of the `fetch_*_gpu_usage` functions, so it names
+    /// the source that is actually tried first.
+    pub fn gpu_method(&self) -> &'static str {
+        let card = self.gpu_card.as_ref().map(|card| card.path.as_path());
+        match self.gpu_vendor {
+            GpuVendor::Nvidia => "nvidia-smi",
+            GpuVendor::Amd if drm_card_has(card, "device/gpu_busy_percent") => "sysfs (gpu_busy_percent)",
+            GpuVendor::Amd if std::path::Path::new("/usr/bin/radeontop").exists() => "radeontop",
+            GpuVendor::Intel if drm_card_has(card, "gt/gt0/rps_cur_freq_mhz") => "sysfs (frequency ratio)",
+            GpuVendor::Intel if std::path::Path::new("/usr/bin/intel_gpu_top").exists() => "intel_gpu_top",
+            GpuVendor::None => "None",
+            _ => "No usable source found",
+        }
+    }
+    
+    // ========================================================================
+    // GPU Vendor Detection
+    // ========================================================================
+    
+    /// Detect which GPU vendor is present on the system.
+    ///
+    /// Checks for:
+    /// 1. nvidia-smi binary (NVIDIA)
+    /// 2. radeontop or rocm-smi (AMD)
+    /// 3. intel_gpu_top (Intel)
+    /// 4. sysfs driver detection (fallback)
+    fn detect_gpu_vendor() -> GpuVendor {
+        // Check for NVIDIA first (most common discrete GPU)
+        if std::path::Path::new("/usr/bin/nvidia-smi").exists() {
+            return GpuVendor::Nvidia;
+        }
+        
+        // Check for AMD tools
+        if std::path::Path::new("/usr/bin/radeontop").exists() 
+            || std::path::Path::new("/opt/rocm/bin/rocm-smi").exists() {
+            return GpuVendor::Amd;
+        }
+        
+        // Check for Intel tools
+        if std::path::Path::new("/usr/bin/intel_gpu_top").exists() {
+            return GpuVendor::Intel;
+        }
+        
+        // Fallback: Check sysfs for GPU driver information
+        if let Ok(entries) = std::fs::read_dir("/sys/class/drm") {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let name_str = name.to_string_lossy();
+                
+                // Look for card devices (card0, card1, etc.), not render nodes
+                if name_str.starts_with("card") && !name_str.contains("-") {
+                    if let Ok(device_path) = std::fs::read_link(entry.path()) {
+                        let device_str = device_path.to_string_lossy();
+                        if device_str.contains("amdgpu") {
+                            return GpuVendor::Amd;
+                        }
+                        if device_str.contains("i915") {
+                            return GpuVendor::Intel;
+                        }
+                    }
+                }
+            }
+        }
+        
+        GpuVendor::None
+    }
+    
+    // ========================================================================
+    // GPU Usage Fetching (called from background thread)
+    // ========================================================================
+    
+    /// Fetch NVIDIA GPU utilization, temperature, fan speed and clocks via
+    /// nvidia-smi.
+    ///
+    /// Asks for the GPU at the PCI address of the selected card, or the
+    /// first GPU, and parses the CSV output ("45, 62, 38, 1845, 7001").
+    fn fetch_nvidia_gpu_usage(card: Option<&Path>) -> Option<(f32, Option<f32>, GpuDetails)> {
+        let mut command = Command::new("nvidia-smi");
+        command
+            .arg("--query-gpu=utilization.gpu,temperature.gpu,fan.speed,clocks.gr,clocks.mem")
+            .arg("--format=csv,noheader,nounits");
+        // The card's device link ends in its PCI address ("0000:01:00.0")
+        if let Some(address) = card.and_then(|card| std::fs::read_link(card.join("device")).ok()) {
+            if let Some(address) = address.file_name() {
+                command.arg(format!("--id={}", address.to_string_lossy()));
+            }
+        }
+        
+        match command.bounded_output(exec::QUICK_TIMEOUT) {
+            Ok(output) if output.status.success() => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let line = stdout.lines().next()?;
+                let (usage, temperature) = parse_nvidia_usage(line)?;
+                Some((usage, temperature, parse_nvidia_details(line)))
+            }
+            _ => None,
+        }
+    }
+    
+    /// Fetch AMD GPU utilization.
+    ///
+    /// Prefers sysfs (no external tools needed), falls back to radeontop.
+    fn fetch_amd_gpu_usage(card: Option<&Path>) -> Option<f32> {
+        // Primary method: Read from sysfs (most reliable, no permissions needed)
+        // AMD GPUs expose utilization in /sys/class/drm/card*/device/gpu_busy_percent
+        for card in usage_cards(card) {
+            let busy_path = card.join("device/gpu_busy_percent");
+            if let Ok(content) = std::fs::read_to_string(&busy_path) {
+                if let Ok(usage) = content.trim().parse::<f32>() {
+                    return Some(usage);
+                }
+            }
+        }
+        
+        // Fallback: radeontop (requires permissions)
+        if std::path::Path::new("/usr/bin/radeontop").exists() {
+            let output = Command::new("radeontop")
+                .arg("-d")
+                .arg("-")
+                .arg("-l")
+                .arg("1")
+                .bounded_output(exec::QUICK_TIMEOUT);
+            
+            if let Ok(output) = output {
+                if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    // Parse "gpu 45.67%" format
+                    for line in stdout.lines() {
+                        if line.contains("gpu") {
+                            if let Some(percent_str) = line.split_whitespace().nth(1) {
+                                if let Some(num_str) = percent_str.strip_suffix('%') {
+                                    if let Ok(usage) = num_str.parse::<f32>() {
+                                        return Some(usage);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// Fetch Intel GPU utilization.
+    ///
+    /// Calculates from frequency ratio (current/max), falls back to intel_gpu_top.
+    fn fetch_intel_gpu_usage(card: Option<&Path>) -> Option<f32> {
+        // Primary method: Calculate usage from frequency ratio
+        // Intel GPUs expose frequency in sysfs
+        for card in usage_cards(card) {
+            // Try gt0 (most common)
+            let cur_freq_path = card.join("gt/gt0/rps_cur_freq_mhz");
+            let max_freq_path = card.join("gt/gt0/rps_max_freq_mhz");
+            
+            if let (Ok(cur_str), Ok(max_str)) = (
+                std::fs::read_to_string(&cur_freq_path),
+                std::fs::read_to_string(&max_freq_path)
+            ) {
+                if let (Ok(cur_freq), Ok(max_freq)) = (
+                    cur_str.trim().parse::<f32>(),
+                    max_str.trim().parse::<f32>()
+                ) {
+                    if max_freq > 0.0 {
+                        return Some((cur_freq / max_freq) * 100.0);
+                    }
+                }
+            }
+        }
+        
+        // Fallback: intel_gpu_top (requires CAP_PERFMON or root)
+        if std::path::Path::new("/usr/bin/intel_gpu_top").exists() {
+            let output = Command::new("intel_gpu_top")
+                .arg("-J")
+                .arg("-s")
+                .arg("100")
+                .bounded_output(exec::QUICK_TIMEOUT);
+            
+            if let Ok(output) = output {
+                if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    // Simple JSON parsing for "busy" field
+                    if let Some(busy_idx) = stdout.find("\"busy\":") {
+                        let after_busy = &stdout[busy_idx + 8..];
+                        if let Some(end_idx) = after_busy.find(|c: char| !c.is_numeric() && c != '.') {
+                            if let Ok(usage) = after_busy[..end_idx].parse::<f32>() {
+                                return Some(usage);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        None
+    }
+}
+
+impl Drop for UtilizationMonitor {
+    fn drop(&mut self) {
+        self.gpu_heartbeat.stop();
+    }
+}
+
+/// Parse one line of nvidia-smi's "utilization, temperature" CSV output.
+///
+/// The temperature reads "[N/A]" on some boards; the usage is still kept.
+fn parse_nvidia_usage(line: &str) -> Option<(f32, Option<f32>)> {
+    let mut fields = line.split(',').map(str::trim);
+    let usage = fields.next()?.parse().ok()?;
+    let temperature = fields.next().and_then(|field| field.parse().ok());
+    Some((usage, temperature))
+}
+
+/// Parse the fan speed and clocks following the usage and temperature in a
+/// line of nvidia-smi's CSV output. Boards without a fan (laptops) report
+/// "[N/A]" for it.
+fn parse_nvidia_details(line: &str) -> GpuDetails {
+    let mut fields = line.split(',').map(str::trim).skip(2).map(|field| field.parse().ok());
+    GpuDetails {
+        fan_percent: fields.next().flatten(),
+        core_mhz: fields.next().flatten(),
+        memory_mhz: fields.next().flatten(),
+    }
+}
+
+/// DRM card directories to read usage from: the selected card, or every
+/// card (card0, card1, ...) when none matched the vendor.
+fn usage_cards(card: Option<&Path>) -> Vec<PathBuf> {
+    match card {
+        Some(card) => vec![card.to_path_buf()],
+        None => gpu_card::cards().into_iter().map(|card| card.path).collect(),
+    }
+}
+
+/// Whether the selected card (or any card) has the given sysfs file.
+fn drm_card_has(card: Option<&Path>, relative: &str) -> bool {
+    usage_cards(card).iter().any(|card| card.join(relative).exists())
+}
+
+// ============================================================================
+// Memory Label
+// ============================================================================
+
+/// Bytes per GB as shown on the memory label (binary, like `free -h`).
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Text next to the memory bar, or `None` when nothing is shown.
+///
+/// `Percentage` follows `show_percentages` like the CPU and GPU bars, but
+/// shows used / total instead of nothing when it is off. Without a total
+/// (no sample yet), only the percentage can be shown.
+pub fn memory_label_text(label: MemoryLabel, show_percentages: bool, used: u64, total: u64, usage: f32) -> Option<String> {
+    let percentage = format!("{:.1}%", usage);
+    if total == 0 {
+        return show_percentages.then_some(percentage);
+    }
+    let absolute = format!("{:.1} / {:.1} GB", used as f64 / BYTES_PER_GB, total as f64 / BYTES_PER_GB);
+    Some(match label {
+        MemoryLabel::Percentage if show_percentages => percentage,
+        MemoryLabel::Percentage | MemoryLabel::Absolute => absolute,
+        MemoryLabel::Both => format!("{} · {:.0}%", absolute, usage),
+    })
+}
+
+// ============================================================================
+// Drawing Helper Functions
+// ============================================================================
+// These functions draw icons using Cairo for the utilization section.
+
+/// Draw a CPU icon (chip with pins).
+///
+/// Used in the utilization section header.
+pub fn draw_cpu_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    // Draw chip body
+    cr.rectangle(x, y, size, size);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    
+    // Draw pins on sides
+    let pin_length = size * 0.2;
+    let pin_spacing = size / 3.0;
+    
+    // Left pins
+    for i in 0..3 {
+        let py = y + pin_spacing * (i as f64 + 0.5);
+        cr.move_to(x, py);
+        cr.line_to(x - pin_length, py);
+    }
+    
+    // Right pins
+    for i in 0..3 {
+        let py = y + pin_spacing * (i as f64 + 0.5);
+        cr.move_to(x + size, py);
+        cr.line_to(x + size + pin_length, py);
+    }
+    
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.stroke().ok();
+}
+
+/// Draw a RAM icon (simple memory chip representation)
+pub fn draw_ram_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    // Draw memory stick body
+    cr.rectangle(x, y + size * 0.2, size, size * 0.8);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    
+    // Draw notch at top
+    let notch_width = size * 0.3;
+    let notch_x = x + (size - notch_width) / 2.0;
+    cr.rectangle(notch_x, y, notch_width, size * 0.2);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    
+    // Draw chips on the body
+    let chip_size = size * 0.15;
+    for i in 0..3 {
+        let chip_y = y + size * 0.3 + i as f64 * size * 0.22;
+        cr.rectangle(x + size * 0.15, chip_y, chip_size, chip_size);
+        cr.rectangle(x + size * 0.55, chip_y, chip_size, chip_size);
+    }
+    set_text_outline(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+}
+
+/// Draw a GPU icon (graphics card representation)
+pub fn draw_gpu_icon(cr: &cairo::Context, x: f64, y: f64, size: f64) {
+    // Draw GPU card body
+    cr.rectangle(x, y + size * 0.3, size * 1.3, size * 0.7);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+    
+    // Draw fan (circle)
+    cr.arc(x + size * 0.65, y + size * 0.65, size * 0.25, 0.0, 2.0 * std::f64::consts::PI);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke().ok();
+    
+    // Draw PCIe connector
+    for i in 0..3 {
+        let connector_x = x + i as f64 * size * 0.15;
+        cr.rectangle(connector_x, y, size * 0.1, size * 0.25);
+    }
+    set_text_outline(cr);
+    cr.set_line_width(1.5);
+    cr.stroke().ok();
+}
+
+/// Draw a horizontal progress bar, filled with `color` (the level color
+/// picked by the caller from the metric's thresholds)
+pub fn draw_progress_bar(cr: &cairo::Context, x: f64, y: f64, width: f64, height: f64, percentage: f32, color: (f64, f64, f64)) {
+    // Draw background
+    cr.rectangle(x, y, width, height);
+    cr.set_source_rgba(0.2, 0.2, 0.2, 0.7);
+    cr.fill().ok();
+    
+    // Draw border
+    cr.rectangle(x, y, width, height);
+    set_text_outline(cr);
+    cr.set_line_width(2.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.set_line_width(1.0);
+    cr.stroke().ok();
+    
+    // Draw filled portion
+    let fill_width = width * (percentage / 100.0).min(1.0) as f64;
+    if fill_width > 0.0 {
+        cr.rectangle(x + 1.0, y + 1.0, fill_width - 2.0, height - 2.0);
+        cr.set_source_rgb(color.0, color.1, color.2);
+        cr.fill().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_label_text() {
+        let gb = 1024 * 1024 * 1024;
+        let (used, total) = (12 * gb + gb / 3, 32 * gb);
+
+        assert_eq!(memory_label_text(MemoryLabel::Percentage, true, used, total, 38.4).as_deref(), Some("38.4%"));
+        assert_eq!(memory_label_text(MemoryLabel::Percentage, false, used, total, 38.4).as_deref(), Some("12.3 / 32.0 GB"));
+        assert_eq!(memory_label_text(MemoryLabel::Absolute, true, used, total, 38.4).as_deref(), Some("12.3 / 32.0 GB"));
+        assert_eq!(memory_label_text(MemoryLabel::Both, false, used, total, 38.4).as_deref(), Some("12.3 / 32.0 GB · 38%"));
+        // No sample yet
+        assert_eq!(memory_label_text(MemoryLabel::Absolute, false, 0, 0, 0.0), None);
+    }
+
+    #[test]
+    fn test_parse_nvidia_usage() {
+        assert_eq!(parse_nvidia_usage("45, 62"), Some((45.0, Some(62.0))));
+        assert_eq!(parse_nvidia_usage("7, [N/A]"), Some((7.0, None)));
+        assert_eq!(parse_nvidia_usage("[N/A], 40"), None);
+        assert_eq!(parse_nvidia_usage("45, 62, 38, 1845, 7001"), Some((45.0, Some(62.0))));
+    }
+
+    #[test]
+    fn test_parse_nvidia_details() {
+        let details = parse_nvidia_details("45, 62, 38, 1845, 7001");
+        assert_eq!(details, GpuDetails { fan_percent: Some(38.0), core_mhz: Some(1845.0), memory_mhz: Some(7001.0) });
+        let laptop = parse_nvidia_details("12, 55, [N/A], 300, 405");
+        assert_eq!(laptop.fan_percent, None);
+        assert_eq!(laptop.core_mhz, Some(300.0));
+        assert!(parse_nvidia_details("7, [N/A]").is_empty());
+    }
+}
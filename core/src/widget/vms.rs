@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Virtual Machines
+//!
+//! Running libvirt domains with their vCPU and memory allocation, one
+//! compact row per domain, checked on a long interval:
+//!
+//! ```text
+//! virsh -c <vms_uri> domstats --list-running --vcpu --balloon
+//!     Domain: 'win11'                 ──► win11      4 vCPU · 8.0 GiB
+//!       vcpu.current=4
+//!       balloon.current=8388608 (KiB)
+//!                     refresher thread, every vms_refresh_minutes
+//! ```
+//!
+//! `virsh` talks to libvirtd (or the modular virtqemud) over its socket, so
+//! one call covers every running domain. `qemu:///system` needs the user in
+//! the `libvirt` group; without access, or without `virsh` installed, the
+//! section shows the error instead.
+//!
+//! Memory is the balloon's current size, which is what the guest has right
+//! now rather than its configured maximum.
+
+use std::io;
+use std::process::Command;
+
+use super::exec::{CommandExt, QUICK_TIMEOUT};
+use super::refresher::{RefreshSettings, Refresher};
+use crate::config::Config;
+
+/// What the refresher checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmSettings {
+    /// libvirt connection URI
+    pub uri: String,
+    /// Minutes between refreshes (at least 1)
+    pub refresh_minutes: u32,
+}
+
+/// Settings for a configuration (`None` while the section is hidden).
+pub fn vm_settings(config: &Config) -> Option<VmSettings> {
+    config.show_vms.then(|| VmSettings {
+        uri: config.vms_uri.trim().to_string(),
+        refresh_minutes: config.vms_refresh_minutes,
+    })
+}
+
+/// A running domain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmInfo {
+    /// Domain name
+    pub name: String,
+    /// Virtual CPUs currently online (None if libvirt didn't say)
+    pub vcpus: Option<u32>,
+    /// Current memory in bytes (None if libvirt didn't say)
+    pub memory: Option<u64>,
+}
+
+/// Refreshes the running domains on a background thread.
+pub type VmMonitor = Refresher<VmSettings, Vec<VmInfo>>;
+
+impl RefreshSettings for VmSettings {
+    fn refresh_minutes(&self) -> u32 {
+        self.refresh_minutes
+    }
+}
+
+impl VmMonitor {
+    /// Start listing the running domains.
+    pub fn new(settings: Option<VmSettings>) -> Self {
+        Refresher::spawn("vms", settings, |settings: &VmSettings| {
+            match running_domains(&settings.uri) {
+                Ok(vms) => {
+                    log::debug!("Virtual machines refreshed: {:?}", vms);
+                    (Some(vms), None)
+                }
+                Err(e) => {
+                    log::warn!("Failed to list virtual machines: {}", e);
+                    (Some(Vec::new()), Some(e))
+                }
+            }
+        })
+    }
+
+    /// Domains of the last refresh (empty before the first one).
+    pub fn vms(&self) -> Vec<VmInfo> {
+        self.value().unwrap_or_default()
+    }
+}
+
+/// Running domains at `uri`, sorted by name.
+fn running_domains(uri: &str) -> Result<Vec<VmInfo>, String> {
+    let mut command = Command::new("virsh");
+    if !uri.is_empty() {
+        command.args(["-c", uri]);
+    }
+    let output = match command.args(["domstats", "--list-running", "--vcpu", "--balloon"]).bounded_output(QUICK_TIMEOUT) {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(String::from("virsh not installed")),
+        Err(e) => return Err(e.to_string()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().find(|line| !line.trim().is_empty()).unwrap_or("virsh failed").trim().to_string());
+    }
+
+    let mut vms = parse_domstats(&String::from_utf8_lossy(&output.stdout));
+    vms.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(vms)
+}
+
+/// Domains of `virsh domstats --vcpu --balloon` output.
+fn parse_domstats(output: &str) -> Vec<VmInfo> {
+    let mut vms: Vec<VmInfo> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("Domain:") {
+            vms.push(VmInfo {
+                name: name.trim().trim_matches('\'').to_string(),
+                vcpus: None,
+                memory: None,
+            });
+            continue;
+        }
+        let (Some(vm), Some((key, value))) = (vms.last_mut(), line.split_once('=')) else {
+            continue;
+        };
+        match key {
+            "vcpu.current" => vm.vcpus = value.parse().ok(),
+            // KiB
+            "balloon.current" => vm.memory = value.parse::<u64>().ok().map(|kib| kib * 1024),
+            _ => {}
+        }
+    }
+    vms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domstats() {
+        let output = "Domain: 'win11'\n  state.state=1\n  vcpu.current=4\n  vcpu.maximum=8\n  balloon.current=8388608\n  balloon.maximum=16777216\n\nDomain: 'router'\n  vcpu.current=1\n\n";
+        assert_eq!(parse_domstats(output), [
+            VmInfo { name: String::from("win11"), vcpus: Some(4), memory: Some(8 * 1024 * 1024 * 1024) },
+            VmInfo { name: String::from("router"), vcpus: Some(1), memory: None },
+        ]);
+        assert!(parse_domstats("").is_empty());
+    }
+}
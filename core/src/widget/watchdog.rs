@@ -0,0 +1,523 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Per-Monitor Watchdog
+//!
+//! Monitors that poll on background threads (the sampler, GPU usage, disk
+//! models, batteries, weather, media players, custom commands) stamp a [`Heartbeat`] after every
+//! completed poll. If a thread stops beating, e.g. because it is stuck in a
+//! driver call, its section would otherwise keep showing the last values as
+//! if they were current.
+//!
+//! Every watched thread, including the event-driven notification listener
+//! that has nothing to beat for, also holds an [`ExitGuard`] for its
+//! lifetime. The guard marks the heartbeat when the thread panics or
+//! returns, so a dead thread is noticed on the next check instead of
+//! silently losing its metric for good.
+//!
+//! The [`Watchdog`] compares each heartbeat against a per-monitor limit:
+//!
+//! ```text
+//! poll thread ──beat()──► Heartbeat ◄──age()/exit()── Watchdog::check()  (main loop)
+//!      └── ExitGuard (drop) ──┘                            │
+//!                           stale or exited? ──────────────┼──► badge section
+//!                                                          └──► restart collector
+//! ```
+//!
+//! A stale monitor is restarted by creating a fresh instance (new thread,
+//! new heartbeat). The stuck thread can't be interrupted, so it is told to
+//! [`stop`](Heartbeat::stop) and exits when its call returns; until then it
+//! only holds its own copies of the shared state. Because the new heartbeat
+//! starts at the restart time, a collector that keeps hanging is retried at
+//! most once per stale limit rather than in a tight loop. A thread that
+//! exited is restarted right away, unless it was already restarted within
+//! its stale limit (so one that crashes on startup isn't respawned every
+//! check).
+//!
+//! A thread that is overdue because the system doesn't give it CPU time
+//! (its `schedstat` shows it waiting on the run queue more than running,
+//! e.g. at idle priority under full load) counts as starved rather than
+//! stalled: its section is badged, but it isn't restarted, since a new
+//! thread would wait just the same.
+//!
+//! Health is published in the diagnostics status for the settings app.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU8, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::WidgetSection;
+use crate::fl;
+
+// ============================================================================
+// Heartbeat
+// ============================================================================
+
+/// [`Heartbeat`] exit state: the thread is still running.
+const RUNNING: u8 = 0;
+/// [`Heartbeat`] exit state: the thread returned.
+const RETURNED: u8 = 1;
+/// [`Heartbeat`] exit state: the thread panicked.
+const PANICKED: u8 = 2;
+
+/// Last time a monitor thread completed a poll, and whether the thread is
+/// still running, shared with the main loop.
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    /// Unix time of the last beat (ms)
+    last_beat: Arc<AtomicU64>,
+    /// [`RUNNING`], [`RETURNED`] or [`PANICKED`]
+    exit: Arc<AtomicU8>,
+    /// Id of the thread that beats (0 until its first beat)
+    thread: Arc<AtomicI32>,
+    /// Set when the thread should exit (its monitor was replaced or dropped)
+    stop: Arc<AtomicBool>,
+}
+
+impl Heartbeat {
+    /// Create a heartbeat stamped with the current time.
+    pub fn new() -> Self {
+        Self {
+            last_beat: Arc::new(AtomicU64::new(unix_now_ms())),
+            exit: Arc::new(AtomicU8::new(RUNNING)),
+            thread: Arc::new(AtomicI32::new(0)),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Record that the monitor just produced fresh data.
+    pub fn beat(&self) {
+        self.last_beat.store(unix_now_ms(), Ordering::Relaxed);
+        if self.thread.load(Ordering::Relaxed) == 0 {
+            self.thread.store(unsafe { libc::gettid() }, Ordering::Relaxed);
+        }
+    }
+
+    /// Time since the last beat.
+    pub fn age(&self) -> Duration {
+        Duration::from_millis(unix_now_ms().saturating_sub(self.last_beat.load(Ordering::Relaxed)))
+    }
+
+    /// Ask the monitor's thread to exit.
+    ///
+    /// Threads check [`Self::stopped`] between polls, so one stuck in a call
+    /// exits once the call returns.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the monitor's thread should exit.
+    pub fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Guard to hold in the monitor's thread; it marks the heartbeat when
+    /// the thread ends.
+    pub fn guard(&self) -> ExitGuard {
+        ExitGuard(self.clone())
+    }
+
+    /// How the monitor's thread ended, `None` while it is running.
+    pub fn exit(&self) -> Option<ThreadExit> {
+        match self.exit.load(Ordering::Relaxed) {
+            RETURNED => Some(ThreadExit::Returned),
+            PANICKED => Some(ThreadExit::Panicked),
+            _ => None,
+        }
+    }
+
+    /// Scheduler statistics of the beating thread, `None` before its first
+    /// beat or if the kernel doesn't provide them.
+    pub fn sched_stats(&self) -> Option<SchedStats> {
+        let thread = self.thread.load(Ordering::Relaxed);
+        if thread == 0 {
+            return None;
+        }
+        SchedStats::parse(&std::fs::read_to_string(format!("/proc/self/task/{}/schedstat", thread)).ok()?)
+    }
+}
+
+/// CPU time of a thread so far, from `/proc/<pid>/task/<tid>/schedstat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchedStats {
+    /// Time spent running on a CPU (ns)
+    pub run_ns: u64,
+    /// Time spent runnable but waiting for a CPU (ns)
+    pub wait_ns: u64,
+}
+
+impl SchedStats {
+    /// Parse a `schedstat` line ("run_ns wait_ns timeslices").
+    fn parse(text: &str) -> Option<Self> {
+        let mut fields = text.split_whitespace().map(|field| field.parse().ok());
+        Some(Self {
+            run_ns: fields.next()??,
+            wait_ns: fields.next()??,
+        })
+    }
+}
+
+/// How a monitor thread ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadExit {
+    /// Returned, e.g. because a child process it reads from exited
+    Returned,
+    /// Panicked
+    Panicked,
+}
+
+/// Marks its [`Heartbeat`] as exited when dropped, which happens when the
+/// thread holding it returns or unwinds from a panic.
+#[derive(Debug)]
+pub struct ExitGuard(Heartbeat);
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        let exit = if std::thread::panicking() { PANICKED } else { RETURNED };
+        self.0.exit.store(exit, Ordering::Relaxed);
+    }
+}
+
+/// Current Unix time in milliseconds.
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// Watched Monitors
+// ============================================================================
+
+/// Background collectors supervised by the watchdog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchedMonitor {
+    /// Sampler thread refreshing CPU, memory, temperatures, network and disks
+    Sampler,
+    /// GPU usage polling thread (1s polls)
+    Gpu,
+    /// Disk model lookup via lsblk (10s polls)
+    Storage,
+    /// Solaar/HeadsetControl thread (5s polls, slow tools)
+    Battery,
+    /// OpenWeatherMap fetch thread (10s polls)
+    Weather,
+    /// Cider/MPRIS polling thread (1s polls)
+    Media,
+    /// Custom command runner (1s ticks, commands run one after another)
+    Custom,
+    /// Default audio output polling thread (1s polls)
+    Audio,
+    /// D-Bus notification listener (event-driven, never beats)
+    Notifications,
+}
+
+impl WatchedMonitor {
+    /// All supervised monitors, in display order.
+    pub const ALL: [WatchedMonitor; 9] = [
+        WatchedMonitor::Sampler,
+        WatchedMonitor::Gpu,
+        WatchedMonitor::Storage,
+        WatchedMonitor::Battery,
+        WatchedMonitor::Weather,
+        WatchedMonitor::Media,
+        WatchedMonitor::Custom,
+        WatchedMonitor::Audio,
+        WatchedMonitor::Notifications,
+    ];
+
+    /// Display name for badges and the diagnostics page.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WatchedMonitor::Sampler => "Sampler",
+            WatchedMonitor::Gpu => "GPU",
+            WatchedMonitor::Storage => "Storage",
+            WatchedMonitor::Battery => "Battery",
+            WatchedMonitor::Weather => "Weather",
+            WatchedMonitor::Media => "Media",
+            WatchedMonitor::Custom => "Custom Commands",
+            WatchedMonitor::Audio => "Audio",
+            WatchedMonitor::Notifications => "Notifications",
+        }
+    }
+
+    /// Widget section showing this monitor's data.
+    pub fn section(&self) -> WidgetSection {
+        match self {
+            WatchedMonitor::Sampler | WatchedMonitor::Gpu => WidgetSection::Utilization,
+            WatchedMonitor::Storage => WidgetSection::Storage,
+            WatchedMonitor::Battery => WidgetSection::Battery,
+            WatchedMonitor::Weather => WidgetSection::Weather,
+            WatchedMonitor::Media => WidgetSection::Media,
+            WatchedMonitor::Custom => WidgetSection::Custom,
+            WatchedMonitor::Audio => WidgetSection::Audio,
+            WatchedMonitor::Notifications => WidgetSection::Notifications,
+        }
+    }
+
+    /// How long without a beat before the monitor counts as stalled.
+    ///
+    /// Several poll periods plus the worst-case tool/HTTP timeouts, so a
+    /// slow but working collector is never restarted. Also the least time
+    /// between two restarts of a thread that keeps exiting.
+    pub fn stale_after(&self) -> Duration {
+        match self {
+            // Beats every 20 ms unless a monitor update hangs
+            WatchedMonitor::Sampler => Duration::from_secs(15),
+            WatchedMonitor::Gpu => Duration::from_secs(15),
+            WatchedMonitor::Storage => Duration::from_secs(60),
+            WatchedMonitor::Battery => Duration::from_secs(90),
+            WatchedMonitor::Weather => Duration::from_secs(90),
+            WatchedMonitor::Media => Duration::from_secs(30),
+            // Each command may take up to its 10s timeout
+            WatchedMonitor::Custom => Duration::from_secs(120),
+            WatchedMonitor::Audio => Duration::from_secs(30),
+            // Only checked for exits (it waits for D-Bus traffic)
+            WatchedMonitor::Notifications => Duration::from_secs(60),
+        }
+    }
+}
+
+// ============================================================================
+// Watchdog
+// ============================================================================
+
+/// Health of one monitor, as published in the diagnostics status.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MonitorHealth {
+    /// Monitor name (e.g., "GPU")
+    pub name: String,
+    /// Seconds since the monitor last produced data
+    pub seconds_since_update: u64,
+    /// Whether the monitor is currently considered stalled
+    pub stale: bool,
+    /// Whether its thread is overdue because it gets no CPU time
+    pub starved: bool,
+    /// Whether its thread panicked
+    pub panicked: bool,
+    /// Whether its thread returned
+    pub stopped: bool,
+    /// How often the watchdog restarted it
+    pub restarts: u32,
+}
+
+/// Watchdog state of one monitor.
+#[derive(Debug)]
+struct Entry {
+    monitor: WatchedMonitor,
+    /// Latest health
+    health: MonitorHealth,
+    /// When the watchdog last restarted it
+    restarted_at: Option<Instant>,
+    /// Scheduler statistics of its thread at the last check
+    sched: Option<SchedStats>,
+}
+
+/// Tracks stale state and restart counts for every watched monitor.
+#[derive(Debug)]
+pub struct Watchdog {
+    /// One entry per monitor, in [`WatchedMonitor::ALL`] order
+    entries: Vec<Entry>,
+}
+
+impl Watchdog {
+    /// Create a watchdog with every monitor considered healthy.
+    pub fn new() -> Self {
+        let entries = WatchedMonitor::ALL
+            .iter()
+            .map(|&monitor| Entry {
+                monitor,
+                health: MonitorHealth {
+                    name: monitor.name().to_string(),
+                    ..Default::default()
+                },
+                restarted_at: None,
+                sched: None,
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Check a monitor's heartbeat.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - Which monitor the heartbeat belongs to
+    /// * `age` - Time since its last beat, or `None` if it has no thread or never beats
+    /// * `exit` - How its thread ended, `None` while it runs (see [`Heartbeat::exit`])
+    /// * `sched` - Scheduler statistics of its thread (see [`Heartbeat::sched_stats`])
+    ///
+    /// # Returns
+    ///
+    /// `true` if the monitor has stalled or its thread ended, and it should
+    /// be restarted. A monitor whose thread is starved of CPU time is
+    /// reported but not restarted.
+    pub fn check(
+        &mut self,
+        monitor: WatchedMonitor,
+        age: Option<Duration>,
+        exit: Option<ThreadExit>,
+        sched: Option<SchedStats>,
+    ) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|entry| entry.monitor == monitor) else {
+            return false;
+        };
+        let health = &mut entry.health;
+
+        let (panicked, stopped) = (exit == Some(ThreadExit::Panicked), exit == Some(ThreadExit::Returned));
+        if (panicked && !health.panicked) || (stopped && !health.stopped) {
+            log::warn!("{} monitor thread {}", monitor.name(), if panicked { "panicked" } else { "stopped" });
+        }
+        health.panicked = panicked;
+        health.stopped = stopped;
+        if exit.is_some() {
+            health.starved = false;
+            // Back off if the last restart didn't last
+            return entry.restarted_at.is_none_or(|at| at.elapsed() >= monitor.stale_after());
+        }
+
+        let Some(age) = age else {
+            health.seconds_since_update = 0;
+            health.stale = false;
+            health.starved = false;
+            return false;
+        };
+
+        // A thread that spent more of the time since the last check waiting
+        // for a CPU than running on one is starved, not stuck: the system is
+        // too busy to run it, and a new thread would fare no better
+        let waiting_for_cpu = match (std::mem::replace(&mut entry.sched, sched), sched) {
+            (Some(before), Some(now)) => {
+                let waited = now.wait_ns.saturating_sub(before.wait_ns);
+                waited > 0 && waited > now.run_ns.saturating_sub(before.run_ns)
+            }
+            _ => false,
+        };
+
+        health.seconds_since_update = age.as_secs();
+        let overdue = age >= monitor.stale_after();
+        let starved = overdue && waiting_for_cpu;
+        let stale = overdue && !starved;
+        if starved && !health.starved {
+            log::warn!("{} monitor starved of CPU time (no data for {}s), not restarting", monitor.name(), age.as_secs());
+        }
+        if stale && !health.stale {
+            log::warn!("{} monitor stalled (no data for {}s)", monitor.name(), age.as_secs());
+        }
+        health.starved = starved;
+        health.stale = stale;
+        stale
+    }
+
+    /// Record that a stalled or exited monitor was restarted.
+    ///
+    /// The badge stays until the next check sees the new heartbeat.
+    pub fn record_restart(&mut self, monitor: WatchedMonitor) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.monitor == monitor) {
+            let health = &mut entry.health;
+            health.restarts += 1;
+            entry.restarted_at = Some(Instant::now());
+            // Statistics of the old thread don't apply to the new one
+            entry.sched = None;
+            let reason = if health.panicked {
+                "panicked"
+            } else if health.stopped {
+                "stopped"
+            } else {
+                "stalled"
+            };
+            super::diagnostics::record_error(
+                "watchdog",
+                format!("{} monitor {}, restarted (restart #{})", monitor.name(), reason, health.restarts),
+            );
+        }
+    }
+
+    /// Message for the section badge if the monitor is stale or starved, or
+    /// its thread ended.
+    pub fn stale_message(&self, monitor: WatchedMonitor) -> Option<String> {
+        let health = &self.entries.iter().find(|entry| entry.monitor == monitor)?.health;
+        if health.panicked {
+            Some(fl!("watchdog-crashed", monitor = monitor.name()))
+        } else if health.stopped {
+            Some(fl!("watchdog-stopped", monitor = monitor.name()))
+        } else if health.starved {
+            Some(fl!("watchdog-starved", monitor = monitor.name(), seconds = health.seconds_since_update))
+        } else if health.stale {
+            Some(fl!("watchdog-stale", monitor = monitor.name(), seconds = health.seconds_since_update))
+        } else {
+            None
+        }
+    }
+
+    /// Health of all monitors for the diagnostics status.
+    pub fn health(&self) -> Vec<MonitorHealth> {
+        self.entries.iter().map(|entry| entry.health.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_after_limit() {
+        let mut watchdog = Watchdog::new();
+        assert!(!watchdog.check(WatchedMonitor::Gpu, Some(Duration::from_secs(2)), None, None));
+        assert!(watchdog.stale_message(WatchedMonitor::Gpu).is_none());
+
+        assert!(watchdog.check(WatchedMonitor::Gpu, Some(Duration::from_secs(20)), None, None));
+        assert!(watchdog.stale_message(WatchedMonitor::Gpu).is_some());
+
+        // Recovers once the restarted thread beats again
+        watchdog.record_restart(WatchedMonitor::Gpu);
+        assert!(!watchdog.check(WatchedMonitor::Gpu, Some(Duration::from_secs(1)), None, None));
+        let gpu = watchdog.health().into_iter().find(|health| health.name == "GPU").unwrap();
+        assert_eq!(gpu.restarts, 1);
+    }
+
+    #[test]
+    fn test_starved_not_restarted() {
+        let sched = |run_ms: u64, wait_ms: u64| SchedStats { run_ns: run_ms * 1_000_000, wait_ns: wait_ms * 1_000_000 };
+        assert_eq!(SchedStats::parse("1000000 53000000 41\n"), Some(sched(1, 53)));
+
+        let mut watchdog = Watchdog::new();
+        let age = Some(Duration::from_secs(20));
+        assert!(!watchdog.check(WatchedMonitor::Gpu, Some(Duration::from_secs(1)), None, Some(sched(10, 5))));
+        // Mostly waiting for a CPU since the last check
+        assert!(!watchdog.check(WatchedMonitor::Gpu, age, None, Some(sched(11, 4000))));
+        assert!(watchdog.stale_message(WatchedMonitor::Gpu).is_some());
+        // Neither running nor waiting: stuck, e.g. in a driver call
+        assert!(watchdog.check(WatchedMonitor::Gpu, age, None, Some(sched(11, 4000))));
+    }
+
+    #[test]
+    fn test_exited_threads() {
+        let heartbeat = Heartbeat::new();
+        let guard = heartbeat.guard();
+        std::thread::spawn(move || {
+            let _guard = guard;
+            panic!("collector failed");
+        })
+        .join()
+        .unwrap_err();
+        assert_eq!(heartbeat.exit(), Some(ThreadExit::Panicked));
+
+        let heartbeat = Heartbeat::new();
+        drop(heartbeat.guard());
+        assert_eq!(heartbeat.exit(), Some(ThreadExit::Returned));
+
+        // Restarted right away, then not again within the stale limit
+        let mut watchdog = Watchdog::new();
+        let monitor = WatchedMonitor::Notifications;
+        assert!(watchdog.check(monitor, None, Some(ThreadExit::Panicked), None));
+        assert!(watchdog.stale_message(monitor).is_some());
+        watchdog.record_restart(monitor);
+        assert!(!watchdog.check(monitor, None, Some(ThreadExit::Returned), None));
+        assert!(!watchdog.check(monitor, None, None, None));
+        assert!(watchdog.stale_message(monitor).is_none());
+    }
+}
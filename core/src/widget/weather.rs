@@ -0,0 +1,1082 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! # Weather Monitoring Module
+//!
+//! This module integrates with the OpenWeatherMap API to display current weather
+//! conditions in the widget. It includes custom icon rendering using the
+//! Weather Icons font.
+//!
+//! ## API Integration
+//!
+//! Uses the OpenWeatherMap "Current Weather Data" API:
+//! ```text
+//! https://api.openweathermap.org/data/2.5/weather?q={location}&appid={key}&units=metric
+//! ```
+//!
+//! Requires a free API key from https://openweathermap.org/api
+//!
+//! ## Update Frequency
+//!
+//! - Minimum interval: 10 minutes (600 seconds)
+//! - Background thread polls for requests every 10 seconds
+//! - First update triggers immediately on startup
+//!
+//! ## Icon System
+//!
+//! OpenWeatherMap returns icon codes like "01d" (clear day) or "10n" (rain night).
+//! These are mapped to Weather Icons font characters for visual display.
+//!
+//! The day/night suffix is not trusted blindly: cached API responses can report
+//! a day icon after sunset. [`WeatherData::display_icon`] recomputes it from
+//! the station coordinates using a local sunrise/sunset calculation.
+//!
+//! ## Error Handling
+//!
+//! - Missing API key: Silently skips updates
+//! - Missing location: Silently skips updates
+//! - API failure: Keeps previous data, logs error, retries with exponential
+//!   backoff (1 min doubling up to 30 min, honoring 429 `Retry-After`)
+//! - Startup: The last successful fetch is restored from the widget cache and
+//!   annotated with "updated Xm ago" until the first fresh fetch completes
+//! - Offline: Cached data keeps rendering with a "stale (2 h)" marker once it
+//!   is older than 30 minutes
+//! - Network timeout: 5 second limit to prevent blocking
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::http::{Backoff, HttpResult};
+use super::theme::{set_text_fill, set_text_outline};
+use super::watchdog::Heartbeat;
+use crate::fl;
+
+/// Weather data older than this is rendered with a "stale" marker (30 minutes).
+///
+/// Normal refreshes happen every 10 minutes, so this means at least two
+/// consecutive fetches have failed.
+const STALE_AFTER_SECS: u64 = 1800;
+
+/// First retry delay after a failed fetch, so data recovers quickly once
+/// the network comes back instead of waiting the full 10 minutes.
+const RETRY_BASE: Duration = Duration::from_secs(60);
+
+/// Upper bound for the retry delay after repeated failures (30 minutes).
+const RETRY_MAX: Duration = Duration::from_secs(1800);
+
+/// Minimum wall-clock time between regular fetches (10 minutes).
+const UPDATE_INTERVAL_SECS: u64 = 600;
+
+/// Wall clock running ahead of the monotonic clock by more than this
+/// between two checks means the system was suspended.
+const RESUME_GAP: Duration = Duration::from_secs(30);
+
+// ============================================================================
+// Embedded Font Resource
+// ============================================================================
+
+/// Weather Icons font embedded directly in the binary.
+///
+/// This TTF file contains glyphs for weather conditions (sun, clouds, rain, etc.)
+/// from the Weather Icons project: https://erikflowers.github.io/weather-icons/
+const WEATHER_ICONS_FONT: &[u8] = include_bytes!("../../../resources/weathericons.ttf");
+
+/// Load the Weather Icons font into the system font cache.
+///
+/// Pango/Cairo require fonts to be accessible via filesystem, so we extract
+/// the embedded font to the user's cache directory on first use.
+///
+/// # Font Location
+///
+/// Written to: `$XDG_CACHE_HOME/cosmic-monitor-weathericons.ttf`
+/// (typically `~/.cache/cosmic-monitor-weathericons.ttf`)
+pub fn load_weather_font() {
+    use std::io::Write;
+    use std::fs;
+    
+    // Create a temporary file for the font (Pango needs a file path)
+    let cache_dir = dirs::cache_dir().unwrap_or_else(|| std::path::PathBuf::from("/tmp"));
+    let font_path = cache_dir.join("cosmic-monitor-weathericons.ttf");
+    
+    // Write font to cache if it doesn't exist or size doesn't match (updated binary)
+    if !font_path.exists() || fs::metadata(&font_path).map(|m| m.len()).unwrap_or(0) != WEATHER_ICONS_FONT.len() as u64 {
+        if let Ok(mut file) = fs::File::create(&font_path) {
+            let _ = file.write_all(WEATHER_ICONS_FONT);
+            log::info!("Weather Icons font loaded from embedded data to {:?}", font_path);
+        }
+    }
+}
+
+// ============================================================================
+// OpenWeatherMap API Response Structures
+// ============================================================================
+
+/// Root response from OpenWeatherMap "Current Weather" API.
+#[derive(Debug, Deserialize)]
+struct OpenWeatherResponse {
+    /// Main weather measurements (temp, humidity)
+    main: MainWeather,
+    /// Array of weather conditions (usually one element)
+    weather: Vec<WeatherCondition>,
+    /// City name from API (may differ from input location)
+    name: String,
+    /// Wind measurements (absent for some stations)
+    #[serde(default)]
+    wind: Option<Wind>,
+    /// Station coordinates, used for local sunrise/sunset calculation
+    #[serde(default)]
+    coord: Option<Coord>,
+    /// Sunrise and sunset of the day, used when coordinates are missing
+    #[serde(default)]
+    sys: Option<Sys>,
+}
+
+/// Sunrise and sunset from API.
+#[derive(Debug, Deserialize)]
+struct Sys {
+    /// Unix timestamp (seconds, UTC)
+    #[serde(default)]
+    sunrise: Option<i64>,
+    /// Unix timestamp (seconds, UTC)
+    #[serde(default)]
+    sunset: Option<i64>,
+}
+
+/// Geographic coordinates from API.
+#[derive(Debug, Deserialize)]
+struct Coord {
+    /// Latitude in degrees (north positive)
+    lat: f64,
+    /// Longitude in degrees (east positive)
+    lon: f64,
+}
+
+/// Temperature and humidity data from API.
+#[derive(Debug, Deserialize)]
+struct MainWeather {
+    /// Current temperature in Celsius (with units=metric)
+    temp: f32,
+    /// "Feels like" temperature accounting for wind/humidity
+    feels_like: f32,
+    /// Minimum temperature (at the moment, not forecast)
+    temp_min: f32,
+    /// Maximum temperature (at the moment, not forecast)
+    temp_max: f32,
+    /// Humidity percentage (0-100)
+    humidity: u8,
+}
+
+/// Wind data from API.
+#[derive(Debug, Deserialize)]
+struct Wind {
+    /// Wind speed in meters/second (with units=metric)
+    speed: f32,
+    /// Meteorological wind direction in degrees (where the wind comes from)
+    #[serde(default)]
+    deg: u16,
+}
+
+/// Weather condition details from API.
+#[derive(Debug, Deserialize)]
+struct WeatherCondition {
+    /// Human-readable description (e.g., "light rain", "clear sky")
+    description: String,
+    /// Icon code for weather visualization (e.g., "01d", "10n")
+    /// Format: 2-digit condition + day(d)/night(n) suffix
+    icon: String,
+}
+
+// ============================================================================
+// Public Weather Data Struct
+// ============================================================================
+
+/// Processed weather data for display in the widget.
+///
+/// This struct contains all weather information needed for rendering,
+/// extracted and normalized from the OpenWeatherMap API response.
+///
+/// # Serialization
+///
+/// Implements Serialize/Deserialize so the last successful fetch can be
+/// persisted in the widget cache across restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherData {
+    /// Current temperature in Celsius
+    pub temperature: f32,
+    /// "Feels like" temperature (wind chill / heat index)
+    pub feels_like: f32,
+    /// Current minimum temperature
+    pub temp_min: f32,
+    /// Current maximum temperature
+    pub temp_max: f32,
+    /// Humidity percentage (0-100)
+    pub humidity: u8,
+    /// Wind speed in meters/second
+    #[serde(default)]
+    pub wind_speed: f32,
+    /// Wind direction in degrees (0 = north, 90 = east)
+    #[serde(default)]
+    pub wind_deg: u16,
+    /// Capitalized weather description (e.g., "Light rain")
+    pub description: String,
+    /// OpenWeatherMap icon code (e.g., "01d", "10n")
+    pub icon: String,
+    /// City name returned by API
+    pub location: String,
+    /// Latitude of the weather station (for day/night calculation)
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    /// Longitude of the weather station (for day/night calculation)
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// Sunrise reported by the API for the fetched day (Unix seconds)
+    #[serde(default)]
+    pub sunrise: Option<i64>,
+    /// Sunset reported by the API for the fetched day (Unix seconds)
+    #[serde(default)]
+    pub sunset: Option<i64>,
+    /// Unix timestamp (seconds) when this data was fetched from the API
+    #[serde(default)]
+    pub fetched_at: u64,
+}
+
+impl WeatherData {
+    /// Icon code to render, with the day/night suffix recomputed locally.
+    ///
+    /// OpenWeatherMap occasionally serves a cached response whose icon still
+    /// carries the day ("d") suffix after sunset. When the station coordinates
+    /// are known, the suffix is replaced based on the actual sun position at
+    /// `now_unix`; otherwise the API icon is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - Current time as a Unix timestamp (seconds, UTC)
+    pub fn display_icon(&self, now_unix: i64) -> String {
+        match (self.latitude, self.longitude) {
+            (Some(lat), Some(lon)) if self.icon.is_char_boundary(2) => {
+                let suffix = if is_daytime(lat, lon, now_unix) { 'd' } else { 'n' };
+                format!("{}{}", &self.icon[..2], suffix)
+            }
+            _ => self.icon.clone(),
+        }
+    }
+    
+    /// Sunrise and sunset for the day closest to `now_unix`.
+    ///
+    /// Computed locally from the station coordinates, so the times follow
+    /// the date even while the data is hours old. Without coordinates, the
+    /// times the API reported for the fetched day are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - Current time as a Unix timestamp (seconds, UTC)
+    pub fn sun_times(&self, now_unix: i64) -> Option<SunTimes> {
+        match (self.latitude, self.longitude, self.sunrise, self.sunset) {
+            (Some(lat), Some(lon), _, _) => Some(sun_times(lat, lon, now_unix)),
+            (_, _, Some(sunrise), Some(sunset)) => Some(SunTimes::Normal { sunrise, sunset }),
+            _ => None,
+        }
+    }
+    
+    /// Short "stale (2 h)" marker when this data is too old to be trusted.
+    ///
+    /// Returns `None` while the data is fresh (or has no fetch timestamp),
+    /// so the renderer can skip the marker entirely.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - Current time as a Unix timestamp (seconds, UTC)
+    pub fn stale_label(&self, now_unix: i64) -> Option<String> {
+        if self.fetched_at == 0 {
+            return None;
+        }
+        let age = (now_unix.max(0) as u64).saturating_sub(self.fetched_at);
+        if age < STALE_AFTER_SECS {
+            return None;
+        }
+        Some(format!("{} ({})", fl!("widget-weather-stale"), format_age(age)))
+    }
+}
+
+/// Format an age in seconds as a compact "45 min" / "2 h" / "3 d" string.
+pub fn format_age(secs: u64) -> String {
+    if secs < 60 {
+        fl!("age-under-minute")
+    } else if secs < 3600 {
+        fl!("age-minutes", count = secs / 60)
+    } else if secs < 86400 {
+        fl!("age-hours", count = secs / 3600)
+    } else {
+        fl!("age-days", count = secs / 86400)
+    }
+}
+
+impl Default for WeatherData {
+    /// Default weather data for display before first API response.
+    fn default() -> Self {
+        Self {
+            temperature: 0.0,
+            feels_like: 0.0,
+            temp_min: 0.0,
+            temp_max: 0.0,
+            humidity: 0,
+            wind_speed: 0.0,
+            wind_deg: 0,
+            description: String::from("N/A"),
+            icon: String::from("01d"),  // Clear day as default icon
+            location: String::from("Unknown"),
+            latitude: None,
+            longitude: None,
+            sunrise: None,
+            sunset: None,
+            fetched_at: 0,
+        }
+    }
+}
+
+// ============================================================================
+// Weather Monitor Struct
+// ============================================================================
+
+/// Monitors weather conditions via OpenWeatherMap API.
+///
+/// Fetches weather data in a background thread to avoid blocking the render loop.
+/// Updates are rate-limited to once every 10 minutes to respect API quotas.
+///
+/// # Threading Model
+///
+/// - `weather_data`: Shared state with latest weather info
+/// - `api_key` / `location`: Shared config, can be updated from settings
+/// - `update_requested`: Flag to trigger background fetch
+/// - Background thread checks for requests every 10 seconds
+///
+/// # Configuration
+///
+/// Requires both an API key and location to be set. Without these, updates
+/// are silently skipped.
+pub struct WeatherMonitor {
+    /// Shared weather data, updated by background thread
+    pub weather_data: Arc<Mutex<Option<WeatherData>>>,
+    /// Wall-clock Unix time (seconds) of the last update request.
+    ///
+    /// Wall-clock rather than `Instant`, which doesn't advance while the
+    /// system is suspended (10 minutes could take all night to elapse).
+    last_update: u64,
+    /// Monotonic and wall-clock time of the previous `update()` call,
+    /// for detecting resume from suspend
+    last_check: Option<(Instant, SystemTime)>,
+    /// OpenWeatherMap API key (shared for background thread)
+    api_key: Arc<Mutex<String>>,
+    /// Location query string (city name or "city,country")
+    location: Arc<Mutex<String>>,
+    /// Flag to signal background thread that an update is needed
+    update_requested: Arc<Mutex<bool>>,
+    /// Retry backoff, updated by the background thread after each fetch
+    backoff: Arc<Mutex<Backoff>>,
+    /// True while `weather_data` still holds data restored from the cache file
+    restored_from_cache: Arc<Mutex<bool>>,
+    /// Human-readable result of the last fetch attempt (for diagnostics)
+    last_fetch_result: Arc<Mutex<Option<String>>>,
+    /// Stamped by the background thread after every check (for the watchdog)
+    heartbeat: Heartbeat,
+}
+
+impl WeatherMonitor {
+    /// Create a new weather monitor with background update thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - OpenWeatherMap API key (from settings)
+    /// * `location` - Location query (e.g., "London", "New York,US")
+    ///
+    /// # Initialization
+    ///
+    /// 1. Leaves `last_update` at 0 to trigger an immediate first update
+    /// 2. Spawns background thread for API requests
+    /// 3. Background thread polls for update requests every 10 seconds
+    pub fn new(api_key: String, location: String) -> Self {
+        // Seed with the last successful fetch for this location so the widget
+        // shows real (if stale) weather immediately instead of "No data"
+        let cached_weather = super::cache::WidgetCache::load().weather_for(&location);
+        let api_key = Arc::new(Mutex::new(api_key));
+        let location = Arc::new(Mutex::new(location));
+        let update_requested = Arc::new(Mutex::new(false));
+        let restored_from_cache = Arc::new(Mutex::new(cached_weather.is_some()));
+        let weather_data = Arc::new(Mutex::new(cached_weather));
+        let backoff = Arc::new(Mutex::new(Backoff::new(RETRY_BASE, RETRY_MAX)));
+        let last_fetch_result = Arc::new(Mutex::new(None));
+        
+        // Spawn background thread for weather updates
+        // This avoids blocking the main render loop on network requests
+        let api_key_clone = Arc::clone(&api_key);
+        let location_clone = Arc::clone(&location);
+        let update_requested_clone = Arc::clone(&update_requested);
+        let weather_data_clone = Arc::clone(&weather_data);
+        let backoff_clone = Arc::clone(&backoff);
+        let restored_from_cache_clone = Arc::clone(&restored_from_cache);
+        let last_fetch_result_clone = Arc::clone(&last_fetch_result);
+        let heartbeat = Heartbeat::new();
+        let heartbeat_clone = heartbeat.clone();
+        
+        std::thread::spawn(move || {
+            let _exit_guard = heartbeat_clone.guard();
+            super::priority::enter_background("weather");
+            loop {
+                // Poll for update requests every 10 seconds
+                std::thread::sleep(std::time::Duration::from_secs(10));
+                if heartbeat_clone.stopped() {
+                    break;
+                }
+                
+                // Check if update is needed (atomic check-and-clear)
+                let requested = {
+                    let mut req = update_requested_clone.lock().unwrap();
+                    if *req {
+                        *req = false;
+                        true
+                    } else {
+                        false
+                    }
+                };
+                
+                if requested {
+                    let api_key = api_key_clone.lock().unwrap().clone();
+                    let location = location_clone.lock().unwrap().clone();
+                    
+                    if !api_key.is_empty() && !location.is_empty() {
+                        log::info!("Background: Fetching weather data for location: {}", location);
+                        match Self::fetch_weather_static(&api_key, &location) {
+                            Ok(data) => {
+                                log::info!("Background: Weather data fetched: {}°C, {} (icon: {})", 
+                                    data.temperature, data.description, data.icon);
+                                *last_fetch_result_clone.lock().unwrap() = Some(format!(
+                                    "OK: {:.1}°C, {} ({})", data.temperature, data.description, data.location));
+                                super::cache::WidgetCache::update_weather(&location, &data);
+                                *weather_data_clone.lock().unwrap() = Some(data);
+                                backoff_clone.lock().unwrap().record_success();
+                                *restored_from_cache_clone.lock().unwrap() = false;
+                            }
+                            Err(e) => {
+                                // Keep the previous data; it will be marked stale once too old
+                                let delay = backoff_clone.lock().unwrap().record_failure(&e);
+                                log::error!("Background: Failed to fetch weather: {} (next retry in {}s)",
+                                    e, delay.as_secs());
+                                *last_fetch_result_clone.lock().unwrap() = Some(format!("Error: {}", e));
+                                super::diagnostics::record_error("weather", &e);
+                            }
+                        }
+                    }
+                }
+                heartbeat_clone.beat();
+            }
+        });
+        
+        Self {
+            weather_data,
+            last_update: 0,
+            last_check: None,
+            api_key,
+            location,
+            update_requested,
+            backoff,
+            restored_from_cache,
+            last_fetch_result,
+            heartbeat,
+        }
+    }
+    
+    /// Heartbeat of the background fetch thread (for the watchdog).
+    pub fn heartbeat(&self) -> &Heartbeat {
+        &self.heartbeat
+    }
+    
+    /// Whether weather data is available (fetched or restored from cache).
+    pub fn has_sample(&self) -> bool {
+        self.weather_data.lock().unwrap().is_some()
+    }
+    
+    /// Coordinates of the weather station, once the API reported them.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let data = self.weather_data.lock().unwrap();
+        let data = data.as_ref()?;
+        Some((data.latitude?, data.longitude?))
+    }
+    
+    /// Human-readable result of the last fetch attempt, if any.
+    pub fn last_fetch_result(&self) -> Option<String> {
+        self.last_fetch_result.lock().unwrap().clone()
+    }
+    
+    /// Current weather error to badge the section with, if any.
+    ///
+    /// Reports missing configuration, or the last fetch error while the
+    /// monitor is backing off after failures.
+    pub fn error(&self) -> Option<String> {
+        if self.api_key.lock().unwrap().trim_matches('"').is_empty() {
+            return Some(String::from("No OpenWeatherMap API key configured"));
+        }
+        if self.location.lock().unwrap().trim_matches('"').is_empty() {
+            return Some(String::from("No weather location configured"));
+        }
+        if self.backoff.lock().unwrap().failures() == 0 {
+            return None;
+        }
+        self.last_fetch_result()
+            .map(|result| format!("Weather fetch failed: {}", result.trim_start_matches("Error: ")))
+    }
+    
+    /// Time until the next retry while backing off after failed fetches.
+    ///
+    /// `None` when the last fetch succeeded.
+    pub fn next_retry_in(&self) -> Option<Duration> {
+        let backoff = self.backoff.lock().unwrap();
+        if backoff.failures() == 0 {
+            return None;
+        }
+        backoff.retry_in()
+    }
+    
+    /// Status marker to render next to the location, if any.
+    ///
+    /// - Restored from cache, no fresh fetch yet: "updated 12 min ago"
+    /// - Fetched data older than 30 minutes: "stale (2 h)"
+    /// - Otherwise: `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `now_unix` - Current time as a Unix timestamp (seconds, UTC)
+    pub fn status_label(&self, now_unix: i64) -> Option<String> {
+        let data = self.weather_data.lock().unwrap();
+        let data = data.as_ref()?;
+        
+        if *self.restored_from_cache.lock().unwrap() && data.fetched_at > 0 {
+            let age = (now_unix.max(0) as u64).saturating_sub(data.fetched_at);
+            return Some(fl!("widget-weather-updated", age = format_age(age)));
+        }
+        
+        data.stale_label(now_unix)
+    }
+
+    /// Request a weather update if rate limit has elapsed.
+    ///
+    /// Rate-limited to once every 10 minutes (600 seconds) to respect
+    /// OpenWeatherMap API quotas. The actual API call runs in the background
+    /// thread - this just sets a flag.
+    ///
+    /// # Skipped When
+    ///
+    /// - API key is empty or not configured
+    /// - Location is empty or not configured
+    /// - Less than 10 minutes since last update
+    /// - Backing off after a failed fetch (see [`Backoff`])
+    pub fn update(&mut self) {
+        // Only update if we have an API key and location
+        {
+            let api_key = self.api_key.lock().unwrap();
+            let location = self.location.lock().unwrap();
+            
+            if api_key.is_empty() || location.is_empty() {
+                log::trace!("Weather update skipped: API key or location not configured");
+                return;
+            }
+        }
+        
+        let now = SystemTime::now();
+        let now_unix = now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        
+        // After a suspend (e.g., overnight) the data is hours old: refresh now,
+        // skipping both the 10 minute limit and any pending backoff delay
+        let resumed = self.last_check.is_some_and(|(mono, wall)| {
+            resumed_from_suspend(mono.elapsed(), now.duration_since(wall).unwrap_or_default())
+        });
+        self.last_check = Some((Instant::now(), now));
+        if resumed {
+            log::info!("Resumed from suspend, refreshing weather");
+            self.backoff.lock().unwrap().retry_now();
+            self.last_update = 0;
+        }
+        
+        // Don't update more than once every 10 minutes (API rate limiting).
+        // After failed fetches, the backoff schedule decides instead, so we
+        // recover quickly when connectivity returns without hammering the API.
+        {
+            let backoff = self.backoff.lock().unwrap();
+            if backoff.failures() > 0 {
+                if let Some(wait) = backoff.retry_in() {
+                    log::trace!("Weather update skipped: backing off ({}s until retry)", wait.as_secs());
+                    return;
+                }
+            } else {
+                // A backwards clock change (now < last_update) allows a fetch
+                let elapsed = now_unix.checked_sub(self.last_update);
+                if let Some(elapsed) = elapsed.filter(|&e| e < UPDATE_INTERVAL_SECS) {
+                    log::trace!("Weather update skipped: too soon ({}s since last update, need {}s)",
+                        elapsed, UPDATE_INTERVAL_SECS);
+                    return;
+                }
+            }
+        }
+        
+        log::info!("Requesting weather update from background thread");
+        *self.update_requested.lock().unwrap() = true;
+        self.last_update = now_unix;
+    }
+    
+    /// Fetch weather data from OpenWeatherMap API (blocking).
+    ///
+    /// This is a static method called from the background thread.
+    ///
+    /// # API Request
+    ///
+    /// ```text
+    /// GET https://api.openweathermap.org/data/2.5/weather?q={location}&appid={key}&units=metric
+    /// ```
+    ///
+    /// # Processing
+    ///
+    /// 1. Strip quotes from config values (cosmic_config quirk)
+    /// 2. Build API URL with metric units
+    /// 3. Make HTTP request with 5-second timeout (shared client)
+    /// 4. Parse JSON response
+    /// 5. Capitalize weather description
+    /// 6. Return processed WeatherData
+    fn fetch_weather_static(api_key: &str, location: &str) -> HttpResult<WeatherData> {
+        // Strip quotes from location and API key (cosmic_config may store them with quotes)
+        let location = location.trim_matches('"');
+        let api_key = api_key.trim_matches('"');
+        
+        log::debug!("Making API request for location: {}", location);
+        
+        let url = format!(
+            "https://api.openweathermap.org/data/2.5/weather?q={}&appid={}&units=metric",
+            location, api_key
+        );
+
+        // Shared client with a 5 second timeout to prevent blocking indefinitely
+        let response: OpenWeatherResponse =
+            super::http::get_json(&url, &[], Duration::from_secs(5))?;
+        
+        log::debug!("Weather API response received for: {}", response.name);
+
+        // Capitalize first letter of description
+        let description = response
+            .weather
+            .first()
+            .map(|w| {
+                let mut desc = w.description.clone();
+                if let Some(first_char) = desc.chars().next() {
+                    desc = first_char.to_uppercase().collect::<String>() + &desc[first_char.len_utf8()..];
+                }
+                desc
+            })
+            .unwrap_or_else(|| String::from("Unknown"));
+
+        // Extract icon code (e.g., "01d", "10n")
+        let icon = response
+            .weather
+            .first()
+            .map(|w| w.icon.clone())
+            .unwrap_or_else(|| String::from("01d"));
+
+        // Wind is optional in the API response, default to calm
+        let (wind_speed, wind_deg) = response
+            .wind
+            .as_ref()
+            .map(|w| (w.speed, w.deg))
+            .unwrap_or((0.0, 0));
+
+        Ok(WeatherData {
+            temperature: response.main.temp,
+            feels_like: response.main.feels_like,
+            temp_min: response.main.temp_min,
+            temp_max: response.main.temp_max,
+            humidity: response.main.humidity,
+            wind_speed,
+            wind_deg,
+            description,
+            icon,
+            location: response.name,
+            latitude: response.coord.as_ref().map(|c| c.lat),
+            longitude: response.coord.as_ref().map(|c| c.lon),
+            sunrise: response.sys.as_ref().and_then(|s| s.sunrise),
+            sunset: response.sys.as_ref().and_then(|s| s.sunset),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        })
+    }
+    
+    /// Update the API key (called when settings change).
+    pub fn set_api_key(&mut self, api_key: String) {
+        *self.api_key.lock().unwrap() = api_key;
+    }
+    
+    /// Update the location query (called when settings change).
+    pub fn set_location(&mut self, location: String) {
+        *self.location.lock().unwrap() = location;
+    }
+}
+
+impl Drop for WeatherMonitor {
+    fn drop(&mut self) {
+        self.heartbeat.stop();
+    }
+}
+
+/// Whether the system was suspended between two checks.
+///
+/// The monotonic clock stops during suspend while the wall clock keeps
+/// going, so a large difference between the two reveals a resume.
+///
+/// # Arguments
+///
+/// * `monotonic` - Elapsed time according to `Instant`
+/// * `wall_clock` - Elapsed time according to `SystemTime`
+fn resumed_from_suspend(monotonic: Duration, wall_clock: Duration) -> bool {
+    wall_clock > monotonic + RESUME_GAP
+}
+
+// ============================================================================
+// Sunrise / Sunset Calculation
+// ============================================================================
+
+/// Julian date of the J2000.0 epoch (2000-01-01 12:00 UTC).
+const J2000: f64 = 2451545.0;
+
+/// Julian date of the Unix epoch (1970-01-01 00:00 UTC).
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+/// Result of the sunrise equation for a single solar day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SunTimes {
+    /// Sun rises and sets; Unix timestamps (seconds, UTC)
+    Normal { sunrise: i64, sunset: i64 },
+    /// Sun stays above the horizon all day (polar summer)
+    PolarDay,
+    /// Sun stays below the horizon all day (polar winter)
+    PolarNight,
+}
+
+/// Compute sunrise and sunset for the solar day closest to `now_unix`.
+///
+/// Uses the standard sunrise equation (mean anomaly, equation of center,
+/// ecliptic longitude, declination) with the -0.833° correction for
+/// atmospheric refraction and the solar disc radius. Accuracy is within
+/// a couple of minutes, which is plenty for picking an icon variant.
+///
+/// # Arguments
+///
+/// * `lat` - Latitude in degrees (north positive)
+/// * `lon` - Longitude in degrees (east positive)
+/// * `now_unix` - Reference time as a Unix timestamp (seconds, UTC)
+pub fn sun_times(lat: f64, lon: f64, now_unix: i64) -> SunTimes {
+    let jd = now_unix as f64 / 86400.0 + UNIX_EPOCH_JD;
+    
+    // Pick the solar noon nearest to `now` at this longitude
+    let n = (jd - J2000 + lon / 360.0).round();
+    let mean_solar_time = n - lon / 360.0;
+    
+    let mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
+    let m = mean_anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.0200 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_longitude = (mean_anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+    let lambda = ecliptic_longitude.to_radians();
+    
+    let transit = J2000 + mean_solar_time + 0.0053 * m.sin() - 0.0069 * (2.0 * lambda).sin();
+    
+    let declination = (lambda.sin() * 23.4397_f64.to_radians().sin()).asin();
+    let phi = lat.to_radians();
+    let cos_hour_angle = ((-0.833_f64).to_radians().sin() - phi.sin() * declination.sin())
+        / (phi.cos() * declination.cos());
+    
+    if cos_hour_angle > 1.0 {
+        return SunTimes::PolarNight;
+    }
+    if cos_hour_angle < -1.0 {
+        return SunTimes::PolarDay;
+    }
+    
+    let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+    let to_unix = |jd: f64| ((jd - UNIX_EPOCH_JD) * 86400.0).round() as i64;
+    
+    SunTimes::Normal {
+        sunrise: to_unix(transit - half_day),
+        sunset: to_unix(transit + half_day),
+    }
+}
+
+/// Whether the sun is above the horizon at the given place and time.
+///
+/// # Arguments
+///
+/// * `lat` - Latitude in degrees (north positive)
+/// * `lon` - Longitude in degrees (east positive)
+/// * `now_unix` - Time to check as a Unix timestamp (seconds, UTC)
+pub fn is_daytime(lat: f64, lon: f64, now_unix: i64) -> bool {
+    sun_times(lat, lon, now_unix).is_up(now_unix)
+}
+
+impl SunTimes {
+    /// Whether the sun is above the horizon at `now_unix` (Unix seconds).
+    pub fn is_up(&self, now_unix: i64) -> bool {
+        match *self {
+            SunTimes::Normal { sunrise, sunset } => now_unix >= sunrise && now_unix < sunset,
+            SunTimes::PolarDay => true,
+            SunTimes::PolarNight => false,
+        }
+    }
+}
+
+// ============================================================================
+// Moon Phase
+// ============================================================================
+
+/// Length of the synodic month (new moon to new moon) in days.
+const SYNODIC_MONTH: f64 = 29.530588853;
+
+/// Julian date of a known new moon (2000-01-06 18:14 UTC).
+const NEW_MOON_JD: f64 = 2451550.26;
+
+/// Moon phase as a fraction of the synodic month.
+///
+/// 0.0 is new moon, 0.25 first quarter, 0.5 full moon and 0.75 third
+/// quarter. Counted from a known new moon with the mean month length,
+/// which drifts by up to about half a day from the true phase.
+///
+/// # Arguments
+///
+/// * `now_unix` - Time as a Unix timestamp (seconds, UTC)
+pub fn moon_phase(now_unix: i64) -> f64 {
+    let jd = now_unix as f64 / 86400.0 + UNIX_EPOCH_JD;
+    ((jd - NEW_MOON_JD) / SYNODIC_MONTH).rem_euclid(1.0)
+}
+
+/// Weather Icons glyph of a moon phase (one of 28 steps).
+///
+/// The font has `wi-moon-alt-new` at U+F0EB and the 27 other steps from
+/// waxing crescent to waning crescent at U+F0D0..U+F0EA.
+fn moon_glyph(phase: f64) -> char {
+    let step = (phase * 28.0).round() as u32 % 28;
+    let code = if step == 0 { 0xf0eb } else { 0xf0d0 + step - 1 };
+    char::from_u32(code).unwrap_or('\u{f0eb}')
+}
+
+/// Name of a moon phase ("Waxing gibbous").
+pub fn moon_phase_name(phase: f64) -> String {
+    match (phase * 8.0).round() as u32 % 8 {
+        0 => fl!("moon-new"),
+        1 => fl!("moon-waxing-crescent"),
+        2 => fl!("moon-first-quarter"),
+        3 => fl!("moon-waxing-gibbous"),
+        4 => fl!("moon-full"),
+        5 => fl!("moon-waning-gibbous"),
+        6 => fl!("moon-third-quarter"),
+        _ => fl!("moon-waning-crescent"),
+    }
+}
+
+// ============================================================================
+// Wind Direction
+// ============================================================================
+
+/// Convert a wind direction in degrees to an 8-point compass label.
+///
+/// # Arguments
+///
+/// * `deg` - Meteorological direction in degrees (0 = north, clockwise)
+///
+/// # Returns
+///
+/// One of "N", "NE", "E", "SE", "S", "SW", "W", "NW"
+pub fn wind_direction_label(deg: u16) -> &'static str {
+    const DIRECTIONS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    // Each sector is 45° wide, centered on its direction (N = 337.5°..22.5°)
+    let index = (((deg % 360) as f32 + 22.5) / 45.0) as usize % 8;
+    DIRECTIONS[index]
+}
+
+// ============================================================================
+// Weather Icon Drawing
+// ============================================================================
+
+/// Draw a weather icon using the Weather Icons font.
+///
+/// Maps OpenWeatherMap icon codes to Weather Icons Unicode characters
+/// and renders them using Pango/Cairo.
+///
+/// # Arguments
+///
+/// * `cr` - Cairo context for drawing
+/// * `x` - Left edge X coordinate
+/// * `y` - Top edge Y coordinate
+/// * `size` - Icon size in pixels (width and height)
+/// * `icon_code` - OpenWeatherMap icon code (e.g., "01d", "10n")
+///
+/// # Icon Code Format
+///
+/// OpenWeatherMap uses codes like "01d" or "10n":
+/// - First 2 chars: Weather condition (01-50)
+/// - Last char: Day (d) or Night (n)
+///
+/// # Weather Condition Mapping
+///
+/// | Code | Day Icon | Night Icon | Condition |
+/// |------|----------|------------|-----------|
+/// | 01   | sunny    | clear      | Clear sky |
+/// | 02   | cloudy   | cloudy     | Few clouds |
+/// | 03   | overcast | partly     | Scattered clouds |
+/// | 04   | cloudy   | cloudy     | Broken clouds |
+/// | 09   | showers  | showers    | Shower rain |
+/// | 10   | rain     | rain       | Rain |
+/// | 11   | storm    | storm      | Thunderstorm |
+/// | 13   | snow     | snow       | Snow |
+/// | 50   | fog      | fog        | Mist/Fog |
+pub fn draw_weather_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, icon_code: &str) {
+    // Parse icon code: first 2 chars are condition, last char is day(d) or night(n)
+    let condition = icon_code.get(..2).unwrap_or("01");
+    let is_day = icon_code.ends_with('d');
+    
+    // Map OpenWeatherMap icon codes to Weather Icons font Unicode characters
+    // Reference: https://erikflowers.github.io/weather-icons/
+    let icon_char = match condition {
+        "01" => if is_day { "\u{f00d}" } else { "\u{f02e}" },  // wi-day-sunny / wi-night-clear
+        "02" => if is_day { "\u{f002}" } else { "\u{f086}" },  // wi-day-cloudy / wi-night-alt-cloudy
+        "03" => if is_day { "\u{f013}" } else { "\u{f031}" },  // wi-day-sunny-overcast / wi-night-partly-cloudy
+        "04" => "\u{f041}",                                     // wi-cloudy (same day/night)
+        "09" => if is_day { "\u{f009}" } else { "\u{f029}" },  // wi-day-showers / wi-night-alt-showers
+        "10" => if is_day { "\u{f008}" } else { "\u{f028}" },  // wi-day-rain / wi-night-alt-rain
+        "11" => if is_day { "\u{f010}" } else { "\u{f02d}" },  // wi-day-thunderstorm / wi-night-alt-thunderstorm
+        "13" => if is_day { "\u{f00a}" } else { "\u{f02a}" },  // wi-day-snow / wi-night-alt-snow
+        "50" => if is_day { "\u{f003}" } else { "\u{f04a}" },  // wi-day-fog / wi-night-fog
+        _ => "\u{f041}",                                        // Default to wi-cloudy
+    };
+    
+    draw_glyph(cr, x, y, size, icon_char);
+}
+
+/// Draw the sunrise (`wi-sunrise`) or sunset (`wi-sunset`) icon.
+pub fn draw_sun_event_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, sunrise: bool) {
+    draw_glyph(cr, x, y, size, if sunrise { "\u{f051}" } else { "\u{f052}" });
+}
+
+/// Draw the moon as it looks in `phase` (see [`moon_phase`]).
+pub fn draw_moon_icon(cr: &cairo::Context, x: f64, y: f64, size: f64, phase: f64) {
+    draw_glyph(cr, x, y, size, &moon_glyph(phase).to_string());
+}
+
+/// Draw a Weather Icons glyph centered in a `size` box, outlined.
+fn draw_glyph(cr: &cairo::Context, x: f64, y: f64, size: f64, glyph: &str) {
+    // Create pango layout for text/icon rendering
+    let layout = pangocairo::functions::create_layout(cr);
+    
+    // Use the Weather Icons font at slightly smaller than requested size
+    // (0.9 factor for visual balance)
+    let font_desc = pango::FontDescription::from_string(&format!("Weather Icons {}", (size * 0.9) as i32));
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(glyph);
+    
+    // Get text dimensions for centering
+    let (text_width, text_height) = layout.pixel_size();
+    
+    // Center the icon within the requested size box
+    let text_x = x + (size - text_width as f64) / 2.0;
+    let text_y = y + (size - text_height as f64) / 2.0;
+    
+    cr.move_to(text_x, text_y);
+    
+    // Draw with the text outline and fill for visibility on any background
+    pangocairo::functions::layout_path(cr, &layout);
+    set_text_outline(cr);
+    cr.set_line_width(3.0);
+    cr.stroke_preserve().ok();
+    set_text_fill(cr);
+    cr.fill().ok();
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_detection() {
+        // Normal one-second tick, small NTP adjustment
+        assert!(!resumed_from_suspend(Duration::from_secs(1), Duration::from_secs(3)));
+        // Eight hours asleep: monotonic barely moved
+        assert!(resumed_from_suspend(Duration::from_secs(1), Duration::from_secs(8 * 3600)));
+    }
+
+    // 2024-06-21 and 2024-12-21, London (51.5N, 0.13W)
+    const LONDON: (f64, f64) = (51.5074, -0.1278);
+    const SUMMER_NOON_UTC: i64 = 1718971200;
+    const WINTER_NOON_UTC: i64 = 1734782400;
+
+    #[test]
+    fn test_london_summer_day_and_night() {
+        let (lat, lon) = LONDON;
+        assert!(is_daytime(lat, lon, SUMMER_NOON_UTC));
+        // 23:00 UTC is well after sunset (~20:21 UTC)
+        assert!(!is_daytime(lat, lon, SUMMER_NOON_UTC + 11 * 3600));
+
+        match sun_times(lat, lon, SUMMER_NOON_UTC) {
+            SunTimes::Normal { sunrise, sunset } => {
+                // Sunrise ~03:43 UTC, sunset ~20:21 UTC (within 10 minutes)
+                assert!((sunrise - (SUMMER_NOON_UTC - 8 * 3600 - 17 * 60)).abs() < 600);
+                assert!((sunset - (SUMMER_NOON_UTC + 8 * 3600 + 21 * 60)).abs() < 600);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_london_winter_afternoon_is_night() {
+        let (lat, lon) = LONDON;
+        // 17:00 UTC in December is after sunset (~15:54 UTC)
+        assert!(!is_daytime(lat, lon, WINTER_NOON_UTC + 5 * 3600));
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        // Svalbard
+        assert_eq!(sun_times(78.2, 15.6, SUMMER_NOON_UTC), SunTimes::PolarDay);
+        assert_eq!(sun_times(78.2, 15.6, WINTER_NOON_UTC), SunTimes::PolarNight);
+    }
+
+    #[test]
+    fn test_moon_phase() {
+        // Full moon 2024-06-22 01:08 UTC, new moon 2024-07-05 22:57 UTC
+        let full = moon_phase(1719018480);
+        assert!((full - 0.5).abs() < 0.02, "{}", full);
+        let new = moon_phase(1720220220);
+        assert!(new < 0.02 || new > 0.98, "{}", new);
+        assert_eq!(moon_glyph(0.5), '\u{f0dd}');
+        assert_eq!(moon_glyph(0.99), '\u{f0eb}');
+        assert_eq!(moon_glyph(0.25), '\u{f0d6}');
+    }
+
+    #[test]
+    fn test_stale_label() {
+        let data = WeatherData { fetched_at: 1_000_000, ..WeatherData::default() };
+        assert_eq!(data.stale_label(1_000_000 + 600), None);
+        assert_eq!(data.stale_label(1_000_000 + 2 * 3600 + 120).as_deref(), Some("stale (2 h)"));
+        // Never-fetched data has no timestamp to compare against
+        assert_eq!(WeatherData::default().stale_label(1_000_000), None);
+    }
+
+    #[test]
+    fn test_display_icon_overrides_suffix() {
+        let data = WeatherData {
+            icon: String::from("01d"),
+            latitude: Some(LONDON.0),
+            longitude: Some(LONDON.1),
+            ..WeatherData::default()
+        };
+        assert_eq!(data.display_icon(SUMMER_NOON_UTC), "01d");
+        assert_eq!(data.display_icon(SUMMER_NOON_UTC + 11 * 3600), "01n");
+
+        // Without coordinates the API icon is kept as-is
+        let no_coords = WeatherData { icon: String::from("10d"), ..WeatherData::default() };
+        assert_eq!(no_coords.display_icon(SUMMER_NOON_UTC + 11 * 3600), "10d");
+    }
+}
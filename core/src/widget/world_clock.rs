@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! World Clocks
+//!
+//! Extra time zones shown as small rows under the main clock. Zones are
+//! IANA names (`Europe/London`, `America/New_York`) resolved with
+//! chrono-tz, so no system time zone database is needed:
+//!
+//! ```text
+//! New York   08:15
+//! Tokyo      22:15 +1
+//! ```
+//!
+//! A row's time is followed by `+1`/`-1` when that zone is already on the
+//! next (or still on the previous) day.
+
+use chrono::{DateTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::config::WorldClock;
+use crate::fl;
+
+/// A resolved world clock, ready to draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldClockRow {
+    /// Configured label, or the zone's city
+    pub label: String,
+    /// Time in the zone (with a day offset), or why it couldn't be shown
+    pub time: String,
+    /// The zone name is not a known IANA zone
+    pub invalid: bool,
+}
+
+/// Clocks with a zone set (rows still being typed in are skipped).
+pub fn active_clocks(clocks: &[WorldClock]) -> impl Iterator<Item = &WorldClock> {
+    clocks.iter().filter(|clock| !clock.zone.trim().is_empty())
+}
+
+/// Resolve the active clocks at `now`.
+pub fn world_clock_rows<T: TimeZone>(clocks: &[WorldClock], now: &DateTime<T>, use_24hour_time: bool) -> Vec<WorldClockRow> {
+    active_clocks(clocks)
+        .map(|clock| {
+            let zone = clock.zone.trim();
+            let label = clock_label(clock);
+            match zone.parse::<Tz>() {
+                Ok(tz) => {
+                    let local = now.with_timezone(&tz);
+                    let mut time = if use_24hour_time {
+                        local.format("%H:%M").to_string()
+                    } else {
+                        local.format("%-I:%M %p").to_string()
+                    };
+                    let days = (local.date_naive() - now.date_naive()).num_days();
+                    if days != 0 {
+                        time.push_str(&format!(" {:+}", days));
+                    }
+                    WorldClockRow { label, time, invalid: false }
+                }
+                Err(_) => WorldClockRow {
+                    label,
+                    time: fl!("world-clock-unknown"),
+                    invalid: true,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Configured label of a clock, or its zone's city.
+pub fn clock_label(clock: &WorldClock) -> String {
+    if clock.label.trim().is_empty() {
+        default_label(clock.zone.trim())
+    } else {
+        clock.label.trim().to_string()
+    }
+}
+
+/// City part of a zone name (`America/New_York` → `New York`).
+pub fn default_label(zone: &str) -> String {
+    zone.rsplit('/').next().unwrap_or(zone).replace('_', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn clock(zone: &str, label: &str) -> WorldClock {
+        WorldClock {
+            zone: zone.to_string(),
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_world_clock_rows() {
+        // 23:30 UTC: Tokyo is already on the next day, New York is not
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 23, 30, 0).unwrap();
+        let clocks = [
+            clock("America/New_York", ""),
+            clock("Asia/Tokyo", "Office"),
+            clock("  ", "typing"),
+            clock("Mars/Olympus_Mons", ""),
+        ];
+        let rows = world_clock_rows(&clocks, &now, true);
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!((rows[0].label.as_str(), rows[0].time.as_str()), ("New York", "18:30"));
+        assert_eq!((rows[1].label.as_str(), rows[1].time.as_str()), ("Office", "08:30 +1"));
+        assert!(rows[2].invalid);
+        assert_eq!(rows[2].label, "Olympus Mons");
+    }
+}
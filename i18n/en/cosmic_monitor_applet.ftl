@@ -9,6 +9,12 @@ git-description = Git commit {$hash} on {$date}
 show-widget = Show Widget
 hide-widget = Hide Widget
 configure = Configure
+copy-stats = Copy Stats
+applet-collecting = Collecting stats…
+applet-cpu-temp = CPU Temperature
+applet-gpu-temp = GPU Temperature
+applet-memory-used = { $percent } ({ $used } of { $total })
+applet-disk-used = { $percent } of { $total }
 
 # Configuration UI
 monitoring-options = Monitoring Options
@@ -17,30 +23,490 @@ widget-display = Widget Display
 temperature-display = Temperature Display
 storage-display = Storage Display
 show-cpu = Show CPU Usage
+show-cpu-frequency = Show CPU Frequency
+show-cpu-governor = Show CPU Governor
+cpu-governor-click = Click the Governor to Switch It (asks for your password)
+show-hardware-info = Show CPU and GPU Models
 show-memory = Show Memory Usage
 show-network = Show Network Activity
+network-rate-bits = Network Rates in Bits per Second
+show-network-graph = Show Network Graph
+network-graph-window = Network Graph Window (seconds)
 show-disk = Show Disk I/O
 show-storage = Show Storage Usage
+storage-min-free = Warn Below Free Space (%, 0 = off)
+storage-max-inode = Warn Above Inode Use (%, 0 = off)
+storage-alerts = Notify When a Filesystem Runs Low
+show-smart = Show Drive Health and Temperature
+smart-refresh = Drive Health Refresh Interval (minutes)
+show-storage-pools = Show ZFS and btrfs Pool Status
+storage-pools-refresh = Pool Status Refresh Interval (minutes)
 show-gpu = Show GPU Usage
+show-gpu-processes = List Top GPU Processes (uses more CPU)
+show-gpu-details = Show GPU Fan and Clocks
+show-psi = Show CPU, Memory and I/O Pressure
+show-power-profile = Show Power Profile (click to switch)
 show-cpu-temp = Show CPU Temperature
 show-gpu-temp = Show GPU Temperature
 use-circular-temp-display = Use Circular Temperature Display
+temps-on-usage-bars = Show Temperatures on Usage Bars
+show-hottest-core = Show Hottest Core
+show-throttling = Show Thermal Throttling Badge
+show-temp-graph = Show Temperature Graph
+temp-graph-window = Temperature Graph Window (minutes)
+temp-graph-mode = Temperature Graph Placement
+show-hostname = Show Hostname
+show-username = Show User Name
+identity-label = Custom Label
+identity-label-automatic = Hostname and user name
+identity-label-description = Shown at the top of the widget instead of the hostname and user name, e.g. to keep your hostname off a stream. Needs one of the two toggles above.
 show-clock = Show Clock
 show-date = Show Date
 use-24hour-time = Use 24-Hour Time Format
+time-format-description = Clock and date formats use strftime patterns, e.g. %H:%M:%S for seconds, %Y-%m-%d for ISO dates, or %V for the week number. Leave empty for the defaults.
+clock-format = Clock Format
+clock-format-automatic = Automatic
+date-format = Date Format
+time-format-example = Preview: { $example }
+time-format-invalid = Not a valid format; the last valid one is kept
+world-clocks-description = World clocks: extra time zones shown under the clock. Use IANA names such as America/New_York or Asia/Tokyo; an empty label shows the city.
+world-clock-zone = Time zone
+world-clock-label = Label
+world-clock-unknown = Unknown time zone
+world-clock-add = Add Time Zone
+calendar = Calendar
+calendar-description = Upcoming events shown under the date, read from a local .ics file or from the calendars set up in Evolution / GNOME Online Accounts (Evolution Data Server). Events of the next 30 days are looked up.
+show-calendar = Show Upcoming Events
+calendar-source = Source
+calendar-ics-path = Calendar File (.ics)
+calendar-max-events = Events Shown
+calendar-refresh = Refresh Interval (minutes)
 show-percentages = Show Percentages
+memory-label = Memory Value
+show-tooltips = Show Detail Tooltips on Hover
+show-panel-readout = Show CPU, RAM and Temperature in the Panel Applet
+enable-animations = Animate Transitions
+layout-mode = Layout
+layout-columns = Columns (Vertical Layout)
+text-contrast = Text Color
+text-contrast-description = Dark text keeps the widget readable on light wallpapers. "Follow dark mode" switches with COSMIC's light and dark theme.
+fonts = Fonts
+fonts-description = Font family and size (points) of the clock, section headers, body text and small labels. Each size scales all text of its kind, so a larger body font also enlarges bold values; rows don't get taller, so sizes far above the defaults (48, 14, 12, 10) crowd the widget.
+font-not-installed = "{ $family }" is not installed; the widget uses { $fallback } instead
 update-interval = Update Interval (ms)
+save-and-apply = Save & Apply Settings
+
+# Bar colors
+bar-colors = Bar Colors
+bar-colors-description = Bars and temperature gauges turn to the warning color at the first value and the critical color at the second (percent, or °C for temperatures). Colors are #RRGGBB.
+threshold-warning = Warning
+threshold-critical = Critical
+ambient-source = Ambient Status
+ambient-target = Ambient Status Shows On
+ambient-description = Tints the clock outline or the widget background with the busiest usage or the hottest temperature, blending from the normal to the warning and critical colors as the thresholds above come near.
+
+# Notifications
+notifications = Notifications
+show-notifications = Show Notifications
+max-notifications = Max Notifications
+
+# Notification history
+compact-notifications = Show Bodies Only on Hover
+notification-history = Keep Notifications After Restart
+notification-history-count = History Size (notifications)
+notification-history-days = Keep for (days, 0 = no limit)
+notification-history-description = Captured notifications are saved to ~/.local/state/cosmic-monitor/notifications.jsonl and shown again when the widget restarts. Turning this off deletes the file.
+notification-history-clear = Clear Notification History
+
+# Scroll bindings (clock, media and notification sections)
+scroll-action = Scroll Wheel
 
 # Weather
 weather-display = Weather Display
 show-weather = Show Weather
+weather-detailed = Detailed Weather (feels like, humidity, wind)
+weather-sun-times = Show Sunrise and Sunset
+weather-moon-phase = Show Moon Phase at Night (in the sunrise and sunset row)
+show-air-quality = Show Air Quality Badge
+air-quality-provider = Air Quality Source (OpenWeatherMap uses the weather API key)
 weather-api-key = OpenWeatherMap API Key
 weather-location = Location (e.g., London, New York)
 
+# Battery
+battery = Battery
+show-battery = Show battery section
+enable-solaar = Enable Solaar integration
+cached-devices = Cached Devices:
+
+# Custom commands
+custom-commands = Custom Commands
+custom-commands-description = Show the output of your own shell commands as rows in the widget. Each command runs with sh -c on its own interval (seconds); {"{}"} in the format is replaced by the first line of its output.
+show-custom-commands = Show Custom Commands
+custom-command-label = Label
+custom-command-command = Command
+custom-command-interval = Seconds
+custom-command-format = Format
+custom-command-add = Add Command
+system-health = System Health
+system-health-description = Counts failed systemd units (system and user), like systemctl --failed. Optionally also counts pending package updates with a command that prints one update per line. Hover the failed units in the widget to see their names.
+show-system-health = Show System Health
+health-update-command = Update Command
+health-refresh = Refresh Interval (minutes)
+containers = Containers
+containers-description = Counts running Docker and Podman containers (rootless Podman included). Optionally also shows the container using the most CPU, which samples every container for a moment on each refresh. Hover the count in the widget to see the container names.
+show-containers = Show Containers
+containers-show-busiest = Show Busiest Container
+containers-refresh = Refresh Interval (minutes)
+vms = Virtual Machines
+vms-description = Lists running libvirt virtual machines with their vCPUs and current memory, read with virsh. qemu:///system (virt-manager) needs your user in the libvirt group; GNOME Boxes VMs live at qemu:///session.
+show-vms = Show Virtual Machines
+vms-uri = Connection URI
+vms-refresh = Refresh Interval (minutes)
+system-section = System
+system-section-description = Shows how many processes and threads are running and how often the kernel switches between tasks each second. A fork loop, a thread leak or a busy-polling program often shows here before it shows in CPU usage.
+show-kernel-stats = Show Processes, Threads and Context Switches
+feeds = Mail & Feeds
+feeds-description = Shows the unread count of IMAP mailboxes and the latest headline of RSS or Atom feeds. Mailboxes are checked with curl; put the password in ~/.netrc (machine imap.example.com login you password secret).
+show-feeds = Show Mail & Feeds
+feeds-refresh = Refresh Interval (minutes)
+feed-label = Label
+feed-add = Add Feed
+ticker = Ticker
+ticker-description = Shows the price and 24-hour change of cryptocurrencies or stocks. CoinGecko needs no API key and takes coin ids like bitcoin. For stocks, point the JSON endpoint at an API that returns one quote per symbol, with {"{"}symbol{"}"} in the URL and JSON pointers to the price and change.
+show-ticker = Show Ticker
+ticker-provider = Provider
+ticker-symbols = Symbols (comma-separated)
+ticker-max-symbols = Most Symbols Shown
+ticker-currency = Currency
+ticker-endpoint = Endpoint URL
+ticker-price-path = Price Field (JSON pointer)
+ticker-change-path = 24h Change Field (JSON pointer, in percent)
+ticker-refresh = Refresh Interval (minutes)
+plugins = Plugins
+plugins-description = Draw your own sections with Lua scripts placed in ~/.config/cosmic-monitor/plugins. Scripts are reloaded when they change. Requires a widget built with the lua-plugins feature.
+show-plugins = Show Plugins
+
+# Media
+media-player = Media Player
+show-media = Show Media Player
+cider-api-token = Cider API Token
+cider-api-token-placeholder = Leave empty if auth disabled
+cider-description = Displays currently playing track from Cider (Apple Music client)
+media-marquee = Scroll Long Titles
+marquee-speed = Title Scroll Speed (px/s)
+marquee-pause = Title Pause at Ends (ms)
+show-lyrics = Show Lyrics
+lyrics-description = Shows the line being sung under the track info. Synced lyrics are looked up on lrclib.net by artist, title, album and length; tracks it doesn't know show no lyrics.
+
+# Audio
+audio = Audio Output
+audio-description = Volume and name of the default output device. Click the section in the widget to mute or unmute. Reads PulseAudio or PipeWire through pactl.
+show-audio = Show Audio Output
+
+# Caffeine
+caffeine = Caffeine
+caffeine-description = A coffee cup that keeps the screen from blanking while it is switched on; click it in the widget to switch. Works while the widget is visible, on compositors with the idle inhibit protocol.
+show-caffeine = Show Caffeine Toggle
+caffeine-on-start = Switch On When the Widget Starts
+
 # Layout
 layout-order = Layout Order
-layout-order-description = Use the arrow buttons to change the order sections appear in the widget
+layout-order-description = Use the arrow buttons to change the order sections appear in the widget, or click a section and then the row it should move to. The dropdown on the right draws a section on a rounded translucent card of its own, at the chosen opacity, for a dashboard look; the corners follow your COSMIC theme.
+card-none = No card
+layout-bundle = Share Layout
+layout-bundle-description = Export the display style and section layout to a file, or import one shared by someone else. Position, API keys, and advanced settings are not included.
+layout-bundle-file = Bundle File
+layout-bundle-export = Export
+layout-bundle-import = Import
+layout-bundle-exported = Exported layout to { $path }
+layout-bundle-imported = Imported layout "{ $name }"
+layout-bundle-failed = Failed: { $error }
+
+# Profiles
+profiles = Profiles
+profiles-description = Save the complete configuration under a name and switch between setups, start from a preset, or move everything to another machine as a file. Profiles include position, API keys, and custom commands, so only import files you trust.
+profiles-presets = Presets
+profiles-save-as = Save current settings as
+profiles-name-placeholder = Profile name
+profiles-save = Save
+profiles-switch = Switch
+profiles-delete = Delete
+profiles-file = Profile File
+profiles-export = Export
+profiles-import = Import
+profiles-preset-applied = Applied the "{ $name }" preset
+profiles-saved = Saved profile "{ $name }"
+profiles-switched = Switched to profile "{ $name }"
+profiles-deleted = Deleted profile "{ $name }"
+profiles-exported = Exported configuration to { $path }
+profiles-imported = Imported configuration from { $path }
+profiles-failed = Failed: { $error }
+
+# Advanced
+log-level = Log Level
+write-logs = Write Logs to a File
+write-logs-description = Writes rotating logs to ~/.local/state/cosmic-monitor (applies after a restart)
+show-debug-overlay = Show Debug Overlay
+debug-overlay-description = Frame time, draw time, buffer size, sampler latency and memory use in the widget's corner (also: cosmic-monitor-widget --debug-overlay)
+publish-stats = Publish Stats for Other Tools
+publish-stats-description = Writes the latest metrics to $XDG_RUNTIME_DIR/cosmic-monitor-widget-stats.json
+export-frames = Export Frames for OBS
+export-frames-description = Writes each rendered frame to $XDG_RUNTIME_DIR/cosmic-monitor-widget-frame.png (add as an OBS Image source)
+record-metrics = Record Metric History
+record-metrics-description = Appends metrics to daily CSV files in ~/.local/share/cosmic-monitor-applet/history
+record-interval = History Interval (seconds)
+record-retention = History Retention (days, 0 = forever)
+low-priority-collectors = Low-Priority Monitoring
+idle-priority-collectors = Idle Priority (SCHED_IDLE)
+pin-efficiency-cores = Pin Monitoring to Efficiency Cores
+collector-priority-description = Runs data collection at the lowest CPU/IO priority (and on E-cores of hybrid CPUs) so it doesn't compete with your workloads. Idle priority only lets it run while a CPU is otherwise idle, so sections may go stale under full load. Takes effect when the widget restarts.
+check-for-updates = Check for Updates
+check-for-updates-description = Asks GitHub once a day for the latest release and shows a small badge on the widget when a newer version is out. Click the badge to open the release page.
 
 # Widget Behavior
 widget-autostart = Auto-start widget on login
 
+# Settings pages
+page-general = General
+page-layout = Layout
+page-sections = Sections
+page-weather = Weather
+page-media = Media
+page-alerts = Alerts
+page-advanced = Advanced
+page-position = Position
+page-diagnostics = Diagnostics
+
+# Invalid stored settings
+config-issues = Some stored settings could not be used and were replaced:
+config-issues-dismiss = Dismiss
+
+# Settings search
+search-placeholder = Search settings
+search-results = Search Results
+search-no-results = No settings match "{ $query }"
+
+# Live preview
+preview = Preview
+preview-description = Sample data, rendered like the widget. Changes show up here right away.
+preview-unavailable = The preview could not be rendered.
+
+# Position picker
+widget-position = Widget Position
+position-description = Drag the widget to where it should appear. It is anchored to the nearest corner of the screen it's on, so it stays in place when the resolution changes.
+position-summary = { $corner } corner, { $x } × { $y } px from the edges, on { $output }
+position-any-output = the compositor's default output
+position-unavailable = The screen layout could not be drawn.
+position-reset = Reset Position
+snap-to-edges = Snap to Screen Edges and Center While Dragging
+snap-grid = Drag Grid (pixels, 0 = off)
+click-through = Click-Through
+click-through-description = Clicks pass through to windows underneath (media and notification buttons stop working). Paused while this window is open so the widget can be dragged.
+keyboard-positioning = Keyboard Positioning
+keyboard-positioning-description = Click the widget to focus it, then press Enter to start moving it, the arrow keys to move it (hold Shift for 10 pixels at a time) and Escape when done.
+auto-hide-fullscreen = Hide for Fullscreen Windows
+auto-hide-maximized = Also Hide for Maximized Windows
+schedules = Schedules
+schedules-description = Dim the widget or hide sections during a daily time range, e.g. notifications and media at night. Times are HH:MM; a range ending before it starts runs past midnight.
+schedule-label = Name
+schedule-invalid-time = Use HH:MM
+schedule-hides = Hides: { $sections }
+schedule-hides-nothing = Hides no sections (pick sections to toggle them)
+schedule-add = Add Schedule
+
+# Diagnostics
+diagnostics = Diagnostics
+diag-version = Version
+diag-widget-process = Widget process
+diag-widget-version = Widget version
+diag-no-status = The widget has not written a status file yet. Start the widget and refresh.
+diag-gpu-vendor = Detected GPU vendor
+diag-gpu-card = GPU card in use
+diag-gpu-method = GPU usage source
+diag-sensors = Temperature sensors
+diag-cpu-sensor = Sensor used for CPU temperature
+diag-gpu-sensor = Sensor used for GPU temperature
+diag-gpu-tools = GPU Tools
+diag-hwmon = Temperature Inputs (hwmon)
+diag-network-interfaces = Network Interfaces (counted in network rates)
+diag-weather = Last weather fetch
+diag-cider = Cider connection
+diag-monitor-health = Monitor Health
+diag-recent-errors = Recent Errors
+diag-no-errors = No errors recorded
+diag-refresh = Refresh
+diag-unknown = Unknown
+diag-process-running = Running (PID { $pid })
+diag-process-no-status = Running, but no status for { $seconds }s
+diag-process-starting = Running (no status written yet)
+diag-process-not-running = Not running
+diag-widget-version-latest = { $version } (latest release: { $latest })
+diag-none-found = None found
+diag-none-matched = None matched
+diag-tool-found = Found ({ $path })
+diag-tool-missing = Not found ({ $path })
+diag-unreadable = Unreadable
+diag-weather-none = No fetch yet
+diag-weather-retry = { $result } (next retry in { $seconds }s)
+diag-health-ok = OK (updated { $seconds }s ago)
+diag-health-starved = Waiting for CPU (no data for { $seconds }s)
+diag-health-stalled = Stalled (no data for { $seconds }s)
+diag-health-stopped = Stopped (thread exited)
+diag-health-crashed = Crashed (thread panicked)
+diag-health-restarted = { $state }, restarted { $count }×
+
+# First-run setup
+setup-title = Welcome to COSMIC Monitor
+setup-progress = Step { $step } of { $total }: { $name }
+setup-output-description = Which monitor should the widget appear on?
+setup-output-unknown = Other monitors are listed once the widget has run. With "Any output" the compositor picks one.
+setup-corner-description = Pick the screen corner the widget sits in. You can drag it to an exact spot later on the Position page.
+setup-sections-description = Choose what the widget shows. Everything can be changed later in the settings.
+setup-weather-description = Weather data comes from OpenWeatherMap and needs a free API key from openweathermap.org.
+setup-weather-provider = Provider
+setup-weather-disabled = Weather is turned off. Enable it on the previous step to set it up.
+setup-finish-description = Choose how the widget starts. Your settings are saved when you press Finish.
+setup-autostart-applet = Start the widget with the panel applet
+setup-autostart-entry = Start the widget on login without the panel applet
+setup-autostart-entry-description = Installs { $path }. When enabled, the panel applet no longer starts its own copy.
+setup-autostart-failed = Could not update the autostart entry: { $error }
+setup-back = Back
+setup-next = Next
+setup-finish = Finish
+setup-skip = Skip Setup
+setup-run-again = First-Run Setup
+setup-open = Run Again
+
+# Widget
+widget-loading = Loading…
+widget-not-available = N/A
+watchdog-crashed = { $monitor } monitor crashed, restarting
+watchdog-stopped = { $monitor } monitor stopped, restarting
+watchdog-stale = Data is stale: { $monitor } monitor not responding for { $seconds }s
+watchdog-starved = Data is stale: { $monitor } monitor waiting { $seconds }s for CPU time
+widget-cpu = CPU
+widget-ram = RAM
+widget-gpu = GPU
+widget-cpu-frequency-unavailable = No frequency data
+widget-smart-passed = PASSED
+widget-smart-failing = FAILING
+widget-smart-no-access = No access
+widget-gpu-fan = Fan { $percent }%
+widget-gpu-core-clock = Core { $mhz } MHz
+widget-gpu-memory-clock = Mem { $mhz } MHz
+widget-gpu-details-unavailable = No fan or clock data
+widget-psi-cpu = CPU
+widget-psi-memory = MEM
+widget-psi-io = IO
+widget-hardware-unknown-cpu = Unknown CPU
+widget-hardware-no-gpu = No GPU detected
+widget-utilization = Utilization
+widget-temperatures = Temperatures
+widget-hottest-core = Hottest: { $core } · { $temp }°C
+widget-hottest-core-unavailable = No per-core sensors
+widget-temp-graph-summary = { $label }  min { $min }° · avg { $avg }° · max { $max }°
+widget-throttling = THROTTLING
+widget-network = Network
+widget-disk-read = Disk Read
+widget-disk-write = Disk Write
+widget-storage = Storage
+widget-storage-free = { $free } free
+widget-storage-inodes = Inodes { $percent }%
+widget-storage-pools = Storage Pools
+widget-no-pools = No ZFS pools or btrfs filesystems
+widget-pool-capacity = { $percent }% of { $size }
+widget-pool-online = Online
+widget-pool-degraded = Degraded
+widget-pool-faulted = Faulted
+widget-pool-scrub-never = Never scrubbed
+widget-pool-scrub-running = Scrubbing { $percent }%
+widget-pool-scrubbing = Scrubbing
+widget-pool-scrubbed-on = Scrubbed { $date }, { $found }
+widget-pool-scrubbed = Scrubbed, { $found }
+widget-pool-scrub-clean = no errors
+widget-pool-scrub-errors = { $errors } errors
+widget-pool-scrub-canceled = Scrub canceled
+widget-vms = Virtual Machines
+widget-no-vms = No virtual machines running
+widget-vm-vcpus = { $count } vCPU
+storage-alert-title = { $name } is almost full
+storage-alert-space = Only { $free } left on { $mount }.
+storage-alert-inodes = { $percent }% of the inodes on { $mount } are in use.
+widget-battery = Battery
+widget-solaar-disabled = Solaar integration disabled
+widget-no-solaar-devices = No Solaar devices detected
+widget-disconnected = Disconnected
+widget-connecting = Connecting...
+widget-weather = Weather
+widget-weather-feels-like = Feels like { $temp }°C   H { $high }° / L { $low }°
+widget-weather-humidity-wind = Humidity { $humidity }%   Wind { $speed } m/s { $direction }
+widget-weather-stale = stale
+widget-weather-updated = updated { $age } ago
+age-under-minute = <1 min
+age-minutes = { $count } min
+age-hours = { $count } h
+age-days = { $count } d
+widget-polar-day = Sun up all day
+widget-polar-night = Sun down all day
+moon-new = New moon
+moon-waxing-crescent = Waxing crescent
+moon-first-quarter = First quarter
+moon-waxing-gibbous = Waxing gibbous
+moon-full = Full moon
+moon-waning-gibbous = Waning gibbous
+moon-third-quarter = Third quarter
+moon-waning-crescent = Waning crescent
+widget-aqi = AQI { $index } · { $level }
+aqi-good = Good
+aqi-moderate = Moderate
+aqi-unhealthy = Unhealthy
+widget-notifications = Notifications
+widget-clear-all = Clear All
+widget-no-notifications = No notifications
+widget-now-playing = Now Playing
+widget-no-media = No media playing
+widget-custom = Custom
+widget-no-commands = No commands configured
+widget-command-error = error
+widget-update-available = Update { $version } available
+widget-caffeine-on = Screen stays on
+widget-caffeine-off = Screen may sleep
+widget-audio-muted = Muted
+widget-audio-no-output = No audio output
+widget-health-failed-units = Failed units
+widget-health-updates = Updates
+widget-kernel-processes = Processes
+widget-kernel-threads = Threads
+widget-kernel-context-switches = Context Switches
+widget-kernel-per-second = { $count }/s
+widget-containers-running = Running containers
+widget-containers-busiest = Busiest: { $name }
+widget-containers-idle = Busiest: none
+widget-no-feeds = No feeds configured
+widget-feed-unread = { $count } unread
+widget-no-symbols = No symbols configured
+widget-tooltip-core = Core { $index }
+widget-tooltip-memory-used = Used: { $amount }
+widget-tooltip-memory-available = Available: { $amount }
+widget-tooltip-memory-total = Total: { $amount }
+widget-tooltip-gpu-method = Read via { $method }
+widget-tooltip-sensor = Sensor: { $name }
+widget-tooltip-no-sensor = No sensor matched
+widget-tooltip-no-interfaces = No active interfaces
+widget-tooltip-no-failed-units = All units running
+widget-tooltip-no-containers = No containers running
+widget-tooltip-package = Package { $index }: { $frequency }
+widget-tooltip-governor = Governor: { $name }
+widget-tooltip-governors = Available: { $names }
+widget-tooltip-governor-click = Click to switch to the next governor
+widget-tooltip-dnd-on = Do not disturb is on
+widget-tooltip-dnd-off = Do not disturb is off
+widget-tooltip-dnd-click-on = Click to silence notification popups
+widget-tooltip-dnd-click-off = Click to show notification popups again
+widget-tooltip-power-profile = Power profile: { $name }
+widget-tooltip-power-profile-click = Click to switch to { $name }
+widget-tooltip-power-profile-unavailable = power-profiles-daemon not running
+calendar-now = Now
+calendar-today = Today
+calendar-tomorrow = Tomorrow
+calendar-untitled = Untitled
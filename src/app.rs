@@ -8,23 +8,47 @@
 //! # Features
 //!
 //! - **Panel Icon**: Displays a system monitor icon (`utilities-system-monitor-symbolic`)
-//! - **Popup Menu**: Shows options to show/hide the widget and open settings
+//! - **Panel Readout**: CPU, RAM and CPU temperature next to the icon on
+//!   horizontal panels (`show_panel_readout`)
+//! - **Popup Menu**: Shows options to show/hide the widget and open settings,
+//!   above the current stats of the sections enabled for the widget
 //! - **Widget Management**: Spawns and kills the standalone widget process
 //! - **Auto-start**: Optionally launches the widget when the applet loads
 //!
 //! # Architecture
 //!
 //! The applet uses the `cosmic::Application` trait to integrate with the COSMIC
-//! desktop. It maintains minimal state - just tracking whether the widget is
-//! running and the current configuration.
+//! desktop. Its stats come from the widget's own monitors
+//! ([`LocalMonitors`]: CPU, memory, GPU, temperatures, network, storage),
+//! sampled at the configured update interval (at most once a second) while
+//! the readout or the popup is shown:
+//!
+//! ```text
+//! ┌────────────────────────────────────┐
+//! │ ▣ CPU 12% · RAM 45% · 52°C         │  panel
+//! └────────────────────────────────────┘
+//!   ┌──────────────────────────────────┐
+//!   │ Hide Widget                  [▣] │
+//!   │ Configure                    [⚙] │
+//!   │ CPU                          12% │
+//!   │ RAM    45% (7.1 GiB of 15.6 GiB) │
+//!   │ CPU Temperature             52°C │  popup
+//!   └──────────────────────────────────┘
+//! ```
 //!
 //! The actual monitoring widget runs as a separate process (`cosmic-monitor-widget`)
 //! to allow for layer-shell positioning and independent lifecycle management.
 
+use std::time::Duration;
+
 use crate::config::Config;
 use crate::fl;
+use crate::widget::clipboard;
+use crate::widget::control::{send_command, ControlCommand};
+use crate::widget::network::format_rate;
+use crate::widget::stats::{format_gib, LocalMonitors, StatsSnapshot};
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
-use cosmic::iced::{window::Id, Limits, Subscription};
+use cosmic::iced::{window::Id, Alignment, Limits, Subscription};
 use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 use cosmic::prelude::*;
 use cosmic::widget;
@@ -63,6 +87,17 @@ pub struct AppModel {
     /// Whether the widget process is currently running.
     /// Updated when opening the popup and after toggle operations.
     widget_running: bool,
+    
+    /// Monitors for the panel readout and the popup stats (created in
+    /// `init()`, their background threads run for the applet's lifetime).
+    monitors: Option<LocalMonitors>,
+    
+    /// Latest readings, `None` until the monitors were sampled twice
+    /// (CPU usage and network rates are deltas between samples).
+    stats: Option<StatsSnapshot>,
+    
+    /// Whether the monitors have been sampled at least once.
+    sampled: bool,
 }
 
 // ============================================================================
@@ -92,6 +127,12 @@ pub enum Message {
     
     /// User clicked "Configure" in the popup menu.
     OpenSettings,
+    
+    /// User clicked "Copy Stats" in the popup menu.
+    CopyStats,
+    
+    /// Sampling interval elapsed - refresh the monitors.
+    Sample,
 }
 
 // ============================================================================
@@ -106,7 +147,7 @@ impl AppModel {
     fn save_config(&self) {
         if let Some(ref config_handler) = self.config_handler {
             if let Err(err) = self.config.write_entry(config_handler) {
-                eprintln!("Failed to save config: {}", err);
+                log::error!("Failed to save config: {}", err);
             }
         }
     }
@@ -131,6 +172,81 @@ impl AppModel {
             false
         }
     }
+    
+    /// How often the monitors are sampled: the widget's update interval,
+    /// but at most once a second (the panel doesn't need more).
+    fn sample_interval(&self) -> Duration {
+        Duration::from_millis(self.config.update_interval_ms.max(1000))
+    }
+    
+    /// Whether anything showing stats is visible.
+    fn shows_stats(&self) -> bool {
+        (self.config.show_panel_readout && self.core.applet.is_horizontal()) || self.popup.is_some()
+    }
+}
+
+/// Short text next to the panel icon ("CPU 12% · RAM 45% · 52°C").
+fn panel_readout(stats: &StatsSnapshot) -> Option<String> {
+    let parts: Vec<String> = [
+        stats.cpu_usage.map(|usage| format!("{} {:.0}%", fl!("widget-cpu"), usage)),
+        stats.memory_usage.map(|usage| format!("{} {:.0}%", fl!("widget-ram"), usage)),
+        stats.cpu_temp.map(|temp| format!("{:.0}°C", temp)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+/// Label and value of each popup row, for the metrics enabled on the widget.
+fn stats_rows(config: &Config, stats: &StatsSnapshot) -> Vec<(String, String)> {
+    let percent = |value: f32| format!("{:.0}%", value);
+    let celsius = |value: f32| format!("{:.0}°C", value);
+    let mut rows = Vec::new();
+    
+    if let Some(usage) = stats.cpu_usage.filter(|_| config.show_cpu) {
+        rows.push((fl!("widget-cpu"), percent(usage)));
+    }
+    if let Some(usage) = stats.memory_usage.filter(|_| config.show_memory) {
+        let value = match (stats.memory_used, stats.memory_total) {
+            (Some(used), Some(total)) => fl!(
+                "applet-memory-used",
+                percent = percent(usage),
+                used = format_gib(used),
+                total = format_gib(total)
+            ),
+            _ => percent(usage),
+        };
+        rows.push((fl!("widget-ram"), value));
+    }
+    if let Some(usage) = stats.gpu_usage.filter(|_| config.show_gpu) {
+        let label = match stats.gpu_vendor {
+            Some(vendor) => format!("{} ({})", fl!("widget-gpu"), vendor),
+            None => fl!("widget-gpu"),
+        };
+        rows.push((label, percent(usage)));
+    }
+    if let Some(temp) = stats.cpu_temp.filter(|_| config.show_cpu_temp) {
+        rows.push((fl!("applet-cpu-temp"), celsius(temp)));
+    }
+    if let Some(temp) = stats.gpu_temp.filter(|_| config.show_gpu_temp) {
+        rows.push((fl!("applet-gpu-temp"), celsius(temp)));
+    }
+    if let Some(network) = stats.network.as_ref().filter(|_| config.show_network) {
+        let value = format!(
+            "↓ {}  ↑ {}",
+            format_rate(network.rx_bytes_per_sec, config.network_rate_bits),
+            format_rate(network.tx_bytes_per_sec, config.network_rate_bits)
+        );
+        rows.push((fl!("widget-network"), value));
+    }
+    if config.show_storage {
+        for disk in &stats.disks {
+            let value = fl!("applet-disk-used", percent = percent(disk.used_percentage), total = format_gib(disk.total_bytes));
+            rows.push((disk.name.clone(), value));
+        }
+    }
+    rows
 }
 
 // ============================================================================
@@ -207,6 +323,7 @@ impl cosmic::Application for AppModel {
             config_handler,
             interval_input,
             widget_running,
+            monitors: Some(LocalMonitors::new()),
             ..Default::default()
         };
 
@@ -223,21 +340,45 @@ impl cosmic::Application for AppModel {
 
     /// Render the panel icon.
     ///
-    /// This is what appears in the COSMIC panel. It's a simple icon button
-    /// that shows the system monitor icon and opens the popup when clicked.
+    /// This is what appears in the COSMIC panel: the system monitor icon,
+    /// followed by the CPU/RAM/temperature readout on horizontal panels.
+    /// Clicking it opens the popup.
     fn view(&self) -> Element<'_, Self::Message> {
-        self.core
-            .applet
-            .icon_button("utilities-system-monitor-symbolic")
+        let readout = self
+            .stats
+            .as_ref()
+            .filter(|_| self.config.show_panel_readout && self.core.applet.is_horizontal())
+            .and_then(panel_readout);
+        let Some(readout) = readout else {
+            return self
+                .core
+                .applet
+                .icon_button("utilities-system-monitor-symbolic")
+                .on_press(Message::TogglePopup)
+                .into();
+        };
+        
+        let (icon_size, _) = self.core.applet.suggested_size(true);
+        let content = widget::row()
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .push(widget::icon::from_name("utilities-system-monitor-symbolic").size(icon_size))
+            .push(self.core.applet.text(readout));
+        widget::button::custom(content)
+            .class(cosmic::theme::Button::AppletIcon)
+            .padding([0, 8])
             .on_press(Message::TogglePopup)
             .into()
     }
 
     /// Render the popup menu content.
     ///
-    /// Shows two options:
+    /// Shows three options:
     /// 1. "Show Widget" / "Hide Widget" - toggles the monitoring widget
     /// 2. "Configure" - opens the settings application
+    /// 3. "Copy Stats" - copies a plain-text stats summary to the clipboard
+    ///
+    /// Below them, the current stats of the sections enabled for the widget.
     ///
     /// The popup uses COSMIC's standard applet popup styling.
     fn view_window(&self, _id: Id) -> Element<'_, Self::Message> {
@@ -262,19 +403,49 @@ impl cosmic::Application for AppModel {
                 fl!("configure"),  // From i18n: "Configure"
                 widget::button::icon(widget::icon::from_name("preferences-system-symbolic"))
                     .on_press(Message::OpenSettings)
+            ))
+            // Copy stats button
+            .add(widget::settings::item(
+                fl!("copy-stats"),
+                widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                    .on_press(Message::CopyStats)
             ));
+        
+        // Current stats, mirroring the widget's sections
+        let stats_list = match &self.stats {
+            Some(stats) => stats_rows(&self.config, stats)
+                .into_iter()
+                .fold(widget::list_column().padding(5).spacing(0), |list, (label, value)| {
+                    list.add(widget::settings::item(label, widget::text::body(value)))
+                }),
+            None => widget::list_column()
+                .padding(5)
+                .add(widget::text::body(fl!("applet-collecting"))),
+        };
+        
+        let content = widget::column()
+            .spacing(8)
+            .push(content_list)
+            .push(stats_list);
 
-        self.core.applet.popup_container(content_list).into()
+        self.core.applet.popup_container(content).into()
     }
 
     /// Set up background tasks and event listeners.
     ///
-    /// Two subscriptions are active:
+    /// Up to three subscriptions are active:
     /// 1. A channel subscription (currently unused, placeholder for future features)
     /// 2. Configuration watcher that syncs changes from settings app
+    /// 3. The sampling timer, while the readout or the popup is shown
     fn subscription(&self) -> Subscription<Self::Message> {
         struct MySubscription;
 
+        let sampling = if self.shows_stats() {
+            cosmic::iced::time::every(self.sample_interval()).map(|_| Message::Sample)
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch(vec![
             // Placeholder subscription channel for future async features
             Subscription::run_with_id(
@@ -290,6 +461,7 @@ impl cosmic::Application for AppModel {
             self.core()
                 .watch_config::<Config>(Self::APP_ID)
                 .map(|update| Message::UpdateConfig(update.config)),
+            sampling,
         ])
     }
 
@@ -342,6 +514,27 @@ impl cosmic::Application for AppModel {
                 let _ = std::process::Command::new("cosmic-monitor-settings").spawn();
             }
             
+            Message::CopyStats => {
+                // The running widget also has weather, media and batteries;
+                // without it, copy what the popup shows
+                let sent = self.widget_running && send_command(ControlCommand::CopyStats).is_ok();
+                if !sent {
+                    let stats = self.stats.clone().unwrap_or_else(StatsSnapshot::capture);
+                    clipboard::copy_text(stats.to_plain());
+                }
+            }
+            
+            Message::Sample => {
+                if let Some(monitors) = self.monitors.as_mut() {
+                    monitors.update();
+                    // The first sample has nothing to compare CPU usage against
+                    if self.sampled {
+                        self.stats = Some(monitors.snapshot(StatsSnapshot::capture().timestamp_ms));
+                    }
+                    self.sampled = true;
+                }
+            }
+            
             Message::TogglePopup => {
                 return if let Some(p) = self.popup.take() {
                     // Popup is open - close it
@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shareable Layout Bundles
+//!
+//! A bundle captures how the widget *looks* (display style and section
+//! layout) in a single JSON file, so complete setups can be shared and
+//! applied in one step from the settings app.
+//!
+//! # Format
+//!
+//! ```json
+//! {
+//!   "format": "cosmic-monitor-bundle",
+//!   "version": 1,
+//!   "name": "Minimal gaming overlay",
+//!   "theme": { "use_circular_temp_display": true, "show_percentages": false, ... },
+//!   "layout": { "section_order": ["Temperatures", "Utilization", ...], "show_cpu": true, ... }
+//! }
+//! ```
+//!
+//! - **theme**: Display style (gauges vs text, percentages, clock format,
+//!   detailed weather, animations). Colors follow the COSMIC desktop theme.
+//! - **layout**: Section order, layout mode and which sections/rows are shown
+//!
+//! Machine-specific and private settings (position, API keys, tokens,
+//! logging, history) are never exported and are left untouched on import.
+//! Neither are custom commands or plugin scripts: importing a bundle must
+//! never add code that the widget would run.
+//!
+//! # Compatibility
+//!
+//! Missing fields fall back to the defaults and unknown fields are ignored,
+//! so bundles from older and newer versions both import. Bundles with a
+//! higher `version` than [`FORMAT_VERSION`] are rejected, since their fields
+//! may have changed meaning.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AmbientSource, AmbientTarget, BarColors, Config, FontSettings, LayoutMode, MemoryLabel, MetricThresholds, SectionCard, TempGraphMode, TextContrast, WidgetSection};
+
+/// Value of the `format` field identifying a bundle file.
+pub const FORMAT: &str = "cosmic-monitor-bundle";
+
+/// Current bundle format version.
+pub const FORMAT_VERSION: u32 = 1;
+
+// ============================================================================
+// Bundle Types
+// ============================================================================
+
+/// Display style settings carried by a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BundleTheme {
+    /// Circular temperature gauges instead of text
+    pub use_circular_temp_display: bool,
+    /// CPU/GPU temperatures as badges on the usage bars
+    pub temps_on_usage_bars: bool,
+    /// Percentages next to utilization bars
+    pub show_percentages: bool,
+    /// Value next to the memory bar
+    pub memory_label: MemoryLabel,
+    /// Network rates in bits per second
+    pub network_rate_bits: bool,
+    /// Where bars and gauges turn yellow and red
+    pub thresholds: MetricThresholds,
+    /// Bar and gauge colors per level
+    pub bar_colors: BarColors,
+    /// Light or dark text, or following dark mode
+    pub text_contrast: TextContrast,
+    /// Sections drawn on a card, with the card's opacity
+    pub section_cards: Vec<SectionCard>,
+    /// Font family and size per text role
+    pub fonts: FontSettings,
+    /// What the ambient status tint follows
+    pub ambient_source: AmbientSource,
+    /// Whether the ambient tint colors the clock outline or the background
+    pub ambient_target: AmbientTarget,
+    /// 24-hour clock
+    pub use_24hour_time: bool,
+    /// strftime pattern of the clock (empty: built-in)
+    pub clock_format: String,
+    /// strftime pattern of the date (empty: built-in)
+    pub date_format: String,
+    /// Detailed weather layout
+    pub weather_detailed: bool,
+    /// Sunrise/sunset row under the weather
+    pub weather_sun_times: bool,
+    /// Moon phase in the sunrise/sunset row at night
+    pub weather_moon_phase: bool,
+    /// Air quality badge next to the weather
+    pub show_air_quality: bool,
+    /// Eased transitions and fades
+    pub enable_animations: bool,
+}
+
+/// Section layout carried by a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BundleLayout {
+    /// Sections from top to bottom
+    pub section_order: Vec<WidgetSection>,
+    /// Vertical, horizontal or compact arrangement
+    pub layout_mode: LayoutMode,
+    /// Columns of the vertical arrangement
+    pub columns: u32,
+    // Visibility of sections and rows (same meaning as in `Config`)
+    pub show_hostname: bool,
+    pub show_username: bool,
+    pub show_clock: bool,
+    pub show_date: bool,
+    pub show_cpu: bool,
+    pub show_cpu_frequency: bool,
+    pub show_cpu_governor: bool,
+    pub show_hardware_info: bool,
+    pub show_memory: bool,
+    pub show_gpu: bool,
+    pub show_gpu_processes: bool,
+    pub show_gpu_details: bool,
+    pub show_psi: bool,
+    pub show_network: bool,
+    pub show_network_graph: bool,
+    pub show_disk: bool,
+    pub show_cpu_temp: bool,
+    pub show_gpu_temp: bool,
+    pub show_hottest_core: bool,
+    pub show_throttling: bool,
+    pub show_temp_graph: bool,
+    pub temp_graph_mode: TempGraphMode,
+    pub show_storage: bool,
+    pub show_storage_pools: bool,
+    pub show_battery: bool,
+    pub show_weather: bool,
+    pub show_notifications: bool,
+    pub max_notifications: usize,
+    pub show_media: bool,
+    pub show_custom_commands: bool,
+    pub show_plugins: bool,
+    pub show_audio: bool,
+    pub show_system_health: bool,
+    pub show_containers: bool,
+    pub show_vms: bool,
+    pub show_kernel_stats: bool,
+    pub show_feeds: bool,
+    pub show_ticker: bool,
+    pub show_caffeine: bool,
+}
+
+/// A complete shareable widget setup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Always [`FORMAT`]
+    pub format: String,
+    /// Format version the bundle was written with
+    pub version: u32,
+    /// Display name chosen by the author
+    #[serde(default)]
+    pub name: String,
+    /// Display style
+    #[serde(default)]
+    pub theme: BundleTheme,
+    /// Section layout
+    #[serde(default)]
+    pub layout: BundleLayout,
+}
+
+impl Default for BundleTheme {
+    fn default() -> Self {
+        Bundle::from_config(&Config::default(), "").theme
+    }
+}
+
+impl Default for BundleLayout {
+    fn default() -> Self {
+        Bundle::from_config(&Config::default(), "").layout
+    }
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+impl Bundle {
+    /// Capture the shareable part of a configuration.
+    pub fn from_config(config: &Config, name: &str) -> Self {
+        Self {
+            format: FORMAT.to_string(),
+            version: FORMAT_VERSION,
+            name: name.to_string(),
+            theme: BundleTheme {
+                use_circular_temp_display: config.use_circular_temp_display,
+                temps_on_usage_bars: config.temps_on_usage_bars,
+                show_percentages: config.show_percentages,
+                memory_label: config.memory_label,
+                network_rate_bits: config.network_rate_bits,
+                thresholds: config.thresholds,
+                bar_colors: config.bar_colors.clone(),
+                text_contrast: config.text_contrast,
+                section_cards: config.section_cards.clone(),
+                fonts: config.fonts.clone(),
+                ambient_source: config.ambient_source,
+                ambient_target: config.ambient_target,
+                use_24hour_time: config.use_24hour_time,
+                clock_format: config.clock_format.clone(),
+                date_format: config.date_format.clone(),
+                weather_detailed: config.weather_detailed,
+                weather_sun_times: config.weather_sun_times,
+                weather_moon_phase: config.weather_moon_phase,
+                show_air_quality: config.show_air_quality,
+                enable_animations: config.enable_animations,
+            },
+            layout: BundleLayout {
+                section_order: config.section_order.clone(),
+                layout_mode: config.layout_mode,
+                columns: config.columns,
+                show_hostname: config.show_hostname,
+                show_username: config.show_username,
+                show_clock: config.show_clock,
+                show_date: config.show_date,
+                show_cpu: config.show_cpu,
+                show_cpu_frequency: config.show_cpu_frequency,
+                show_cpu_governor: config.show_cpu_governor,
+                show_hardware_info: config.show_hardware_info,
+                show_memory: config.show_memory,
+                show_gpu: config.show_gpu,
+                show_gpu_processes: config.show_gpu_processes,
+                show_gpu_details: config.show_gpu_details,
+                show_psi: config.show_psi,
+                show_network: config.show_network,
+                show_network_graph: config.show_network_graph,
+                show_disk: config.show_disk,
+                show_cpu_temp: config.show_cpu_temp,
+                show_gpu_temp: config.show_gpu_temp,
+                show_hottest_core: config.show_hottest_core,
+                show_throttling: config.show_throttling,
+                show_temp_graph: config.show_temp_graph,
+                temp_graph_mode: config.temp_graph_mode,
+                show_storage: config.show_storage,
+                show_storage_pools: config.show_storage_pools,
+                show_battery: config.show_battery,
+                show_weather: config.show_weather,
+                show_notifications: config.show_notifications,
+                max_notifications: config.max_notifications,
+                show_media: config.show_media,
+                show_custom_commands: config.show_custom_commands,
+                show_plugins: config.show_plugins,
+                show_audio: config.show_audio,
+                show_system_health: config.show_system_health,
+                show_containers: config.show_containers,
+                show_vms: config.show_vms,
+                show_kernel_stats: config.show_kernel_stats,
+                show_feeds: config.show_feeds,
+                show_ticker: config.show_ticker,
+                show_caffeine: config.show_caffeine,
+            },
+        }
+    }
+
+    /// Apply the bundle on top of a configuration.
+    ///
+    /// Sections missing from the bundle's order (e.g., added in a newer
+    /// version of the widget) keep their relative order at the end;
+    /// duplicates are dropped.
+    pub fn apply(&self, config: &mut Config) {
+        let theme = &self.theme;
+        config.use_circular_temp_display = theme.use_circular_temp_display;
+        config.temps_on_usage_bars = theme.temps_on_usage_bars;
+        config.show_percentages = theme.show_percentages;
+        config.memory_label = theme.memory_label;
+        config.network_rate_bits = theme.network_rate_bits;
+        config.thresholds = theme.thresholds;
+        config.bar_colors = theme.bar_colors.clone();
+        config.text_contrast = theme.text_contrast;
+        config.section_cards = theme.section_cards.clone();
+        config.fonts = theme.fonts.clone();
+        config.ambient_source = theme.ambient_source;
+        config.ambient_target = theme.ambient_target;
+        config.use_24hour_time = theme.use_24hour_time;
+        config.clock_format = theme.clock_format.clone();
+        config.date_format = theme.date_format.clone();
+        config.weather_detailed = theme.weather_detailed;
+        config.weather_sun_times = theme.weather_sun_times;
+        config.weather_moon_phase = theme.weather_moon_phase;
+        config.show_air_quality = theme.show_air_quality;
+        config.enable_animations = theme.enable_animations;
+
+        let layout = &self.layout;
+        let mut order: Vec<WidgetSection> = Vec::new();
+        for section in layout.section_order.iter().chain(config.section_order.iter()) {
+            if !order.contains(section) {
+                order.push(*section);
+            }
+        }
+        config.section_order = order;
+        config.layout_mode = layout.layout_mode;
+        config.columns = layout.columns.clamp(1, 2);
+        config.show_hostname = layout.show_hostname;
+        config.show_username = layout.show_username;
+        config.show_clock = layout.show_clock;
+        config.show_date = layout.show_date;
+        config.show_cpu = layout.show_cpu;
+        config.show_cpu_frequency = layout.show_cpu_frequency;
+        config.show_cpu_governor = layout.show_cpu_governor;
+        config.show_hardware_info = layout.show_hardware_info;
+        config.show_memory = layout.show_memory;
+        config.show_gpu = layout.show_gpu;
+        config.show_gpu_processes = layout.show_gpu_processes;
+        config.show_gpu_details = layout.show_gpu_details;
+        config.show_psi = layout.show_psi;
+        config.show_network = layout.show_network;
+        config.show_network_graph = layout.show_network_graph;
+        config.show_disk = layout.show_disk;
+        config.show_cpu_temp = layout.show_cpu_temp;
+        config.show_gpu_temp = layout.show_gpu_temp;
+        config.show_hottest_core = layout.show_hottest_core;
+        config.show_throttling = layout.show_throttling;
+        config.show_temp_graph = layout.show_temp_graph;
+        config.temp_graph_mode = layout.temp_graph_mode;
+        config.show_storage = layout.show_storage;
+        config.show_storage_pools = layout.show_storage_pools;
+        config.show_battery = layout.show_battery;
+        config.show_weather = layout.show_weather;
+        config.show_notifications = layout.show_notifications;
+        config.max_notifications = layout.max_notifications.clamp(1, 20);
+        config.show_media = layout.show_media;
+        config.show_custom_commands = layout.show_custom_commands;
+        config.show_plugins = layout.show_plugins;
+        config.show_audio = layout.show_audio;
+        config.show_system_health = layout.show_system_health;
+        config.show_containers = layout.show_containers;
+        config.show_vms = layout.show_vms;
+        config.show_kernel_stats = layout.show_kernel_stats;
+        config.show_feeds = layout.show_feeds;
+        config.show_ticker = layout.show_ticker;
+        config.show_caffeine = layout.show_caffeine;
+    }
+
+    // ========================================================================
+    // File I/O
+    // ========================================================================
+
+    /// Default location offered for exporting and importing.
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("cosmic-monitor-layout.json")
+    }
+
+    /// Read and validate a bundle file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the read error, or `InvalidData` if the file isn't a bundle
+    /// or was written by a newer format version.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let bundle: Self = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("not a valid bundle: {}", e)))?;
+        if bundle.format != FORMAT {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a COSMIC Monitor bundle"));
+        }
+        if bundle.version > FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("bundle version {} is newer than supported ({})", bundle.version, FORMAT_VERSION),
+            ));
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors from writing the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_keeps_private_settings_and_completes_order() {
+        let mut shared = Config::default();
+        shared.show_percentages = false;
+        shared.section_order = vec![WidgetSection::Media, WidgetSection::Media, WidgetSection::Weather];
+        let bundle = Bundle::from_config(&shared, "test");
+
+        let mut config = Config::default();
+        config.weather_api_key = String::from("secret");
+        config.widget_x = 123;
+        bundle.apply(&mut config);
+
+        assert!(!config.show_percentages);
+        assert_eq!(config.weather_api_key, "secret");
+        assert_eq!(config.widget_x, 123);
+        assert_eq!(&config.section_order[..2], &[WidgetSection::Media, WidgetSection::Weather]);
+        assert_eq!(config.section_order.len(), Config::default().section_order.len());
+    }
+
+    #[test]
+    fn test_unknown_and_missing_fields() {
+        let json = r#"{"format":"cosmic-monitor-bundle","version":1,"theme":{"show_percentages":false},"thresholds":{}}"#;
+        let bundle: Bundle = serde_json::from_str(json).unwrap();
+        assert!(!bundle.theme.show_percentages);
+        assert_eq!(bundle.layout, BundleLayout::default());
+    }
+}
@@ -5,6 +5,7 @@
 //! This is the main entry point for the **panel applet** component of COSMIC Monitor.
 //! The applet runs inside the COSMIC panel and provides:
 //! - A clickable tray icon that spawns the standalone widget
+//! - A compact CPU/RAM/temperature readout and a popup with current stats
 //! - Quick access to the settings application
 //!
 //! # Component Overview
@@ -21,35 +22,38 @@
 //! - Proper styling that matches the system theme
 //! - Popup window support for menus
 //!
-//! The actual system monitoring happens in the widget process, not here.
-//! This separation allows the widget to use Wayland layer-shell for positioning
-//! while the applet stays integrated with the panel.
+//! The widget runs in its own process, so it can use Wayland layer-shell for
+//! positioning while the applet stays integrated with the panel. For its
+//! panel readout and popup stats the applet samples the same monitors
+//! (`widget::stats::LocalMonitors`) itself.
 
 mod app;
-mod config;
-mod i18n;
+
+use cosmic_monitor_core::{config, fl, i18n, logging, widget};
+
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
 
 /// Panel applet entry point.
 ///
 /// Initializes logging and internationalization, then starts the iced event loop
 /// for the panel applet. The applet itself is defined in `app.rs`.
 fn main() -> cosmic::iced::Result {
-    // Initialize logger to write to /tmp/cosmic-monitor.log
-    // This log file is shared with the widget process for unified debugging.
-    // Note: Logging is always enabled for the applet (it's lightweight).
-    use std::fs::OpenOptions;
-    
-    let log_file = OpenOptions::new()
-        .create(true)      // Create file if it doesn't exist
-        .append(true)      // Append to existing content (don't truncate)
-        .open("/tmp/cosmic-monitor.log")
-        .expect("Failed to open log file");
-    
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
-        .target(env_logger::Target::Pipe(Box::new(log_file)))
-        .init();
+    // Initialize the logger with the level and file output from the shared config.
+    // `--verbose` raises the level to Debug (see logging.rs).
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let verbose = logging::take_verbose_flag(&mut args);
+    let migrated = config::migrate_stored();
+    let config = cosmic_config::Config::new("com.github.zoliviragh.CosmicMonitor", config::Config::VERSION)
+        .map(|handler| config::Config::get_entry(&handler).unwrap_or_else(|(_errors, config)| config))
+        .unwrap_or_default();
+    logging::init(
+        "cosmic-monitor-applet",
+        logging::level_filter(config.log_level, verbose),
+        config.enable_logging,
+    );
     
     log::info!("Starting COSMIC Monitor Applet");
+    config::log_migration(&migrated);
     
     // Initialize internationalization (i18n) support.
     // Uses the system's preferred language list to select the appropriate
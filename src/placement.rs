@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Position Picker
+//!
+//! Draws the connected outputs, as last seen by the widget (see
+//! [`crate::widget::cache::CachedOutput`]), scaled down into a small mockup
+//! with a rectangle standing in for the widget. Dragging that rectangle
+//! picks everything the widget needs to be placed:
+//!
+//! - the output under the rectangle (`widget_output`),
+//! - the nearest corner of that output (`widget_anchor`),
+//! - the offsets from that corner (`widget_x`, `widget_y`).
+//!
+//! ```text
+//!  ┌──────── DP-1 ────────┐┌──── HDMI-A-1 ────┐
+//!  │ ┌──┐                 ││                  │
+//!  │ └──┘ ◄ widget proxy  ││                  │
+//!  └──────────────────────┘└──────────────────┘
+//! ```
+//!
+//! Anchoring to the nearest corner keeps a widget dragged to the bottom
+//! right in the bottom right when the output's resolution changes.
+
+use cosmic::widget::image;
+
+use crate::config::{Config, WidgetAnchor};
+use crate::preview::argb_to_rgba;
+use crate::widget::cache::CachedOutput;
+use crate::widget::theme::CosmicTheme;
+
+/// Width of the mockup in pixels.
+pub const MOCKUP_WIDTH: u32 = 480;
+
+/// Height of the mockup in pixels.
+pub const MOCKUP_HEIGHT: u32 = 240;
+
+/// Space around the outputs in the mockup.
+const MOCKUP_PADDING: f64 = 8.0;
+
+/// Stand-in screen until the widget has reported the real outputs.
+const FALLBACK_OUTPUT: (i32, i32) = (1920, 1080);
+
+/// Where the widget goes, as picked in the mockup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    /// Output name (empty for the stand-in screen)
+    pub output: String,
+    /// Corner the offsets are measured from
+    pub anchor: WidgetAnchor,
+    /// Horizontal offset from the anchored edge
+    pub x: i32,
+    /// Vertical offset from the anchored edge
+    pub y: i32,
+}
+
+/// Output layout scaled to fit the mockup.
+pub struct Mockup {
+    /// Outputs in the compositor's global space
+    outputs: Vec<CachedOutput>,
+    /// Mockup pixels per logical pixel
+    scale: f64,
+    /// Mockup position of the global origin
+    origin: (f64, f64),
+}
+
+impl Mockup {
+    /// Fit `outputs` into the mockup, centered.
+    ///
+    /// Without outputs a single 1920x1080 screen is shown.
+    pub fn new(outputs: &[CachedOutput]) -> Self {
+        let outputs = if outputs.is_empty() {
+            vec![CachedOutput {
+                width: FALLBACK_OUTPUT.0,
+                height: FALLBACK_OUTPUT.1,
+                ..Default::default()
+            }]
+        } else {
+            outputs.to_vec()
+        };
+
+        let left = outputs.iter().map(|o| o.x).min().unwrap_or(0) as f64;
+        let top = outputs.iter().map(|o| o.y).min().unwrap_or(0) as f64;
+        let right = outputs.iter().map(|o| o.x + o.width).max().unwrap_or(1) as f64;
+        let bottom = outputs.iter().map(|o| o.y + o.height).max().unwrap_or(1) as f64;
+        let (width, height) = ((right - left).max(1.0), (bottom - top).max(1.0));
+
+        let scale = ((MOCKUP_WIDTH as f64 - 2.0 * MOCKUP_PADDING) / width)
+            .min((MOCKUP_HEIGHT as f64 - 2.0 * MOCKUP_PADDING) / height);
+        let origin = (
+            (MOCKUP_WIDTH as f64 - width * scale) / 2.0 - left * scale,
+            (MOCKUP_HEIGHT as f64 - height * scale) / 2.0 - top * scale,
+        );
+
+        Self { outputs, scale, origin }
+    }
+
+    /// Output the widget is shown on: the named one, or the first if it
+    /// isn't connected (or none is configured).
+    fn target_output(&self, name: &str) -> &CachedOutput {
+        self.outputs
+            .iter()
+            .find(|output| !name.is_empty() && output.name == name)
+            .unwrap_or(&self.outputs[0])
+    }
+
+    /// Widget rectangle (x, y, width, height) in mockup pixels.
+    pub fn proxy_rect(&self, config: &Config, size: (u32, u32)) -> (f64, f64, f64, f64) {
+        let output = self.target_output(&config.widget_output);
+        let (width, height) = (size.0 as i32, size.1 as i32);
+        let x = if config.widget_anchor.is_right() {
+            output.x + output.width - config.widget_x - width
+        } else {
+            output.x + config.widget_x
+        };
+        let y = if config.widget_anchor.is_bottom() {
+            output.y + output.height - config.widget_y - height
+        } else {
+            output.y + config.widget_y
+        };
+        (
+            self.origin.0 + x as f64 * self.scale,
+            self.origin.1 + y as f64 * self.scale,
+            width as f64 * self.scale,
+            height as f64 * self.scale,
+        )
+    }
+
+    /// Placement for the proxy with its top-left corner at `point` (mockup pixels).
+    ///
+    /// The proxy goes to the output under its center (or the closest one)
+    /// and is kept inside it.
+    pub fn place(&self, point: (f64, f64), size: (u32, u32)) -> Placement {
+        let (width, height) = (size.0 as f64, size.1 as f64);
+        let x = (point.0 - self.origin.0) / self.scale;
+        let y = (point.1 - self.origin.1) / self.scale;
+        let center = (x + width / 2.0, y + height / 2.0);
+
+        let output = self
+            .outputs
+            .iter()
+            .min_by(|a, b| distance(a, center).total_cmp(&distance(b, center)))
+            .unwrap_or(&self.outputs[0]);
+        let (left, top) = (output.x as f64, output.y as f64);
+        let (right, bottom) = (left + output.width as f64, top + output.height as f64);
+
+        // Keep the widget on the output (top-left wins if it's larger)
+        let x = x.min(right - width).max(left);
+        let y = y.min(bottom - height).max(top);
+
+        let anchor_right = x + width / 2.0 > (left + right) / 2.0;
+        let anchor_bottom = y + height / 2.0 > (top + bottom) / 2.0;
+        Placement {
+            output: output.name.clone(),
+            anchor: WidgetAnchor::from_sides(anchor_right, anchor_bottom),
+            x: if anchor_right { right - (x + width) } else { x - left }.round() as i32,
+            y: if anchor_bottom { bottom - (y + height) } else { y - top }.round() as i32,
+        }
+    }
+
+    /// Draw the outputs and the widget proxy.
+    ///
+    /// Returns `None` if Cairo fails.
+    pub fn render(&self, config: &Config, size: (u32, u32), theme: &CosmicTheme) -> Option<image::Handle> {
+        let mut surface =
+            cairo::ImageSurface::create(cairo::Format::ARgb32, MOCKUP_WIDTH as i32, MOCKUP_HEIGHT as i32).ok()?;
+        {
+            let cr = cairo::Context::new(&surface).ok()?;
+            let layout = pangocairo::functions::create_layout(&cr);
+            layout.set_font_description(Some(&pango::FontDescription::from_string("Ubuntu 8")));
+            let target = self.target_output(&config.widget_output);
+
+            for output in &self.outputs {
+                let x = self.origin.0 + output.x as f64 * self.scale;
+                let y = self.origin.1 + output.y as f64 * self.scale;
+                let (width, height) = (output.width as f64 * self.scale, output.height as f64 * self.scale);
+
+                cr.rectangle(x + 1.0, y + 1.0, width - 2.0, height - 2.0);
+                cr.set_source_rgb(0.2, 0.2, 0.22);
+                cr.fill_preserve().ok()?;
+                if std::ptr::eq(output, target) {
+                    cr.set_source_rgb(theme.accent.red, theme.accent.green, theme.accent.blue);
+                    cr.set_line_width(2.0);
+                } else {
+                    cr.set_source_rgb(0.5, 0.5, 0.5);
+                    cr.set_line_width(1.0);
+                }
+                cr.stroke().ok()?;
+
+                let label = if output.name.is_empty() { "Screen" } else { output.name.as_str() };
+                layout.set_text(label);
+                cr.move_to(x + 6.0, y + 4.0);
+                cr.set_source_rgb(0.85, 0.85, 0.85);
+                pangocairo::functions::show_layout(&cr, &layout);
+            }
+
+            let (x, y, width, height) = self.proxy_rect(config, size);
+            cr.rectangle(x, y, width.max(4.0), height.max(4.0));
+            cr.set_source_rgba(theme.accent.red, theme.accent.green, theme.accent.blue, 0.6);
+            cr.fill_preserve().ok()?;
+            cr.set_source_rgb(theme.accent.red, theme.accent.green, theme.accent.blue);
+            cr.set_line_width(1.5);
+            cr.stroke().ok()?;
+        }
+
+        surface.flush();
+        let stride = surface.stride() as usize;
+        let data = surface.data().ok()?;
+        let mut pixels = Vec::with_capacity(MOCKUP_WIDTH as usize * MOCKUP_HEIGHT as usize * 4);
+        for row in data.chunks(stride).take(MOCKUP_HEIGHT as usize) {
+            pixels.extend_from_slice(&row[..MOCKUP_WIDTH as usize * 4]);
+        }
+        argb_to_rgba(&mut pixels);
+        Some(image::Handle::from_rgba(MOCKUP_WIDTH, MOCKUP_HEIGHT, pixels))
+    }
+}
+
+/// Distance from a point to an output (0 inside it).
+fn distance(output: &CachedOutput, point: (f64, f64)) -> f64 {
+    let dx = (output.x as f64 - point.0).max(point.0 - (output.x + output.width) as f64).max(0.0);
+    let dy = (output.y as f64 - point.1).max(point.1 - (output.y + output.height) as f64).max(0.0);
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(name: &str, x: i32, width: i32) -> CachedOutput {
+        CachedOutput {
+            name: name.to_string(),
+            x,
+            width,
+            height: 1080,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_place_round_trip() {
+        let mockup = Mockup::new(&[output("DP-1", 0, 1920), output("HDMI-A-1", 1920, 1920)]);
+        let size = (370, 300);
+
+        // Near the bottom-right corner of the second output
+        let config = Config {
+            widget_output: String::from("HDMI-A-1"),
+            widget_anchor: WidgetAnchor::BottomRight,
+            widget_x: 40,
+            widget_y: 60,
+            ..Default::default()
+        };
+        let (x, y, _, _) = mockup.proxy_rect(&config, size);
+
+        let placement = mockup.place((x, y), size);
+        assert_eq!(placement.output, "HDMI-A-1");
+        assert_eq!(placement.anchor, WidgetAnchor::BottomRight);
+        assert!((placement.x - 40).abs() <= 2 && (placement.y - 60).abs() <= 2);
+    }
+
+    #[test]
+    fn test_place_clamps_to_output() {
+        let mockup = Mockup::new(&[output("DP-1", 0, 1920)]);
+        let placement = mockup.place((-50.0, -50.0), (370, 300));
+        assert_eq!(placement.anchor, WidgetAnchor::TopLeft);
+        assert_eq!((placement.x, placement.y), (0, 0));
+    }
+}
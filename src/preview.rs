@@ -0,0 +1,601 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Live Widget Preview
+//!
+//! Renders a mock of the layer-shell widget inside the settings window so
+//! toggles, colors and layout changes are visible right away, without
+//! waiting for the widget's next config poll (or having it on screen).
+//!
+//! The preview uses the widget's own renderer ([`crate::widget::renderer`])
+//! and layout code, fed with fixed sample data instead of live monitors:
+//!
+//! ```text
+//! Config ──► sample data ──► render_widget() ──► ARGB32 canvas
+//!                                                   │ unpremultiply, reorder
+//!                                                   ▼
+//!                                          image::Handle ──► settings pane
+//! ```
+//!
+//! Custom commands are shown with their labels but never run, and plugins
+//! are not loaded, so the preview stays cheap to redraw on every change.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use cosmic::widget::image;
+
+use crate::config::{time_locale, Config, FeedKind};
+use crate::widget::air_quality::{AirQuality, AqiLevel};
+use crate::widget::audio::AudioState;
+use crate::widget::battery::BatteryDevice;
+use crate::widget::fonts::Fonts;
+use crate::widget::calendar::{event_rows, CalendarEvent};
+use crate::widget::cpufreq::CpuFrequency;
+use crate::widget::custom::{active_commands, CommandOutput};
+use crate::widget::feeds::{feed_settings, FeedRow, FeedValue};
+use crate::widget::gpu_card::GpuDetails;
+use crate::widget::smart::{DriveHealth, SmartVerdict};
+use crate::widget::pools::{PoolHealth, PoolKind, PoolStatus, ScrubStatus};
+use crate::widget::gpu_processes::GpuProcess;
+use crate::widget::hardware::HardwareInfo;
+use crate::widget::identity::Identity;
+use crate::widget::health::HealthReport;
+use crate::widget::containers::{BusiestContainer, ContainerReport};
+use crate::widget::psi::Pressure;
+use crate::widget::kernel_stats::KernelStats;
+use crate::widget::vms::VmInfo;
+use crate::widget::snap::SnapGuides;
+use crate::widget::history::History;
+use crate::widget::layout::{calculate_widget_size, compact_slots, section_columns, ContentCounts};
+use crate::widget::marquee::Marquee;
+use crate::widget::media::{MediaInfo, PlaybackStatus};
+use crate::widget::notifications::{Notification, Urgency};
+use crate::widget::renderer::{render_widget, FrameData};
+use crate::widget::storage::DiskInfo;
+use crate::widget::temperature::CoreReading;
+use crate::widget::theme::CosmicTheme;
+use crate::widget::ticker::{ticker_settings, Quote, TickerRow};
+use crate::widget::weather::SunTimes;
+use crate::widget::world_clock::world_clock_rows;
+
+/// Size of the preview relative to the real widget.
+pub const PREVIEW_SCALE: f32 = 0.5;
+
+/// Bytes in a gibibyte, for the sample disks.
+const GIB: u64 = 1024 * 1024 * 1024;
+
+/// A rendered preview frame.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    /// Image of the widget, ready for `widget::image`
+    pub handle: image::Handle,
+    /// Width of the widget in pixels (before scaling)
+    pub width: u32,
+    /// Height of the widget in pixels (before scaling)
+    pub height: u32,
+}
+
+/// Render the widget as it would look with `config`.
+///
+/// Returns `None` if rendering panicked (the error is logged).
+pub fn render(config: &Config, theme: &CosmicTheme) -> Option<Preview> {
+    let disks = sample_disks();
+    let drive_health = sample_drive_health(config);
+    let storage_pools = sample_pools(config);
+    let vms = sample_vms(config);
+    let batteries = sample_batteries();
+    let notifications = sample_notifications();
+    let media = sample_media();
+    let audio = sample_audio();
+    let cpu_frequency = sample_cpu_frequency();
+    let hardware_lines = sample_hardware().lines();
+    let identity = sample_identity().text(config);
+    let health = sample_health();
+    let containers = sample_containers();
+    let feed_rows = sample_feeds(config);
+    let ticker_rows = sample_ticker(config);
+    let gpu_processes = sample_gpu_processes(config);
+    let gpu_details = sample_gpu_details();
+    let (network_rx_history, network_tx_history) = sample_network_history(config.network_graph_seconds);
+    let (cpu_temp_history, gpu_temp_history) = sample_temp_history(config.temp_graph_minutes.saturating_mul(60));
+    let custom_outputs: Vec<CommandOutput> = active_commands(config)
+        .into_iter()
+        .map(|command| CommandOutput {
+            label: command.label,
+            text: String::new(),
+            error: None,
+        })
+        .collect();
+
+    let counts = sample_counts(config);
+    let (width, height) = calculate_widget_size(config, counts);
+    let section_columns = section_columns(config, counts);
+    let compact_slots = compact_slots(config);
+    let location = if config.weather_location.is_empty() {
+        "Sample City"
+    } else {
+        config.weather_location.as_str()
+    };
+    let collapsed_groups = HashSet::new();
+    // Compact notifications show their bodies only when hovered
+    let notification_expansion: HashMap<String, f64> = notifications
+        .iter()
+        .flat_map(|(app_name, group)| group.iter().map(move |n| format!("{}:{}", app_name, n.timestamp)))
+        .map(|key| (key, if config.compact_notifications { 0.0 } else { 1.0 }))
+        .collect();
+    let now = chrono::Local::now();
+    let locale = time_locale();
+    let world_clocks = world_clock_rows(&config.world_clocks, &now, config.use_24hour_time);
+    let fonts = Fonts::resolve(&config.fonts);
+    let calendar_events = if config.show_calendar {
+        event_rows(&sample_events(now), now, config.use_24hour_time, locale, config.calendar_max_events as usize)
+    } else {
+        Vec::new()
+    };
+
+    let hottest_core = CoreReading {
+        label: String::from("Core 3"),
+        temp: 71.0,
+    };
+
+    let data = FrameData {
+        width: width as i32,
+        height: height as i32,
+        cpu_usage: 42.0,
+        memory_usage: 63.0,
+        memory_used: 10_820_000_000,
+        memory_total: 17_179_869_184,
+        gpu_usage: 27.0,
+        cpu_temp: 58.0,
+        gpu_temp: 49.0,
+        network_rx_rate: 1_250_000.0,
+        network_tx_rate: 180_000.0,
+        network_rx_history: &network_rx_history,
+        network_tx_history: &network_tx_history,
+        cpu_frequency: Some(&cpu_frequency),
+        hardware_info: config.show_hardware_info.then_some(hardware_lines.as_slice()),
+        gpu_processes: &gpu_processes,
+        pressure: Some(Pressure { cpu: Some(2.1), memory: Some(0.0), io: Some(14.6) }),
+        kernel_stats: Some(KernelStats { processes: 412, threads: 1893, context_switches_per_sec: Some(12_400.0) }),
+        gpu_details: Some(&gpu_details),
+        cpu_temp_history: &cpu_temp_history,
+        gpu_temp_history: &gpu_temp_history,
+        hottest_core: Some(&hottest_core),
+        // Shown while enabled, so the badge can be seen
+        throttling: true,
+        identity: identity.as_deref(),
+        caffeine_active: config.caffeine_on_start,
+        power_profile: config.show_power_profile.then_some("balanced"),
+        weather_temp: 18.0,
+        weather_desc: "Partly cloudy",
+        weather_location: location,
+        weather_icon: "02d",
+        weather_status_label: "",
+        weather_feels_like: 17.0,
+        weather_temp_min: 14.0,
+        weather_temp_max: 21.0,
+        weather_humidity: 62,
+        weather_wind_speed: 3.4,
+        weather_wind_deg: 220,
+        weather_sun: Some(sample_sun_times()),
+        // Shown although the sample is daytime, so the option can be seen
+        weather_moon_phase: config.weather_moon_phase.then_some(0.4),
+        air_quality: config
+            .show_air_quality
+            .then_some(AirQuality { index: 42, level: AqiLevel::Good }),
+        disk_info: &disks,
+        drive_health: &drive_health,
+        storage_pools: &storage_pools,
+        battery_devices: &batteries,
+        grouped_notifications: &notifications,
+        collapsed_groups: &collapsed_groups,
+        notification_expansion: &notification_expansion,
+        do_not_disturb: Some(false),
+        notification_scroll: 0,
+        media_info: &media,
+        // A still image: long titles show their start, clipped
+        media_marquee: config.media_marquee.then_some(Marquee {
+            elapsed: Duration::ZERO,
+            speed: config.marquee_speed,
+            pause: Duration::from_millis(config.marquee_pause_ms as u64),
+        }),
+        lyrics_line: Some("Sample lyrics line"),
+        custom_outputs: &custom_outputs,
+        plugin_frames: &[],
+        audio: Some(&audio),
+        health: Some(&health),
+        containers: Some(&containers),
+        vms: &vms,
+        feed_rows: &feed_rows,
+        ticker_rows: &ticker_rows,
+        player_count: 1,
+        current_player_index: 0,
+        section_columns: &section_columns,
+        compact_slots: &compact_slots,
+        current_time: now,
+        locale,
+        world_clocks: &world_clocks,
+        clock_zone: None,
+        calendar_events: &calendar_events,
+        fonts: &fonts,
+        section_errors: &[],
+        hovered_error: None,
+        snap_guides: SnapGuides::default(),
+        tooltip: None,
+        loading_sections: &[],
+        update_available: None,
+        opacity: 1.0,
+        debug_overlay: None,
+    };
+
+    let mut canvas = vec![0u8; width as usize * height as usize * 4];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        render_widget(&mut canvas, config, theme, data).map(|_| ())
+    }));
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            log::error!("Failed to render the preview: {}", e);
+            return None;
+        }
+        Err(e) => {
+            log::error!("Panic occurred while rendering the preview: {:?}", e);
+            return None;
+        }
+    }
+
+    argb_to_rgba(&mut canvas);
+    Some(Preview {
+        handle: image::Handle::from_rgba(width, height, canvas),
+        width,
+        height,
+    })
+}
+
+/// Size of the widget with `config` and the sample data.
+///
+/// Close to the real size; only the number of disks, devices and
+/// notifications differs.
+pub fn widget_size(config: &Config) -> (u32, u32) {
+    calculate_widget_size(config, sample_counts(config))
+}
+
+/// Content counts of the sample data for the enabled sections.
+fn sample_counts(config: &Config) -> ContentCounts {
+    ContentCounts {
+        disks: if config.show_storage { sample_disks().len() } else { 0 },
+        batteries: if config.show_battery { sample_batteries().len() } else { 0 },
+        notifications: if config.show_notifications {
+            sample_notifications().iter().map(|(_, group)| group.len()).sum()
+        } else {
+            0
+        },
+        players: if config.show_media { 1 } else { 0 },
+        plugin_height: 0,
+        events: if config.show_calendar {
+            sample_events(chrono::Local::now()).len().min(config.calendar_max_events as usize)
+        } else {
+            0
+        },
+        gpu_processes: sample_gpu_processes(config).len(),
+        drives: sample_drive_health(config).len(),
+        pools: sample_pools(config).len(),
+        vms: sample_vms(config).len(),
+    }
+}
+
+/// Convert Cairo's premultiplied ARGB32 pixels to straight RGBA in place.
+///
+/// Cairo stores each pixel as a native-endian `u32`, so the byte order
+/// depends on the platform; iced expects plain R, G, B, A bytes.
+pub fn argb_to_rgba(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let argb = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+        let alpha = (argb >> 24) & 0xff;
+        let unpremultiply = |channel: u32| -> u8 {
+            if alpha == 0 {
+                0
+            } else {
+                ((channel * 255 + alpha / 2) / alpha).min(255) as u8
+            }
+        };
+        pixel.copy_from_slice(&[
+            unpremultiply((argb >> 16) & 0xff),
+            unpremultiply((argb >> 8) & 0xff),
+            unpremultiply(argb & 0xff),
+            alpha as u8,
+        ]);
+    }
+}
+
+// ============================================================================
+// Sample Data
+// ============================================================================
+
+/// A healthy NVMe drive, when drive health is shown.
+fn sample_drive_health(config: &Config) -> Vec<DriveHealth> {
+    if !(config.show_storage && config.show_smart) {
+        return Vec::new();
+    }
+    vec![DriveHealth {
+        name: String::from("nvme0n1"),
+        model: Some(String::from("Samsung SSD 980 PRO 1TB")),
+        temperature: Some(41.0),
+        verdict: SmartVerdict::Passed,
+    }]
+}
+
+/// A healthy ZFS pool, when the storage pools section is shown.
+fn sample_pools(config: &Config) -> Vec<PoolStatus> {
+    if !config.show_storage_pools {
+        return Vec::new();
+    }
+    vec![PoolStatus {
+        name: String::from("tank"),
+        kind: PoolKind::Zfs,
+        health: PoolHealth::Online,
+        capacity: Some((1100 * GIB, 3712 * GIB)),
+        scrub: ScrubStatus::Finished { errors: 0, date: Some(String::from("Oct 13")) },
+    }]
+}
+
+/// A Windows guest and a small router, when the virtual machines section is shown.
+fn sample_vms(config: &Config) -> Vec<VmInfo> {
+    if !config.show_vms {
+        return Vec::new();
+    }
+    vec![
+        VmInfo { name: String::from("router"), vcpus: Some(1), memory: Some(GIB / 2) },
+        VmInfo { name: String::from("win11"), vcpus: Some(4), memory: Some(8 * GIB) },
+    ]
+}
+
+/// Two disks, one of them fairly full.
+fn sample_disks() -> Vec<DiskInfo> {
+    vec![
+        DiskInfo {
+            name: String::from("System"),
+            mount_point: String::from("/"),
+            used_percentage: 48.0,
+            total_space: 512 * GIB,
+            available_space: 266 * GIB,
+            inode_percentage: Some(31.0),
+            is_loading: false,
+        },
+        DiskInfo {
+            name: String::from("Home"),
+            mount_point: String::from("/home"),
+            used_percentage: 71.0,
+            total_space: 1024 * GIB,
+            available_space: 297 * GIB,
+            inode_percentage: Some(12.0),
+            is_loading: false,
+        },
+    ]
+}
+
+/// A single wireless mouse.
+fn sample_batteries() -> Vec<BatteryDevice> {
+    vec![BatteryDevice {
+        name: String::from("Wireless Mouse"),
+        level: Some(80),
+        status: Some(String::from("discharging")),
+        kind: Some(String::from("mouse")),
+        is_loading: false,
+        is_connected: true,
+    }]
+}
+
+/// A download burst and steady upload over the whole graph window.
+fn sample_network_history(window_secs: u32) -> (History, History) {
+    let mut rx = History::new(window_secs);
+    let mut tx = History::new(window_secs);
+    let steps = 60;
+    for step in 0..=steps {
+        let timestamp = window_secs as u64 * 1000 * step / steps;
+        let phase = step as f64 / steps as f64 * std::f64::consts::TAU;
+        rx.push(timestamp, 1_250_000.0 * (1.0 + phase.sin()) / 2.0 + 50_000.0);
+        tx.push(timestamp, 180_000.0 * (1.0 + (phase * 2.0).cos()) / 2.0 + 20_000.0);
+    }
+    (rx, tx)
+}
+
+/// A CPU warming up under a load spike and a steadier GPU.
+fn sample_temp_history(window_secs: u32) -> (History, History) {
+    let mut cpu = History::new(window_secs);
+    let mut gpu = History::new(window_secs);
+    let steps = 60;
+    for step in 0..=steps {
+        let timestamp = window_secs as u64 * 1000 * step / steps;
+        let phase = step as f64 / steps as f64 * std::f64::consts::TAU;
+        cpu.push(timestamp, 55.0 + 18.0 * (phase * 1.5).sin().max(0.0) + 3.0 * (phase * 7.0).sin());
+        gpu.push(timestamp, 47.0 + 6.0 * (phase * 0.5).sin());
+    }
+    (cpu, gpu)
+}
+
+/// A meeting later today, an appointment tomorrow and an all-day event.
+fn sample_events(now: chrono::DateTime<chrono::Local>) -> Vec<CalendarEvent> {
+    let event = |title: &str, start: chrono::DateTime<chrono::Local>, hours, all_day| CalendarEvent {
+        title: String::from(title),
+        start,
+        end: start + chrono::TimeDelta::hours(hours),
+        all_day,
+    };
+    let tomorrow = (now + chrono::TimeDelta::days(1)).date_naive();
+    let local = |date: chrono::NaiveDate, hour| date.and_hms_opt(hour, 0, 0).and_then(|t| t.and_local_timezone(chrono::Local).earliest());
+    let mut events = vec![event("Team meeting", now + chrono::TimeDelta::hours(1), 1, false)];
+    events.extend(local(tomorrow, 9).map(|start| event("Dentist", start, 1, false)));
+    events.extend(local(tomorrow + chrono::Days::new(2), 0).map(|start| event("Conference", start, 24, true)));
+    events
+}
+
+/// One notification group with a single message.
+fn sample_notifications() -> Vec<(String, Vec<Notification>)> {
+    let notification = Notification {
+        app_name: String::from("System"),
+        summary: String::from("Updates installed"),
+        body: String::from("Restart to finish applying updates."),
+        timestamp: chrono::Local::now().timestamp().max(0) as u64,
+        urgency: Urgency::Normal,
+        actions: Vec::new(),
+        desktop_entry: None,
+    };
+    vec![(notification.app_name.clone(), vec![notification])]
+}
+
+/// A paused track, halfway through.
+fn sample_media() -> MediaInfo {
+    MediaInfo {
+        player_name: String::from("Music"),
+        title: String::from("Sample Track"),
+        artist: String::from("Sample Artist"),
+        album: String::from("Sample Album"),
+        status: PlaybackStatus::Paused,
+        position: 95_000,
+        duration: 190_000,
+        ..Default::default()
+    }
+}
+
+/// A single package boosting under load.
+fn sample_cpu_frequency() -> CpuFrequency {
+    CpuFrequency {
+        packages: vec![3420.0],
+        governor: Some(String::from("powersave")),
+        available_governors: vec![String::from("performance"), String::from("powersave")],
+    }
+}
+
+/// A desktop with a discrete GPU.
+fn sample_hardware() -> HardwareInfo {
+    HardwareInfo {
+        cpu_model: Some(String::from("AMD Ryzen 7 5800X")),
+        cores: Some(8),
+        threads: 16,
+        gpu_name: Some(String::from("AMD Radeon RX 6800 XT")),
+    }
+}
+
+/// A user on their desktop.
+fn sample_identity() -> Identity {
+    Identity { hostname: String::from("workstation"), username: String::from("alice") }
+}
+
+/// Built-in speakers at a moderate volume.
+fn sample_audio() -> AudioState {
+    AudioState {
+        device: String::from("Built-in Audio Analog Stereo"),
+        volume: 45,
+        muted: false,
+    }
+}
+
+/// A desktop card under light load.
+fn sample_gpu_details() -> GpuDetails {
+    GpuDetails {
+        fan_percent: Some(34.0),
+        core_mhz: Some(1845.0),
+        memory_mhz: Some(2000.0),
+    }
+}
+
+/// A game and the compositor, when the process list is shown.
+fn sample_gpu_processes(config: &Config) -> Vec<GpuProcess> {
+    if !(config.show_gpu && config.show_gpu_processes) {
+        return Vec::new();
+    }
+    vec![
+        GpuProcess {
+            pid: 4242,
+            name: String::from("game"),
+            usage: 21.0,
+        },
+        GpuProcess {
+            pid: 1337,
+            name: String::from("cosmic-comp"),
+            usage: 5.0,
+        },
+    ]
+}
+
+/// Sunrise at 06:10 and sunset at 20:35 today, local time.
+fn sample_sun_times() -> SunTimes {
+    let today = chrono::Local::now().date_naive();
+    let at = |hour, minute| {
+        today
+            .and_hms_opt(hour, minute, 0)
+            .and_then(|time| time.and_local_timezone(chrono::Local).earliest())
+            .map(|time| time.timestamp())
+            .unwrap_or_default()
+    };
+    SunTimes::Normal {
+        sunrise: at(6, 10),
+        sunset: at(20, 35),
+    }
+}
+
+/// One failed unit and a few pending updates.
+fn sample_health() -> HealthReport {
+    HealthReport {
+        failed_units: vec![String::from("backup.service")],
+        updates: Some(12),
+    }
+}
+
+/// A database, a web server and a cache, the database busiest.
+fn sample_containers() -> ContainerReport {
+    ContainerReport {
+        running: vec![String::from("nginx"), String::from("postgres"), String::from("redis")],
+        busiest: Some(BusiestContainer { name: String::from("postgres"), cpu_percent: 12.3 }),
+    }
+}
+
+/// A few unread messages per mailbox and a headline per feed.
+fn sample_feeds(config: &Config) -> Vec<FeedRow> {
+    let feeds = feed_settings(config).map(|settings| settings.feeds).unwrap_or_default();
+    feeds
+        .into_iter()
+        .map(|feed| FeedRow {
+            label: feed.label,
+            kind: feed.kind,
+            value: Some(match feed.kind {
+                FeedKind::Imap => FeedValue::Unread(3),
+                FeedKind::Rss => FeedValue::Headline(String::from("COSMIC Epoch 1 released")),
+            }),
+            failed: false,
+        })
+        .collect()
+}
+
+/// Made-up quotes for the configured symbols, alternating up and down.
+fn sample_ticker(config: &Config) -> Vec<TickerRow> {
+    let symbols = ticker_settings(config).map(|settings| settings.symbols).unwrap_or_default();
+    symbols
+        .into_iter()
+        .enumerate()
+        .map(|(index, symbol)| TickerRow {
+            symbol,
+            quote: Some(Quote {
+                price: 1234.5 / (index + 1) as f64,
+                change_percent: Some(if index % 2 == 0 { 2.4 } else { -1.3 }),
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_argb_to_rgba() {
+        // Opaque orange, half-transparent premultiplied white, fully transparent
+        let mut pixels: Vec<u8> = [0xffff8000u32, 0x80808080, 0x00000000]
+            .iter()
+            .flat_map(|argb| argb.to_ne_bytes())
+            .collect();
+        argb_to_rgba(&mut pixels);
+        assert_eq!(pixels, vec![0xff, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0x80, 0, 0, 0, 0]);
+    }
+}
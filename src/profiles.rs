@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Configuration Profiles
+//!
+//! Unlike [layout bundles](crate::bundle), profiles hold the *complete*
+//! configuration, so a whole setup can be backed up, moved to another
+//! machine, or switched in one step:
+//!
+//! - **Files**: the full [`Config`] exported to or imported from any path.
+//! - **Saved profiles**: named files in `~/.config/cosmic-monitor/profiles`,
+//!   listed in the settings app and switched with one click.
+//! - **Presets**: built-in starting points ([`Preset`]) that only change
+//!   which sections are shown and how often they update.
+//!
+//! Files are RON, the format cosmic-config uses for each entry on disk:
+//!
+//! ```ron
+//! (
+//!     show_cpu: true,
+//!     show_memory: true,
+//!     section_order: [Clock, Utilization, Temperatures, ...],
+//!     ...
+//! )
+//! ```
+//!
+//! Missing fields fall back to the defaults, so profiles written by older
+//! versions still load. Profiles include API keys and custom commands, so
+//! only import profiles you trust; use a bundle to share a look instead.
+//!
+//! Whatever is loaded is written through cosmic-config by the settings app,
+//! and the widget picks it up on its next config poll.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, WidgetSection};
+
+/// File extension of profile files.
+pub const EXTENSION: &str = "ron";
+
+// ============================================================================
+// Presets
+// ============================================================================
+
+/// Built-in starting points offered in the settings app.
+///
+/// Presets are applied on top of the current configuration: position,
+/// API keys, custom commands and advanced settings are left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Clock, CPU and memory only
+    Minimal,
+    /// Utilization and temperatures first, GPU and network included
+    Gamer,
+    /// Battery and storage, with slower, lighter sampling
+    Laptop,
+}
+
+impl Preset {
+    /// All presets in display order.
+    pub const ALL: [Preset; 3] = [Preset::Minimal, Preset::Gamer, Preset::Laptop];
+
+    /// Human-readable name shown on the preset buttons.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Preset::Minimal => "Minimal",
+            Preset::Gamer => "Gamer",
+            Preset::Laptop => "Laptop",
+        }
+    }
+
+    /// Change the shown sections and sampling of `config` to the preset.
+    pub fn apply(&self, config: &mut Config) {
+        // Every preset starts from a clean slate of sections
+        config.show_clock = true;
+        config.show_date = true;
+        config.show_cpu = true;
+        config.show_cpu_frequency = false;
+        config.show_cpu_governor = false;
+        config.show_hardware_info = false;
+        config.show_memory = true;
+        config.show_gpu = false;
+        config.show_gpu_processes = false;
+        config.show_gpu_details = false;
+        config.show_psi = false;
+        config.show_network = false;
+        config.show_disk = false;
+        config.show_cpu_temp = false;
+        config.show_gpu_temp = false;
+        config.show_hottest_core = false;
+        config.show_throttling = false;
+        config.show_storage = false;
+        config.show_storage_pools = false;
+        config.show_battery = false;
+        config.show_power_profile = false;
+        config.show_weather = false;
+        config.show_notifications = false;
+        config.show_media = false;
+        config.show_custom_commands = false;
+        config.show_plugins = false;
+        config.show_audio = false;
+        config.show_system_health = false;
+        config.show_containers = false;
+        config.show_vms = false;
+        config.show_kernel_stats = false;
+        config.show_feeds = false;
+        config.show_ticker = false;
+        config.show_caffeine = false;
+        config.show_calendar = false;
+
+        match self {
+            Preset::Minimal => {
+                config.show_date = false;
+                config.show_percentages = false;
+                config.enable_animations = false;
+                config.update_interval_ms = 2000;
+            }
+            Preset::Gamer => {
+                config.show_gpu = true;
+                config.show_network = true;
+                config.show_cpu_temp = true;
+                config.show_gpu_temp = true;
+                config.show_media = true;
+                config.show_percentages = true;
+                config.use_circular_temp_display = true;
+                config.update_interval_ms = 1000;
+                move_to_front(config, &[WidgetSection::Utilization, WidgetSection::Temperatures]);
+            }
+            Preset::Laptop => {
+                config.show_cpu_temp = true;
+                config.show_storage = true;
+                config.show_battery = true;
+                config.show_power_profile = true;
+                config.show_percentages = true;
+                config.enable_animations = false;
+                config.update_interval_ms = 3000;
+                config.low_priority_collectors = true;
+                move_to_front(config, &[WidgetSection::Clock, WidgetSection::Battery]);
+            }
+        }
+    }
+}
+
+/// Move `sections` to the start of the section order, in the given order.
+fn move_to_front(config: &mut Config, sections: &[WidgetSection]) {
+    config.section_order.retain(|section| !sections.contains(section));
+    for (index, section) in sections.iter().enumerate() {
+        config.section_order.insert(index, *section);
+    }
+}
+
+// ============================================================================
+// Profile Files
+// ============================================================================
+
+/// Directory of the saved profiles.
+pub fn profile_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("cosmic-monitor")
+        .join("profiles")
+}
+
+/// Default location offered for exporting and importing.
+pub fn default_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(format!("cosmic-monitor-profile.{}", EXTENSION))
+}
+
+/// Write the full configuration to `path`.
+///
+/// # Errors
+///
+/// Returns serialization and I/O errors.
+pub fn export(config: &Config, path: &Path) -> io::Result<()> {
+    let ron = ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    fs::write(path, ron)
+}
+
+/// Read a full configuration from `path`.
+///
+/// The section order is migrated as when loading from cosmic-config.
+///
+/// # Errors
+///
+/// Returns the read error, or `InvalidData` if the file isn't a profile.
+pub fn import(path: &Path) -> io::Result<Config> {
+    let content = fs::read_to_string(path)?;
+    let mut config: Config = ron::from_str(&content)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("not a valid profile: {}", e)))?;
+    config.migrate_section_order();
+    Ok(config)
+}
+
+/// Names of the saved profiles, sorted.
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(profile_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Save `config` as the named profile, replacing an existing one.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for unusable names and I/O errors from writing.
+pub fn save(name: &str, config: &Config) -> io::Result<()> {
+    let path = profile_path(name)?;
+    fs::create_dir_all(profile_dir())?;
+    export(config, &path)
+}
+
+/// Load the named profile.
+///
+/// # Errors
+///
+/// Same as [`import`], plus `InvalidInput` for unusable names.
+pub fn load(name: &str) -> io::Result<Config> {
+    import(&profile_path(name)?)
+}
+
+/// Delete the named profile.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for unusable names and I/O errors from removing.
+pub fn delete(name: &str) -> io::Result<()> {
+    fs::remove_file(profile_path(name)?)
+}
+
+/// File of a named profile.
+///
+/// Names may use letters, digits, spaces, `-` and `_`, so they can't point
+/// outside the profile directory.
+fn profile_path(name: &str) -> io::Result<PathBuf> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_');
+    if !valid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "profile names may only use letters, digits, spaces, '-' and '_'",
+        ));
+    }
+    Ok(profile_dir().join(format!("{}.{}", name, EXTENSION)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let config = Config {
+            show_gpu: true,
+            weather_location: String::from("London,UK"),
+            widget_x: 321,
+            section_order: vec![WidgetSection::Media, WidgetSection::Clock],
+            ..Default::default()
+        };
+        let path = std::env::temp_dir().join(format!("cosmic-monitor-profile-test-{}.ron", std::process::id()));
+        export(&config, &path).unwrap();
+        let imported = import(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(imported.show_gpu);
+        assert_eq!(imported.weather_location, "London,UK");
+        assert_eq!(imported.widget_x, 321);
+        // Migrated: the exported order only had two sections
+        assert_eq!(&imported.section_order[..2], &[WidgetSection::Media, WidgetSection::Clock]);
+        assert_eq!(imported.section_order.len(), Config::default().section_order.len());
+    }
+
+    #[test]
+    fn test_preset_keeps_private_settings() {
+        let mut config = Config {
+            weather_api_key: String::from("secret"),
+            widget_x: 123,
+            show_weather: true,
+            ..Default::default()
+        };
+        Preset::Gamer.apply(&mut config);
+
+        assert_eq!(config.weather_api_key, "secret");
+        assert_eq!(config.widget_x, 123);
+        assert!(!config.show_weather && config.show_gpu_temp);
+        assert_eq!(&config.section_order[..2], &[WidgetSection::Utilization, WidgetSection::Temperatures]);
+    }
+
+    #[test]
+    fn test_profile_names() {
+        assert!(profile_path("Evening Setup_2").is_ok());
+        assert!(profile_path("../config").is_err());
+        assert!(profile_path("  ").is_err());
+    }
+}
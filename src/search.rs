@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Settings Search
+//!
+//! The search field in the settings window's header looks through the
+//! options of every page, like COSMIC Settings does. Each option is listed
+//! here with the page and section it's on, and a few extra keywords for
+//! words people search for that aren't in its label:
+//!
+//! ```text
+//! query "temp graph"
+//!   Show Temperature Graph        Sections › Temperature Display
+//!   Temperature Graph Window      Sections › Temperature Display
+//! ```
+//!
+//! Every word of the query has to appear in the label, the keywords, the
+//! section or the page name (case-insensitive). Picking a result opens its
+//! page.
+//!
+//! Options added to a page should be added here as well, or search won't
+//! find them.
+
+use crate::fl;
+use crate::settings::SettingsPage;
+
+/// One option that search can find.
+#[derive(Debug, Clone)]
+pub struct SearchEntry {
+    /// Page the option is on
+    pub page: SettingsPage,
+    /// Section heading on that page
+    pub section: String,
+    /// Label of the option, as shown
+    pub label: String,
+    /// Other words that should find the option
+    keywords: &'static str,
+}
+
+impl SearchEntry {
+    /// Whether every word of `query` appears in the entry.
+    pub fn matches(&self, query: &str) -> bool {
+        let haystack = format!("{} {} {} {}", self.label, self.keywords, self.section, self.page.label()).to_lowercase();
+        matches_words(&haystack, query)
+    }
+}
+
+/// Whether every whitespace-separated word of `query` is in `haystack`
+/// (already lowercase). An empty query matches nothing.
+fn matches_words(haystack: &str, query: &str) -> bool {
+    let mut words = query.split_whitespace().map(str::to_lowercase).peekable();
+    words.peek().is_some() && words.all(|word| haystack.contains(&word))
+}
+
+/// Options matching `query`, in page order.
+pub fn search(query: &str) -> Vec<SearchEntry> {
+    entries().into_iter().filter(|entry| entry.matches(query)).collect()
+}
+
+/// Every searchable option.
+fn entries() -> Vec<SearchEntry> {
+    use SettingsPage::*;
+
+    let mut entries = Vec::new();
+    let mut section = |page: SettingsPage, heading: String, options: &[(String, &'static str)]| {
+        for (label, keywords) in options {
+            entries.push(SearchEntry { page, section: heading.clone(), label: label.clone(), keywords });
+        }
+    };
+
+    section(General, fl!("widget-display"), &[
+        (fl!("show-hostname"), "machine computer name identity stream"),
+        (fl!("show-username"), "login account identity stream"),
+        (fl!("identity-label"), "hostname user name stream privacy"),
+        (fl!("show-clock"), "time"),
+        (fl!("show-date"), "day"),
+        (fl!("use-24hour-time"), "12 am pm clock"),
+        (fl!("clock-format"), "strftime pattern seconds time"),
+        (fl!("date-format"), "strftime pattern iso week"),
+        (fl!("world-clock-add"), "time zone world clocks tokyo"),
+    ]);
+    section(General, fl!("calendar"), &[
+        (fl!("show-calendar"), "events agenda ics evolution"),
+        (fl!("calendar-source"), "ics evolution data server"),
+        (fl!("calendar-ics-path"), "file"),
+        (fl!("calendar-max-events"), "count"),
+        (fl!("calendar-refresh"), "interval minutes"),
+    ]);
+    section(General, fl!("profiles"), &[
+        (fl!("profiles-presets"), "minimal gamer laptop"),
+        (fl!("profiles-save-as"), "backup"),
+        (fl!("profiles-file"), "export import ron backup"),
+    ]);
+
+    section(Layout, fl!("display-options"), &[
+        (fl!("show-percentages"), "numbers values bars"),
+        (fl!("memory-label"), "ram used total gb"),
+        (fl!("show-tooltips"), "hover details"),
+        (fl!("show-panel-readout"), "applet panel top bar cpu ram temperature"),
+        (fl!("enable-animations"), "fade transitions ease"),
+        (fl!("layout-mode"), "vertical horizontal compact strip"),
+        (fl!("layout-columns"), "two columns"),
+        (fl!("text-contrast"), "dark light mode wallpaper"),
+    ]);
+    section(Layout, fl!("fonts"), &[(fl!("fonts"), "font family size typeface clock header body small label pango")]);
+    section(Layout, fl!("layout-order"), &[(fl!("layout-order"), "sections reorder move arrange card background panel dashboard conky opacity")]);
+    section(Layout, fl!("layout-bundle"), &[
+        (fl!("layout-bundle-file"), "share export import json theme"),
+    ]);
+
+    section(Sections, fl!("monitoring-options"), &[
+        (fl!("show-cpu"), "processor usage utilization"),
+        (fl!("show-cpu-frequency"), "clock speed ghz mhz"),
+        (fl!("show-cpu-governor"), "cpufreq powersave performance"),
+        (fl!("cpu-governor-click"), "cpufreq pkexec switch"),
+        (fl!("show-hardware-info"), "model cores threads name"),
+        (fl!("show-memory"), "ram usage"),
+        (fl!("show-gpu"), "graphics usage nvidia amd intel"),
+        (fl!("show-gpu-processes"), "graphics top apps"),
+        (fl!("show-gpu-details"), "graphics fan speed clock mhz core memory vram"),
+        (fl!("show-psi"), "psi pressure stall load struggling swap thrashing io wait"),
+        (fl!("show-network"), "bandwidth download upload rates"),
+        (fl!("network-rate-bits"), "bandwidth mbit"),
+        (fl!("show-network-graph"), "bandwidth history"),
+        (fl!("network-graph-window"), "bandwidth history seconds"),
+        (fl!("show-disk"), "io read write activity"),
+    ]);
+    section(Sections, fl!("storage-display"), &[
+        (fl!("show-storage"), "disk space drives usage"),
+        (fl!("storage-min-free"), "low disk space full warning threshold red"),
+        (fl!("storage-max-inode"), "inodes files full warning threshold"),
+        (fl!("storage-alerts"), "low disk space full notification alert"),
+        (fl!("show-smart"), "smart smartctl nvme ssd hdd drive temperature failing"),
+        (fl!("smart-refresh"), "smart interval minutes"),
+        (fl!("show-storage-pools"), "zfs zpool btrfs raid scrub nas homelab health"),
+        (fl!("storage-pools-refresh"), "zfs btrfs interval minutes"),
+    ]);
+    section(Sections, fl!("temperature-display"), &[
+        (fl!("show-cpu-temp"), "temperature sensor heat"),
+        (fl!("show-gpu-temp"), "temperature sensor heat"),
+        (fl!("use-circular-temp-display"), "gauge circle"),
+        (fl!("temps-on-usage-bars"), "badges merged"),
+        (fl!("show-hottest-core"), "per core temperature"),
+        (fl!("show-throttling"), "thermal throttle"),
+        (fl!("show-temp-graph"), "temperature history min max average"),
+        (fl!("temp-graph-window"), "temperature history minutes"),
+        (fl!("temp-graph-mode"), "temperature graph replace gauges"),
+    ]);
+    section(Sections, fl!("battery"), &[
+        (fl!("show-battery"), "power charge laptop"),
+        (fl!("enable-solaar"), "logitech mouse keyboard wireless"),
+        (fl!("show-power-profile"), "power-profiles-daemon performance balanced saver laptop"),
+    ]);
+    section(Sections, fl!("custom-commands"), &[
+        (fl!("show-custom-commands"), "shell script scripts output"),
+        (fl!("custom-command-add"), "shell script"),
+    ]);
+    section(Sections, fl!("system-health"), &[
+        (fl!("show-system-health"), "systemd failed units updates"),
+        (fl!("health-update-command"), "checkupdates packages"),
+        (fl!("health-refresh"), "interval minutes"),
+    ]);
+    section(Sections, fl!("containers"), &[
+        (fl!("show-containers"), "docker podman container running"),
+        (fl!("containers-show-busiest"), "docker podman cpu stats"),
+        (fl!("containers-refresh"), "interval minutes"),
+    ]);
+    section(Sections, fl!("vms"), &[
+        (fl!("show-vms"), "libvirt virsh qemu kvm vm virtual machine guest"),
+        (fl!("vms-uri"), "libvirt qemu system session connection"),
+        (fl!("vms-refresh"), "interval minutes"),
+    ]);
+    section(Sections, fl!("system-section"), &[
+        (fl!("show-kernel-stats"), "kernel processes threads context switches tasks fork leak"),
+    ]);
+    section(Sections, fl!("feeds"), &[
+        (fl!("show-feeds"), "mail imap rss atom unread"),
+        (fl!("feeds-refresh"), "interval minutes"),
+        (fl!("feed-add"), "mail imap rss atom"),
+    ]);
+    section(Sections, fl!("ticker"), &[
+        (fl!("show-ticker"), "crypto stocks prices bitcoin"),
+        (fl!("ticker-provider"), "coingecko json endpoint"),
+        (fl!("ticker-symbols"), "crypto stocks"),
+        (fl!("ticker-max-symbols"), "count"),
+        (fl!("ticker-currency"), "usd eur"),
+        (fl!("ticker-endpoint"), "json url api"),
+        (fl!("ticker-refresh"), "interval"),
+    ]);
+    section(Sections, fl!("plugins"), &[(fl!("show-plugins"), "lua scripts")]);
+    section(Sections, fl!("caffeine"), &[
+        (fl!("show-caffeine"), "idle inhibit screen blank sleep"),
+        (fl!("caffeine-on-start"), "idle inhibit"),
+    ]);
+
+    section(Weather, fl!("weather-display"), &[
+        (fl!("show-weather"), "forecast temperature"),
+        (fl!("weather-detailed"), "feels like humidity wind"),
+        (fl!("weather-sun-times"), "sunrise sunset"),
+        (fl!("weather-moon-phase"), "moon night"),
+        (fl!("show-air-quality"), "aqi pollution"),
+        (fl!("air-quality-provider"), "aqi open-meteo openweathermap"),
+        (fl!("weather-api-key"), "openweathermap key token"),
+        (fl!("weather-location"), "city place coordinates"),
+    ]);
+
+    section(Media, fl!("media-player"), &[
+        (fl!("show-media"), "music now playing cider apple mpris"),
+        (fl!("cider-api-token"), "music apple auth"),
+        (fl!("media-marquee"), "marquee track title long ticker"),
+        (fl!("marquee-speed"), "marquee title scroll"),
+        (fl!("marquee-pause"), "marquee title scroll wait"),
+        (fl!("show-lyrics"), "lyrics lrclib synced karaoke words"),
+    ]);
+    section(Media, fl!("audio"), &[(fl!("show-audio"), "volume sound speakers output mute")]);
+
+    section(Alerts, fl!("bar-colors"), &[
+        (fl!("threshold-warning"), "thresholds levels yellow percent"),
+        (fl!("threshold-critical"), "thresholds levels red percent"),
+        (fl!("ambient-source"), "tint load temperature status color"),
+        (fl!("ambient-target"), "tint clock outline background"),
+    ]);
+    section(Alerts, fl!("notifications"), &[
+        (fl!("show-notifications"), "desktop alerts messages"),
+        (fl!("max-notifications"), "count limit"),
+        (fl!("compact-notifications"), "body text expand collapse hover"),
+        (fl!("notification-history"), "persist restart keep saved disk"),
+        (fl!("notification-history-count"), "history limit"),
+        (fl!("notification-history-days"), "history age retention"),
+        (fl!("notification-history-clear"), "history delete wipe privacy"),
+    ]);
+
+    section(Advanced, fl!("page-advanced"), &[
+        (fl!("update-interval"), "sampling refresh rate milliseconds"),
+        (fl!("log-level"), "logging debug verbose"),
+        (fl!("write-logs"), "logging rotate"),
+        (fl!("show-debug-overlay"), "performance frame time fps"),
+        (fl!("publish-stats"), "json scripts"),
+        (fl!("export-frames"), "stream png overlay"),
+        (fl!("record-metrics"), "csv log"),
+        (fl!("record-interval"), "csv record"),
+        (fl!("record-retention"), "csv record delete"),
+        (fl!("low-priority-collectors"), "nice priority"),
+        (fl!("idle-priority-collectors"), "sched_idle background priority"),
+        (fl!("pin-efficiency-cores"), "e-cores hybrid"),
+        (fl!("check-for-updates"), "release github version"),
+        (fl!("setup-run-again"), "wizard first run"),
+    ]);
+
+    section(Position, fl!("widget-position"), &[
+        (fl!("snap-to-edges"), "drag align"),
+        (fl!("snap-grid"), "drag align pixels"),
+        (fl!("widget-autostart"), "login startup"),
+        (fl!("click-through"), "mouse input pass"),
+        (fl!("keyboard-positioning"), "arrow keys move"),
+        (fl!("auto-hide-fullscreen"), "games video auto hide"),
+        (fl!("auto-hide-maximized"), "auto hide"),
+    ]);
+    section(Position, fl!("schedules"), &[(fl!("schedules"), "night dim hide time ranges")]);
+
+    section(Diagnostics, fl!("diagnostics"), &[
+        (fl!("diag-widget-process"), "running status pid"),
+        (fl!("diag-gpu-vendor"), "graphics detection"),
+        (fl!("diag-sensors"), "hwmon temperature detection"),
+        (fl!("diag-recent-errors"), "problems log"),
+    ]);
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_words() {
+        let haystack = "show temperature graph temperature history min max sections";
+        assert!(matches_words(haystack, "temp graph"));
+        assert!(matches_words(haystack, "  GRAPH  "));
+        assert!(!matches_words(haystack, "temp weather"));
+        assert!(!matches_words(haystack, ""));
+        assert!(!matches_words(haystack, "   "));
+    }
+}
@@ -12,9 +12,11 @@
 //! - **Weather configuration**: API key and location settings
 //! - **Notification settings**: Enable and set max notification count
 //! - **Media player settings**: Cider API token configuration
-//! - **Widget positioning**: Set X/Y coordinates or drag while settings open
+//! - **Widget positioning**: Drag the widget on a mockup of the connected screens, or drag the widget itself while settings are open
 //! - **Section reordering**: Change the order of widget sections
 //! - **Advanced options**: Debug logging toggle
+//! - **Live preview**: Scaled-down widget rendered with sample data next to the options
+//! - **Diagnostics page**: Widget status, detected hardware, and recent errors
 //!
 //! # Architecture
 //!
@@ -26,83 +28,83 @@
 //! Changes are saved immediately when toggles change, allowing the widget
 //! to pick them up on its next config poll (typically within 1 second).
 
-use crate::config::{Config, WidgetSection};
+use crate::bundle::Bundle;
+use crate::config::{
+    is_valid_time_format, parse_hex_color, time_locale, AirQualityProvider, AmbientSource, AmbientTarget, CalendarSource, Config, CustomCommand, Feed, FeedKind, LayoutMode, Level, LogLevel,
+    MemoryLabel, Schedule, ScrollAction, SectionCard, TempGraphMode, TextContrast, TextRole, ThresholdMetric, TickerProvider, WidgetAnchor, WidgetSection, WorldClock, CALENDAR_MAX_EVENTS, CUSTOM_INTERVAL_SECS,
+    DEFAULT_DATE_FORMAT, DEFAULT_FONT_FAMILY, FONT_SIZE, MARQUEE_PAUSE_MS, MARQUEE_SPEED, MAX_NOTIFICATIONS, MAX_THRESHOLD, NOTIFICATION_HISTORY_COUNT, NOTIFICATION_HISTORY_DAYS, NETWORK_GRAPH_SECONDS, RECORD_INTERVAL_SECS, REFRESH_MINUTES, SNAP_GRID, STORAGE_MAX_INODE_PERCENT, STORAGE_MIN_FREE_PERCENT, TEMP_GRAPH_MINUTES, TICKER_MAX_SYMBOLS, UPDATE_INTERVAL_MS,
+};
 use crate::fl;
+use crate::placement::{Mockup, MOCKUP_HEIGHT, MOCKUP_WIDTH};
+use crate::preview::{self, Preview, PREVIEW_SCALE};
+use crate::profiles::{self, Preset};
+use crate::search;
+use crate::setup::{self, SetupStep, SetupToggle};
+use crate::widget::cache::{CachedBatteryDevice, CachedOutput, WidgetCache};
+use crate::widget::control::{self, ControlCommand};
+use crate::widget::notification_history;
+use crate::widget::diagnostics::WidgetStatus;
+use crate::widget::fonts;
+use crate::widget::schedule::parse_time;
+use crate::widget::theme::CosmicTheme;
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use cosmic::prelude::*;
 use cosmic::widget;
 use cosmic::Application;
 use cosmic::Element;
-use serde::{Deserialize, Serialize};
 
-// ============================================================================
-// Widget Cache Structures
-// ============================================================================
-// The widget caches discovered devices (batteries, disks) to a JSON file.
-// The settings app reads this cache to display device information and allow
-// users to remove stale entries.
+/// Column counts offered for the vertical layout.
+const COLUMN_OPTIONS: &[&str] = &["1", "2"];
 
-/// Cached battery device information from Solaar or system.
-///
-/// The widget discovers battery devices at runtime and caches them so the
-/// settings app can display them without requiring the same device access.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct CachedBatteryDevice {
-    /// Device name (e.g., "MX Master 3" or "BAT0")
-    pub name: String,
-    /// Device type (e.g., "Mouse", "Keyboard", or None for system batteries)
-    pub kind: Option<String>,
-}
+/// Opacities (percent) a schedule can set, with their dropdown labels.
+const SCHEDULE_OPACITIES: &[u32] = &[100, 80, 60, 40, 20];
+const SCHEDULE_OPACITY_LABELS: &[&str] = &["100%", "80%", "60%", "40%", "20%"];
 
-/// Cache file structure for widget-discovered information.
-///
-/// This cache allows the settings app to show what devices/disks the widget
-/// has found, without needing to probe the system itself.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct WidgetCache {
-    /// Mounted disks discovered by the storage monitor
-    pub disks: Vec<CachedDiskInfo>,
-    /// Battery devices from system or Solaar integration
-    pub battery_devices: Vec<CachedBatteryDevice>,
+/// Opacities (percent) of a section card (0 = no card).
+const CARD_OPACITIES: &[u32] = &[0, 20, 40, 60, 80, 100];
+
+/// Check whether a widget process is running (same approach as the applet).
+fn widget_process_running() -> bool {
+    std::process::Command::new("pgrep")
+        .arg("-f")
+        .arg("cosmic-monitor-widget")
+        .output()
+        .map(|output| !output.stdout.is_empty())
+        .unwrap_or(false)
 }
 
-/// Cached disk information for storage display.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct CachedDiskInfo {
-    /// Disk name (e.g., "nvme0n1p1")
-    pub name: String,
-    /// Mount point path (e.g., "/home")
-    pub mount_point: String,
+/// Format a Unix timestamp as local wall-clock time (HH:MM:SS).
+fn format_local_time(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| String::from("--:--:--"))
 }
 
-impl WidgetCache {
-    /// Returns the path to the cache file.
-    ///
-    /// Located at `~/.cache/cosmic-monitor-applet/widget_cache.json`
-    fn cache_path() -> std::path::PathBuf {
-        let mut path = dirs::cache_dir().unwrap_or_else(|| std::path::PathBuf::from("/tmp"));
-        path.push("cosmic-monitor-applet");
-        std::fs::create_dir_all(&path).ok();
-        path.push("widget_cache.json");
-        path
-    }
-
-    /// Load the cache from disk, returning default if file doesn't exist.
-    fn load() -> Self {
-        let path = Self::cache_path();
-        if let Ok(content) = std::fs::read_to_string(&path) {
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Self::default()
-        }
+/// Live example of a clock/date pattern input, or why it can't be used.
+///
+/// An empty input shows `fallback`, the pattern the widget uses then.
+fn format_example(input: &str, fallback: &str) -> String {
+    let pattern = if input.trim().is_empty() { fallback } else { input.trim() };
+    if is_valid_time_format(pattern) {
+        let example = chrono::Local::now().format_localized(pattern, time_locale()).to_string();
+        fl!("time-format-example", example = example)
+    } else {
+        fl!("time-format-invalid")
     }
+}
 
-    /// Save the cache to disk.
-    fn save(&self) {
-        let path = Self::cache_path();
-        if let Ok(json) = serde_json::to_string_pretty(self) {
-            std::fs::write(&path, json).ok();
-        }
+/// Size inputs of the font roles, in `TextRole::ALL` order.
+fn font_size_inputs(config: &Config) -> Vec<String> {
+    TextRole::ALL.iter().map(|role| config.fonts.get(*role).size.to_string()).collect()
+}
+
+/// Format a byte count as MiB or GiB for the diagnostics page.
+fn format_traffic(bytes: u64) -> String {
+    let mib = bytes as f64 / (1024.0 * 1024.0);
+    if mib >= 1024.0 {
+        format!("{:.1} GiB", mib / 1024.0)
+    } else {
+        format!("{:.1} MiB", mib)
     }
 }
 
@@ -110,6 +112,78 @@ impl WidgetCache {
 // Application Model
 // ============================================================================
 
+/// Top-level pages of the settings window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsPage {
+    /// Identity, clock and date, world clocks, calendar, and profiles
+    General,
+    /// Display options, section order, and layout bundles
+    Layout,
+    /// What each widget section shows
+    Sections,
+    /// Weather display and air quality
+    Weather,
+    /// Media player and audio output
+    Media,
+    /// Thresholds and colors, ambient status, and notifications
+    Alerts,
+    /// Sampling, logging, and background behavior
+    Advanced,
+    /// Position picker and placement options
+    Position,
+    /// Widget status, detected hardware, and recent errors
+    Diagnostics,
+    /// First-run setup wizard (not in the navigation bar)
+    Setup,
+}
+
+impl SettingsPage {
+    /// Pages in the navigation bar, in order.
+    pub const NAV: [SettingsPage; 9] = [
+        SettingsPage::General,
+        SettingsPage::Layout,
+        SettingsPage::Sections,
+        SettingsPage::Weather,
+        SettingsPage::Media,
+        SettingsPage::Alerts,
+        SettingsPage::Advanced,
+        SettingsPage::Position,
+        SettingsPage::Diagnostics,
+    ];
+
+    /// Title in the navigation bar and above the page.
+    pub fn label(&self) -> String {
+        match self {
+            SettingsPage::General => fl!("page-general"),
+            SettingsPage::Layout => fl!("page-layout"),
+            SettingsPage::Sections => fl!("page-sections"),
+            SettingsPage::Weather => fl!("page-weather"),
+            SettingsPage::Media => fl!("page-media"),
+            SettingsPage::Alerts => fl!("page-alerts"),
+            SettingsPage::Advanced => fl!("page-advanced"),
+            SettingsPage::Position => fl!("page-position"),
+            SettingsPage::Diagnostics => fl!("page-diagnostics"),
+            SettingsPage::Setup => fl!("setup-title"),
+        }
+    }
+
+    /// Symbolic icon in the navigation bar.
+    fn icon(&self) -> &'static str {
+        match self {
+            SettingsPage::General => "preferences-system-symbolic",
+            SettingsPage::Layout => "view-grid-symbolic",
+            SettingsPage::Sections => "view-list-symbolic",
+            SettingsPage::Weather => "weather-few-clouds-symbolic",
+            SettingsPage::Media => "audio-x-generic-symbolic",
+            SettingsPage::Alerts => "dialog-warning-symbolic",
+            SettingsPage::Advanced => "preferences-other-symbolic",
+            SettingsPage::Position => "view-fullscreen-symbolic",
+            SettingsPage::Diagnostics => "utilities-system-monitor-symbolic",
+            SettingsPage::Setup => "system-run-symbolic",
+        }
+    }
+}
+
 /// Main application state for the settings window.
 ///
 /// Holds the current configuration, text input states for editable fields,
@@ -130,20 +204,152 @@ pub struct SettingsApp {
     
     /// Update interval input (milliseconds)
     interval_input: String,
-    /// Widget X position input (pixels)
-    x_input: String,
-    /// Widget Y position input (pixels)
-    y_input: String,
     /// OpenWeatherMap API key input
     weather_api_key_input: String,
     /// Weather location input (city name or coordinates)
     weather_location_input: String,
     /// Maximum notifications count input
     max_notifications_input: String,
+    /// Text input for the notification history size
+    notification_history_count_input: String,
+    /// Text input for the notification history age limit
+    notification_history_days_input: String,
     /// Cider REST API token input
     cider_api_token_input: String,
+    /// Interval inputs of the custom commands (seconds), in config order
+    custom_interval_inputs: Vec<String>,
+    /// Font size inputs (points), in `TextRole::ALL` order
+    font_size_inputs: Vec<String>,
+    /// Font families Pango can draw with (for the "not installed" hint)
+    installed_fonts: Vec<String>,
+    /// Clock strftime pattern input
+    clock_format_input: String,
+    /// Date strftime pattern input
+    date_format_input: String,
+    /// Number of calendar events input
+    calendar_max_events_input: String,
+    /// Calendar refresh interval input (minutes)
+    calendar_refresh_input: String,
+    /// System health refresh interval input (minutes)
+    health_refresh_input: String,
+    /// Container refresh interval input (minutes)
+    containers_refresh_input: String,
+    /// Virtual machine refresh interval input (minutes)
+    vms_refresh_input: String,
+    /// Low space warning input (percent free)
+    storage_min_free_input: String,
+    /// Inode warning input (percent used)
+    storage_max_inode_input: String,
+    /// Drive health refresh interval input (minutes)
+    smart_refresh_input: String,
+    /// Storage pool refresh interval input (minutes)
+    storage_pools_refresh_input: String,
+    /// Feed refresh interval input (minutes)
+    feeds_refresh_input: String,
+    /// Most ticker symbols input
+    ticker_max_input: String,
+    /// Ticker refresh interval input (minutes)
+    ticker_refresh_input: String,
+    /// Drag grid size input (pixels)
+    snap_grid_input: String,
+    /// Title marquee speed input (pixels per second)
+    marquee_speed_input: String,
+    /// Title marquee pause input (milliseconds)
+    marquee_pause_input: String,
+    /// Raw network graph window input (validated before saving)
+    network_graph_input: String,
+    /// Raw temperature graph window input in minutes (validated before saving)
+    temp_graph_input: String,
+    /// Dropdown labels for `TempGraphMode::ALL`
+    temp_graph_mode_labels: Vec<&'static str>,
+    /// Warning and critical threshold inputs, in `ThresholdMetric::ALL` order
+    threshold_inputs: Vec<(String, String)>,
+    /// Bar color inputs (`#RRGGBB`), in `Level::ALL` order
+    bar_color_inputs: Vec<String>,
+    /// Metric history recording interval input (seconds)
+    record_interval_input: String,
+    /// Metric history retention input (days)
+    record_retention_input: String,
+    /// Layout bundle file path input
+    bundle_path_input: String,
+    /// Result of the last bundle import/export
+    bundle_status: Option<String>,
+    /// Name input for saving the current configuration as a profile
+    profile_name_input: String,
+    /// Profile file path input (full config import/export)
+    profile_path_input: String,
+    /// Names of the saved profiles
+    saved_profiles: Vec<String>,
+    /// Result of the last profile action
+    profile_status: Option<String>,
+    /// Current step of the setup wizard
+    setup_step: SetupStep,
+    /// Configuration being built by the setup wizard, written on Finish
+    setup_config: Config,
+    /// Install the XDG autostart entry when the wizard finishes
+    setup_install_autostart: bool,
+    /// Error from the last wizard finish, if any
+    setup_status: Option<String>,
+    /// Section picked in the layout list, moved on the next click
+    picked_section: Option<usize>,
+    /// Dropdown labels for `LayoutMode::ALL`
+    layout_mode_labels: Vec<&'static str>,
+    /// Dropdown labels for `TextContrast::ALL`
+    text_contrast_labels: Vec<&'static str>,
+    /// Dropdown labels for `AmbientSource::ALL`
+    ambient_source_labels: Vec<&'static str>,
+    /// Dropdown labels for `AmbientTarget::ALL`
+    ambient_target_labels: Vec<&'static str>,
+    /// Dropdown labels for `CalendarSource::ALL`
+    calendar_source_labels: Vec<&'static str>,
+    /// Dropdown labels for `FeedKind::ALL`
+    feed_kind_labels: Vec<&'static str>,
+    /// Dropdown labels for `TickerProvider::ALL`
+    ticker_provider_labels: Vec<&'static str>,
+    /// Dropdown labels for `AirQualityProvider::ALL`
+    air_quality_provider_labels: Vec<&'static str>,
+    /// Dropdown labels for `LogLevel::ALL`
+    log_level_labels: Vec<&'static str>,
+    /// Dropdown labels for `MemoryLabel::ALL`
+    memory_label_labels: Vec<&'static str>,
+    /// Dropdown labels for `ScrollAction::ALL`
+    scroll_action_labels: Vec<&'static str>,
+    /// Dropdown labels of every section, for the sections a schedule hides
+    schedule_section_labels: Vec<&'static str>,
+    /// Dropdown labels for `CARD_OPACITIES`
+    card_opacity_labels: Vec<String>,
     /// Cached battery devices from widget discovery
     cached_devices: Vec<CachedBatteryDevice>,
+    /// Connected outputs as last seen by the widget
+    cached_outputs: Vec<CachedOutput>,
+    
+    /// Currently displayed page
+    page: SettingsPage,
+    /// Pages in the navigation bar (all but the setup wizard)
+    nav: widget::nav_bar::Model,
+    /// Text in the header's search field (results replace the page while set)
+    search_query: String,
+    /// Last loaded widget status snapshot (diagnostics page)
+    widget_status: Option<WidgetStatus>,
+    /// Whether a widget process was found at the last diagnostics refresh
+    widget_running: bool,
+    /// Stored settings that couldn't be read or were out of range, shown
+    /// above the pages until dismissed
+    config_issues: Vec<String>,
+    
+    /// Desktop theme used for the preview (loaded once at startup)
+    theme: CosmicTheme,
+    /// Last rendered preview, `None` if rendering failed
+    preview: Option<Preview>,
+    /// Config the preview was rendered with (re-rendered when it differs)
+    preview_config: Config,
+    /// Rendered position picker mockup
+    placement_image: Option<cosmic::widget::image::Handle>,
+    /// Last pointer position over the position picker
+    placement_cursor: cosmic::iced::Point,
+    /// Where the widget proxy was grabbed (offset from its top-left corner)
+    /// while it is being dragged
+    placement_grab: Option<(f64, f64)>,
 }
 
 // ============================================================================
@@ -159,20 +365,56 @@ pub enum Message {
     // === Config sync ===
     /// Configuration changed externally - update our view
     UpdateConfig(Config),
+    /// Hide the notice about invalid stored settings
+    DismissConfigIssues,
     
     // === Utilization toggles ===
     /// Toggle CPU usage monitoring
     ToggleCpu(bool),
     /// Toggle Memory usage monitoring
     ToggleMemory(bool),
-    /// Toggle Network monitoring (not yet in reorderable sections)
+    /// Toggle the package frequencies under the CPU bar
+    ToggleCpuFrequency(bool),
+    /// Toggle the cpufreq governor under the CPU bar
+    ToggleCpuGovernor(bool),
+    /// Toggle switching the governor by clicking it
+    ToggleCpuGovernorClick(bool),
+    /// Toggle the CPU and GPU models under the Utilization header
+    ToggleHardwareInfo(bool),
+    /// Toggle Network monitoring
     ToggleNetwork(bool),
-    /// Toggle Disk I/O monitoring (not yet in reorderable sections)
+    /// Toggle network rates in bits per second
+    ToggleNetworkBits(bool),
+    /// Toggle the network history graph
+    ToggleNetworkGraph(bool),
+    /// Update the network graph window in seconds (validated)
+    UpdateNetworkGraphWindow(String),
+    /// Toggle Disk I/O monitoring
     ToggleDisk(bool),
     /// Toggle Storage space display
     ToggleStorage(bool),
+    /// Update the low space warning (text input, percent free)
+    UpdateStorageMinFree(String),
+    /// Update the inode warning (text input, percent used)
+    UpdateStorageMaxInode(String),
+    /// Toggle desktop notifications for low filesystems
+    ToggleStorageAlerts(bool),
+    /// Toggle drive SMART health under the storage section
+    ToggleSmart(bool),
+    /// Update the drive health refresh interval (text input, minutes)
+    UpdateSmartRefresh(String),
+    /// Toggle the ZFS/btrfs storage pools section
+    ToggleStoragePools(bool),
+    /// Update the storage pool refresh interval (text input, minutes)
+    UpdateStoragePoolsRefresh(String),
     /// Toggle GPU usage monitoring
     ToggleGpu(bool),
+    /// Toggle the GPU process list under the GPU bar
+    ToggleGpuProcesses(bool),
+    /// Show the GPU fan speed and clocks under the GPU bar
+    ToggleGpuDetails(bool),
+    /// Show the CPU/memory/I/O pressure row
+    TogglePsi(bool),
     
     // === Temperature toggles ===
     /// Toggle CPU temperature display
@@ -181,6 +423,25 @@ pub enum Message {
     ToggleGpuTemp(bool),
     /// Toggle between circular gauge and text temperature display
     ToggleCircularTempDisplay(bool),
+    ToggleTempsOnUsageBars(bool),
+    /// Toggle the hottest core row
+    ToggleHottestCore(bool),
+    /// Toggle the throttling badge
+    ToggleThrottling(bool),
+    /// Toggle the temperature history graph
+    ToggleTempGraph(bool),
+    /// Update the temperature graph window in minutes (validated)
+    UpdateTempGraphWindow(String),
+    /// Select where the temperature graph goes (index into `TempGraphMode::ALL`)
+    SetTempGraphMode(usize),
+    
+    // === Identity ===
+    /// Toggle the hostname at the top of the widget
+    ToggleHostname(bool),
+    /// Toggle the user name at the top of the widget
+    ToggleUsername(bool),
+    /// Update the label shown instead of the hostname and user name
+    UpdateIdentityLabel(String),
     
     // === Clock/Date toggles ===
     /// Toggle clock display
@@ -189,16 +450,70 @@ pub enum Message {
     ToggleDate(bool),
     /// Toggle between 24-hour and 12-hour time format
     Toggle24HourTime(bool),
+    /// Update the clock strftime pattern (saved when valid)
+    UpdateClockFormat(String),
+    /// Update the date strftime pattern (saved when valid)
+    UpdateDateFormat(String),
+    /// Add an empty world clock row
+    AddWorldClock,
+    /// Remove a world clock by index
+    RemoveWorldClock(usize),
+    /// Update the IANA zone of a world clock
+    UpdateWorldClockZone(usize, String),
+    /// Update the label of a world clock
+    UpdateWorldClockLabel(usize, String),
+    
+    // === Calendar settings ===
+    /// Toggle upcoming events under the date
+    ToggleCalendar(bool),
+    /// Select where events are read from (index into `CalendarSource::ALL`)
+    SetCalendarSource(usize),
+    /// Update the .ics file path
+    UpdateCalendarIcsPath(String),
+    /// Update the number of events shown (validated)
+    UpdateCalendarMaxEvents(String),
+    /// Update the refresh interval in minutes (validated)
+    UpdateCalendarRefresh(String),
     
     // === Display option toggles ===
     /// Toggle percentage values on utilization bars
     TogglePercentages(bool),
+    /// Select the memory bar's value (index into `MemoryLabel::ALL`)
+    SetMemoryLabel(usize),
+    /// Toggle detail tooltips on hovered metrics
+    ToggleTooltips(bool),
+    /// Toggle the CPU/RAM/temperature readout in the panel applet
+    TogglePanelReadout(bool),
+    /// Update a metric's warning threshold (validated)
+    UpdateWarningThreshold(ThresholdMetric, String),
+    /// Update a metric's critical threshold (validated)
+    UpdateCriticalThreshold(ThresholdMetric, String),
+    /// Update the bar color of a level (validated `#RRGGBB`)
+    UpdateBarColor(Level, String),
+    /// Toggle eased transitions and fades
+    ToggleAnimations(bool),
+    /// Select the layout mode (index into `LayoutMode::ALL`)
+    SetLayoutMode(usize),
+    /// Select the number of columns (index into `COLUMN_OPTIONS`)
+    SetColumns(usize),
+    /// Select light or dark text (index into `TextContrast::ALL`)
+    SetTextContrast(usize),
+    /// Update the font family of a text role (index into `TextRole::ALL`, text input)
+    UpdateFontFamily(usize, String),
+    /// Update the font size of a text role (index into `TextRole::ALL`, text input, points)
+    UpdateFontSize(usize, String),
+    /// Select what the ambient tint follows (index into `AmbientSource::ALL`)
+    SetAmbientSource(usize),
+    /// Select what the ambient tint colors (index into `AmbientTarget::ALL`)
+    SetAmbientTarget(usize),
     
     // === Battery toggles ===
     /// Toggle battery section visibility
     ToggleBatterySection(bool),
     /// Toggle Solaar integration for Logitech device batteries
     ToggleSolaarIntegration(bool),
+    /// Show the power profile button in the Battery header
+    TogglePowerProfile(bool),
     /// Remove a cached battery device by index
     RemoveCachedDevice(usize),
     
@@ -207,24 +522,155 @@ pub enum Message {
     ToggleNotifications(bool),
     /// Update max notifications count (text input)
     UpdateMaxNotifications(String),
+    /// Toggle showing notification bodies only on hover
+    ToggleCompactNotifications(bool),
+    /// Toggle keeping notifications across restarts
+    ToggleNotificationHistory(bool),
+    /// Update notification history size (text input)
+    UpdateNotificationHistoryCount(String),
+    /// Update notification history age limit (text input, days)
+    UpdateNotificationHistoryDays(String),
+    /// Delete the notification history and clear the widget's list
+    ClearNotificationHistory,
+    
+    // === Scroll bindings ===
+    /// Select what scrolling over the clock, media, audio or notifications
+    /// section does (index into `ScrollAction::ALL`)
+    SetScrollAction(WidgetSection, usize),
     
     // === Media player settings ===
     /// Toggle media player section
     ToggleMedia(bool),
     /// Update Cider API token (text input)
     UpdateCiderApiToken(String),
+    /// Toggle scrolling of long track titles
+    ToggleMediaMarquee(bool),
+    /// Update title marquee speed (text input, pixels per second)
+    UpdateMarqueeSpeed(String),
+    /// Update title marquee pause (text input, milliseconds)
+    UpdateMarqueePause(String),
+    /// Toggle the lyrics line under the track info
+    ToggleLyrics(bool),
+    
+    // === Audio output ===
+    /// Toggle audio output section
+    ToggleAudio(bool),
+    
+    // === Custom commands ===
+    /// Toggle custom commands section
+    ToggleCustomCommands(bool),
+    /// Append an empty command
+    AddCustomCommand,
+    /// Remove the command at an index
+    RemoveCustomCommand(usize),
+    /// Update a command's label (index, text)
+    UpdateCustomLabel(usize, String),
+    /// Update a command's shell command (index, text)
+    UpdateCustomCommand(usize, String),
+    /// Update a command's interval in seconds (index, text input)
+    UpdateCustomInterval(usize, String),
+    /// Update a command's row format (index, text)
+    UpdateCustomFormat(usize, String),
+    /// Toggle the system health section
+    ToggleSystemHealth(bool),
+    /// Update the pending updates command (text input)
+    UpdateHealthUpdateCommand(String),
+    /// Update the system health refresh interval (text input, minutes)
+    UpdateHealthRefresh(String),
+    /// Toggle the containers section
+    ToggleContainers(bool),
+    /// Toggle the busiest container row
+    ToggleContainersBusiest(bool),
+    /// Update the container refresh interval (text input, minutes)
+    UpdateContainersRefresh(String),
+    /// Toggle the virtual machines section
+    ToggleVms(bool),
+    /// Update the libvirt connection URI (text input)
+    UpdateVmsUri(String),
+    /// Update the virtual machine refresh interval (text input, minutes)
+    UpdateVmsRefresh(String),
+    /// Toggle the kernel statistics row of the System section
+    ToggleKernelStats(bool),
+    // === Mail & feeds ===
+    /// Toggle the mail & feeds section
+    ToggleFeeds(bool),
+    /// Append an empty feed
+    AddFeed,
+    /// Remove the feed at an index
+    RemoveFeed(usize),
+    /// Set a feed's kind (index, index into `FeedKind::ALL`)
+    SetFeedKind(usize, usize),
+    /// Update a feed's label (index, text)
+    UpdateFeedLabel(usize, String),
+    /// Update a feed's URL (index, text)
+    UpdateFeedUrl(usize, String),
+    /// Update the feed refresh interval (text input, minutes)
+    UpdateFeedsRefresh(String),
+    // === Ticker ===
+    /// Toggle the ticker section
+    ToggleTicker(bool),
+    /// Select the quote provider (index into `TickerProvider::ALL`)
+    SetTickerProvider(usize),
+    /// Update the comma-separated symbols
+    UpdateTickerSymbols(String),
+    /// Update the most symbols shown (text input)
+    UpdateTickerMaxSymbols(String),
+    /// Update the quote currency
+    UpdateTickerCurrency(String),
+    /// Update the JSON endpoint URL
+    UpdateTickerEndpoint(String),
+    /// Update the JSON pointer to the price
+    UpdateTickerPricePath(String),
+    /// Update the JSON pointer to the 24h change
+    UpdateTickerChangePath(String),
+    /// Update the ticker refresh interval (text input, minutes)
+    UpdateTickerRefresh(String),
+    /// Toggle the Lua plugins section
+    TogglePlugins(bool),
+    /// Toggle the caffeine section
+    ToggleCaffeine(bool),
+    /// Toggle switching caffeine on at startup
+    ToggleCaffeineOnStart(bool),
     
     // === Interval and position ===
     /// Update polling interval (text input)
     UpdateInterval(String),
-    /// Update widget X position (text input)
-    UpdateX(String),
-    /// Update widget Y position (text input)
-    UpdateY(String),
+    /// Pointer pressed on the position picker (starts dragging the widget)
+    PlacementPressed,
+    /// Pointer released on or left the position picker
+    PlacementReleased,
+    /// Pointer moved over the position picker
+    PlacementMoved(cosmic::iced::Point),
+    /// Move the widget back to its default position
+    ResetPosition,
+    /// Add a schedule with default times
+    AddSchedule,
+    /// Remove the schedule at an index
+    RemoveSchedule(usize),
+    /// Update a schedule's name
+    UpdateScheduleLabel(usize, String),
+    /// Update a schedule's start time ("HH:MM")
+    UpdateScheduleStart(usize, String),
+    /// Update a schedule's end time ("HH:MM")
+    UpdateScheduleEnd(usize, String),
+    /// Set a schedule's opacity (index into `SCHEDULE_OPACITIES`)
+    SetScheduleOpacity(usize, usize),
+    /// Hide or show a section during a schedule (index into the section list)
+    ToggleScheduleSection(usize, usize),
     
     // === Weather settings ===
     /// Toggle weather display
     ToggleWeather(bool),
+    /// Toggle detailed weather layout (feels-like, humidity, wind, min/max)
+    ToggleWeatherDetailed(bool),
+    /// Toggle the sunrise/sunset row
+    ToggleWeatherSunTimes(bool),
+    /// Toggle the moon phase at night
+    ToggleWeatherMoonPhase(bool),
+    /// Toggle the air quality badge
+    ToggleAirQuality(bool),
+    /// Select where the air quality index comes from (index into `AirQualityProvider::ALL`)
+    SetAirQualityProvider(usize),
     /// Update OpenWeatherMap API key (text input)
     UpdateWeatherApiKey(String),
     /// Update weather location (text input)
@@ -233,14 +679,116 @@ pub enum Message {
     // === Widget behavior ===
     /// Toggle auto-start widget when panel loads
     ToggleWidgetAutostart(bool),
-    /// Toggle debug logging to file
+    /// Toggle pointer pass-through to windows underneath the widget
+    ToggleClickThrough(bool),
+    /// Toggle moving the widget with the arrow keys
+    ToggleKeyboardPositioning(bool),
+    /// Toggle snapping to the screen edges and center lines while dragging
+    ToggleSnapToEdges(bool),
+    /// Update the drag grid size in pixels (validated)
+    UpdateSnapGrid(String),
+    /// Toggle hiding the widget while a fullscreen window covers it
+    ToggleAutoHideFullscreen(bool),
+    /// Toggle hiding the widget while a maximized window covers it
+    ToggleAutoHideMaximized(bool),
+    /// Toggle writing logs to rotating files
     ToggleLogging(bool),
+    /// Select how much to log (index into `LogLevel::ALL`)
+    SetLogLevel(usize),
+    /// Toggle the debug overlay in the widget's corner
+    ToggleDebugOverlay(bool),
+    /// Toggle publishing stats JSON for other tools
+    TogglePublishStats(bool),
+    /// Toggle writing rendered frames to PNG for OBS
+    ToggleExportFrames(bool),
+    /// Toggle recording metric history to CSV
+    ToggleRecordMetrics(bool),
+    /// Update history recording interval (text input)
+    UpdateRecordInterval(String),
+    /// Update history retention in days (text input)
+    UpdateRecordRetention(String),
+    /// Toggle low priority for background collectors
+    ToggleLowPriorityCollectors(bool),
+    /// Toggle the idle scheduling classes for background collectors
+    ToggleIdlePriorityCollectors(bool),
+    /// Toggle pinning background collectors to efficiency cores
+    TogglePinEfficiencyCores(bool),
+    /// Toggle the daily check for a newer release
+    ToggleUpdateCheck(bool),
     
     // === Section reordering ===
     /// Move a section up in the order list
     MoveSectionUp(usize),
     /// Move a section down in the order list
     MoveSectionDown(usize),
+    /// Pick a section, or move the picked section to this position
+    PickSection(usize),
+    /// Set the card behind a section (index into the order list, index into `CARD_OPACITIES`)
+    SetSectionCard(usize, usize),
+    
+    // === Layout bundles ===
+    /// Update bundle file path (text input)
+    UpdateBundlePath(String),
+    /// Write the current layout to the bundle file
+    ExportBundle,
+    /// Apply the layout from the bundle file
+    ImportBundle,
+    
+    // === Profiles ===
+    /// Apply a built-in preset
+    ApplyPreset(Preset),
+    /// Update profile name (text input)
+    UpdateProfileName(String),
+    /// Save the current configuration under the entered name
+    SaveProfile,
+    /// Replace the configuration with a saved profile
+    SwitchProfile(String),
+    /// Delete a saved profile
+    DeleteProfile(String),
+    /// Update profile file path (text input)
+    UpdateProfilePath(String),
+    /// Write the full configuration to the profile file
+    ExportProfile,
+    /// Replace the configuration with the profile file
+    ImportProfile,
+    
+    // === Setup wizard ===
+    /// Open the setup wizard, starting from the current configuration
+    StartSetup,
+    /// Go to the previous wizard step
+    SetupBack,
+    /// Go to the next wizard step
+    SetupNext,
+    /// Pick the output (empty for any)
+    SetupOutput(String),
+    /// Pick the screen corner
+    SetupAnchor(WidgetAnchor),
+    /// Turn a section on or off
+    SetupToggle(SetupToggle, bool),
+    /// Update the weather API key
+    SetupWeatherApiKey(String),
+    /// Update the weather location
+    SetupWeatherLocation(String),
+    /// Toggle starting the widget with the panel applet
+    SetupWidgetAutostart(bool),
+    /// Toggle installing the login autostart entry
+    SetupInstallAutostart(bool),
+    /// Write the configuration and leave the wizard
+    SetupFinish,
+    /// Leave the wizard without writing anything
+    SetupCancel,
+    
+    // === Navigation ===
+    /// Switch to another settings page
+    ShowPage(SettingsPage),
+    /// Search field text changed
+    UpdateSearch(String),
+    /// Search field cleared
+    ClearSearch,
+    /// Open the page of a search result
+    OpenSearchResult(SettingsPage),
+    /// Reload the widget status snapshot on the diagnostics page
+    RefreshDiagnostics,
     
     // === Actions ===
     /// Save config and restart the widget
@@ -261,197 +809,193 @@ impl SettingsApp {
     fn save_config(&self) {
         if let Some(ref config_handler) = self.config_handler {
             if let Err(err) = self.config.write_entry(config_handler) {
-                eprintln!("Failed to save config: {}", err);
+                log::error!("Failed to save config: {}", err);
             }
         }
     }
-}
-
-// ============================================================================
-// COSMIC Application Implementation
-// ============================================================================
-
-impl Application for SettingsApp {
-    type Executor = cosmic::executor::Default;
-    type Flags = ();
-    type Message = Message;
-
-    /// Settings app ID - distinct from the main applet to allow separate windows.
-    const APP_ID: &'static str = "com.github.zoliviragh.CosmicMonitor.Settings";
-
-    fn core(&self) -> &cosmic::app::Core {
-        &self.core
+    
+    /// Replace the whole configuration (profile switch or import) and save it.
+    ///
+    /// Movable mode stays on while the settings window is open, and the
+    /// text inputs are reset to the new values.
+    fn replace_config(&mut self, mut config: Config) {
+        config.widget_movable = true;
+        self.config_issues = config.validate();
+        self.config = config;
+        self.interval_input = self.config.update_interval_ms.to_string();
+        self.weather_api_key_input = self.config.weather_api_key.clone();
+        self.weather_location_input = self.config.weather_location.clone();
+        self.max_notifications_input = self.config.max_notifications.to_string();
+        self.notification_history_count_input = self.config.notification_history_count.to_string();
+        self.notification_history_days_input = self.config.notification_history_days.to_string();
+        self.cider_api_token_input = self.config.cider_api_token.clone();
+        self.custom_interval_inputs = self
+            .config
+            .custom_commands
+            .iter()
+            .map(|c| c.interval_secs.to_string())
+            .collect();
+        self.font_size_inputs = font_size_inputs(&self.config);
+        self.clock_format_input = self.config.clock_format.clone();
+        self.date_format_input = self.config.date_format.clone();
+        self.calendar_max_events_input = self.config.calendar_max_events.to_string();
+        self.calendar_refresh_input = self.config.calendar_refresh_minutes.to_string();
+        self.health_refresh_input = self.config.health_refresh_minutes.to_string();
+        self.containers_refresh_input = self.config.containers_refresh_minutes.to_string();
+        self.vms_refresh_input = self.config.vms_refresh_minutes.to_string();
+        self.storage_min_free_input = self.config.storage_min_free_percent.to_string();
+        self.storage_max_inode_input = self.config.storage_max_inode_percent.to_string();
+        self.smart_refresh_input = self.config.smart_refresh_minutes.to_string();
+        self.storage_pools_refresh_input = self.config.storage_pools_refresh_minutes.to_string();
+        self.feeds_refresh_input = self.config.feeds_refresh_minutes.to_string();
+        self.ticker_max_input = self.config.ticker_max_symbols.to_string();
+        self.ticker_refresh_input = self.config.ticker_refresh_minutes.to_string();
+        self.snap_grid_input = self.config.snap_grid.to_string();
+        self.marquee_speed_input = self.config.marquee_speed.to_string();
+        self.marquee_pause_input = self.config.marquee_pause_ms.to_string();
+        self.network_graph_input = self.config.network_graph_seconds.to_string();
+        self.temp_graph_input = self.config.temp_graph_minutes.to_string();
+        self.threshold_inputs = ThresholdMetric::ALL
+            .iter()
+            .map(|metric| {
+                let thresholds = self.config.thresholds.get(*metric);
+                (thresholds.warning.to_string(), thresholds.critical.to_string())
+            })
+            .collect();
+        self.bar_color_inputs = Level::ALL.iter().map(|level| self.config.bar_colors.get(*level).to_string()).collect();
+        self.record_interval_input = self.config.record_interval_secs.to_string();
+        self.record_retention_input = self.config.record_retention_days.to_string();
+        self.picked_section = None;
+        self.save_config();
     }
-
-    fn core_mut(&mut self) -> &mut cosmic::app::Core {
-        &mut self.core
+    
+    /// Dropdown of what scrolling over `section` does.
+    fn scroll_action_item(&self, section: WidgetSection, action: ScrollAction) -> Element<'_, Message> {
+        widget::settings::item(
+            fl!("scroll-action"),
+            widget::dropdown(
+                &self.scroll_action_labels,
+                ScrollAction::ALL.iter().position(|a| *a == action),
+                move |index| Message::SetScrollAction(section, index),
+            ),
+        )
+        .into()
     }
-
-    /// Handle window close - disable widget movement mode before closing.
-    fn on_close_requested(&self, _id: cosmic::iced::window::Id) -> Option<Message> {
-        Some(Message::CloseRequested)
+    
+    /// Highlight `page` in the navigation bar, for page switches that
+    /// didn't come from it (e.g., the setup wizard finishing).
+    fn activate_nav(&mut self, page: SettingsPage) {
+        let entity = self.nav.iter().find(|id| self.nav.data::<SettingsPage>(*id) == Some(&page));
+        if let Some(id) = entity {
+            self.nav.activate(id);
+        }
     }
-
-    /// Initialize the settings application.
-    ///
-    /// - Loads current configuration
-    /// - Migrates old configs (adds new sections if missing)
-    /// - Enables widget movement mode
-    /// - Loads cached device information
-    fn init(
-        core: cosmic::app::Core,
-        _flags: Self::Flags,
-    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
-        // Load config from the main app's config path (not the settings app path)
-        let config_handler = cosmic_config::Config::new(
-            "com.github.zoliviragh.CosmicMonitor",
-            Config::VERSION,
-        )
-        .ok();
-
-        let mut config = config_handler
-            .as_ref()
-            .map(|context| match Config::get_entry(context) {
-                Ok(config) => config,
-                Err((_errors, config)) => config,
-            })
-            .unwrap_or_default();
-
-        // === Config Migration ===
-        // When new sections are added to the app, existing configs won't have them.
-        // This ensures users don't lose access to new features.
+    
+    /// Whether search results are shown instead of the current page.
+    fn is_searching(&self) -> bool {
+        self.page != SettingsPage::Setup && !self.search_query.trim().is_empty()
+    }
+    
+    /// Options matching the search field, each with a button to its page.
+    fn view_search(&self) -> Element<'_, Message> {
+        let results = search::search(&self.search_query);
+        let mut content = widget::column()
+            .spacing(12)
+            .padding(24)
+            .push(widget::text::title1(fl!("search-results")))
+            .push(widget::divider::horizontal::default());
         
-        // Add Battery section if missing (added in v1.x)
-        if !config.section_order.iter().any(|s| matches!(s, WidgetSection::Battery)) {
-            if let Some(storage_pos) = config.section_order.iter().position(|s| matches!(s, WidgetSection::Storage)) {
-                config.section_order.insert(storage_pos + 1, WidgetSection::Battery);
-            } else if let Some(weather_pos) = config.section_order.iter().position(|s| matches!(s, WidgetSection::Weather)) {
-                config.section_order.insert(weather_pos, WidgetSection::Battery);
-            } else {
-                config.section_order.push(WidgetSection::Battery);
-            }
+        if results.is_empty() {
+            content = content.push(widget::text::body(fl!("search-no-results", query = self.search_query.trim())));
         }
-
-        // Add Notifications section if missing
-        if !config.section_order.iter().any(|s| matches!(s, WidgetSection::Notifications)) {
-            config.section_order.push(WidgetSection::Notifications);
+        for entry in results {
+            content = content.push(widget::settings::item(
+                entry.label,
+                widget::button::text(format!("{} › {}", entry.page.label(), entry.section))
+                    .on_press(Message::OpenSearchResult(entry.page)),
+            ));
         }
+        
+        let scrollable_content = widget::scrollable(content).width(cosmic::iced::Length::Fill);
+        let with_preview = widget::row()
+            .push(scrollable_content)
+            .push(self.preview_pane());
 
-        // Add Media section if missing
-        if !config.section_order.iter().any(|s| matches!(s, WidgetSection::Media)) {
-            config.section_order.push(WidgetSection::Media);
+        widget::container(with_preview)
+            .width(cosmic::iced::Length::Fill)
+            .height(cosmic::iced::Length::Fill)
+            .into()
+    }
+    
+    /// Stored settings that were replaced on load, with a button to hide
+    /// the list (empty when there are none).
+    fn config_issues_notice(&self) -> widget::Column<'_, Message> {
+        let mut notice = widget::column().spacing(4);
+        if self.config_issues.is_empty() {
+            return notice;
         }
-
-        // Enable widget movement while settings window is open
-        // This allows users to drag the widget to reposition it
-        config.widget_movable = true;
-        if let Some(ref handler) = config_handler {
-            let _ = config.write_entry(handler);
+        notice = notice.push(widget::text::heading(fl!("config-issues")));
+        for issue in &self.config_issues {
+            notice = notice.push(widget::text::caption(issue.as_str()));
         }
-
-        // Initialize text inputs from current config values
-        let interval_input = format!("{}", config.update_interval_ms);
-        let x_input = format!("{}", config.widget_x);
-        let y_input = format!("{}", config.widget_y);
-        let weather_api_key_input = config.weather_api_key.clone();
-        let weather_location_input = config.weather_location.clone();
-        let max_notifications_input = config.max_notifications.to_string();
-        let cider_api_token_input = config.cider_api_token.clone();
-        
-        // Load cached battery devices from widget's cache file
-        let cache = WidgetCache::load();
-        let cached_devices = cache.battery_devices.clone();
-
-        let app = SettingsApp {
-            core,
-            config,
-            config_handler,
-            interval_input,
-            x_input,
-            y_input,
-            weather_api_key_input,
-            weather_location_input,
-            max_notifications_input,
-            cider_api_token_input,
-            cached_devices,
-        };
-
-        (app, Task::none())
+        notice.push(widget::button::text(fl!("config-issues-dismiss")).on_press(Message::DismissConfigIssues))
     }
-
-    /// Render the settings UI.
-    ///
-    /// The UI is organized into sections matching the widget's features:
-    /// - Monitoring Options (CPU, Memory, GPU, Network, Disk)
-    /// - Storage Display
-    /// - Temperature Display
-    /// - Widget Display (Clock, Date, Time format)
-    /// - Display Options (Percentages)
-    /// - Battery (including Solaar and cached devices)
-    /// - Weather
-    /// - Notifications
-    /// - Media Player
-    /// - Layout Order (drag-to-reorder sections)
-    /// - Widget Position
-    /// - Advanced (logging)
-    fn view(&self) -> Element<Self::Message> {
-        let mut content = widget::column()
+    
+    /// Frame a configuration page: its title above, the Save & Apply button
+    /// below, scrollable for smaller screens, with the live preview beside it.
+    fn config_page<'a>(&'a self, content: widget::Column<'a, Message>) -> Element<'a, Message> {
+        let content = widget::column()
             .spacing(12)
             .padding(24)
-            // === Header ===
-            .push(widget::text::title1(fl!("app-title")))
-            .push(widget::divider::horizontal::default())
-            
-            // === Monitoring Options Section ===
-            .push(widget::text::heading(fl!("monitoring-options")))
-            .push(widget::settings::item(
-                fl!("show-cpu"),
-                widget::toggler(self.config.show_cpu).on_toggle(Message::ToggleCpu),
-            ))
-            .push(widget::settings::item(
-                fl!("show-memory"),
-                widget::toggler(self.config.show_memory).on_toggle(Message::ToggleMemory),
-            ))
-            .push(widget::settings::item(
-                fl!("show-gpu"),
-                widget::toggler(self.config.show_gpu).on_toggle(Message::ToggleGpu),
-            ))
-            .push(widget::settings::item(
-                fl!("show-network"),
-                widget::toggler(self.config.show_network).on_toggle(Message::ToggleNetwork),
-            ))
-            .push(widget::settings::item(
-                fl!("show-disk"),
-                widget::toggler(self.config.show_disk).on_toggle(Message::ToggleDisk),
-            ))
+            .push(widget::text::title1(self.page.label()))
             .push(widget::divider::horizontal::default())
+            .push(self.config_issues_notice())
+            .push(content)
             
-            // === Storage Display Section ===
-            .push(widget::text::heading(fl!("storage-display")))
-            .push(widget::settings::item(
-                fl!("show-storage"),
-                widget::toggler(self.config.show_storage).on_toggle(Message::ToggleStorage),
-            ))
-            .push(widget::divider::horizontal::default())
+            // === Save & Apply Button ===
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .push(widget::column().width(cosmic::iced::Length::Fill))
+                    .push(
+                        widget::button::suggested(fl!("save-and-apply"))
+                            .on_press(Message::SaveAndApply)
+                    )
+                    .push(widget::column().width(cosmic::iced::Length::Fill))
+            );
+        
+        let scrollable_content = widget::scrollable(content).width(cosmic::iced::Length::Fill);
+        let with_preview = widget::row()
+            .push(scrollable_content)
+            .push(self.preview_pane());
+
+        widget::container(with_preview)
+            .width(cosmic::iced::Length::Fill)
+            .height(cosmic::iced::Length::Fill)
+            .into()
+    }
+    
+    /// General page: hostname line, clock, date, world clocks, calendar, and profiles.
+    fn view_general(&self) -> widget::Column<'_, Message> {
+        let mut content = widget::column()
+            .spacing(12)
             
-            // === Temperature Display Section ===
-            .push(widget::text::heading(fl!("temperature-display")))
+            // === Widget Display Section (Clock/Date) ===
+            .push(widget::text::heading(fl!("widget-display")))
             .push(widget::settings::item(
-                fl!("show-cpu-temp"),
-                widget::toggler(self.config.show_cpu_temp).on_toggle(Message::ToggleCpuTemp),
+                fl!("show-hostname"),
+                widget::toggler(self.config.show_hostname).on_toggle(Message::ToggleHostname),
             ))
             .push(widget::settings::item(
-                fl!("show-gpu-temp"),
-                widget::toggler(self.config.show_gpu_temp).on_toggle(Message::ToggleGpuTemp),
+                fl!("show-username"),
+                widget::toggler(self.config.show_username).on_toggle(Message::ToggleUsername),
             ))
             .push(widget::settings::item(
-                fl!("use-circular-temp-display"),
-                widget::toggler(self.config.use_circular_temp_display).on_toggle(Message::ToggleCircularTempDisplay),
+                fl!("identity-label"),
+                widget::text_input(fl!("identity-label-automatic"), &self.config.identity_label)
+                    .on_input(Message::UpdateIdentityLabel),
             ))
-            .push(widget::divider::horizontal::default())
-            
-            // === Widget Display Section (Clock/Date) ===
-            .push(widget::text::heading(fl!("widget-display")))
+            .push(widget::text::caption(fl!("identity-label-description")))
             .push(widget::settings::item(
                 fl!("show-clock"),
                 widget::toggler(self.config.show_clock).on_toggle(Message::ToggleClock),
@@ -464,113 +1008,246 @@ impl Application for SettingsApp {
                 fl!("use-24hour-time"),
                 widget::toggler(self.config.use_24hour_time).on_toggle(Message::Toggle24HourTime),
             ))
-            .push(widget::divider::horizontal::default())
-            
-            // === Display Options Section ===
-            .push(widget::text::heading(fl!("display-options")))
+            .push(widget::text::body(fl!("time-format-description")))
             .push(widget::settings::item(
-                fl!("show-percentages"),
-                widget::toggler(self.config.show_percentages).on_toggle(Message::TogglePercentages),
+                fl!("clock-format"),
+                widget::text_input(fl!("clock-format-automatic"), &self.clock_format_input)
+                    .on_input(Message::UpdateClockFormat),
             ))
-            .push(widget::divider::horizontal::default())
-            
-            // === Battery Section ===
-            .push(widget::text::heading("Battery"))
+            .push(widget::text::caption(format_example(&self.clock_format_input, if self.config.use_24hour_time {
+                "%H:%M:%S"
+            } else {
+                "%-I:%M:%S %p"
+            })))
             .push(widget::settings::item(
-                "Show battery section",
-                widget::toggler(self.config.show_battery)
-                    .on_toggle(Message::ToggleBatterySection),
+                fl!("date-format"),
+                widget::text_input(DEFAULT_DATE_FORMAT, &self.date_format_input)
+                    .on_input(Message::UpdateDateFormat),
             ))
-            .push(widget::settings::item(
-                "Enable Solaar integration",
-                widget::toggler(self.config.enable_solaar_integration)
-                    .on_toggle(Message::ToggleSolaarIntegration),
-            ));
+            .push(widget::text::caption(format_example(&self.date_format_input, DEFAULT_DATE_FORMAT)))
+            .push(widget::text::body(fl!("world-clocks-description")));
         
-        // Display cached battery devices with remove buttons
-        if !self.cached_devices.is_empty() {
-            content = content.push(widget::text::body("Cached Devices:"));
-            
-            for (index, device) in self.cached_devices.iter().enumerate() {
-                let device_kind = device.kind.as_deref().unwrap_or("device");
-                let device_label = format!("{} ({})", device.name, device_kind);
-                
-                content = content.push(
-                    widget::row()
-                        .spacing(8)
-                        .padding([4, 16])
-                        .push(widget::text::body(device_label))
-                        .push(widget::horizontal_space())
-                        .push(
-                            widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
-                                .on_press(Message::RemoveCachedDevice(index))
-                                .padding(4)
-                        )
+        // One row of inputs per extra time zone
+        for (index, clock) in self.config.world_clocks.iter().enumerate() {
+            let zone = clock.zone.trim();
+            let mut row = widget::row()
+                .spacing(8)
+                .padding([4, 0])
+                .push(
+                    widget::text_input(fl!("world-clock-zone"), &clock.zone)
+                        .on_input(move |value| Message::UpdateWorldClockZone(index, value))
+                        .width(cosmic::iced::Length::FillPortion(3)),
+                )
+                .push(
+                    widget::text_input(fl!("world-clock-label"), &clock.label)
+                        .on_input(move |value| Message::UpdateWorldClockLabel(index, value))
+                        .width(cosmic::iced::Length::FillPortion(2)),
                 );
+            if !zone.is_empty() && zone.parse::<chrono_tz::Tz>().is_err() {
+                row = row.push(widget::text::caption(fl!("world-clock-unknown")));
             }
+            content = content.push(
+                row.push(
+                    widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                        .on_press(Message::RemoveWorldClock(index))
+                        .padding(4),
+                ),
+            );
         }
         
         content = content
-            // === Update Interval ===
-            .push(widget::settings::item(
-                fl!("update-interval"),
-                widget::text_input("", &self.interval_input).on_input(Message::UpdateInterval),
-            ))
+            .push(
+                widget::row().push(
+                    widget::button::standard(fl!("world-clock-add")).on_press(Message::AddWorldClock),
+                ),
+            )
+            .push(self.scroll_action_item(WidgetSection::Clock, self.config.clock_scroll))
             .push(widget::divider::horizontal::default())
             
-            // === Weather Display Section ===
-            .push(widget::text::heading(fl!("weather-display")))
+            // === Calendar Section ===
+            .push(widget::text::heading(fl!("calendar")))
+            .push(widget::text::body(fl!("calendar-description")))
             .push(widget::settings::item(
-                fl!("show-weather"),
-                widget::toggler(self.config.show_weather)
-                    .on_toggle(Message::ToggleWeather),
+                fl!("show-calendar"),
+                widget::toggler(self.config.show_calendar).on_toggle(Message::ToggleCalendar),
             ))
             .push(widget::settings::item(
-                fl!("weather-api-key"),
-                widget::text_input("", &self.weather_api_key_input)
-                    .on_input(Message::UpdateWeatherApiKey),
-            ))
+                fl!("calendar-source"),
+                widget::dropdown(
+                    &self.calendar_source_labels,
+                    CalendarSource::ALL.iter().position(|source| *source == self.config.calendar_source),
+                    Message::SetCalendarSource,
+                ),
+            ));
+        
+        if self.config.calendar_source == CalendarSource::IcsFile {
+            content = content.push(widget::settings::item(
+                fl!("calendar-ics-path"),
+                widget::text_input("~/calendar.ics", &self.config.calendar_ics_path)
+                    .on_input(Message::UpdateCalendarIcsPath),
+            ));
+        }
+        
+        content = content
             .push(widget::settings::item(
-                fl!("weather-location"),
-                widget::text_input("", &self.weather_location_input)
-                    .on_input(Message::UpdateWeatherLocation),
+                fl!("calendar-max-events"),
+                widget::text_input("", &self.calendar_max_events_input).on_input(Message::UpdateCalendarMaxEvents),
             ))
-            .push(widget::divider::horizontal::default())
-            
-            // === Notifications Section ===
-            .push(widget::text::heading("Notifications"))
             .push(widget::settings::item(
-                "Show Notifications",
-                widget::toggler(self.config.show_notifications)
-                    .on_toggle(Message::ToggleNotifications),
+                fl!("calendar-refresh"),
+                widget::text_input("", &self.calendar_refresh_input).on_input(Message::UpdateCalendarRefresh),
             ))
+            .push(widget::divider::horizontal::default());
+        
+        // === Profiles Section ===
+        let mut presets = widget::row().spacing(8);
+        for preset in Preset::ALL {
+            presets = presets.push(widget::button::standard(preset.label()).on_press(Message::ApplyPreset(preset)));
+        }
+        content = content
+            .push(widget::text::heading(fl!("profiles")))
+            .push(widget::text::body(fl!("profiles-description")))
+            .push(widget::settings::item(fl!("profiles-presets"), presets))
             .push(widget::settings::item(
-                "Max Notifications",
-                widget::text_input("", &self.max_notifications_input)
-                    .on_input(Message::UpdateMaxNotifications),
+                fl!("profiles-save-as"),
+                widget::row()
+                    .spacing(8)
+                    .push(
+                        widget::text_input(fl!("profiles-name-placeholder"), &self.profile_name_input)
+                            .on_input(Message::UpdateProfileName),
+                    )
+                    .push(widget::button::standard(fl!("profiles-save")).on_press(Message::SaveProfile)),
+            ));
+        
+        for name in &self.saved_profiles {
+            content = content.push(widget::settings::item(
+                name.clone(),
+                widget::row()
+                    .spacing(8)
+                    .push(widget::button::standard(fl!("profiles-switch")).on_press(Message::SwitchProfile(name.clone())))
+                    .push(widget::button::destructive(fl!("profiles-delete")).on_press(Message::DeleteProfile(name.clone()))),
+            ));
+        }
+        
+        content = content
+            .push(widget::settings::item(
+                fl!("profiles-file"),
+                widget::text_input("", &self.profile_path_input).on_input(Message::UpdateProfilePath),
             ))
-            .push(widget::divider::horizontal::default())
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .push(widget::button::standard(fl!("profiles-export")).on_press(Message::ExportProfile))
+                    .push(widget::button::standard(fl!("profiles-import")).on_press(Message::ImportProfile))
+            );
+        
+        if let Some(status) = &self.profile_status {
+            content = content.push(widget::text::body(status.clone()));
+        }
+        
+        content
+    }
+    
+    /// Layout page: display style, section order, and layout bundles.
+    fn view_layout(&self) -> widget::Column<'_, Message> {
+        let mut content = widget::column()
+            .spacing(12)
             
-            // === Media Player Section ===
-            .push(widget::text::heading("Media Player"))
+            // === Display Options Section ===
+            .push(widget::text::heading(fl!("display-options")))
             .push(widget::settings::item(
-                "Show Media Player",
-                widget::toggler(self.config.show_media)
-                    .on_toggle(Message::ToggleMedia),
+                fl!("show-percentages"),
+                widget::toggler(self.config.show_percentages).on_toggle(Message::TogglePercentages),
             ))
             .push(widget::settings::item(
-                "Cider API Token",
-                widget::text_input("Leave empty if auth disabled", &self.cider_api_token_input)
-                    .on_input(Message::UpdateCiderApiToken),
+                fl!("memory-label"),
+                widget::dropdown(
+                    &self.memory_label_labels,
+                    MemoryLabel::ALL.iter().position(|label| *label == self.config.memory_label),
+                    Message::SetMemoryLabel,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("show-tooltips"),
+                widget::toggler(self.config.show_tooltips).on_toggle(Message::ToggleTooltips),
+            ))
+            .push(widget::settings::item(
+                fl!("show-panel-readout"),
+                widget::toggler(self.config.show_panel_readout).on_toggle(Message::TogglePanelReadout),
+            ))
+            .push(widget::settings::item(
+                fl!("enable-animations"),
+                widget::toggler(self.config.enable_animations).on_toggle(Message::ToggleAnimations),
+            ))
+            .push(widget::settings::item(
+                fl!("layout-mode"),
+                widget::dropdown(
+                    &self.layout_mode_labels,
+                    LayoutMode::ALL.iter().position(|mode| *mode == self.config.layout_mode),
+                    Message::SetLayoutMode,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("layout-columns"),
+                widget::dropdown(
+                    COLUMN_OPTIONS,
+                    Some(self.config.columns.clamp(1, COLUMN_OPTIONS.len() as u32) as usize - 1),
+                    Message::SetColumns,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("text-contrast"),
+                widget::dropdown(
+                    &self.text_contrast_labels,
+                    TextContrast::ALL.iter().position(|contrast| *contrast == self.config.text_contrast),
+                    Message::SetTextContrast,
+                ),
             ))
-            .push(widget::text::body("Displays currently playing track from Cider (Apple Music client)"))
+            .push(widget::text::caption(fl!("text-contrast-description")))
+            .push(widget::divider::horizontal::default())
+            
+            // === Fonts Section ===
+            .push(widget::text::heading(fl!("fonts")))
+            .push(widget::text::body(fl!("fonts-description")));
+        
+        // One row per text role: family and size
+        for (index, role) in TextRole::ALL.iter().enumerate() {
+            let font = self.config.fonts.get(*role);
+            let size_input = self.font_size_inputs.get(index).cloned().unwrap_or_else(|| font.size.to_string());
+            content = content.push(
+                widget::row()
+                    .spacing(8)
+                    .push(widget::text::body(role.label()).width(cosmic::iced::Length::FillPortion(2)))
+                    .push(
+                        widget::text_input(DEFAULT_FONT_FAMILY, &font.family)
+                            .on_input(move |value| Message::UpdateFontFamily(index, value))
+                            .width(cosmic::iced::Length::FillPortion(3)),
+                    )
+                    .push(
+                        widget::text_input("", size_input)
+                            .on_input(move |value| Message::UpdateFontSize(index, value))
+                            .width(cosmic::iced::Length::FillPortion(1)),
+                    ),
+            );
+            let family = font.family.trim();
+            if !family.is_empty() && !self.installed_fonts.is_empty() && !fonts::is_installed(family, &self.installed_fonts) {
+                content = content.push(widget::text::caption(fl!(
+                    "font-not-installed",
+                    family = family.to_string(),
+                    fallback = DEFAULT_FONT_FAMILY
+                )));
+            }
+        }
+        
+        content = content
             .push(widget::divider::horizontal::default())
             
             // === Layout Order Section ===
             .push(widget::text::heading(fl!("layout-order")))
             .push(widget::text::body(fl!("layout-order-description")));
         
-        // Render section order list with up/down move buttons
+        // Render section order list with up/down move buttons; clicking a
+        // section picks it up and clicking another row drops it there
         for (index, section) in self.config.section_order.iter().enumerate() {
             // Up button (disabled if at top)
             let up_button = if index > 0 {
@@ -592,193 +1269,2397 @@ impl Application for SettingsApp {
                     .padding(4)
             };
             
+            let label = if self.picked_section == Some(index) {
+                widget::button::suggested(section.label())
+            } else {
+                widget::button::text(section.label())
+            };
+            
             content = content.push(
                 widget::row()
                     .spacing(8)
                     .padding([4, 8])
                     .push(up_button)
                     .push(down_button)
-                    .push(widget::text::body(section.label()))
+                    .push(label.on_press(Message::PickSection(index)))
                     .push(widget::horizontal_space())
+                    .push(widget::dropdown(
+                        &self.card_opacity_labels,
+                        CARD_OPACITIES
+                            .iter()
+                            .position(|opacity| *opacity == self.config.card_opacity(*section).unwrap_or(0)),
+                        move |choice| Message::SetSectionCard(index, choice),
+                    ))
             );
         }
         
         content = content
             .push(widget::divider::horizontal::default())
             
-            // === Widget Position Section ===
-            .push(widget::text::heading("Widget Position"))
+            // === Layout Bundle Section ===
+            .push(widget::text::heading(fl!("layout-bundle")))
+            .push(widget::text::body(fl!("layout-bundle-description")))
             .push(widget::settings::item(
-                fl!("widget-autostart"),
-                widget::toggler(self.config.widget_autostart)
-                    .on_toggle(Message::ToggleWidgetAutostart),
+                fl!("layout-bundle-file"),
+                widget::text_input("", &self.bundle_path_input).on_input(Message::UpdateBundlePath),
+            ))
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .push(widget::button::standard(fl!("layout-bundle-export")).on_press(Message::ExportBundle))
+                    .push(widget::button::standard(fl!("layout-bundle-import")).on_press(Message::ImportBundle))
+            );
+        
+        if let Some(status) = &self.bundle_status {
+            content = content.push(widget::text::body(status.clone()));
+        }
+        
+        content
+    }
+    
+    /// Sections page: what each section shows (utilization, temperatures,
+    /// storage, battery, custom commands, system health, feeds, ticker,
+    /// plugins, and caffeine).
+    fn view_sections(&self) -> widget::Column<'_, Message> {
+        let mut content = widget::column()
+            .spacing(12)
+            
+            // === Monitoring Options Section ===
+            .push(widget::text::heading(fl!("monitoring-options")))
+            .push(widget::settings::item(
+                fl!("show-cpu"),
+                widget::toggler(self.config.show_cpu).on_toggle(Message::ToggleCpu),
+            ))
+            .push(widget::settings::item(
+                fl!("show-cpu-frequency"),
+                widget::toggler(self.config.show_cpu_frequency).on_toggle(Message::ToggleCpuFrequency),
+            ))
+            .push(widget::settings::item(
+                fl!("show-cpu-governor"),
+                widget::toggler(self.config.show_cpu_governor).on_toggle(Message::ToggleCpuGovernor),
+            ))
+            .push(widget::settings::item(
+                fl!("cpu-governor-click"),
+                widget::toggler(self.config.cpu_governor_click).on_toggle(Message::ToggleCpuGovernorClick),
+            ))
+            .push(widget::settings::item(
+                fl!("show-hardware-info"),
+                widget::toggler(self.config.show_hardware_info).on_toggle(Message::ToggleHardwareInfo),
+            ))
+            .push(widget::settings::item(
+                fl!("show-memory"),
+                widget::toggler(self.config.show_memory).on_toggle(Message::ToggleMemory),
+            ))
+            .push(widget::settings::item(
+                fl!("show-gpu"),
+                widget::toggler(self.config.show_gpu).on_toggle(Message::ToggleGpu),
+            ))
+            .push(widget::settings::item(
+                fl!("show-gpu-processes"),
+                widget::toggler(self.config.show_gpu_processes).on_toggle(Message::ToggleGpuProcesses),
+            ))
+            .push(widget::settings::item(
+                fl!("show-gpu-details"),
+                widget::toggler(self.config.show_gpu_details).on_toggle(Message::ToggleGpuDetails),
+            ))
+            .push(widget::settings::item(
+                fl!("show-psi"),
+                widget::toggler(self.config.show_psi).on_toggle(Message::TogglePsi),
+            ))
+            .push(widget::settings::item(
+                fl!("show-network"),
+                widget::toggler(self.config.show_network).on_toggle(Message::ToggleNetwork),
             ))
             .push(widget::settings::item(
-                "X Position",
-                widget::text_input("", &self.x_input).on_input(Message::UpdateX),
+                fl!("network-rate-bits"),
+                widget::toggler(self.config.network_rate_bits).on_toggle(Message::ToggleNetworkBits),
             ))
             .push(widget::settings::item(
-                "Y Position",
-                widget::text_input("", &self.y_input).on_input(Message::UpdateY),
+                fl!("show-network-graph"),
+                widget::toggler(self.config.show_network_graph).on_toggle(Message::ToggleNetworkGraph),
+            ))
+            .push(widget::settings::item(
+                fl!("network-graph-window"),
+                widget::text_input("", &self.network_graph_input).on_input(Message::UpdateNetworkGraphWindow),
+            ))
+            .push(widget::settings::item(
+                fl!("show-disk"),
+                widget::toggler(self.config.show_disk).on_toggle(Message::ToggleDisk),
             ))
             .push(widget::divider::horizontal::default())
             
-            // === Advanced Section ===
-            .push(widget::text::heading("Advanced"))
+            // === Storage Display Section ===
+            .push(widget::text::heading(fl!("storage-display")))
             .push(widget::settings::item(
-                "Enable Debug Logging",
-                widget::toggler(self.config.enable_logging)
-                    .on_toggle(Message::ToggleLogging),
+                fl!("show-storage"),
+                widget::toggler(self.config.show_storage).on_toggle(Message::ToggleStorage),
+            ))
+            .push(widget::settings::item(
+                fl!("storage-min-free"),
+                widget::text_input("", &self.storage_min_free_input).on_input(Message::UpdateStorageMinFree),
+            ))
+            .push(widget::settings::item(
+                fl!("storage-max-inode"),
+                widget::text_input("", &self.storage_max_inode_input).on_input(Message::UpdateStorageMaxInode),
+            ))
+            .push(widget::settings::item(
+                fl!("storage-alerts"),
+                widget::toggler(self.config.storage_alerts).on_toggle(Message::ToggleStorageAlerts),
+            ))
+            .push(widget::settings::item(
+                fl!("show-smart"),
+                widget::toggler(self.config.show_smart).on_toggle(Message::ToggleSmart),
+            ))
+            .push(widget::settings::item(
+                fl!("smart-refresh"),
+                widget::text_input("", &self.smart_refresh_input).on_input(Message::UpdateSmartRefresh),
+            ))
+            .push(widget::settings::item(
+                fl!("show-storage-pools"),
+                widget::toggler(self.config.show_storage_pools).on_toggle(Message::ToggleStoragePools),
+            ))
+            .push(widget::settings::item(
+                fl!("storage-pools-refresh"),
+                widget::text_input("", &self.storage_pools_refresh_input).on_input(Message::UpdateStoragePoolsRefresh),
             ))
-            .push(widget::text::body("Writes debug logs to /tmp/cosmic-monitor.log"))
+            .push(widget::divider::horizontal::default())
             
-            // === Save & Apply Button ===
-            .push(
+            // === Temperature Display Section ===
+            .push(widget::text::heading(fl!("temperature-display")))
+            .push(widget::settings::item(
+                fl!("show-cpu-temp"),
+                widget::toggler(self.config.show_cpu_temp).on_toggle(Message::ToggleCpuTemp),
+            ))
+            .push(widget::settings::item(
+                fl!("show-gpu-temp"),
+                widget::toggler(self.config.show_gpu_temp).on_toggle(Message::ToggleGpuTemp),
+            ))
+            .push(widget::settings::item(
+                fl!("use-circular-temp-display"),
+                widget::toggler(self.config.use_circular_temp_display).on_toggle(Message::ToggleCircularTempDisplay),
+            ))
+            .push(widget::settings::item(
+                fl!("temps-on-usage-bars"),
+                widget::toggler(self.config.temps_on_usage_bars).on_toggle(Message::ToggleTempsOnUsageBars),
+            ))
+            .push(widget::settings::item(
+                fl!("show-hottest-core"),
+                widget::toggler(self.config.show_hottest_core).on_toggle(Message::ToggleHottestCore),
+            ))
+            .push(widget::settings::item(
+                fl!("show-throttling"),
+                widget::toggler(self.config.show_throttling).on_toggle(Message::ToggleThrottling),
+            ))
+            .push(widget::settings::item(
+                fl!("show-temp-graph"),
+                widget::toggler(self.config.show_temp_graph).on_toggle(Message::ToggleTempGraph),
+            ))
+            .push(widget::settings::item(
+                fl!("temp-graph-window"),
+                widget::text_input("", &self.temp_graph_input).on_input(Message::UpdateTempGraphWindow),
+            ))
+            .push(widget::settings::item(
+                fl!("temp-graph-mode"),
+                widget::dropdown(
+                    &self.temp_graph_mode_labels,
+                    TempGraphMode::ALL.iter().position(|mode| *mode == self.config.temp_graph_mode),
+                    Message::SetTempGraphMode,
+                ),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Battery Section ===
+            .push(widget::text::heading(fl!("battery")))
+            .push(widget::settings::item(
+                fl!("show-battery"),
+                widget::toggler(self.config.show_battery)
+                    .on_toggle(Message::ToggleBatterySection),
+            ))
+            .push(widget::settings::item(
+                fl!("enable-solaar"),
+                widget::toggler(self.config.enable_solaar_integration)
+                    .on_toggle(Message::ToggleSolaarIntegration),
+            ))
+            .push(widget::settings::item(
+                fl!("show-power-profile"),
+                widget::toggler(self.config.show_power_profile).on_toggle(Message::TogglePowerProfile),
+            ));
+        
+        // Display cached battery devices with remove buttons
+        if !self.cached_devices.is_empty() {
+            content = content.push(widget::text::body(fl!("cached-devices")));
+            
+            for (index, device) in self.cached_devices.iter().enumerate() {
+                let device_kind = device.kind.as_deref().unwrap_or("device");
+                let device_label = format!("{} ({})", device.name, device_kind);
+                
+                content = content.push(
+                    widget::row()
+                        .spacing(8)
+                        .padding([4, 16])
+                        .push(widget::text::body(device_label))
+                        .push(widget::horizontal_space())
+                        .push(
+                            widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                                .on_press(Message::RemoveCachedDevice(index))
+                                .padding(4)
+                        )
+                );
+            }
+        }
+        
+        content = content
+            .push(widget::divider::horizontal::default())
+            
+            // === Custom Commands Section ===
+            .push(widget::text::heading(fl!("custom-commands")))
+            .push(widget::text::body(fl!("custom-commands-description")))
+            .push(widget::settings::item(
+                fl!("show-custom-commands"),
+                widget::toggler(self.config.show_custom_commands).on_toggle(Message::ToggleCustomCommands),
+            ));
+        
+        // One row of inputs per command
+        for (index, custom) in self.config.custom_commands.iter().enumerate() {
+            let interval_input = self
+                .custom_interval_inputs
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| custom.interval_secs.to_string());
+            content = content.push(
                 widget::row()
                     .spacing(8)
-                    .push(widget::column().width(cosmic::iced::Length::Fill))
+                    .padding([4, 0])
                     .push(
-                        widget::button::suggested("Save & Apply Settings")
-                            .on_press(Message::SaveAndApply)
+                        widget::text_input(fl!("custom-command-label"), &custom.label)
+                            .on_input(move |value| Message::UpdateCustomLabel(index, value))
+                            .width(cosmic::iced::Length::FillPortion(2)),
                     )
-                    .push(widget::column().width(cosmic::iced::Length::Fill))
+                    .push(
+                        widget::text_input(fl!("custom-command-command"), &custom.command)
+                            .on_input(move |value| Message::UpdateCustomCommand(index, value))
+                            .width(cosmic::iced::Length::FillPortion(4)),
+                    )
+                    .push(
+                        widget::text_input(fl!("custom-command-interval"), interval_input)
+                            .on_input(move |value| Message::UpdateCustomInterval(index, value))
+                            .width(cosmic::iced::Length::FillPortion(1)),
+                    )
+                    .push(
+                        widget::text_input(fl!("custom-command-format"), &custom.format)
+                            .on_input(move |value| Message::UpdateCustomFormat(index, value))
+                            .width(cosmic::iced::Length::FillPortion(2)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::RemoveCustomCommand(index))
+                            .padding(4),
+                    ),
             );
-
-        // Wrap in scrollable container for smaller screens
-        let scrollable_content = widget::scrollable(content);
-
-        widget::container(scrollable_content)
-            .width(cosmic::iced::Length::Fill)
-            .height(cosmic::iced::Length::Fill)
-            .into()
-    }
-
-    /// Process messages and update application state.
-    ///
-    /// Most messages simply update a config field and save. Text inputs
-    /// validate their content before updating (e.g., interval must be 100-10000ms).
-    fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
-        match message {
-            // === Config Sync ===
-            Message::UpdateConfig(config) => {
-                self.config = config;
+        }
+        
+        content = content
+            .push(
+                widget::row().push(
+                    widget::button::standard(fl!("custom-command-add")).on_press(Message::AddCustomCommand),
+                ),
+            )
+            .push(widget::divider::horizontal::default())
+            
+            // === System Health Section ===
+            .push(widget::text::heading(fl!("system-health")))
+            .push(widget::text::body(fl!("system-health-description")))
+            .push(widget::settings::item(
+                fl!("show-system-health"),
+                widget::toggler(self.config.show_system_health).on_toggle(Message::ToggleSystemHealth),
+            ))
+            .push(widget::settings::item(
+                fl!("health-update-command"),
+                widget::text_input("checkupdates", &self.config.health_update_command)
+                    .on_input(Message::UpdateHealthUpdateCommand),
+            ))
+            .push(widget::settings::item(
+                fl!("health-refresh"),
+                widget::text_input("", &self.health_refresh_input).on_input(Message::UpdateHealthRefresh),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Containers Section ===
+            .push(widget::text::heading(fl!("containers")))
+            .push(widget::text::body(fl!("containers-description")))
+            .push(widget::settings::item(
+                fl!("show-containers"),
+                widget::toggler(self.config.show_containers).on_toggle(Message::ToggleContainers),
+            ))
+            .push(widget::settings::item(
+                fl!("containers-show-busiest"),
+                widget::toggler(self.config.containers_show_busiest).on_toggle(Message::ToggleContainersBusiest),
+            ))
+            .push(widget::settings::item(
+                fl!("containers-refresh"),
+                widget::text_input("", &self.containers_refresh_input).on_input(Message::UpdateContainersRefresh),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Virtual Machines Section ===
+            .push(widget::text::heading(fl!("vms")))
+            .push(widget::text::body(fl!("vms-description")))
+            .push(widget::settings::item(
+                fl!("show-vms"),
+                widget::toggler(self.config.show_vms).on_toggle(Message::ToggleVms),
+            ))
+            .push(widget::settings::item(
+                fl!("vms-uri"),
+                widget::text_input("qemu:///system", &self.config.vms_uri).on_input(Message::UpdateVmsUri),
+            ))
+            .push(widget::settings::item(
+                fl!("vms-refresh"),
+                widget::text_input("", &self.vms_refresh_input).on_input(Message::UpdateVmsRefresh),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === System Section ===
+            .push(widget::text::heading(fl!("system-section")))
+            .push(widget::text::body(fl!("system-section-description")))
+            .push(widget::settings::item(
+                fl!("show-kernel-stats"),
+                widget::toggler(self.config.show_kernel_stats).on_toggle(Message::ToggleKernelStats),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Mail & Feeds Section ===
+            .push(widget::text::heading(fl!("feeds")))
+            .push(widget::text::body(fl!("feeds-description")))
+            .push(widget::settings::item(
+                fl!("show-feeds"),
+                widget::toggler(self.config.show_feeds).on_toggle(Message::ToggleFeeds),
+            ))
+            .push(widget::settings::item(
+                fl!("feeds-refresh"),
+                widget::text_input("", &self.feeds_refresh_input).on_input(Message::UpdateFeedsRefresh),
+            ));
+        
+        // One row of inputs per mailbox or feed
+        for (index, feed) in self.config.feeds.iter().enumerate() {
+            let placeholder = match feed.kind {
+                FeedKind::Imap => "imaps://user@imap.example.com/INBOX",
+                FeedKind::Rss => "https://example.com/feed.xml",
+            };
+            content = content.push(
+                widget::row()
+                    .spacing(8)
+                    .padding([4, 0])
+                    .push(widget::dropdown(
+                        &self.feed_kind_labels,
+                        FeedKind::ALL.iter().position(|kind| *kind == feed.kind),
+                        move |choice| Message::SetFeedKind(index, choice),
+                    ))
+                    .push(
+                        widget::text_input(fl!("feed-label"), &feed.label)
+                            .on_input(move |value| Message::UpdateFeedLabel(index, value))
+                            .width(cosmic::iced::Length::FillPortion(2)),
+                    )
+                    .push(
+                        widget::text_input(placeholder, &feed.url)
+                            .on_input(move |value| Message::UpdateFeedUrl(index, value))
+                            .width(cosmic::iced::Length::FillPortion(5)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::RemoveFeed(index))
+                            .padding(4),
+                    ),
+            );
+        }
+        
+        content = content
+            .push(widget::row().push(widget::button::standard(fl!("feed-add")).on_press(Message::AddFeed)))
+            .push(widget::divider::horizontal::default())
+            
+            // === Ticker Section ===
+            .push(widget::text::heading(fl!("ticker")))
+            .push(widget::text::body(fl!("ticker-description")))
+            .push(widget::settings::item(
+                fl!("show-ticker"),
+                widget::toggler(self.config.show_ticker).on_toggle(Message::ToggleTicker),
+            ))
+            .push(widget::settings::item(
+                fl!("ticker-provider"),
+                widget::dropdown(
+                    &self.ticker_provider_labels,
+                    TickerProvider::ALL.iter().position(|provider| *provider == self.config.ticker_provider),
+                    Message::SetTickerProvider,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("ticker-symbols"),
+                widget::text_input("bitcoin, ethereum", &self.config.ticker_symbols).on_input(Message::UpdateTickerSymbols),
+            ))
+            .push(widget::settings::item(
+                fl!("ticker-max-symbols"),
+                widget::text_input("", &self.ticker_max_input).on_input(Message::UpdateTickerMaxSymbols),
+            ))
+            .push(widget::settings::item(
+                fl!("ticker-currency"),
+                widget::text_input("usd", &self.config.ticker_currency).on_input(Message::UpdateTickerCurrency),
+            ));
+        
+        if self.config.ticker_provider == TickerProvider::JsonEndpoint {
+            content = content
+                .push(widget::settings::item(
+                    fl!("ticker-endpoint"),
+                    widget::text_input("https://finnhub.io/api/v1/quote?symbol={symbol}&token=…", &self.config.ticker_endpoint)
+                        .on_input(Message::UpdateTickerEndpoint),
+                ))
+                .push(widget::settings::item(
+                    fl!("ticker-price-path"),
+                    widget::text_input("/c", &self.config.ticker_price_path).on_input(Message::UpdateTickerPricePath),
+                ))
+                .push(widget::settings::item(
+                    fl!("ticker-change-path"),
+                    widget::text_input("/dp", &self.config.ticker_change_path).on_input(Message::UpdateTickerChangePath),
+                ));
+        }
+        
+        content = content
+            .push(widget::settings::item(
+                fl!("ticker-refresh"),
+                widget::text_input("", &self.ticker_refresh_input).on_input(Message::UpdateTickerRefresh),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Plugins Section ===
+            .push(widget::text::heading(fl!("plugins")))
+            .push(widget::text::body(fl!("plugins-description")))
+            .push(widget::settings::item(
+                fl!("show-plugins"),
+                widget::toggler(self.config.show_plugins).on_toggle(Message::TogglePlugins),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Caffeine Section ===
+            .push(widget::text::heading(fl!("caffeine")))
+            .push(widget::text::body(fl!("caffeine-description")))
+            .push(widget::settings::item(
+                fl!("show-caffeine"),
+                widget::toggler(self.config.show_caffeine).on_toggle(Message::ToggleCaffeine),
+            ))
+            .push(widget::settings::item(
+                fl!("caffeine-on-start"),
+                widget::toggler(self.config.caffeine_on_start).on_toggle(Message::ToggleCaffeineOnStart),
+            ));
+        
+        content
+    }
+    
+    /// Weather page: weather display, air quality, and the weather location.
+    fn view_weather(&self) -> widget::Column<'_, Message> {
+        widget::column()
+            .spacing(12)
+            
+            // === Weather Display Section ===
+            .push(widget::text::heading(fl!("weather-display")))
+            .push(widget::settings::item(
+                fl!("show-weather"),
+                widget::toggler(self.config.show_weather)
+                    .on_toggle(Message::ToggleWeather),
+            ))
+            .push(widget::settings::item(
+                fl!("weather-detailed"),
+                widget::toggler(self.config.weather_detailed)
+                    .on_toggle(Message::ToggleWeatherDetailed),
+            ))
+            .push(widget::settings::item(
+                fl!("weather-sun-times"),
+                widget::toggler(self.config.weather_sun_times).on_toggle(Message::ToggleWeatherSunTimes),
+            ))
+            .push(widget::settings::item(
+                fl!("weather-moon-phase"),
+                widget::toggler(self.config.weather_moon_phase).on_toggle(Message::ToggleWeatherMoonPhase),
+            ))
+            .push(widget::settings::item(
+                fl!("show-air-quality"),
+                widget::toggler(self.config.show_air_quality).on_toggle(Message::ToggleAirQuality),
+            ))
+            .push(widget::settings::item(
+                fl!("air-quality-provider"),
+                widget::dropdown(
+                    &self.air_quality_provider_labels,
+                    AirQualityProvider::ALL.iter().position(|provider| *provider == self.config.air_quality_provider),
+                    Message::SetAirQualityProvider,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("weather-api-key"),
+                widget::text_input("", &self.weather_api_key_input)
+                    .on_input(Message::UpdateWeatherApiKey),
+            ))
+            .push(widget::settings::item(
+                fl!("weather-location"),
+                widget::text_input("", &self.weather_location_input)
+                    .on_input(Message::UpdateWeatherLocation),
+            ))
+    }
+    
+    /// Media page: media player and audio output.
+    fn view_media(&self) -> widget::Column<'_, Message> {
+        widget::column()
+            .spacing(12)
+            
+            // === Media Player Section ===
+            .push(widget::text::heading(fl!("media-player")))
+            .push(widget::settings::item(
+                fl!("show-media"),
+                widget::toggler(self.config.show_media)
+                    .on_toggle(Message::ToggleMedia),
+            ))
+            .push(widget::settings::item(
+                fl!("cider-api-token"),
+                widget::text_input(fl!("cider-api-token-placeholder"), &self.cider_api_token_input)
+                    .on_input(Message::UpdateCiderApiToken),
+            ))
+            .push(self.scroll_action_item(WidgetSection::Media, self.config.media_scroll))
+            .push(widget::settings::item(
+                fl!("media-marquee"),
+                widget::toggler(self.config.media_marquee).on_toggle(Message::ToggleMediaMarquee),
+            ))
+            .push(widget::settings::item(
+                fl!("marquee-speed"),
+                widget::text_input("30", &self.marquee_speed_input).on_input(Message::UpdateMarqueeSpeed),
+            ))
+            .push(widget::settings::item(
+                fl!("marquee-pause"),
+                widget::text_input("1500", &self.marquee_pause_input).on_input(Message::UpdateMarqueePause),
+            ))
+            .push(widget::settings::item(
+                fl!("show-lyrics"),
+                widget::toggler(self.config.show_lyrics).on_toggle(Message::ToggleLyrics),
+            ))
+            .push(widget::text::body(fl!("lyrics-description")))
+            .push(widget::text::body(fl!("cider-description")))
+            .push(widget::divider::horizontal::default())
+            
+            // === Audio Output Section ===
+            .push(widget::text::heading(fl!("audio")))
+            .push(widget::text::body(fl!("audio-description")))
+            .push(widget::settings::item(
+                fl!("show-audio"),
+                widget::toggler(self.config.show_audio).on_toggle(Message::ToggleAudio),
+            ))
+            .push(self.scroll_action_item(WidgetSection::Audio, self.config.audio_scroll))
+    }
+    
+    /// Alerts page: thresholds and colors, the ambient status tint, and notifications.
+    fn view_alerts(&self) -> widget::Column<'_, Message> {
+        let mut content = widget::column()
+            .spacing(12)
+            
+            // === Bar Colors Section ===
+            .push(widget::text::heading(fl!("bar-colors")))
+            .push(widget::text::body(fl!("bar-colors-description")));
+        
+        // Warning and critical threshold of each metric
+        for (index, metric) in ThresholdMetric::ALL.iter().copied().enumerate() {
+            let (warning, critical) = self.threshold_inputs.get(index).cloned().unwrap_or_default();
+            content = content.push(widget::settings::item(
+                metric.label(),
+                widget::row()
+                    .spacing(8)
+                    .push(
+                        widget::text_input(fl!("threshold-warning"), warning)
+                            .on_input(move |value| Message::UpdateWarningThreshold(metric, value))
+                            .width(cosmic::iced::Length::Fixed(80.0)),
+                    )
+                    .push(
+                        widget::text_input(fl!("threshold-critical"), critical)
+                            .on_input(move |value| Message::UpdateCriticalThreshold(metric, value))
+                            .width(cosmic::iced::Length::Fixed(80.0)),
+                    ),
+            ));
+        }
+        
+        // Color of each level
+        for (index, level) in Level::ALL.iter().copied().enumerate() {
+            let color = self.bar_color_inputs.get(index).cloned().unwrap_or_default();
+            content = content.push(widget::settings::item(
+                level.label(),
+                widget::text_input("#RRGGBB", color)
+                    .on_input(move |value| Message::UpdateBarColor(level, value))
+                    .width(cosmic::iced::Length::Fixed(120.0)),
+            ));
+        }
+        
+        content = content
+            .push(widget::settings::item(
+                fl!("ambient-source"),
+                widget::dropdown(
+                    &self.ambient_source_labels,
+                    AmbientSource::ALL.iter().position(|source| *source == self.config.ambient_source),
+                    Message::SetAmbientSource,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("ambient-target"),
+                widget::dropdown(
+                    &self.ambient_target_labels,
+                    AmbientTarget::ALL.iter().position(|target| *target == self.config.ambient_target),
+                    Message::SetAmbientTarget,
+                ),
+            ))
+            .push(widget::text::caption(fl!("ambient-description")))
+            .push(widget::divider::horizontal::default())
+            
+            // === Notifications Section ===
+            .push(widget::text::heading(fl!("notifications")))
+            .push(widget::settings::item(
+                fl!("show-notifications"),
+                widget::toggler(self.config.show_notifications)
+                    .on_toggle(Message::ToggleNotifications),
+            ))
+            .push(widget::settings::item(
+                fl!("max-notifications"),
+                widget::text_input("", &self.max_notifications_input)
+                    .on_input(Message::UpdateMaxNotifications),
+            ))
+            .push(self.scroll_action_item(WidgetSection::Notifications, self.config.notifications_scroll))
+            .push(widget::settings::item(
+                fl!("compact-notifications"),
+                widget::toggler(self.config.compact_notifications).on_toggle(Message::ToggleCompactNotifications),
+            ))
+            .push(widget::settings::item(
+                fl!("notification-history"),
+                widget::toggler(self.config.notification_history).on_toggle(Message::ToggleNotificationHistory),
+            ))
+            .push(widget::settings::item(
+                fl!("notification-history-count"),
+                widget::text_input("50", &self.notification_history_count_input)
+                    .on_input(Message::UpdateNotificationHistoryCount),
+            ))
+            .push(widget::settings::item(
+                fl!("notification-history-days"),
+                widget::text_input("7", &self.notification_history_days_input)
+                    .on_input(Message::UpdateNotificationHistoryDays),
+            ))
+            .push(widget::text::caption(fl!("notification-history-description")))
+            .push(
+                widget::button::destructive(fl!("notification-history-clear"))
+                    .on_press(Message::ClearNotificationHistory),
+            );
+        
+        content
+    }
+    
+    /// Advanced page: sampling, logging, diagnostics output, and background behavior.
+    fn view_advanced(&self) -> widget::Column<'_, Message> {
+        widget::column()
+            .spacing(12)
+            
+            // === Update Interval ===
+            .push(widget::settings::item(
+                fl!("update-interval"),
+                widget::text_input("", &self.interval_input).on_input(Message::UpdateInterval),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Advanced Section ===
+            .push(widget::text::heading(fl!("page-advanced")))
+            .push(widget::settings::item(
+                fl!("log-level"),
+                widget::dropdown(
+                    &self.log_level_labels,
+                    LogLevel::ALL.iter().position(|level| *level == self.config.log_level),
+                    Message::SetLogLevel,
+                ),
+            ))
+            .push(widget::settings::item(
+                fl!("write-logs"),
+                widget::toggler(self.config.enable_logging)
+                    .on_toggle(Message::ToggleLogging),
+            ))
+            .push(widget::text::body(fl!("write-logs-description")))
+            .push(widget::settings::item(
+                fl!("show-debug-overlay"),
+                widget::toggler(self.config.show_debug_overlay)
+                    .on_toggle(Message::ToggleDebugOverlay),
+            ))
+            .push(widget::text::body(fl!("debug-overlay-description")))
+            .push(widget::settings::item(
+                fl!("publish-stats"),
+                widget::toggler(self.config.publish_stats)
+                    .on_toggle(Message::TogglePublishStats),
+            ))
+            .push(widget::text::body(fl!("publish-stats-description")))
+            .push(widget::settings::item(
+                fl!("export-frames"),
+                widget::toggler(self.config.export_frames)
+                    .on_toggle(Message::ToggleExportFrames),
+            ))
+            .push(widget::text::body(fl!("export-frames-description")))
+            .push(widget::settings::item(
+                fl!("record-metrics"),
+                widget::toggler(self.config.record_metrics)
+                    .on_toggle(Message::ToggleRecordMetrics),
+            ))
+            .push(widget::text::body(fl!("record-metrics-description")))
+            .push(widget::settings::item(
+                fl!("record-interval"),
+                widget::text_input("", &self.record_interval_input).on_input(Message::UpdateRecordInterval),
+            ))
+            .push(widget::settings::item(
+                fl!("record-retention"),
+                widget::text_input("", &self.record_retention_input).on_input(Message::UpdateRecordRetention),
+            ))
+            .push(widget::settings::item(
+                fl!("low-priority-collectors"),
+                widget::toggler(self.config.low_priority_collectors)
+                    .on_toggle(Message::ToggleLowPriorityCollectors),
+            ))
+            .push(widget::settings::item(
+                fl!("idle-priority-collectors"),
+                widget::toggler(self.config.idle_priority_collectors)
+                    .on_toggle(Message::ToggleIdlePriorityCollectors),
+            ))
+            .push(widget::settings::item(
+                fl!("pin-efficiency-cores"),
+                widget::toggler(self.config.pin_collectors_to_efficiency_cores)
+                    .on_toggle(Message::TogglePinEfficiencyCores),
+            ))
+            .push(widget::text::body(fl!("collector-priority-description")))
+            .push(widget::settings::item(
+                fl!("check-for-updates"),
+                widget::toggler(self.config.check_for_updates)
+                    .on_toggle(Message::ToggleUpdateCheck),
+            ))
+            .push(widget::text::body(fl!("check-for-updates-description")))
+            .push(widget::settings::item(
+                fl!("setup-run-again"),
+                widget::button::standard(fl!("setup-open")).on_press(Message::StartSetup),
+            ))
+    }
+    
+    /// Render the live preview pane shown beside the configuration pages.
+    fn preview_pane(&self) -> Element<'_, Message> {
+        let mut pane = widget::column()
+            .spacing(8)
+            .padding(24)
+            .push(widget::text::heading(fl!("preview")));
+        
+        pane = match &self.preview {
+            Some(preview) => pane.push(
+                widget::image(preview.handle.clone())
+                    .width(cosmic::iced::Length::Fixed(preview.width as f32 * PREVIEW_SCALE))
+                    .height(cosmic::iced::Length::Fixed(preview.height as f32 * PREVIEW_SCALE)),
+            ),
+            None => pane.push(widget::text::body(fl!("preview-unavailable"))),
+        };
+        
+        widget::scrollable(pane.push(widget::text::caption(fl!("preview-description")))).into()
+    }
+    
+    /// Re-render the preview and the position picker if the config changed
+    /// since the last render.
+    ///
+    /// While the setup wizard is open, its draft is rendered instead.
+    fn refresh_preview(&mut self) {
+        let config = if self.page == SettingsPage::Setup {
+            &self.setup_config
+        } else {
+            &self.config
+        };
+        if self.preview.is_some() && self.preview_config == *config {
+            return;
+        }
+        self.preview = preview::render(config, &self.theme);
+        self.placement_image = Mockup::new(&self.cached_outputs).render(
+            config,
+            preview::widget_size(config),
+            &self.theme,
+        );
+        self.preview_config = config.clone();
+    }
+    
+    /// Render the setup wizard.
+    ///
+    /// One step at a time, with the live preview beside it. Nothing is
+    /// written until Finish.
+    fn view_setup(&self) -> Element<'_, Message> {
+        let step = self.setup_step;
+        let draft = &self.setup_config;
+        
+        let mut content = widget::column()
+            .spacing(12)
+            .padding(24)
+            .push(widget::text::title1(fl!("setup-title")))
+            .push(widget::text::body(fl!(
+                "setup-progress",
+                step = step.index() + 1,
+                total = SetupStep::ALL.len(),
+                name = step.label()
+            )))
+            .push(widget::divider::horizontal::default());
+        
+        match step {
+            SetupStep::Output => {
+                let option = |label: String, name: &str| {
+                    let button = if draft.widget_output == name {
+                        widget::button::suggested(label)
+                    } else {
+                        widget::button::standard(label)
+                    };
+                    button.on_press(Message::SetupOutput(name.to_string()))
+                };
+                content = content
+                    .push(widget::text::body(fl!("setup-output-description")))
+                    .push(option(fl!("position-any-output"), ""));
+                for output in &self.cached_outputs {
+                    let label = if output.description.is_empty() {
+                        format!("{} ({}x{})", output.name, output.width, output.height)
+                    } else {
+                        format!("{} – {} ({}x{})", output.name, output.description, output.width, output.height)
+                    };
+                    content = content.push(option(label, &output.name));
+                }
+                if self.cached_outputs.is_empty() {
+                    content = content.push(widget::text::caption(fl!("setup-output-unknown")));
+                }
+            }
+            SetupStep::Corner => {
+                let mut corners = widget::row().spacing(8);
+                for anchor in WidgetAnchor::ALL {
+                    let button = if draft.widget_anchor == anchor {
+                        widget::button::suggested(anchor.label())
+                    } else {
+                        widget::button::standard(anchor.label())
+                    };
+                    corners = corners.push(button.on_press(Message::SetupAnchor(anchor)));
+                }
+                content = content
+                    .push(widget::text::body(fl!("setup-corner-description")))
+                    .push(corners);
+                if let Some(handle) = &self.placement_image {
+                    content = content.push(
+                        widget::image(handle.clone())
+                            .width(cosmic::iced::Length::Fixed(MOCKUP_WIDTH as f32))
+                            .height(cosmic::iced::Length::Fixed(MOCKUP_HEIGHT as f32)),
+                    );
+                }
+            }
+            SetupStep::Sections => {
+                content = content.push(widget::text::body(fl!("setup-sections-description")));
+                for toggle in SetupToggle::ALL {
+                    content = content.push(widget::settings::item(
+                        toggle.label(),
+                        widget::toggler(toggle.get(draft))
+                            .on_toggle(move |enabled| Message::SetupToggle(toggle, enabled)),
+                    ));
+                }
+            }
+            SetupStep::Weather => {
+                content = content.push(widget::text::body(fl!("setup-weather-description")));
+                if draft.show_weather {
+                    content = content
+                        .push(widget::settings::item(
+                            fl!("setup-weather-provider"),
+                            widget::text::body("OpenWeatherMap"),
+                        ))
+                        .push(widget::settings::item(
+                            fl!("weather-api-key"),
+                            widget::text_input("", &draft.weather_api_key).on_input(Message::SetupWeatherApiKey),
+                        ))
+                        .push(widget::settings::item(
+                            fl!("weather-location"),
+                            widget::text_input("", &draft.weather_location).on_input(Message::SetupWeatherLocation),
+                        ));
+                } else {
+                    content = content.push(widget::text::caption(fl!("setup-weather-disabled")));
+                }
+            }
+            SetupStep::Finish => {
+                content = content
+                    .push(widget::text::body(fl!("setup-finish-description")))
+                    .push(widget::settings::item(
+                        fl!("setup-autostart-applet"),
+                        widget::toggler(draft.widget_autostart).on_toggle(Message::SetupWidgetAutostart),
+                    ))
+                    .push(widget::settings::item(
+                        fl!("setup-autostart-entry"),
+                        widget::toggler(self.setup_install_autostart).on_toggle(Message::SetupInstallAutostart),
+                    ))
+                    .push(widget::text::caption(fl!(
+                        "setup-autostart-entry-description",
+                        path = setup::autostart_path().display().to_string()
+                    )));
+                if let Some(status) = &self.setup_status {
+                    content = content.push(widget::text::body(status.clone()));
+                }
+            }
+        }
+        
+        // === Navigation ===
+        let mut back = widget::button::standard(fl!("setup-back"));
+        if step.previous().is_some() {
+            back = back.on_press(Message::SetupBack);
+        }
+        let forward = if step.next().is_some() {
+            widget::button::suggested(fl!("setup-next")).on_press(Message::SetupNext)
+        } else {
+            widget::button::suggested(fl!("setup-finish")).on_press(Message::SetupFinish)
+        };
+        content = content
+            .push(widget::divider::horizontal::default())
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .push(widget::button::text(fl!("setup-skip")).on_press(Message::SetupCancel))
+                    .push(widget::horizontal_space())
+                    .push(back)
+                    .push(forward),
+            );
+        
+        widget::container(
+            widget::row()
+                .push(widget::scrollable(content).width(cosmic::iced::Length::Fill))
+                .push(self.preview_pane()),
+        )
+        .width(cosmic::iced::Length::Fill)
+        .height(cosmic::iced::Length::Fill)
+        .into()
+    }
+    
+    /// Render the position page.
+    ///
+    /// Shows the position picker (the connected outputs with a draggable
+    /// stand-in for the widget) and the options for when the widget is shown.
+    fn view_position(&self) -> Element<'_, Message> {
+        let picker: Element<'_, Message> = match &self.placement_image {
+            Some(handle) => cosmic::iced::widget::mouse_area(
+                widget::image(handle.clone())
+                    .width(cosmic::iced::Length::Fixed(MOCKUP_WIDTH as f32))
+                    .height(cosmic::iced::Length::Fixed(MOCKUP_HEIGHT as f32)),
+            )
+            .on_press(Message::PlacementPressed)
+            .on_release(Message::PlacementReleased)
+            .on_exit(Message::PlacementReleased)
+            .on_move(Message::PlacementMoved)
+            .into(),
+            None => widget::text::body(fl!("position-unavailable")).into(),
+        };
+        let output = if self.config.widget_output.is_empty() {
+            fl!("position-any-output")
+        } else {
+            self.config.widget_output.clone()
+        };
+        
+        let mut content = widget::column()
+            .spacing(12)
+            .padding(24)
+            .push(widget::text::title1(self.page.label()))
+            .push(widget::divider::horizontal::default())
+            
+            // === Position Picker ===
+            .push(widget::text::heading(fl!("widget-position")))
+            .push(widget::text::body(fl!("position-description")))
+            .push(picker)
+            .push(widget::text::body(fl!(
+                "position-summary",
+                corner = self.config.widget_anchor.label(),
+                x = self.config.widget_x,
+                y = self.config.widget_y,
+                output = output
+            )))
+            .push(widget::button::standard(fl!("position-reset")).on_press(Message::ResetPosition))
+            .push(widget::settings::item(
+                fl!("snap-to-edges"),
+                widget::toggler(self.config.snap_to_edges).on_toggle(Message::ToggleSnapToEdges),
+            ))
+            .push(widget::settings::item(
+                fl!("snap-grid"),
+                widget::text_input("0", &self.snap_grid_input).on_input(Message::UpdateSnapGrid),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Visibility ===
+            .push(widget::settings::item(
+                fl!("widget-autostart"),
+                widget::toggler(self.config.widget_autostart)
+                    .on_toggle(Message::ToggleWidgetAutostart),
+            ))
+            .push(widget::settings::item(
+                fl!("click-through"),
+                widget::toggler(self.config.click_through)
+                    .on_toggle(Message::ToggleClickThrough),
+            ))
+            .push(widget::text::body(fl!("click-through-description")))
+            .push(widget::settings::item(
+                fl!("keyboard-positioning"),
+                widget::toggler(self.config.keyboard_positioning)
+                    .on_toggle(Message::ToggleKeyboardPositioning),
+            ))
+            .push(widget::text::body(fl!("keyboard-positioning-description")))
+            .push(widget::settings::item(
+                fl!("auto-hide-fullscreen"),
+                widget::toggler(self.config.auto_hide_fullscreen)
+                    .on_toggle(Message::ToggleAutoHideFullscreen),
+            ))
+            .push(widget::settings::item(
+                fl!("auto-hide-maximized"),
+                widget::toggler(self.config.auto_hide_maximized)
+                    .on_toggle(Message::ToggleAutoHideMaximized),
+            ))
+            .push(widget::divider::horizontal::default())
+            
+            // === Schedules ===
+            .push(widget::text::heading(fl!("schedules")))
+            .push(widget::text::body(fl!("schedules-description")));
+        
+        // Two rows per schedule: name, times, opacity; then hidden sections
+        for (index, schedule) in self.config.schedules.iter().enumerate() {
+            let mut times = widget::row()
+                .spacing(8)
+                .padding([4, 0])
+                .push(
+                    widget::text_input(fl!("schedule-label"), &schedule.label)
+                        .on_input(move |value| Message::UpdateScheduleLabel(index, value))
+                        .width(cosmic::iced::Length::FillPortion(3)),
+                )
+                .push(
+                    widget::text_input("22:00", &schedule.start)
+                        .on_input(move |value| Message::UpdateScheduleStart(index, value))
+                        .width(cosmic::iced::Length::FillPortion(1)),
+                )
+                .push(widget::text::body("–"))
+                .push(
+                    widget::text_input("07:00", &schedule.end)
+                        .on_input(move |value| Message::UpdateScheduleEnd(index, value))
+                        .width(cosmic::iced::Length::FillPortion(1)),
+                )
+                .push(widget::dropdown(
+                    SCHEDULE_OPACITY_LABELS,
+                    SCHEDULE_OPACITIES.iter().position(|opacity| *opacity == schedule.opacity),
+                    move |choice| Message::SetScheduleOpacity(index, choice),
+                ));
+            if parse_time(&schedule.start).is_none() || parse_time(&schedule.end).is_none() {
+                times = times.push(widget::text::caption(fl!("schedule-invalid-time")));
+            }
+            
+            let hidden = if schedule.hidden_sections.is_empty() {
+                fl!("schedule-hides-nothing")
+            } else {
+                let names: Vec<&str> = schedule.hidden_sections.iter().map(WidgetSection::label).collect();
+                fl!("schedule-hides", sections = names.join(", "))
+            };
+            content = content
+                .push(
+                    times.push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::RemoveSchedule(index))
+                            .padding(4),
+                    ),
+                )
+                .push(
+                    widget::row()
+                        .spacing(8)
+                        .push(widget::text::caption(hidden).width(cosmic::iced::Length::Fill))
+                        .push(widget::dropdown(
+                            &self.schedule_section_labels,
+                            None,
+                            move |choice| Message::ToggleScheduleSection(index, choice),
+                        )),
+                );
+        }
+        
+        content = content.push(
+            widget::row().push(widget::button::standard(fl!("schedule-add")).on_press(Message::AddSchedule)),
+        );
+        
+        widget::container(widget::scrollable(content))
+            .width(cosmic::iced::Length::Fill)
+            .height(cosmic::iced::Length::Fill)
+            .into()
+    }
+    
+    /// Move the widget to follow the pointer while its proxy is dragged.
+    ///
+    /// Writes output, anchor and offsets together so the widget never sees
+    /// a half-updated position.
+    fn drag_placement(&mut self) {
+        let Some((grab_x, grab_y)) = self.placement_grab else {
+            return;
+        };
+        let mockup = Mockup::new(&self.cached_outputs);
+        let point = (self.placement_cursor.x as f64 - grab_x, self.placement_cursor.y as f64 - grab_y);
+        let placement = mockup.place(point, preview::widget_size(&self.config));
+        
+        if placement.output != self.config.widget_output
+            || placement.anchor != self.config.widget_anchor
+            || placement.x != self.config.widget_x
+            || placement.y != self.config.widget_y
+        {
+            self.config.widget_output = placement.output;
+            self.config.widget_anchor = placement.anchor;
+            self.config.widget_x = placement.x;
+            self.config.widget_y = placement.y;
+            self.save_config();
+        }
+    }
+    
+    /// Render the diagnostics page.
+    ///
+    /// Shows the installed and running versions, widget process status,
+    /// detected GPU vendor and read method, GPU tools, every hwmon
+    /// temperature input (and the ones matched for CPU/GPU), network
+    /// interfaces, the last weather fetch, Cider connectivity, and recent
+    /// errors.
+    fn view_diagnostics(&self) -> Element<'_, Message> {
+        let mut content = widget::column()
+            .spacing(12)
+            .padding(24)
+            .push(widget::text::title1(self.page.label()))
+            .push(widget::divider::horizontal::default())
+            .push(widget::text::heading(fl!("diagnostics")))
+            .push(widget::settings::item(
+                fl!("diag-version"),
+                widget::text::body(env!("CARGO_PKG_VERSION")),
+            ));
+        
+        // === Widget Process ===
+        let process_status = match (&self.widget_status, self.widget_running) {
+            (Some(status), true) if status.age_secs() <= WidgetStatus::STALE_AFTER_SECS => {
+                fl!("diag-process-running", pid = status.pid)
+            }
+            (Some(status), true) => fl!("diag-process-no-status", seconds = status.age_secs()),
+            (None, true) => fl!("diag-process-starting"),
+            (_, false) => fl!("diag-process-not-running"),
+        };
+        content = content.push(widget::settings::item(
+            fl!("diag-widget-process"),
+            widget::text::body(process_status),
+        ));
+        
+        let Some(status) = &self.widget_status else {
+            return widget::container(widget::scrollable(
+                content
+                    .push(widget::text::body(fl!("diag-no-status")))
+                    .push(widget::button::standard(fl!("diag-refresh")).on_press(Message::RefreshDiagnostics)),
+            ))
+            .width(cosmic::iced::Length::Fill)
+            .height(cosmic::iced::Length::Fill)
+            .into();
+        };
+        
+        // === Widget Version ===
+        // Differs from the settings version while an old widget is still running
+        let widget_version = match (status.version.as_str(), &status.latest_version) {
+            ("", _) => fl!("diag-unknown"),
+            (version, Some(latest)) => fl!("diag-widget-version-latest", version = version, latest = latest.as_str()),
+            (version, None) => version.to_string(),
+        };
+        content = content.push(widget::settings::item(
+            fl!("diag-widget-version"),
+            widget::text::body(widget_version),
+        ));
+        
+        // === Hardware ===
+        let sensors = if status.sensors.is_empty() {
+            fl!("diag-none-found")
+        } else {
+            status.sensors.join(", ")
+        };
+        let matched = |sensor: &Option<String>| sensor.clone().unwrap_or_else(|| fl!("diag-none-matched"));
+        let gpu_method = if status.gpu_method.is_empty() {
+            fl!("diag-unknown")
+        } else {
+            status.gpu_method.clone()
+        };
+        content = content
+            .push(widget::settings::item(
+                fl!("diag-gpu-vendor"),
+                widget::text::body(status.gpu_vendor.clone()),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-gpu-method"),
+                widget::text::body(gpu_method),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-gpu-card"),
+                widget::text::body(matched(&status.gpu_card)),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-sensors"),
+                widget::text::body(sensors),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-cpu-sensor"),
+                widget::text::body(matched(&status.cpu_sensor)),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-gpu-sensor"),
+                widget::text::body(matched(&status.gpu_sensor)),
+            ));
+        
+        // === GPU Tools ===
+        if !status.gpu_tools.is_empty() {
+            content = content
+                .push(widget::divider::horizontal::default())
+                .push(widget::text::heading(fl!("diag-gpu-tools")));
+            for tool in &status.gpu_tools {
+                let state = if tool.found {
+                    fl!("diag-tool-found", path = tool.path.as_str())
+                } else {
+                    fl!("diag-tool-missing", path = tool.path.as_str())
+                };
+                content = content.push(widget::settings::item(tool.name.clone(), widget::text::body(state)));
+            }
+        }
+        
+        // === Temperature Inputs (hwmon) ===
+        if !status.hwmon_readings.is_empty() {
+            content = content
+                .push(widget::divider::horizontal::default())
+                .push(widget::text::heading(fl!("diag-hwmon")));
+            for reading in &status.hwmon_readings {
+                let temperature = reading
+                    .temperature
+                    .map(|t| format!("{:.1} °C", t))
+                    .unwrap_or_else(|| fl!("diag-unreadable"));
+                content = content.push(widget::settings::item(
+                    format!("{} · {}", reading.chip, reading.label),
+                    widget::text::body(temperature),
+                ));
+            }
+        }
+        
+        // === Network Interfaces ===
+        if !status.network_interfaces.is_empty() {
+            content = content
+                .push(widget::divider::horizontal::default())
+                .push(widget::text::heading(fl!("diag-network-interfaces")));
+            for interface in &status.network_interfaces {
+                content = content.push(widget::settings::item(
+                    interface.name.clone(),
+                    widget::text::body(format!(
+                        "↓ {}  ↑ {}",
+                        format_traffic(interface.total_received),
+                        format_traffic(interface.total_transmitted)
+                    )),
+                ));
+            }
+        }
+        content = content.push(widget::divider::horizontal::default());
+        
+        // === Weather & Cider ===
+        let mut weather = status
+            .weather_last_result
+            .clone()
+            .unwrap_or_else(|| fl!("diag-weather-none"));
+        if let Some(retry) = status.weather_next_retry_secs {
+            weather = fl!("diag-weather-retry", result = weather, seconds = retry);
+        }
+        content = content
+            .push(widget::settings::item(
+                fl!("diag-weather"),
+                widget::text::body(weather),
+            ))
+            .push(widget::settings::item(
+                fl!("diag-cider"),
+                widget::text::body(status.cider_status.clone()),
+            ));
+        
+        // === Monitor Health (watchdog) ===
+        if !status.monitor_health.is_empty() {
+            content = content
+                .push(widget::divider::horizontal::default())
+                .push(widget::text::heading(fl!("diag-monitor-health")));
+            for health in &status.monitor_health {
+                let mut state = if health.panicked {
+                    fl!("diag-health-crashed")
+                } else if health.stopped {
+                    fl!("diag-health-stopped")
+                } else if health.starved {
+                    fl!("diag-health-starved", seconds = health.seconds_since_update)
+                } else if health.stale {
+                    fl!("diag-health-stalled", seconds = health.seconds_since_update)
+                } else {
+                    fl!("diag-health-ok", seconds = health.seconds_since_update)
+                };
+                if health.restarts > 0 {
+                    state = fl!("diag-health-restarted", state = state, count = health.restarts);
+                }
+                content = content.push(widget::settings::item(health.name.clone(), widget::text::body(state)));
+            }
+        }
+        
+        content = content
+            .push(widget::divider::horizontal::default())
+            .push(widget::text::heading(fl!("diag-recent-errors")));
+        
+        // === Recent Errors (newest first) ===
+        if status.recent_errors.is_empty() {
+            content = content.push(widget::text::body(fl!("diag-no-errors")));
+        } else {
+            for error in status.recent_errors.iter().rev() {
+                content = content.push(widget::text::body(format!(
+                    "{}  [{}] {}",
+                    format_local_time(error.timestamp),
+                    error.source,
+                    error.message
+                )));
+            }
+        }
+        
+        content = content.push(
+            widget::button::standard(fl!("diag-refresh")).on_press(Message::RefreshDiagnostics),
+        );
+        
+        widget::container(widget::scrollable(content))
+            .width(cosmic::iced::Length::Fill)
+            .height(cosmic::iced::Length::Fill)
+            .into()
+    }
+}
+
+// ============================================================================
+// COSMIC Application Implementation
+// ============================================================================
+
+impl Application for SettingsApp {
+    type Executor = cosmic::executor::Default;
+    type Flags = ();
+    type Message = Message;
+
+    /// Settings app ID - distinct from the main applet to allow separate windows.
+    const APP_ID: &'static str = "com.github.zoliviragh.CosmicMonitor.Settings";
+
+    fn core(&self) -> &cosmic::app::Core {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut cosmic::app::Core {
+        &mut self.core
+    }
+
+    /// Handle window close - disable widget movement mode before closing.
+    fn on_close_requested(&self, _id: cosmic::iced::window::Id) -> Option<Message> {
+        Some(Message::CloseRequested)
+    }
+
+    /// Initialize the settings application.
+    ///
+    /// - Loads current configuration
+    /// - Migrates old configs (adds new sections if missing)
+    /// - Enables widget movement mode
+    /// - Loads cached device information
+    fn init(
+        core: cosmic::app::Core,
+        _flags: Self::Flags,
+    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
+        // Nothing stored yet: start with the setup wizard
+        let first_run = setup::is_first_run();
+        
+        // Load config from the main app's config path (not the settings app path)
+        let config_handler = cosmic_config::Config::new(
+            "com.github.zoliviragh.CosmicMonitor",
+            Config::VERSION,
+        )
+        .ok();
+
+        // Fields that can't be read fall back to their defaults
+        let (mut config, mut config_issues) = config_handler
+            .as_ref()
+            .map(|context| match Config::get_entry(context) {
+                Ok(config) => (config, Vec::new()),
+                Err((errors, config)) => (config, errors.iter().map(ToString::to_string).collect()),
+            })
+            .unwrap_or_default();
+
+        // === Config Migration ===
+        // When new sections are added to the app, existing configs won't have them.
+        // This ensures users don't lose access to new features.
+        config.migrate_section_order();
+        // Out-of-range values are fixed here and written back below
+        config_issues.extend(config.validate());
+        for issue in &config_issues {
+            log::warn!("Invalid stored setting: {}", issue);
+        }
+
+        // Enable widget movement while settings window is open
+        // This allows users to drag the widget to reposition it.
+        // On the first run the wizard writes the complete config instead.
+        config.widget_movable = true;
+        if !first_run {
+            if let Some(ref handler) = config_handler {
+                let _ = config.write_entry(handler);
+            }
+        }
+
+        // One navigation entry per page; the wizard hides the bar until it's done
+        let page = if first_run { SettingsPage::Setup } else { SettingsPage::General };
+        let mut nav = widget::nav_bar::Model::default();
+        for nav_page in SettingsPage::NAV {
+            let entity = nav
+                .insert()
+                .text(nav_page.label())
+                .icon(widget::icon::from_name(nav_page.icon()))
+                .data::<SettingsPage>(nav_page);
+            if nav_page == SettingsPage::General {
+                entity.activate();
+            }
+        }
+
+        // Initialize text inputs from current config values
+        let interval_input = format!("{}", config.update_interval_ms);
+        let weather_api_key_input = config.weather_api_key.clone();
+        let weather_location_input = config.weather_location.clone();
+        let max_notifications_input = config.max_notifications.to_string();
+        let notification_history_count_input = config.notification_history_count.to_string();
+        let notification_history_days_input = config.notification_history_days.to_string();
+        let cider_api_token_input = config.cider_api_token.clone();
+        let custom_interval_inputs = config.custom_commands.iter().map(|c| c.interval_secs.to_string()).collect();
+        let font_size_inputs = font_size_inputs(&config);
+        let clock_format_input = config.clock_format.clone();
+        let date_format_input = config.date_format.clone();
+        let calendar_max_events_input = config.calendar_max_events.to_string();
+        let calendar_refresh_input = config.calendar_refresh_minutes.to_string();
+        let health_refresh_input = config.health_refresh_minutes.to_string();
+        let containers_refresh_input = config.containers_refresh_minutes.to_string();
+        let vms_refresh_input = config.vms_refresh_minutes.to_string();
+        let storage_min_free_input = config.storage_min_free_percent.to_string();
+        let storage_max_inode_input = config.storage_max_inode_percent.to_string();
+        let smart_refresh_input = config.smart_refresh_minutes.to_string();
+        let storage_pools_refresh_input = config.storage_pools_refresh_minutes.to_string();
+        let feeds_refresh_input = config.feeds_refresh_minutes.to_string();
+        let ticker_max_input = config.ticker_max_symbols.to_string();
+        let ticker_refresh_input = config.ticker_refresh_minutes.to_string();
+        let snap_grid_input = config.snap_grid.to_string();
+        let marquee_speed_input = config.marquee_speed.to_string();
+        let marquee_pause_input = config.marquee_pause_ms.to_string();
+        let network_graph_input = config.network_graph_seconds.to_string();
+        let temp_graph_input = config.temp_graph_minutes.to_string();
+        let threshold_inputs = ThresholdMetric::ALL
+            .iter()
+            .map(|metric| {
+                let thresholds = config.thresholds.get(*metric);
+                (thresholds.warning.to_string(), thresholds.critical.to_string())
+            })
+            .collect();
+        let bar_color_inputs = Level::ALL.iter().map(|level| config.bar_colors.get(*level).to_string()).collect();
+        let record_interval_input = config.record_interval_secs.to_string();
+        let record_retention_input = config.record_retention_days.to_string();
+        let bundle_path_input = Bundle::default_path().display().to_string();
+        let profile_path_input = profiles::default_path().display().to_string();
+        let setup_config = config.clone();
+        
+        // Load cached battery devices from widget's cache file
+        let cache = WidgetCache::load();
+        let cached_devices = cache.battery_devices.clone();
+        let cached_outputs = cache.outputs.clone();
+
+        let mut app = SettingsApp {
+            core,
+            config,
+            config_handler,
+            interval_input,
+            weather_api_key_input,
+            weather_location_input,
+            max_notifications_input,
+            notification_history_count_input,
+            notification_history_days_input,
+            cider_api_token_input,
+            custom_interval_inputs,
+            font_size_inputs,
+            installed_fonts: fonts::installed_families(),
+            clock_format_input,
+            date_format_input,
+            calendar_max_events_input,
+            calendar_refresh_input,
+            health_refresh_input,
+            containers_refresh_input,
+            vms_refresh_input,
+            storage_min_free_input,
+            storage_max_inode_input,
+            smart_refresh_input,
+            storage_pools_refresh_input,
+            feeds_refresh_input,
+            ticker_max_input,
+            ticker_refresh_input,
+            snap_grid_input,
+            marquee_speed_input,
+            marquee_pause_input,
+            network_graph_input,
+            temp_graph_input,
+            temp_graph_mode_labels: TempGraphMode::ALL.iter().map(TempGraphMode::label).collect(),
+            threshold_inputs,
+            bar_color_inputs,
+            record_interval_input,
+            record_retention_input,
+            bundle_path_input,
+            bundle_status: None,
+            profile_name_input: String::new(),
+            profile_path_input,
+            saved_profiles: profiles::list(),
+            profile_status: None,
+            setup_step: SetupStep::Output,
+            setup_config,
+            setup_install_autostart: false,
+            setup_status: None,
+            picked_section: None,
+            layout_mode_labels: LayoutMode::ALL.iter().map(LayoutMode::label).collect(),
+            text_contrast_labels: TextContrast::ALL.iter().map(TextContrast::label).collect(),
+            ambient_source_labels: AmbientSource::ALL.iter().map(AmbientSource::label).collect(),
+            ambient_target_labels: AmbientTarget::ALL.iter().map(AmbientTarget::label).collect(),
+            calendar_source_labels: CalendarSource::ALL.iter().map(CalendarSource::label).collect(),
+            feed_kind_labels: FeedKind::ALL.iter().map(FeedKind::label).collect(),
+            ticker_provider_labels: TickerProvider::ALL.iter().map(TickerProvider::label).collect(),
+            air_quality_provider_labels: AirQualityProvider::ALL.iter().map(AirQualityProvider::label).collect(),
+            log_level_labels: LogLevel::ALL.iter().map(LogLevel::label).collect(),
+            memory_label_labels: MemoryLabel::ALL.iter().map(MemoryLabel::label).collect(),
+            scroll_action_labels: ScrollAction::ALL.iter().map(ScrollAction::label).collect(),
+            schedule_section_labels: Config::default().section_order.iter().map(WidgetSection::label).collect(),
+            card_opacity_labels: CARD_OPACITIES
+                .iter()
+                .map(|opacity| if *opacity == 0 { fl!("card-none") } else { format!("{}%", opacity) })
+                .collect(),
+            cached_devices,
+            cached_outputs,
+            page,
+            nav,
+            search_query: String::new(),
+            widget_status: None,
+            widget_running: false,
+            config_issues,
+            theme: CosmicTheme::load(),
+            preview: None,
+            preview_config: Config::default(),
+            placement_image: None,
+            placement_cursor: cosmic::iced::Point::ORIGIN,
+            placement_grab: None,
+        };
+        
+        // The weather icons need their font on disk, as in the widget
+        crate::widget::load_weather_font();
+        app.refresh_preview();
+
+        (app, Task::none())
+    }
+
+    /// Render the settings UI.
+    ///
+    /// The configuration is split into pages picked in the navigation bar,
+    /// each built only while it's shown:
+    /// - General (identity, clock and date, world clocks, calendar, profiles)
+    /// - Layout (display options, section order, layout bundles)
+    /// - Sections (utilization, temperatures, storage, battery, custom
+    ///   commands, system health, feeds, ticker, plugins, caffeine)
+    /// - Weather (weather display, air quality)
+    /// - Media (media player, audio output)
+    /// - Alerts (thresholds and colors, ambient status, notifications)
+    /// - Advanced (update interval, logging, stats, recording, priority)
+    ///
+    /// The Position and Diagnostics pages and the setup wizard are rendered
+    /// separately by `view_position()`, `view_diagnostics()` and
+    /// `view_setup()`.
+    fn view(&self) -> Element<Self::Message> {
+        if self.is_searching() {
+            return self.view_search();
+        }
+        let content = match self.page {
+            SettingsPage::General => self.view_general(),
+            SettingsPage::Layout => self.view_layout(),
+            SettingsPage::Sections => self.view_sections(),
+            SettingsPage::Weather => self.view_weather(),
+            SettingsPage::Media => self.view_media(),
+            SettingsPage::Alerts => self.view_alerts(),
+            SettingsPage::Advanced => self.view_advanced(),
+            SettingsPage::Position => return self.view_position(),
+            SettingsPage::Diagnostics => return self.view_diagnostics(),
+            SettingsPage::Setup => return self.view_setup(),
+        };
+        self.config_page(content)
+    }
+
+    /// The pages in the navigation bar (hidden while the setup wizard runs).
+    fn nav_model(&self) -> Option<&widget::nav_bar::Model> {
+        (self.page != SettingsPage::Setup).then_some(&self.nav)
+    }
+
+    /// Search field at the end of the header bar (not in the setup wizard).
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        if self.page == SettingsPage::Setup {
+            return Vec::new();
+        }
+        vec![widget::search_input(fl!("search-placeholder"), &self.search_query)
+            .on_input(Message::UpdateSearch)
+            .on_clear(Message::ClearSearch)
+            .width(cosmic::iced::Length::Fixed(240.0))
+            .into()]
+    }
+
+    /// Switch to the page picked in the navigation bar.
+    fn on_nav_select(&mut self, id: widget::nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
+        match self.nav.data::<SettingsPage>(id).copied() {
+            Some(page) => self.update(Message::ShowPage(page)),
+            None => Task::none(),
+        }
+    }
+
+    /// Process messages and update application state.
+    ///
+    /// Most messages simply update a config field and save. Text inputs
+    /// validate their content before updating (e.g., interval must be 100-10000ms).
+    fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
+        match message {
+            // === Config Sync ===
+            Message::UpdateConfig(mut config) => {
+                let issues = config.validate();
+                if !issues.is_empty() {
+                    self.config_issues = issues;
+                }
+                self.config = config;
+            }
+            Message::DismissConfigIssues => {
+                self.config_issues.clear();
+            }
+            
+            // === Window Close ===
+            Message::CloseRequested => {
+                // Disable widget movement when settings closes
+                self.config.widget_movable = false;
+                self.save_config();
+                return cosmic::iced::window::get_latest()
+                    .and_then(|id| cosmic::iced::window::close(id));
+            }
+            
+            // === Simple Toggle Messages ===
+            // Each toggle updates config and saves immediately
+            Message::ToggleCpu(enabled) => {
+                self.config.show_cpu = enabled;
+                self.save_config();
+            }
+            Message::ToggleCpuFrequency(enabled) => {
+                self.config.show_cpu_frequency = enabled;
+                self.save_config();
+            }
+            Message::ToggleCpuGovernor(enabled) => {
+                self.config.show_cpu_governor = enabled;
+                self.save_config();
+            }
+            Message::ToggleHardwareInfo(enabled) => {
+                self.config.show_hardware_info = enabled;
+                self.save_config();
+            }
+            Message::ToggleCpuGovernorClick(enabled) => {
+                self.config.cpu_governor_click = enabled;
+                self.save_config();
+            }
+            Message::ToggleMemory(enabled) => {
+                self.config.show_memory = enabled;
+                self.save_config();
+            }
+            Message::ToggleNetwork(enabled) => {
+                self.config.show_network = enabled;
+                self.save_config();
+            }
+            Message::ToggleNetworkBits(enabled) => {
+                self.config.network_rate_bits = enabled;
+                self.save_config();
+            }
+            Message::ToggleNetworkGraph(enabled) => {
+                self.config.show_network_graph = enabled;
+                self.save_config();
+            }
+            Message::UpdateNetworkGraphWindow(value) => {
+                self.network_graph_input = value.clone();
+                // Validate: 10 seconds to 1 hour
+                if let Ok(seconds) = value.parse::<u32>() {
+                    if NETWORK_GRAPH_SECONDS.contains(&seconds) {
+                        self.config.network_graph_seconds = seconds;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleDisk(enabled) => {
+                self.config.show_disk = enabled;
+                self.save_config();
+            }
+            Message::ToggleStorage(enabled) => {
+                self.config.show_storage = enabled;
+                self.save_config();
+            }
+            Message::UpdateStorageMinFree(value) => {
+                self.storage_min_free_input = value.clone();
+                // Validate: 0 (off) to 50 percent
+                if let Ok(percent) = value.parse::<u32>() {
+                    if STORAGE_MIN_FREE_PERCENT.contains(&percent) {
+                        self.config.storage_min_free_percent = percent;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateStorageMaxInode(value) => {
+                self.storage_max_inode_input = value.clone();
+                // Validate: 0 (off) to 100 percent
+                if let Ok(percent) = value.parse::<u32>() {
+                    if STORAGE_MAX_INODE_PERCENT.contains(&percent) {
+                        self.config.storage_max_inode_percent = percent;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleStorageAlerts(enabled) => {
+                self.config.storage_alerts = enabled;
+                self.save_config();
+            }
+            Message::ToggleSmart(enabled) => {
+                self.config.show_smart = enabled;
+                self.save_config();
+            }
+            Message::UpdateSmartRefresh(value) => {
+                self.smart_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.smart_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleStoragePools(enabled) => {
+                self.config.show_storage_pools = enabled;
+                self.save_config();
+            }
+            Message::UpdateStoragePoolsRefresh(value) => {
+                self.storage_pools_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.storage_pools_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleGpu(enabled) => {
+                self.config.show_gpu = enabled;
+                self.save_config();
+            }
+            Message::ToggleGpuProcesses(enabled) => {
+                self.config.show_gpu_processes = enabled;
+                self.save_config();
+            }
+            Message::ToggleGpuDetails(enabled) => {
+                self.config.show_gpu_details = enabled;
+                self.save_config();
+            }
+            Message::TogglePsi(enabled) => {
+                self.config.show_psi = enabled;
+                self.save_config();
+            }
+            Message::ToggleCpuTemp(enabled) => {
+                self.config.show_cpu_temp = enabled;
+                self.save_config();
+            }
+            Message::ToggleGpuTemp(enabled) => {
+                self.config.show_gpu_temp = enabled;
+                self.save_config();
+            }
+            Message::ToggleCircularTempDisplay(enabled) => {
+                self.config.use_circular_temp_display = enabled;
+                self.save_config();
+            }
+            Message::ToggleTempsOnUsageBars(enabled) => {
+                self.config.temps_on_usage_bars = enabled;
+                self.save_config();
+            }
+            Message::ToggleHottestCore(enabled) => {
+                self.config.show_hottest_core = enabled;
+                self.save_config();
+            }
+            Message::ToggleThrottling(enabled) => {
+                self.config.show_throttling = enabled;
+                self.save_config();
+            }
+            Message::ToggleTempGraph(enabled) => {
+                self.config.show_temp_graph = enabled;
+                self.save_config();
+            }
+            Message::UpdateTempGraphWindow(value) => {
+                self.temp_graph_input = value.clone();
+                // Validate: 1 minute to 2 hours
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if TEMP_GRAPH_MINUTES.contains(&minutes) {
+                        self.config.temp_graph_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::SetTempGraphMode(index) => {
+                if let Some(mode) = TempGraphMode::ALL.get(index) {
+                    self.config.temp_graph_mode = *mode;
+                    self.save_config();
+                }
+            }
+            Message::ToggleClock(enabled) => {
+                self.config.show_clock = enabled;
+                self.save_config();
+            }
+            Message::ToggleDate(enabled) => {
+                self.config.show_date = enabled;
+                self.save_config();
+            }
+            Message::ToggleHostname(enabled) => {
+                self.config.show_hostname = enabled;
+                self.save_config();
+            }
+            Message::ToggleUsername(enabled) => {
+                self.config.show_username = enabled;
+                self.save_config();
+            }
+            Message::UpdateIdentityLabel(value) => {
+                self.config.identity_label = value;
+                self.save_config();
+            }
+            Message::Toggle24HourTime(enabled) => {
+                self.config.use_24hour_time = enabled;
+                self.save_config();
+            }
+            Message::UpdateClockFormat(value) => {
+                self.clock_format_input = value.clone();
+                if is_valid_time_format(&value) {
+                    self.config.clock_format = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateDateFormat(value) => {
+                self.date_format_input = value.clone();
+                if is_valid_time_format(&value) {
+                    self.config.date_format = value;
+                    self.save_config();
+                }
+            }
+            Message::AddWorldClock => {
+                self.config.world_clocks.push(WorldClock::default());
+                self.save_config();
+            }
+            Message::RemoveWorldClock(index) => {
+                if index < self.config.world_clocks.len() {
+                    self.config.world_clocks.remove(index);
+                    self.save_config();
+                }
+            }
+            Message::UpdateWorldClockZone(index, value) => {
+                if let Some(clock) = self.config.world_clocks.get_mut(index) {
+                    clock.zone = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateWorldClockLabel(index, value) => {
+                if let Some(clock) = self.config.world_clocks.get_mut(index) {
+                    clock.label = value;
+                    self.save_config();
+                }
+            }
+            Message::ToggleCalendar(enabled) => {
+                self.config.show_calendar = enabled;
+                self.save_config();
+            }
+            Message::SetCalendarSource(index) => {
+                if let Some(source) = CalendarSource::ALL.get(index) {
+                    self.config.calendar_source = *source;
+                    self.save_config();
+                }
+            }
+            Message::UpdateCalendarIcsPath(value) => {
+                self.config.calendar_ics_path = value;
+                self.save_config();
+            }
+            Message::UpdateCalendarMaxEvents(value) => {
+                self.calendar_max_events_input = value.clone();
+                // Validate: 1-10 events
+                if let Ok(max) = value.parse::<u32>() {
+                    if CALENDAR_MAX_EVENTS.contains(&max) {
+                        self.config.calendar_max_events = max;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateCalendarRefresh(value) => {
+                self.calendar_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.calendar_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::TogglePercentages(enabled) => {
+                self.config.show_percentages = enabled;
+                self.save_config();
+            }
+            Message::SetMemoryLabel(index) => {
+                if let Some(label) = MemoryLabel::ALL.get(index) {
+                    self.config.memory_label = *label;
+                    self.save_config();
+                }
+            }
+            Message::UpdateWarningThreshold(metric, value) => {
+                if let Some(index) = ThresholdMetric::ALL.iter().position(|m| *m == metric) {
+                    if let Some(inputs) = self.threshold_inputs.get_mut(index) {
+                        inputs.0 = value.clone();
+                    }
+                }
+                // Validate: 0 to 1000, not above the critical threshold
+                let thresholds = self.config.thresholds.get_mut(metric);
+                if let Ok(warning) = value.trim().parse::<u32>() {
+                    if warning <= MAX_THRESHOLD && warning <= thresholds.critical {
+                        thresholds.warning = warning;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateCriticalThreshold(metric, value) => {
+                if let Some(index) = ThresholdMetric::ALL.iter().position(|m| *m == metric) {
+                    if let Some(inputs) = self.threshold_inputs.get_mut(index) {
+                        inputs.1 = value.clone();
+                    }
+                }
+                // Validate: 0 to 1000, not below the warning threshold
+                let thresholds = self.config.thresholds.get_mut(metric);
+                if let Ok(critical) = value.trim().parse::<u32>() {
+                    if critical <= MAX_THRESHOLD && critical >= thresholds.warning {
+                        thresholds.critical = critical;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateBarColor(level, value) => {
+                if let Some(index) = Level::ALL.iter().position(|l| *l == level) {
+                    if let Some(input) = self.bar_color_inputs.get_mut(index) {
+                        *input = value.clone();
+                    }
+                }
+                if parse_hex_color(&value).is_some() {
+                    *self.config.bar_colors.get_mut(level) = value.trim().to_string();
+                    self.save_config();
+                }
+            }
+            Message::ToggleTooltips(enabled) => {
+                self.config.show_tooltips = enabled;
+                self.save_config();
+            }
+            Message::TogglePanelReadout(enabled) => {
+                self.config.show_panel_readout = enabled;
+                self.save_config();
+            }
+            Message::ToggleAnimations(enabled) => {
+                self.config.enable_animations = enabled;
+                self.save_config();
+            }
+            Message::SetLayoutMode(index) => {
+                if let Some(mode) = LayoutMode::ALL.get(index) {
+                    self.config.layout_mode = *mode;
+                    self.save_config();
+                }
+            }
+            Message::SetColumns(index) => {
+                self.config.columns = index as u32 + 1;
+                self.save_config();
+            }
+            Message::SetTextContrast(index) => {
+                if let Some(contrast) = TextContrast::ALL.get(index) {
+                    self.config.text_contrast = *contrast;
+                    self.save_config();
+                }
+            }
+            Message::UpdateFontFamily(index, value) => {
+                if let Some(role) = TextRole::ALL.get(index) {
+                    self.config.fonts.get_mut(*role).family = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateFontSize(index, value) => {
+                let Some(role) = TextRole::ALL.get(index) else {
+                    return Task::none();
+                };
+                if let Some(input) = self.font_size_inputs.get_mut(index) {
+                    *input = value.clone();
+                }
+                // Validate: 6 to 96 points
+                if let Ok(size) = value.parse::<u32>() {
+                    if FONT_SIZE.contains(&size) {
+                        self.config.fonts.get_mut(*role).size = size;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::SetAmbientSource(index) => {
+                if let Some(source) = AmbientSource::ALL.get(index) {
+                    self.config.ambient_source = *source;
+                    self.save_config();
+                }
+            }
+            Message::SetAmbientTarget(index) => {
+                if let Some(target) = AmbientTarget::ALL.get(index) {
+                    self.config.ambient_target = *target;
+                    self.save_config();
+                }
+            }
+            Message::ToggleBatterySection(enabled) => {
+                self.config.show_battery = enabled;
+                self.save_config();
+            }
+            Message::ToggleSolaarIntegration(enabled) => {
+                self.config.enable_solaar_integration = enabled;
+                self.save_config();
+            }
+            Message::TogglePowerProfile(enabled) => {
+                self.config.show_power_profile = enabled;
+                self.save_config();
+            }
+            
+            // === Battery Device Cache ===
+            Message::RemoveCachedDevice(index) => {
+                if index < self.cached_devices.len() {
+                    let removed = self.cached_devices.remove(index);
+                    // Persist to cache file (the widget may have updated it meanwhile)
+                    WidgetCache::modify(|cache| {
+                        cache
+                            .battery_devices
+                            .retain(|device| device.name != removed.name || device.kind != removed.kind);
+                    });
+                }
+            }
+            
+            // === Notification Settings ===
+            Message::ToggleNotifications(enabled) => {
+                self.config.show_notifications = enabled;
+                self.save_config();
+            }
+            Message::UpdateMaxNotifications(value) => {
+                // Validate: must be 1-20
+                if let Ok(max) = value.parse::<usize>() {
+                    if MAX_NOTIFICATIONS.contains(&max) {
+                        self.config.max_notifications = max;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleCompactNotifications(enabled) => {
+                self.config.compact_notifications = enabled;
+                self.save_config();
+            }
+            Message::ToggleNotificationHistory(enabled) => {
+                self.config.notification_history = enabled;
+                self.save_config();
+            }
+            Message::UpdateNotificationHistoryCount(value) => {
+                self.notification_history_count_input = value.clone();
+                if let Ok(count) = value.parse::<usize>() {
+                    if NOTIFICATION_HISTORY_COUNT.contains(&count) {
+                        self.config.notification_history_count = count;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateNotificationHistoryDays(value) => {
+                self.notification_history_days_input = value.clone();
+                if let Ok(days) = value.parse::<u32>() {
+                    if NOTIFICATION_HISTORY_DAYS.contains(&days) {
+                        self.config.notification_history_days = days;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ClearNotificationHistory => {
+                if let Err(e) = notification_history::clear() {
+                    log::warn!("Failed to delete notification history: {}", e);
+                }
+                // The running widget would write its list back otherwise
+                if let Err(e) = control::send_command(ControlCommand::ClearNotifications) {
+                    log::debug!("No running widget to clear notifications in: {}", e);
+                }
+            }
+            
+            // === Scroll Bindings ===
+            Message::SetScrollAction(section, index) => {
+                if let Some(action) = ScrollAction::ALL.get(index) {
+                    match section {
+                        WidgetSection::Clock => self.config.clock_scroll = *action,
+                        WidgetSection::Media => self.config.media_scroll = *action,
+                        WidgetSection::Audio => self.config.audio_scroll = *action,
+                        WidgetSection::Notifications => self.config.notifications_scroll = *action,
+                        _ => return Task::none(),
+                    }
+                    self.save_config();
+                }
+            }
+            
+            // === Media Settings ===
+            Message::ToggleMedia(enabled) => {
+                self.config.show_media = enabled;
+                self.save_config();
+            }
+            Message::UpdateCiderApiToken(value) => {
+                self.cider_api_token_input = value.clone();
+                self.config.cider_api_token = value;
+                self.save_config();
+            }
+            Message::ToggleMediaMarquee(enabled) => {
+                self.config.media_marquee = enabled;
+                self.save_config();
+            }
+            Message::ToggleLyrics(enabled) => {
+                self.config.show_lyrics = enabled;
+                self.save_config();
+            }
+            Message::UpdateMarqueeSpeed(value) => {
+                self.marquee_speed_input = value.clone();
+                if let Ok(speed) = value.parse::<u32>() {
+                    if MARQUEE_SPEED.contains(&speed) {
+                        self.config.marquee_speed = speed;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateMarqueePause(value) => {
+                self.marquee_pause_input = value.clone();
+                if let Ok(millis) = value.parse::<u32>() {
+                    if MARQUEE_PAUSE_MS.contains(&millis) {
+                        self.config.marquee_pause_ms = millis;
+                        self.save_config();
+                    }
+                }
             }
             
-            // === Window Close ===
-            Message::CloseRequested => {
-                // Disable widget movement when settings closes
-                self.config.widget_movable = false;
+            // === Audio Settings ===
+            Message::ToggleAudio(enabled) => {
+                self.config.show_audio = enabled;
                 self.save_config();
-                return cosmic::iced::window::get_latest()
-                    .and_then(|id| cosmic::iced::window::close(id));
             }
             
-            // === Simple Toggle Messages ===
-            // Each toggle updates config and saves immediately
-            Message::ToggleCpu(enabled) => {
-                self.config.show_cpu = enabled;
+            // === Custom Commands, System Health & Plugins ===
+            Message::ToggleSystemHealth(enabled) => {
+                self.config.show_system_health = enabled;
                 self.save_config();
             }
-            Message::ToggleMemory(enabled) => {
-                self.config.show_memory = enabled;
+            Message::UpdateHealthUpdateCommand(value) => {
+                self.config.health_update_command = value;
                 self.save_config();
             }
-            Message::ToggleNetwork(enabled) => {
-                self.config.show_network = enabled;
-                self.save_config();
+            Message::UpdateHealthRefresh(value) => {
+                self.health_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.health_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
             }
-            Message::ToggleDisk(enabled) => {
-                self.config.show_disk = enabled;
+            Message::ToggleContainers(enabled) => {
+                self.config.show_containers = enabled;
                 self.save_config();
             }
-            Message::ToggleStorage(enabled) => {
-                self.config.show_storage = enabled;
+            Message::ToggleContainersBusiest(enabled) => {
+                self.config.containers_show_busiest = enabled;
                 self.save_config();
             }
-            Message::ToggleGpu(enabled) => {
-                self.config.show_gpu = enabled;
-                self.save_config();
+            Message::UpdateContainersRefresh(value) => {
+                self.containers_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.containers_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
             }
-            Message::ToggleCpuTemp(enabled) => {
-                self.config.show_cpu_temp = enabled;
+            Message::ToggleVms(enabled) => {
+                self.config.show_vms = enabled;
                 self.save_config();
             }
-            Message::ToggleGpuTemp(enabled) => {
-                self.config.show_gpu_temp = enabled;
+            Message::UpdateVmsUri(value) => {
+                self.config.vms_uri = value;
                 self.save_config();
             }
-            Message::ToggleCircularTempDisplay(enabled) => {
-                self.config.use_circular_temp_display = enabled;
+            Message::UpdateVmsRefresh(value) => {
+                self.vms_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.vms_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleKernelStats(enabled) => {
+                self.config.show_kernel_stats = enabled;
                 self.save_config();
             }
-            Message::ToggleClock(enabled) => {
-                self.config.show_clock = enabled;
+            Message::ToggleFeeds(enabled) => {
+                self.config.show_feeds = enabled;
                 self.save_config();
             }
-            Message::ToggleDate(enabled) => {
-                self.config.show_date = enabled;
+            Message::AddFeed => {
+                self.config.feeds.push(Feed::default());
                 self.save_config();
             }
-            Message::Toggle24HourTime(enabled) => {
-                self.config.use_24hour_time = enabled;
+            Message::RemoveFeed(index) => {
+                if index < self.config.feeds.len() {
+                    self.config.feeds.remove(index);
+                    self.save_config();
+                }
+            }
+            Message::SetFeedKind(index, choice) => {
+                if let (Some(feed), Some(kind)) = (self.config.feeds.get_mut(index), FeedKind::ALL.get(choice)) {
+                    feed.kind = *kind;
+                    self.save_config();
+                }
+            }
+            Message::UpdateFeedLabel(index, value) => {
+                if let Some(feed) = self.config.feeds.get_mut(index) {
+                    feed.label = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateFeedUrl(index, value) => {
+                if let Some(feed) = self.config.feeds.get_mut(index) {
+                    feed.url = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateFeedsRefresh(value) => {
+                self.feeds_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.feeds_refresh_minutes = minutes;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleTicker(enabled) => {
+                self.config.show_ticker = enabled;
                 self.save_config();
             }
-            Message::TogglePercentages(enabled) => {
-                self.config.show_percentages = enabled;
+            Message::SetTickerProvider(index) => {
+                if let Some(provider) = TickerProvider::ALL.get(index) {
+                    self.config.ticker_provider = *provider;
+                    self.save_config();
+                }
+            }
+            Message::UpdateTickerSymbols(value) => {
+                self.config.ticker_symbols = value;
                 self.save_config();
             }
-            Message::ToggleBatterySection(enabled) => {
-                self.config.show_battery = enabled;
+            Message::UpdateTickerMaxSymbols(value) => {
+                self.ticker_max_input = value.clone();
+                // Validate: 1-20 symbols
+                if let Ok(max) = value.parse::<u32>() {
+                    if TICKER_MAX_SYMBOLS.contains(&max) {
+                        self.config.ticker_max_symbols = max;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateTickerCurrency(value) => {
+                self.config.ticker_currency = value;
                 self.save_config();
             }
-            Message::ToggleSolaarIntegration(enabled) => {
-                self.config.enable_solaar_integration = enabled;
+            Message::UpdateTickerEndpoint(value) => {
+                self.config.ticker_endpoint = value;
                 self.save_config();
             }
-            
-            // === Battery Device Cache ===
-            Message::RemoveCachedDevice(index) => {
-                if index < self.cached_devices.len() {
-                    self.cached_devices.remove(index);
-                    // Persist to cache file
-                    let mut cache = WidgetCache::load();
-                    cache.battery_devices = self.cached_devices.clone();
-                    cache.save();
-                }
+            Message::UpdateTickerPricePath(value) => {
+                self.config.ticker_price_path = value;
+                self.save_config();
             }
-            
-            // === Notification Settings ===
-            Message::ToggleNotifications(enabled) => {
-                self.config.show_notifications = enabled;
+            Message::UpdateTickerChangePath(value) => {
+                self.config.ticker_change_path = value;
                 self.save_config();
             }
-            Message::UpdateMaxNotifications(value) => {
-                // Validate: must be 1-20
-                if let Ok(max) = value.parse::<usize>() {
-                    if max > 0 && max <= 20 {
-                        self.config.max_notifications = max;
+            Message::UpdateTickerRefresh(value) => {
+                self.ticker_refresh_input = value.clone();
+                // Validate: 1 minute to 1 day
+                if let Ok(minutes) = value.parse::<u32>() {
+                    if REFRESH_MINUTES.contains(&minutes) {
+                        self.config.ticker_refresh_minutes = minutes;
                         self.save_config();
                     }
                 }
             }
-            
-            // === Media Settings ===
-            Message::ToggleMedia(enabled) => {
-                self.config.show_media = enabled;
+            Message::TogglePlugins(enabled) => {
+                self.config.show_plugins = enabled;
                 self.save_config();
             }
-            Message::UpdateCiderApiToken(value) => {
-                self.cider_api_token_input = value.clone();
-                self.config.cider_api_token = value;
+            Message::ToggleCaffeine(enabled) => {
+                self.config.show_caffeine = enabled;
+                self.save_config();
+            }
+            Message::ToggleCaffeineOnStart(enabled) => {
+                self.config.caffeine_on_start = enabled;
+                self.save_config();
+            }
+            Message::ToggleCustomCommands(enabled) => {
+                self.config.show_custom_commands = enabled;
+                self.save_config();
+            }
+            Message::AddCustomCommand => {
+                let custom = CustomCommand::default();
+                self.custom_interval_inputs.resize(self.config.custom_commands.len(), String::new());
+                self.custom_interval_inputs.push(custom.interval_secs.to_string());
+                self.config.custom_commands.push(custom);
                 self.save_config();
             }
+            Message::RemoveCustomCommand(index) => {
+                if index < self.config.custom_commands.len() {
+                    self.config.custom_commands.remove(index);
+                    if index < self.custom_interval_inputs.len() {
+                        self.custom_interval_inputs.remove(index);
+                    }
+                    self.save_config();
+                }
+            }
+            Message::UpdateCustomLabel(index, value) => {
+                if let Some(custom) = self.config.custom_commands.get_mut(index) {
+                    custom.label = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateCustomCommand(index, value) => {
+                if let Some(custom) = self.config.custom_commands.get_mut(index) {
+                    custom.command = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateCustomInterval(index, value) => {
+                if index >= self.config.custom_commands.len() {
+                    return Task::none();
+                }
+                if self.custom_interval_inputs.len() <= index {
+                    self.custom_interval_inputs.resize(index + 1, String::new());
+                }
+                self.custom_interval_inputs[index] = value.clone();
+                // Validate: at least 1 second, at most a day
+                if let Ok(secs) = value.parse::<u32>() {
+                    if CUSTOM_INTERVAL_SECS.contains(&secs) {
+                        self.config.custom_commands[index].interval_secs = secs;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateCustomFormat(index, value) => {
+                if let Some(custom) = self.config.custom_commands.get_mut(index) {
+                    custom.format = value;
+                    self.save_config();
+                }
+            }
             
             // === Interval Setting ===
             Message::UpdateInterval(value) => {
                 self.interval_input = value.clone();
                 // Validate: must be 100-10000ms
                 if let Ok(interval) = value.parse::<u64>() {
-                    if interval >= 100 && interval <= 10000 {
+                    if UPDATE_INTERVAL_MS.contains(&interval) {
                         self.config.update_interval_ms = interval;
                         self.save_config();
                     }
@@ -786,34 +3667,203 @@ impl Application for SettingsApp {
             }
             
             // === Position Settings ===
-            Message::UpdateX(value) => {
-                self.x_input = value.clone();
-                if let Ok(x) = value.parse::<i32>() {
-                    self.config.widget_x = x;
+            Message::PlacementPressed => {
+                let mockup = Mockup::new(&self.cached_outputs);
+                let (x, y, width, height) = mockup.proxy_rect(&self.config, preview::widget_size(&self.config));
+                let (cursor_x, cursor_y) = (self.placement_cursor.x as f64, self.placement_cursor.y as f64);
+                let on_proxy = cursor_x >= x && cursor_x <= x + width && cursor_y >= y && cursor_y <= y + height;
+                // Clicking next to the proxy moves its center there
+                self.placement_grab = Some(if on_proxy {
+                    (cursor_x - x, cursor_y - y)
+                } else {
+                    (width / 2.0, height / 2.0)
+                });
+                self.drag_placement();
+            }
+            Message::PlacementReleased => {
+                self.placement_grab = None;
+            }
+            Message::PlacementMoved(position) => {
+                self.placement_cursor = position;
+                self.drag_placement();
+            }
+            Message::AddSchedule => {
+                self.config.schedules.push(Schedule::default());
+                self.save_config();
+            }
+            Message::RemoveSchedule(index) => {
+                if index < self.config.schedules.len() {
+                    self.config.schedules.remove(index);
+                    self.save_config();
+                }
+            }
+            Message::UpdateScheduleLabel(index, value) => {
+                if let Some(schedule) = self.config.schedules.get_mut(index) {
+                    schedule.label = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateScheduleStart(index, value) => {
+                if let Some(schedule) = self.config.schedules.get_mut(index) {
+                    schedule.start = value;
+                    self.save_config();
+                }
+            }
+            Message::UpdateScheduleEnd(index, value) => {
+                if let Some(schedule) = self.config.schedules.get_mut(index) {
+                    schedule.end = value;
+                    self.save_config();
+                }
+            }
+            Message::SetScheduleOpacity(index, choice) => {
+                if let (Some(schedule), Some(opacity)) = (self.config.schedules.get_mut(index), SCHEDULE_OPACITIES.get(choice)) {
+                    schedule.opacity = *opacity;
                     self.save_config();
                 }
             }
-            Message::UpdateY(value) => {
-                self.y_input = value.clone();
-                if let Ok(y) = value.parse::<i32>() {
-                    self.config.widget_y = y;
+            Message::ToggleScheduleSection(index, choice) => {
+                let sections = Config::default().section_order;
+                if let (Some(schedule), Some(section)) = (self.config.schedules.get_mut(index), sections.get(choice)) {
+                    match schedule.hidden_sections.iter().position(|hidden| hidden == section) {
+                        Some(position) => {
+                            schedule.hidden_sections.remove(position);
+                        }
+                        None => schedule.hidden_sections.push(*section),
+                    }
                     self.save_config();
                 }
             }
+            Message::ResetPosition => {
+                // Written together, like a drag in the picker
+                let defaults = Config::default();
+                self.config.widget_output = defaults.widget_output;
+                self.config.widget_anchor = defaults.widget_anchor;
+                self.config.widget_x = defaults.widget_x;
+                self.config.widget_y = defaults.widget_y;
+                self.placement_grab = None;
+                self.save_config();
+            }
             
             // === Weather Settings ===
             Message::ToggleWeather(enabled) => {
                 self.config.show_weather = enabled;
                 self.save_config();
             }
+            Message::ToggleWeatherDetailed(enabled) => {
+                self.config.weather_detailed = enabled;
+                self.save_config();
+            }
+            Message::ToggleWeatherSunTimes(enabled) => {
+                self.config.weather_sun_times = enabled;
+                self.save_config();
+            }
+            Message::ToggleWeatherMoonPhase(enabled) => {
+                self.config.weather_moon_phase = enabled;
+                self.save_config();
+            }
+            Message::ToggleAirQuality(enabled) => {
+                self.config.show_air_quality = enabled;
+                self.save_config();
+            }
+            Message::SetAirQualityProvider(index) => {
+                if let Some(provider) = AirQualityProvider::ALL.get(index) {
+                    self.config.air_quality_provider = *provider;
+                    self.save_config();
+                }
+            }
             Message::ToggleWidgetAutostart(enabled) => {
                 self.config.widget_autostart = enabled;
                 self.save_config();
             }
+            Message::ToggleClickThrough(enabled) => {
+                self.config.click_through = enabled;
+                self.save_config();
+            }
+            Message::ToggleKeyboardPositioning(enabled) => {
+                self.config.keyboard_positioning = enabled;
+                self.save_config();
+            }
+            Message::ToggleSnapToEdges(enabled) => {
+                self.config.snap_to_edges = enabled;
+                self.save_config();
+            }
+            Message::UpdateSnapGrid(value) => {
+                self.snap_grid_input = value.clone();
+                // Validate: off (0) up to 200 px
+                if let Ok(pixels) = value.parse::<u32>() {
+                    if SNAP_GRID.contains(&pixels) {
+                        self.config.snap_grid = pixels;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::ToggleAutoHideFullscreen(enabled) => {
+                self.config.auto_hide_fullscreen = enabled;
+                self.save_config();
+            }
+            Message::ToggleAutoHideMaximized(enabled) => {
+                self.config.auto_hide_maximized = enabled;
+                self.save_config();
+            }
             Message::ToggleLogging(enabled) => {
                 self.config.enable_logging = enabled;
                 self.save_config();
             }
+            Message::SetLogLevel(index) => {
+                if let Some(level) = LogLevel::ALL.get(index) {
+                    self.config.log_level = *level;
+                    self.save_config();
+                }
+            }
+            Message::ToggleDebugOverlay(enabled) => {
+                self.config.show_debug_overlay = enabled;
+                self.save_config();
+            }
+            Message::TogglePublishStats(enabled) => {
+                self.config.publish_stats = enabled;
+                self.save_config();
+            }
+            Message::ToggleExportFrames(enabled) => {
+                self.config.export_frames = enabled;
+                self.save_config();
+            }
+            Message::ToggleRecordMetrics(enabled) => {
+                self.config.record_metrics = enabled;
+                self.save_config();
+            }
+            Message::UpdateRecordInterval(value) => {
+                self.record_interval_input = value.clone();
+                // Validate: 1 second to 1 hour
+                if let Ok(secs) = value.parse::<u64>() {
+                    if RECORD_INTERVAL_SECS.contains(&secs) {
+                        self.config.record_interval_secs = secs;
+                        self.save_config();
+                    }
+                }
+            }
+            Message::UpdateRecordRetention(value) => {
+                self.record_retention_input = value.clone();
+                if let Ok(days) = value.parse::<u32>() {
+                    self.config.record_retention_days = days;
+                    self.save_config();
+                }
+            }
+            Message::ToggleLowPriorityCollectors(enabled) => {
+                self.config.low_priority_collectors = enabled;
+                self.save_config();
+            }
+            Message::ToggleIdlePriorityCollectors(enabled) => {
+                self.config.idle_priority_collectors = enabled;
+                self.save_config();
+            }
+            Message::TogglePinEfficiencyCores(enabled) => {
+                self.config.pin_collectors_to_efficiency_cores = enabled;
+                self.save_config();
+            }
+            Message::ToggleUpdateCheck(enabled) => {
+                self.config.check_for_updates = enabled;
+                self.save_config();
+            }
             Message::UpdateWeatherApiKey(value) => {
                 self.weather_api_key_input = value.clone();
                 self.config.weather_api_key = value;
@@ -838,6 +3888,204 @@ impl Application for SettingsApp {
                     self.save_config();
                 }
             }
+            Message::PickSection(index) => {
+                match self.picked_section.take() {
+                    None => self.picked_section = Some(index),
+                    Some(from) if from != index && from < self.config.section_order.len() && index < self.config.section_order.len() => {
+                        let section = self.config.section_order.remove(from);
+                        self.config.section_order.insert(index, section);
+                        self.save_config();
+                    }
+                    // Clicking the picked section again cancels
+                    Some(_) => {}
+                }
+            }
+            Message::SetSectionCard(index, choice) => {
+                if let (Some(section), Some(opacity)) = (self.config.section_order.get(index).copied(), CARD_OPACITIES.get(choice)) {
+                    self.config.section_cards.retain(|card| card.section != section);
+                    if *opacity > 0 {
+                        self.config.section_cards.push(SectionCard { section, opacity: *opacity });
+                    }
+                    self.save_config();
+                }
+            }
+            
+            // === Layout Bundles ===
+            Message::UpdateBundlePath(value) => {
+                self.bundle_path_input = value;
+            }
+            Message::ExportBundle => {
+                let path = std::path::PathBuf::from(&self.bundle_path_input);
+                let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                self.bundle_status = Some(match Bundle::from_config(&self.config, &name).save(&path) {
+                    Ok(()) => fl!("layout-bundle-exported", path = path.display().to_string()),
+                    Err(e) => fl!("layout-bundle-failed", error = e.to_string()),
+                });
+            }
+            Message::ImportBundle => {
+                self.bundle_status = Some(match Bundle::load(std::path::Path::new(&self.bundle_path_input)) {
+                    Ok(bundle) => {
+                        bundle.apply(&mut self.config);
+                        self.max_notifications_input = self.config.max_notifications.to_string();
+                        self.save_config();
+                        fl!("layout-bundle-imported", name = bundle.name)
+                    }
+                    Err(e) => fl!("layout-bundle-failed", error = e.to_string()),
+                });
+            }
+            
+            // === Profiles ===
+            Message::ApplyPreset(preset) => {
+                preset.apply(&mut self.config);
+                self.interval_input = self.config.update_interval_ms.to_string();
+                self.save_config();
+                self.profile_status = Some(fl!("profiles-preset-applied", name = preset.label()));
+            }
+            Message::UpdateProfileName(value) => {
+                self.profile_name_input = value;
+            }
+            Message::SaveProfile => {
+                let name = self.profile_name_input.trim().to_string();
+                self.profile_status = Some(match profiles::save(&name, &self.config) {
+                    Ok(()) => {
+                        self.saved_profiles = profiles::list();
+                        self.profile_name_input.clear();
+                        fl!("profiles-saved", name = name)
+                    }
+                    Err(e) => fl!("profiles-failed", error = e.to_string()),
+                });
+            }
+            Message::SwitchProfile(name) => {
+                self.profile_status = Some(match profiles::load(&name) {
+                    Ok(config) => {
+                        self.replace_config(config);
+                        fl!("profiles-switched", name = name)
+                    }
+                    Err(e) => fl!("profiles-failed", error = e.to_string()),
+                });
+            }
+            Message::DeleteProfile(name) => {
+                self.profile_status = Some(match profiles::delete(&name) {
+                    Ok(()) => fl!("profiles-deleted", name = name),
+                    Err(e) => fl!("profiles-failed", error = e.to_string()),
+                });
+                self.saved_profiles = profiles::list();
+            }
+            Message::UpdateProfilePath(value) => {
+                self.profile_path_input = value;
+            }
+            Message::ExportProfile => {
+                let path = std::path::PathBuf::from(&self.profile_path_input);
+                self.profile_status = Some(match profiles::export(&self.config, &path) {
+                    Ok(()) => fl!("profiles-exported", path = path.display().to_string()),
+                    Err(e) => fl!("profiles-failed", error = e.to_string()),
+                });
+            }
+            Message::ImportProfile => {
+                let path = std::path::PathBuf::from(&self.profile_path_input);
+                self.profile_status = Some(match profiles::import(&path) {
+                    Ok(config) => {
+                        self.replace_config(config);
+                        fl!("profiles-imported", path = path.display().to_string())
+                    }
+                    Err(e) => fl!("profiles-failed", error = e.to_string()),
+                });
+            }
+            
+            // === Setup Wizard ===
+            Message::StartSetup => {
+                self.setup_config = self.config.clone();
+                self.setup_step = SetupStep::Output;
+                self.setup_install_autostart = setup::autostart_installed();
+                self.setup_status = None;
+                self.cached_outputs = WidgetCache::load().outputs;
+                self.page = SettingsPage::Setup;
+            }
+            Message::SetupBack => {
+                if let Some(step) = self.setup_step.previous() {
+                    self.setup_step = step;
+                }
+            }
+            Message::SetupNext => {
+                if let Some(step) = self.setup_step.next() {
+                    self.setup_step = step;
+                }
+            }
+            Message::SetupOutput(name) => {
+                self.setup_config.widget_output = name;
+            }
+            Message::SetupAnchor(anchor) => {
+                self.setup_config.widget_anchor = anchor;
+            }
+            Message::SetupToggle(toggle, enabled) => {
+                toggle.set(&mut self.setup_config, enabled);
+            }
+            Message::SetupWeatherApiKey(value) => {
+                self.setup_config.weather_api_key = value;
+            }
+            Message::SetupWeatherLocation(value) => {
+                self.setup_config.weather_location = value;
+            }
+            Message::SetupWidgetAutostart(enabled) => {
+                self.setup_config.widget_autostart = enabled;
+            }
+            Message::SetupInstallAutostart(enabled) => {
+                self.setup_install_autostart = enabled;
+            }
+            Message::SetupFinish => {
+                // The login entry and the applet would each start a widget
+                if self.setup_install_autostart {
+                    self.setup_config.widget_autostart = false;
+                }
+                match setup::set_autostart(self.setup_install_autostart) {
+                    Ok(()) => {
+                        self.replace_config(self.setup_config.clone());
+                        self.page = SettingsPage::General;
+                        self.activate_nav(SettingsPage::General);
+                    }
+                    Err(e) => self.setup_status = Some(fl!("setup-autostart-failed", error = e.to_string())),
+                }
+            }
+            Message::SetupCancel => {
+                self.page = SettingsPage::General;
+                self.activate_nav(SettingsPage::General);
+            }
+            
+            // === Navigation ===
+            Message::ShowPage(page) => {
+                self.page = page;
+                self.activate_nav(page);
+                if page == SettingsPage::General {
+                    // Profiles may have been added or removed on disk
+                    self.saved_profiles = profiles::list();
+                }
+                if page == SettingsPage::Position {
+                    // Outputs may have changed since the window was opened
+                    self.cached_outputs = WidgetCache::load().outputs;
+                    self.placement_image = Mockup::new(&self.cached_outputs).render(
+                        &self.config,
+                        preview::widget_size(&self.config),
+                        &self.theme,
+                    );
+                }
+                if page == SettingsPage::Diagnostics {
+                    return self.update(Message::RefreshDiagnostics);
+                }
+            }
+            Message::UpdateSearch(query) => {
+                self.search_query = query;
+            }
+            Message::ClearSearch => {
+                self.search_query.clear();
+            }
+            Message::OpenSearchResult(page) => {
+                self.search_query.clear();
+                return self.update(Message::ShowPage(page));
+            }
+            Message::RefreshDiagnostics => {
+                self.widget_status = WidgetStatus::load();
+                self.widget_running = widget_process_running();
+            }
             
             // === Save & Apply Action ===
             Message::SaveAndApply => {
@@ -845,28 +4093,19 @@ impl Application for SettingsApp {
                 self.save_config();
                 
                 // Restart widget to apply changes that require restart
-                eprintln!("Save & Apply clicked! Restarting widget with current settings.");
-                
-                // Kill existing widget process
-                match std::process::Command::new("pkill")
-                    .arg("-f")
-                    .arg("cosmic-monitor-widget")
-                    .status() {
-                    Ok(status) => eprintln!("pkill status: {:?}", status),
-                    Err(e) => eprintln!("pkill error: {:?}", e),
-                }
-                
-                // Brief delay for process cleanup
-                std::thread::sleep(std::time::Duration::from_millis(300));
+                log::info!("Save & Apply clicked! Restarting widget with current settings.");
                 
-                // Spawn new widget using installed binary (from PATH)
+                // Spawn new widget using installed binary (from PATH); it asks
+                // the running widget to shut down and takes its place
                 match std::process::Command::new("cosmic-monitor-widget")
+                    .arg("--replace")
                     .spawn() {
-                    Ok(child) => eprintln!("Widget spawned with PID: {:?}", child.id()),
-                    Err(e) => eprintln!("Spawn error: {:?}", e),
+                    Ok(child) => log::info!("Widget spawned with PID: {:?}", child.id()),
+                    Err(e) => log::error!("Failed to spawn the widget: {:?}", e),
                 }
             }
         }
+        self.refresh_preview();
         Task::none()
     }
 }
@@ -16,12 +16,16 @@
 //! # Features
 //!
 //! The settings app provides a comprehensive GUI for:
+//! - A first-run setup wizard (output, corner, sections, weather, autostart)
 //! - Toggling monitoring sections (CPU, Memory, GPU, etc.)
 //! - Configuring weather API credentials
 //! - Setting notification preferences
-//! - Adjusting widget position (with live drag support)
+//! - Placing the widget on a mockup of the connected screens (or dragging it live)
 //! - Reordering widget sections
-//! - Enabling/disabling debug logging
+//! - Previewing the widget live while changing settings
+//! - Sharing layouts as bundle files
+//! - Saving, switching, and exporting full configuration profiles
+//! - Choosing the log level and enabling file logging
 //!
 //! # Architecture
 //!
@@ -29,15 +33,38 @@
 //! `cosmic::app` framework for a standalone window. Changes are saved to
 //! the shared cosmic-config and immediately visible to the widget.
 
-mod config;
-mod i18n;
+mod bundle;
+mod placement;
+mod preview;
+mod profiles;
+mod search;
 mod settings;
+mod setup;
+
+use cosmic_monitor_core::{config, fl, i18n, logging, widget};
+
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
 
 /// Settings application entry point.
 ///
-/// Initializes i18n and starts the COSMIC application event loop
+/// Initializes logging and i18n, and starts the COSMIC application event loop
 /// with the SettingsApp model defined in `settings.rs`.
 fn main() -> cosmic::iced::Result {
+    // Log at the configured level (`--verbose` raises it to Debug, see logging.rs)
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let verbose = logging::take_verbose_flag(&mut args);
+    let migrated = config::migrate_stored();
+    let config = cosmic_config::Config::new("com.github.zoliviragh.CosmicMonitor", config::Config::VERSION)
+        .map(|handler| config::Config::get_entry(&handler).unwrap_or_else(|(_errors, config)| config))
+        .unwrap_or_default();
+    logging::init(
+        "cosmic-monitor-settings",
+        logging::level_filter(config.log_level, verbose),
+        config.enable_logging,
+    );
+    log::info!("Starting COSMIC Monitor Settings");
+    config::log_migration(&migrated);
+
     // Initialize internationalization with system language preferences.
     // This loads translations from i18n/en/cosmic_monitor_applet.ftl (and other locales).
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! First-Run Setup
+//!
+//! When the settings app starts and no configuration has been stored yet,
+//! it opens a short wizard instead of the full settings page:
+//!
+//! ```text
+//! Output ──► Corner ──► Sections ──► Weather ──► Finish
+//! ```
+//!
+//! The choices are collected in a draft [`Config`] and written through
+//! cosmic-config in one go on Finish, so leaving the wizard early doesn't
+//! leave a half-configured widget behind. The wizard can be started again
+//! from the Advanced section.
+//!
+//! Finish can also install an XDG autostart entry, so the widget starts on
+//! login without the panel applet.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use cosmic::cosmic_config::CosmicConfigEntry;
+
+use crate::config::{Config, CONFIG_ID};
+
+/// Desktop entry installed into the autostart directory.
+const AUTOSTART_ENTRY: &str = include_str!("../resources/widget.desktop");
+
+/// File name of the autostart entry.
+const AUTOSTART_FILE: &str = "com.github.zoliviragh.CosmicMonitor.Widget.desktop";
+
+/// Steps of the wizard, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    /// Output the widget is shown on
+    Output,
+    /// Screen corner and offsets
+    Corner,
+    /// Which sections are shown
+    Sections,
+    /// Weather provider and location
+    Weather,
+    /// Autostart, then write the config
+    Finish,
+}
+
+impl SetupStep {
+    /// All steps in order.
+    pub const ALL: [SetupStep; 5] = [
+        SetupStep::Output,
+        SetupStep::Corner,
+        SetupStep::Sections,
+        SetupStep::Weather,
+        SetupStep::Finish,
+    ];
+
+    /// Position of the step (0-based).
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|step| step == self).unwrap_or(0)
+    }
+
+    /// Following step, if any.
+    pub fn next(&self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    /// Preceding step, if any.
+    pub fn previous(&self) -> Option<Self> {
+        self.index().checked_sub(1).map(|index| Self::ALL[index])
+    }
+
+    /// Heading shown above the step.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SetupStep::Output => "Monitor",
+            SetupStep::Corner => "Corner",
+            SetupStep::Sections => "Sections",
+            SetupStep::Weather => "Weather",
+            SetupStep::Finish => "Finish",
+        }
+    }
+}
+
+/// Section toggles offered on the Sections step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupToggle {
+    Clock,
+    Date,
+    Cpu,
+    Memory,
+    Gpu,
+    Network,
+    Disk,
+    CpuTemp,
+    GpuTemp,
+    Storage,
+    Battery,
+    Weather,
+    Notifications,
+    Media,
+}
+
+impl SetupToggle {
+    /// All toggles in display order.
+    pub const ALL: [SetupToggle; 14] = [
+        SetupToggle::Clock,
+        SetupToggle::Date,
+        SetupToggle::Cpu,
+        SetupToggle::Memory,
+        SetupToggle::Gpu,
+        SetupToggle::Network,
+        SetupToggle::Disk,
+        SetupToggle::CpuTemp,
+        SetupToggle::GpuTemp,
+        SetupToggle::Storage,
+        SetupToggle::Battery,
+        SetupToggle::Weather,
+        SetupToggle::Notifications,
+        SetupToggle::Media,
+    ];
+
+    /// Human-readable name shown next to the toggle.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SetupToggle::Clock => "Clock",
+            SetupToggle::Date => "Date",
+            SetupToggle::Cpu => "CPU usage",
+            SetupToggle::Memory => "Memory usage",
+            SetupToggle::Gpu => "GPU usage",
+            SetupToggle::Network => "Network activity",
+            SetupToggle::Disk => "Disk activity",
+            SetupToggle::CpuTemp => "CPU temperature",
+            SetupToggle::GpuTemp => "GPU temperature",
+            SetupToggle::Storage => "Storage usage",
+            SetupToggle::Battery => "Batteries",
+            SetupToggle::Weather => "Weather",
+            SetupToggle::Notifications => "Notifications",
+            SetupToggle::Media => "Media player",
+        }
+    }
+
+    /// The config field behind the toggle.
+    fn field<'a>(&self, config: &'a mut Config) -> &'a mut bool {
+        match self {
+            SetupToggle::Clock => &mut config.show_clock,
+            SetupToggle::Date => &mut config.show_date,
+            SetupToggle::Cpu => &mut config.show_cpu,
+            SetupToggle::Memory => &mut config.show_memory,
+            SetupToggle::Gpu => &mut config.show_gpu,
+            SetupToggle::Network => &mut config.show_network,
+            SetupToggle::Disk => &mut config.show_disk,
+            SetupToggle::CpuTemp => &mut config.show_cpu_temp,
+            SetupToggle::GpuTemp => &mut config.show_gpu_temp,
+            SetupToggle::Storage => &mut config.show_storage,
+            SetupToggle::Battery => &mut config.show_battery,
+            SetupToggle::Weather => &mut config.show_weather,
+            SetupToggle::Notifications => &mut config.show_notifications,
+            SetupToggle::Media => &mut config.show_media,
+        }
+    }
+
+    /// Whether the toggle is on in `config`.
+    pub fn get(&self, config: &Config) -> bool {
+        match self {
+            SetupToggle::Clock => config.show_clock,
+            SetupToggle::Date => config.show_date,
+            SetupToggle::Cpu => config.show_cpu,
+            SetupToggle::Memory => config.show_memory,
+            SetupToggle::Gpu => config.show_gpu,
+            SetupToggle::Network => config.show_network,
+            SetupToggle::Disk => config.show_disk,
+            SetupToggle::CpuTemp => config.show_cpu_temp,
+            SetupToggle::GpuTemp => config.show_gpu_temp,
+            SetupToggle::Storage => config.show_storage,
+            SetupToggle::Battery => config.show_battery,
+            SetupToggle::Weather => config.show_weather,
+            SetupToggle::Notifications => config.show_notifications,
+            SetupToggle::Media => config.show_media,
+        }
+    }
+
+    /// Turn the toggle on or off in `config`.
+    pub fn set(&self, config: &mut Config, enabled: bool) {
+        *self.field(config) = enabled;
+    }
+}
+
+/// Whether no configuration has been stored yet.
+///
+/// Checked before the settings app writes anything, so it is only true
+/// on the very first start (or after the config directory was removed).
+pub fn is_first_run() -> bool {
+    let dir = dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("cosmic")
+        .join(CONFIG_ID)
+        .join(format!("v{}", Config::VERSION));
+    fs::read_dir(dir).map(|mut entries| entries.next().is_none()).unwrap_or(true)
+}
+
+/// Location of the widget's XDG autostart entry.
+pub fn autostart_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("autostart")
+        .join(AUTOSTART_FILE)
+}
+
+/// Whether the autostart entry is installed.
+pub fn autostart_installed() -> bool {
+    autostart_path().exists()
+}
+
+/// Install or remove the autostart entry.
+///
+/// # Errors
+///
+/// Returns I/O errors from writing or removing the entry (a missing entry
+/// is not an error when removing).
+pub fn set_autostart(enabled: bool) -> io::Result<()> {
+    let path = autostart_path();
+    if enabled {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, AUTOSTART_ENTRY)
+    } else {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
@@ -29,13 +29,24 @@
 //! │  └── SeatState         (input handling: mouse, keyboard)        │
 //! ├──────────────────────────────────────────────────────────────────┤
 //! │  Monitor Modules                                                 │
-//! │  ├── UtilizationMonitor  (CPU, Memory, GPU usage)               │
-//! │  ├── TemperatureMonitor  (CPU/GPU temps from hwmon/nvidia-smi)  │
-//! │  ├── StorageMonitor      (disk space from mount points)         │
-//! │  ├── BatteryMonitor      (system + Solaar Bluetooth devices)    │
+//! │  ├── Sampler             (thread owning the monitors below)     │
+//! │  │   ├── UtilizationMonitor  (CPU, Memory, GPU usage)           │
+//! │  │   ├── TemperatureMonitor  (CPU/GPU temps, hwmon/nvidia-smi)  │
+//! │  │   ├── NetworkMonitor      (upload/download rates)            │
+//! │  │   ├── StorageMonitor      (disk space from mount points)     │
+//! │  │   └── BatteryMonitor      (system + Solaar devices)          │
 //! │  ├── WeatherMonitor      (OpenWeatherMap API)                   │
 //! │  ├── NotificationMonitor (D-Bus notifications)                  │
-//! │  └── MediaMonitor        (Cider Apple Music client)             │
+//! │  ├── MediaMonitor        (Cider Apple Music client)             │
+//! │  ├── CustomCommandMonitor (user-defined shell commands)         │
+//! │  ├── AudioMonitor        (default output volume via pactl)      │
+//! │  ├── HealthMonitor       (failed systemd units, updates)        │
+//! │  ├── ContainerMonitor    (running Docker/Podman containers)     │
+//! │  ├── VmMonitor           (running libvirt domains)              │
+//! │  ├── FeedMonitor         (unread IMAP mail, RSS/Atom headlines) │
+//! │  ├── TickerMonitor       (crypto and stock quotes)              │
+//! │  ├── CalendarMonitor     (upcoming events: .ics file or EDS)     │
+//! │  └── PluginHost          (Lua plugin sections, optional)        │
 //! └──────────────────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -43,7 +54,8 @@
 //!
 //! The main loop:
 //! 1. Polls Wayland for events (input, configure, etc.)
-//! 2. Updates system statistics at the configured interval
+//! 2. Takes the newest [`MetricsSnapshot`] the sampler thread sent (one per
+//!    configured interval); drawing only reads snapshots, never sysinfo
 //! 3. Re-renders when the clock second changes
 //! 4. Handles click events for notifications and media controls
 //! 5. Checks for configuration changes every 500ms
@@ -60,14 +72,95 @@
 //!
 //! If the Wayland connection is lost (compositor restart, etc.), the widget
 //! automatically attempts to reconnect with exponential backoff.
+//!
+//! # Runtime Visibility Control
+//!
+//! The running widget listens on a named pipe (see [`widget::control`]) for
+//! `toggle`/`show`/`hide` commands. Hiding destroys the layer surface while
+//! the monitors keep running, so showing it again is instant. Send commands
+//! from the command line (e.g., bound to a keyboard shortcut):
+//!
+//! ```bash
+//! cosmic-monitor-widget --toggle   # or --show / --hide
+//! ```
+//!
+//! `cosmic-monitor-widget --debug-overlay` toggles the debug overlay (frame
+//! and draw times, buffer size, sampler latency, memory use; see
+//! [`widget::debug_overlay`]) the same way, and `--copy-stats` copies the
+//! plain-text stats of the last sample to the clipboard (see
+//! [`widget::clipboard`]).
+//!
+//! # Single Instance
+//!
+//! Only one widget runs at a time (see [`widget::instance`]). Starting a
+//! second one prints the running widget's PID and exits;
+//! `cosmic-monitor-widget --replace` shuts the running widget down and takes
+//! its place instead.
+//!
+//! # One-Shot Stats
+//!
+//! `cosmic-monitor-widget --stats [json|plain]` samples the monitors once and
+//! prints the result without connecting to Wayland (see [`widget::stats`]).
+//!
+//! # Prometheus Exporter
+//!
+//! With the `prometheus` cargo feature, `--export-prometheus <port>` serves
+//! the same metrics over HTTP instead of showing the widget.
+//!
+//! # Logging
+//!
+//! Logs go to stderr at the configured level (see [`logging`]); `--verbose`
+//! raises it to Debug and `RUST_LOG` overrides it. With debug logging
+//! enabled in the settings, they are also written to
+//! `~/.local/state/cosmic-monitor/cosmic-monitor-widget.log`.
 
-mod config;
-mod widget;
-
-use config::Config;
-use widget::{UtilizationMonitor, TemperatureMonitor, NetworkMonitor, WeatherMonitor, StorageMonitor, BatteryMonitor, NotificationMonitor, MediaMonitor, CosmicTheme, load_weather_font};
-use widget::renderer::{render_widget, RenderParams};
-use widget::layout::calculate_widget_height_with_all;
+use cosmic_monitor_core::{config, i18n, logging, widget};
+use config::{Config, LogLevel, ScrollAction, WidgetSection};
+use widget::{WeatherMonitor, NotificationMonitor, MediaMonitor, CosmicTheme, load_weather_font};
+use widget::renderer::{render_widget, FrameData, SectionAreaBounds};
+use widget::weather::moon_phase;
+use widget::layout::{calculate_widget_size, compact_slots, section_columns, ContentCounts};
+use widget::control::{ControlCommand, ControlListener};
+use widget::fonts::Fonts;
+use widget::instance::InstanceLock;
+use widget::stats::StatsSnapshot;
+use widget::scroll::ScrollSteps;
+use widget::snap::{self, SnapGuides};
+use widget::schedule::ScheduleEffect;
+use widget::tooltip::{self, HoverTarget, HoverTargetBounds, HoverTracker};
+use widget::world_clock::{active_clocks, world_clock_rows};
+use widget::frame_export::FrameExporter;
+use widget::sampler::{sampler_settings, MetricsSnapshot, Sampler};
+use widget::recorder::MetricRecorder;
+use widget::watchdog::{Heartbeat, WatchedMonitor, Watchdog};
+use widget::toplevel::ToplevelTracker;
+use widget::animation::Animator;
+use widget::marquee::Marquee;
+use widget::history::History;
+use widget::custom::{active_commands, CustomCommandMonitor};
+use widget::audio::AudioMonitor;
+use widget::cpufreq;
+use widget::health::{health_settings, HealthMonitor};
+use widget::containers::{container_settings, ContainerMonitor};
+use widget::vms::{vm_settings, VmMonitor};
+use widget::smart::{smart_settings, SmartMonitor};
+use widget::pools::{pool_settings, PoolMonitor};
+use widget::space_alert::{self, space_limits, SpaceAlerts};
+use widget::feeds::{feed_settings, FeedMonitor};
+use widget::ticker::{ticker_settings, TickerMonitor};
+use widget::air_quality::{air_quality_settings, AirQualityMonitor};
+use widget::calendar::{active_settings, event_rows, CalendarMonitor};
+use widget::update::{open_release_page, UpdateChecker};
+use widget::plugins::PluginHost;
+use widget::storage::DiskInfo;
+use widget::cache::{CachedOutput, WidgetCache};
+use widget::debug_overlay::DebugStats;
+use widget::hardware::HardwareInfo;
+use widget::identity::Identity;
+use widget::dnd::DoNotDisturb;
+use widget::power_profile::PowerProfileMonitor;
+use widget::notification_history::history_settings;
+use widget::lyrics::{LyricsMonitor, TrackKey};
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -75,33 +168,44 @@ use std::thread;
 
 // smithay-client-toolkit provides Rust-friendly wrappers around Wayland protocols
 use smithay_client_toolkit::{
-    compositor::{CompositorHandler, CompositorState},
+    compositor::{CompositorHandler, CompositorState, Region},
     delegate_compositor, delegate_layer, delegate_output, delegate_registry, delegate_shm,
-    delegate_seat, delegate_pointer,
+    delegate_seat, delegate_pointer, delegate_keyboard,
     output::{OutputHandler, OutputState},
     registry::{ProvidesRegistryState, RegistryState},
     registry_handlers,
     seat::{Capability, SeatHandler, SeatState},
     seat::pointer::{PointerHandler, PointerEvent, PointerEventKind},
+    seat::keyboard::{KeyEvent, KeyboardHandler, Keysym, Modifiers, RawModifiers},
     shell::{
         wlr_layer::{
-            Anchor, Layer, LayerShell, LayerShellHandler, LayerSurface, LayerSurfaceConfigure,
+            Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface, LayerSurfaceConfigure,
         },
         WaylandSurface,
     },
     shm::{slot::SlotPool, Shm, ShmHandler},
 };
 use wayland_client::{
+    event_created_child,
     globals::registry_queue_init,
-    protocol::{wl_output, wl_shm, wl_surface},
-    Connection, QueueHandle,
+    protocol::{wl_keyboard, wl_output, wl_shm, wl_surface},
+    backend::ObjectId,
+    Connection, Dispatch, Proxy, QueueHandle,
+};
+use wayland_protocols::wp::idle_inhibit::zv1::client::{
+    zwp_idle_inhibit_manager_v1::{self, ZwpIdleInhibitManagerV1},
+    zwp_idle_inhibitor_v1::{self, ZwpIdleInhibitorV1},
+};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::{
+    zwlr_foreign_toplevel_handle_v1::{self, ZwlrForeignToplevelHandleV1},
+    zwlr_foreign_toplevel_manager_v1::{self, ZwlrForeignToplevelManagerV1},
 };
 
 // ============================================================================
 // Constants
 // ============================================================================
 
-/// Fixed widget width in pixels (height is dynamic based on content)
+/// Initial widget width in pixels (recalculated for the layout mode)
 const WIDGET_WIDTH: u32 = 370;
 /// Default/initial widget height (recalculated based on enabled sections)
 const WIDGET_HEIGHT: u32 = 400;
@@ -147,33 +251,78 @@ struct MonitorWidget {
     // === System Monitoring Modules ===
     // Each module is responsible for collecting and caching specific metrics
     
-    /// CPU, Memory, and GPU utilization percentages
-    utilization: UtilizationMonitor,
-    /// CPU and GPU temperatures from sensors
-    temperature: TemperatureMonitor,
-    /// Network upload/download rates (currently unused in UI)
-    network: NetworkMonitor,
+    /// Sampler thread owning the CPU, memory, GPU, temperature, network,
+    /// storage and battery monitors
+    sampler: Sampler,
+    /// Latest readings from the sampler thread
+    metrics: MetricsSnapshot,
+    /// Recent download rates for the network graph
+    network_rx_history: History,
+    /// Recent upload rates for the network graph
+    network_tx_history: History,
+    /// Recent CPU temperatures for the temperature graph
+    cpu_temp_history: History,
+    /// Recent GPU temperatures for the temperature graph
+    gpu_temp_history: History,
     /// Weather data from OpenWeatherMap API
     weather: WeatherMonitor,
-    /// Mounted disk space information
-    storage: StorageMonitor,
-    /// Battery levels from system and Solaar
-    battery: BatteryMonitor,
+    /// Air quality at the weather station
+    air_quality: AirQualityMonitor,
     /// D-Bus desktop notifications
     notifications: NotificationMonitor,
+    /// Do-not-disturb switch of the notification daemon
+    dnd: DoNotDisturb,
+    /// Active power profile of power-profiles-daemon
+    power_profile: PowerProfileMonitor,
     /// Now playing from Cider
     media: MediaMonitor,
-    /// Last time system stats were updated
-    last_update: Instant,
+    /// Synced lyrics of the playing track
+    lyrics: LyricsMonitor,
+    /// User-defined commands shown in the Custom Commands section
+    custom_commands: CustomCommandMonitor,
+    /// Volume and mute state of the default audio output
+    audio: AudioMonitor,
+    /// Failed systemd units and pending updates
+    health: HealthMonitor,
+    /// Running Docker/Podman containers
+    containers: ContainerMonitor,
+    /// Running libvirt virtual machines
+    vms: VmMonitor,
+    /// SMART health and temperature of the drives
+    smart: SmartMonitor,
+    /// ZFS pools and btrfs filesystems
+    pools: PoolMonitor,
+    /// Filesystems already reported low (desktop alerts fire once per crossing)
+    space_alerts: SpaceAlerts,
+    /// Unread mail counts and feed headlines
+    feeds: FeedMonitor,
+    /// Crypto and stock quotes
+    ticker: TickerMonitor,
+    /// Upcoming events drawn under the date
+    calendar: CalendarMonitor,
+    /// Locale of month and day names, read from the environment at startup
+    locale: chrono::Locale,
+    /// Lua plugin scripts drawn in the Plugins section
+    plugins: PluginHost,
+    /// Daily check for a newer release (idle unless enabled)
+    update_checker: UpdateChecker,
     
     // === Rendering State ===
     
     /// Shared memory pool for Wayland buffer allocation
     pool: Option<SlotPool>,
+    /// Last rendered width (for detecting resize needs)
+    last_width: u32,
     /// Last rendered height (for detecting resize needs)
     last_height: u32,
     /// Last drawn clock second (for sync'd updates)
     last_drawn_second: Option<String>,
+    /// Eased values and opacity between samples
+    animator: Animator,
+    /// A frame callback is requested and hasn't fired yet
+    frame_pending: bool,
+    /// Shown track title and when it appeared (where its marquee starts)
+    media_title_since: (String, Instant),
     
     // === Mouse Interaction State ===
     
@@ -183,29 +332,73 @@ struct MonitorWidget {
     drag_start_x: f64,
     /// Starting Y position of drag operation
     drag_start_y: f64,
+    /// Movement of the drag in progress (screen pixels, right/down positive),
+    /// saved to the config once on release
+    drag_offset: (i32, i32),
+    /// Guides the dragged widget snapped to (drawn on the surface)
+    snap_guides: SnapGuides,
+    
+    // === Schedules ===
+    
+    /// Opacity and hidden sections of the schedules active right now
+    schedule_effect: ScheduleEffect,
+    
+    // === Keyboard Interaction State ===
+    
+    /// Whether Shift is held (arrow keys move in bigger steps)
+    shift_held: bool,
     
     // === Click Detection Bounds ===
     // These are populated by the renderer and used for hit testing
     
-    /// Vertical bounds of the notification section (y_start, y_end)
-    notification_bounds: Option<(f64, f64)>,
+    /// Bounds of the notification section (x_start, y_start, x_end, y_end)
+    notification_bounds: Option<(f64, f64, f64, f64)>,
     /// Bounds of notification group headers for collapse toggle
     /// Format: [(app_name, y_start, y_end)]
     notification_group_bounds: Vec<(String, f64, f64)>,
-    /// Bounds of X buttons for clearing groups/notifications
+    /// Bounds of X buttons for clearing groups/notifications, and of
+    /// notification rows (hover shows the body, click runs the default action)
     /// Format: [(key, x_start, y_start, x_end, y_end)]
-    /// Key is "app_name" for groups, "app_name:timestamp" for individual
+    /// Key is "app_name" for groups, "app_name:timestamp" for individual,
+    /// "row:app_name:timestamp" for rows
     notification_clear_bounds: Vec<(String, f64, f64, f64, f64)>,
     /// Bounds of the "Clear All" button
     clear_all_bounds: Option<(f64, f64, f64, f64)>,
     /// Bounds of media playback control buttons
     /// Format: [(button_name, x_start, y_start, x_end, y_end)]
     media_button_bounds: Vec<(String, f64, f64, f64, f64)>,
+    /// Bounds of section error badges (hover shows the message)
+    /// Format: [(section, x_start, y_start, x_end, y_end)]
+    error_badge_bounds: Vec<(WidgetSection, f64, f64, f64, f64)>,
+    /// Section whose error badge is currently hovered
+    hovered_error: Option<WidgetSection>,
+    /// Bounds of the "update available" badge (click opens the release page)
+    update_badge_bounds: Option<(f64, f64, f64, f64)>,
+    /// Area of each drawn section, for routing scroll events
+    section_area_bounds: SectionAreaBounds,
+    /// Metrics with a detail tooltip
+    /// Format: [(target, x_start, y_start, x_end, y_end)]
+    hover_target_bounds: HoverTargetBounds,
+    /// Metric under the pointer, shown after the hover delay
+    hover: HoverTracker,
+    
+    // === Scroll State ===
+    
+    /// Touchpad scrolling not yet adding up to a whole step
+    scroll_steps: ScrollSteps,
+    /// World clock shown by the large clock (0 = local time, n = n-th active world clock)
+    clock_zone_index: usize,
+    /// Notifications scrolled past at the top of each group
+    notification_scroll: usize,
     
     // === Notification UI State ===
     
     /// Set of app names whose notification groups are collapsed
     collapsed_groups: std::collections::HashSet<String>,
+    /// Notification under the pointer ("app_name:timestamp"), body shown
+    hovered_notification: Option<String>,
+    /// Notifications clicked open ("app_name:timestamp"), bodies stay shown
+    expanded_notifications: std::collections::HashSet<String>,
     /// Cached grouped notifications to avoid recomputing each frame
     grouped_notifications: Vec<(String, Vec<widget::notifications::Notification>)>,
     /// Version counter to detect notification changes
@@ -219,11 +412,45 @@ struct MonitorWidget {
     last_click_time: std::time::Instant,
     /// Set to true when compositor requests close
     exit: bool,
+    /// Widget hidden via the control pipe (layer surface destroyed)
+    hidden: bool,
+    /// Widget hidden because a fullscreen window covers its output
+    auto_hidden: bool,
+    /// Foreign toplevel manager (None if the compositor lacks the protocol)
+    toplevel_manager: Option<ZwlrForeignToplevelManagerV1>,
+    /// State of other windows, for auto-hide
+    toplevels: ToplevelTracker<ObjectId, wl_output::WlOutput>,
+    /// Idle inhibit manager (None if the compositor lacks the protocol)
+    idle_inhibit_manager: Option<ZwpIdleInhibitManagerV1>,
+    /// Inhibitor on the widget surface while caffeine is on and the surface exists
+    idle_inhibitor: Option<ZwpIdleInhibitorV1>,
+    /// Caffeine switched on (kept across surface re-creation)
+    caffeine_active: bool,
+    /// Debug overlay shown (config or `--debug-overlay`, kept across reconnects)
+    debug_overlay: bool,
+    /// Frame timings and memory use for the debug overlay
+    debug_stats: DebugStats,
+    /// CPU and GPU lines under the Utilization header (read once at startup)
+    hardware_lines: Vec<String>,
+    /// Hostname and user for the identity line (read once at startup)
+    identity: Identity,
+    /// Output the widget surface is shown on (last entered)
+    widget_output: Option<wl_output::WlOutput>,
+    /// Output the surface was created for (None = compositor's choice)
+    surface_output: Option<wl_output::WlOutput>,
+    /// PNG frame writer for OBS overlays (Some while `export_frames` is on)
+    frame_exporter: Option<FrameExporter>,
+    /// CSV metric history writer (Some while `record_metrics` is on)
+    recorder: Option<MetricRecorder>,
+    /// Stall detection for background collectors
+    watchdog: Watchdog,
     
     // === Theme ===
     
     /// Current COSMIC theme (accent color, dark/light mode)
     theme: CosmicTheme,
+    /// Configured fonts, with uninstalled families replaced
+    fonts: Fonts,
     /// Last time we checked for theme changes
     last_theme_check: Instant,
 }
@@ -261,7 +488,8 @@ impl CompositorHandler for MonitorWidget {
     }
 
     /// Frame callback - compositor is ready for next frame.
-    /// This triggers a redraw with the current timestamp.
+    /// Requested by `draw()` while an animation is running; draws the next
+    /// intermediate frame (with the main loop's one-second clock offset).
     fn frame(
         &mut self,
         _conn: &Connection,
@@ -269,17 +497,28 @@ impl CompositorHandler for MonitorWidget {
         _surface: &wl_surface::WlSurface,
         _time: u32,
     ) {
-        self.draw(qh, chrono::Local::now(), true);
+        self.frame_pending = false;
+        self.draw(qh, chrono::Local::now() - chrono::Duration::seconds(1));
     }
 
     /// Called when surface enters an output (becomes visible).
+    /// Remembered so auto-hide only reacts to windows on the same output,
+    /// and so the position can be kept within its bounds.
     fn surface_enter(
         &mut self,
         _conn: &Connection,
-        _qh: &QueueHandle<Self>,
+        qh: &QueueHandle<Self>,
         _surface: &wl_surface::WlSurface,
-        _output: &wl_output::WlOutput,
+        output: &wl_output::WlOutput,
     ) {
+        self.widget_output = Some(output.clone());
+        self.update_auto_hide(qh);
+        
+        // Now that the output's size is known, keep a saved position on it
+        if let Some(layer_surface) = self.layer_surface.clone() {
+            self.apply_position(&layer_surface);
+            layer_surface.commit();
+        }
     }
 
     /// Called when surface leaves an output (no longer visible).
@@ -294,7 +533,9 @@ impl CompositorHandler for MonitorWidget {
 }
 
 /// Handles output (display) events.
-/// Currently unused but required by the registry.
+/// Keeps the output layout in the widget cache for the settings app's
+/// position picker, and moves the widget to its configured output once
+/// that output shows up.
 impl OutputHandler for MonitorWidget {
     fn output_state(&mut self) -> &mut OutputState {
         &mut self.output_state
@@ -303,9 +544,11 @@ impl OutputHandler for MonitorWidget {
     fn new_output(
         &mut self,
         _conn: &Connection,
-        _qh: &QueueHandle<Self>,
+        qh: &QueueHandle<Self>,
         _output: wl_output::WlOutput,
     ) {
+        self.cache_outputs(None);
+        self.move_to_configured_output(qh);
     }
 
     fn update_output(
@@ -314,14 +557,16 @@ impl OutputHandler for MonitorWidget {
         _qh: &QueueHandle<Self>,
         _output: wl_output::WlOutput,
     ) {
+        self.cache_outputs(None);
     }
 
     fn output_destroyed(
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _output: wl_output::WlOutput,
+        output: wl_output::WlOutput,
     ) {
+        self.cache_outputs(Some(&output));
     }
 }
 
@@ -352,7 +597,7 @@ impl LayerShellHandler for MonitorWidget {
         if configure.new_size.0 == 0 || configure.new_size.1 == 0 {
             // Use our default size
         }
-        self.draw(qh, chrono::Local::now(), true);
+        self.draw(qh, chrono::Local::now());
     }
 }
 
@@ -365,12 +610,18 @@ impl SeatHandler for MonitorWidget {
     fn new_seat(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _seat: wayland_client::protocol::wl_seat::WlSeat) {}
     
     /// Called when a seat gains a new capability (pointer, keyboard, touch).
-    /// We request pointer events when pointer capability is available.
+    /// We request pointer and keyboard events when available; keys only
+    /// arrive while the surface has keyboard focus (keyboard positioning).
     fn new_capability(&mut self, _conn: &Connection, qh: &QueueHandle<Self>, seat: wayland_client::protocol::wl_seat::WlSeat, capability: Capability) {
         if capability == Capability::Pointer {
             // Request pointer events
             let _ = self.seat_state.get_pointer(qh, &seat);
         }
+        if capability == Capability::Keyboard {
+            if let Err(e) = self.seat_state.get_keyboard(qh, &seat, None) {
+                log::warn!("Failed to get keyboard: {}", e);
+            }
+        }
     }
     fn remove_capability(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _seat: wayland_client::protocol::wl_seat::WlSeat, _capability: Capability) {}
     fn remove_seat(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _seat: wayland_client::protocol::wl_seat::WlSeat) {}
@@ -384,7 +635,7 @@ impl PointerHandler for MonitorWidget {
     fn pointer_frame(
         &mut self,
         _conn: &Connection,
-        _qh: &QueueHandle<Self>,
+        qh: &QueueHandle<Self>,
         _pointer: &wayland_client::protocol::wl_pointer::WlPointer,
         events: &[PointerEvent],
     ) {
@@ -409,6 +660,55 @@ impl PointerHandler for MonitorWidget {
                     
                     let mut handled = false;
                     
+                    // "Update available" badge (in the bottom padding, never overlaps a section)
+                    if let Some((x_start, y_start, x_end, y_end)) = self.update_badge_bounds {
+                        if click_x >= x_start && click_x <= x_end && click_y >= y_start && click_y <= y_end {
+                            if let Some(release) = self.update_checker.available() {
+                                log::info!("Opening release page for {}", release.version);
+                                open_release_page(&release.url);
+                            }
+                            handled = true;
+                        }
+                    }
+                    
+                    // Caffeine toggle (the whole section is the button)
+                    if !handled && self.section_at(event.position) == Some(WidgetSection::Caffeine) {
+                        self.set_caffeine(!self.caffeine_active, qh);
+                        handled = true;
+                    }
+                    
+                    // Governor switch (the frequency row under the CPU bar)
+                    if !handled && self.config.cpu_governor_click && self.hover_target_at(event.position) == Some(HoverTarget::CpuFrequency) {
+                        if let Some(governor) = self.metrics.cpu_frequency.as_ref().and_then(|f| f.next_governor()) {
+                            cpufreq::cycle_governor(governor);
+                        }
+                        handled = true;
+                    }
+                    
+                    // Do-not-disturb bell in the notifications header
+                    if !handled && self.hover_target_at(event.position) == Some(HoverTarget::DoNotDisturb) {
+                        if self.dnd.toggle() {
+                            self.force_redraw = true;
+                        }
+                        handled = true;
+                    }
+                    
+                    // Power profile button in the Battery header
+                    if !handled && self.hover_target_at(event.position) == Some(HoverTarget::PowerProfile) {
+                        if self.power_profile.cycle() {
+                            self.force_redraw = true;
+                        }
+                        handled = true;
+                    }
+                    
+                    // Mute toggle (the whole audio section is the button)
+                    if !handled && self.section_at(event.position) == Some(WidgetSection::Audio) {
+                        if self.audio.toggle_mute() {
+                            self.force_redraw = true;
+                        }
+                        handled = true;
+                    }
+                    
                     // Priority 1: Check "Clear All" button (top of notification section)
                     if let Some((x_start, y_start, x_end, y_end)) = self.clear_all_bounds {
                         if click_x >= x_start && click_x <= x_end && click_y >= y_start && click_y <= y_end {
@@ -421,11 +721,27 @@ impl PointerHandler for MonitorWidget {
                     }
                     
                     // Priority 2: Check notification X buttons (group clear or individual dismiss)
-                    // Key format: "app_name" for groups, "app_name:timestamp" for individual
+                    // and notification rows (default action, else keep the body open)
+                    // Key format: "app_name" for groups, "app_name:timestamp" for individual,
+                    // "row:app_name:timestamp" for rows
                     if !handled {
                         for (key, x_start, y_start, x_end, y_end) in &self.notification_clear_bounds {
                             log::trace!("Checking X button for {}: ({}-{}, {}-{})", key, x_start, x_end, y_start, y_end);
                             if click_x >= *x_start && click_x <= *x_end && click_y >= *y_start && click_y <= *y_end {
+                                if let Some(row) = key.strip_prefix("row:") {
+                                    let target = row.rsplit_once(':').and_then(|(app_name, timestamp)| Some((app_name, timestamp.parse::<u64>().ok()?)));
+                                    if let Some((app_name, timestamp)) = target {
+                                        if self.notifications.activate(app_name, timestamp) {
+                                            log::info!("Activated notification: {} at timestamp {}", app_name, timestamp);
+                                        } else if !self.expanded_notifications.remove(row) {
+                                            self.expanded_notifications.insert(row.to_string());
+                                        }
+                                        self.force_redraw = true;
+                                        handled = true;
+                                        break;
+                                    }
+                                    continue;
+                                }
                                 // Check if this is an individual notification dismiss (format: "app_name:timestamp")
                                 // or a group clear (format: just "app_name")
                                 if let Some((app_name, timestamp_str)) = key.split_once(':') {
@@ -452,13 +768,15 @@ impl PointerHandler for MonitorWidget {
                     
                     // Priority 3: Check notification group headers for collapse/expand toggle
                     // Clicking a group header (excluding X button area) toggles visibility
-                    if !handled {
+                    // Group bounds are vertical only; the section's column gives the x range
+                    let notification_x = self.notification_bounds.map(|(x_start, _, x_end, _)| (x_start, x_end));
+                    if let Some((section_x, section_x_end)) = notification_x.filter(|_| !handled) {
                         for (app_name, y_start, y_end) in &self.notification_group_bounds {
                             log::trace!("Checking group header for {}: {}-{}", app_name, y_start, y_end);
-                            if click_y >= *y_start && click_y <= *y_end {
+                            if click_y >= *y_start && click_y <= *y_end && click_x >= section_x && click_x <= section_x_end {
                                 // Make sure we're not clicking the X button area
                                 // X button is at x=340, with radius 7, so roughly 333-347
-                                if click_x < 333.0 {
+                                if click_x < section_x + 333.0 {
                                     log::debug!("Toggling notification group: {}", app_name);
                                     if self.collapsed_groups.contains(app_name) {
                                         self.collapsed_groups.remove(app_name);
@@ -475,7 +793,8 @@ impl PointerHandler for MonitorWidget {
                         }
                     }
                     
-                    // Priority 4: Check media control buttons (previous, play/pause, next, progress_bar, player_dot_N)
+                    // Priority 4: Check media control buttons (previous, play/pause, next, progress_bar,
+                    // shuffle, loop, volume, player_dot_N)
                     if !handled {
                         for (button_name, x_start, y_start, x_end, y_end) in &self.media_button_bounds {
                             if click_x >= *x_start && click_x <= *x_end && click_y >= *y_start && click_y <= *y_end {
@@ -498,6 +817,17 @@ impl PointerHandler for MonitorWidget {
                                         log::info!("Progress bar clicked: {:.1}%", progress * 100.0);
                                         self.media.seek_to_progress(progress);
                                     }
+                                    "shuffle" => {
+                                        self.media.toggle_shuffle();
+                                    }
+                                    "loop" => {
+                                        self.media.cycle_loop_mode();
+                                    }
+                                    "volume" => {
+                                        let volume = ((click_x - x_start) / (x_end - x_start)).clamp(0.0, 1.0);
+                                        log::info!("Volume bar clicked: {:.0}%", volume * 100.0);
+                                        self.media.set_volume(volume);
+                                    }
                                     name if name.starts_with("player_dot_") => {
                                         // Extract player index from button name
                                         if let Some(index_str) = name.strip_prefix("player_dot_") {
@@ -525,9 +855,9 @@ impl PointerHandler for MonitorWidget {
                 
                 // === Right-click: Quick clear notifications in section ===
                 PointerEventKind::Press { button, .. } if button == 0x111 => {
-                    if let Some((y_start, y_end)) = self.notification_bounds {
-                        let click_y = event.position.1;
-                        if click_y >= y_start && click_y <= y_end {
+                    if let Some((x_start, y_start, x_end, y_end)) = self.notification_bounds {
+                        let (click_x, click_y) = event.position;
+                        if click_x >= x_start && click_x <= x_end && click_y >= y_start && click_y <= y_end {
                             log::info!("Right-click on notifications section, clearing");
                             self.notifications.clear();
                             self.collapsed_groups.clear();
@@ -547,38 +877,137 @@ impl PointerHandler for MonitorWidget {
                     self.drag_start_y = event.position.1;
                 }
                 
-                // End drag on release
-                PointerEventKind::Release { button, .. } if button == 0x110 && self.config.widget_movable => {
-                    self.dragging = false;
+                // End drag on release (saves the final position)
+                PointerEventKind::Release { button, .. } if button == 0x110 && self.dragging => {
+                    self.finish_drag();
                 }
                 
-                // Update position while dragging (saves to config for persistence)
+                // Move the surface while dragging; the config is written on release
                 PointerEventKind::Motion { .. } if self.dragging && self.config.widget_movable => {
                     let delta_x = (event.position.0 - self.drag_start_x) as i32;
                     let delta_y = (event.position.1 - self.drag_start_y) as i32;
+                    self.drag_offset.0 += delta_x;
+                    self.drag_offset.1 += delta_y;
+                    if let Some(layer_surface) = &self.layer_surface {
+                        self.apply_position(layer_surface);
+                        layer_surface.commit();
+                    }
                     
-                    let mut new_config = (*self.config).clone();
-                    new_config.widget_x += delta_x;
-                    new_config.widget_y += delta_y;
-                    
-                    if new_config.write_entry(&self.config_handler).is_ok() {
-                        self.config = Arc::new(new_config);
-                        
-                        if let Some(layer_surface) = &self.layer_surface {
-                            layer_surface.set_margin(self.config.widget_y, 0, 0, self.config.widget_x);
-                            layer_surface.commit();
-                        }
+                    // Show the guides the widget snapped to
+                    let guides = self.placement().1;
+                    if guides != self.snap_guides {
+                        self.snap_guides = guides;
+                        self.force_redraw = true;
                     }
                     
                     self.drag_start_x = event.position.0;
                     self.drag_start_y = event.position.1;
                 }
+                
+                // === Error badge and metric hover (tooltips) ===
+                PointerEventKind::Motion { .. } => {
+                    let (x, y) = event.position;
+                    let hovered = self.error_badge_bounds
+                        .iter()
+                        .find(|(_, x1, y1, x2, y2)| x >= *x1 && x <= *x2 && y >= *y1 && y <= *y2)
+                        .map(|(section, ..)| *section);
+                    if hovered != self.hovered_error {
+                        self.hovered_error = hovered;
+                        self.force_redraw = true;
+                    }
+                    
+                    // Hovered notification shows its body
+                    let notification = self.notification_row_at(event.position);
+                    if notification != self.hovered_notification {
+                        self.hovered_notification = notification;
+                        self.force_redraw = true;
+                    }
+                    
+                    // Shown by the main loop once the pointer rests on it
+                    let target = self.hover_target_at(event.position).filter(|_| self.config.show_tooltips);
+                    if self.hover.hover(target, Instant::now()) {
+                        self.force_redraw = true;
+                    }
+                }
+                PointerEventKind::Leave { .. } => {
+                    self.scroll_steps.reset();
+                    if self.hovered_error.take().is_some() {
+                        self.force_redraw = true;
+                    }
+                    if self.hovered_notification.take().is_some() {
+                        self.force_redraw = true;
+                    }
+                    if self.hover.hover(None, Instant::now()) {
+                        self.force_redraw = true;
+                    }
+                }
+                
+                // === Scroll wheel: the section's configured action ===
+                PointerEventKind::Axis { vertical, .. } => {
+                    let steps = self.scroll_steps.push(vertical.discrete, vertical.absolute);
+                    if steps != 0 {
+                        self.handle_scroll(event.position, steps);
+                    }
+                }
                 _ => {}
             }
         }
     }
 }
 
+/// Handles key presses while the widget has keyboard focus.
+///
+/// Only with keyboard positioning on, which makes the surface focusable
+/// (`KeyboardInteractivity::OnDemand`, focused by clicking it):
+///
+/// | Key                | Action                                   |
+/// |--------------------|------------------------------------------|
+/// | Enter              | Enter move mode                          |
+/// | Arrow keys         | Move by 1 pixel (move mode only)         |
+/// | Shift + arrow keys | Move by 10 pixels (move mode only)       |
+/// | Escape             | Leave move mode                          |
+impl KeyboardHandler for MonitorWidget {
+    fn enter(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _keyboard: &wl_keyboard::WlKeyboard,
+        _surface: &wl_surface::WlSurface,
+        _serial: u32,
+        _raw: &[u32],
+        _keysyms: &[Keysym],
+    ) {
+        log::debug!("Widget got keyboard focus");
+    }
+    
+    fn leave(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _keyboard: &wl_keyboard::WlKeyboard, _surface: &wl_surface::WlSurface, _serial: u32) {
+        self.shift_held = false;
+    }
+    
+    fn press_key(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _keyboard: &wl_keyboard::WlKeyboard, _serial: u32, event: KeyEvent) {
+        self.handle_key(event.keysym);
+    }
+    
+    fn repeat_key(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _keyboard: &wl_keyboard::WlKeyboard, _serial: u32, event: KeyEvent) {
+        self.handle_key(event.keysym);
+    }
+    
+    fn release_key(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _keyboard: &wl_keyboard::WlKeyboard, _serial: u32, _event: KeyEvent) {}
+    
+    fn update_modifiers(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _keyboard: &wl_keyboard::WlKeyboard,
+        _serial: u32,
+        modifiers: Modifiers,
+        _raw_modifiers: RawModifiers,
+        _layout: u32,
+    ) {
+        self.shift_held = modifiers.shift;
+    }
+}
+
 /// Handles shared memory buffer allocation for Wayland rendering.
 impl ShmHandler for MonitorWidget {
     fn shm_state(&mut self) -> &mut Shm {
@@ -620,6 +1049,36 @@ impl MonitorWidget {
         } else {
             Some(config.cider_api_token.clone())
         };
+        let custom_commands = CustomCommandMonitor::new(active_commands(&config));
+        let sampler = Sampler::new(sampler_settings(&config));
+        let fonts = Fonts::resolve(&config.fonts);
+        let audio = AudioMonitor::new(config.show_audio);
+        let health = HealthMonitor::new(health_settings(&config));
+        let containers = ContainerMonitor::new(container_settings(&config));
+        let vms = VmMonitor::new(vm_settings(&config));
+        let smart = SmartMonitor::new(smart_settings(&config));
+        let pools = PoolMonitor::new(pool_settings(&config));
+        let feeds = FeedMonitor::new(feed_settings(&config));
+        let ticker = TickerMonitor::new(ticker_settings(&config));
+        let air_quality = AirQualityMonitor::new(air_quality_settings(&config));
+        let calendar = CalendarMonitor::new(active_settings(&config));
+        let network_history = History::new(config.network_graph_seconds);
+        let temp_history = History::new(config.temp_graph_minutes.saturating_mul(60));
+        let update_checker = UpdateChecker::new(config.check_for_updates);
+        let animator = Animator::new(config.enable_animations);
+        
+        // Window state for auto-hide; optional since not every compositor has it
+        let toplevel_manager = globals
+            .bind::<ZwlrForeignToplevelManagerV1, _, _>(qh, 1..=3, ())
+            .map_err(|e| log::info!("Foreign toplevel protocol not available, auto-hide disabled: {}", e))
+            .ok();
+        
+        // Screen blanking inhibitor for the caffeine section
+        let idle_inhibit_manager = globals
+            .bind::<ZwpIdleInhibitManagerV1, _, _>(qh, 1..=1, ())
+            .map_err(|e| log::info!("Idle inhibit protocol not available, caffeine disabled: {}", e))
+            .ok();
+        let caffeine_active = config.show_caffeine && config.caffeine_on_start;
 
         Self {
             registry_state,
@@ -632,33 +1091,87 @@ impl MonitorWidget {
             config: Arc::new(config),
             config_handler,
             last_config_check: Instant::now(),
-            utilization: UtilizationMonitor::new(),
-            temperature: TemperatureMonitor::new(),
-            network: NetworkMonitor::new(),
+            sampler,
+            metrics: MetricsSnapshot::default(),
+            network_rx_history: network_history.clone(),
+            network_tx_history: network_history,
+            cpu_temp_history: temp_history.clone(),
+            gpu_temp_history: temp_history,
             weather: WeatherMonitor::new(weather_api_key, weather_location),
-            storage: StorageMonitor::new(),
-            battery: BatteryMonitor::new(),
-            notifications: NotificationMonitor::new(5), // Keep last 5 notifications
+            air_quality,
+            notifications: NotificationMonitor::new(5, history_settings(&config)), // Keep last 5 notifications without history
+            dnd: DoNotDisturb::new(),
+            power_profile: PowerProfileMonitor::new(config.show_battery && config.show_power_profile),
             media: MediaMonitor::new(cider_api_token),
-            last_update: Instant::now(),
+            lyrics: LyricsMonitor::new(),
+            custom_commands,
+            audio,
+            health,
+            containers,
+            vms,
+            smart,
+            pools,
+            space_alerts: SpaceAlerts::default(),
+            feeds,
+            ticker,
+            calendar,
+            locale: config::time_locale(),
+            plugins: PluginHost::new(),
+            update_checker,
             pool: None,
+            last_width: WIDGET_WIDTH,
             last_height: WIDGET_HEIGHT,
             last_drawn_second: None,
+            animator,
+            frame_pending: false,
+            media_title_since: (String::new(), Instant::now()),
             dragging: false,
             drag_start_x: 0.0,
             drag_start_y: 0.0,
+            drag_offset: (0, 0),
+            snap_guides: SnapGuides::default(),
+            schedule_effect: ScheduleEffect::default(),
+            shift_held: false,
             notification_bounds: None,
             notification_group_bounds: Vec::new(),
             notification_clear_bounds: Vec::new(),
             clear_all_bounds: None,
             media_button_bounds: Vec::new(),
+            error_badge_bounds: Vec::new(),
+            hovered_error: None,
+            update_badge_bounds: None,
+            section_area_bounds: Vec::new(),
+            hover_target_bounds: Vec::new(),
+            hover: HoverTracker::default(),
+            scroll_steps: ScrollSteps::default(),
+            clock_zone_index: 0,
+            notification_scroll: 0,
             collapsed_groups: std::collections::HashSet::new(),
+            hovered_notification: None,
+            expanded_notifications: std::collections::HashSet::new(),
             grouped_notifications: Vec::new(),
             notifications_version: 0,
             force_redraw: false,
             last_click_time: Instant::now(),
             exit: false,
+            hidden: false,
+            auto_hidden: false,
+            toplevel_manager,
+            toplevels: ToplevelTracker::new(),
+            idle_inhibit_manager,
+            idle_inhibitor: None,
+            caffeine_active,
+            debug_overlay: config.show_debug_overlay,
+            debug_stats: DebugStats::default(),
+            hardware_lines: HardwareInfo::detect().lines(),
+            identity: Identity::detect(),
+            widget_output: None,
+            surface_output: None,
+            frame_exporter: None,
+            recorder: None,
+            watchdog: Watchdog::new(),
             theme: CosmicTheme::load(),
+            fonts,
             last_theme_check: Instant::now(),
         }
     }
@@ -666,91 +1179,367 @@ impl MonitorWidget {
     /// Create the layer surface for desktop overlay rendering.
     ///
     /// Configures the surface to:
-    /// - Anchor to top-left corner with offset from config
+    /// - Show on the configured output, if it is connected
+    /// - Anchor to the configured corner with offsets from config
     /// - Use Layer::Bottom so windows can cover the widget
     /// - Not reserve exclusive space
-    /// - Accept keyboard input on demand (for future features)
+    /// - Accept keyboard input on demand, only with keyboard positioning on
     fn create_layer_surface(&mut self, qh: &QueueHandle<Self>) {
         let surface = self.compositor_state.create_surface(qh);
+        let output = self.configured_output();
         
         let layer_surface = self.layer_shell.create_layer_surface(
             qh,
             surface,
             Layer::Bottom,  // Below windows, acts like desktop widget
             Some("cosmic-monitor-widget"),
-            None,
+            output.as_ref(),
         );
+        self.surface_output = output;
 
         // Configure the layer surface
-        layer_surface.set_anchor(Anchor::TOP | Anchor::LEFT); // Anchor to top-left corner
         layer_surface.set_size(WIDGET_WIDTH, WIDGET_HEIGHT);
         layer_surface.set_exclusive_zone(-1); // Don't reserve space
-        log::debug!("Setting layer surface margins: top={}, left={}", self.config.widget_y, self.config.widget_x);
-        layer_surface.set_margin(self.config.widget_y, 0, 0, self.config.widget_x);
-        // Use OnDemand to get input focus when clicked - improves input responsiveness
-        layer_surface.set_keyboard_interactivity(
-            smithay_client_toolkit::shell::wlr_layer::KeyboardInteractivity::OnDemand
-        );
+        self.apply_position(&layer_surface);
+        self.update_input_region(&layer_surface);
         
         layer_surface.commit();
         
         self.layer_surface = Some(layer_surface);
+        self.frame_pending = false;
+        self.animator.fade_in_new_surface(Instant::now());
+        self.update_idle_inhibit(qh);
     }
-
-    /// Update system statistics from all enabled monitoring modules.
+    
+    /// Move the widget by a keyboard delta and save the position.
+    fn move_widget(&mut self, delta_x: i32, delta_y: i32) {
+        self.drag_offset.0 += delta_x;
+        self.drag_offset.1 += delta_y;
+        self.save_position();
+    }
+    
+    /// End the drag in progress and save where it left the widget
+    /// (snapped, so saved while still dragging).
+    fn finish_drag(&mut self) {
+        self.save_position();
+        self.dragging = false;
+        if self.snap_guides.any() {
+            self.snap_guides = SnapGuides::default();
+            self.force_redraw = true;
+        }
+    }
+    
+    /// Write the position including the pending drag offset, once.
     ///
-    /// Respects the configured update interval to avoid excessive polling.
-    /// Only updates modules that are currently enabled in the config.
-    fn update_system_stats(&mut self) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+    /// The offset is folded into `widget_x`/`widget_y` clamped to the
+    /// output, so a widget dragged past the edge is saved at the edge.
+    fn save_position(&mut self) {
+        if self.drag_offset == (0, 0) {
+            return;
+        }
+        let (x, y) = self.position();
+        self.drag_offset = (0, 0);
+        if (x, y) == (self.config.widget_x, self.config.widget_y) {
+            return;
+        }
+        
+        let mut new_config = (*self.config).clone();
+        new_config.widget_x = x;
+        new_config.widget_y = y;
+        match new_config.write_entry(&self.config_handler) {
+            Ok(()) => {
+                log::info!("Saved widget position {} x {} from the {} corner", x, y, new_config.widget_anchor.label());
+                self.config = Arc::new(new_config);
+            }
+            Err(e) => log::warn!("Failed to save widget position: {}", e),
+        }
+        if let Some(layer_surface) = &self.layer_surface {
+            self.apply_position(layer_surface);
+            layer_surface.commit();
+        }
+    }
+    
+    /// Offsets from the anchored corner including the pending drag offset,
+    /// kept on the output.
+    fn position(&self) -> (i32, i32) {
+        self.placement().0
+    }
+    
+    /// Offsets from the anchored corner including the pending drag offset,
+    /// and the guides a pointer drag snapped to.
+    ///
+    /// Offsets from the right/bottom edge grow towards the left/top. The
+    /// output's logical size is only known once the surface entered it;
+    /// until then offsets are just kept from going negative, and nothing
+    /// snaps.
+    fn placement(&self) -> ((i32, i32), SnapGuides) {
+        let anchor = self.config.widget_anchor;
+        let (dx, dy) = self.drag_offset;
+        let x = self.config.widget_x + if anchor.is_right() { -dx } else { dx };
+        let y = self.config.widget_y + if anchor.is_bottom() { -dy } else { dy };
+        
+        let output_size = self.widget_output
+            .as_ref()
+            .or(self.surface_output.as_ref())
+            .and_then(|output| self.output_state.info(output))
+            .and_then(|info| info.logical_size);
+        let Some((output_width, output_height)) = output_size else {
+            return ((x.max(0), y.max(0)), SnapGuides::default());
+        };
+        let (width, height) = (self.last_width as i32, self.last_height as i32);
         
-        if elapsed < (self.config.update_interval_ms as f64 / 1000.0) {
+        // Snap in top-left coordinates, then measure from the anchor again
+        let left = if anchor.is_right() { output_width - width - x } else { x };
+        let top = if anchor.is_bottom() { output_height - height - y } else { y };
+        let ((left, top), guides) = if self.dragging {
+            snap::snap((left, top), (width, height), (output_width, output_height), self.config.snap_to_edges, self.config.snap_grid)
+        } else {
+            ((left, top), SnapGuides::default())
+        };
+        let left = left.clamp(0, (output_width - width).max(0));
+        let top = top.clamp(0, (output_height - height).max(0));
+        (
+            (
+                if anchor.is_right() { output_width - width - left } else { left },
+                if anchor.is_bottom() { output_height - height - top } else { top },
+            ),
+            guides,
+        )
+    }
+    
+    /// Handle a key pressed while the widget has keyboard focus.
+    fn handle_key(&mut self, keysym: Keysym) {
+        if !self.config.keyboard_positioning {
+            return;
+        }
+        let step = if self.shift_held { 10 } else { 1 };
+        let delta = match keysym {
+            Keysym::Left => (-step, 0),
+            Keysym::Right => (step, 0),
+            Keysym::Up => (0, -step),
+            Keysym::Down => (0, step),
+            Keysym::Return | Keysym::KP_Enter => {
+                self.set_move_mode(true);
+                return;
+            }
+            Keysym::Escape => {
+                self.set_move_mode(false);
+                return;
+            }
+            _ => return,
+        };
+        if self.config.widget_movable {
+            self.move_widget(delta.0, delta.1);
+        }
+    }
+    
+    /// Turn move mode on or off and save it.
+    ///
+    /// The same flag the settings window sets while open, so leaving move
+    /// mode also ends dragging and restores click-through.
+    fn set_move_mode(&mut self, movable: bool) {
+        if self.config.widget_movable == movable {
             return;
         }
+        log::info!("{} move mode from the keyboard", if movable { "Entering" } else { "Leaving" });
+        self.finish_drag();
+        let mut new_config = (*self.config).clone();
+        new_config.widget_movable = movable;
+        if new_config.write_entry(&self.config_handler).is_ok() {
+            self.config = Arc::new(new_config);
+            if let Some(layer_surface) = &self.layer_surface {
+                self.update_input_region(layer_surface);
+                layer_surface.commit();
+            }
+        }
+    }
+    
+    /// Anchor the surface to the configured corner and apply the offsets.
+    ///
+    /// Takes effect on the next commit.
+    fn apply_position(&self, layer_surface: &LayerSurface) {
+        let anchor = self.config.widget_anchor;
+        let (x, y) = self.position();
+        let vertical = if anchor.is_bottom() { Anchor::BOTTOM } else { Anchor::TOP };
+        let horizontal = if anchor.is_right() { Anchor::RIGHT } else { Anchor::LEFT };
+        log::debug!("Setting layer surface position: {:?}, x={}, y={}", anchor, x, y);
+        layer_surface.set_anchor(vertical | horizontal);
         
-        self.last_update = now;
-
-        log::trace!("Updating system stats");
+        // Margins are (top, right, bottom, left); only the anchored edges count
+        layer_surface.set_margin(
+            if anchor.is_bottom() { 0 } else { y },
+            if anchor.is_right() { x } else { 0 },
+            if anchor.is_bottom() { y } else { 0 },
+            if anchor.is_right() { 0 } else { x },
+        );
+    }
+    
+    /// The connected output named by `config.widget_output`, if any.
+    fn configured_output(&self) -> Option<wl_output::WlOutput> {
+        if self.config.widget_output.is_empty() {
+            return None;
+        }
+        self.output_state.outputs().find(|output| {
+            self.output_state
+                .info(output)
+                .and_then(|info| info.name)
+                .is_some_and(|name| name == self.config.widget_output)
+        })
+    }
+    
+    /// Recreate the surface if it isn't on the configured output.
+    ///
+    /// Layer surfaces can't change outputs, so the surface is replaced. This
+    /// happens when the setting changes and when outputs appear: the surface
+    /// is created before the compositor has announced any output, and the
+    /// configured one may be plugged in later.
+    fn move_to_configured_output(&mut self, qh: &QueueHandle<Self>) {
+        if self.layer_surface.is_none() || self.configured_output() == self.surface_output {
+            return;
+        }
+        log::info!("Moving widget to output '{}'", self.config.widget_output);
+        self.destroy_surface();
+        self.restore_surface(qh);
+    }
+    
+    /// Write the connected outputs to the widget cache for the settings app.
+    ///
+    /// `removed` is left out (it is still listed while being destroyed).
+    fn cache_outputs(&self, removed: Option<&wl_output::WlOutput>) {
+        let mut outputs: Vec<CachedOutput> = self.output_state
+            .outputs()
+            .filter(|output| Some(output) != removed)
+            .filter_map(|output| self.output_state.info(&output))
+            .filter_map(|info| {
+                let (x, y) = info.logical_position?;
+                let (width, height) = info.logical_size?;
+                Some(CachedOutput {
+                    name: info.name.unwrap_or_default(),
+                    description: info.description.unwrap_or_default(),
+                    x,
+                    y,
+                    width,
+                    height,
+                })
+            })
+            .collect();
+        outputs.sort_by_key(|output| (output.x, output.y));
+        WidgetCache::update_outputs(outputs);
+    }
+    
+    /// Set the surface's input region according to click-through mode, and
+    /// whether it takes keyboard focus.
+    ///
+    /// An empty region makes the compositor deliver all pointer events to
+    /// whatever is underneath; `None` restores the default (whole surface).
+    /// Click-through is suspended while the widget is movable so it can
+    /// still be dragged. Takes effect on the next commit.
+    fn update_input_region(&self, layer_surface: &LayerSurface) {
+        // Focusable (when clicked) only for keyboard positioning, so the
+        // widget doesn't take focus from windows otherwise
+        layer_surface.set_keyboard_interactivity(if self.config.keyboard_positioning {
+            KeyboardInteractivity::OnDemand
+        } else {
+            KeyboardInteractivity::None
+        });
+        
+        let surface = layer_surface.wl_surface();
+        if self.config.click_through && !self.config.widget_movable {
+            match Region::new(&self.compositor_state) {
+                // The region is copied by the compositor, so it can be dropped right away
+                Ok(region) => surface.set_input_region(Some(region.wl_region())),
+                Err(e) => log::warn!("Failed to create input region: {}", e),
+            }
+        } else {
+            surface.set_input_region(None);
+        }
+    }
 
-        // Update monitoring modules (only if enabled)
-        if self.config.show_cpu || self.config.show_memory || self.config.show_gpu {
-            log::trace!("Updating CPU/Memory/GPU utilization");
-            self.utilization.update();
+    /// Take the newest snapshot from the sampler thread, if one arrived.
+    ///
+    /// The sampler samples on interval-aligned ticks independently of
+    /// rendering, so samples are evenly spaced no matter when redraws
+    /// happen. See [`widget::sampler`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if a new sample arrived (the caller should redraw).
+    fn poll_sampling(&mut self) -> bool {
+        match self.sampler.latest() {
+            Some(metrics) => {
+                self.debug_stats.record_sample(metrics.sample_ms);
+                self.update_system_stats(metrics);
+                true
+            }
+            None => false,
         }
+    }
+
+    /// Apply a new sample and update everything that follows the sample tick.
+    ///
+    /// # Arguments
+    /// * `metrics` - Snapshot from the sampler thread
+    fn update_system_stats(&mut self, metrics: MetricsSnapshot) {
+        log::trace!("Updating system stats");
+        self.metrics = metrics;
+        let sample_ms = self.metrics.sample_ms;
         
-        if self.config.show_cpu_temp || self.config.show_gpu_temp {
-            log::trace!("Updating temperature");
-            self.temperature.update();
+        if self.config.show_network && self.metrics.network_ready {
+            self.network_rx_history.push(sample_ms, self.metrics.network_rx_rate);
+            self.network_tx_history.push(sample_ms, self.metrics.network_tx_rate);
         }
         
-        if self.config.show_network {
-            log::trace!("Updating network");
-            self.network.update();
+        // 0°C means the sensor is missing, which would drag the graph down
+        if self.config.shows_temp_graph() && self.metrics.temperatures_ready {
+            if self.metrics.cpu_temp > 0.0 {
+                self.cpu_temp_history.push(sample_ms, self.metrics.cpu_temp as f64);
+            }
+            if self.metrics.gpu_temp > 0.0 {
+                self.gpu_temp_history.push(sample_ms, self.metrics.gpu_temp as f64);
+            }
         }
         
-        // Update storage
+        // Alert once when a filesystem runs low on space or inodes
         if self.config.show_storage {
-            log::trace!("Updating storage");
-            self.storage.update();
-            log::trace!("Storage updated, {} disks found", self.storage.disk_info.len());
-        }
-
-        // Update battery info only when the section and Solaar integration are enabled
-        if self.config.show_battery && self.config.enable_solaar_integration {
-            log::trace!("Updating battery info from Solaar");
-            self.battery.update();
+            let crossed = self.space_alerts.check(&self.metrics.disks, &space_limits(&self.config));
+            if self.config.storage_alerts {
+                for (disk, low) in crossed {
+                    space_alert::send_alert(disk, low);
+                }
+            }
         }
         
         // Update weather (has its own rate limiting - every 10 minutes)
         if self.config.show_weather {
             log::trace!("Requesting weather update");
             self.weather.update();
+            // The air quality monitor has its own 30 minute refresh
+            self.air_quality.set_coordinates(self.weather.coordinates());
         }
         
         // Update grouped notifications cache if notifications changed
         if self.config.show_notifications {
             self.update_notification_groups();
+            // DND may have been switched in the panel
+            if self.dnd.refresh() {
+                self.force_redraw = true;
+            }
+        }
+        
+        if self.config.publish_stats || self.config.record_metrics || self.config.show_plugins {
+            let snapshot = self.stats_snapshot();
+            if self.config.publish_stats {
+                if let Err(e) = snapshot.publish() {
+                    log::warn!("Failed to publish stats: {}", e);
+                }
+            }
+            // Plugins draw from the same snapshot that is published
+            if self.config.show_plugins {
+                self.plugins.update(&snapshot);
+            }
+            self.record_metrics(&snapshot);
+        } else {
+            self.recorder = None;
         }
         
         log::trace!("System stats update complete");
@@ -758,7 +1547,8 @@ impl MonitorWidget {
     
     /// Update the cached notification groups.
     ///
-    /// Groups notifications by app name and sorts by most recent.
+    /// Groups notifications by app name and sorts by most recent, with
+    /// groups holding a critical notification first.
     /// Only recomputes if the notification count has changed.
     fn update_notification_groups(&mut self) {
         let notifications = self.notifications.get_notifications();
@@ -776,12 +1566,15 @@ impl MonitorWidget {
                        .push(n);
             }
             
-            // Convert to vec and sort by most recent notification
+            // Convert to vec and sort by most recent notification,
+            // groups with critical notifications pinned first
             let mut groups: Vec<_> = grouped.into_iter().collect();
             groups.sort_by(|a, b| {
+                let a_critical = a.1.iter().any(|n| n.is_critical());
+                let b_critical = b.1.iter().any(|n| n.is_critical());
                 let a_latest = a.1.iter().map(|n| n.timestamp).max().unwrap_or(0);
                 let b_latest = b.1.iter().map(|n| n.timestamp).max().unwrap_or(0);
-                b_latest.cmp(&a_latest)
+                b_critical.cmp(&a_critical).then(b_latest.cmp(&a_latest))
             });
             
             self.grouped_notifications = groups;
@@ -790,6 +1583,18 @@ impl MonitorWidget {
         }
     }
 
+    /// Forget the click and hover bounds of a frame that wasn't drawn.
+    fn clear_interaction_bounds(&mut self) {
+        self.notification_group_bounds.clear();
+        self.notification_clear_bounds.clear();
+        self.clear_all_bounds = None;
+        self.media_button_bounds.clear();
+        self.error_badge_bounds.clear();
+        self.update_badge_bounds = None;
+        self.section_area_bounds.clear();
+        self.hover_target_bounds.clear();
+    }
+
     /// Render the widget to the Wayland surface.
     ///
     /// This is the main rendering function that:
@@ -799,11 +1604,27 @@ impl MonitorWidget {
     /// 4. Updates click bounds for interactive elements
     /// 5. Commits the buffer to the compositor
     ///
+    /// A failed pool, buffer or Cairo draw skips the frame instead of
+    /// crashing; the shm pool is rebuilt on the next frame.
+    ///
     /// # Arguments
-    /// * `qh` - Queue handle (unused but required by trait)
+    /// * `qh` - Queue handle (for frame callbacks while animating)
     /// * `current_time` - Time to display on clock
-    /// * `update_stats` - Whether to poll system statistics
-    fn draw(&mut self, _qh: &QueueHandle<Self>, current_time: chrono::DateTime<chrono::Local>, update_stats: bool) {
+    ///
+    /// Rendering never samples monitors; see [`Self::poll_sampling`].
+    fn draw(&mut self, qh: &QueueHandle<Self>, current_time: chrono::DateTime<chrono::Local>) {
+        let now = Instant::now();
+        
+        // Nothing to draw on while hidden (control pipe or auto-hide),
+        // except the remaining frames of a fade-out
+        if self.auto_hidden {
+            return;
+        }
+        if self.hidden && !self.animator.fading_out(now) {
+            self.destroy_surface();
+            return;
+        }
+        
         let layer_surface = match &self.layer_surface {
             Some(ls) => ls.clone(),
             None => {
@@ -812,84 +1633,176 @@ impl MonitorWidget {
             }
         };
 
-        // Only update system stats for timed updates, not for UI-only redraws
-        if update_stats {
-            self.update_system_stats();
+        // Start or stop the OBS frame exporter to follow the config
+        if self.config.export_frames != self.frame_exporter.is_some() {
+            self.frame_exporter = self.config.export_frames.then(FrameExporter::new);
         }
-        
-        // Calculate dynamic height based on enabled components
-        let disk_count = if self.config.show_storage { self.storage.disk_info.len() } else { 0 };
-        let battery_count = if self.config.show_battery { self.battery.devices().len() } else { 0 };
+
+        // Calculate dynamic size based on enabled components and layout mode
+        let disk_count = if self.config.show_storage { self.metrics.disks.len() } else { 0 };
+        let battery_count = if self.config.show_battery { self.metrics.batteries.len() } else { 0 };
         let notification_count = if self.config.show_notifications { self.notifications.get_notifications().len() } else { 0 };
         let player_count = if self.config.show_media { self.media.get_player_state().player_count() } else { 0 };
-        let width = WIDGET_WIDTH as i32;
-        let height = calculate_widget_height_with_all(&self.config, disk_count, battery_count, notification_count, player_count) as i32;
+        let calendar_events = if self.config.show_calendar {
+            event_rows(
+                &self.calendar.events(),
+                current_time,
+                self.config.use_24hour_time,
+                self.locale,
+                self.config.calendar_max_events as usize,
+            )
+        } else {
+            Vec::new()
+        };
+        let gpu_processes: &[_] = if self.config.show_gpu && self.config.show_gpu_processes {
+            &self.metrics.gpu_processes
+        } else {
+            &[]
+        };
+        let drive_health = if smart_settings(&self.config).is_some() { self.smart.drives() } else { Vec::new() };
+        let storage_pools = if self.config.show_storage_pools { self.pools.pools() } else { Vec::new() };
+        let vms = if self.config.show_vms { self.vms.vms() } else { Vec::new() };
+        let counts = ContentCounts {
+            disks: disk_count,
+            batteries: battery_count,
+            notifications: notification_count,
+            players: player_count,
+            plugin_height: if self.config.show_plugins {
+                widget::plugins::total_height(self.plugins.frames())
+            } else {
+                0
+            },
+            events: calendar_events.len(),
+            gpu_processes: gpu_processes.len(),
+            drives: drive_health.len(),
+            pools: storage_pools.len(),
+            vms: vms.len(),
+        };
+        // Sections hidden by a schedule are left out of the layout
+        let layout_config = self.schedule_effect.layout_config(&self.config);
+        let (width, height) = calculate_widget_size(&layout_config, counts);
+        let (width, height) = (width as i32, height as i32);
         let stride = width * 4;
+        let section_columns = section_columns(&layout_config, counts);
+        let compact_slots = compact_slots(&layout_config);
 
         log::trace!("Drawing widget: {}x{} (disks: {})", width, height, disk_count);
 
-        // Update layer surface size if height changed OR create pool if it doesn't exist
-        if height as u32 != self.last_height || self.pool.is_none() {
+        // Update layer surface size if the size changed OR create pool if it doesn't exist
+        if width as u32 != self.last_width || height as u32 != self.last_height || self.pool.is_none() {
             log::debug!("Updating surface size to {}x{}", width, height);
+            self.last_width = width as u32;
             self.last_height = height as u32;
             layer_surface.set_size(width as u32, height as u32);
             layer_surface.commit();
             
-            // Recreate pool with new size
-            self.pool = Some(SlotPool::new(width as usize * height as usize * 4, &self.shm_state)
-                .expect("Failed to create pool"));
-        }
-
-        // Store the data we need for rendering
-        let cpu_usage = self.utilization.cpu_usage;
-        let memory_usage = self.utilization.memory_usage;
-        let gpu_usage = self.utilization.get_gpu_usage();
-        let cpu_temp = self.temperature.cpu_temp;
-        let gpu_temp = self.temperature.gpu_temp;
-        let network_rx_rate = self.network.network_rx_rate;
-        let network_tx_rate = self.network.network_tx_rate;
-        let show_cpu = self.config.show_cpu;
-        let show_memory = self.config.show_memory;
-        let show_network = self.config.show_network;
-        let show_disk = self.config.show_disk;
-        let show_storage = self.config.show_storage;
-        let show_gpu = self.config.show_gpu;
-        let show_cpu_temp = self.config.show_cpu_temp;
-        let show_gpu_temp = self.config.show_gpu_temp;
-        let show_clock = self.config.show_clock;
-        let show_date = self.config.show_date;
-        let show_percentages = self.config.show_percentages;
-        let use_24hour_time = self.config.use_24hour_time;
-        let use_circular_temp_display = self.config.use_circular_temp_display;
-        let show_weather = self.config.show_weather;
-        let show_battery = self.config.show_battery;
-        let enable_solaar_integration = self.config.enable_solaar_integration;
+            // Recreate pool with new size; on failure the pool stays empty
+            // and the next frame tries again
+            match SlotPool::new(width as usize * height as usize * 4, &self.shm_state) {
+                Ok(pool) => self.pool = Some(pool),
+                Err(e) => {
+                    log::error!("Failed to create shm pool, skipping frame: {}", e);
+                    widget::diagnostics::record_error("render", format!("failed to create shm pool: {}", e));
+                    self.pool = None;
+                    return;
+                }
+            }
+        }
+
+        // Store the data we need for rendering (bars and arcs eased between samples)
+        let cpu_usage = self.animator.value("cpu", self.metrics.cpu_usage, now);
+        let memory_usage = self.animator.value("memory", self.metrics.memory_usage, now);
+        let gpu_usage = self.animator.value("gpu", self.metrics.gpu_usage, now);
+        let cpu_temp = self.animator.value("cpu_temp", self.metrics.cpu_temp, now);
+        let gpu_temp = self.animator.value("gpu_temp", self.metrics.gpu_temp, now);
+        let disk_info: Vec<DiskInfo> = self.metrics.disks.iter().map(|disk| {
+            let mut disk = disk.clone();
+            if !disk.is_loading {
+                let key = format!("disk:{}", disk.mount_point);
+                disk.used_percentage = self.animator.value(&key, disk.used_percentage, now);
+            }
+            disk
+        }).collect();
+        
+        // Notification bodies slide open while hovered or clicked open
+        let mut notification_expansion = std::collections::HashMap::new();
+        for (app_name, group) in &self.grouped_notifications {
+            for notification in group {
+                let key = format!("{}:{}", app_name, notification.timestamp);
+                let open = !self.config.compact_notifications
+                    || self.hovered_notification.as_ref() == Some(&key)
+                    || self.expanded_notifications.contains(&key);
+                let shown = self.animator.value(&format!("notification:{}", key), if open { 1.0 } else { 0.0 }, now);
+                notification_expansion.insert(key, shown as f64);
+            }
+        }
+        self.expanded_notifications.retain(|key| notification_expansion.contains_key(key));
+        
+        self.animator.retain(|key| {
+            if let Some(mount) = key.strip_prefix("disk:") {
+                disk_info.iter().any(|disk| disk.mount_point == mount)
+            } else if let Some(notification) = key.strip_prefix("notification:") {
+                notification_expansion.contains_key(notification)
+            } else {
+                true
+            }
+        });
+        let network_rx_rate = self.metrics.network_rx_rate;
+        let network_tx_rate = self.metrics.network_tx_rate;
         
         // Extract weather data
+        let weather_snapshot = self.weather.weather_data.lock().unwrap().clone();
         let (weather_temp, weather_desc, weather_location, weather_icon) = {
-            let weather_data_guard = self.weather.weather_data.lock().unwrap();
-            if let Some(ref data) = *weather_data_guard {
-                (data.temperature, data.description.clone(), data.location.clone(), data.icon.clone())
+            if let Some(ref data) = weather_snapshot {
+                // Recompute day/night locally rather than trusting the (possibly cached) icon suffix
+                let icon = data.display_icon(current_time.timestamp());
+                (data.temperature, data.description.clone(), data.location.clone(), icon)
             } else {
                 (f32::NAN, String::from("No data"), String::from("Unknown"), String::from("01d"))
             }
         };
+        let weather_status_label = self.weather.status_label(current_time.timestamp()).unwrap_or_default();
+        let weather_sun = weather_snapshot.as_ref().and_then(|data| data.sun_times(current_time.timestamp()));
+        let weather_moon_phase = weather_sun
+            .filter(|sun| self.config.weather_moon_phase && !sun.is_up(current_time.timestamp()))
+            .map(|_| moon_phase(current_time.timestamp()));
+        let weather_details = weather_snapshot.unwrap_or_default();
         
         let weather_desc = weather_desc.as_str();
         let weather_location = weather_location.as_str();
         let weather_icon = weather_icon.as_str();
 
-        // Snapshot battery devices for this frame
-        let battery_devices = self.battery.devices();
+        let section_errors = self.section_errors();
+        let loading_sections = self.loading_sections();
+        let custom_outputs = self.custom_commands.outputs();
+        let audio = self.audio.state();
+        let power_profile = self.power_profile.state().filter(|_| self.config.show_battery && self.config.show_power_profile);
+        let health = self.health.report();
+        let containers = self.containers.report();
+        let feed_rows = self.feeds.rows();
+        let ticker_rows = self.ticker.rows();
+        let world_clocks = world_clock_rows(&self.config.world_clocks, &current_time, self.config.use_24hour_time);
+        let update = self.update_checker.available();
+        let hovered_metric = self.hover.shown().filter(|_| self.config.show_tooltips);
+        let tooltip_lines = hovered_metric.map(|target| self.tooltip_lines(target)).unwrap_or_default();
+        let debug_lines = if self.debug_overlay { self.debug_stats.lines() } else { Vec::new() };
+        let identity = self.identity.text(&self.config);
         
         // Use cached grouped notifications (updated in update_system_stats)
         let grouped_notifications = &self.grouped_notifications;
 
         let pool = self.pool.as_mut().unwrap();
 
-        let (buffer, canvas) = pool
-            .create_buffer(width, height, stride, wl_shm::Format::Argb8888)
-            .expect("Failed to create buffer");
+        let (buffer, canvas) = match pool.create_buffer(width, height, stride, wl_shm::Format::Argb8888) {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                // Drop the pool so the next frame starts from a fresh one
+                log::error!("Failed to create buffer, skipping frame: {}", e);
+                widget::diagnostics::record_error("render", format!("failed to create buffer: {}", e));
+                self.pool = None;
+                return;
+            }
+        };
 
         // Get media info
         let player_state = self.media.get_player_state();
@@ -899,75 +1812,145 @@ impl MonitorWidget {
         let player_count = player_state.player_count();
         let current_player_index = player_state.current_index;
         
+        // A new track's title starts scrolling from the beginning
+        if self.media_title_since.0 != media_info.title {
+            self.media_title_since = (media_info.title.clone(), now);
+        }
+        let media_marquee = self.config.media_marquee.then(|| Marquee {
+            elapsed: now.saturating_duration_since(self.media_title_since.1),
+            speed: self.config.marquee_speed,
+            pause: Duration::from_millis(self.config.marquee_pause_ms as u64),
+        });
+        
+        // Lyrics are only fetched while they're shown
+        let show_lyrics = self.config.show_media && self.config.show_lyrics;
+        self.lyrics.set_track(if show_lyrics { TrackKey::from_media(&media_info) } else { None });
+        let lyrics = self.lyrics.current();
+        let lyrics_line = lyrics.as_deref().and_then(|lyrics| lyrics.line_at(media_info.position));
+        
         // Use Cairo for rendering
-        let params = RenderParams {
+        let data = FrameData {
             width,
             height,
             cpu_usage,
             memory_usage,
+            memory_used: self.metrics.memory_used,
+            memory_total: self.metrics.memory_total,
             gpu_usage,
             cpu_temp,
             gpu_temp,
             network_rx_rate,
             network_tx_rate,
-            show_cpu,
-            show_memory,
-            show_network,
-            show_disk,
-            show_storage,
-            show_gpu,
-            show_cpu_temp,
-            show_gpu_temp,
-            show_clock,
-            show_date,
-            show_percentages,
-            use_24hour_time,
-            use_circular_temp_display,
-            show_weather,
-            show_battery,
-            show_notifications: self.config.show_notifications,
-            show_media: self.config.show_media,
-            enable_solaar_integration,
+            network_rx_history: &self.network_rx_history,
+            network_tx_history: &self.network_tx_history,
+            cpu_frequency: self.metrics.cpu_frequency.as_ref(),
+            hardware_info: self.config.show_hardware_info.then_some(self.hardware_lines.as_slice()),
+            gpu_processes,
+            pressure: self.metrics.pressure,
+            kernel_stats: self.metrics.kernel_stats,
+            gpu_details: self.metrics.gpu_details.as_ref(),
+            cpu_temp_history: &self.cpu_temp_history,
+            gpu_temp_history: &self.gpu_temp_history,
+            hottest_core: self.metrics.hottest_core.as_ref(),
+            throttling: self.metrics.throttling,
+            identity: identity.as_deref(),
+            caffeine_active: self.caffeine_active,
+            power_profile: power_profile.as_ref().map(|profiles| profiles.active.as_str()),
             weather_temp,
             weather_desc,
             weather_location,
             weather_icon,
-            disk_info: &self.storage.disk_info,
-            battery_devices: &battery_devices,
+            weather_status_label: &weather_status_label,
+            weather_feels_like: weather_details.feels_like,
+            weather_temp_min: weather_details.temp_min,
+            weather_temp_max: weather_details.temp_max,
+            weather_humidity: weather_details.humidity,
+            weather_wind_speed: weather_details.wind_speed,
+            weather_wind_deg: weather_details.wind_deg,
+            weather_sun,
+            weather_moon_phase,
+            air_quality: self.air_quality.reading(),
+            disk_info: &disk_info,
+            drive_health: &drive_health,
+            storage_pools: &storage_pools,
+            battery_devices: &self.metrics.batteries,
             grouped_notifications,
             collapsed_groups: &self.collapsed_groups,
+            notification_expansion: &notification_expansion,
+            do_not_disturb: self.dnd.state(),
+            notification_scroll: self.notification_scroll,
             media_info: &media_info,
+            media_marquee,
+            lyrics_line,
+            custom_outputs: &custom_outputs,
+            plugin_frames: self.plugins.frames(),
+            audio: audio.as_ref(),
+            health: health.as_ref(),
+            containers: containers.as_ref(),
+            vms: &vms,
+            feed_rows: &feed_rows,
+            ticker_rows: &ticker_rows,
             player_count,
             current_player_index,
-            section_order: &self.config.section_order,
+            section_columns: &section_columns,
+            compact_slots: &compact_slots,
             current_time,
-            theme: &self.theme,
+            locale: self.locale,
+            world_clocks: &world_clocks,
+            clock_zone: self.clock_zone_index.checked_sub(1).and_then(|index| active_clocks(&self.config.world_clocks).nth(index)),
+            calendar_events: &calendar_events,
+            fonts: &self.fonts,
+            section_errors: &section_errors,
+            hovered_error: self.hovered_error,
+            snap_guides: self.snap_guides,
+            tooltip: hovered_metric.map(|target| (target, tooltip_lines.as_slice())),
+            loading_sections: &loading_sections,
+            update_available: update.as_ref().map(|release| release.version.as_str()),
+            opacity: self.animator.opacity(now) * self.schedule_effect.opacity_factor(),
+            debug_overlay: self.debug_overlay.then_some(debug_lines.as_slice()),
         };
         
         // Wrap rendering in panic catch to prevent crashes
+        let mut marquee_running = false;
         let render_start = Instant::now();
         let render_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            render_widget(canvas, params)
+            render_widget(&mut *canvas, &self.config, &self.theme, data)
         }));
-        log::info!("Cairo render took: {:?}", render_start.elapsed());
+        let render_duration = render_start.elapsed();
+        log::trace!("Cairo render took: {:?}", render_duration);
+        self.debug_stats.record_frame(now, render_duration, (width, height, stride as usize * height as usize));
         
         match render_result {
-            Ok((bounds, groups, clear_bounds, clear_all, media_bounds)) => {
-                let group_count = groups.len();
-                self.notification_bounds = bounds;
-                self.notification_group_bounds = groups;
-                self.notification_clear_bounds = clear_bounds;
-                self.clear_all_bounds = clear_all;
-                self.media_button_bounds = media_bounds;
+            Ok(Ok(bounds)) => {
+                let group_count = bounds.notification_groups.len();
+                self.notification_bounds = bounds.notifications;
+                self.notification_group_bounds = bounds.notification_groups;
+                self.notification_clear_bounds = bounds.notification_clear;
+                self.clear_all_bounds = bounds.clear_all;
+                self.media_button_bounds = bounds.media_buttons;
+                self.error_badge_bounds = bounds.error_badges;
+                self.update_badge_bounds = bounds.update_badge;
+                self.section_area_bounds = bounds.section_areas;
+                self.hover_target_bounds = bounds.hover_targets;
+                marquee_running = bounds.marquee_running;
                 log::trace!("Render successful, {} notification groups", group_count);
+                
+                if let Some(exporter) = &self.frame_exporter {
+                    exporter.submit(canvas, width, height);
+                }
+            }
+            Ok(Err(e)) => {
+                log::error!("Rendering failed, skipping frame: {}", e);
+                widget::diagnostics::record_error("render", e.to_string());
+                // The buffer may be half drawn; rebuild the pool on the next frame
+                self.pool = None;
+                self.clear_interaction_bounds();
+                return; // Skip this frame
             }
             Err(e) => {
                 log::error!("Panic occurred during rendering: {:?}", e);
-                // Clear potentially corrupted state
-                self.notification_group_bounds.clear();
-                self.notification_clear_bounds.clear();
-                self.clear_all_bounds = None;
-                self.media_button_bounds.clear();
+                widget::diagnostics::record_error("render", "panic during rendering");
+                self.clear_interaction_bounds();
                 return; // Skip this frame
             }
         }
@@ -978,26 +1961,697 @@ impl MonitorWidget {
             .attach(Some(buffer.wl_buffer()), 0, 0);
         layer_surface.wl_surface().damage_buffer(0, 0, width, height);
         
+        // Keep frames coming until every transition has settled, and while
+        // a title scrolls
+        if (self.animator.is_running(now) || marquee_running) && !self.frame_pending {
+            let surface = layer_surface.wl_surface();
+            surface.frame(qh, surface.clone());
+            self.frame_pending = true;
+        }
+        
         // Commit changes
         layer_surface.wl_surface().commit();
     }
 }
 
-// Empty impl block - keeping for potential future private helpers
+// Private helpers
 impl MonitorWidget {
-}
-
-// ============================================================================
-// smithay-client-toolkit Delegation Macros
-// ============================================================================
-// These macros generate the boilerplate code to route Wayland events
-// to our handler implementations above.
-
-delegate_compositor!(MonitorWidget);
+    /// Publish a diagnostics snapshot for the settings app.
+    ///
+    /// Called with the heartbeat (every 5 seconds). See [`widget::diagnostics`].
+    fn write_diagnostics(&self) {
+        let mut status = widget::diagnostics::WidgetStatus::capture();
+        status.gpu_vendor = self.metrics.gpu_vendor.to_string();
+        status.gpu_method = self.metrics.gpu_method.to_string();
+        status.gpu_card = self.metrics.gpu_card.clone();
+        status.gpu_tools = widget::diagnostics::gpu_tools();
+        status.sensors = self.metrics.sensors.clone();
+        status.hwmon_readings = widget::diagnostics::hwmon_readings();
+        (status.cpu_sensor, status.gpu_sensor) = (self.metrics.cpu_sensor.clone(), self.metrics.gpu_sensor.clone());
+        status.network_interfaces = self.metrics.network_interfaces.clone();
+        status.weather_last_result = self.weather.last_fetch_result();
+        status.weather_next_retry_secs = self.weather.next_retry_in().map(|d| d.as_secs());
+        status.cider_status = self.media.cider_status();
+        status.monitor_health = self.watchdog.health();
+        if self.config.check_for_updates {
+            status.latest_version = self.update_checker.latest_version();
+        }
+        status.save();
+    }
+    
+    /// Check every background collector's heartbeat and restart stalled
+    /// ones and ones whose thread ended.
+    ///
+    /// Only collectors whose section is enabled are restarted; disabled
+    /// ones are still reported on the diagnostics page.
+    fn check_monitor_health(&mut self) {
+        for monitor in WatchedMonitor::ALL {
+            let heartbeat = match monitor {
+                WatchedMonitor::Sampler => Some(self.sampler.heartbeat()),
+                WatchedMonitor::Gpu => self.metrics.gpu_heartbeat.as_ref(),
+                WatchedMonitor::Storage => self.metrics.storage_heartbeat.as_ref(),
+                WatchedMonitor::Battery => self.metrics.battery_heartbeat.as_ref(),
+                WatchedMonitor::Weather => Some(self.weather.heartbeat()),
+                WatchedMonitor::Media => Some(self.media.heartbeat()),
+                WatchedMonitor::Custom => Some(self.custom_commands.heartbeat()),
+                WatchedMonitor::Audio => Some(self.audio.heartbeat()),
+                WatchedMonitor::Notifications => Some(self.notifications.heartbeat()),
+            };
+            // The notification listener never beats; only its exit counts
+            let age = heartbeat.filter(|_| monitor != WatchedMonitor::Notifications).map(Heartbeat::age);
+            let exit = heartbeat.and_then(Heartbeat::exit);
+            let sched = heartbeat.and_then(Heartbeat::sched_stats);
+            if self.watchdog.check(monitor, age, exit, sched) && self.monitor_enabled(monitor) {
+                self.restart_monitor(monitor);
+            }
+        }
+    }
+    
+    /// Whether a watched collector's section is currently shown.
+    fn monitor_enabled(&self, monitor: WatchedMonitor) -> bool {
+        let config = &self.config;
+        match monitor {
+            WatchedMonitor::Sampler => true,
+            WatchedMonitor::Gpu => config.show_gpu,
+            WatchedMonitor::Storage => config.show_storage,
+            WatchedMonitor::Battery => config.show_battery && config.enable_solaar_integration,
+            WatchedMonitor::Weather => config.show_weather,
+            WatchedMonitor::Media => config.show_media,
+            WatchedMonitor::Custom => config.show_custom_commands,
+            WatchedMonitor::Audio => config.show_audio,
+            WatchedMonitor::Notifications => config.show_notifications,
+        }
+    }
+    
+    /// Replace a stalled or exited collector with a fresh instance.
+    ///
+    /// The stuck thread can't be killed; dropping its monitor tells it to
+    /// exit once its call returns. Until then it only holds its own copies
+    /// of the shared state, so it can't overwrite new data.
+    fn restart_monitor(&mut self, monitor: WatchedMonitor) {
+        log::warn!("Restarting {} monitor", monitor.name());
+        match monitor {
+            // The stuck sampler thread stops once its channels are dropped
+            WatchedMonitor::Sampler => self.sampler = Sampler::new(sampler_settings(&self.config)),
+            WatchedMonitor::Gpu | WatchedMonitor::Storage | WatchedMonitor::Battery => self.sampler.restart(monitor),
+            WatchedMonitor::Weather => {
+                self.weather = WeatherMonitor::new(
+                    self.config.weather_api_key.clone(),
+                    self.config.weather_location.clone(),
+                );
+            }
+            WatchedMonitor::Media => {
+                self.media = MediaMonitor::new(Some(self.config.cider_api_token.clone()));
+            }
+            WatchedMonitor::Custom => {
+                self.custom_commands = CustomCommandMonitor::new(active_commands(&self.config));
+            }
+            WatchedMonitor::Audio => self.audio = AudioMonitor::new(self.config.show_audio),
+            WatchedMonitor::Notifications => {
+                self.notifications = NotificationMonitor::new(5, history_settings(&self.config));
+            }
+        }
+        self.watchdog.record_restart(monitor);
+    }
+    
+    /// Snapshot the metrics collected this cycle.
+    ///
+    /// Only enabled sections are filled in; see [`widget::stats`].
+    fn stats_snapshot(&self) -> StatsSnapshot {
+        let config = &self.config;
+        // System metrics were filled in by the sampler thread
+        let mut snapshot = self.metrics.stats.clone();
+        
+        if config.show_weather {
+            if let Some(data) = self.weather.weather_data.lock().unwrap().as_ref() {
+                snapshot.set_weather(data);
+            }
+        }
+        if config.show_media {
+            if let Some((_, info)) = self.media.get_player_state().current_player() {
+                snapshot.set_media(info);
+            }
+        }
+        
+        snapshot
+    }
+    
+    /// Append a snapshot to the metric history, following the config.
+    fn record_metrics(&mut self, snapshot: &StatsSnapshot) {
+        let config = &self.config;
+        if !config.record_metrics {
+            self.recorder = None;
+            return;
+        }
+        
+        // (Re)create the recorder when enabled or its settings changed
+        let interval = config.record_interval_secs;
+        let retention = config.record_retention_days;
+        if !self.recorder.as_ref().is_some_and(|r| r.matches(interval, retention)) {
+            self.recorder = Some(MetricRecorder::new(interval, retention));
+        }
+        
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.record(snapshot) {
+                log::warn!("Failed to record metric history: {}", e);
+            }
+        }
+    }
+    
+    /// Apply a command received on the control pipe.
+    fn apply_control(&mut self, command: ControlCommand, qh: &QueueHandle<Self>) {
+        let hide = match command {
+            ControlCommand::Toggle => !self.hidden,
+            ControlCommand::Show => false,
+            ControlCommand::Hide => true,
+            ControlCommand::DebugOverlay => {
+                self.debug_overlay = !self.debug_overlay;
+                log::info!("Debug overlay {}", if self.debug_overlay { "shown" } else { "hidden" });
+                self.force_redraw = true;
+                return;
+            }
+            ControlCommand::ClearNotifications => {
+                self.notifications.clear();
+                self.collapsed_groups.clear();
+                self.force_redraw = true;
+                return;
+            }
+            ControlCommand::CopyStats => {
+                widget::clipboard::copy_text(self.stats_snapshot().to_plain());
+                return;
+            }
+        };
+        if hide {
+            self.hide();
+        } else {
+            self.show(qh);
+        }
+    }
+    
+    /// Hide the widget (control pipe).
+    ///
+    /// With animations enabled the widget fades out first; `draw()` destroys
+    /// the surface once the fade has finished. Monitors keep running so data
+    /// is current when shown again.
+    fn hide(&mut self) {
+        if self.hidden {
+            return;
+        }
+        log::info!("Hiding widget");
+        self.hidden = true;
+        if self.config.enable_animations && self.layer_surface.is_some() {
+            self.animator.fade_out(Instant::now());
+            self.force_redraw = true;
+        } else {
+            self.destroy_surface();
+        }
+    }
+    
+    /// Show the widget again (control pipe), unless auto-hidden.
+    fn show(&mut self, qh: &QueueHandle<Self>) {
+        if !self.hidden {
+            return;
+        }
+        log::info!("Showing widget");
+        self.hidden = false;
+        if !self.auto_hidden {
+            if self.layer_surface.is_some() {
+                // Still fading out: reverse the fade
+                self.animator.fade_in(Instant::now());
+                self.force_redraw = true;
+            } else {
+                // Recreated surfaces fade in from transparent
+                self.restore_surface(qh);
+            }
+        }
+    }
+    
+    /// Hide or restore the widget depending on fullscreen windows.
+    ///
+    /// Called whenever toplevel state, the widget's output, or the config
+    /// changes. Hiding destroys the surface, so nothing is rendered while a
+    /// game or video covers the output.
+    fn update_auto_hide(&mut self, qh: &QueueHandle<Self>) {
+        // Until the surface has entered an output, go by the configured one
+        let output = self.widget_output.clone().or_else(|| self.configured_output());
+        let covered = self.config.auto_hide_fullscreen && self.toplevels.covers(output.as_ref(), self.config.auto_hide_maximized);
+        if covered == self.auto_hidden {
+            return;
+        }
+        
+        self.auto_hidden = covered;
+        if covered {
+            log::info!("Fullscreen window on widget output, auto-hiding");
+            self.destroy_surface();
+        } else if !self.hidden {
+            log::info!("Output clear again, showing widget");
+            self.restore_surface(qh);
+        }
+    }
+    
+    /// Destroy the layer surface and forget all hit-test regions.
+    fn destroy_surface(&mut self) {
+        if self.layer_surface.is_none() {
+            return;
+        }
+        self.finish_drag();
+        self.hovered_error = None;
+        self.hovered_notification = None;
+        self.hover = HoverTracker::default();
+        self.frame_pending = false;
+        // The inhibitor belongs to the surface
+        if let Some(inhibitor) = self.idle_inhibitor.take() {
+            inhibitor.destroy();
+        }
+        // Dropping the last reference destroys the surface
+        self.layer_surface = None;
+        self.pool = None;
+        self.notification_bounds = None;
+        self.notification_group_bounds.clear();
+        self.notification_clear_bounds.clear();
+        self.clear_all_bounds = None;
+        self.media_button_bounds.clear();
+        self.error_badge_bounds.clear();
+        self.update_badge_bounds = None;
+        self.section_area_bounds.clear();
+        self.hover_target_bounds.clear();
+    }
+    
+    /// Switch caffeine on or off.
+    fn set_caffeine(&mut self, active: bool, qh: &QueueHandle<Self>) {
+        if active == self.caffeine_active {
+            return;
+        }
+        log::info!("Caffeine {}", if active { "on" } else { "off" });
+        self.caffeine_active = active;
+        self.update_idle_inhibit(qh);
+        self.force_redraw = true;
+    }
+    
+    /// Create or destroy the idle inhibitor to match the caffeine state.
+    ///
+    /// The inhibitor lives on the widget surface, so it is recreated along
+    /// with the surface and only holds while the compositor shows it.
+    fn update_idle_inhibit(&mut self, qh: &QueueHandle<Self>) {
+        let wanted = self.caffeine_active && self.config.show_caffeine;
+        match (&self.idle_inhibit_manager, &self.layer_surface) {
+            (Some(manager), Some(layer_surface)) if wanted => {
+                if self.idle_inhibitor.is_none() {
+                    self.idle_inhibitor = Some(manager.create_inhibitor(layer_surface.wl_surface(), qh, ()));
+                }
+            }
+            _ => {
+                if let Some(inhibitor) = self.idle_inhibitor.take() {
+                    inhibitor.destroy();
+                }
+            }
+        }
+    }
+    
+    /// Recreate the layer surface if it was destroyed.
+    ///
+    /// The first frame is drawn from the compositor's configure event.
+    fn restore_surface(&mut self, qh: &QueueHandle<Self>) {
+        if self.layer_surface.is_some() {
+            return;
+        }
+        self.last_drawn_second = None;
+        self.create_layer_surface(qh);
+    }
+    
+    /// Collect the current error of every enabled section's monitor.
+    ///
+    /// The renderer draws a warning badge on these sections, with the
+    /// message shown in a tooltip on hover.
+    fn section_errors(&self) -> Vec<(WidgetSection, String)> {
+        let config = &self.config;
+        let mut errors = Vec::new();
+        
+        // Stalled collectors first: their badge takes precedence
+        for monitor in WatchedMonitor::ALL {
+            if self.monitor_enabled(monitor) {
+                if let Some(message) = self.watchdog.stale_message(monitor) {
+                    errors.push((monitor.section(), message));
+                }
+            }
+        }
+        
+        if config.show_gpu {
+            if let Some(e) = self.metrics.gpu_error.clone() {
+                errors.push((WidgetSection::Utilization, e));
+            }
+        }
+        if let Some(e) = self.metrics.temperature_error.clone() {
+            let section = if config.temps_on_usage_bars { WidgetSection::Utilization } else { WidgetSection::Temperatures };
+            errors.push((section, e));
+        }
+        if config.show_battery && config.enable_solaar_integration {
+            if let Some(e) = self.metrics.battery_error.clone() {
+                errors.push((WidgetSection::Battery, e));
+            }
+        }
+        if config.show_battery && config.show_power_profile {
+            if let Some(e) = self.power_profile.error() {
+                errors.push((WidgetSection::Battery, format!("Power profile: {}", e)));
+            }
+        }
+        if config.show_weather {
+            if let Some(e) = self.weather.error() {
+                errors.push((WidgetSection::Weather, e));
+            }
+            if let Some(e) = self.air_quality.error() {
+                errors.push((WidgetSection::Weather, format!("Air quality: {}", e)));
+            }
+        }
+        if config.show_media && config.show_lyrics {
+            if let Some(e) = self.lyrics.error() {
+                errors.push((WidgetSection::Media, format!("Lyrics: {}", e)));
+            }
+        }
+        if config.show_notifications {
+            if let Some(e) = self.notifications.error() {
+                errors.push((WidgetSection::Notifications, e));
+            }
+        }
+        if config.show_calendar {
+            if let Some(e) = self.calendar.error() {
+                errors.push((WidgetSection::Clock, format!("Calendar: {}", e)));
+            }
+        }
+        if config.show_custom_commands {
+            if let Some(e) = self.custom_commands.error() {
+                errors.push((WidgetSection::Custom, e));
+            }
+        }
+        if config.show_audio {
+            if let Some(e) = self.audio.error() {
+                errors.push((WidgetSection::Audio, e));
+            }
+        }
+        if config.show_system_health {
+            if let Some(e) = self.health.error() {
+                errors.push((WidgetSection::SystemHealth, e));
+            }
+        }
+        if config.show_containers {
+            if let Some(e) = self.containers.error() {
+                errors.push((WidgetSection::Containers, e));
+            }
+        }
+        if config.show_vms {
+            if let Some(e) = self.vms.error() {
+                errors.push((WidgetSection::VirtualMachines, e));
+            }
+        }
+        if config.show_storage_pools {
+            if let Some(e) = self.pools.error() {
+                errors.push((WidgetSection::StoragePools, e));
+            }
+        }
+        if config.show_feeds {
+            if let Some(e) = self.feeds.error() {
+                errors.push((WidgetSection::Feeds, e));
+            }
+        }
+        if config.show_ticker {
+            if let Some(e) = self.ticker.error() {
+                errors.push((WidgetSection::Ticker, e));
+            }
+        }
+        if config.show_plugins {
+            if let Some(e) = self.plugins.error() {
+                errors.push((WidgetSection::Plugins, e));
+            }
+        }
+        if config.show_caffeine && self.idle_inhibit_manager.is_none() {
+            errors.push((WidgetSection::Caffeine, String::from("The compositor does not support idle inhibition")));
+        }
+        
+        errors
+    }
+    
+    /// Run the scroll action bound to the section under `position`.
+    ///
+    /// Positive `steps` scroll down: quieter, next track or zone, older
+    /// notifications.
+    fn handle_scroll(&mut self, position: (f64, f64), steps: i32) {
+        let Some(section) = self.section_at(position) else {
+            return;
+        };
+        let action = match section {
+            WidgetSection::Clock => self.config.clock_scroll,
+            WidgetSection::Media => self.config.media_scroll,
+            WidgetSection::Audio => self.config.audio_scroll,
+            WidgetSection::Notifications => self.config.notifications_scroll,
+            _ => return,
+        };
+        log::debug!("Scrolled {} step(s) over {:?}: {:?}", steps, section, action);
+        
+        match action {
+            ScrollAction::Nothing => {}
+            ScrollAction::MediaVolume => {
+                self.media.change_volume(-steps as f64 * widget::media::VOLUME_STEP);
+            }
+            ScrollAction::SystemVolume => {
+                if self.audio.change_volume(-steps) {
+                    self.force_redraw = true;
+                }
+            }
+            ScrollAction::MediaTrack => {
+                if steps > 0 {
+                    self.media.next();
+                } else {
+                    self.media.previous();
+                }
+                self.force_redraw = true;
+            }
+            ScrollAction::TimeZones => {
+                // Local time plus each world clock, wrapping around
+                let zones = active_clocks(&self.config.world_clocks).count() as i64 + 1;
+                if zones > 1 {
+                    self.clock_zone_index = (self.clock_zone_index as i64 + steps as i64).rem_euclid(zones) as usize;
+                    self.force_redraw = true;
+                }
+            }
+            ScrollAction::Notifications => {
+                // Up to where the longest group shows its oldest 5
+                let max = self
+                    .grouped_notifications
+                    .iter()
+                    .map(|(_, group)| group.len().saturating_sub(5))
+                    .max()
+                    .unwrap_or(0);
+                let scroll = (self.notification_scroll as i64 + steps as i64).clamp(0, max as i64) as usize;
+                if scroll != self.notification_scroll {
+                    self.notification_scroll = scroll;
+                    self.force_redraw = true;
+                }
+            }
+        }
+    }
+    
+    /// Metric drawn under `position`.
+    fn hover_target_at(&self, position: (f64, f64)) -> Option<HoverTarget> {
+        let (x, y) = position;
+        self.hover_target_bounds
+            .iter()
+            .find(|(_, x1, y1, x2, y2)| x >= *x1 && x <= *x2 && y >= *y1 && y <= *y2)
+            .map(|(target, ..)| *target)
+    }
+    
+    /// Notification row under `position` ("app_name:timestamp").
+    fn notification_row_at(&self, position: (f64, f64)) -> Option<String> {
+        let (x, y) = position;
+        self.notification_clear_bounds
+            .iter()
+            .filter(|(_, x1, y1, x2, y2)| x >= *x1 && x <= *x2 && y >= *y1 && y <= *y2)
+            .find_map(|(key, ..)| key.strip_prefix("row:"))
+            .map(str::to_string)
+    }
+    
+    /// Section drawn under `position`.
+    fn section_at(&self, position: (f64, f64)) -> Option<WidgetSection> {
+        let (x, y) = position;
+        self.section_area_bounds
+            .iter()
+            .find(|(_, x1, y1, x2, y2)| x >= *x1 && x <= *x2 && y >= *y1 && y <= *y2)
+            .map(|(section, ..)| *section)
+    }
+    
+    /// Text of the detail tooltip of a hovered metric, from the latest sample.
+    fn tooltip_lines(&self, target: HoverTarget) -> Vec<String> {
+        match target {
+            HoverTarget::Cpu => tooltip::core_lines(&self.metrics.core_usage),
+            HoverTarget::CpuFrequency => {
+                tooltip::cpu_frequency_lines(self.metrics.cpu_frequency.as_ref(), self.config.cpu_governor_click)
+            }
+            HoverTarget::Memory => tooltip::memory_lines(self.metrics.memory_used, self.metrics.memory_total),
+            HoverTarget::Gpu => tooltip::gpu_lines(self.metrics.gpu_vendor, self.metrics.gpu_method),
+            HoverTarget::CpuTemp => tooltip::sensor_lines(self.metrics.cpu_sensor.as_deref(), self.metrics.cpu_temp),
+            HoverTarget::GpuTemp => tooltip::sensor_lines(self.metrics.gpu_sensor.as_deref(), self.metrics.gpu_temp),
+            HoverTarget::Network => tooltip::network_lines(&self.metrics.interface_rates, self.config.network_rate_bits),
+            HoverTarget::SystemHealth => {
+                tooltip::failed_unit_lines(&self.health.report().map(|report| report.failed_units).unwrap_or_default())
+            }
+            HoverTarget::Containers => {
+                tooltip::container_lines(&self.containers.report().map(|report| report.running).unwrap_or_default())
+            }
+            HoverTarget::DoNotDisturb => tooltip::dnd_lines(self.dnd.state().unwrap_or(false)),
+            HoverTarget::PowerProfile => tooltip::power_profile_lines(self.power_profile.state().as_ref()),
+        }
+    }
+    
+    /// Enabled sections whose monitor hasn't produced its first sample yet.
+    ///
+    /// These draw a "Loading…" placeholder instead of zeros. Sections
+    /// with an error show the error badge instead.
+    fn loading_sections(&self) -> Vec<WidgetSection> {
+        let config = &self.config;
+        let mut loading = Vec::new();
+        
+        if (config.show_cpu || config.show_memory || config.show_gpu) && !self.metrics.utilization_ready {
+            loading.push(WidgetSection::Utilization);
+        }
+        // Merged temperatures just leave their badges out until the first sample
+        if (config.show_cpu_temp || config.show_gpu_temp) && !config.temps_on_usage_bars && !self.metrics.temperatures_ready {
+            loading.push(WidgetSection::Temperatures);
+        }
+        if config.show_network && !self.metrics.network_ready {
+            loading.push(WidgetSection::Network);
+        }
+        if config.show_weather && !self.weather.has_sample() && self.weather.error().is_none() {
+            loading.push(WidgetSection::Weather);
+        }
+        if config.show_audio && !self.audio.has_sample() && self.audio.error().is_none() {
+            loading.push(WidgetSection::Audio);
+        }
+        if config.show_system_health && !self.health.has_sample() {
+            loading.push(WidgetSection::SystemHealth);
+        }
+        if config.show_containers && !self.containers.has_sample() {
+            loading.push(WidgetSection::Containers);
+        }
+        if config.show_vms && !self.vms.has_sample() {
+            loading.push(WidgetSection::VirtualMachines);
+        }
+        if config.show_storage_pools && !self.pools.has_sample() {
+            loading.push(WidgetSection::StoragePools);
+        }
+        if config.show_feeds && !self.feeds.has_sample() {
+            loading.push(WidgetSection::Feeds);
+        }
+        
+        loading
+    }
+}
+
+// ============================================================================
+// Foreign Toplevel Dispatch (auto-hide)
+// ============================================================================
+// smithay-client-toolkit has no wrapper for wlr-foreign-toplevel-management,
+// so these are implemented directly. State lives in `ToplevelTracker`.
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, ()> for MonitorWidget {
+    fn event(
+        state: &mut Self,
+        _manager: &ZwlrForeignToplevelManagerV1,
+        event: zwlr_foreign_toplevel_manager_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_foreign_toplevel_manager_v1::Event::Toplevel { toplevel } => {
+                state.toplevels.add(toplevel.id());
+            }
+            zwlr_foreign_toplevel_manager_v1::Event::Finished => {
+                log::info!("Foreign toplevel manager finished");
+                state.toplevel_manager = None;
+            }
+            _ => {}
+        }
+    }
+
+    event_created_child!(MonitorWidget, ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (ZwlrForeignToplevelHandleV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, ()> for MonitorWidget {
+    fn event(
+        state: &mut Self,
+        handle: &ZwlrForeignToplevelHandleV1,
+        event: zwlr_foreign_toplevel_handle_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        let id = handle.id();
+        match event {
+            zwlr_foreign_toplevel_handle_v1::Event::State { state: raw } => {
+                state.toplevels.set_state(&id, &raw);
+            }
+            zwlr_foreign_toplevel_handle_v1::Event::OutputEnter { output } => {
+                state.toplevels.output_enter(&id, output);
+            }
+            zwlr_foreign_toplevel_handle_v1::Event::OutputLeave { output } => {
+                state.toplevels.output_leave(&id, &output);
+            }
+            // State changes are applied atomically on `done`
+            zwlr_foreign_toplevel_handle_v1::Event::Done => {
+                state.update_auto_hide(qh);
+            }
+            zwlr_foreign_toplevel_handle_v1::Event::Closed => {
+                state.toplevels.remove(&id);
+                handle.destroy();
+                state.update_auto_hide(qh);
+            }
+            _ => {}
+        }
+    }
+}
+
+// ============================================================================
+// Idle Inhibit Dispatch (caffeine)
+// ============================================================================
+// Neither object sends events; the inhibitor holds while it exists.
+
+impl Dispatch<ZwpIdleInhibitManagerV1, ()> for MonitorWidget {
+    fn event(
+        _state: &mut Self,
+        _manager: &ZwpIdleInhibitManagerV1,
+        _event: zwp_idle_inhibit_manager_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpIdleInhibitorV1, ()> for MonitorWidget {
+    fn event(
+        _state: &mut Self,
+        _inhibitor: &ZwpIdleInhibitorV1,
+        _event: zwp_idle_inhibitor_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+// ============================================================================
+// smithay-client-toolkit Delegation Macros
+// ============================================================================
+// These macros generate the boilerplate code to route Wayland events
+// to our handler implementations above.
+
+delegate_compositor!(MonitorWidget);
 delegate_output!(MonitorWidget);
 delegate_shm!(MonitorWidget);
 delegate_seat!(MonitorWidget);
 delegate_pointer!(MonitorWidget);
+delegate_keyboard!(MonitorWidget);
 delegate_layer!(MonitorWidget);
 
 delegate_registry!(MonitorWidget);
@@ -1027,42 +2681,131 @@ impl ProvidesRegistryState for MonitorWidget {
 /// Non-recoverable errors (e.g., layer-shell not available) cause immediate exit.
 /// Recoverable errors (broken pipe) trigger reconnection.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Ignore SIGPIPE so a closed socket becomes a normal EPIPE result, not a signal.
-    // This prevents the process from being killed when the compositor closes the connection.
-    unsafe { 
-        libc::signal(libc::SIGPIPE, libc::SIG_IGN); 
+    // === CLI Modes ===
+    // `--stats [json|plain]` prints one sample and exits.
+    // `--toggle`, `--show`, `--hide`, `--debug-overlay`, `--copy-stats` send a command to the running widget and exit.
+    // `--replace` starts normally, shutting down a widget that is already running.
+    // `--verbose` (any position) logs at Debug level or above.
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let verbose = logging::take_verbose_flag(&mut args);
+    let replace = args.first().is_some_and(|flag| flag == "--replace");
+    if let Some(flag) = args.first().filter(|_| !replace) {
+        if flag == "--stats" {
+            let snapshot = StatsSnapshot::collect_once();
+            match args.get(1).map(String::as_str).unwrap_or("plain") {
+                "json" => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+                "plain" => println!("{}", snapshot.to_plain()),
+                other => {
+                    eprintln!("cosmic-monitor-widget: unknown stats format '{}' (expected json or plain)", other);
+                    std::process::exit(2);
+                }
+            }
+            return Ok(());
+        }
+        
+        #[cfg(feature = "prometheus")]
+        if flag == "--export-prometheus" {
+            let Some(addr) = args.get(1).and_then(|a| widget::prometheus::parse_listen_addr(a)) else {
+                eprintln!("Usage: cosmic-monitor-widget --export-prometheus <port | host:port>");
+                std::process::exit(2);
+            };
+            logging::init("cosmic-monitor-widget", logging::level_filter(LogLevel::Warn, verbose), false);
+            widget::prometheus::run(addr)?;
+            return Ok(());
+        }
+        
+        match ControlCommand::from_flag(flag) {
+            Some(command) => {
+                if let Err(e) = widget::control::send_command(command) {
+                    eprintln!("cosmic-monitor-widget: no running widget to {} ({})", command.as_str(), e);
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            None => {
+                eprintln!("Usage: cosmic-monitor-widget [--verbose] [--replace | --toggle | --show | --hide | --debug-overlay | --clear-notifications | --copy-stats | --stats [json|plain]]");
+                std::process::exit(2);
+            }
+        }
     }
     
-    // Load configuration to check if logging should be enabled
+    // Load configuration first; it decides how much to log
+    let migrated = config::migrate_stored();
     let config_handler = cosmic_config::Config::new(
         "com.github.zoliviragh.CosmicMonitor",
         Config::VERSION,
     )?;
     
-    let mut base_config = Config::get_entry(&config_handler).unwrap_or_default();
+    // Fields that can't be read fall back to their defaults
+    let (mut base_config, load_errors) = match Config::get_entry(&config_handler) {
+        Ok(config) => (config, Vec::new()),
+        Err((errors, config)) => (config, errors),
+    };
+    base_config.migrate_section_order();
+    let config_issues = base_config.validate();
     
-    // Initialize logger only if enabled in config
-    if base_config.enable_logging {
-        use std::fs::OpenOptions;
-        
-        let log_file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("/tmp/cosmic-monitor.log")
-            .expect("Failed to open log file");
-        
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
-            .target(env_logger::Target::Pipe(Box::new(log_file)))
-            .init();
-        
-        log::info!("Starting COSMIC Monitor Widget (logging enabled)");
-        log::info!("Widget starting with position: X={}, Y={}", base_config.widget_x, base_config.widget_y);
-        log::info!("Weather enabled: {}, API key set: {}", base_config.show_weather, !base_config.weather_api_key.is_empty());
-        log::info!("Notifications enabled: {}, section_order: {:?}", base_config.show_notifications, base_config.section_order);
+    // Log level and file output are read once; changes apply after a restart
+    logging::init(
+        "cosmic-monitor-widget",
+        logging::level_filter(base_config.log_level, verbose),
+        base_config.enable_logging,
+    );
+    log::info!("Starting COSMIC Monitor Widget");
+    config::log_migration(&migrated);
+    for error in &load_errors {
+        log::warn!("Stored setting could not be read, using its default: {}", error);
     }
+    for issue in &config_issues {
+        log::warn!("Invalid stored setting: {}", issue);
+    }
+    log::info!(
+        "Widget starting with position: X={}, Y={} ({:?}, output '{}')",
+        base_config.widget_x, base_config.widget_y, base_config.widget_anchor, base_config.widget_output
+    );
+    log::info!("Weather enabled: {}, API key set: {}", base_config.show_weather, !base_config.weather_api_key.is_empty());
+    log::info!("Notifications enabled: {}, section_order: {:?}", base_config.show_notifications, base_config.section_order);
+    
+    // === Single Instance ===
+    // A second widget would draw a second, overlapping surface
+    let instance = match InstanceLock::acquire(replace) {
+        Ok(lock) => lock,
+        Err(e) => {
+            eprintln!("cosmic-monitor-widget: {}", e);
+            std::process::exit(1);
+        }
+    };
+    
+    // Section headers and labels in the user's language (English fallback)
+    let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
+    i18n::init(&requested_languages);
+    
+    // Ignore SIGPIPE so a closed socket becomes a normal EPIPE result, not a signal.
+    // This prevents the process from being killed when the compositor closes the connection.
+    unsafe { 
+        libc::signal(libc::SIGPIPE, libc::SIG_IGN); 
+    }
+    
+    // Collector threads pick this up when they start (before any monitor exists)
+    widget::priority::configure(
+        base_config.low_priority_collectors,
+        base_config.idle_priority_collectors,
+        base_config.pin_collectors_to_efficiency_cores,
+    );
     
     // Load custom Weather Icons font for weather display
     load_weather_font();
+    
+    // Listen for show/hide/toggle commands (outlives Wayland reconnects)
+    let control = match ControlListener::start() {
+        Ok(listener) => Some(listener),
+        Err(e) => {
+            log::warn!("Control pipe unavailable, --toggle will not work: {}", e);
+            None
+        }
+    };
+    let mut hidden = false;
+    let mut caffeine = base_config.show_caffeine && base_config.caffeine_on_start;
+    let mut debug_overlay = base_config.show_debug_overlay;
 
     // === Reconnection Loop ===
     // Uses exponential backoff: 1s, 2s, 5s, 10s, 20s, 30s, then cycles
@@ -1080,8 +2823,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Create widget for this connection
         let mut widget = MonitorWidget::new(&globals, &qh, base_config.clone(), config_handler.clone());
+        // Keep caffeine and the debug overlay as the user left them before the reconnect
+        widget.caffeine_active = caffeine;
+        widget.debug_overlay = debug_overlay;
         widget.create_layer_surface(&qh);
         
+        // Stay hidden across reconnects if the user hid the widget
+        if hidden {
+            widget.hide();
+        }
+        
         // Perform initial roundtrip to receive configure event from compositor
         log::info!("Waiting for compositor configure event...");
         if let Err(e) = event_queue.roundtrip(&mut widget) {
@@ -1123,26 +2874,48 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let display_time = current_time - chrono::Duration::seconds(1);
             let current_second = display_time.format("%S").to_string();
             
+            // === Interval-Aligned Sampling ===
+            // Monitors are sampled on wall-clock ticks, independent of rendering
+            let sampled = widget.poll_sampling();
+            
+            // === Hover Tooltips ===
+            // Draw a metric's tooltip once the pointer rested on it
+            if widget.hover.poll(now) {
+                widget.force_redraw = true;
+            }
+            
+            // === Schedules ===
+            // Dim the widget or hide sections during the configured time ranges
+            let schedule_effect = ScheduleEffect::at(&widget.config.schedules, current_time.time());
+            if schedule_effect != widget.schedule_effect {
+                log::info!(
+                    "Schedule changed: {}% opacity, hidden sections {:?}",
+                    schedule_effect.opacity,
+                    schedule_effect.hidden_sections
+                );
+                widget.schedule_effect = schedule_effect;
+                widget.force_redraw = true;
+            }
+            
             // === Immediate UI Redraw ===
-            // Fast path for notification/media interactions (skip system stats update)
+            // Fast path for notification/media interactions
             if widget.force_redraw {
-                widget.draw(&qh, display_time, false);
+                widget.draw(&qh, display_time);
                 widget.force_redraw = false;
                 // Immediately flush to ensure compositor receives the update
                 let _ = conn.flush();
             }
             
             // === Second-Based Redraw ===
-            // Full redraw with system stats when clock second changes
+            // Redraw when the clock second changes or a new sample arrived
             let should_redraw = if let Some(ref last_sec) = widget.last_drawn_second {
                 &current_second != last_sec
             } else {
                 true // First draw
             };
             
-            // Periodic full update with system stats
-            if should_redraw {
-                widget.draw(&qh, display_time, true);
+            if should_redraw || sampled {
+                widget.draw(&qh, display_time);
                 widget.last_drawn_second = Some(current_second);
             }
             
@@ -1150,7 +2923,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Check for external config changes every 500ms (from settings app)
             if now.duration_since(widget.last_config_check).as_millis() > 500 {
                 widget.last_config_check = now;
-                if let Ok(new_config) = Config::get_entry(&widget.config_handler) {
+                if let Ok(mut new_config) = Config::get_entry(&widget.config_handler) {
+                    new_config.migrate_section_order();
+                    new_config.validate();
                     // Only update if config actually changed
                     if *widget.config != new_config {
                         log::info!("Configuration changed, updating widget");
@@ -1167,10 +2942,127 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             log::info!("Weather location changed to: {}", new_config.weather_location);
                             widget.weather.set_location(new_config.weather_location.clone());
                         }
+                        if active_commands(&widget.config) != active_commands(&new_config) {
+                            log::info!("Custom commands changed");
+                            widget.custom_commands.set_commands(active_commands(&new_config));
+                        }
+                        if sampler_settings(&widget.config) != sampler_settings(&new_config) {
+                            widget.sampler.set_settings(sampler_settings(&new_config));
+                        }
+                        if widget.config.show_audio != new_config.show_audio {
+                            widget.audio.set_enabled(new_config.show_audio);
+                        }
+                        let power_profile_shown = |config: &Config| config.show_battery && config.show_power_profile;
+                        if power_profile_shown(&widget.config) != power_profile_shown(&new_config) {
+                            widget.power_profile.set_enabled(power_profile_shown(&new_config));
+                        }
+                        if health_settings(&widget.config) != health_settings(&new_config) {
+                            log::info!("System health settings changed");
+                            widget.health.set_settings(health_settings(&new_config));
+                        }
+                        if container_settings(&widget.config) != container_settings(&new_config) {
+                            log::info!("Container settings changed");
+                            widget.containers.set_settings(container_settings(&new_config));
+                        }
+                        if widget.config.fonts != new_config.fonts {
+                            log::info!("Fonts changed");
+                            widget.fonts = Fonts::resolve(&new_config.fonts);
+                        }
+                        if vm_settings(&widget.config) != vm_settings(&new_config) {
+                            log::info!("Virtual machine settings changed");
+                            widget.vms.set_settings(vm_settings(&new_config));
+                        }
+                        if smart_settings(&widget.config) != smart_settings(&new_config) {
+                            log::info!("Drive health settings changed");
+                            widget.smart.set_settings(smart_settings(&new_config));
+                        }
+                        if pool_settings(&widget.config) != pool_settings(&new_config) {
+                            log::info!("Storage pool settings changed");
+                            widget.pools.set_settings(pool_settings(&new_config));
+                        }
+                        if feed_settings(&widget.config) != feed_settings(&new_config) {
+                            log::info!("Feed settings changed");
+                            widget.feeds.set_settings(feed_settings(&new_config));
+                        }
+                        if ticker_settings(&widget.config) != ticker_settings(&new_config) {
+                            log::info!("Ticker settings changed");
+                            widget.ticker.set_settings(ticker_settings(&new_config));
+                        }
+                        if history_settings(&widget.config) != history_settings(&new_config) {
+                            log::info!("Notification history settings changed");
+                            widget.notifications.set_history(history_settings(&new_config));
+                        }
+                        if air_quality_settings(&widget.config) != air_quality_settings(&new_config) {
+                            log::info!("Air quality settings changed");
+                            widget.air_quality.set_settings(air_quality_settings(&new_config));
+                        }
+                        if active_settings(&widget.config) != active_settings(&new_config) {
+                            log::info!("Calendar settings changed");
+                            widget.calendar.set_settings(active_settings(&new_config));
+                        }
+                        if widget.config.network_graph_seconds != new_config.network_graph_seconds {
+                            widget.network_rx_history.set_window(new_config.network_graph_seconds);
+                            widget.network_tx_history.set_window(new_config.network_graph_seconds);
+                        }
+                        if widget.config.temp_graph_minutes != new_config.temp_graph_minutes {
+                            let window_secs = new_config.temp_graph_minutes.saturating_mul(60);
+                            widget.cpu_temp_history.set_window(window_secs);
+                            widget.gpu_temp_history.set_window(window_secs);
+                        }
+                        widget.update_checker.set_enabled(new_config.check_for_updates);
+                        
+                        let input_changed = widget.config.click_through != new_config.click_through
+                            || widget.config.widget_movable != new_config.widget_movable
+                            || widget.config.keyboard_positioning != new_config.keyboard_positioning;
+                        let output_changed = widget.config.widget_output != new_config.widget_output;
+                        let position_changed = widget.config.widget_anchor != new_config.widget_anchor
+                            || widget.config.widget_x != new_config.widget_x
+                            || widget.config.widget_y != new_config.widget_y;
+                        let auto_hide_changed = widget.config.auto_hide_fullscreen != new_config.auto_hide_fullscreen
+                            || widget.config.auto_hide_maximized != new_config.auto_hide_maximized;
+                        
+                        if widget.config.enable_animations != new_config.enable_animations {
+                            widget.animator.set_enabled(new_config.enable_animations);
+                        }
+                        let caffeine_changed = widget.config.show_caffeine != new_config.show_caffeine;
+                        if widget.config.show_debug_overlay != new_config.show_debug_overlay {
+                            widget.debug_overlay = new_config.show_debug_overlay;
+                        }
                         
                         widget.config = Arc::new(new_config);
-                        // Force a redraw with full stats update
-                        widget.draw(&qh, chrono::Local::now(), true);
+                        
+                        // Showing the section again starts from the configured state
+                        if caffeine_changed {
+                            widget.caffeine_active = widget.config.show_caffeine && widget.config.caffeine_on_start;
+                            widget.update_idle_inhibit(&qh);
+                        }
+                        
+                        if auto_hide_changed {
+                            widget.update_auto_hide(&qh);
+                        }
+                        
+                        if input_changed {
+                            if let Some(layer_surface) = widget.layer_surface.clone() {
+                                log::info!("Updating input region (click-through: {})", widget.config.click_through);
+                                widget.update_input_region(&layer_surface);
+                                layer_surface.commit();
+                            }
+                        }
+                        
+                        // A new surface already gets the new position
+                        if output_changed {
+                            widget.move_to_configured_output(&qh);
+                        } else if position_changed {
+                            if let Some(layer_surface) = widget.layer_surface.clone() {
+                                widget.apply_position(&layer_surface);
+                                layer_surface.commit();
+                            }
+                        }
+                        
+                        // Sample right away (sections may have been enabled), then redraw;
+                        // the sample arrives on one of the next iterations
+                        widget.sampler.sample_now();
+                        widget.draw(&qh, chrono::Local::now());
                     }
                 }
             }
@@ -1188,7 +3080,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 {
                     log::info!("Theme changed, reloading");
                     widget.theme = new_theme;
-                    widget.draw(&qh, chrono::Local::now(), true);
+                    widget.draw(&qh, chrono::Local::now());
                 }
             }
 
@@ -1196,6 +3088,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Log every 5 seconds to confirm widget is still running
             if now.duration_since(last_heartbeat) >= Duration::from_secs(5) {
                 log::info!("Heartbeat: widget still running");
+                if let Some(wait) = widget.weather.next_retry_in() {
+                    log::info!("Weather fetch backing off, next retry in {}s", wait.as_secs());
+                }
+                widget.check_monitor_health();
+                widget.write_diagnostics();
                 last_heartbeat = now;
             }
             
@@ -1216,6 +3113,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             log::trace!("Flush complete");
             
+            // === Visibility Control ===
+            // Surface changes take effect on the next roundtrip (configure → draw)
+            if let Some(control) = &control {
+                while let Some(command) = control.try_recv() {
+                    widget.apply_control(command, &qh);
+                }
+                hidden = widget.hidden;
+            }
+            caffeine = widget.caffeine_active;
+            debug_overlay = widget.debug_overlay;
+            
             // === Frame Pacing ===
             // Small sleep to avoid busy-waiting while staying responsive (~60 FPS)
             thread::sleep(Duration::from_millis(16));
@@ -1225,6 +3133,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 log::info!("Exit requested, shutting down");
                 return Ok(());
             }
+            if instance.replace_requested() {
+                log::info!("Replaced by a new widget, shutting down");
+                return Ok(());
+            }
         } // end 'session
 
         // === Reconnection Backoff ===